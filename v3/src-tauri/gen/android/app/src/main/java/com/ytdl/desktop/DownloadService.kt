@@ -0,0 +1,227 @@
+package com.ytdl.desktop
+
+import android.app.NotificationChannel
+import android.app.NotificationManager
+import android.app.PendingIntent
+import android.app.Service
+import android.content.Context
+import android.content.Intent
+import android.content.pm.ServiceInfo
+import android.net.ConnectivityManager
+import android.os.BatteryManager
+import android.os.Build
+import android.os.IBinder
+import android.util.Log
+import androidx.core.app.NotificationCompat
+import androidx.core.app.NotificationManagerCompat
+import androidx.core.app.ServiceCompat
+import androidx.core.content.ContextCompat
+
+/**
+ * DownloadService: foreground service kept alive while downloads run.
+ *
+ * yt-dlp itself runs in Termux, but the poller that picks up its result
+ * lives in our process. Without a foreground service Android freezes or
+ * kills the app once it is backgrounded, and finished downloads are never
+ * recorded. Rust reports each download's start and end through
+ * [NativeBridge.downloadServiceStarted] / [NativeBridge.downloadServiceFinished];
+ * the service stops itself when the last one ends.
+ */
+class DownloadService : Service() {
+
+  override fun onBind(intent: Intent?): IBinder? = null
+
+  override fun onStartCommand(intent: Intent?, flags: Int, startId: Int): Int {
+    if (intent?.action == ACTION_CANCEL) {
+      // Rust cancels, the poller ends and downloadServiceFinished updates us
+      val id = intent.getStringExtra(EXTRA_DOWNLOAD_ID).orEmpty()
+      val ids = if (id.isEmpty()) synchronized(active) { active.keys.toList() } else listOf(id)
+      ids.forEach { NativeBridge.onNotificationAction("cancel", it) }
+    }
+    val entries = synchronized(active) { active.toList() }
+    if (entries.isEmpty()) {
+      ServiceCompat.stopForeground(this, ServiceCompat.STOP_FOREGROUND_REMOVE)
+      stopSelf()
+      return START_NOT_STICKY
+    }
+    try {
+      ServiceCompat.startForeground(
+        this,
+        ONGOING_ID,
+        buildOngoing(this, entries),
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.Q) {
+          ServiceInfo.FOREGROUND_SERVICE_TYPE_DATA_SYNC
+        } else {
+          0
+        }
+      )
+    } catch (e: Throwable) {
+      Log.e(TAG, "startForeground failed", e)
+      stopSelf()
+    }
+    return START_NOT_STICKY
+  }
+
+  /** Android 15 caps dataSync services at 6 hours a day. */
+  override fun onTimeout(startId: Int, fgsType: Int) {
+    Log.w(TAG, "Foreground time limit reached, stopping service")
+    ServiceCompat.stopForeground(this, ServiceCompat.STOP_FOREGROUND_REMOVE)
+    stopSelf()
+  }
+
+  companion object {
+    private const val TAG = "YTDL-DownloadService"
+    private const val CHANNEL_PROGRESS = "downloads"
+    private const val CHANNEL_RESULT = "download_results"
+    private const val ONGOING_ID = 1001
+    private const val ACTION_CANCEL = "com.ytdl.desktop.action.CANCEL_DOWNLOAD"
+    private const val EXTRA_DOWNLOAD_ID = "downloadId"
+
+    /** Download id → title, in start order. */
+    private val active = LinkedHashMap<String, String>()
+
+    @Volatile private var appContext: Context? = null
+
+    /** Called from MainActivity.onCreate(). */
+    fun init(context: Context) {
+      appContext = context.applicationContext
+      createChannels(context.applicationContext)
+    }
+
+    fun started(id: String, title: String): Boolean {
+      val ctx = appContext ?: return false
+      synchronized(active) { active[id] = title }
+      return refresh(ctx)
+    }
+
+    /**
+     * Drop [id] from the ongoing notification. [outcome] is "completed" or
+     * "failed" to post a result notification, or "" to end silently.
+     */
+    fun finished(id: String, title: String, outcome: String, message: String): Boolean {
+      val ctx = appContext ?: return false
+      synchronized(active) { active.remove(id) }
+      if (outcome.isNotEmpty()) {
+        postResult(ctx, id, title, outcome == "completed", message)
+      }
+      return refresh(ctx)
+    }
+
+    /** "metered", "unmetered" or "none", for the Wi-Fi-only setting. */
+    fun networkState(): String {
+      val ctx = appContext ?: return ""
+      val manager = ctx.getSystemService(ConnectivityManager::class.java) ?: return ""
+      if (manager.activeNetwork == null) return "none"
+      return if (manager.isActiveNetworkMetered) "metered" else "unmetered"
+    }
+
+    /** "<percent>,<charging 0|1>", for the battery thresholds. */
+    fun batteryState(): String {
+      val ctx = appContext ?: return ""
+      val manager = ctx.getSystemService(BatteryManager::class.java) ?: return ""
+      val percent = manager.getIntProperty(BatteryManager.BATTERY_PROPERTY_CAPACITY)
+      if (percent < 0 || percent == Int.MIN_VALUE) return ""
+      return "$percent,${if (manager.isCharging) 1 else 0}"
+    }
+
+    private fun refresh(ctx: Context): Boolean {
+      val empty = synchronized(active) { active.isEmpty() }
+      return try {
+        val intent = Intent(ctx, DownloadService::class.java)
+        if (empty) {
+          // Let the running instance take itself out of the foreground
+          ctx.startService(intent)
+        } else {
+          ContextCompat.startForegroundService(ctx, intent)
+        }
+        true
+      } catch (e: Throwable) {
+        // Starting from the background is refused on Android 12+; the
+        // poller keeps working for as long as the process survives
+        Log.w(TAG, "Could not update download service", e)
+        false
+      }
+    }
+
+    private fun createChannels(ctx: Context) {
+      if (Build.VERSION.SDK_INT < Build.VERSION_CODES.O) return
+      val manager = ctx.getSystemService(NotificationManager::class.java) ?: return
+      manager.createNotificationChannel(
+        NotificationChannel(CHANNEL_PROGRESS, "Active downloads", NotificationManager.IMPORTANCE_LOW)
+      )
+      manager.createNotificationChannel(
+        NotificationChannel(CHANNEL_RESULT, "Finished downloads", NotificationManager.IMPORTANCE_DEFAULT)
+      )
+    }
+
+    private fun openAppIntent(ctx: Context): PendingIntent? {
+      val launch = ctx.packageManager.getLaunchIntentForPackage(ctx.packageName) ?: return null
+      return PendingIntent.getActivity(
+        ctx,
+        0,
+        launch,
+        PendingIntent.FLAG_UPDATE_CURRENT or PendingIntent.FLAG_IMMUTABLE
+      )
+    }
+
+    /** Cancel button: one download, or all of them when [id] is empty. */
+    private fun cancelIntent(ctx: Context, id: String): PendingIntent {
+      val intent = Intent(ctx, DownloadService::class.java)
+        .setAction(ACTION_CANCEL)
+        .putExtra(EXTRA_DOWNLOAD_ID, id)
+      return PendingIntent.getService(
+        ctx,
+        id.hashCode(),
+        intent,
+        PendingIntent.FLAG_UPDATE_CURRENT or PendingIntent.FLAG_IMMUTABLE
+      )
+    }
+
+    /**
+     * Termux downloads can't be resumed from the app, so there is no pause
+     * button, only cancel.
+     */
+    private fun buildOngoing(ctx: Context, entries: List<Pair<String, String>>) =
+      NotificationCompat.Builder(ctx, CHANNEL_PROGRESS)
+        .setSmallIcon(android.R.drawable.stat_sys_download)
+        .setContentTitle(
+          if (entries.size == 1) "Downloading" else "Downloading ${entries.size} files"
+        )
+        .setContentText(entries.first().second)
+        .setStyle(NotificationCompat.InboxStyle().also { style -> entries.take(5).forEach { style.addLine(it.second) } })
+        .setProgress(0, 0, true)
+        .setOngoing(true)
+        .setOnlyAlertOnce(true)
+        .setContentIntent(openAppIntent(ctx))
+        .addAction(
+          android.R.drawable.ic_menu_close_clear_cancel,
+          if (entries.size == 1) "Cancel" else "Cancel all",
+          cancelIntent(ctx, if (entries.size == 1) entries.first().first else "")
+        )
+        .setForegroundServiceBehavior(NotificationCompat.FOREGROUND_SERVICE_IMMEDIATE)
+        .build()
+
+    private fun postResult(ctx: Context, id: String, title: String, success: Boolean, message: String) {
+      val notification = NotificationCompat.Builder(ctx, CHANNEL_RESULT)
+        .setSmallIcon(
+          if (success) android.R.drawable.stat_sys_download_done else android.R.drawable.stat_notify_error
+        )
+        .setContentTitle(if (success) "Download complete" else "Download failed")
+        .setContentText(title)
+        .setStyle(
+          NotificationCompat.BigTextStyle().bigText(
+            if (message.isEmpty()) title else "$title\n$message"
+          )
+        )
+        .setAutoCancel(true)
+        .setContentIntent(openAppIntent(ctx))
+        .build()
+      try {
+        NotificationManagerCompat.from(ctx).notify(id.hashCode(), notification)
+      } catch (e: SecurityException) {
+        // POST_NOTIFICATIONS not granted on Android 13+
+        Log.d(TAG, "Result notification not shown: ${e.message}")
+      }
+    }
+  }
+}
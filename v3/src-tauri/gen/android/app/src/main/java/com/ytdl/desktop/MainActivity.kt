@@ -28,6 +28,13 @@ class MainActivity : TauriActivity() {
     }
   }
 
+  // SAF folder picker for the download target (see SafStorage)
+  private val folderPickerLauncher = registerForActivityResult(
+    ActivityResultContracts.OpenDocumentTree()
+  ) { uri ->
+    SafStorage.onTreePicked(this, uri)
+  }
+
   // Termux RUN_COMMAND permission launcher — re-init bridge after result
   private val termuxPermissionLauncher = registerForActivityResult(
     ActivityResultContracts.RequestPermission()
@@ -98,13 +105,19 @@ class MainActivity : TauriActivity() {
         Log.e(tag, "TermuxBridge.init() failed — Termux features disabled", e)
       }
 
+      // 4. Scoped storage target picker
+      SafStorage.init(this) { folderPickerLauncher.launch(null) }
+
+      // 5. Foreground service that keeps downloads alive in the background
+      DownloadService.init(this)
+
       // Mark as fully initialized AFTER all JNI bridge calls
       tauriInitialized = true
 
-      // 4. Request storage permissions so downloads are user-accessible
+      // 6. Request storage permissions so downloads are user-accessible
       requestStoragePermissionsIfNeeded()
 
-      // 5. Request Termux RUN_COMMAND permission if Termux is installed but permission not granted
+      // 7. Request Termux RUN_COMMAND permission if Termux is installed but permission not granted
       requestTermuxPermissionIfNeeded()
     } catch (e: Throwable) {
       Log.e(tag, "Error during onCreate initialization", e)
@@ -207,6 +207,151 @@ object NativeBridge {
     }
   }
 
+  // ── Scoped storage bridge (called from Rust via JNI) ────────────────────
+
+  @JvmStatic
+  external fun nativeOnSafTreePicked(uri: String): Unit
+
+  fun onSafTreePicked(uri: String) {
+    if (!nativeLibLoaded) return
+    try {
+      nativeOnSafTreePicked(uri)
+    } catch (e: Throwable) {
+      Log.w(TAG, "nativeOnSafTreePicked failed", e)
+    }
+  }
+
+  /**
+   * Show the SAF folder picker; the result arrives via nativeOnSafTreePicked.
+   * Called by Rust's android_bridge::pick_saf_tree().
+   */
+  @JvmStatic
+  fun pickSafTree(): Boolean {
+    return try {
+      SafStorage.pickTree()
+    } catch (e: Throwable) {
+      Log.e(TAG, "pickSafTree failed", e)
+      false
+    }
+  }
+
+  @JvmStatic
+  fun hasSafTreeAccess(treeUri: String): Boolean {
+    return try {
+      SafStorage.hasTreeAccess(treeUri)
+    } catch (e: Throwable) {
+      Log.e(TAG, "hasSafTreeAccess failed", e)
+      false
+    }
+  }
+
+  /**
+   * Copy a finished download into the picked folder. Returns the new URI or "".
+   * Called by Rust's android_bridge::copy_to_saf_tree().
+   */
+  @JvmStatic
+  fun copyToSafTree(treeUri: String, path: String, mimeType: String): String {
+    return try {
+      SafStorage.copyToTree(treeUri, path, mimeType)
+    } catch (e: Throwable) {
+      Log.e(TAG, "copyToSafTree failed", e)
+      ""
+    }
+  }
+
+  /**
+   * Copy a finished download into Movies/YTDL or Music/YTDL. Returns the new URI or "".
+   * Called by Rust's android_bridge::copy_to_media_store().
+   */
+  @JvmStatic
+  fun copyToMediaStore(path: String, mimeType: String): String {
+    return try {
+      SafStorage.copyToMediaStore(path, mimeType)
+    } catch (e: Throwable) {
+      Log.e(TAG, "copyToMediaStore failed", e)
+      ""
+    }
+  }
+
+  @JvmStatic
+  fun deleteContentUri(uri: String): Boolean {
+    return try {
+      SafStorage.deleteUri(uri)
+    } catch (e: Throwable) {
+      Log.e(TAG, "deleteContentUri failed", e)
+      false
+    }
+  }
+
+  // ── Download service bridge (called from Rust via JNI) ─────────────────
+
+  @JvmStatic
+  external fun nativeOnNotificationAction(action: String, downloadId: String): Unit
+
+  fun onNotificationAction(action: String, downloadId: String) {
+    if (!nativeLibLoaded) return
+    try {
+      nativeOnNotificationAction(action, downloadId)
+    } catch (e: Throwable) {
+      Log.w(TAG, "nativeOnNotificationAction failed", e)
+    }
+  }
+
+  /**
+   * Keep the app in the foreground while a download runs.
+   * Called by Rust's android_bridge::download_service_started().
+   */
+  @JvmStatic
+  fun downloadServiceStarted(id: String, title: String): Boolean {
+    return try {
+      DownloadService.started(id, title)
+    } catch (e: Throwable) {
+      Log.e(TAG, "downloadServiceStarted failed", e)
+      false
+    }
+  }
+
+  /**
+   * Called by Rust's android_bridge::download_service_finished().
+   */
+  @JvmStatic
+  fun downloadServiceFinished(id: String, title: String, outcome: String, message: String): Boolean {
+    return try {
+      DownloadService.finished(id, title, outcome, message)
+    } catch (e: Throwable) {
+      Log.e(TAG, "downloadServiceFinished failed", e)
+      false
+    }
+  }
+
+  /**
+   * "metered", "unmetered" or "none" for the active network.
+   * Called by Rust's android_bridge::get_network_state().
+   */
+  @JvmStatic
+  fun getNetworkState(): String {
+    return try {
+      DownloadService.networkState()
+    } catch (e: Throwable) {
+      Log.e(TAG, "getNetworkState failed", e)
+      ""
+    }
+  }
+
+  /**
+   * "<percent>,<charging 0|1>" or "" when unknown.
+   * Called by Rust's android_bridge::get_battery_state().
+   */
+  @JvmStatic
+  fun getBatteryState(): String {
+    return try {
+      DownloadService.batteryState()
+    } catch (e: Throwable) {
+      Log.e(TAG, "getBatteryState failed", e)
+      ""
+    }
+  }
+
   /**
    * Open a local file or directory on Android.
    * Called by Rust's android_bridge::open_file_path().
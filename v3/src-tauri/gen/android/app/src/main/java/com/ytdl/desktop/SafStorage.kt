@@ -0,0 +1,179 @@
+package com.ytdl.desktop
+
+import android.content.ContentValues
+import android.content.Context
+import android.content.Intent
+import android.media.MediaScannerConnection
+import android.net.Uri
+import android.os.Build
+import android.os.Environment
+import android.os.Handler
+import android.os.Looper
+import android.provider.DocumentsContract
+import android.provider.MediaStore
+import android.util.Log
+import java.io.File
+
+/**
+ * SafStorage: moves finished downloads out of the Termux download folder
+ * into storage the user actually browses.
+ *
+ * Two targets are supported:
+ *
+ * - a folder picked with the Storage Access Framework (`ACTION_OPEN_DOCUMENT_TREE`),
+ *   kept across restarts through a persistable URI permission;
+ * - the shared media collections (`Movies/YTDL`, `Music/YTDL`) via MediaStore,
+ *   so videos show up in the gallery and audio in music players.
+ *
+ * Copies return the new `content://` URI (or file path before Android 10),
+ * or an empty string on failure — Rust keeps the original file in that case.
+ */
+object SafStorage {
+  private const val TAG = "YTDL-SafStorage"
+  private const val SUBFOLDER = "YTDL"
+
+  @Volatile private var appContext: Context? = null
+  @Volatile private var launchPicker: (() -> Unit)? = null
+
+  /** Called from MainActivity.onCreate() with a launcher for the tree picker. */
+  fun init(context: Context, picker: () -> Unit) {
+    appContext = context.applicationContext
+    launchPicker = picker
+  }
+
+  /**
+   * Show the folder picker. The result is reported to Rust through
+   * [NativeBridge.nativeOnSafTreePicked].
+   */
+  fun pickTree(): Boolean {
+    val picker = launchPicker ?: return false
+    Handler(Looper.getMainLooper()).post {
+      try {
+        picker()
+      } catch (e: Throwable) {
+        Log.e(TAG, "Failed to launch folder picker", e)
+        NativeBridge.onSafTreePicked("")
+      }
+    }
+    return true
+  }
+
+  /** Result of the picker; `uri` is null when the user backed out. */
+  fun onTreePicked(context: Context, uri: Uri?) {
+    if (uri == null) {
+      NativeBridge.onSafTreePicked("")
+      return
+    }
+    try {
+      context.contentResolver.takePersistableUriPermission(
+        uri,
+        Intent.FLAG_GRANT_READ_URI_PERMISSION or Intent.FLAG_GRANT_WRITE_URI_PERMISSION
+      )
+    } catch (e: Throwable) {
+      Log.w(TAG, "Could not persist access to $uri", e)
+    }
+    NativeBridge.onSafTreePicked(uri.toString())
+  }
+
+  /** True while we still hold write access to the picked tree. */
+  fun hasTreeAccess(treeUri: String): Boolean {
+    val ctx = appContext ?: return false
+    return ctx.contentResolver.persistedUriPermissions.any {
+      it.uri.toString() == treeUri && it.isWritePermission
+    }
+  }
+
+  fun copyToTree(treeUri: String, srcPath: String, mimeType: String): String {
+    val ctx = appContext ?: return ""
+    return try {
+      val src = File(srcPath)
+      val tree = Uri.parse(treeUri)
+      val parent = DocumentsContract.buildDocumentUriUsingTree(
+        tree,
+        DocumentsContract.getTreeDocumentId(tree)
+      )
+      val doc = DocumentsContract.createDocument(ctx.contentResolver, parent, mimeType, src.name)
+        ?: return ""
+      val out = ctx.contentResolver.openOutputStream(doc) ?: return ""
+      out.use { stream -> src.inputStream().use { it.copyTo(stream) } }
+      doc.toString()
+    } catch (e: Throwable) {
+      Log.e(TAG, "copyToTree failed for $srcPath", e)
+      ""
+    }
+  }
+
+  fun copyToMediaStore(srcPath: String, mimeType: String): String {
+    val ctx = appContext ?: return ""
+    val src = File(srcPath)
+    val isAudio = mimeType.startsWith("audio/")
+    return try {
+      if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.Q) {
+        val collection = if (isAudio) {
+          MediaStore.Audio.Media.getContentUri(MediaStore.VOLUME_EXTERNAL_PRIMARY)
+        } else {
+          MediaStore.Video.Media.getContentUri(MediaStore.VOLUME_EXTERNAL_PRIMARY)
+        }
+        val dir = if (isAudio) Environment.DIRECTORY_MUSIC else Environment.DIRECTORY_MOVIES
+        val values = ContentValues().apply {
+          put(MediaStore.MediaColumns.DISPLAY_NAME, src.name)
+          put(MediaStore.MediaColumns.MIME_TYPE, mimeType)
+          put(MediaStore.MediaColumns.RELATIVE_PATH, "$dir/$SUBFOLDER")
+          put(MediaStore.MediaColumns.IS_PENDING, 1)
+        }
+        val resolver = ctx.contentResolver
+        val item = resolver.insert(collection, values) ?: return ""
+        try {
+          val out = resolver.openOutputStream(item) ?: throw IllegalStateException("No output stream")
+          out.use { stream -> src.inputStream().use { it.copyTo(stream) } }
+        } catch (e: Throwable) {
+          resolver.delete(item, null, null)
+          throw e
+        }
+        values.clear()
+        values.put(MediaStore.MediaColumns.IS_PENDING, 0)
+        resolver.update(item, values, null, null)
+        item.toString()
+      } else {
+        @Suppress("DEPRECATION")
+        val base = Environment.getExternalStoragePublicDirectory(
+          if (isAudio) Environment.DIRECTORY_MUSIC else Environment.DIRECTORY_MOVIES
+        )
+        val dest = uniqueFile(File(base, SUBFOLDER), src.name)
+        src.copyTo(dest)
+        MediaScannerConnection.scanFile(ctx, arrayOf(dest.absolutePath), arrayOf(mimeType), null)
+        dest.absolutePath
+      }
+    } catch (e: Throwable) {
+      Log.e(TAG, "copyToMediaStore failed for $srcPath", e)
+      ""
+    }
+  }
+
+  /** Delete a document or media item created by one of the copies above. */
+  fun deleteUri(uri: String): Boolean {
+    val ctx = appContext ?: return false
+    return try {
+      val parsed = Uri.parse(uri)
+      if (DocumentsContract.isDocumentUri(ctx, parsed)) {
+        DocumentsContract.deleteDocument(ctx.contentResolver, parsed)
+      } else {
+        ctx.contentResolver.delete(parsed, null, null) > 0
+      }
+    } catch (e: Throwable) {
+      Log.e(TAG, "deleteUri failed for $uri", e)
+      false
+    }
+  }
+
+  private fun uniqueFile(dir: File, name: String): File {
+    dir.mkdirs()
+    var candidate = File(dir, name)
+    var n = 1
+    while (candidate.exists()) {
+      candidate = File(dir, "${name.substringBeforeLast('.')} ($n).${name.substringAfterLast('.', "")}")
+      n++
+    }
+    return candidate
+  }
+}
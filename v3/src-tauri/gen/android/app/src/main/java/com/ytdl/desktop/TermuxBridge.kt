@@ -505,6 +505,16 @@ object TermuxBridge {
     fun openFilePath(path: String, context: Context? = appContext): Boolean {
         val ctx = context ?: return false
         return try {
+            // Downloads moved to a picked folder or the media collections
+            if (path.startsWith("content://")) {
+                val intent = Intent(Intent.ACTION_VIEW).apply {
+                    setDataAndType(Uri.parse(path), ctx.contentResolver.getType(Uri.parse(path)) ?: "*/*")
+                    addFlags(Intent.FLAG_ACTIVITY_NEW_TASK or Intent.FLAG_GRANT_READ_URI_PERMISSION)
+                }
+                ctx.startActivity(intent)
+                return true
+            }
+
             val file = File(path)
             if (!file.exists()) {
                 Log.w(TAG, "openFilePath: path does not exist: $path")
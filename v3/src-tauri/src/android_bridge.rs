@@ -406,6 +406,287 @@ pub fn open_file_path(path: &str) -> Result<bool, String> {
         .map_err(|e| format!("Invalid JNI return type: {}", e))
 }
 
+// ══════════════════════════════════════════════════════════════════════════════
+// Rust → Kotlin bridge: scoped storage (SAF / MediaStore)
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// Waiting `pick_saf_tree` caller; completed by `nativeOnSafTreePicked`.
+#[cfg(target_os = "android")]
+static SAF_PICK_RESULT: std::sync::Mutex<Option<tokio::sync::oneshot::Sender<String>>> =
+    std::sync::Mutex::new(None);
+
+/// Show the SAF folder picker and wait for the user's choice. Returns the
+/// tree URI, or `None` if the picker was dismissed.
+#[cfg(target_os = "android")]
+pub async fn pick_saf_tree() -> Result<Option<String>, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    if let Ok(mut slot) = SAF_PICK_RESULT.lock() {
+        // A picker still open from an earlier call is abandoned
+        *slot = Some(tx);
+    }
+
+    let launched = {
+        let vm = JAVA_VM.get().ok_or("JavaVM not initialized — JNI bridge unavailable")?;
+        let mut env = vm.attach_current_thread()
+            .map_err(|e| format!("Failed to attach JVM thread: {}", e))?;
+
+        if env.exception_check().unwrap_or(false) {
+            let _ = env.exception_clear();
+        }
+
+        let class = get_native_bridge_class(&mut env)?;
+        let result = env.call_static_method(class, "pickSafTree", "()Z", &[]);
+
+        if env.exception_check().unwrap_or(false) {
+            let _ = env.exception_describe();
+            let _ = env.exception_clear();
+            return Err("JVM exception during pickSafTree".to_string());
+        }
+
+        result
+            .map_err(|e| format!("JNI call pickSafTree failed: {}", e))?
+            .z()
+            .map_err(|e| format!("Invalid JNI return type: {}", e))?
+    };
+    if !launched {
+        return Err("Could not open the folder picker".to_string());
+    }
+
+    match tokio::time::timeout(std::time::Duration::from_secs(10 * 60), rx).await {
+        Ok(Ok(uri)) if !uri.is_empty() => Ok(Some(uri)),
+        Ok(_) => Ok(None),
+        Err(_) => Err("Timed out waiting for a folder to be picked".to_string()),
+    }
+}
+
+/// Whether the app still holds a persisted write grant for `tree_uri`.
+#[cfg(target_os = "android")]
+pub fn has_saf_tree_access(tree_uri: &str) -> Result<bool, String> {
+    let vm = JAVA_VM.get().ok_or("JavaVM not initialized — JNI bridge unavailable")?;
+    let mut env = vm.attach_current_thread()
+        .map_err(|e| format!("Failed to attach JVM thread: {}", e))?;
+
+    if env.exception_check().unwrap_or(false) {
+        let _ = env.exception_clear();
+    }
+
+    let class = get_native_bridge_class(&mut env)?;
+
+    let j_uri = env.new_string(tree_uri)
+        .map_err(|e| format!("Failed to create JNI string: {}", e))?;
+
+    let result = env.call_static_method(
+        class,
+        "hasSafTreeAccess",
+        "(Ljava/lang/String;)Z",
+        &[JValue::Object(&j_uri)],
+    );
+
+    if env.exception_check().unwrap_or(false) {
+        let _ = env.exception_describe();
+        let _ = env.exception_clear();
+        return Err("JVM exception during hasSafTreeAccess".to_string());
+    }
+
+    result
+        .map_err(|e| format!("JNI call hasSafTreeAccess failed: {}", e))?
+        .z()
+        .map_err(|e| format!("Invalid JNI return type: {}", e))
+}
+
+/// Call a `NativeBridge` static method taking string arguments and
+/// returning a string. An empty result means the Kotlin side failed.
+#[cfg(target_os = "android")]
+fn call_string_method(method: &str, args: &[&str]) -> Result<String, String> {
+    let vm = JAVA_VM.get().ok_or("JavaVM not initialized — JNI bridge unavailable")?;
+    let mut env = vm.attach_current_thread()
+        .map_err(|e| format!("Failed to attach JVM thread: {}", e))?;
+
+    if env.exception_check().unwrap_or(false) {
+        let _ = env.exception_clear();
+    }
+
+    let class = get_native_bridge_class(&mut env)?;
+
+    let mut j_args = Vec::with_capacity(args.len());
+    for arg in args {
+        j_args.push(
+            env.new_string(arg)
+                .map_err(|e| format!("Failed to create JNI string: {}", e))?,
+        );
+    }
+    let values: Vec<JValue> = j_args.iter().map(|s| JValue::Object(s)).collect();
+    let signature = format!("({})Ljava/lang/String;", "Ljava/lang/String;".repeat(args.len()));
+
+    let result = env.call_static_method(class, method, &signature, &values);
+
+    if env.exception_check().unwrap_or(false) {
+        let _ = env.exception_describe();
+        let _ = env.exception_clear();
+        return Err(format!("JVM exception during {}", method));
+    }
+
+    let object = result
+        .map_err(|e| format!("JNI call {} failed: {}", method, e))?
+        .l()
+        .map_err(|e| format!("Invalid JNI return type: {}", e))?;
+    let j_result = JString::from(object);
+    let value: String = env
+        .get_string(&j_result)
+        .map_err(|e| format!("Failed to read JNI string: {}", e))?
+        .into();
+    Ok(value)
+}
+
+/// Copy `path` into the picked SAF folder. Returns the new document URI.
+#[cfg(target_os = "android")]
+pub fn copy_to_saf_tree(tree_uri: &str, path: &str, mime_type: &str) -> Result<String, String> {
+    let uri = call_string_method("copyToSafTree", &[tree_uri, path, mime_type])?;
+    if uri.is_empty() {
+        return Err(format!("Could not copy '{}' to the selected folder", path));
+    }
+    Ok(uri)
+}
+
+/// Copy `path` into Movies/YTDL or Music/YTDL (by `mime_type`). Returns the
+/// new media URI (a file path before Android 10).
+#[cfg(target_os = "android")]
+pub fn copy_to_media_store(path: &str, mime_type: &str) -> Result<String, String> {
+    let uri = call_string_method("copyToMediaStore", &[path, mime_type])?;
+    if uri.is_empty() {
+        return Err(format!("Could not add '{}' to the media library", path));
+    }
+    Ok(uri)
+}
+
+/// Delete a document or media item created by the copies above.
+#[cfg(target_os = "android")]
+pub fn delete_content_uri(uri: &str) -> Result<bool, String> {
+    let vm = JAVA_VM.get().ok_or("JavaVM not initialized — JNI bridge unavailable")?;
+    let mut env = vm.attach_current_thread()
+        .map_err(|e| format!("Failed to attach JVM thread: {}", e))?;
+
+    if env.exception_check().unwrap_or(false) {
+        let _ = env.exception_clear();
+    }
+
+    let class = get_native_bridge_class(&mut env)?;
+
+    let j_uri = env.new_string(uri)
+        .map_err(|e| format!("Failed to create JNI string: {}", e))?;
+
+    let result = env.call_static_method(
+        class,
+        "deleteContentUri",
+        "(Ljava/lang/String;)Z",
+        &[JValue::Object(&j_uri)],
+    );
+
+    if env.exception_check().unwrap_or(false) {
+        let _ = env.exception_describe();
+        let _ = env.exception_clear();
+        return Err("JVM exception during deleteContentUri".to_string());
+    }
+
+    result
+        .map_err(|e| format!("JNI call deleteContentUri failed: {}", e))?
+        .z()
+        .map_err(|e| format!("Invalid JNI return type: {}", e))
+}
+
+// ── Download foreground service ────────────────────────────────────────────
+
+/// Call a `NativeBridge` static method taking string arguments and
+/// returning a boolean.
+#[cfg(target_os = "android")]
+fn call_bool_method(method: &str, args: &[&str]) -> Result<bool, String> {
+    let vm = JAVA_VM.get().ok_or("JavaVM not initialized — JNI bridge unavailable")?;
+    let mut env = vm.attach_current_thread()
+        .map_err(|e| format!("Failed to attach JVM thread: {}", e))?;
+
+    if env.exception_check().unwrap_or(false) {
+        let _ = env.exception_clear();
+    }
+
+    let class = get_native_bridge_class(&mut env)?;
+
+    let mut j_args = Vec::with_capacity(args.len());
+    for arg in args {
+        j_args.push(
+            env.new_string(arg)
+                .map_err(|e| format!("Failed to create JNI string: {}", e))?,
+        );
+    }
+    let values: Vec<JValue> = j_args.iter().map(|s| JValue::Object(s)).collect();
+    let signature = format!("({})Z", "Ljava/lang/String;".repeat(args.len()));
+
+    let result = env.call_static_method(class, method, &signature, &values);
+
+    if env.exception_check().unwrap_or(false) {
+        let _ = env.exception_describe();
+        let _ = env.exception_clear();
+        return Err(format!("JVM exception during {}", method));
+    }
+
+    result
+        .map_err(|e| format!("JNI call {} failed: {}", method, e))?
+        .z()
+        .map_err(|e| format!("Invalid JNI return type: {}", e))
+}
+
+/// Add a download to the foreground service's notification, starting the
+/// service if it isn't running.
+#[cfg(target_os = "android")]
+pub fn download_service_started(download_id: &str, title: &str) -> Result<bool, String> {
+    call_bool_method("downloadServiceStarted", &[download_id, title])
+}
+
+/// Remove a download from the foreground service. `outcome` is
+/// `"completed"` or `"failed"` to post a result notification, or empty to
+/// drop it silently. The service stops once nothing is left.
+#[cfg(target_os = "android")]
+pub fn download_service_finished(
+    download_id: &str,
+    title: &str,
+    outcome: &str,
+    message: &str,
+) -> Result<bool, String> {
+    call_bool_method("downloadServiceFinished", &[download_id, title, outcome, message])
+}
+
+// ── Network and battery state ──────────────────────────────────────────────────────────
+
+/// "metered", "unmetered" or "none" (no connection) for the active network.
+#[cfg(target_os = "android")]
+pub fn get_network_state() -> Result<String, String> {
+    call_string_method("getNetworkState", &[])
+}
+
+/// "<percent>,<charging 0|1>", or empty if the battery can't be read.
+#[cfg(target_os = "android")]
+pub fn get_battery_state() -> Result<String, String> {
+    call_string_method("getBatteryState", &[])
+}
+
+// ── Notification actions ───────────────────────────────────────────────────
+
+/// Sender for taps on notification buttons, `(action, download id)`.
+#[cfg(target_os = "android")]
+static NOTIFICATION_ACTIONS: std::sync::Mutex<
+    Option<tokio::sync::mpsc::UnboundedSender<(String, String)>>,
+> = std::sync::Mutex::new(None);
+
+/// Start receiving notification button taps. Only the latest receiver
+/// gets them.
+#[cfg(target_os = "android")]
+pub fn notification_actions() -> tokio::sync::mpsc::UnboundedReceiver<(String, String)> {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    if let Ok(mut slot) = NOTIFICATION_ACTIONS.lock() {
+        *slot = Some(tx);
+    }
+    rx
+}
+
 // ── JNI: nativeSetNativeLibDir ─────────────────────────────────────────────
 
 #[cfg(target_os = "android")]
@@ -490,3 +771,42 @@ pub extern "system" fn Java_com_ytdl_desktop_NativeBridge_nativeSetTermuxInfo(
         perm
     );
 }
+
+// ── JNI: nativeOnSafTreePicked ─────────────────────────────────────────────
+
+/// Called by Kotlin `SafStorage.onTreePicked()` with the picked tree URI,
+/// or an empty string if the picker was dismissed.
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "system" fn Java_com_ytdl_desktop_NativeBridge_nativeOnSafTreePicked(
+    mut env: JNIEnv,
+    _class: JClass,
+    uri: JString,
+) {
+    store_jvm(&mut env);
+    let uri: String = env.get_string(&uri).map(Into::into).unwrap_or_default();
+    log::info!("[android_bridge] SAF folder picked: {}", if uri.is_empty() { "<cancelled>" } else { &uri });
+    if let Some(tx) = SAF_PICK_RESULT.lock().ok().and_then(|mut slot| slot.take()) {
+        let _ = tx.send(uri);
+    }
+}
+
+// ── JNI: nativeOnNotificationAction ────────────────────────────────────────
+
+/// Called by Kotlin `DownloadService` when a notification button is tapped.
+#[cfg(target_os = "android")]
+#[no_mangle]
+pub extern "system" fn Java_com_ytdl_desktop_NativeBridge_nativeOnNotificationAction(
+    mut env: JNIEnv,
+    _class: JClass,
+    action: JString,
+    download_id: JString,
+) {
+    store_jvm(&mut env);
+    let action: String = env.get_string(&action).map(Into::into).unwrap_or_default();
+    let download_id: String = env.get_string(&download_id).map(Into::into).unwrap_or_default();
+    log::info!("[android_bridge] Notification action {} for {}", action, download_id);
+    if let Some(tx) = NOTIFICATION_ACTIONS.lock().ok().and_then(|slot| slot.clone()) {
+        let _ = tx.send((action, download_id));
+    }
+}
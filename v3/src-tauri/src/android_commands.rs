@@ -6,13 +6,15 @@ use crate::db::Database;
 use crate::download;
 use crate::commands::validate_url;
 #[cfg(target_os = "android")]
-use crate::commands::sanitize_ytdlp_flags;
+use crate::commands::custom_ytdlp_flags;
 
 /// Returns Termux availability information for the frontend to display
 /// appropriate setup instructions.  On non-Android platforms returns a
 /// default "no Termux" response so the frontend always gets a consistent shape.
 #[tauri::command]
-pub async fn get_android_info() -> Result<serde_json::Value, String> {
+pub async fn get_android_info(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<serde_json::Value, String> {
     #[cfg(target_os = "android")]
     {
         let (installed, has_permission) = crate::android_bridge::termux_info();
@@ -26,11 +28,22 @@ pub async fn get_android_info() -> Result<serde_json::Value, String> {
         let has_storage_permission = crate::android_bridge::check_storage_permission()
             .unwrap_or(false);
 
+        let saf_tree_uri = db
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get_setting("android_saf_tree_uri")
+            .unwrap_or(None)
+            .unwrap_or_default();
+        let saf_folder_accessible = !saf_tree_uri.is_empty()
+            && crate::android_bridge::has_saf_tree_access(&saf_tree_uri).unwrap_or(false);
+
         return Ok(serde_json::json!({
             "platform": "android",
             "termuxInstalled": installed,
             "termuxHasPermission": has_permission,
             "hasStoragePermission": has_storage_permission,
+            "safFolder": saf_tree_uri,
+            "safFolderAccessible": saf_folder_accessible,
             "nativeLibDir": native_lib_dir,
             // These are always false on Android — kept for API compatibility
             "bundledYtdlpWorks": false,
@@ -38,12 +51,16 @@ pub async fn get_android_info() -> Result<serde_json::Value, String> {
         }));
     }
 
+    #[cfg(not(target_os = "android"))]
+    let _ = db;
     #[cfg(not(target_os = "android"))]
     Ok(serde_json::json!({
         "platform": std::env::consts::OS,
         "termuxInstalled": false,
         "termuxHasPermission": false,
         "hasStoragePermission": true,
+        "safFolder": "",
+        "safFolderAccessible": false,
         "nativeLibDir": "",
         "bundledYtdlpWorks": false,
         "bundledFfmpegWorks": false,
@@ -112,6 +129,9 @@ pub async fn termux_download(
                        In Termux, run: echo 'allow-external-apps=true' >> ~/.termux/termux.properties \
                        Then restart Termux.".to_string());
         }
+        if let Some(reason) = crate::download_guard::held_reason() {
+            return Err(reason.to_string());
+        }
 
         // Use shared storage dir (accessible by both Termux and our app)
         let output_dir = {
@@ -133,21 +153,17 @@ pub async fn termux_download(
         let format = format_id.unwrap_or_else(|| "bestvideo+bestaudio/best".to_string());
 
         // Get extra args from settings
-        let extra_args = {
+        let sanitized_args = {
             let db_lock = db.lock().map_err(|e| e.to_string())?;
             let flags_str = db_lock
                 .get_setting("ytdlp_flags")
                 .map_err(|e| e.to_string())?
                 .unwrap_or_default();
-            if flags_str.is_empty() {
-                vec![]
-            } else {
-                flags_str.split_whitespace().map(|s| s.to_string()).collect::<Vec<_>>()
-            }
+            let mut args = custom_ytdlp_flags(&flags_str);
+            args.extend(crate::extractor_args::ytdlp_args());
+            args
         };
 
-        let sanitized_args = sanitize_ytdlp_flags(&extra_args);
-
         // Generate ID before launching so we can pass it to Termux for sentinel file
         let id = uuid::Uuid::new_v4().to_string();
 
@@ -211,3 +227,120 @@ pub async fn request_storage_permission() -> Result<bool, String> {
     #[cfg(not(target_os = "android"))]
     Ok(true)
 }
+
+/// Let the user pick a folder with the Storage Access Framework and make it
+/// the target for finished downloads. Returns `None` if the picker was
+/// dismissed.
+#[tauri::command]
+pub async fn pick_android_download_folder(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Option<String>, String> {
+    #[cfg(target_os = "android")]
+    {
+        let Some(uri) = crate::android_bridge::pick_saf_tree().await? else {
+            return Ok(None);
+        };
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        db_lock
+            .save_setting("android_saf_tree_uri", &uri)
+            .map_err(|e| e.to_string())?;
+        db_lock
+            .save_setting("android_storage_target", "saf")
+            .map_err(|e| e.to_string())?;
+        log::info!("[pick_android_download_folder] Downloads will be moved to {}", uri);
+        return Ok(Some(uri));
+    }
+    #[cfg(not(target_os = "android"))]
+    {
+        let _ = db;
+        Err("Picking a storage folder is only available on Android".to_string())
+    }
+}
+
+/// Move a finished download to the `android_storage_target`. Returns the
+/// new location (a `content://` URI) or `None` when the file stays where
+/// it is, either by setting or because the copy failed.
+#[cfg(target_os = "android")]
+pub(crate) fn move_to_storage_target(db: &Arc<Mutex<Database>>, file_path: &str) -> Option<String> {
+    if file_path.is_empty() || !std::path::Path::new(file_path).is_file() {
+        return None;
+    }
+    let (target, tree_uri) = {
+        let db_lock = db.lock().ok()?;
+        let setting = |key: &str| db_lock.get_setting(key).unwrap_or(None).unwrap_or_default();
+        (setting("android_storage_target"), setting("android_saf_tree_uri"))
+    };
+    let mime_type = crate::stream_proxy::content_type_for_path(std::path::Path::new(file_path));
+
+    let copied = match target.as_str() {
+        "media" => crate::android_bridge::copy_to_media_store(file_path, mime_type),
+        "saf" if tree_uri.is_empty() => Err("No storage folder has been picked".to_string()),
+        "saf" => match crate::android_bridge::has_saf_tree_access(&tree_uri) {
+            Ok(true) => crate::android_bridge::copy_to_saf_tree(&tree_uri, file_path, mime_type),
+            _ => Err("Access to the picked storage folder was revoked; pick it again".to_string()),
+        },
+        _ => return None,
+    };
+    match copied {
+        Ok(uri) => {
+            if let Err(e) = std::fs::remove_file(file_path) {
+                log::warn!("[storage] Copied but could not remove {}: {}", file_path, e);
+            }
+            log::info!("[storage] Moved {} to {}", file_path, uri);
+            Some(uri)
+        }
+        Err(e) => {
+            log::warn!("[storage] Keeping {} in the download folder: {}", file_path, e);
+            None
+        }
+    }
+}
+
+/// Stop the Termux shell running download `id` along with its yt-dlp
+/// child. The shell's command line contains the sentinel path, which
+/// carries the id; `[.]` keeps the pattern from matching this command.
+#[cfg(target_os = "android")]
+pub(crate) fn stop_termux_download(id: &str) {
+    let command = format!(
+        "for p in $(pgrep -f '[.]status/{}'); do pkill -P \"$p\"; kill \"$p\"; done; echo stopped",
+        id
+    );
+    let output_file = format!("{}/stop_{}.txt", crate::tool_install_commands::get_shared_check_dir(), id);
+    match crate::android_bridge::run_termux_check(&command, &output_file) {
+        Ok(true) => {
+            tokio::spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                let _ = std::fs::remove_file(&output_file);
+            });
+        }
+        Ok(false) => log::warn!("[termux] Could not send stop command for {}", id),
+        Err(e) => log::warn!("[termux] Stop command for {} failed: {}", id, e),
+    }
+}
+
+/// Handle the buttons on the download notification (`DownloadService`).
+#[cfg(target_os = "android")]
+pub fn init_notification_actions(app: &tauri::App) {
+    use tauri::{Emitter, Manager};
+
+    let mut actions = crate::android_bridge::notification_actions();
+    let app = app.handle().clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some((action, id)) = actions.recv().await {
+            match action.as_str() {
+                "cancel" => {
+                    match crate::commands::cancel_download(app.state(), app.state(), id.clone()).await {
+                        Ok(()) => {
+                            let _ = app.emit(
+                                "download-status",
+                                serde_json::json!({ "id": id, "status": "cancelled" }),
+                            );
+                        }
+                        Err(e) => log::warn!("[notification] Failed to cancel {}: {}", id, e),
+                    }
+                }
+                other => log::warn!("[notification] Unknown action '{}'", other),
+            }
+        }
+    });
+}
@@ -0,0 +1,129 @@
+//! "Full archive" downloads.
+//!
+//! A download started with mode `archive` keeps everything yt-dlp can save
+//! about a video next to it: thumbnail, description, info.json (with the
+//! comments), and every uploaded subtitle track. All of it goes into a
+//! folder of its own, `Title [id]/`, and stays one download in the list.
+//! Once the download finishes the folder is scanned and each file is
+//! recorded in `download_artifacts`, so the UI can list the pieces and
+//! deleting the download removes all of them.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use tauri::State;
+
+use crate::db::Database;
+
+/// `mode` value that turns a download into an archive bundle.
+pub const MODE: &str = "archive";
+
+/// yt-dlp flags for the sidecar files of a bundle.
+pub fn ytdlp_args() -> Vec<String> {
+    [
+        "--write-thumbnail",
+        "--write-description",
+        "--write-info-json",
+        "--write-comments",
+        "--write-subs",
+        "--sub-langs",
+        "all,-live_chat",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+/// Put a file name template into a per-video folder:
+/// `%(title)s.%(ext)s` becomes `%(title)s [%(id)s]/%(title)s.%(ext)s`.
+/// Subfolders already in the template are kept above the new one.
+pub fn file_template(template: &str) -> String {
+    let Some(stem) = template.strip_suffix(".%(ext)s") else {
+        return template.to_string();
+    };
+    let (dir, name) = match stem.rsplit_once('/') {
+        Some((dir, name)) => (format!("{}/", dir), name),
+        None => (String::new(), stem),
+    };
+    let folder = if name.contains("%(id)s") {
+        name.to_string()
+    } else {
+        format!("{} [%(id)s]", name)
+    };
+    format!("{}{}/{}.%(ext)s", dir, folder, name)
+}
+
+/// What a file in a bundle folder is, from its name.
+fn artifact_kind(path: &Path, main_file: &Path) -> &'static str {
+    if path == main_file {
+        return "video";
+    }
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if name.ends_with(".info.json") {
+        "info"
+    } else if ext == "description" {
+        "description"
+    } else if matches!(ext.as_str(), "jpg" | "jpeg" | "png" | "webp") {
+        "thumbnail"
+    } else if matches!(ext.as_str(), "vtt" | "srt" | "ass" | "ttml" | "srv3" | "json3") {
+        "subtitle"
+    } else {
+        "other"
+    }
+}
+
+/// Record the files of a finished bundle. Does nothing for downloads that
+/// aren't bundles.
+pub fn record_artifacts(db: &Arc<Mutex<Database>>, download_id: &str, file_path: &str) {
+    let Ok(db_lock) = db.lock() else { return };
+    let is_bundle = db_lock
+        .get_download(download_id)
+        .unwrap_or(None)
+        .is_some_and(|d| d["mode"].as_str() == Some(MODE));
+    if !is_bundle {
+        return;
+    }
+
+    let main_file = Path::new(file_path);
+    let Some(folder) = main_file.parent() else { return };
+    let Ok(entries) = std::fs::read_dir(folder) else { return };
+    let mut files: Vec<(String, String, i64)> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .map(|entry| {
+            let path = entry.path();
+            let size = entry.metadata().map(|m| m.len() as i64).unwrap_or(0);
+            (
+                artifact_kind(&path, main_file).to_string(),
+                path.to_string_lossy().to_string(),
+                size,
+            )
+        })
+        // yt-dlp's leftovers from an interrupted run
+        .filter(|(_, path, _)| !path.ends_with(".part") && !path.ends_with(".ytdl"))
+        .collect();
+    files.sort();
+
+    if let Err(e) = db_lock.replace_download_artifacts(download_id, &files) {
+        log::warn!("[archive] Failed to record files of {}: {}", download_id, e);
+    }
+}
+
+/// Files that make up an archive bundle: `{kind, path, size}`, where kind
+/// is video, thumbnail, description, info (info.json with the comments),
+/// subtitle or other.
+#[tauri::command]
+pub async fn get_download_artifacts(
+    db: State<'_, Arc<Mutex<Database>>>,
+    id: String,
+) -> Result<Vec<serde_json::Value>, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.get_download_artifacts(&id).map_err(|e| e.to_string())
+}
@@ -0,0 +1,59 @@
+//! Launch on login.
+//!
+//! The `auto_launch` setting registers the app with the OS (LaunchAgent on
+//! macOS, the Run registry key on Windows, an XDG autostart entry on
+//! Linux). Those launches pass [`MINIMIZED_ARG`]; with `start_minimized`
+//! on, the window then starts minimized while the schedulers run as usual.
+
+use tauri::{AppHandle, Manager};
+
+use crate::db::Database;
+
+pub const MINIMIZED_ARG: &str = "--minimized";
+
+#[cfg(desktop)]
+pub fn plugin<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    tauri_plugin_autostart::init(
+        tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+        Some(vec![MINIMIZED_ARG]),
+    )
+}
+
+/// Register or unregister the login item.
+pub fn apply(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    #[cfg(desktop)]
+    {
+        use tauri_plugin_autostart::ManagerExt;
+        let launcher = app.autolaunch();
+        let registered = launcher.is_enabled().unwrap_or(false);
+        let result = match (enabled, registered) {
+            (true, false) => launcher.enable(),
+            (false, true) => launcher.disable(),
+            _ => Ok(()),
+        };
+        result.map_err(|e| format!("Failed to update launch on login: {}", e))
+    }
+    #[cfg(mobile)]
+    {
+        let _ = (app, enabled);
+        Ok(())
+    }
+}
+
+/// Bring the login item in line with the setting (it may have been removed
+/// by the OS or point at an old install) and minimize the window if this
+/// is an autostart launch.
+pub fn init(app: &tauri::App, db: &Database) {
+    let setting = |key: &str| db.get_setting(key).unwrap_or(None).unwrap_or_default();
+    if let Err(e) = apply(app.handle(), setting("auto_launch") == "true") {
+        log::warn!("[autostart] {}", e);
+    }
+
+    let autostarted = std::env::args().any(|arg| arg == MINIMIZED_ARG);
+    if autostarted && setting("start_minimized") != "false" {
+        log::info!("[autostart] Started on login, minimizing window");
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.minimize();
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Battery-aware scheduling.
+//!
+//! When the battery drops below `battery_min_percent` (0 = off) and the
+//! device isn't charging, the download guard in [`crate::download_guard`]
+//! pauses running and queued downloads and the RSS and playlist schedulers
+//! hold their next check. Everything picks up again once the device is
+//! plugged in or the battery is back at `battery_resume_percent`.
+//!
+//! Detection: BatteryManager on Android, `/sys/class/power_supply` on
+//! Linux, `pmset` on macOS and `GetSystemPowerStatus` on Windows. Devices without a battery,
+//! or where the check fails, never count as low.
+
+use std::sync::Mutex;
+
+use crate::db::Database;
+
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatteryStatus {
+    pub percent: u8,
+    pub charging: bool,
+}
+
+struct Thresholds {
+    /// Pause below this, 0 = battery is ignored.
+    min_percent: u32,
+    /// Resume at or above this even when not charging.
+    resume_percent: u32,
+}
+
+static THRESHOLDS: Mutex<Thresholds> = Mutex::new(Thresholds { min_percent: 0, resume_percent: 0 });
+
+/// Reload the thresholds. Called at startup and whenever one changes.
+pub fn load_from_db(db: &Database) {
+    let setting = |key: &str| {
+        db.get_setting(key)
+            .unwrap_or(None)
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .unwrap_or(0)
+    };
+    let min_percent = setting("battery_min_percent").min(100);
+    let resume_percent = setting("battery_resume_percent").clamp(min_percent, 100);
+    if let Ok(mut thresholds) = THRESHOLDS.lock() {
+        thresholds.min_percent = min_percent;
+        thresholds.resume_percent = resume_percent;
+    }
+}
+
+pub fn enabled() -> bool {
+    THRESHOLDS.lock().map(|t| t.min_percent > 0).unwrap_or(false)
+}
+
+/// Whether work should stay paused. `was_low` is the previous answer, so
+/// the battery has to climb back to the resume threshold before a pause
+/// ends rather than flapping around the minimum.
+pub fn is_low(status: Option<BatteryStatus>, was_low: bool) -> bool {
+    let Some(status) = status else { return false };
+    let Ok(thresholds) = THRESHOLDS.lock() else { return false };
+    if thresholds.min_percent == 0 || status.charging {
+        return false;
+    }
+    let percent = status.percent as u32;
+    if was_low {
+        percent < thresholds.resume_percent.max(thresholds.min_percent)
+    } else {
+        percent < thresholds.min_percent
+    }
+}
+
+/// Current charge, `None` without a battery or when it can't be read.
+pub async fn status() -> Option<BatteryStatus> {
+    #[cfg(target_os = "android")]
+    {
+        // "<percent>,<charging 0|1>"
+        let value = crate::android_bridge::get_battery_state().ok()?;
+        let (percent, charging) = value.split_once(',')?;
+        Some(BatteryStatus {
+            percent: percent.trim().parse().ok()?,
+            charging: charging.trim() == "1",
+        })
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let entries = std::fs::read_dir("/sys/class/power_supply").ok()?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let kind = std::fs::read_to_string(path.join("type")).unwrap_or_default();
+            if kind.trim() != "Battery" {
+                continue;
+            }
+            let Some(percent) = std::fs::read_to_string(path.join("capacity"))
+                .ok()
+                .and_then(|v| v.trim().parse::<u8>().ok())
+            else {
+                continue;
+            };
+            // "Charging", "Discharging", "Full" or "Not charging" (plugged in, held)
+            let state = std::fs::read_to_string(path.join("status")).unwrap_or_default();
+            return Some(BatteryStatus { percent, charging: state.trim() != "Discharging" });
+        }
+        None
+    }
+    #[cfg(target_os = "macos")]
+    {
+        // Now drawing from 'Battery Power'
+        //  -InternalBattery-0 (id=...)	54%; discharging; 3:12 remaining present: true
+        let output = crate::download::create_hidden_command("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .await
+            .ok()?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        let percent = text
+            .split_whitespace()
+            .find_map(|word| word.strip_suffix("%;").and_then(|p| p.parse::<u8>().ok()))?;
+        Some(BatteryStatus { percent, charging: !text.contains("'Battery Power'") })
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // A plain kernel32 call, cheap enough for the guard's regular check
+        /// Laid out like `SYSTEM_POWER_STATUS`.
+        #[repr(C)]
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct SystemPowerStatus {
+            ac_line_status: u8,
+            battery_flag: u8,
+            battery_life_percent: u8,
+            system_status_flag: u8,
+            battery_life_time: u32,
+            battery_full_life_time: u32,
+        }
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn GetSystemPowerStatus(status: *mut SystemPowerStatus) -> i32;
+        }
+
+        let mut power = SystemPowerStatus::default();
+        // SAFETY: `power` is a writable struct with the layout the call fills in
+        if unsafe { GetSystemPowerStatus(&mut power) } == 0 {
+            return None;
+        }
+        // Flag 128 = no system battery (255 = unknown has it set too)
+        if power.battery_flag & 128 != 0 || power.battery_life_percent > 100 {
+            return None;
+        }
+        // ACLineStatus 1 = on AC power
+        Some(BatteryStatus {
+            percent: power.battery_life_percent,
+            charging: power.ac_line_status == 1,
+        })
+    }
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "windows"
+    )))]
+    {
+        None
+    }
+}
+
+/// Battery level and whether scheduled work is being held for it.
+#[tauri::command]
+pub async fn get_battery_status() -> Result<serde_json::Value, String> {
+    let status = status().await;
+    Ok(serde_json::json!({
+        "battery": status,
+        "enabled": enabled(),
+        "low": crate::download_guard::battery_low(),
+    }))
+}
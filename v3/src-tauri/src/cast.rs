@@ -0,0 +1,506 @@
+//! Casting to DLNA/UPnP media renderers (smart TVs, Kodi, VLC, most AV
+//! receivers) and Google Cast devices. Renderers are found via SSDP and
+//! driven through their AVTransport service; Google Cast devices are found
+//! via mDNS and driven over CASTv2 ([`crate::chromecast`]). The media itself
+//! is served to either by the LAN side of [`StreamProxy`], a proxied stream
+//! or a downloaded file, under a token only that device may fetch and that
+//! is dropped once the cast stops.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::State;
+
+use crate::chromecast;
+use crate::commands::validate_url;
+use crate::db::Database;
+use crate::stream_proxy::{self, StreamProxy};
+
+const SSDP_ADDR: &str = "239.255.255.250:1900";
+const MEDIA_RENDERER: &str = "urn:schemas-upnp-org:device:MediaRenderer:1";
+const AV_TRANSPORT: &str = "urn:schemas-upnp-org:service:AVTransport:1";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Renderer {
+    pub id: String,
+    pub name: String,
+    pub manufacturer: String,
+    pub model: String,
+    pub location: String,
+    /// "dlna" or "googlecast".
+    pub kind: &'static str,
+    #[serde(skip)]
+    protocol: Protocol,
+    #[serde(skip)]
+    address: IpAddr,
+}
+
+#[derive(Debug, Clone)]
+enum Protocol {
+    /// AVTransport control URL.
+    Dlna(String),
+    GoogleCast(chromecast::Device),
+}
+
+impl Renderer {
+    fn from_google_cast(device: chromecast::Device) -> Self {
+        Self {
+            id: device.id.clone(),
+            name: device.name.clone(),
+            manufacturer: String::new(),
+            model: device.model.clone(),
+            location: SocketAddr::new(device.address, device.port).to_string(),
+            kind: "googlecast",
+            address: device.address,
+            protocol: Protocol::GoogleCast(device),
+        }
+    }
+}
+
+/// What a renderer is currently casting.
+struct CastSession {
+    /// Proxy token of the media.
+    token: String,
+    /// The renderer has reported playback since the cast started, so a
+    /// later `STOPPED` means it is over.
+    started: bool,
+}
+
+/// Renderers found by the last discovery, keyed by UDN, and what each of
+/// them is casting.
+pub struct CastManager {
+    renderers: Mutex<HashMap<String, Renderer>>,
+    sessions: Mutex<HashMap<String, CastSession>>,
+}
+
+impl CastManager {
+    pub fn new() -> Self {
+        Self {
+            renderers: Mutex::new(HashMap::new()),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remember `token` as what `device_id` casts, revoking the one it
+    /// replaces.
+    fn begin(&self, proxy: &StreamProxy, device_id: &str, token: String) {
+        if let Ok(mut sessions) = self.sessions.lock() {
+            let session = CastSession { token, started: false };
+            if let Some(old) = sessions.insert(device_id.to_string(), session) {
+                proxy.revoke(&old.token);
+            }
+        }
+    }
+
+    /// The cast on `device_id` is over; its media is no longer served.
+    fn end(&self, proxy: &StreamProxy, device_id: &str) {
+        if let Some(session) = self.sessions.lock().ok().and_then(|mut s| s.remove(device_id)) {
+            proxy.revoke(&session.token);
+        }
+    }
+
+    /// Follow the transport state reported for `device_id`.
+    fn observe(&self, proxy: &StreamProxy, device_id: &str, state: &str) {
+        let stopped = {
+            let Ok(mut sessions) = self.sessions.lock() else { return };
+            let Some(session) = sessions.get_mut(device_id) else { return };
+            match state {
+                "PLAYING" | "PAUSED_PLAYBACK" => {
+                    session.started = true;
+                    false
+                }
+                "STOPPED" | "NO_MEDIA_PRESENT" => session.started,
+                _ => false,
+            }
+        };
+        if stopped {
+            log::info!("[cast] Cast on {} stopped", device_id);
+            self.end(proxy, device_id);
+        }
+    }
+
+    fn get(&self, id: &str) -> Result<Renderer, String> {
+        self.renderers
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get(id)
+            .cloned()
+            .ok_or_else(|| format!("Cast device not found: {}. Run discovery again.", id))
+    }
+}
+
+/// Search for media renderers (SSDP) and Google Cast devices (mDNS) and
+/// describe every device that answers within `timeout_ms` (default 3 s).
+#[tauri::command]
+pub async fn discover_cast_devices(
+    cast: State<'_, CastManager>,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<Renderer>, String> {
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(3000).clamp(500, 15000));
+    let (locations, cast_devices) = tokio::join!(ssdp_search(timeout), chromecast::discover(timeout));
+    let cast_devices = cast_devices.unwrap_or_else(|e| {
+        log::warn!("[cast] Google Cast discovery failed: {}", e);
+        Vec::new()
+    });
+    let locations = match locations {
+        Ok(locations) => locations,
+        Err(e) if !cast_devices.is_empty() => {
+            log::warn!("[cast] {}", e);
+            Vec::new()
+        }
+        Err(e) => return Err(e),
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(3))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut found = Vec::new();
+    for location in locations {
+        match describe_renderer(&client, &location).await {
+            Ok(Some(renderer)) => found.push(renderer),
+            Ok(None) => {}
+            Err(e) => log::debug!("[cast] Skipping {}: {}", location, e),
+        }
+    }
+    found.extend(cast_devices.into_iter().map(Renderer::from_google_cast));
+
+    let mut renderers = cast.renderers.lock().map_err(|e| e.to_string())?;
+    renderers.clear();
+    for renderer in &found {
+        renderers.insert(renderer.id.clone(), renderer.clone());
+    }
+    log::info!("[cast] Found {} renderer(s)", found.len());
+    Ok(found)
+}
+
+/// Play `source` on a renderer. `source` is either a URL returned by
+/// `get_stream_url` (the player's proxied stream), the path of a file in
+/// the download folders, or any other http(s) media URL. Returns the URL
+/// handed to the renderer.
+#[tauri::command]
+pub async fn cast_media(
+    cast: State<'_, CastManager>,
+    proxy: State<'_, StreamProxy>,
+    db: State<'_, Arc<Mutex<Database>>>,
+    device_id: String,
+    source: String,
+    title: Option<String>,
+) -> Result<String, String> {
+    let renderer = cast.get(&device_id)?;
+    let source = source.trim();
+
+    let (token, content_type) = if let Some(token) = proxy.token_for_local_url(source) {
+        (proxy.share(&token, renderer.address)?, "video/mp4")
+    } else if std::path::Path::new(source).is_file() {
+        let path = PathBuf::from(source)
+            .canonicalize()
+            .map_err(|e| format!("Cannot resolve '{}': {}", source, e))?;
+        {
+            let db_lock = db.lock().map_err(|e| e.to_string())?;
+            crate::commands::ensure_download_file(&db_lock, &path).map_err(|_| {
+                format!("Not casting '{}': it is outside the download folders", path.display())
+            })?;
+        }
+        let content_type = stream_proxy::content_type_for_path(&path);
+        (proxy.register_file(path, renderer.address)?, content_type)
+    } else {
+        validate_url(source)?;
+        (proxy.register_for(source, Vec::new(), renderer.address)?, "video/mp4")
+    };
+
+    let host_ip = local_ip_toward(renderer.address)?;
+    let media_url = proxy.lan_url(&token, host_ip).await?;
+    let title = title.filter(|t| !t.trim().is_empty()).unwrap_or_else(|| "YTDL".to_string());
+
+    cast.begin(&proxy, &device_id, token.clone());
+    let started = match &renderer.protocol {
+        Protocol::Dlna(control_url) => dlna_play(control_url, &media_url, content_type, &title).await,
+        Protocol::GoogleCast(device) => chromecast::load(device, &media_url, content_type, &title).await,
+    };
+    if let Err(e) = started {
+        cast.end(&proxy, &device_id);
+        return Err(e);
+    }
+
+    log::info!("[cast] Casting to {}: {}", renderer.name, media_url);
+    Ok(media_url)
+}
+
+/// Hand `media_url` to an AVTransport renderer and start playback.
+async fn dlna_play(control_url: &str, media_url: &str, content_type: &str, title: &str) -> Result<(), String> {
+    let didl = format!(
+        r#"<DIDL-Lite xmlns="urn:schemas-upnp-org:metadata-1-0/DIDL-Lite/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:upnp="urn:schemas-upnp-org:metadata-1-0/upnp/"><item id="0" parentID="-1" restricted="1"><dc:title>{}</dc:title><upnp:class>{}</upnp:class><res protocolInfo="http-get:*:{}:*">{}</res></item></DIDL-Lite>"#,
+        xml_escape(title),
+        if content_type.starts_with("audio/") { "object.item.audioItem.musicTrack" } else { "object.item.videoItem" },
+        content_type,
+        xml_escape(media_url),
+    );
+
+    // Some renderers refuse a new URI while playing
+    let _ = soap_call(control_url, "Stop", "").await;
+    soap_call(
+        control_url,
+        "SetAVTransportURI",
+        &format!(
+            "<CurrentURI>{}</CurrentURI><CurrentURIMetaData>{}</CurrentURIMetaData>",
+            xml_escape(media_url),
+            xml_escape(&didl)
+        ),
+    )
+    .await?;
+    soap_call(control_url, "Play", "<Speed>1</Speed>").await?;
+    Ok(())
+}
+
+/// `play`, `pause` or `stop` the current media on a renderer.
+#[tauri::command]
+pub async fn cast_control(
+    cast: State<'_, CastManager>,
+    proxy: State<'_, StreamProxy>,
+    device_id: String,
+    action: String,
+) -> Result<(), String> {
+    let renderer = cast.get(&device_id)?;
+    if !matches!(action.as_str(), "play" | "pause" | "stop") {
+        return Err(format!("Unknown cast action: {}", action));
+    }
+    let result = match &renderer.protocol {
+        Protocol::Dlna(control_url) => {
+            let (soap_action, args) = match action.as_str() {
+                "play" => ("Play", "<Speed>1</Speed>"),
+                "pause" => ("Pause", ""),
+                _ => ("Stop", ""),
+            };
+            soap_call(control_url, soap_action, args).await.map(|_| ())
+        }
+        Protocol::GoogleCast(device) => chromecast::control(device, &action.to_uppercase(), None).await,
+    };
+    if action == "stop" {
+        cast.end(&proxy, &device_id);
+    }
+    result
+}
+
+#[tauri::command]
+pub async fn cast_seek(
+    cast: State<'_, CastManager>,
+    device_id: String,
+    position: f64,
+) -> Result<(), String> {
+    let renderer = cast.get(&device_id)?;
+    match &renderer.protocol {
+        Protocol::Dlna(control_url) => {
+            soap_call(
+                control_url,
+                "Seek",
+                &format!("<Unit>REL_TIME</Unit><Target>{}</Target>", format_upnp_time(position)),
+            )
+            .await?;
+            Ok(())
+        }
+        Protocol::GoogleCast(device) => chromecast::control(device, "SEEK", Some(position)).await,
+    }
+}
+
+/// Transport state (`PLAYING`, `PAUSED_PLAYBACK`, `STOPPED`, ...) plus
+/// position and duration in seconds.
+#[tauri::command]
+pub async fn get_cast_status(
+    cast: State<'_, CastManager>,
+    proxy: State<'_, StreamProxy>,
+    device_id: String,
+) -> Result<serde_json::Value, String> {
+    let renderer = cast.get(&device_id)?;
+    let (state, position, duration) = match &renderer.protocol {
+        Protocol::Dlna(control_url) => {
+            let transport = soap_call(control_url, "GetTransportInfo", "").await?;
+            let position = soap_call(control_url, "GetPositionInfo", "").await?;
+            (
+                xml_tag(&transport, "CurrentTransportState").unwrap_or_default(),
+                xml_tag(&position, "RelTime").map(|t| parse_upnp_time(&t)).unwrap_or(0.0),
+                xml_tag(&position, "TrackDuration").map(|t| parse_upnp_time(&t)).unwrap_or(0.0),
+            )
+        }
+        Protocol::GoogleCast(device) => {
+            let status = chromecast::status(device).await?;
+            (status.state.to_string(), status.position, status.duration)
+        }
+    };
+    cast.observe(&proxy, &device_id, &state);
+
+    Ok(serde_json::json!({
+        "state": state,
+        "position": position,
+        "duration": duration,
+    }))
+}
+
+async fn ssdp_search(timeout: Duration) -> Result<Vec<String>, String> {
+    let socket = tokio::net::UdpSocket::bind("0.0.0.0:0")
+        .await
+        .map_err(|e| format!("Failed to open discovery socket: {}", e))?;
+    let request = format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {}\r\nMAN: \"ssdp:discover\"\r\nMX: {}\r\nST: {}\r\n\r\n",
+        SSDP_ADDR,
+        timeout.as_secs().max(1),
+        MEDIA_RENDERER
+    );
+    let target: SocketAddr = SSDP_ADDR.parse().map_err(|e: std::net::AddrParseError| e.to_string())?;
+    // UDP is lossy; ask twice
+    for _ in 0..2 {
+        socket
+            .send_to(request.as_bytes(), target)
+            .await
+            .map_err(|e| format!("SSDP search failed: {}", e))?;
+    }
+
+    let mut locations: Vec<String> = Vec::new();
+    let mut buf = [0u8; 2048];
+    let deadline = tokio::time::Instant::now() + timeout;
+    while let Ok(Ok((n, _))) = tokio::time::timeout_at(deadline, socket.recv_from(&mut buf)).await {
+        let response = String::from_utf8_lossy(&buf[..n]);
+        let location = response.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim().eq_ignore_ascii_case("location").then(|| value.trim().to_string())
+        });
+        if let Some(location) = location {
+            if !locations.contains(&location) {
+                locations.push(location);
+            }
+        }
+    }
+    Ok(locations)
+}
+
+async fn describe_renderer(client: &reqwest::Client, location: &str) -> Result<Option<Renderer>, String> {
+    let base = url::Url::parse(location).map_err(|e| e.to_string())?;
+    let address: IpAddr = base
+        .host_str()
+        .and_then(|h| h.trim_matches(['[', ']']).parse().ok())
+        .ok_or_else(|| "Renderer location has no IP host".to_string())?;
+    let xml = client
+        .get(location)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let control_path = xml
+        .split("<service>")
+        .skip(1)
+        .find(|service| xml_tag(service, "serviceType").as_deref() == Some(AV_TRANSPORT))
+        .and_then(|service| xml_tag(service, "controlURL"));
+    let Some(control_path) = control_path else {
+        return Ok(None);
+    };
+    let url_base = xml_tag(&xml, "URLBase")
+        .and_then(|b| url::Url::parse(&b).ok())
+        .unwrap_or(base);
+    let control_url = url_base.join(&control_path).map_err(|e| e.to_string())?;
+
+    Ok(Some(Renderer {
+        id: xml_tag(&xml, "UDN").unwrap_or_else(|| location.to_string()),
+        name: xml_tag(&xml, "friendlyName").unwrap_or_else(|| address.to_string()),
+        manufacturer: xml_tag(&xml, "manufacturer").unwrap_or_default(),
+        model: xml_tag(&xml, "modelName").unwrap_or_default(),
+        location: location.to_string(),
+        kind: "dlna",
+        protocol: Protocol::Dlna(control_url.to_string()),
+        address,
+    }))
+}
+
+async fn soap_call(control_url: &str, action: &str, args: &str) -> Result<String, String> {
+    let body = format!(
+        r#"<?xml version="1.0" encoding="utf-8"?><s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/" s:encodingStyle="http://schemas.xmlsoap.org/soap/encoding/"><s:Body><u:{action} xmlns:u="{service}"><InstanceID>0</InstanceID>{args}</u:{action}></s:Body></s:Envelope>"#,
+        action = action,
+        service = AV_TRANSPORT,
+        args = args,
+    );
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(control_url)
+        .header("Content-Type", "text/xml; charset=\"utf-8\"")
+        .header("SOAPAction", format!("\"{}#{}\"", AV_TRANSPORT, action))
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("Cast device unreachable: {}", e))?;
+
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        let detail = xml_tag(&text, "errorDescription").unwrap_or_else(|| status.to_string());
+        return Err(format!("{} failed: {}", action, detail));
+    }
+    Ok(text)
+}
+
+/// Address of our interface that routes to `peer`, i.e. the one the
+/// renderer can reach us on. No packets are sent.
+fn local_ip_toward(peer: IpAddr) -> Result<IpAddr, String> {
+    let bind = if peer.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+    let socket = std::net::UdpSocket::bind(bind).map_err(|e| e.to_string())?;
+    socket.connect((peer, 1900)).map_err(|e| e.to_string())?;
+    Ok(socket.local_addr().map_err(|e| e.to_string())?.ip())
+}
+
+/// Text of the first `<tag>` (namespace prefixes ignored), unescaped.
+fn xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let mut search_from = 0;
+    while let Some(rel) = xml[search_from..].find('<') {
+        let open = search_from + rel;
+        let close = open + xml[open..].find('>')?;
+        let name = xml[open + 1..close].split_whitespace().next().unwrap_or("");
+        let local = name.rsplit(':').next().unwrap_or(name);
+        if local == tag && !name.starts_with('/') {
+            let content_start = close + 1;
+            let end = content_start + xml[content_start..].find(&format!("</{}", name))?;
+            return Some(xml_unescape(xml[content_start..end].trim()));
+        }
+        search_from = close + 1;
+    }
+    None
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn format_upnp_time(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!("{:02}:{:02}:{:02}", total / 3600, (total % 3600) / 60, total % 60)
+}
+
+/// `H+:MM:SS[.fff]` → seconds. `NOT_IMPLEMENTED` and garbage become 0.
+fn parse_upnp_time(value: &str) -> f64 {
+    let parts: Vec<f64> = value.split(':').filter_map(|p| p.trim().parse().ok()).collect();
+    match parts.as_slice() {
+        [h, m, s] => h * 3600.0 + m * 60.0 + s,
+        [m, s] => m * 60.0 + s,
+        _ => 0.0,
+    }
+}
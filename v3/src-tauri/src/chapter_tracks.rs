@@ -0,0 +1,170 @@
+//! "Album" downloads: a music upload split into one track per chapter.
+//!
+//! A download started with mode `tracks` extracts the audio and has yt-dlp
+//! cut it at the chapter marks (`--split-chapters`). Everything lands in a
+//! folder of its own, `Title [id]/`, next to the full-length file. yt-dlp
+//! gives the pieces no tags of their own, so once the download finishes
+//! each one is tagged with ffmpeg: title from the chapter, track number,
+//! album from the video title and artist from the uploader. The tracks are
+//! recorded in `download_artifacts`, so deleting the download removes them.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::db::Database;
+use crate::download;
+
+/// `mode` value that splits a download into chapter tracks.
+pub const MODE: &str = "tracks";
+
+/// Format picked when the user didn't choose one; only the audio is kept.
+/// Spelled out so a resumed download still goes to the audio folder.
+pub const DEFAULT_FORMAT: &str = "bestaudio/best";
+
+/// Tracks are named `01 - Chapter title.mp3`.
+const TRACK_TEMPLATE: &str = "%(section_number)02d - %(section_title)s.%(ext)s";
+
+/// yt-dlp flags for extracting the audio and splitting it. `output_dir`
+/// and `template` are the download's folder and (already bundled) file
+/// template; the tracks go next to the full file.
+pub fn ytdlp_args(output_dir: &str, template: &str) -> Vec<String> {
+    let folder = template.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+    let track_template = if folder.is_empty() {
+        format!("{}/{}", output_dir, TRACK_TEMPLATE)
+    } else {
+        format!("{}/{}/{}", output_dir, folder, TRACK_TEMPLATE)
+    };
+    vec![
+        "-x".to_string(),
+        "--audio-format".to_string(),
+        "mp3".to_string(),
+        "--audio-quality".to_string(),
+        "0".to_string(),
+        "--split-chapters".to_string(),
+        // Chapter titles and the uploader for tagging, see `finish`
+        "--write-info-json".to_string(),
+        "-o".to_string(),
+        format!("chapter:{}", track_template),
+    ]
+}
+
+/// Track number from a file named by [`TRACK_TEMPLATE`].
+fn track_number(path: &Path) -> Option<usize> {
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let (number, _) = name.split_once(" - ")?;
+    if number.len() < 2 || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    number.parse().ok()
+}
+
+/// Rewrite `path` with the given tags, keeping the streams as they are.
+async fn tag_track(ffmpeg: &str, path: &Path, tags: &[(&str, String)]) -> Result<(), String> {
+    let ext = path.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+    let tagged = path.with_extension(format!("tagged.{}", ext));
+    let mut cmd = download::create_hidden_command(ffmpeg);
+    cmd.args(["-y", "-v", "error", "-i"])
+        .arg(path)
+        .args(["-map", "0", "-c", "copy"]);
+    if ext.eq_ignore_ascii_case("mp3") {
+        cmd.args(["-id3v2_version", "3"]);
+    }
+    for (key, value) in tags {
+        cmd.arg("-metadata").arg(format!("{}={}", key, value));
+    }
+    let output = cmd.arg(&tagged).output().await.map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&tagged);
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    std::fs::rename(&tagged, path).map_err(|e| e.to_string())
+}
+
+/// Tag the tracks of a finished album download and record them. Does
+/// nothing for other downloads.
+pub async fn finish(ffmpeg: &str, db: &Arc<Mutex<Database>>, download_id: &str, file_path: &str) {
+    let is_album = db
+        .lock()
+        .ok()
+        .and_then(|db_lock| db_lock.get_download(download_id).unwrap_or(None))
+        .is_some_and(|d| d["mode"].as_str() == Some(MODE));
+    if !is_album {
+        return;
+    }
+
+    let main_file = Path::new(file_path);
+    let Some(folder) = main_file.parent() else { return };
+    let Ok(entries) = std::fs::read_dir(folder) else { return };
+    let mut tracks: Vec<(usize, PathBuf)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path != main_file)
+        .filter_map(|path| Some((track_number(&path)?, path)))
+        .collect();
+    tracks.sort();
+    if tracks.is_empty() {
+        log::warn!("[tracks] No chapter tracks found for {}", download_id);
+        return;
+    }
+
+    let info = download::read_info_json(file_path).unwrap_or_default();
+    let chapters: Vec<String> = info["chapters"]
+        .as_array()
+        .map(|chapters| {
+            chapters
+                .iter()
+                .map(|c| c["title"].as_str().unwrap_or_default().trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    let text = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| info[*key].as_str().filter(|v| !v.trim().is_empty()))
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+    let album = text(&["album", "title"]);
+    let artist = text(&["artist", "creator", "uploader", "channel"]);
+
+    let total = tracks.len();
+    let mut artifacts = vec![(
+        "audio".to_string(),
+        file_path.to_string(),
+        std::fs::metadata(main_file).map(|m| m.len() as i64).unwrap_or(0),
+    )];
+    for (number, path) in &tracks {
+        let title = chapters
+            .get(number.saturating_sub(1))
+            .filter(|t| !t.is_empty())
+            .cloned()
+            .unwrap_or_else(|| {
+                let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+                stem.split_once(" - ").map(|(_, t)| t.to_string()).unwrap_or(stem)
+            });
+        let mut tags = vec![("title", title), ("track", format!("{}/{}", number, total))];
+        if !album.is_empty() {
+            tags.push(("album", album.clone()));
+        }
+        if !artist.is_empty() {
+            tags.push(("artist", artist.clone()));
+            tags.push(("album_artist", artist.clone()));
+        }
+        if let Err(e) = tag_track(ffmpeg, path, &tags).await {
+            log::warn!("[tracks] Failed to tag {}: {}", path.display(), e);
+        }
+        let size = std::fs::metadata(path).map(|m| m.len() as i64).unwrap_or(0);
+        artifacts.push(("track".to_string(), path.to_string_lossy().to_string(), size));
+    }
+    let info_json = main_file.with_extension("info.json");
+    if info_json.is_file() {
+        let size = std::fs::metadata(&info_json).map(|m| m.len() as i64).unwrap_or(0);
+        artifacts.push(("info".to_string(), info_json.to_string_lossy().to_string(), size));
+    }
+
+    if let Ok(db_lock) = db.lock() {
+        if let Err(e) = db_lock.replace_download_artifacts(download_id, &artifacts) {
+            log::warn!("[tracks] Failed to record tracks of {}: {}", download_id, e);
+        }
+    }
+}
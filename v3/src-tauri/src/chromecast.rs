@@ -0,0 +1,544 @@
+//! Google Cast (Chromecast, Google TV, Cast-enabled speakers and TVs).
+//!
+//! Devices advertise `_googlecast._tcp` over mDNS and are driven over
+//! CASTv2: length-prefixed protobuf `CastMessage`s carrying JSON, on a TLS
+//! connection to port 8009. Casting launches the Default Media Receiver and
+//! hands it the media URL; the receiver keeps playing after we disconnect,
+//! so every command opens its own connection and looks up the running media
+//! session first.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::rustls;
+
+const MDNS_ADDR: &str = "224.0.0.251:5353";
+const SERVICE: &str = "_googlecast._tcp.local";
+const DEFAULT_PORT: u16 = 8009;
+/// The stock receiver app that plays a media URL.
+const DEFAULT_MEDIA_RECEIVER: &str = "CC1AD845";
+
+const NS_CONNECTION: &str = "urn:x-cast:com.google.cast.tp.connection";
+const NS_HEARTBEAT: &str = "urn:x-cast:com.google.cast.tp.heartbeat";
+const NS_RECEIVER: &str = "urn:x-cast:com.google.cast.receiver";
+const NS_MEDIA: &str = "urn:x-cast:com.google.cast.media";
+const SENDER_ID: &str = "sender-0";
+const RECEIVER_ID: &str = "receiver-0";
+
+/// Longest message we accept from a device; status messages are a few KB.
+const MAX_MESSAGE: usize = 64 * 1024;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Launching the receiver app can take a while on older devices.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone)]
+pub struct Device {
+    /// The `id` TXT entry, or the service instance name.
+    pub id: String,
+    pub name: String,
+    pub model: String,
+    pub address: IpAddr,
+    pub port: u16,
+}
+
+/// What the running media session reports, in AVTransport terms so the
+/// frontend and [`crate::cast`] treat both kinds of device alike.
+pub struct Status {
+    pub state: &'static str,
+    pub position: f64,
+    pub duration: f64,
+}
+
+// ───────────────────────────── Discovery ─────────────────────────────
+
+/// Ask for `_googlecast._tcp` over mDNS and collect every device that
+/// answers within `timeout`.
+pub async fn discover(timeout: Duration) -> Result<Vec<Device>, String> {
+    let socket = tokio::net::UdpSocket::bind("0.0.0.0:0")
+        .await
+        .map_err(|e| format!("Failed to open discovery socket: {}", e))?;
+    let target: SocketAddr = MDNS_ADDR.parse().map_err(|e: std::net::AddrParseError| e.to_string())?;
+    let query = mdns_query(SERVICE);
+    // UDP is lossy; ask twice
+    for _ in 0..2 {
+        socket
+            .send_to(&query, target)
+            .await
+            .map_err(|e| format!("mDNS search failed: {}", e))?;
+    }
+
+    let mut records = MdnsRecords::default();
+    let mut buf = [0u8; 9000];
+    let deadline = tokio::time::Instant::now() + timeout;
+    while let Ok(Ok((n, from))) = tokio::time::timeout_at(deadline, socket.recv_from(&mut buf)).await {
+        if let Err(e) = records.read(&buf[..n], from.ip()) {
+            log::debug!("[cast] Bad mDNS response from {}: {}", from, e);
+        }
+    }
+    Ok(records.devices())
+}
+
+/// A standard query with one PTR question. Sent from an ephemeral port, so
+/// responders answer us directly (RFC 6762 §6.7).
+fn mdns_query(name: &str) -> Vec<u8> {
+    let mut packet = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    for label in name.split('.') {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+    // QTYPE PTR, QCLASS IN
+    packet.extend_from_slice(&[0, 12, 0, 1]);
+    packet
+}
+
+#[derive(Default)]
+struct MdnsRecords {
+    /// Service instances, with the address of the packet that named them.
+    instances: Vec<(String, IpAddr)>,
+    /// Instance → (port, target host).
+    srv: HashMap<String, (u16, String)>,
+    txt: HashMap<String, HashMap<String, String>>,
+    hosts: HashMap<String, Ipv4Addr>,
+}
+
+impl MdnsRecords {
+    fn read(&mut self, packet: &[u8], from: IpAddr) -> Result<(), String> {
+        let field = |at: usize| -> Result<u16, String> {
+            packet
+                .get(at..at + 2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .ok_or_else(|| "truncated".to_string())
+        };
+        let questions = field(4)?;
+        let records = field(6)? as usize + field(8)? as usize + field(10)? as usize;
+
+        let mut at = 12;
+        for _ in 0..questions {
+            at = read_name(packet, at)?.1 + 4;
+        }
+        for _ in 0..records {
+            let (name, next) = read_name(packet, at)?;
+            let kind = field(next)?;
+            let length = field(next + 8)? as usize;
+            let data = next + 10;
+            let end = data + length;
+            if end > packet.len() {
+                return Err("truncated".to_string());
+            }
+            match kind {
+                // PTR
+                12 if name.eq_ignore_ascii_case(SERVICE) => {
+                    let instance = read_name(packet, data)?.0;
+                    if !self.instances.iter().any(|(i, _)| *i == instance) {
+                        self.instances.push((instance, from));
+                    }
+                }
+                // SRV
+                33 => {
+                    let port = field(data + 4)?;
+                    let host = read_name(packet, data + 6)?.0;
+                    self.srv.insert(name, (port, host));
+                }
+                // TXT
+                16 => {
+                    let mut entries = HashMap::new();
+                    let mut i = data;
+                    while i < end {
+                        let len = packet[i] as usize;
+                        let entry = packet.get(i + 1..(i + 1 + len).min(end)).unwrap_or_default();
+                        let entry = String::from_utf8_lossy(entry);
+                        if let Some((key, value)) = entry.split_once('=') {
+                            entries.insert(key.to_lowercase(), value.to_string());
+                        }
+                        i += 1 + len;
+                    }
+                    self.txt.insert(name, entries);
+                }
+                // A
+                1 if length == 4 => {
+                    let ip = Ipv4Addr::new(packet[data], packet[data + 1], packet[data + 2], packet[data + 3]);
+                    self.hosts.insert(name.to_lowercase(), ip);
+                }
+                _ => {}
+            }
+            at = end;
+        }
+        Ok(())
+    }
+
+    fn devices(&self) -> Vec<Device> {
+        let mut devices: Vec<Device> = Vec::new();
+        for (instance, from) in &self.instances {
+            let txt = self.txt.get(instance);
+            let entry = |key: &str| txt.and_then(|t| t.get(key)).cloned().unwrap_or_default();
+            let (port, host) = self.srv.get(instance).cloned().unwrap_or((DEFAULT_PORT, String::new()));
+            let address = self
+                .hosts
+                .get(&host.to_lowercase())
+                .map(|ip| IpAddr::V4(*ip))
+                .unwrap_or(*from);
+            let id = Some(entry("id")).filter(|id| !id.is_empty()).unwrap_or_else(|| instance.clone());
+            if devices.iter().any(|d| d.id == id) {
+                continue;
+            }
+            let name = Some(entry("fn"))
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| instance.split('.').next().unwrap_or(instance).to_string());
+            devices.push(Device { id, name, model: entry("md"), address, port });
+        }
+        devices
+    }
+}
+
+/// The (possibly compressed) domain name at `at`, and the offset just past
+/// it in the record.
+fn read_name(packet: &[u8], mut at: usize) -> Result<(String, usize), String> {
+    let mut labels: Vec<String> = Vec::new();
+    let mut end = None;
+    // Bounds pointer loops in malformed packets
+    for _ in 0..128 {
+        let len = *packet.get(at).ok_or("truncated name")? as usize;
+        if len == 0 {
+            return Ok((labels.join("."), end.unwrap_or(at + 1)));
+        }
+        if len & 0xC0 == 0xC0 {
+            let low = *packet.get(at + 1).ok_or("truncated name")? as usize;
+            end.get_or_insert(at + 2);
+            at = ((len & 0x3F) << 8) | low;
+            continue;
+        }
+        let label = packet.get(at + 1..at + 1 + len).ok_or("truncated name")?;
+        labels.push(String::from_utf8_lossy(label).to_string());
+        at += 1 + len;
+    }
+    Err("name too long".to_string())
+}
+
+// ───────────────────────────── CASTv2 ─────────────────────────────
+
+/// Cast devices present self-signed certificates, which can't be checked
+/// against any root. The channel only carries playback commands and the
+/// address came from discovery on the local network.
+#[derive(Debug)]
+struct AcceptDeviceCert(Arc<rustls::crypto::CryptoProvider>);
+
+impl rustls::client::danger::ServerCertVerifier for AcceptDeviceCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::pki_types::CertificateDer<'_>,
+        _intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        _server_name: &rustls::pki_types::ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: rustls::pki_types::UnixTime,
+    ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::danger::ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+struct Connection {
+    stream: TlsStream<TcpStream>,
+    next_request: u64,
+}
+
+impl Connection {
+    async fn open(device: &Device) -> Result<Self, String> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let config = rustls::ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|e| e.to_string())?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptDeviceCert(provider)))
+            .with_no_client_auth();
+        let tcp = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect((device.address, device.port)))
+            .await
+            .map_err(|_| format!("Cast device {} did not answer", device.name))?
+            .map_err(|e| format!("Cast device unreachable: {}", e))?;
+        let stream = tokio_rustls::TlsConnector::from(Arc::new(config))
+            .connect(rustls::pki_types::ServerName::from(device.address), tcp)
+            .await
+            .map_err(|e| format!("Cast device handshake failed: {}", e))?;
+        let mut connection = Self { stream, next_request: 1 };
+        connection.connect_to(RECEIVER_ID).await?;
+        Ok(connection)
+    }
+
+    /// Open the virtual connection to `destination` (the platform or an app).
+    async fn connect_to(&mut self, destination: &str) -> Result<(), String> {
+        self.send(destination, NS_CONNECTION, &serde_json::json!({ "type": "CONNECT" }))
+            .await
+    }
+
+    async fn send(&mut self, destination: &str, namespace: &str, payload: &serde_json::Value) -> Result<(), String> {
+        let message = encode_message(SENDER_ID, destination, namespace, &payload.to_string());
+        let mut frame = (message.len() as u32).to_be_bytes().to_vec();
+        frame.extend_from_slice(&message);
+        self.stream.write_all(&frame).await.map_err(|e| e.to_string())
+    }
+
+    /// The next message for us as (namespace, payload). Heartbeats are
+    /// answered here.
+    async fn recv(&mut self) -> Result<(String, serde_json::Value), String> {
+        loop {
+            let mut length = [0u8; 4];
+            self.stream.read_exact(&mut length).await.map_err(|e| e.to_string())?;
+            let length = u32::from_be_bytes(length) as usize;
+            if length > MAX_MESSAGE {
+                return Err("Cast message too large".to_string());
+            }
+            let mut message = vec![0u8; length];
+            self.stream.read_exact(&mut message).await.map_err(|e| e.to_string())?;
+            let (source, namespace, payload) = decode_message(&message)?;
+            let payload: serde_json::Value = serde_json::from_str(&payload).unwrap_or_default();
+            if namespace == NS_HEARTBEAT && payload["type"] == "PING" {
+                self.send(&source, NS_HEARTBEAT, &serde_json::json!({ "type": "PONG" }))
+                    .await?;
+                continue;
+            }
+            return Ok((namespace, payload));
+        }
+    }
+
+    /// Send `payload` with a fresh `requestId` and wait for the reply that
+    /// carries it.
+    async fn request(
+        &mut self,
+        destination: &str,
+        namespace: &str,
+        mut payload: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let request_id = self.next_request;
+        self.next_request += 1;
+        payload["requestId"] = serde_json::json!(request_id);
+        self.send(destination, namespace, &payload).await?;
+        loop {
+            let (reply_namespace, reply) = self.recv().await?;
+            if reply_namespace == namespace && reply["requestId"].as_u64() == Some(request_id) {
+                return Ok(reply);
+            }
+        }
+    }
+
+    /// Transport id of the running media app, if there is one.
+    async fn media_app(&mut self) -> Result<Option<String>, String> {
+        let status = self
+            .request(RECEIVER_ID, NS_RECEIVER, serde_json::json!({ "type": "GET_STATUS" }))
+            .await?;
+        Ok(media_transport(&status))
+    }
+
+    /// The running media app and its current media session.
+    async fn media_session(&mut self) -> Result<Option<(String, serde_json::Value)>, String> {
+        let Some(transport) = self.media_app().await? else { return Ok(None) };
+        self.connect_to(&transport).await?;
+        let status = self
+            .request(&transport, NS_MEDIA, serde_json::json!({ "type": "GET_STATUS" }))
+            .await?;
+        let session = status["status"].as_array().and_then(|s| s.first()).cloned();
+        Ok(session.map(|session| (transport, session)))
+    }
+}
+
+/// Transport id of the first running app that takes media commands.
+fn media_transport(receiver_status: &serde_json::Value) -> Option<String> {
+    receiver_status["status"]["applications"]
+        .as_array()?
+        .iter()
+        .find(|app| {
+            app["namespaces"]
+                .as_array()
+                .is_some_and(|ns| ns.iter().any(|n| n["name"] == NS_MEDIA))
+        })
+        .and_then(|app| app["transportId"].as_str().map(String::from))
+}
+
+/// Launch the Default Media Receiver on `device` and have it play `url`.
+pub async fn load(device: &Device, url: &str, content_type: &str, title: &str) -> Result<(), String> {
+    tokio::time::timeout(COMMAND_TIMEOUT, async {
+        let mut connection = Connection::open(device).await?;
+
+        let launch = serde_json::json!({ "type": "LAUNCH", "appId": DEFAULT_MEDIA_RECEIVER });
+        let reply = connection.request(RECEIVER_ID, NS_RECEIVER, launch).await?;
+        if reply["type"] == "LAUNCH_ERROR" {
+            return Err(format!(
+                "Cast device refused to start the player: {}",
+                reply["reason"].as_str().unwrap_or("unknown reason")
+            ));
+        }
+        // The reply may come before the app is up; later statuses follow
+        let transport = loop {
+            if let Some(transport) = media_transport(&reply) {
+                break transport;
+            }
+            let (namespace, status) = connection.recv().await?;
+            if namespace == NS_RECEIVER {
+                if let Some(transport) = media_transport(&status) {
+                    break transport;
+                }
+            }
+        };
+
+        connection.connect_to(&transport).await?;
+        let load = serde_json::json!({
+            "type": "LOAD",
+            "autoplay": true,
+            "currentTime": 0,
+            "media": {
+                "contentId": url,
+                "streamType": "BUFFERED",
+                "contentType": content_type,
+                "metadata": { "metadataType": 0, "title": title },
+            },
+        });
+        let reply = connection.request(&transport, NS_MEDIA, load).await?;
+        match reply["type"].as_str() {
+            Some("MEDIA_STATUS") => Ok(()),
+            Some(other) => Err(format!("Cast device could not play the media ({})", other)),
+            None => Err("Cast device sent an unexpected reply".to_string()),
+        }
+    })
+    .await
+    .map_err(|_| format!("Cast device {} did not respond in time", device.name))?
+}
+
+/// `PLAY`, `PAUSE`, `STOP` or (with `position`) `SEEK` the current media.
+pub async fn control(device: &Device, command: &str, position: Option<f64>) -> Result<(), String> {
+    tokio::time::timeout(COMMAND_TIMEOUT, async {
+        let mut connection = Connection::open(device).await?;
+        let Some((transport, session)) = connection.media_session().await? else {
+            return if command == "STOP" { Ok(()) } else { Err("Nothing is playing on this device".to_string()) };
+        };
+        let mut payload = serde_json::json!({
+            "type": command,
+            "mediaSessionId": session["mediaSessionId"],
+        });
+        if let Some(position) = position {
+            payload["currentTime"] = serde_json::json!(position.max(0.0));
+        }
+        let reply = connection.request(&transport, NS_MEDIA, payload).await?;
+        match reply["type"].as_str() {
+            Some("MEDIA_STATUS") => Ok(()),
+            other => Err(format!("{} failed: {}", command, other.unwrap_or("no status"))),
+        }
+    })
+    .await
+    .map_err(|_| format!("Cast device {} did not respond in time", device.name))?
+}
+
+pub async fn status(device: &Device) -> Result<Status, String> {
+    tokio::time::timeout(COMMAND_TIMEOUT, async {
+        let mut connection = Connection::open(device).await?;
+        let Some((_, session)) = connection.media_session().await? else {
+            return Ok(Status { state: "NO_MEDIA_PRESENT", position: 0.0, duration: 0.0 });
+        };
+        let state = match session["playerState"].as_str() {
+            Some("PLAYING") => "PLAYING",
+            Some("PAUSED") => "PAUSED_PLAYBACK",
+            Some("BUFFERING") | Some("LOADING") => "TRANSITIONING",
+            _ => "STOPPED",
+        };
+        Ok(Status {
+            state,
+            position: session["currentTime"].as_f64().unwrap_or(0.0),
+            duration: session["media"]["duration"].as_f64().unwrap_or(0.0),
+        })
+    })
+    .await
+    .map_err(|_| format!("Cast device {} did not respond in time", device.name))?
+}
+
+// ───────────────────────────── CastMessage ─────────────────────────────
+
+/// Protobuf `CastMessage` with a string payload: protocol_version (1),
+/// source_id (2), destination_id (3), namespace (4), payload_type (5),
+/// payload_utf8 (6).
+fn encode_message(source: &str, destination: &str, namespace: &str, payload: &str) -> Vec<u8> {
+    let mut message = vec![0x08, 0x00];
+    for (tag, value) in [(0x12, source), (0x1a, destination), (0x22, namespace)] {
+        message.push(tag);
+        push_varint(&mut message, value.len() as u64);
+        message.extend_from_slice(value.as_bytes());
+    }
+    message.extend_from_slice(&[0x28, 0x00, 0x32]);
+    push_varint(&mut message, payload.len() as u64);
+    message.extend_from_slice(payload.as_bytes());
+    message
+}
+
+/// (source_id, namespace, payload_utf8) of a `CastMessage`.
+fn decode_message(message: &[u8]) -> Result<(String, String, String), String> {
+    let (mut source, mut namespace, mut payload) = (String::new(), String::new(), String::new());
+    let mut at = 0;
+    while at < message.len() {
+        let key = read_varint(message, &mut at)?;
+        match key & 7 {
+            0 => {
+                read_varint(message, &mut at)?;
+            }
+            1 => at += 8,
+            5 => at += 4,
+            2 => {
+                let len = read_varint(message, &mut at)? as usize;
+                let value = message.get(at..at + len).ok_or("truncated cast message")?;
+                let value = String::from_utf8_lossy(value).to_string();
+                match key >> 3 {
+                    2 => source = value,
+                    4 => namespace = value,
+                    6 => payload = value,
+                    _ => {}
+                }
+                at += len;
+            }
+            _ => return Err("unsupported cast message field".to_string()),
+        }
+    }
+    Ok((source, namespace, payload))
+}
+
+fn push_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(data: &[u8], at: &mut usize) -> Result<u64, String> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*at).ok_or("truncated varint")?;
+        *at += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("varint too long".to_string())
+}
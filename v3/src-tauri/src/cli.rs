@@ -0,0 +1,465 @@
+//! Command-line companion mode.
+//!
+//! The same binary that runs the app also understands a few subcommands
+//! that work on its database without opening a window, for servers and
+//! scripts:
+//!
+//! ```text
+//! ytdl add <url> [--format <id>] [--mode video|audio|shorts|podcast]
+//! ytdl list [--status <status>]
+//! ytdl download <url> [--format <id>] [--mode <mode>]
+//! ytdl run [--once]        (also: ytdl --headless)
+//! ```
+//!
+//! `add` only queues; queued downloads are started by `run`, which works
+//! through the queue and, unless `--once` is given, keeps checking RSS
+//! feeds on the configured interval and queues new items of feeds that
+//! have auto-download on.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::commands::{custom_ytdlp_flags, validate_url};
+use crate::db::Database;
+use crate::download::{self, MediaKind, QualityPreference};
+use crate::settings::AppSettings;
+use crate::{extractor_args, proxy, rate_limit, remux, rss};
+
+/// Must match `identifier` in tauri.conf.json so the CLI and the app share
+/// one database.
+const APP_IDENTIFIER: &str = "com.ytdl.desktop";
+/// How often `run` looks for newly queued downloads.
+const QUEUE_POLL: Duration = Duration::from_secs(30);
+
+const USAGE: &str = "Usage:
+  ytdl                          start the app
+  ytdl add <url> [--format <id>] [--mode <mode>]
+                                queue a download (started by `ytdl run`)
+  ytdl list [--status <status>] list downloads
+  ytdl download <url> [--format <id>] [--mode <mode>]
+                                download now, printing progress
+  ytdl run [--once]             process the queue without a window and keep
+                                checking RSS feeds (--once: drain and exit)
+  ytdl --headless               same as `ytdl run`
+
+Modes: video, audio, shorts, podcast";
+
+/// Handle CLI arguments. Returns `None` when the app should start normally
+/// (no subcommand, or arguments meant for the app such as a URL to open),
+/// otherwise the process exit code.
+pub fn run_from_args(args: &[String]) -> Option<i32> {
+    if crate::native_host::is_native_messaging_launch(args) {
+        let _ = env_logger::try_init();
+        return Some(crate::native_host::run());
+    }
+    let command = args.get(1)?.as_str();
+    let rest = &args[2..];
+    let _ = env_logger::try_init();
+    let result = match command {
+        "add" => with_db(|db| add(db, rest)),
+        "list" => with_db(|db| list(db, rest)),
+        "download" => block_on(download_now(rest)),
+        "run" | "--headless" => block_on(run(rest.iter().any(|a| a == "--once"))),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => return None,
+    };
+    Some(match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("ytdl: {}", e);
+            1
+        }
+    })
+}
+
+pub(crate) fn app_data_dir() -> Result<PathBuf, String> {
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| "Could not determine the app data directory".to_string())
+}
+
+pub(crate) fn open_db() -> Result<Database, String> {
+    let dir = app_data_dir()?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let db = Database::new(&dir.join("ytdl.db")).map_err(|e| e.to_string())?;
+    db.migrate().map_err(|e| e.to_string())?;
+    proxy::load_from_db(&db);
+    extractor_args::load_from_db(&db);
+    remux::load_from_db(&db);
+    rate_limit::load_from_db(&db);
+    Ok(db)
+}
+
+fn with_db(f: impl FnOnce(&Database) -> Result<(), String>) -> Result<(), String> {
+    f(&open_db()?)
+}
+
+fn block_on(future: impl std::future::Future<Output = Result<(), String>>) -> Result<(), String> {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string())?
+        .block_on(future)
+}
+
+/// `<url> [--format <id>] [--mode <mode>]`
+struct DownloadArgs {
+    url: String,
+    format_id: Option<String>,
+    kind: Option<MediaKind>,
+}
+
+fn parse_download_args(args: &[String]) -> Result<DownloadArgs, String> {
+    let mut url = None;
+    let mut format_id = None;
+    let mut kind = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" | "-f" => {
+                format_id = Some(iter.next().ok_or("--format needs a value")?.clone());
+            }
+            "--mode" | "-m" => {
+                let mode = iter.next().ok_or("--mode needs a value")?;
+                kind = Some(MediaKind::from_name(mode).ok_or_else(|| format!("Unknown mode '{}'", mode))?);
+            }
+            other if other.starts_with('-') => return Err(format!("Unknown option '{}'\n\n{}", other, USAGE)),
+            other if url.is_none() => url = Some(other.to_string()),
+            other => return Err(format!("Unexpected argument '{}'", other)),
+        }
+    }
+    let url = url.ok_or_else(|| format!("Missing URL\n\n{}", USAGE))?;
+    validate_url(&url)?;
+    Ok(DownloadArgs { url, format_id, kind })
+}
+
+fn add(db: &Database, args: &[String]) -> Result<(), String> {
+    let request = parse_download_args(args)?;
+    let format_id = request.format_id.unwrap_or_default();
+    if let Some(status) = db.download_exists_by_url(&request.url, &format_id, "").map_err(|e| e.to_string())? {
+        return Err(format!("This video with the same quality is already {}", status));
+    }
+    let id = uuid::Uuid::new_v4().to_string();
+    db.queue_download(&id, &request.url, &format_id, "cli")
+        .map_err(|e| e.to_string())?;
+    if let Some(kind) = request.kind {
+        db.set_download_mode(&id, kind.name()).map_err(|e| e.to_string())?;
+    }
+    println!("{}", id);
+    Ok(())
+}
+
+fn list(db: &Database, args: &[String]) -> Result<(), String> {
+    let status = match args {
+        [] => None,
+        [flag, value] if flag == "--status" => Some(value.as_str()),
+        _ => return Err(format!("Usage: ytdl list [--status <status>]\n\n{}", USAGE)),
+    };
+    let downloads = db.get_downloads().map_err(|e| e.to_string())?;
+    for d in downloads
+        .iter()
+        .filter(|d| status.map_or(true, |s| d["status"].as_str() == Some(s)))
+    {
+        println!(
+            "{}\t{}\t{:.0}%\t{}",
+            d["id"].as_str().unwrap_or(""),
+            d["status"].as_str().unwrap_or(""),
+            d["progress"].as_f64().unwrap_or(0.0),
+            d["title"].as_str().filter(|t| !t.is_empty()).or(d["url"].as_str()).unwrap_or(""),
+        );
+    }
+    Ok(())
+}
+
+/// Where the tools live: env var, then the settings override, then the
+/// app's own binaries folder, then PATH (same order as the app).
+fn tool_path(db: &Database, env: &str, setting: &str, name: &str) -> String {
+    let exe = if cfg!(windows) { format!("{}.exe", name) } else { name.to_string() };
+    let candidates = [
+        std::env::var(env).ok(),
+        db.get_setting(setting).unwrap_or(None),
+        app_data_dir()
+            .ok()
+            .map(|dir| dir.join("binaries").join(&exe).to_string_lossy().to_string()),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .map(|p| p.trim().to_string())
+        .find(|p| !p.is_empty() && PathBuf::from(p).is_file())
+        .unwrap_or(exe)
+}
+
+/// The configured folder for `kind`, falling back to `download_path`.
+fn download_folder(settings: &AppSettings, kind: MediaKind) -> String {
+    let folder = match kind {
+        MediaKind::Video => &settings.video_download_path,
+        MediaKind::Audio => &settings.audio_download_path,
+        MediaKind::Short => &settings.shorts_download_path,
+        MediaKind::Podcast => &settings.podcast_download_path,
+    };
+    if folder.is_empty() { settings.download_path.clone() } else { folder.clone() }
+}
+
+/// Run one download to completion, recording it in the database under
+/// `id` (which must already exist) and printing progress to stderr.
+/// `output_dir` overrides the folder picked from the settings, e.g. a
+/// feed's folder template.
+async fn run_one(
+    db: &Arc<Mutex<Database>>,
+    id: &str,
+    url: &str,
+    format_id: Option<&str>,
+    kind: Option<MediaKind>,
+    output_dir: Option<&str>,
+) -> Result<String, String> {
+    let (ytdlp, ffmpeg, settings, quality, cookie_args) = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        let quality = QualityPreference::from_settings(|key| db.get_setting(key).unwrap_or(None).unwrap_or_default());
+        let cookie_args = db
+            .get_download(id)
+            .unwrap_or(None)
+            .and_then(|d| d["cookieProfile"].as_str().map(String::from))
+            .and_then(|profile| crate::cookie_profiles::stored_args(&db, &profile));
+        (
+            tool_path(&db, "YTDLP_PATH", "ytdlp_path", "yt-dlp"),
+            tool_path(&db, "FFMPEG_PATH", "ffmpeg_path", "ffmpeg"),
+            AppSettings::load(&db),
+            quality,
+            cookie_args,
+        )
+    };
+
+    let kind = kind.unwrap_or_else(|| MediaKind::detect(url, format_id, &[]));
+    let output_dir = match output_dir.filter(|dir| !dir.is_empty()) {
+        Some(dir) => dir.to_string(),
+        None => download_folder(&settings, kind),
+    };
+    std::fs::create_dir_all(&output_dir).map_err(|e| format!("Failed to create {}: {}", output_dir, e))?;
+
+    let mut extra_args = custom_ytdlp_flags(&settings.ytdlp_flags);
+    if settings.embed_thumbnail {
+        extra_args.push("--embed-thumbnail".to_string());
+    }
+    if settings.embed_metadata {
+        extra_args.push("--embed-metadata".to_string());
+    }
+    if let Some(args) = cookie_args {
+        extra_args.extend(args);
+    } else if settings.browser_cookies != "none" && !settings.browser_cookies.is_empty() {
+        extra_args.push("--cookies-from-browser".to_string());
+        extra_args.push(settings.browser_cookies.clone());
+    }
+
+    if let Ok(db) = db.lock() {
+        let _ = db.update_download_status(id, "downloading");
+    }
+
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::channel::<download::DownloadProgress>(32);
+    let (_cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
+    let db_for_progress = db.clone();
+    let progress_task = tokio::spawn(async move {
+        while let Some(p) = progress_rx.recv().await {
+            eprint!("\r{:>5.1}%  {:>12}  ETA {:<10}", p.progress, p.speed, p.eta);
+            if let Ok(db) = db_for_progress.lock() {
+                let _ = db.update_download_progress(&p.id, p.progress, &p.speed, &p.eta);
+            }
+        }
+        eprintln!();
+    });
+
+    let result = download::run_download(
+        &ytdlp,
+        &ffmpeg,
+        url,
+        &output_dir,
+        format_id,
+        &quality,
+        &extra_args,
+        progress_tx,
+        cancel_rx,
+        id.to_string(),
+        None,
+        app_data_dir().ok().map(|dir| download::download_log_path(&dir, id)),
+    )
+    .await;
+    let _ = progress_task.await;
+
+    let db = db.lock().map_err(|e| e.to_string())?;
+    match result {
+        Ok(file_path) => {
+            let file_size = std::fs::metadata(&file_path).map(|m| m.len() as i64).unwrap_or(0);
+            let _ = db.update_download_complete(id, &file_path, file_size);
+            Ok(file_path)
+        }
+        Err(e) => {
+            let _ = db.update_download_error(id, &e.to_string());
+            Err(e.to_string())
+        }
+    }
+}
+
+async fn download_now(args: &[String]) -> Result<(), String> {
+    let request = parse_download_args(args)?;
+    let db = Arc::new(Mutex::new(open_db()?));
+    let id = uuid::Uuid::new_v4().to_string();
+    {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        db.queue_download(&id, &request.url, request.format_id.as_deref().unwrap_or(""), "cli")
+            .map_err(|e| e.to_string())?;
+    }
+    let path = run_one(&db, &id, &request.url, request.format_id.as_deref(), request.kind, None).await?;
+    println!("{}", path);
+    Ok(())
+}
+
+/// Start every queued download, one at a time. Returns how many ran.
+async fn drain_queue(db: &Arc<Mutex<Database>>) -> Result<usize, String> {
+    let mut processed = 0;
+    loop {
+        let next = {
+            let db = db.lock().map_err(|e| e.to_string())?;
+            let downloads = db.get_downloads().map_err(|e| e.to_string())?;
+            // Highest priority first, oldest first within a priority
+            downloads
+                .into_iter()
+                .rev()
+                .filter(|d| d["status"].as_str() == Some("queued"))
+                .min_by_key(|d| std::cmp::Reverse(d["priority"].as_i64().unwrap_or(0)))
+        };
+        let Some(next) = next else { return Ok(processed) };
+
+        let id = next["id"].as_str().unwrap_or_default().to_string();
+        let url = next["url"].as_str().unwrap_or_default().to_string();
+        let format_id = next["formatId"].as_str().filter(|f| !f.is_empty()).map(String::from);
+        // Set by `add --mode`; the app's own modes aren't kinds and are ignored
+        let kind = next["mode"].as_str().and_then(MediaKind::from_name);
+        let output_dir = next["outputDir"].as_str().map(String::from);
+        println!("Downloading {}", url);
+        match run_one(db, &id, &url, format_id.as_deref(), kind, output_dir.as_deref()).await {
+            Ok(path) => println!("Saved {}", path),
+            Err(e) => {
+                eprintln!("Failed {}: {}", url, e);
+                // Make sure it leaves the queue even if it failed before starting
+                if let Ok(db) = db.lock() {
+                    let _ = db.update_download_error(&id, &e);
+                }
+            }
+        }
+        processed += 1;
+    }
+}
+
+/// Fetch every feed, store new items and queue those of auto-download
+/// feeds. Returns how many downloads were queued.
+async fn check_feeds(db: &Arc<Mutex<Database>>) -> Result<usize, String> {
+    let (feeds, policy) = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        (db.get_feeds().map_err(|e| e.to_string())?, rss::FeedPolicy::load(&db))
+    };
+    let mut queued = 0;
+    for feed in feeds {
+        let auto_download = feed.auto_download;
+        let folder_template = feed.folder_template;
+        let cookie_profile = feed.cookie_profile;
+        let channel_name = feed.channel_name;
+        let feed_title = feed.title;
+        let feed_id = feed.id;
+        let feed_url = feed.url;
+        if feed_url.is_empty() {
+            continue;
+        }
+        if let Err(e) = policy.check(&feed_url) {
+            log::warn!("[cli] Skipping feed {}: {}", feed_url, e);
+            continue;
+        }
+        let items = match rss::normalize_feed_url(&feed_url).await {
+            Ok(url) => rss::fetch_feed_items(&url).await,
+            Err(e) => Err(e),
+        };
+        let (_, mut items) = match items {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!("[cli] Failed to fetch feed {}: {}", feed_url, e);
+                continue;
+            }
+        };
+        // Without yt-dlp only the RSS feed's own shorts/videos split is known
+        if rss::extract_channel_id_from_feed_url(&feed_url).is_some() {
+            rss::keep_channel_tabs(&mut items, &rss::parse_channel_tabs(&feed.tabs));
+        }
+
+        let db = db.lock().map_err(|e| e.to_string())?;
+        let _ = db.update_feed_last_checked(&feed_id);
+        let new_ids = match db.insert_feed_items(&feed_id, &items) {
+            Ok(ids) => ids,
+            Err(e) => {
+                log::warn!("[cli] Failed to save items for feed {}: {}", feed_url, e);
+                continue;
+            }
+        };
+        if !auto_download {
+            continue;
+        }
+        let batch_id = uuid::Uuid::new_v4().to_string();
+        let base_dir = download_folder(&AppSettings::load(&db), MediaKind::Video);
+        for item in items.iter().filter(|item| new_ids.contains(&item.id)) {
+            let id = uuid::Uuid::new_v4().to_string();
+            if db.queue_download(&id, &item.url, "", "rss").is_ok() {
+                let _ = db.set_download_batch(&id, &batch_id);
+                if !cookie_profile.is_empty() {
+                    let _ = db.set_download_cookie_profile(&id, &cookie_profile);
+                }
+                if let Some(folder) = rss::render_folder_template(&folder_template, &channel_name, &feed_title, item) {
+                    let dir = Path::new(&base_dir).join(folder);
+                    let _ = db.set_download_output_dir(&id, &dir.to_string_lossy());
+                }
+                let _ = db.update_feed_item_downloaded(&item.id, true);
+                queued += 1;
+            }
+        }
+    }
+    Ok(queued)
+}
+
+async fn run(once: bool) -> Result<(), String> {
+    let db = Arc::new(Mutex::new(open_db()?));
+    if once {
+        let count = drain_queue(&db).await?;
+        println!("Processed {} download(s)", count);
+        return Ok(());
+    }
+
+    println!("YTDL running headless (Ctrl+C to stop)");
+    let mut last_feed_check: Option<std::time::Instant> = None;
+    loop {
+        let interval = {
+            let db = db.lock().map_err(|e| e.to_string())?;
+            AppSettings::load(&db).rss_check_interval
+        };
+        let feeds_due = interval > 0
+            && last_feed_check.map_or(true, |at| at.elapsed() >= Duration::from_secs(interval * 60));
+        if feeds_due {
+            last_feed_check = Some(std::time::Instant::now());
+            match check_feeds(&db).await {
+                Ok(0) => {}
+                Ok(n) => println!("Queued {} new feed item(s)", n),
+                Err(e) => log::warn!("[cli] Feed check failed: {}", e),
+            }
+        }
+
+        drain_queue(&db).await?;
+
+        tokio::select! {
+            _ = tokio::time::sleep(QUEUE_POLL) => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!("Stopping");
+                return Ok(());
+            }
+        }
+    }
+}
@@ -1,15 +1,20 @@
 use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter, Manager, State};
 
-use crate::db::Database;
+use crate::db::{Database, FeedItem, FeedSummary};
 use crate::download::{self, DownloadManager, DownloadProgress};
+use crate::error::AppError;
+use crate::notifications;
+use crate::webhooks;
 use crate::rss;
+use crate::settings::AppSettings;
 
 const RSS_SYNC_BATCH_SIZE: usize = 200;
 
 /// Validates a URL for security (SSRF protection).
-/// Resolves the hostname and checks against RFC 1918, loopback, and link-local ranges
-/// to prevent bypasses via decimal IPs, IPv6-mapped addresses, or DNS rebinding.
+/// The host is taken from the parsed URL (which normalizes decimal, hex and
+/// octal IPv4 forms), then resolved; every address is checked against the
+/// loopback, private, link-local and other reserved ranges.
 pub(crate) fn validate_url(url: &str) -> Result<(), String> {
     if url.trim().is_empty() {
         return Err("URL cannot be empty".to_string());
@@ -18,45 +23,68 @@ pub(crate) fn validate_url(url: &str) -> Result<(), String> {
         return Err("URL is too short".to_string());
     }
 
-    let trimmed = url.trim().to_lowercase();
-    if !trimmed.starts_with("http://") && !trimmed.starts_with("https://") {
+    let parsed = url::Url::parse(url.trim()).map_err(|e| format!("Invalid URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
         return Err("URL must start with http:// or https://".to_string());
     }
-
-    // Parse the URL properly
-    let parsed = url::Url::parse(url.trim()).map_err(|e| format!("Invalid URL: {}", e))?;
-    let host_str = parsed.host_str().ok_or("URL has no host")?;
-
-    // Direct pattern checks for common bypass strings
-    let blocked_patterns = ["localhost", "file://"];
-    for pattern in blocked_patterns {
-        if trimmed.contains(pattern) {
-            return Err(format!("URL contains blocked pattern: {}", pattern));
-        }
-    }
-
-    // Resolve hostname to IP addresses and validate each one
-    let port = parsed.port_or_known_default().unwrap_or(80);
-    let resolve_target = format!("{}:{}", host_str, port);
-    if let Ok(addrs) = std::net::ToSocketAddrs::to_socket_addrs(&resolve_target.as_str()) {
-        for addr in addrs {
-            let ip = addr.ip();
-            if is_private_or_reserved_ip(&ip) {
-                return Err(format!(
-                    "URL resolves to private/reserved IP address ({}). This is blocked for security.",
-                    ip
-                ));
+    let host = parsed.host().ok_or("URL has no host")?;
+    check_host(&host)?;
+
+    if let url::Host::Domain(domain) = host {
+        // Resolution failures are left to the downloader to report; there is
+        // nothing to connect to either way
+        let port = parsed.port_or_known_default().unwrap_or(80);
+        if let Ok(addrs) = std::net::ToSocketAddrs::to_socket_addrs(&(domain, port)) {
+            for addr in addrs {
+                let ip = addr.ip();
+                if is_private_or_reserved_ip(&ip) {
+                    return Err(format!(
+                        "URL resolves to private/reserved IP address ({}). This is blocked for security.",
+                        ip
+                    ));
+                }
             }
         }
     }
-    // If DNS resolution fails, do a textual check as fallback
-    else {
-        check_host_textual(host_str)?;
-    }
 
     Ok(())
 }
 
+/// Checks that need no DNS lookup: IP literals and `localhost` names.
+fn check_host(host: &url::Host<&str>) -> Result<(), String> {
+    let blocked = match host {
+        url::Host::Ipv4(v4) => is_private_or_reserved_ip(&std::net::IpAddr::V4(*v4)),
+        url::Host::Ipv6(v6) => is_private_or_reserved_ip(&std::net::IpAddr::V6(*v6)),
+        url::Host::Domain(domain) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost")
+        }
+    };
+    if blocked {
+        return Err(format!("URL host '{}' is blocked", host));
+    }
+    Ok(())
+}
+
+/// Redirect policy for requests to user-supplied URLs: a redirect may not
+/// lead to a blocked host. Only IP literals and `localhost` are checked
+/// since the policy runs synchronously inside the client.
+pub(crate) fn ssrf_redirect_policy() -> reqwest::redirect::Policy {
+    reqwest::redirect::Policy::custom(|attempt| {
+        if attempt.previous().len() >= 10 {
+            return attempt.error("too many redirects");
+        }
+        let allowed = matches!(attempt.url().scheme(), "http" | "https")
+            && attempt.url().host().map(|h| check_host(&h).is_ok()).unwrap_or(false);
+        if allowed {
+            attempt.follow()
+        } else {
+            let reason = format!("redirect to blocked URL {}", attempt.url());
+            attempt.error(reason)
+        }
+    })
+}
+
 /// Check if an IP address is private, loopback, link-local, or otherwise reserved
 fn is_private_or_reserved_ip(ip: &std::net::IpAddr) -> bool {
     match ip {
@@ -66,19 +94,24 @@ fn is_private_or_reserved_ip(ip: &std::net::IpAddr) -> bool {
             || v4.is_link_local()      // 169.254/16
             || v4.is_broadcast()       // 255.255.255.255
             || v4.is_unspecified()     // 0.0.0.0
+            || v4.is_multicast()       // 224/4
+            || v4.octets()[0] == 0     // 0/8
             || v4.octets()[0] == 100 && (v4.octets()[1] & 0xC0) == 64  // 100.64/10 (CGNAT)
             || v4.octets()[0] == 198 && (v4.octets()[1] & 0xFE) == 18  // 198.18/15 (benchmarking)
+            || v4.octets()[0] >= 240   // 240/4 (reserved)
         }
         std::net::IpAddr::V6(v6) => {
             v6.is_loopback()           // ::1
             || v6.is_unspecified()     // ::
+            || v6.is_multicast()       // ff00::/8
             // IPv6 unique local (fc00::/7)
             || (v6.segments()[0] & 0xFE00) == 0xFC00
             // IPv6 link-local (fe80::/10)
             || (v6.segments()[0] & 0xFFC0) == 0xFE80
-            // IPv4-mapped IPv6 (::ffff:x.x.x.x) — check the embedded IPv4
+            // IPv4-mapped (::ffff:x.x.x.x) and IPv4-compatible (::x.x.x.x)
+            // addresses — check the embedded IPv4
             || {
-                if let Some(v4) = v6.to_ipv4_mapped() {
+                if let Some(v4) = v6.to_ipv4() {
                     is_private_or_reserved_ip(&std::net::IpAddr::V4(v4))
                 } else {
                     false
@@ -88,31 +121,64 @@ fn is_private_or_reserved_ip(ip: &std::net::IpAddr) -> bool {
     }
 }
 
-/// Textual fallback for host validation when DNS resolution fails
-fn check_host_textual(host: &str) -> Result<(), String> {
-    let h = host.to_lowercase();
-    let blocked = [
-        "localhost", "127.0.0.1", "0.0.0.0", "::1", "[::1]",
-    ];
-    for b in blocked {
-        if h == b || h.starts_with(&format!("{}.", b)) {
-            return Err(format!("URL host '{}' is blocked", host));
+/// Split the `ytdlp_flags` setting into arguments, shell style: whitespace
+/// separates arguments unless quoted with `'` or `"`, and a backslash
+/// escapes a following quote or space. Other backslashes are kept as-is so
+/// Windows paths survive.
+pub(crate) fn split_ytdlp_flags(input: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') if matches!(chars.peek(), Some('"') | Some('\\')) => {
+                current.extend(chars.next());
+            }
+            (Some(_), c) => current.push(c),
+            (None, '\'') | (None, '"') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, '\\') if matches!(chars.peek(), Some(n) if n.is_whitespace() || *n == '\'' || *n == '"') => {
+                current.extend(chars.next());
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
         }
     }
-    // Block known private ranges textually
-    if h.starts_with("10.") || h.starts_with("192.168.") || h.starts_with("169.254.") {
-        return Err(format!("URL host '{}' is in a private IP range", host));
+
+    if let Some(q) = quote {
+        return Err(format!("Unterminated {} quote in yt-dlp flags", q));
     }
-    if h.starts_with("172.") {
-        if let Some(second) = h.split('.').nth(1) {
-            if let Ok(octet) = second.parse::<u8>() {
-                if (16..=31).contains(&octet) {
-                    return Err(format!("URL host '{}' is in a private IP range", host));
-                }
-            }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// Tokenize and sanitize the `ytdlp_flags` setting. Unparseable input is
+/// ignored (the setting is checked on save, so this only hits old values).
+pub(crate) fn custom_ytdlp_flags(setting: &str) -> Vec<String> {
+    match split_ytdlp_flags(setting) {
+        Ok(raw) => sanitize_ytdlp_flags(&raw),
+        Err(e) => {
+            log::warn!("Ignoring custom yt-dlp flags: {}", e);
+            Vec::new()
         }
     }
-    Ok(())
 }
 
 /// Sanitize yt-dlp flags using an ALLOWLIST approach.
@@ -120,19 +186,34 @@ fn check_host_textual(host: &str) -> Result<(), String> {
 /// data exfiltration via flags like --exec, --proxy, --cookies-from-browser,
 /// --print-to-file, --load-info-json, etc.
 pub(crate) fn sanitize_ytdlp_flags(flags: &[String]) -> Vec<String> {
-    // Allowlist of safe yt-dlp flags (prefix-matched)
-    let allowed_prefixes: &[&str] = &[
+    // Allowlist of safe yt-dlp flags that take no value
+    let allowed_switches: &[&str] = &[
         // Format selection
-        "-f", "--format", "--format-sort", "--merge-output-format",
-        "--audio-format", "--audio-quality", "--video-multistreams",
-        "--audio-multistreams", "--prefer-free-formats",
-        // Output template
-        "-o", "--output", "--restrict-filenames", "--no-overwrites",
+        "--video-multistreams", "--audio-multistreams", "--prefer-free-formats",
+        // Output naming (the output template itself is set by the app
+        // and kept inside the download folder)
+        "--restrict-filenames", "--no-overwrites",
         "--continue", "--no-continue",
         // Metadata/embedding
         "--embed-thumbnail", "--embed-metadata", "--embed-subs",
         "--embed-chapters", "--embed-info-json",
         "--write-thumbnail", "--write-subs", "--write-auto-subs",
+        // Extraction
+        "--extract-audio", "-x", "--keep-video",
+        // Playlist
+        "--no-playlist", "--yes-playlist",
+        // Misc safe
+        "--no-warnings", "--newline", "--progress",
+        "--no-mtime", "--geo-bypass",
+        "--no-sponsorblock", "--no-match-filter",
+    ];
+    // Allowlist of safe yt-dlp flags that take exactly one value, given as
+    // the next argument or after `=`
+    let allowed_options: &[&str] = &[
+        // Format selection
+        "-f", "--format", "--format-sort", "--merge-output-format",
+        "--audio-format", "--audio-quality",
+        // Metadata/embedding
         "--sub-lang", "--sub-format",
         // Download behavior
         "--retries", "--fragment-retries", "--buffer-size",
@@ -140,35 +221,51 @@ pub(crate) fn sanitize_ytdlp_flags(flags: &[String]) -> Vec<String> {
         "--limit-rate", "--throttled-rate",
         "--sleep-interval", "--max-sleep-interval",
         // Extraction
-        "--extract-audio", "-x", "--keep-video",
         "--recode-video", "--remux-video",
         // Playlist
         "--playlist-start", "--playlist-end", "--playlist-items",
-        "--no-playlist", "--yes-playlist",
         // Network (safe subset)
         "--socket-timeout", "--source-address",
         // Misc safe
-        "--no-warnings", "--newline", "--progress",
-        "--no-mtime", "--geo-bypass",
         "--sponsorblock-mark", "--sponsorblock-remove",
-        "--no-sponsorblock",
-        "--age-limit",
-        "--match-filter", "--no-match-filter",
+        "--age-limit", "--match-filter",
     ];
 
-    flags
-        .iter()
-        .filter(|f| {
-            let lower = f.to_lowercase();
-            // Allow flags that match our allowlist
-            allowed_prefixes.iter().any(|allowed| {
-                lower == *allowed || lower.starts_with(&format!("{}=", allowed))
-            })
-            // Also allow values (non-flag arguments that don't start with -)
-            || !lower.starts_with('-')
-        })
-        .cloned()
-        .collect()
+    // Only an allowed option's own value is kept, so the argument of a
+    // rejected flag (or a stray extra URL) never reaches yt-dlp
+    let mut sanitized = Vec::new();
+    let mut args = flags.iter().filter(|flag| !flag.contains('\0')).peekable();
+    while let Some(flag) = args.next() {
+        let lower = flag.to_lowercase();
+        if !lower.starts_with('-') {
+            log::warn!("Dropping stray yt-dlp argument {}", flag);
+            continue;
+        }
+        if allowed_switches.contains(&lower.as_str()) {
+            sanitized.push(flag.clone());
+            continue;
+        }
+        let (name, inline_value) = match lower.split_once('=') {
+            Some((name, _)) => (name, true),
+            None => (lower.as_str(), false),
+        };
+        if !allowed_options.contains(&name) {
+            log::warn!("Dropping disallowed yt-dlp flag {}", flag);
+            continue;
+        }
+        if inline_value {
+            sanitized.push(flag.clone());
+            continue;
+        }
+        match args.next_if(|value| !value.starts_with('-')) {
+            Some(value) => {
+                sanitized.push(flag.clone());
+                sanitized.push(value.clone());
+            }
+            None => log::warn!("Dropping yt-dlp flag {} without a value", flag),
+        }
+    }
+    sanitized
 }
 
 /// Shell-escape a URL for safe inclusion in a shell command string.
@@ -178,7 +275,7 @@ fn shell_escape_url(url: &str) -> String {
     format!("'{}'", url.replace('\'', "'\\''"))
 }
 
-fn default_download_dir(app: &AppHandle) -> String {
+pub(crate) fn default_download_dir(app: &AppHandle) -> String {
     #[cfg(target_os = "android")]
     {
         let _ = app;
@@ -229,6 +326,25 @@ fn default_download_dir(app: &AppHandle) -> String {
     }
 }
 
+/// Destination folder for a download of the given kind: its own path
+/// setting if set, else `download_path`, else the platform default.
+pub(crate) fn resolve_download_dir(app: &AppHandle, db: &Database, kind: download::MediaKind) -> String {
+    let setting = |key: &str| {
+        db.get_setting(key)
+            .unwrap_or(None)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    setting(kind.path_setting())
+        .or_else(|| setting("download_path"))
+        .unwrap_or_else(|| default_download_dir(app))
+}
+
+/// The user's default quality preference, applied when no format is chosen.
+pub(crate) fn quality_preference(db: &Database) -> download::QualityPreference {
+    download::QualityPreference::from_settings(|key| db.get_setting(key).unwrap_or(None).unwrap_or_default())
+}
+
 async fn emit_rss_sync_progress(
     app: &AppHandle,
     feed_id: &str,
@@ -258,8 +374,13 @@ async fn emit_rss_sync_progress(
 
 // ────────────────────────────────────────────────── Video Info ──────────────────────────────────────────────────
 
+/// Errors carry an [`ErrorCode`](crate::error::ErrorCode) for the UI.
 #[tauri::command]
-pub async fn get_video_info(_app: AppHandle, url: String) -> Result<serde_json::Value, String> {
+pub async fn get_video_info(app: AppHandle, url: String) -> Result<serde_json::Value, AppError> {
+    get_video_info_inner(app, url).await.map_err(AppError::from)
+}
+
+pub(crate) async fn get_video_info_inner(_app: AppHandle, url: String) -> Result<serde_json::Value, String> {
     // Validate URL for security
     validate_url(&url)?;
 
@@ -279,6 +400,7 @@ pub async fn get_video_info(_app: AppHandle, url: String) -> Result<serde_json::
         let _ = std::fs::remove_file(&output_file);
 
         // Run yt-dlp -j URL in Termux background
+        crate::rate_limit::acquire().await?;
         let command = format!("yt-dlp --no-warnings -J {}", shell_escape_url(&url));
         log::info!("[get_video_info] Sending to Termux: {} → {}", command, output_file);
         match crate::android_bridge::run_termux_check(&command, &output_file) {
@@ -290,6 +412,7 @@ pub async fn get_video_info(_app: AppHandle, url: String) -> Result<serde_json::
                         let trimmed = content.trim();
                         if !trimmed.is_empty() {
                             let _ = std::fs::remove_file(&output_file);
+                            crate::rate_limit::observe(if trimmed.starts_with('{') { "" } else { trimmed });
                             // Try to parse as JSON
                             if trimmed.starts_with('{') {
                                 match serde_json::from_str::<serde_json::Value>(trimmed) {
@@ -343,19 +466,141 @@ pub async fn get_video_info(_app: AppHandle, url: String) -> Result<serde_json::
 
 // â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€ Downloads â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
+/// Errors carry an [`ErrorCode`](crate::error::ErrorCode) for the UI.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn start_download(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
+    url: String,
+    format_id: Option<String>,
+    mode: Option<String>,
+    cookies_from_browser: Option<String>,
+    info: Option<download::VideoInfo>,
+    section: Option<String>,
+    audio_languages: Option<Vec<String>>,
+    concurrent_fragments: Option<u32>,
+    output_dir: Option<String>,
+    cookie_profile: Option<String>,
+) -> Result<String, AppError> {
+    start_download_inner(
+        app,
+        db,
+        dl,
+        url,
+        format_id,
+        mode,
+        cookies_from_browser,
+        info,
+        section,
+        audio_languages,
+        concurrent_fragments,
+        output_dir,
+        cookie_profile,
+    )
+    .await
+    .map_err(AppError::from)
+}
+
+/// `info` is what the caller already got from `get_video_info`; yt-dlp
+/// is only asked again when it's missing. It only feeds the title,
+/// thumbnail and kind detection, never the download itself.
+///
+/// `section` limits the download to a time range or to chapters, see
+/// [`download::normalize_download_section`]. `audio_languages` picks the
+/// audio tracks of multi-language videos (`VideoInfo::audio_tracks`); with
+/// more than one, each becomes its own stream in the file.
+/// `concurrent_fragments` overrides the setting of the same name, and
+/// `output_dir` the folder the download would go to. `cookie_profile`
+/// (id or name) replaces `browser_cookies`, see [`crate::cookie_profiles`].
 #[allow(unreachable_code)]
 #[allow(unused_variables)]
-pub async fn start_download(
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn start_download_inner(
     app: AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
     dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
     url: String,
     format_id: Option<String>,
+    mode: Option<String>,
+    cookies_from_browser: Option<String>,
+    info: Option<download::VideoInfo>,
+    section: Option<String>,
+    audio_languages: Option<Vec<String>>,
+    concurrent_fragments: Option<u32>,
+    output_dir: Option<String>,
+    cookie_profile: Option<String>,
 ) -> Result<String, String> {
     // Validate URL for security
     validate_url(&url)?;
 
+    let output_dir = output_dir.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    if let Some(dir) = &output_dir {
+        crate::settings::check_folder("Download folder", dir)?;
+    }
+
+    if let Some(fragments) = concurrent_fragments {
+        crate::settings::check_concurrent_fragments(fragments)?;
+    }
+    let concurrent_fragments = concurrent_fragments.unwrap_or(0);
+
+    let audio_languages: Vec<String> = audio_languages
+        .unwrap_or_default()
+        .into_iter()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    download::validate_audio_languages(&audio_languages)?;
+
+    let section = match section.as_deref().filter(|s| !s.trim().is_empty()) {
+        Some(s) => Some(download::normalize_download_section(s)?),
+        None => None,
+    };
+
+    // Per-download override of the `browser_cookies` setting (e.g. the
+    // browser extension sending the browser it runs in)
+    let cookies_from_browser = cookies_from_browser
+        .map(|b| b.trim().to_lowercase())
+        .filter(|b| !b.is_empty());
+    if let Some(browser) = &cookies_from_browser {
+        crate::settings::validate_setting("browser_cookies", browser)?;
+    }
+
+    // A named cookie profile wins over both; without either, the profile
+    // of a feed the video came from applies
+    let cookie_profile = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        match cookie_profile.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+            Some(profile) => Some(crate::cookie_profiles::find(&db_lock, profile)?),
+            None if cookies_from_browser.is_none() => db_lock
+                .get_feed_cookie_profile_for_url(&url)
+                .unwrap_or(None)
+                .and_then(|id| crate::cookie_profiles::find(&db_lock, &id).ok()),
+            None => None,
+        }
+    };
+
+    // An explicit mode ("video", "audio", "shorts", "podcast") picks the
+    // destination folder; otherwise it is guessed from the URL and format.
+    // "archive" keeps the guess and saves everything about the video into
+    // a folder of its own, see `archive_bundle`. "tracks" goes to the audio
+    // folder as an album of chapter tracks, see `chapter_tracks`. "live"
+    // keeps the guess and records a stream from its start, see
+    // `live_schedule`.
+    let archive = mode.as_deref().map(str::trim) == Some(crate::archive_bundle::MODE);
+    let tracks = mode.as_deref().map(str::trim) == Some(crate::chapter_tracks::MODE);
+    let live = mode.as_deref().map(str::trim) == Some(crate::live_schedule::MODE);
+    let requested_kind = match mode.as_deref().filter(|m| !m.trim().is_empty() && !archive && !tracks && !live) {
+        Some(m) => Some(download::MediaKind::from_name(m).ok_or_else(|| format!("Unknown download mode: {}", m))?),
+        None if tracks => Some(download::MediaKind::Audio),
+        None => None,
+    };
+    let format_id = match format_id.filter(|f| !f.trim().is_empty()) {
+        None if tracks => Some(crate::chapter_tracks::DEFAULT_FORMAT.to_string()),
+        format_id => format_id,
+    };
+
     // ── Android: delegate to Termux ──────────────────────────────────────────
     // On Android, bundled Linux ARM64 binaries can't run due to ELF interpreter
     // mismatch (Android uses /system/bin/linker64, not /lib/ld-linux-aarch64.so.1).
@@ -363,6 +608,14 @@ pub async fn start_download(
     // The only working approach is Termux RUN_COMMAND Intent.
     #[cfg(target_os = "android")]
     {
+        // No format list here, so only the selector syntax is checked
+        if let Some(fid) = format_id.as_deref().filter(|f| !f.trim().is_empty()) {
+            download::validate_format_id(fid, &[])?;
+        }
+        // Termux downloads can't be paused from here, so they aren't started
+        if let Some(reason) = crate::download_guard::held_reason() {
+            return Err(reason.to_string());
+        }
         let (installed, has_perm) = crate::android_bridge::termux_info();
         if !installed {
             return Err(
@@ -381,12 +634,14 @@ pub async fn start_download(
         }
 
         // Use shared storage dir (Termux can access shared storage)
-        let output_dir = {
-            let db_lock = db.lock().map_err(|e| e.to_string())?;
-            db_lock
-                .get_setting("download_path")
-                .map_err(|e| e.to_string())?
-                .unwrap_or_else(|| download::android_shared_download_dir())
+        let output_dir = match output_dir.clone() {
+            Some(dir) => dir,
+            None => {
+                let kind = requested_kind
+                    .unwrap_or_else(|| download::MediaKind::detect(&url, format_id.as_deref(), &[]));
+                let db_lock = db.lock().map_err(|e| e.to_string())?;
+                resolve_download_dir(&app, &db_lock, kind)
+            }
         };
 
         let termux_output = if output_dir.starts_with("/data/data/")
@@ -397,9 +652,19 @@ pub async fn start_download(
             output_dir
         };
 
-        let format = format_id
-            .clone()
-            .unwrap_or_else(|| "bestvideo+bestaudio/best".to_string());
+        let mut format = match format_id.clone() {
+            Some(f) => f,
+            None => {
+                let db_lock = db.lock().map_err(|e| e.to_string())?;
+                quality_preference(&db_lock).format_expression()
+            }
+        };
+        if !audio_languages.is_empty() {
+            if format == "best" {
+                format = "bestvideo+bestaudio/best".to_string();
+            }
+            format = download::select_audio_languages(&format, &audio_languages, &[]);
+        }
 
         let extra_args = {
             let db_lock = db.lock().map_err(|e| e.to_string())?;
@@ -407,13 +672,36 @@ pub async fn start_download(
                 .get_setting("ytdlp_flags")
                 .map_err(|e| e.to_string())?
                 .unwrap_or_default();
-            if flags_str.is_empty() {
-                vec![]
-            } else {
-                let raw: Vec<String> =
-                    flags_str.split_whitespace().map(String::from).collect();
-                sanitize_ytdlp_flags(&raw)
+            let mut args = custom_ytdlp_flags(&flags_str);
+            args.extend(crate::extractor_args::ytdlp_args());
+            args.extend(concurrent_fragments_args(&db_lock, concurrent_fragments));
+            if download::has_multiple_audio_streams(&format) {
+                args.push("--audio-multistreams".to_string());
+            }
+            let mut template = download::DEFAULT_FILE_TEMPLATE.to_string();
+            if archive {
+                args.extend(crate::archive_bundle::ytdlp_args());
+                template = crate::archive_bundle::file_template(&template);
+            }
+            // Split but untagged: ffmpeg only runs inside Termux
+            if tracks {
+                template = crate::archive_bundle::file_template(&template);
+                args.extend(crate::chapter_tracks::ytdlp_args(&termux_output, &template));
+            }
+            if live {
+                args.extend(crate::live_schedule::ytdlp_args());
             }
+            if let Some(section) = &section {
+                args.push("--download-sections".to_string());
+                args.push(section.clone());
+                template = download::section_file_template(&template, section);
+            }
+            // The Termux command line has its own -o; a later one wins
+            if archive || tracks || section.is_some() {
+                args.push("-o".to_string());
+                args.push(format!("{}/{}", termux_output, template));
+            }
+            args
         };
 
         // Generate ID before launching so we can pass it to Termux for sentinel file
@@ -432,6 +720,24 @@ pub async fn start_download(
                     let db_lock = db.lock().map_err(|e| e.to_string())?;
                     let title = format!("Termux: {}", url.chars().take(60).collect::<String>());
                     let _ = db_lock.insert_download(&id, &url, &title, "");
+                    if let Some(section) = &section {
+                        let _ = db_lock.set_download_section(&id, section);
+                    }
+                    if archive {
+                        let _ = db_lock.set_download_mode(&id, crate::archive_bundle::MODE);
+                    }
+                    if tracks {
+                        let _ = db_lock.set_download_mode(&id, crate::chapter_tracks::MODE);
+                    }
+                    if live {
+                        let _ = db_lock.set_download_mode(&id, crate::live_schedule::MODE);
+                    }
+                    if concurrent_fragments > 0 {
+                        let _ = db_lock.set_download_fragments(&id, concurrent_fragments);
+                    }
+                    if output_dir.is_some() {
+                        let _ = db_lock.set_download_output_dir(&id, &termux_output);
+                    }
                     let _ = db_lock.update_download_status(&id, "downloading");
                 }
 
@@ -473,32 +779,83 @@ pub async fn start_download(
     let ytdlp = download::get_ytdlp_path(&app);
     let ffmpeg = download::get_ffmpeg_path(&app);
 
-    let info = download::fetch_video_info(&ytdlp, &url)
-        .await
-        .map_err(|e| e.to_string())?;
+    let info = match info.filter(|i| !i.title.trim().is_empty()) {
+        Some(info) => info,
+        None => download::fetch_video_info(&ytdlp, &url)
+            .await
+            .map_err(|e| e.to_string())?,
+    };
+    if let Some(fid) = format_id.as_deref().filter(|f| !f.trim().is_empty()) {
+        download::validate_format_id(fid, &info.formats)?;
+    }
+    if tracks && info.chapters.is_empty() {
+        return Err("This video has no chapters to split into tracks".to_string());
+    }
+
+    // Audio languages turn the requested format into an explicit selector,
+    // stored on the row so a resume keeps the same tracks
+    let (format_id, audio_label) = if audio_languages.is_empty() {
+        (format_id, None)
+    } else {
+        let base = match format_id.as_deref().filter(|f| !f.trim().is_empty()) {
+            Some("best") => "bestvideo+bestaudio/best".to_string(),
+            Some(f) => f.to_string(),
+            None => {
+                let db_lock = db.lock().map_err(|e| e.to_string())?;
+                quality_preference(&db_lock).format_expression()
+            }
+        };
+        let names: Vec<&str> = audio_languages
+            .iter()
+            .map(|l| {
+                info.audio_tracks
+                    .iter()
+                    .find(|t| &t.language == l)
+                    .map(|t| t.label.as_str())
+                    .unwrap_or(l.as_str())
+            })
+            .collect();
+        (
+            Some(download::select_audio_languages(&base, &audio_languages, &info.formats)),
+            Some(format!("Audio: {}", names.join(", "))),
+        )
+    };
 
     // Check for duplicates using O(1) SQL query instead of loading all rows
     {
         let db_lock = db.lock().map_err(|e| e.to_string())?;
         let format_to_check = format_id.as_deref().unwrap_or("");
-        if let Some(status) = db_lock.download_exists_by_url(&url, format_to_check)
+        let section_to_check = section.as_deref().unwrap_or("");
+        if let Some(status) = db_lock.download_exists_by_url(&url, format_to_check, section_to_check)
             .map_err(|e| e.to_string())? {
             return Err(format!("This video with the same quality is already {}", status));
         }
     }
 
-    let download_dir = {
-        let db_lock = db.lock().map_err(|e| e.to_string())?;
-        db_lock
-            .get_setting("download_path")
-            .map_err(|e| e.to_string())?
-            .unwrap_or_else(|| default_download_dir(&app))
+    let download_dir = match &output_dir {
+        Some(dir) => dir.clone(),
+        None => {
+            let kind = requested_kind
+                .unwrap_or_else(|| download::MediaKind::detect(&url, format_id.as_deref(), &info.formats));
+            let db_lock = db.lock().map_err(|e| e.to_string())?;
+            resolve_download_dir(&app, &db_lock, kind)
+        }
     };
 
     std::fs::create_dir_all(&download_dir).map_err(|e| e.to_string())?;
 
     // Get embed settings
-    let (embed_thumb, embed_meta, browser_cookies) = {
+    let quality = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        quality_preference(&db_lock)
+    };
+
+    let write_info_json = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        wants_info_json(&db_lock)
+    };
+
+    let (embed_thumb, embed_meta, embed_chapters, browser_cookies) = {
         let db_lock = db.lock().map_err(|e| e.to_string())?;
         let thumb = db_lock
             .get_setting("embed_thumbnail")
@@ -508,11 +865,17 @@ pub async fn start_download(
             .get_setting("embed_metadata")
             .unwrap_or(None)
             .unwrap_or_else(|| "true".to_string());
-        let cookies = db_lock
-            .get_setting("browser_cookies")
+        let chapters = db_lock
+            .get_setting("embed_chapters")
             .unwrap_or(None)
-            .unwrap_or_else(|| "none".to_string());
-        (thumb, meta, cookies)
+            .unwrap_or_else(|| "true".to_string());
+        let cookies = cookies_from_browser.unwrap_or_else(|| {
+            db_lock
+                .get_setting("browser_cookies")
+                .unwrap_or(None)
+                .unwrap_or_else(|| "none".to_string())
+        });
+        (thumb, meta, chapters, cookies)
     };
 
     {
@@ -520,6 +883,61 @@ pub async fn start_download(
         db_lock
             .insert_download(&id, &url, &info.title, &info.thumbnail)
             .map_err(|e| e.to_string())?;
+        if let Some(section) = &section {
+            db_lock
+                .set_download_section(&id, section)
+                .map_err(|e| e.to_string())?;
+        }
+        if let (Some(fid), Some(label)) = (&format_id, &audio_label) {
+            db_lock
+                .set_download_format(&id, fid, label)
+                .map_err(|e| e.to_string())?;
+        }
+        if archive {
+            db_lock
+                .set_download_mode(&id, crate::archive_bundle::MODE)
+                .map_err(|e| e.to_string())?;
+        }
+        if tracks {
+            db_lock
+                .set_download_mode(&id, crate::chapter_tracks::MODE)
+                .map_err(|e| e.to_string())?;
+            if let (Some(fid), None) = (&format_id, &audio_label) {
+                let label = format!("{} chapter tracks", info.chapters.len());
+                db_lock
+                    .set_download_format(&id, fid, &label)
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        if live {
+            db_lock
+                .set_download_mode(&id, crate::live_schedule::MODE)
+                .map_err(|e| e.to_string())?;
+        }
+        if let Some(profile) = &cookie_profile {
+            db_lock
+                .set_download_cookie_profile(&id, &profile.id)
+                .map_err(|e| e.to_string())?;
+        }
+        if concurrent_fragments > 0 {
+            db_lock
+                .set_download_fragments(&id, concurrent_fragments)
+                .map_err(|e| e.to_string())?;
+        }
+        if output_dir.is_some() {
+            db_lock
+                .set_download_output_dir(&id, &download_dir)
+                .map_err(|e| e.to_string())?;
+        }
+        // Metered connection with Wi-Fi-only on, low battery, quiet hours or
+        // the quota used up: wait as paused
+        if crate::download_guard::download_held(&id) {
+            db_lock
+                .update_download_status(&id, "paused")
+                .map_err(|e| e.to_string())?;
+            crate::download_guard::hold(&id);
+            return Ok(id);
+        }
         db_lock
             .update_download_status(&id, "downloading")
             .map_err(|e| e.to_string())?;
@@ -550,6 +968,8 @@ pub async fn start_download(
     tokio::spawn(async move {
         while let Some(progress) = progress_rx.recv().await {
             let _ = app_for_progress.emit("download-progress", &progress);
+            crate::speed_history::record(&id_for_progress, &progress.speed);
+            crate::queue_summary::update(&progress);
             // Also update DB periodically
             if let Ok(db_lock) = db_ref.lock() {
                 let _ = db_lock.update_download_progress(
@@ -558,8 +978,15 @@ pub async fn start_download(
                     &progress.speed,
                     &progress.eta,
                 );
+                crate::quota::record(&db_lock, &progress);
             }
         }
+        // The sender is dropped once yt-dlp has exited
+        crate::queue_summary::remove(&id_for_progress);
+        crate::quota::finish(&id_for_progress);
+        if let Ok(db_lock) = db_ref.lock() {
+            crate::speed_history::finish(&db_lock, &id_for_progress);
+        }
     });
 
     let dl_arc = dl.inner().clone();
@@ -569,12 +996,9 @@ pub async fn start_download(
             .get_setting("ytdlp_flags")
             .unwrap_or(None)
             .unwrap_or_default();
-        if flags.is_empty() {
-            vec![]
-        } else {
-            let raw: Vec<String> = flags.split_whitespace().map(String::from).collect();
-            sanitize_ytdlp_flags(&raw)
-        }
+        let mut args = custom_ytdlp_flags(&flags);
+        args.extend(concurrent_fragments_args(&db_lock, concurrent_fragments));
+        args
     };
 
     // Add embed options
@@ -584,24 +1008,72 @@ pub async fn start_download(
     if embed_meta == "true" {
         extra_args.push("--embed-metadata".to_string());
     }
-    if browser_cookies != "none" && !browser_cookies.is_empty() {
+    // --embed-metadata would add them too
+    if embed_chapters == "true" {
+        extra_args.push("--embed-chapters".to_string());
+    } else {
+        extra_args.push("--no-embed-chapters".to_string());
+    }
+    if write_info_json {
+        extra_args.push("--write-info-json".to_string());
+    }
+    if let Some(profile) = &cookie_profile {
+        extra_args.extend(profile.ytdlp_args());
+    } else if browser_cookies != "none" && !browser_cookies.is_empty() {
         extra_args.push("--cookies-from-browser".to_string());
         extra_args.push(browser_cookies);
     }
+    if format_id.as_deref().is_some_and(download::has_multiple_audio_streams) {
+        extra_args.push("--audio-multistreams".to_string());
+    }
+    let mut file_template = None;
+    if archive {
+        extra_args.extend(crate::archive_bundle::ytdlp_args());
+        file_template = Some(crate::archive_bundle::file_template(download::DEFAULT_FILE_TEMPLATE));
+    }
+    if tracks {
+        let template = crate::archive_bundle::file_template(download::DEFAULT_FILE_TEMPLATE);
+        extra_args.extend(crate::chapter_tracks::ytdlp_args(&download_dir, &template));
+        file_template = Some(template);
+    }
+    if live {
+        extra_args.extend(crate::live_schedule::ytdlp_args());
+    }
+    if let Some(section) = &section {
+        extra_args.push("--download-sections".to_string());
+        extra_args.push(section.clone());
+        let template = file_template.as_deref().unwrap_or(download::DEFAULT_FILE_TEMPLATE);
+        file_template = Some(download::section_file_template(template, section));
+    }
+
+    let cookie_fallback = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        cookie_fallback_browser(&db_lock, &extra_args)
+    };
 
     let db_for_result = db.inner().clone();
+    let log_file = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| download::download_log_path(&dir, &id));
 
     tokio::spawn(async move {
-        let result = download::run_download(
+        let result = run_download_with_cookie_fallback(
+            &db_for_result,
             &ytdlp,
             &ffmpeg,
             &url,
             &download_dir,
             format_id.as_deref(),
+            &quality,
             &extra_args,
             progress_tx,
             cancel_rx,
-            id_clone.clone(),
+            &id_clone,
+            file_template.as_deref(),
+            log_file,
+            cookie_fallback,
         )
         .await;
 
@@ -619,10 +1091,16 @@ pub async fn start_download(
                         .unwrap_or(0);
                     let _ = db_lock.update_download_complete(&id_clone, &file_path, file_size);
                 }
+                record_media_details(&db_for_result, &id_clone, &file_path);
+                crate::archive_bundle::record_artifacts(&db_for_result, &id_clone, &file_path);
+                crate::chapter_tracks::finish(&ffmpeg, &db_for_result, &id_clone, &file_path).await;
                 let _ = app_clone.emit(
                     "download-complete",
                     serde_json::json!({ "id": id_clone, "outputPath": file_path }),
                 );
+                notifications::download_complete(&app_clone, &id_clone);
+                webhooks::download_finished(&app_clone, &id_clone, Ok(&file_path));
+                crate::media_server::download_finished(&app_clone, &file_path);
             }
             Err(e) => {
                 if let Ok(db_lock) = db_for_result.lock() {
@@ -630,8 +1108,10 @@ pub async fn start_download(
                 }
                 let _ = app_clone.emit(
                     "download-error",
-                    serde_json::json!({ "id": id_clone, "error": e.to_string() }),
+                    serde_json::json!({ "id": id_clone, "error": e.to_string(), "code": e.code() }),
                 );
+                notifications::download_failed(&app_clone, &id_clone, &e.to_string());
+                webhooks::download_finished(&app_clone, &id_clone, Err(&e.to_string()));
             }
         }
     });
@@ -639,6 +1119,131 @@ pub async fn start_download(
     Ok(id)
 }
 
+/// `-N` for a download: its own override (0 = none) or the
+/// `concurrent_fragments` setting. Nothing when only one fragment is
+/// fetched at a time, yt-dlp's default.
+fn concurrent_fragments_args(db: &Database, fragments: u32) -> Vec<String> {
+    let fragments = if fragments > 0 {
+        fragments
+    } else {
+        db.get_setting("concurrent_fragments")
+            .unwrap_or(None)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1)
+    };
+    if fragments > 1 {
+        vec!["-N".to_string(), fragments.to_string()]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Whether `write_info_json` is on.
+fn wants_info_json(db: &Database) -> bool {
+    db.get_setting("write_info_json").unwrap_or(None).as_deref() == Some("true")
+}
+
+/// Store description, tags, upload date and view count from the .info.json
+/// next to a finished download, when yt-dlp wrote one.
+fn record_media_details(db: &Arc<Mutex<Database>>, id: &str, file_path: &str) {
+    let Some(json) = download::read_info_json(file_path) else { return };
+    let details = download::MediaDetails::from_json(&json);
+    if let Ok(db_lock) = db.lock() {
+        if let Err(e) = db_lock.set_download_details(id, &details) {
+            log::warn!("[info_json] Failed to store metadata of {}: {}", id, e);
+        }
+    }
+}
+
+/// `cookies_fallback_browser`, unless the download already runs with
+/// browser cookies or a cookies file.
+fn cookie_fallback_browser(db: &Database, extra_args: &[String]) -> Option<String> {
+    if extra_args.iter().any(|a| a == "--cookies-from-browser" || a == "--cookies") {
+        return None;
+    }
+    db.get_setting("cookies_fallback_browser")
+        .unwrap_or(None)
+        .map(|b| b.trim().to_lowercase())
+        .filter(|b| !b.is_empty() && b != "none")
+}
+
+/// [`crate::throttle::run_download`], run once more with `fallback_browser`'s
+/// cookies when yt-dlp reports the video as age-restricted or needing a
+/// signed-in account. A download that only worked with cookies gets a
+/// note saying why they were used.
+#[allow(clippy::too_many_arguments)]
+async fn run_download_with_cookie_fallback(
+    db: &Arc<Mutex<Database>>,
+    ytdlp: &str,
+    ffmpeg: &str,
+    url: &str,
+    output_dir: &str,
+    format_id: Option<&str>,
+    quality: &download::QualityPreference,
+    extra_args: &[String],
+    progress_tx: tokio::sync::mpsc::Sender<DownloadProgress>,
+    cancel_rx: tokio::sync::watch::Receiver<bool>,
+    id: &str,
+    file_template: Option<&str>,
+    log_file: Option<std::path::PathBuf>,
+    fallback_browser: Option<String>,
+) -> crate::error::AppResult<String> {
+    let result = crate::throttle::run_download(
+        db,
+        ytdlp,
+        ffmpeg,
+        url,
+        output_dir,
+        format_id,
+        quality,
+        extra_args,
+        progress_tx.clone(),
+        cancel_rx.clone(),
+        id.to_string(),
+        file_template,
+        log_file.clone(),
+    )
+    .await;
+
+    let Some(browser) = fallback_browser else { return result };
+    let reason = match &result {
+        Err(e) if e.code() == crate::error::ErrorCode::AgeRestricted => "the video is age-restricted",
+        Err(e) if e.code() == crate::error::ErrorCode::LoginRequired => "the video needs a signed-in account",
+        _ => return result,
+    };
+    if *cancel_rx.borrow() {
+        return result;
+    }
+
+    log::info!("[download] {} failed because {}, retrying with {} cookies", id, reason, browser);
+    let mut args = extra_args.to_vec();
+    args.push("--cookies-from-browser".to_string());
+    args.push(browser.clone());
+    let retry = crate::throttle::run_download(
+        db,
+        ytdlp,
+        ffmpeg,
+        url,
+        output_dir,
+        format_id,
+        quality,
+        &args,
+        progress_tx,
+        cancel_rx,
+        id.to_string(),
+        file_template,
+        log_file,
+    )
+    .await;
+    if retry.is_ok() {
+        if let Ok(db_lock) = db.lock() {
+            let note = format!("Used {} cookies: {}", browser, reason);
+            let _ = db_lock.set_download_note(id, &note);
+        }
+    }
+    retry
+}
+
 pub async fn start_download_existing(
     app: AppHandle,
     db: Arc<Mutex<Database>>,
@@ -647,40 +1252,124 @@ pub async fn start_download_existing(
     url: String,
     format_id: Option<String>,
 ) -> Result<(), String> {
+    start_download_existing_tracked(app, db, dl, id, url, format_id)
+        .await
+        .map(|_| ())
+}
+
+/// Same as `start_download_existing`, but hands back the handle of the
+/// background task so callers (e.g. the playlist queue) can wait for the
+/// download to finish before starting the next one.
+pub async fn start_download_existing_tracked(
+    app: AppHandle,
+    db: Arc<Mutex<Database>>,
+    dl: Arc<tokio::sync::Mutex<DownloadManager>>,
+    id: String,
+    url: String,
+    format_id: Option<String>,
+) -> Result<tokio::task::JoinHandle<()>, String> {
     validate_url(&url)?;
 
     let ytdlp = download::get_ytdlp_path(&app);
     let ffmpeg = download::get_ffmpeg_path(&app);
 
+    // A download sent to a folder of its own goes back there
     let download_dir = {
+        let kind = download::MediaKind::detect(&url, format_id.as_deref(), &[]);
         let db_lock = db.lock().map_err(|e| e.to_string())?;
         db_lock
-            .get_setting("download_path")
-            .map_err(|e| e.to_string())?
-            .unwrap_or_else(|| default_download_dir(&app))
+            .get_download(&id)
+            .unwrap_or(None)
+            .and_then(|d| d["outputDir"].as_str().map(String::from))
+            .filter(|dir| !dir.is_empty())
+            .unwrap_or_else(|| resolve_download_dir(&app, &db_lock, kind))
+    };
+
+    // Playlist entries get the playlist's naming template (with the index
+    // substituted) and, optionally, a subfolder named after the playlist.
+    // Channel mirrors always download into their own folder and record
+    // finished videos in that folder's yt-dlp archive.
+    let (download_dir, mut file_template, archive_file) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        match db_lock.get_playlist_context_for_download(&id).unwrap_or(None) {
+            Some(ctx) => {
+                let use_subfolder = db_lock
+                    .get_setting("playlist_subfolder")
+                    .unwrap_or(None)
+                    .unwrap_or_else(|| "true".to_string())
+                    == "true";
+                let is_mirror = ctx.kind == "channel";
+                let dir = if is_mirror {
+                    crate::mirror_commands::resolve_mirror_dir(&download_dir, &ctx.title, &ctx.output_dir)
+                        .to_string_lossy()
+                        .to_string()
+                } else if use_subfolder && !ctx.title.trim().is_empty() {
+                    std::path::Path::new(&download_dir)
+                        .join(download::sanitize_path_component(&ctx.title))
+                        .to_string_lossy()
+                        .to_string()
+                } else {
+                    download_dir
+                };
+                let template = download::render_playlist_template(
+                    &ctx.naming_template,
+                    ctx.index.max(0) as usize,
+                    &ctx.title,
+                );
+                let archive = is_mirror.then(|| {
+                    std::path::Path::new(&dir)
+                        .join(crate::mirror_commands::MIRROR_ARCHIVE_FILE)
+                        .to_string_lossy()
+                        .to_string()
+                });
+                (dir, template, archive)
+            }
+            None => (download_dir, None, None),
+        }
     };
 
     std::fs::create_dir_all(&download_dir).map_err(|e| e.to_string())?;
 
-    let (embed_thumb, embed_meta, browser_cookies) = {
+    let quality = {
         let db_lock = db.lock().map_err(|e| e.to_string())?;
-        let thumb = db_lock
-            .get_setting("embed_thumbnail")
-            .unwrap_or(None)
+        quality_preference(&db_lock)
+    };
+
+    let write_info_json = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        wants_info_json(&db_lock)
+    };
+
+    let (embed_thumb, embed_meta, embed_chapters, browser_cookies) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let thumb = db_lock
+            .get_setting("embed_thumbnail")
+            .unwrap_or(None)
             .unwrap_or_else(|| "true".to_string());
         let meta = db_lock
             .get_setting("embed_metadata")
             .unwrap_or(None)
             .unwrap_or_else(|| "true".to_string());
+        let chapters = db_lock
+            .get_setting("embed_chapters")
+            .unwrap_or(None)
+            .unwrap_or_else(|| "true".to_string());
         let cookies = db_lock
             .get_setting("browser_cookies")
             .unwrap_or(None)
             .unwrap_or_else(|| "none".to_string());
-        (thumb, meta, cookies)
+        (thumb, meta, chapters, cookies)
     };
 
     {
         let db_lock = db.lock().map_err(|e| e.to_string())?;
+        if crate::download_guard::download_held(&id) {
+            db_lock
+                .update_download_status(&id, "paused")
+                .map_err(|e| e.to_string())?;
+            crate::download_guard::hold(&id);
+            return Ok(tokio::spawn(async {}));
+        }
         db_lock
             .update_download_status(&id, "downloading")
             .map_err(|e| e.to_string())?;
@@ -708,6 +1397,8 @@ pub async fn start_download_existing(
     tokio::spawn(async move {
         while let Some(progress) = progress_rx.recv().await {
             let _ = app_clone.emit("download-progress", &progress);
+            crate::speed_history::record(&id_clone, &progress.speed);
+            crate::queue_summary::update(&progress);
             if let Ok(db_lock) = db_ref.lock() {
                 let _ = db_lock.update_download_progress(
                     &id_clone,
@@ -715,8 +1406,15 @@ pub async fn start_download_existing(
                     &progress.speed,
                     &progress.eta,
                 );
+                crate::quota::record(&db_lock, &progress);
             }
         }
+        // The sender is dropped once yt-dlp has exited
+        crate::queue_summary::remove(&id_clone);
+        crate::quota::finish(&id_clone);
+        if let Ok(db_lock) = db_ref.lock() {
+            crate::speed_history::finish(&db_lock, &id_clone);
+        }
     });
 
     let dl_arc = dl.clone();
@@ -726,12 +1424,7 @@ pub async fn start_download_existing(
             .get_setting("ytdlp_flags")
             .unwrap_or(None)
             .unwrap_or_default();
-        if flags.is_empty() {
-            vec![]
-        } else {
-            let raw: Vec<String> = flags.split_whitespace().map(String::from).collect();
-            sanitize_ytdlp_flags(&raw)
-        }
+        custom_ytdlp_flags(&flags)
     };
 
     if embed_thumb == "true" {
@@ -740,25 +1433,96 @@ pub async fn start_download_existing(
     if embed_meta == "true" {
         extra_args.push("--embed-metadata".to_string());
     }
-    if browser_cookies != "none" && !browser_cookies.is_empty() {
+    // --embed-metadata would add them too
+    if embed_chapters == "true" {
+        extra_args.push("--embed-chapters".to_string());
+    } else {
+        extra_args.push("--no-embed-chapters".to_string());
+    }
+    if write_info_json {
+        extra_args.push("--write-info-json".to_string());
+    }
+    if let Some(archive) = archive_file {
+        extra_args.push("--download-archive".to_string());
+        extra_args.push(archive);
+    }
+    if format_id.as_deref().is_some_and(download::has_multiple_audio_streams) {
+        extra_args.push("--audio-multistreams".to_string());
+    }
+    // A clip stays a clip and a bundle a bundle when resumed or retried,
+    // and a fragments override and cookie profile stay in place
+    let (section, mode, profile_args) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let row = db_lock.get_download(&id).unwrap_or(None);
+        let field = |key: &str| {
+            row.as_ref()
+                .and_then(|d| d[key].as_str().map(String::from))
+                .filter(|s| !s.is_empty())
+        };
+        let fragments = row
+            .as_ref()
+            .and_then(|d| d["concurrentFragments"].as_u64())
+            .unwrap_or(0) as u32;
+        extra_args.extend(concurrent_fragments_args(&db_lock, fragments));
+        let profile_args = field("cookieProfile").and_then(|p| crate::cookie_profiles::stored_args(&db_lock, &p));
+        (field("section"), field("mode"), profile_args)
+    };
+    if let Some(args) = profile_args {
+        extra_args.extend(args);
+    } else if browser_cookies != "none" && !browser_cookies.is_empty() {
         extra_args.push("--cookies-from-browser".to_string());
         extra_args.push(browser_cookies);
     }
+    if mode.as_deref() == Some(crate::archive_bundle::MODE) {
+        let template = file_template.as_deref().unwrap_or(download::DEFAULT_FILE_TEMPLATE);
+        file_template = Some(crate::archive_bundle::file_template(template));
+        extra_args.extend(crate::archive_bundle::ytdlp_args());
+    }
+    if mode.as_deref() == Some(crate::chapter_tracks::MODE) {
+        let template = file_template.as_deref().unwrap_or(download::DEFAULT_FILE_TEMPLATE);
+        let template = crate::archive_bundle::file_template(template);
+        extra_args.extend(crate::chapter_tracks::ytdlp_args(&download_dir, &template));
+        file_template = Some(template);
+    }
+    if mode.as_deref() == Some(crate::live_schedule::MODE) {
+        extra_args.extend(crate::live_schedule::ytdlp_args());
+    }
+    if let Some(section) = section {
+        let template = file_template.as_deref().unwrap_or(download::DEFAULT_FILE_TEMPLATE);
+        file_template = Some(download::section_file_template(template, &section));
+        extra_args.push("--download-sections".to_string());
+        extra_args.push(section);
+    }
+
+    let cookie_fallback = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        cookie_fallback_browser(&db_lock, &extra_args)
+    };
 
     let db_for_result = db.clone();
     let app_for_result = app.clone();
     let id_for_result = id.clone();
-    tokio::spawn(async move {
-        let result = download::run_download(
+    let log_file = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| download::download_log_path(&dir, &id));
+    let handle = tokio::spawn(async move {
+        let result = run_download_with_cookie_fallback(
+            &db_for_result,
             &ytdlp,
             &ffmpeg,
             &url,
             &download_dir,
             format_id.as_deref(),
+            &quality,
             &extra_args,
             progress_tx,
             cancel_rx,
-            id_for_result.clone(),
+            &id_for_result,
+            file_template.as_deref(),
+            log_file,
+            cookie_fallback,
         )
         .await;
 
@@ -775,10 +1539,16 @@ pub async fn start_download_existing(
                         .unwrap_or(0);
                     let _ = db_lock.update_download_complete(&id_for_result, &file_path, file_size);
                 }
+                record_media_details(&db_for_result, &id_for_result, &file_path);
+                crate::archive_bundle::record_artifacts(&db_for_result, &id_for_result, &file_path);
+                crate::chapter_tracks::finish(&ffmpeg, &db_for_result, &id_for_result, &file_path).await;
                 let _ = app_for_result.emit(
                     "download-complete",
                     serde_json::json!({ "id": id_for_result, "outputPath": file_path }),
                 );
+                notifications::download_complete(&app_for_result, &id_for_result);
+                webhooks::download_finished(&app_for_result, &id_for_result, Ok(&file_path));
+                crate::media_server::download_finished(&app_for_result, &file_path);
             }
             Err(e) => {
                 if let Ok(db_lock) = db_for_result.lock() {
@@ -786,13 +1556,17 @@ pub async fn start_download_existing(
                 }
                 let _ = app_for_result.emit(
                     "download-error",
-                    serde_json::json!({ "id": id_for_result, "error": e.to_string() }),
+                    serde_json::json!({ "id": id_for_result, "error": e.to_string(), "code": e.code() }),
                 );
+                notifications::download_failed(&app_for_result, &id_for_result, &e.to_string());
+                webhooks::download_finished(&app_for_result, &id_for_result, Err(&e.to_string()));
             }
         }
+
+        crate::playlist_commands::emit_playlist_progress(&app_for_result, &db_for_result, &id_for_result);
     });
 
-    Ok(())
+    Ok(handle)
 }
 
 #[tauri::command]
@@ -822,14 +1596,14 @@ pub async fn resume_download(
     db: State<'_, Arc<Mutex<Database>>>,
     dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
     id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let (url, format_id) = {
         let db_lock = db.lock().map_err(|e| e.to_string())?;
-        let downloads = db_lock.get_downloads().map_err(|e| e.to_string())?;
+        let downloads = db_lock.get_downloads()?;
         let dl_entry = downloads
             .iter()
             .find(|d| d["id"].as_str() == Some(&id))
-            .ok_or_else(|| "Download not found".to_string())?;
+            .ok_or_else(|| AppError::NotFound(format!("download {}", id)))?;
         let url = dl_entry["url"].as_str().map(String::from)
             .ok_or_else(|| "Download URL not found".to_string())?;
         let format_id = dl_entry["formatId"].as_str()
@@ -860,6 +1634,9 @@ pub async fn cancel_download(
     db_lock
         .update_download_status(&id, "cancelled")
         .map_err(|e| e.to_string())?;
+    // Termux downloads have no cancel token; stop the process in Termux
+    #[cfg(target_os = "android")]
+    crate::android_commands::stop_termux_download(&id);
     Ok(())
 }
 
@@ -869,7 +1646,7 @@ pub async fn retry_download(
     db: State<'_, Arc<Mutex<Database>>>,
     dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
     id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     resume_download(app, db, dl, id).await
 }
 
@@ -1371,16 +2148,191 @@ fn find_file_by_title_in_fallback_locations(
     None
 }
 
+/// Locate a download's file on disk from its stored path, falling back to
+/// similar names and a title search in the usual download locations.
+pub(crate) fn resolve_download_file(
+    raw_path: Option<&str>,
+    title: Option<&str>,
+    configured_download_dir: Option<&str>,
+) -> Option<std::path::PathBuf> {
+    if let Some(path) = raw_path {
+        log::info!("[resolve_download_file] Resolving: {}", path);
+        let normalized = normalize_user_path(path);
+        log::info!("[resolve_download_file] Normalized path: {}", normalized);
+
+        let mut file_path = std::path::PathBuf::from(&normalized);
+        if !file_path.exists() {
+            if let Some(similar) = find_similar_file(&file_path) {
+                file_path = similar;
+            } else if let Some(found) =
+                find_file_in_fallback_locations(&file_path, configured_download_dir)
+            {
+                file_path = found;
+            }
+        }
+
+        if file_path.exists() && file_path.is_file() {
+            return Some(file_path);
+        }
+    }
+
+    title.and_then(|t| find_file_by_title_in_fallback_locations(t, configured_download_dir))
+}
+
 #[tauri::command]
 pub async fn delete_download(
     db: State<'_, Arc<Mutex<Database>>>,
     id: String,
     delete_file: bool,
 ) -> Result<(), String> {
-    let (file_path_to_delete, title_to_delete, configured_download_dir): (Option<String>, Option<String>, Option<String>) = if delete_file {
+    delete_download_record(db.inner(), &id, delete_file)
+}
+
+/// yt-dlp output captured for a download. Only the last 512 KB of a long
+/// log is returned.
+#[tauri::command]
+pub async fn get_download_log(app: AppHandle, id: String) -> Result<serde_json::Value, String> {
+    const MAX_BYTES: u64 = 512 * 1024;
+    let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let path = download::download_log_path(&app_data, id.trim());
+    if !path.is_file() {
+        return Ok(serde_json::json!({
+            "id": id,
+            "path": path.to_string_lossy(),
+            "exists": false,
+            "content": "",
+            "truncated": false,
+        }));
+    }
+
+    let bytes = std::fs::read(&path).map_err(|e| e.to_string())?;
+    let start = bytes.len().saturating_sub(MAX_BYTES as usize);
+    let content = String::from_utf8_lossy(&bytes[start..]).to_string();
+    Ok(serde_json::json!({
+        "id": id,
+        "path": path.to_string_lossy(),
+        "exists": true,
+        "content": content,
+        "truncated": start > 0,
+    }))
+}
+
+/// Folders the app downloads into: the path settings, the platform
+/// default and channel mirror folders. Only files under one of these, or
+/// under the folder their own download was sent to, are ever deleted.
+fn managed_download_roots(db: &Database) -> Vec<std::path::PathBuf> {
+    let mut roots: Vec<std::path::PathBuf> = [
+        "download_path",
+        "video_download_path",
+        "audio_download_path",
+        "shorts_download_path",
+        "podcast_download_path",
+    ]
+    .iter()
+    .filter_map(|key| db.get_setting(key).unwrap_or(None))
+    .filter(|v| !v.trim().is_empty())
+    .map(|v| std::path::PathBuf::from(normalize_user_path(&v)))
+    .collect();
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    if let Some(downloads) = dirs::download_dir() {
+        roots.push(downloads.join("YTDL"));
+    }
+    #[cfg(target_os = "android")]
+    roots.push(std::path::PathBuf::from(download::android_shared_download_dir()));
+
+    for playlist in db.get_playlists().unwrap_or_default() {
+        let dir = playlist["outputDir"].as_str().unwrap_or_default().trim();
+        if !dir.is_empty() && std::path::Path::new(dir).is_absolute() {
+            roots.push(std::path::PathBuf::from(dir));
+        }
+    }
+    roots
+}
+
+/// Check that `dir`, a folder a remote client asked a download to go to,
+/// lies under the download folders. Settings and the app itself may pick
+/// any folder; a remote client can only choose between the ones set here.
+pub(crate) fn check_remote_output_dir(db: &Database, dir: &str) -> Result<(), String> {
+    crate::settings::check_folder("Download folder", dir)?;
+    let path = std::path::Path::new(dir);
+    // A folder that doesn't exist yet is judged by its parent
+    let resolved = path
+        .canonicalize()
+        .or_else(|_| {
+            let parent = path.parent().ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))?;
+            Ok::<_, std::io::Error>(parent.canonicalize()?.join(path.file_name().unwrap_or_default()))
+        })
+        .map_err(|e| format!("Cannot resolve '{}': {}", dir, e))?;
+    if is_within_roots(&resolved, &managed_download_roots(db)) {
+        Ok(())
+    } else {
+        Err(format!("Download folder '{}' is outside the download folders", dir))
+    }
+}
+
+/// Whether the already canonical `path` is one of `roots` or lies below one.
+fn is_within_roots(path: &std::path::Path, roots: &[std::path::PathBuf]) -> bool {
+    roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| path.starts_with(root))
+}
+
+/// Refuse to touch `path` unless it resolves (symlinks and `..` included)
+/// to a file under one of `roots`.
+fn ensure_managed_file(path: &std::path::Path, roots: &[std::path::PathBuf]) -> Result<(), String> {
+    let resolved = path
+        .canonicalize()
+        .map_err(|e| format!("Cannot resolve '{}': {}", path.display(), e))?;
+    if !resolved.is_file() {
+        return Err(format!("'{}' is not a file", path.display()));
+    }
+    if is_within_roots(&resolved, roots) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Refusing to delete '{}': it is outside the download folders",
+            resolved.display()
+        ))
+    }
+}
+
+/// Refuse `path` unless it is a file under the download folders or the
+/// folder its own download was sent to.
+pub(crate) fn ensure_download_file(db: &Database, path: &std::path::Path) -> Result<(), String> {
+    let mut roots = managed_download_roots(db);
+    let own_dirs = db
+        .get_download_output_dirs_for_file(&path.to_string_lossy())
+        .unwrap_or_default();
+    roots.extend(own_dirs.iter().map(|d| std::path::PathBuf::from(normalize_user_path(d))));
+    ensure_managed_file(path, &roots)
+}
+
+/// Remove a download row and, when `delete_file` is set, its file on disk.
+pub(crate) fn delete_download_record(
+    db: &Arc<Mutex<Database>>,
+    id: &str,
+    delete_file: bool,
+) -> Result<(), String> {
+    // Sidecar files of an archive bundle and album tracks go with the download
+    let artifacts: Vec<String> = if delete_file {
         let db_lock = db.lock().map_err(|e| e.to_string())?;
-        let downloads = db_lock.get_downloads().map_err(|e| e.to_string())?;
-        let row = downloads.iter().find(|d| d["id"].as_str() == Some(&id));
+        db_lock
+            .get_download_artifacts(id)
+            .unwrap_or_default()
+            .iter()
+            // The main file itself is removed below
+            .filter(|a| !matches!(a["kind"].as_str(), Some("video" | "audio")))
+            .filter_map(|a| a["path"].as_str().map(String::from))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let (file_path_to_delete, title_to_delete, configured_download_dir, managed_roots): (Option<String>, Option<String>, Option<String>, Vec<std::path::PathBuf>) = if delete_file {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let row = db_lock.get_download(id).map_err(|e| e.to_string())?;
+        let row = row.as_ref();
         let file_path = row
             .and_then(|d| d["filePath"].as_str())
             .filter(|p| !p.trim().is_empty())
@@ -1393,67 +2345,72 @@ pub async fn delete_download(
             .get_setting("download_path")
             .map_err(|e| e.to_string())?
             .filter(|v| !v.trim().is_empty());
-        (file_path, title, download_dir)
+        let mut roots = managed_download_roots(&db_lock);
+        // The folder picked for this download when it was started
+        if let Some(dir) = row
+            .and_then(|d| d["outputDir"].as_str())
+            .filter(|d| !d.trim().is_empty())
+        {
+            roots.push(std::path::PathBuf::from(normalize_user_path(dir)));
+        }
+        (file_path, title, download_dir, roots)
     } else {
-        (None, None, None)
+        (None, None, None, Vec::new())
     };
 
-    if delete_file {
-        let mut resolved_file: Option<std::path::PathBuf> = None;
-        let mut normalized_original_path = String::new();
-
-        if let Some(ref path) = file_path_to_delete {
-            log::info!("[delete_download] Attempting to delete file: {}", path);
-            let normalized = normalize_user_path(path);
-            normalized_original_path = normalized.clone();
-            log::info!("[delete_download] Normalized path: {}", normalized);
-
-            let mut file_path = std::path::PathBuf::from(&normalized);
-            if !file_path.exists() {
-                if let Some(similar) = find_similar_file(&file_path) {
-                    file_path = similar;
-                } else if let Some(found) = find_file_in_fallback_locations(
-                    &file_path,
-                    configured_download_dir.as_deref(),
-                ) {
-                    file_path = found;
-                }
-            }
-
-            if file_path.exists() && file_path.is_file() {
-                resolved_file = Some(file_path);
-            }
+    // Moved to the media collections or a picked folder on Android
+    #[cfg(target_os = "android")]
+    if let Some(uri) = file_path_to_delete.as_deref().filter(|p| p.starts_with("content://")) {
+        if !crate::android_bridge::delete_content_uri(uri)? {
+            return Err(format!("Failed to delete '{}'", uri));
         }
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        db_lock.delete_download(id).map_err(|e| e.to_string())?;
+        return Ok(());
+    }
 
-        if resolved_file.is_none() {
-            if let Some(ref title) = title_to_delete {
-                resolved_file = find_file_by_title_in_fallback_locations(
-                    title,
-                    configured_download_dir.as_deref(),
-                );
+    if delete_file {
+        let file_to_delete = match resolve_download_file(
+            file_path_to_delete.as_deref(),
+            title_to_delete.as_deref(),
+            configured_download_dir.as_deref(),
+        ) {
+            Some(path) => path,
+            None => {
+                let details = if let Some(ref path) = file_path_to_delete {
+                    normalize_user_path(path)
+                } else if let Some(title) = title_to_delete {
+                    format!("title='{}'", title)
+                } else {
+                    "unknown path/title".to_string()
+                };
+                return Err(format!("File not found on disk: {}", details));
             }
-        }
-
-        let file_to_delete = if let Some(path) = resolved_file {
-            path
-        } else {
-            let details = if !normalized_original_path.is_empty() {
-                normalized_original_path
-            } else if let Some(title) = title_to_delete {
-                format!("title='{}'", title)
-            } else {
-                "unknown path/title".to_string()
-            };
-            return Err(format!("File not found on disk: {}", details));
         };
 
+        ensure_managed_file(&file_to_delete, &managed_roots)?;
         std::fs::remove_file(&file_to_delete)
             .map_err(|e| format!("Failed to delete file '{}': {}", file_to_delete.display(), e))?;
         log::info!("[delete_download] File deleted successfully: {}", file_to_delete.display());
+
+        for artifact in &artifacts {
+            let path = std::path::Path::new(artifact);
+            if path.is_file() && ensure_managed_file(path, &managed_roots).is_ok() {
+                if let Err(e) = std::fs::remove_file(path) {
+                    log::warn!("[delete_download] Failed to delete {}: {}", path.display(), e);
+                }
+            }
+        }
+        if !artifacts.is_empty() {
+            // The bundle's own folder, once nothing else is left in it
+            if let Some(folder) = file_to_delete.parent() {
+                let _ = std::fs::remove_dir(folder);
+            }
+        }
     }
 
     let db_lock = db.lock().map_err(|e| e.to_string())?;
-    db_lock.delete_download(&id).map_err(|e| e.to_string())?;
+    db_lock.delete_download(id).map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -1465,28 +2422,95 @@ pub async fn get_downloads(
     db_lock.get_downloads().map_err(|e| e.to_string())
 }
 
+/// `H:MM:SS`, or `M:SS` under an hour.
+fn export_duration(seconds: Option<f64>) -> String {
+    let Some(seconds) = seconds.filter(|s| *s > 0.0) else { return String::new() };
+    let total = seconds.round() as u64;
+    let (h, m, s) = (total / 3600, total / 60 % 60, total % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+fn export_size(bytes: i64) -> String {
+    if bytes <= 0 {
+        return String::new();
+    }
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escape what would break a Markdown table cell.
+fn markdown_cell(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Render the download list as `json`, `csv`, `html` or `markdown`.
+/// `statuses` keeps only downloads in one of those statuses; `from` and `to`
+/// (YYYY-MM-DD, inclusive) limit the day they were added. With `path` the
+/// report is written there and the path is returned, otherwise the report
+/// itself is.
 #[tauri::command]
 pub async fn export_downloads(
     db: State<'_, Arc<Mutex<Database>>>,
     format: String,
+    statuses: Option<Vec<String>>,
+    from: Option<String>,
+    to: Option<String>,
+    path: Option<String>,
 ) -> Result<String, String> {
-    let db_lock = db.lock().map_err(|e| e.to_string())?;
-    let downloads = db_lock.get_downloads().map_err(|e| e.to_string())?;
+    let valid_day = |day: &Option<String>| -> Result<Option<String>, String> {
+        match day.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+            Some(d) => chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .map(|_| Some(d.to_string()))
+                .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", d)),
+            None => Ok(None),
+        }
+    };
+    let (from, to) = (valid_day(&from)?, valid_day(&to)?);
+    let statuses: Vec<String> = statuses.unwrap_or_default().into_iter().filter(|s| !s.is_empty()).collect();
 
-    match format.as_str() {
+    let downloads: Vec<serde_json::Value> = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        db_lock.get_downloads().map_err(|e| e.to_string())?
+    };
+    let downloads: Vec<serde_json::Value> = downloads
+        .into_iter()
+        .filter(|d| statuses.is_empty() || statuses.iter().any(|s| d["status"].as_str() == Some(s.as_str())))
+        .filter(|d| {
+            // createdAt is `YYYY-MM-DD HH:MM:SS`, so the day compares as text
+            let day = d["createdAt"].as_str().unwrap_or("").get(..10).unwrap_or("");
+            from.as_deref().map_or(true, |from| day >= from) && to.as_deref().map_or(true, |to| day <= to)
+        })
+        .collect();
+
+    let field = |d: &serde_json::Value, key: &str| d[key].as_str().unwrap_or("").to_string();
+    let report = match format.as_str() {
         "json" => {
-            serde_json::to_string_pretty(&downloads).map_err(|e| e.to_string())
+            serde_json::to_string_pretty(&downloads).map_err(|e| e.to_string())?
         }
         "csv" => {
-            let mut csv = String::from("id,title,url,status,format,created_at,updated_at\n");
-            for d in downloads {
-                let id = d["id"].as_str().unwrap_or("");
-                let title = d["title"].as_str().unwrap_or("");
-                let url = d["url"].as_str().unwrap_or("");
-                let status = d["status"].as_str().unwrap_or("");
-                let format_label = d["formatLabel"].as_str().unwrap_or("");
-                let created_at = d["createdAt"].as_str().unwrap_or("");
-                let updated_at = d["updatedAt"].as_str().unwrap_or("");
+            let mut csv = String::from("id,title,url,status,format,file_path,file_size,duration,created_at,updated_at\n");
+            for d in &downloads {
                 // CSV quoting with injection protection: prefix dangerous
                 // leading chars (=, +, -, @, \t, \r) that spreadsheet apps
                 // interpret as formulas.
@@ -1502,16 +2526,75 @@ pub async fn export_downloads(
                     };
                     format!("\"{}\"" , safe)
                 };
+                let file_size = d["fileSize"].as_i64().filter(|n| *n > 0).map(|n| n.to_string()).unwrap_or_default();
+                let duration = d["duration"].as_f64().map(|n| format!("{:.0}", n)).unwrap_or_default();
                 csv.push_str(&format!(
-                    "{},{},{},{},{},{},{}\n",
-                    quote_field(id), quote_field(title), quote_field(url),
-                    quote_field(status), quote_field(format_label),
-                    quote_field(created_at), quote_field(updated_at)
+                    "{},{},{},{},{},{},{},{},{},{}\n",
+                    quote_field(&field(d, "id")), quote_field(&field(d, "title")), quote_field(&field(d, "url")),
+                    quote_field(&field(d, "status")), quote_field(&field(d, "formatLabel")),
+                    quote_field(&field(d, "filePath")), file_size, duration,
+                    quote_field(&field(d, "createdAt")), quote_field(&field(d, "updatedAt"))
+                ));
+            }
+            csv
+        }
+        "html" => {
+            let total: i64 = downloads.iter().filter_map(|d| d["fileSize"].as_i64()).filter(|n| *n > 0).sum();
+            let mut html = String::from(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>YTDL downloads</title>\n\
+                 <style>body{font-family:sans-serif;margin:2em}table{border-collapse:collapse;width:100%}\
+                 th,td{border:1px solid #ccc;padding:4px 8px;text-align:left}th{background:#f3f3f3}\
+                 td.num{text-align:right;white-space:nowrap}</style>\n</head>\n<body>\n",
+            );
+            html.push_str(&format!(
+                "<h1>YTDL downloads</h1>\n<p>{} downloads, {}</p>\n<table>\n\
+                 <tr><th>Title</th><th>Status</th><th>Format</th><th>Size</th><th>Duration</th><th>Added</th></tr>\n",
+                downloads.len(),
+                html_escape(&export_size(total)),
+            ));
+            for d in &downloads {
+                html.push_str(&format!(
+                    "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>\n",
+                    html_escape(&field(d, "url")),
+                    html_escape(&field(d, "title")),
+                    html_escape(&field(d, "status")),
+                    html_escape(&field(d, "formatLabel")),
+                    export_size(d["fileSize"].as_i64().unwrap_or(0)),
+                    export_duration(d["duration"].as_f64()),
+                    html_escape(&field(d, "createdAt")),
+                ));
+            }
+            html.push_str("</table>\n</body>\n</html>\n");
+            html
+        }
+        "markdown" | "md" => {
+            let mut md = format!(
+                "# YTDL downloads\n\n{} downloads\n\n| Title | Status | Format | Size | Duration | Added |\n|---|---|---|---:|---:|---|\n",
+                downloads.len()
+            );
+            for d in &downloads {
+                md.push_str(&format!(
+                    "| [{}]({}) | {} | {} | {} | {} | {} |\n",
+                    markdown_cell(&field(d, "title")).replace(['[', ']'], ""),
+                    field(d, "url").replace(' ', "%20").replace(')', "%29"),
+                    field(d, "status"),
+                    markdown_cell(&field(d, "formatLabel")),
+                    export_size(d["fileSize"].as_i64().unwrap_or(0)),
+                    export_duration(d["duration"].as_f64()),
+                    field(d, "createdAt"),
                 ));
             }
-            Ok(csv)
+            md
         }
-        _ => Err("Unsupported format. Use 'json' or 'csv'.".to_string()),
+        _ => return Err("Unsupported format. Use 'json', 'csv', 'html' or 'markdown'.".to_string()),
+    };
+
+    match path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => {
+            std::fs::write(path, report).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+            Ok(path.to_string())
+        }
+        None => Ok(report),
     }
 }
 
@@ -1519,20 +2602,130 @@ pub async fn export_downloads(
 
 #[tauri::command]
 pub async fn get_settings(db: State<'_, Arc<Mutex<Database>>>) -> Result<serde_json::Value, String> {
+    let secrets: Vec<(&str, Option<String>)> = crate::secrets::SECRET_KEYS
+        .iter()
+        .map(|key| (*key, crate::secrets::keyring_secret(key)))
+        .collect();
     let db_lock = db.lock().map_err(|e| e.to_string())?;
-    db_lock.get_all_settings().map_err(|e| e.to_string())
+    let mut settings = db_lock.get_all_settings().map_err(|e| e.to_string())?;
+    // Fill in defaults for typed keys that were never saved
+    if let Some(map) = settings.as_object_mut() {
+        for (key, value) in AppSettings::load(&db_lock).to_pairs() {
+            map.entry(key).or_insert(serde_json::Value::String(value));
+        }
+        for (key, from_keyring) in secrets {
+            let value = crate::secrets::secret_or_setting(&db_lock, key, from_keyring);
+            map.insert(key.to_string(), serde_json::Value::String(value));
+        }
+    }
+    Ok(settings)
 }
 
+/// Save a single setting. Keys covered by `AppSettings` are parsed and
+/// validated first, API keys go to the system keyring; anything else is
+/// stored verbatim.
 #[tauri::command]
 pub async fn save_setting(
+    app: AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
     key: String,
     value: String,
 ) -> Result<(), String> {
+    crate::settings::validate_setting(&key, &value)?;
+    if key == "auto_launch" {
+        crate::autostart::apply(&app, value.trim() == "true" || value.trim() == "1")?;
+    }
+    if crate::secrets::is_secret_key(&key) {
+        let in_keyring = crate::secrets::keyring_store(&key, value.trim());
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        return crate::secrets::store_setting(&db_lock, &key, value.trim(), in_keyring);
+    }
     let db_lock = db.lock().map_err(|e| e.to_string())?;
     db_lock
         .save_setting(&key, &value)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    if key == "proxy_url" || key == "use_system_proxy" {
+        crate::proxy::load_from_db(&db_lock);
+    }
+    if key == "ytdlp_extractor_args" {
+        crate::extractor_args::load_from_db(&db_lock);
+    }
+    if key == "merge_policy" {
+        crate::remux::load_from_db(&db_lock);
+    }
+    if key == "log_retention_days" {
+        crate::logger::set_retention_days(value.trim().parse().unwrap_or_default());
+    }
+    if key == "ytdlp_requests_per_minute" || key == "ytdlp_cooldown_minutes" {
+        crate::rate_limit::load_from_db(&db_lock);
+    }
+    if key == "download_wifi_only" {
+        crate::download_guard::load_from_db(&db_lock);
+    }
+    if key == "battery_min_percent" || key == "battery_resume_percent" {
+        crate::battery::load_from_db(&db_lock);
+    }
+    if key.starts_with("quiet_hours_") {
+        crate::quiet_hours::load_from_db(&db_lock);
+    }
+    if key == "download_quota_gb" || key == "download_quota_period" {
+        crate::quota::load_from_db(&db_lock);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_app_settings(db: State<'_, Arc<Mutex<Database>>>) -> Result<AppSettings, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    Ok(AppSettings::load(&db_lock))
+}
+
+/// Merge `patch` (a partial `AppSettings` object) into the current
+/// settings, validate the changed fields and save. Nothing is written if
+/// any of them is invalid. Returns the updated settings.
+#[tauri::command]
+pub async fn update_app_settings(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    patch: serde_json::Value,
+) -> Result<AppSettings, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    let current = AppSettings::load(&db_lock);
+    let mut merged = serde_json::to_value(&current).map_err(|e| e.to_string())?;
+    let Some(fields) = patch.as_object() else {
+        return Err("Settings patch must be an object".to_string());
+    };
+    for (key, value) in fields {
+        match merged.get_mut(key) {
+            Some(slot) => *slot = value.clone(),
+            None => return Err(format!("Unknown setting: {}", key)),
+        }
+    }
+    let settings: AppSettings = serde_json::from_value(merged).map_err(|e| format!("Invalid settings: {}", e))?;
+    // Only changed fields are checked, so a stale value elsewhere (e.g. a
+    // deleted config file) doesn't block unrelated edits
+    let before = current.to_pairs();
+    for ((key, value), (_, old)) in settings.to_pairs().iter().zip(before.iter()) {
+        if value != old {
+            settings.check(key)?;
+        }
+    }
+    if settings.auto_launch != current.auto_launch {
+        crate::autostart::apply(&app, settings.auto_launch)?;
+    }
+    settings.save(&db_lock).map_err(|e| e.to_string())?;
+    crate::proxy::load_from_db(&db_lock);
+    crate::extractor_args::load_from_db(&db_lock);
+    crate::remux::load_from_db(&db_lock);
+    crate::rate_limit::load_from_db(&db_lock);
+    crate::download_guard::load_from_db(&db_lock);
+    crate::battery::load_from_db(&db_lock);
+    crate::quiet_hours::load_from_db(&db_lock);
+    crate::quota::load_from_db(&db_lock);
+    if settings.log_retention_days != current.log_retention_days {
+        crate::logger::set_retention_days(settings.log_retention_days);
+    }
+    Ok(settings)
 }
 
 #[tauri::command]
@@ -1559,16 +2752,49 @@ pub async fn select_directory(app: AppHandle) -> Result<Option<String>, String>
 #[tauri::command]
 pub async fn get_feeds(
     db: State<'_, Arc<Mutex<Database>>>,
-) -> Result<Vec<serde_json::Value>, String> {
-    let db_lock = db.lock().map_err(|e| e.to_string())?;
-    db_lock.get_feeds().map_err(|e| e.to_string())
+) -> Result<Vec<FeedSummary>, AppError> {
+    let db_lock = db.lock()?;
+    db_lock.get_feeds()
 }
 
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedItemsPage {
+    pub items: Vec<FeedItem>,
+    /// Items matching the filter across all pages.
+    pub total: i64,
+    pub offset: i64,
+    pub has_more: bool,
+}
+
+/// One page of a feed's items, newest first. See
+/// [`Database::get_feed_items`] for the filters.
 #[tauri::command]
-pub async fn add_feed(db: State<'_, Arc<Mutex<Database>>>, url: String) -> Result<String, String> {
-    let feed_url = rss::normalize_feed_url(&url)
-        .await
-        .map_err(|e| e.to_string())?;
+pub async fn get_feed_items(
+    db: State<'_, Arc<Mutex<Database>>>,
+    feed_id: String,
+    offset: Option<i64>,
+    limit: Option<i64>,
+    filter: Option<String>,
+) -> Result<FeedItemsPage, AppError> {
+    let offset = offset.unwrap_or(0).max(0);
+    let limit = limit.unwrap_or(100).clamp(1, 500);
+    let db_lock = db.lock()?;
+    let (items, total) =
+        db_lock.get_feed_items(&feed_id, offset, limit, filter.as_deref().unwrap_or("all"))?;
+    let has_more = offset + (items.len() as i64) < total;
+    Ok(FeedItemsPage { items, total, offset, has_more })
+}
+
+#[tauri::command]
+pub async fn add_feed(db: State<'_, Arc<Mutex<Database>>>, url: String) -> Result<String, AppError> {
+    let policy = rss::FeedPolicy::load(&*db.lock()?);
+    // Resolving a channel URL already fetches it, so check it up front too
+    if url.trim().starts_with("http://") || url.trim().starts_with("https://") {
+        policy.check(&url)?;
+    }
+    let feed_url = rss::normalize_feed_url(&url).await?;
+    policy.check(&feed_url)?;
 
     // Fast path: avoid long blocking operations when adding feed.
     // We try to fetch title quickly, but fallback to URL if network is slow.
@@ -1585,18 +2811,15 @@ pub async fn add_feed(db: State<'_, Arc<Mutex<Database>>>, url: String) -> Resul
     }
 
     let id = uuid::Uuid::new_v4().to_string();
-    let db_lock = db.lock().map_err(|e| e.to_string())?;
-    db_lock
-        .insert_feed(&id, &feed_url, &title, "")
-        .map_err(|e| e.to_string())?;
+    let db_lock = db.lock()?;
+    db_lock.insert_feed(&id, &feed_url, &title, "")?;
     Ok(id)
 }
 
 #[tauri::command]
-pub async fn remove_feed(db: State<'_, Arc<Mutex<Database>>>, id: String) -> Result<(), String> {
-    let db_lock = db.lock().map_err(|e| e.to_string())?;
-    db_lock.delete_feed(&id).map_err(|e| e.to_string())?;
-    Ok(())
+pub async fn remove_feed(db: State<'_, Arc<Mutex<Database>>>, id: String) -> Result<(), AppError> {
+    let db_lock = db.lock()?;
+    db_lock.delete_feed(&id)
 }
 
 #[tauri::command]
@@ -1604,7 +2827,7 @@ pub async fn check_feed(
     app: AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
     id: String,
-) -> Result<Vec<serde_json::Value>, String> {
+) -> Result<Vec<FeedItem>, AppError> {
     emit_rss_sync_progress(
         &app,
         &id,
@@ -1615,41 +2838,33 @@ pub async fn check_feed(
     )
     .await;
 
-    let (feed_url, existing_channel_name, existing_avatar) = {
-        let db_lock = db.lock().map_err(|e| e.to_string())?;
-        let feeds = db_lock.get_feeds().map_err(|e| e.to_string())?;
+    let (feed_url, existing_channel_name, existing_avatar, tabs, policy) = {
+        let db_lock = db.lock()?;
+        let feeds = db_lock.get_feeds()?;
         let feed = feeds
-            .iter()
-            .find(|f| f["id"].as_str() == Some(&id))
-            .ok_or_else(|| "Feed not found".to_string())?;
+            .into_iter()
+            .find(|f| f.id == id)
+            .ok_or_else(|| AppError::NotFound(format!("Feed {}", id)))?;
 
         (
-            feed["url"]
-                .as_str()
-                .map(String::from)
-                .ok_or_else(|| "Feed URL not found".to_string())?,
-            feed["channelName"].as_str().unwrap_or_default().to_string(),
-            feed["channelAvatar"]
-                .as_str()
-                .unwrap_or_default()
-                .to_string(),
+            feed.url,
+            feed.channel_name,
+            feed.channel_avatar,
+            feed.tabs,
+            rss::FeedPolicy::load(&db_lock),
         )
     };
 
-    let normalized_url = rss::normalize_feed_url(&feed_url)
-        .await
-        .map_err(|e| e.to_string())?;
+    policy.check(&feed_url)?;
+    let normalized_url = rss::normalize_feed_url(&feed_url).await?;
+    policy.check(&normalized_url)?;
 
     if normalized_url != feed_url {
-        let db_lock = db.lock().map_err(|e| e.to_string())?;
-        db_lock
-            .update_feed_url(&id, &normalized_url)
-            .map_err(|e| e.to_string())?;
+        let db_lock = db.lock()?;
+        db_lock.update_feed_url(&id, &normalized_url)?;
     }
 
-    let (title, items) = rss::fetch_feed_items_extended(&app, &normalized_url)
-        .await
-        .map_err(|e| e.to_string())?;
+    let (title, items) = rss::fetch_feed_items_extended(&app, &normalized_url, &tabs).await?;
 
     let total_items = items.len();
     emit_rss_sync_progress(
@@ -1680,45 +2895,29 @@ pub async fn check_feed(
 
     // Update last_checked and channel info in DB
     {
-        let db_lock = db.lock().map_err(|e| e.to_string())?;
-        db_lock
-            .update_feed_last_checked(&id)
-            .map_err(|e| e.to_string())?;
+        let db_lock = db.lock()?;
+        db_lock.update_feed_last_checked(&id)?;
         
         // Update channel info while preserving existing values when network metadata is missing
         if !channel_name_to_store.is_empty() || !channel_avatar_to_store.is_empty() {
-            db_lock
-            .update_feed_channel_info(&id, &channel_name_to_store, &channel_avatar_to_store)
-                .map_err(|e| e.to_string())?;
+            db_lock.update_feed_channel_info(&id, &channel_name_to_store, &channel_avatar_to_store)?;
         }
     }
 
     // Save items to database in batches
     {
-        let db_lock = db.lock().map_err(|e| e.to_string())?;
-        let feeds = db_lock.get_feeds().map_err(|e| e.to_string())?;
-        let feed_exists = feeds.iter().any(|f| f["id"].as_str() == Some(&id));
-        if !feed_exists {
-            return Err(format!("Feed {} not found in database", id));
+        let db_lock = db.lock()?;
+        let feeds = db_lock.get_feeds()?;
+        if !feeds.iter().any(|f| f.id == id) {
+            return Err(AppError::NotFound(format!("Feed {}", id)));
         }
     }
 
     let mut processed_count = 0usize;
     for chunk in items.chunks(RSS_SYNC_BATCH_SIZE) {
         {
-            let db_lock = db.lock().map_err(|e| e.to_string())?;
-            for item in chunk {
-                let _ = db_lock.insert_feed_item(
-                    &item.id,
-                    &id,
-                    &item.video_id,
-                    &item.title,
-                    &item.thumbnail,
-                    &item.url,
-                    &item.published_at,
-                    &item.video_type,
-                );
-            }
+            let db_lock = db.lock()?;
+            db_lock.insert_feed_items(&id, chunk)?;
         }
 
         processed_count += chunk.len();
@@ -1735,20 +2934,7 @@ pub async fn check_feed(
         tokio::task::yield_now().await;
     }
 
-    let result: Vec<serde_json::Value> = items
-        .iter()
-        .map(|item| {
-            serde_json::json!({
-                "id": item.id,
-                "title": item.title,
-                "url": item.url,
-                "thumbnail": item.thumbnail,
-                "publishedAt": item.published_at,
-                "status": if item.downloaded { "downloaded" } else { "not_queued" },
-                "videoType": item.video_type,
-            })
-        })
-        .collect();
+    let result: Vec<FeedItem> = items.iter().map(FeedItem::from).collect();
 
     emit_rss_sync_progress(
         &app,
@@ -1792,31 +2978,96 @@ pub async fn open_external(url: String) -> Result<(), String> {
     }
 }
 
-#[tauri::command]
-pub async fn open_path(
-    db: State<'_, Arc<Mutex<Database>>>,
-    path: String,
-) -> Result<(), String> {
-    // Validate path: block obviously malicious patterns
+/// Find the file or folder `path` refers to (a moved or renamed download
+/// is looked up by name) and check that it lies under the download folders
+/// or the app's data folder. Returns the canonical path.
+fn locate_app_path(app: &AppHandle, db: &Arc<Mutex<Database>>, path: &str) -> Result<std::path::PathBuf, String> {
     let trimmed = path.trim();
     if trimmed.is_empty() {
         return Err("Path cannot be empty".to_string());
     }
-    
-    log::info!("[open_path] Original path: {}", trimmed);
     let normalized = normalize_user_path(trimmed);
     log::info!("[open_path] Normalized path: {}", normalized);
 
+    let (configured_download_dir, mut roots) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let download_dir = db_lock
+            .get_setting("download_path")
+            .map_err(|e| e.to_string())?
+            .filter(|v| !v.trim().is_empty());
+        let mut roots = managed_download_roots(&db_lock);
+        // Downloads of this file that were sent to a folder of their own
+        let mut own_dirs = db_lock.get_download_output_dirs_for_file(trimmed).unwrap_or_default();
+        if normalized != trimmed {
+            own_dirs.extend(db_lock.get_download_output_dirs_for_file(&normalized).unwrap_or_default());
+        }
+        roots.extend(own_dirs.iter().map(|d| std::path::PathBuf::from(normalize_user_path(d))));
+        (download_dir, roots)
+    };
+    if let Ok(app_data) = app.path().app_data_dir() {
+        roots.push(app_data);
+    }
+
+    let target = std::path::PathBuf::from(&normalized);
+    let found = if target.exists() {
+        Some(target)
+    } else if cfg!(target_os = "android") {
+        None
+    } else {
+        log::warn!("[open_path] Not found, searching for similar: {}", target.display());
+        find_similar_file(&target)
+            .or_else(|| find_file_in_fallback_locations(&target, configured_download_dir.as_deref()))
+    };
+    let found = found.ok_or_else(|| format!("Failed to open '{}': path not found", normalized))?;
+
+    let resolved = found
+        .canonicalize()
+        .map_err(|e| format!("Cannot resolve '{}': {}", found.display(), e))?;
+    if !is_within_roots(&resolved, &roots) {
+        log::warn!("[open_path] Refusing path outside app folders: {}", resolved.display());
+        return Err(format!(
+            "Not opening '{}': it is outside the download folders",
+            resolved.display()
+        ));
+    }
+    Ok(resolved)
+}
+
+#[tauri::command]
+pub async fn open_path(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    path: String,
+) -> Result<(), String> {
+    // Downloads moved out by `android_storage_target` are content URIs
+    #[cfg(target_os = "android")]
+    if path.starts_with("content://") {
+        let known = {
+            let db_lock = db.lock().map_err(|e| e.to_string())?;
+            db_lock.has_download_file(&path).map_err(|e| e.to_string())?
+        };
+        if !known {
+            return Err("Path is not a downloaded file".to_string());
+        }
+        return match crate::android_bridge::open_file_path(&path)? {
+            true => Ok(()),
+            false => Err("Could not open file on Android".to_string()),
+        };
+    }
+
+    let target = locate_app_path(&app, db.inner(), &path)?;
+
     // On Android, open::that() doesn't work — use Android intents via JNI
     #[cfg(target_os = "android")]
     {
-        return crate::android_bridge::open_file_path(&normalized)
+        let path = target.to_string_lossy().to_string();
+        return crate::android_bridge::open_file_path(&path)
             .and_then(|ok| {
                 if ok { 
-                    log::info!("[open_path] Successfully opened on Android: {}", normalized);
+                    log::info!("[open_path] Successfully opened on Android: {}", path);
                     Ok(()) 
                 } else { 
-                    log::error!("[open_path] Failed to open on Android: {}", normalized);
+                    log::error!("[open_path] Failed to open on Android: {}", path);
                     Err("Could not open file on Android".to_string()) 
                 }
             });
@@ -1824,41 +3075,89 @@ pub async fn open_path(
 
     #[cfg(not(target_os = "android"))]
     {
-        let configured_download_dir = db
-            .lock()
-            .map_err(|e| e.to_string())?
-            .get_setting("download_path")
-            .map_err(|e| e.to_string())?
-            .filter(|v| !v.trim().is_empty());
-        let target = std::path::PathBuf::from(&normalized);
-        log::info!("[open_path] Checking if file exists: {}", target.display());
+        log::info!("[open_path] Opening: {}", target.display());
+        open::that(&target).map_err(|e| format!("Failed to open '{}': {}", target.display(), e))
+    }
+}
 
-        if target.exists() {
-            log::info!("[open_path] File exists, opening: {}", target.display());
-            return open::that(&target)
-                .map_err(|e| format!("Failed to open '{}': {}", target.display(), e));
-        }
+/// Show a downloaded file selected in the system file manager. Folders are
+/// simply opened.
+#[tauri::command]
+pub async fn reveal_in_folder(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    path: String,
+) -> Result<(), String> {
+    let target = locate_app_path(&app, db.inner(), &path)?;
 
+    #[cfg(mobile)]
+    {
+        let _ = target;
+        Err("Revealing files is not supported on this platform".to_string())
+    }
+
+    #[cfg(desktop)]
+    {
         if target.is_dir() {
             return open::that(&target)
-                .map_err(|e| format!("Failed to open directory '{}': {}", target.display(), e));
+                .map_err(|e| format!("Failed to open '{}': {}", target.display(), e));
         }
 
-        log::warn!("[open_path] File not found, searching for similar: {}", target.display());
-        if let Some(similar) = find_similar_file(&target) {
-            log::info!("[open_path] Found similar file, opening: {}", similar.display());
-            return open::that(&similar)
-                .map_err(|e| format!("Failed to open '{}': {}", similar.display(), e));
+        #[cfg(target_os = "windows")]
+        {
+            // explorer.exe exits with 1 even when it worked
+            let mut select = std::ffi::OsString::from("/select,");
+            select.push(target.as_os_str());
+            std::process::Command::new("explorer")
+                .arg(select)
+                .spawn()
+                .map_err(|e| format!("Failed to start Explorer: {}", e))?;
+            Ok(())
         }
 
-        if let Some(found) = find_file_in_fallback_locations(&target, configured_download_dir.as_deref()) {
-            log::info!("[open_path] Found file in fallback location, opening: {}", found.display());
-            return open::that(&found)
-                .map_err(|e| format!("Failed to open '{}': {}", found.display(), e));
+        #[cfg(target_os = "macos")]
+        {
+            let status = tokio::process::Command::new("open")
+                .arg("-R")
+                .arg(&target)
+                .status()
+                .await
+                .map_err(|e| format!("Failed to start Finder: {}", e))?;
+            if status.success() {
+                Ok(())
+            } else {
+                Err(format!("Finder could not reveal '{}'", target.display()))
+            }
         }
 
-        log::error!("[open_path] No file or directory found for: {}", normalized);
-        Err(format!("Failed to open '{}': path not found", normalized))
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            // Most file managers implement the FileManager1 D-Bus interface;
+            // fall back to opening the containing folder
+            let uri = url::Url::from_file_path(&target)
+                .map(|u| u.to_string())
+                .unwrap_or_default();
+            let selected = tokio::process::Command::new("dbus-send")
+                .args([
+                    "--session",
+                    "--print-reply",
+                    "--dest=org.freedesktop.FileManager1",
+                    "--type=method_call",
+                    "/org/freedesktop/FileManager1",
+                    "org.freedesktop.FileManager1.ShowItems",
+                ])
+                .arg(format!("array:string:{}", uri))
+                .arg("string:")
+                .status()
+                .await
+                .map(|status| status.success())
+                .unwrap_or(false);
+            if selected {
+                return Ok(());
+            }
+            let parent = target.parent().unwrap_or(&target);
+            open::that(parent).map_err(|e| format!("Failed to open '{}': {}", parent.display(), e))
+        }
     }
 }
 
@@ -1867,13 +3166,127 @@ pub async fn open_path(
 /// Extract direct stream URLs from a video URL using yt-dlp.
 /// This allows playing videos in a custom player even in countries where YouTube is blocked,
 /// because yt-dlp can use proxies/cookies and returns direct CDN URLs.
+///
+/// With `proxy` (the default) the returned URLs point at the local
+/// [`StreamProxy`](crate::stream_proxy::StreamProxy), which adds the headers and
+/// cookies the CDN expects; the raw CDN URLs are kept under `direct`.
+///
+/// With `audio_only` only the best audio stream is returned (video fields
+/// are left empty), which is all background listening needs.
+///
+/// Results are cached until shortly before the CDN URLs expire; `expiresAt`
+/// (unix seconds, 0 if unknown) and `expiresIn` tell the player how long
+/// they stay valid.
+///
+/// `cookie_profile` (id or name) replaces `browser_cookies` for the
+/// extraction, see [`crate::cookie_profiles`].
 #[tauri::command]
 pub async fn get_stream_url(
-    _app: AppHandle,
-    _db: State<'_, Arc<Mutex<Database>>>,
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    stream_proxy: State<'_, crate::stream_proxy::StreamProxy>,
+    cache: State<'_, crate::stream_proxy::StreamInfoCache>,
+    url: String,
+    proxy: Option<bool>,
+    audio_only: Option<bool>,
+    cookie_profile: Option<String>,
+) -> Result<serde_json::Value, String> {
+    validate_url(&url)?;
+    let proxy = proxy.unwrap_or(true);
+    let audio_only = audio_only.unwrap_or(false);
+    let profile = stream_cookie_profile(db.inner(), cookie_profile.as_deref())?;
+    let profile_id = profile.as_ref().map(|p| p.id.as_str()).unwrap_or("");
+    let key = crate::stream_proxy::StreamInfoCache::key(&url, proxy, audio_only, profile_id);
+    if let Some(info) = cache.get(&key) {
+        return Ok(info);
+    }
+
+    let info = build_stream_info(&app, db.inner(), &stream_proxy, &url, proxy, audio_only, profile.as_ref()).await?;
+    Ok(cache.insert(key, info))
+}
+
+/// Re-extract stream URLs, bypassing the cache. For the player to call when
+/// the CDN starts answering 403 mid-playback because the URLs expired.
+#[tauri::command]
+pub async fn refresh_stream_url(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    stream_proxy: State<'_, crate::stream_proxy::StreamProxy>,
+    cache: State<'_, crate::stream_proxy::StreamInfoCache>,
     url: String,
+    proxy: Option<bool>,
+    audio_only: Option<bool>,
+    cookie_profile: Option<String>,
 ) -> Result<serde_json::Value, String> {
     validate_url(&url)?;
+    let proxy = proxy.unwrap_or(true);
+    let audio_only = audio_only.unwrap_or(false);
+    let profile = stream_cookie_profile(db.inner(), cookie_profile.as_deref())?;
+    let profile_id = profile.as_ref().map(|p| p.id.as_str()).unwrap_or("");
+    let key = crate::stream_proxy::StreamInfoCache::key(&url, proxy, audio_only, profile_id);
+    cache.remove(&key);
+
+    log::info!("[refresh_stream_url] Re-extracting stream for {}", url);
+    let info = build_stream_info(&app, db.inner(), &stream_proxy, &url, proxy, audio_only, profile.as_ref()).await?;
+    Ok(cache.insert(key, info))
+}
+
+fn stream_cookie_profile(
+    db: &Arc<Mutex<Database>>,
+    profile: Option<&str>,
+) -> Result<Option<crate::cookie_profiles::CookieProfile>, String> {
+    let Some(profile) = profile.map(str::trim).filter(|p| !p.is_empty()) else { return Ok(None) };
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    crate::cookie_profiles::find(&db_lock, profile).map(Some)
+}
+
+async fn build_stream_info(
+    app: &AppHandle,
+    db: &Arc<Mutex<Database>>,
+    stream_proxy: &crate::stream_proxy::StreamProxy,
+    url: &str,
+    proxy: bool,
+    audio_only: bool,
+    cookie_profile: Option<&crate::cookie_profiles::CookieProfile>,
+) -> Result<serde_json::Value, String> {
+    let json = extract_stream_json(app, db, url, cookie_profile).await?;
+    let mut info = parse_stream_json(&json)?;
+    if audio_only {
+        if info["audioUrl"].as_str().unwrap_or("").is_empty() {
+            // No separate audio track (e.g. some non-YouTube sites): fall
+            // back to the combined stream, which still plays as audio.
+            info["audioUrl"] = info["combinedUrl"].clone();
+        }
+        info["videoUrl"] = serde_json::Value::String(String::new());
+        info["combinedUrl"] = serde_json::Value::String(String::new());
+        info["qualities"] = serde_json::json!([]);
+        info["storyboards"] = serde_json::json!([]);
+    }
+    info["audioOnly"] = serde_json::Value::Bool(audio_only);
+
+    // Taken from the direct URLs, before they're swapped for proxy URLs
+    let expires_at = ["videoUrl", "audioUrl", "combinedUrl"]
+        .iter()
+        .filter_map(|key| info[*key].as_str())
+        .filter_map(crate::stream_proxy::url_expiry)
+        .min()
+        .unwrap_or(0);
+    info["expiresAt"] = serde_json::json!(expires_at);
+
+    if proxy {
+        proxy_stream_info(stream_proxy, &json, info).await
+    } else {
+        Ok(info)
+    }
+}
+
+/// Run `yt-dlp -j` for `url` (through Termux on Android).
+async fn extract_stream_json(
+    _app: &AppHandle,
+    _db: &Arc<Mutex<Database>>,
+    url: &str,
+    cookie_profile: Option<&crate::cookie_profiles::CookieProfile>,
+) -> Result<serde_json::Value, String> {
 
     // On Android, use Termux to run yt-dlp -j for stream extraction.
     #[cfg(target_os = "android")]
@@ -1887,7 +3300,16 @@ pub async fn get_stream_url(
         let output_file = format!("{}/stream_{}.json", check_dir, uuid::Uuid::new_v4());
         let _ = std::fs::remove_file(&output_file);
 
-        let command = format!("yt-dlp --no-warnings -j --no-playlist {}", shell_escape_url(&url));
+        crate::rate_limit::acquire().await?;
+        // A cookies.txt profile on shared storage is readable from Termux too
+        let cookie_args: Vec<String> = cookie_profile
+            .map(|p| p.ytdlp_args().iter().map(|a| shell_escape_url(a)).collect())
+            .unwrap_or_default();
+        let command = format!(
+            "yt-dlp --no-warnings -j --no-playlist {}{}",
+            cookie_args.iter().map(|a| format!("{} ", a)).collect::<String>(),
+            shell_escape_url(url)
+        );
         log::info!("[get_stream_url] Sending to Termux: {}", command);
 
         match crate::android_bridge::run_termux_check(&command, &output_file) {
@@ -1899,10 +3321,11 @@ pub async fn get_stream_url(
                         let trimmed = content.trim();
                         if !trimmed.is_empty() {
                             let _ = std::fs::remove_file(&output_file);
+                            crate::rate_limit::observe(if trimmed.starts_with('{') { "" } else { trimmed });
                             if trimmed.starts_with('{') {
                                 match serde_json::from_str::<serde_json::Value>(trimmed) {
                                     Ok(json) => {
-                                        return parse_stream_json(&json);
+                                        return Ok(json);
                                     }
                                     Err(e) => {
                                         return Err(format!("yt-dlp returned invalid JSON: {}", e));
@@ -1928,7 +3351,7 @@ pub async fn get_stream_url(
     #[cfg(not(target_os = "android"))]
     {
 
-    let ytdlp = download::get_ytdlp_path(&_app);
+    let ytdlp = download::get_ytdlp_path(_app);
 
     // Get browser cookies setting for bypassing restrictions
     let browser_cookies = {
@@ -1944,31 +3367,98 @@ pub async fn get_stream_url(
         "--no-download".to_string(),
         "--no-warnings".to_string(),
         "--no-playlist".to_string(),
-        url.clone(),
+        url.to_string(),
     ];
 
-    if browser_cookies != "none" && !browser_cookies.is_empty() {
+    if let Some(profile) = cookie_profile {
+        args.splice(0..0, profile.ytdlp_args());
+    } else if browser_cookies != "none" && !browser_cookies.is_empty() {
         args.insert(0, "--cookies-from-browser".to_string());
         args.insert(1, browser_cookies);
     }
 
-    let output = download::create_hidden_command(&ytdlp)
-        .args(&args)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run yt-dlp: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("yt-dlp failed: {}", stderr.trim()));
+    crate::rate_limit::acquire().await?;
+    let output = download::create_hidden_command(&ytdlp)
+        .args(crate::extractor_args::ytdlp_args())
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run yt-dlp: {}", e))?;
+
+    crate::rate_limit::observe(&String::from_utf8_lossy(&output.stderr));
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("yt-dlp failed: {}", stderr.trim()));
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse yt-dlp output: {}", e))
+
+    } // #[cfg(not(target_os = "android"))]
+}
+
+/// Swap every URL in the parsed stream info for a local proxy URL carrying
+/// the headers yt-dlp reported for that format.
+async fn proxy_stream_info(
+    stream_proxy: &crate::stream_proxy::StreamProxy,
+    json: &serde_json::Value,
+    mut info: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let mut headers_by_url: std::collections::HashMap<String, Vec<(String, String)>> =
+        std::collections::HashMap::new();
+    if let Some(formats) = json["formats"].as_array() {
+        for f in formats {
+            if let Some(format_url) = f["url"].as_str() {
+                headers_by_url.insert(format_url.to_string(), crate::stream_proxy::headers_for_format(f));
+            }
+        }
+    }
+    let fallback_headers = crate::stream_proxy::headers_for_format(json);
+
+    let mut proxied: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    let mut direct = serde_json::Map::new();
+    for key in ["videoUrl", "audioUrl", "combinedUrl"] {
+        let original = info[key].as_str().unwrap_or("").to_string();
+        if original.is_empty() {
+            continue;
+        }
+        let headers = headers_by_url.get(&original).cloned().unwrap_or_else(|| fallback_headers.clone());
+        let local = stream_proxy.register(&original, headers).await?;
+        proxied.insert(original.clone(), local.clone());
+        direct.insert(key.to_string(), serde_json::Value::String(original));
+        info[key] = serde_json::Value::String(local);
     }
 
-    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
-        .map_err(|e| format!("Failed to parse yt-dlp output: {}", e))?;
+    if let Some(qualities) = info["qualities"].as_array_mut() {
+        for quality in qualities {
+            let original = quality["url"].as_str().unwrap_or("").to_string();
+            if original.is_empty() {
+                continue;
+            }
+            let local = match proxied.get(&original) {
+                Some(local) => local.clone(),
+                None => {
+                    let headers = headers_by_url.get(&original).cloned().unwrap_or_else(|| fallback_headers.clone());
+                    stream_proxy.register(&original, headers).await?
+                }
+            };
+            quality["url"] = serde_json::Value::String(local);
+        }
+    }
 
-    parse_stream_json(&json)
+    // Served from the proxy so `<track>` gets the CORS header it needs
+    if let Some(subtitles) = info["subtitles"].as_array_mut() {
+        for track in subtitles {
+            let original = track["url"].as_str().unwrap_or("").to_string();
+            if !original.is_empty() {
+                track["url"] = serde_json::Value::String(stream_proxy.register(&original, fallback_headers.clone()).await?);
+            }
+        }
+    }
 
-    } // #[cfg(not(target_os = "android"))]
+    info["direct"] = serde_json::Value::Object(direct);
+    info["proxied"] = serde_json::Value::Bool(true);
+    Ok(info)
 }
 
 /// Parse yt-dlp JSON output into stream info for the video player.
@@ -2078,16 +3568,327 @@ fn parse_stream_json(json: &serde_json::Value) -> Result<serde_json::Value, Stri
         "duration": duration,
         "uploader": uploader,
         "qualities": qualities,
+        "subtitles": parse_subtitle_tracks(json),
+        "chapters": parse_chapters(json),
+        "storyboards": parse_storyboards(json),
     }))
 }
 
+/// yt-dlp's storyboard formats (`sb0`, `sb1`, ...): each fragment is a
+/// sprite sheet of `rows` x `columns` frames of `width` x `height`, covering
+/// `duration` seconds. Largest frames first.
+fn parse_storyboards(json: &serde_json::Value) -> Vec<serde_json::Value> {
+    let Some(formats) = json["formats"].as_array() else {
+        return Vec::new();
+    };
+    let mut storyboards: Vec<serde_json::Value> = formats
+        .iter()
+        .filter(|f| f["format_note"].as_str() == Some("storyboard"))
+        .filter_map(|f| {
+            let fragments: Vec<serde_json::Value> = f["fragments"]
+                .as_array()?
+                .iter()
+                .filter_map(|frag| {
+                    Some(serde_json::json!({
+                        "url": frag["url"].as_str()?,
+                        "duration": frag["duration"].as_f64().unwrap_or(0.0),
+                    }))
+                })
+                .collect();
+            if fragments.is_empty() {
+                return None;
+            }
+            Some(serde_json::json!({
+                "formatId": f["format_id"].as_str().unwrap_or(""),
+                "width": f["width"].as_i64().unwrap_or(0),
+                "height": f["height"].as_i64().unwrap_or(0),
+                "rows": f["rows"].as_i64().unwrap_or(1),
+                "columns": f["columns"].as_i64().unwrap_or(1),
+                "fps": f["fps"].as_f64().unwrap_or(0.0),
+                "fragments": fragments,
+            }))
+        })
+        .collect();
+    storyboards.sort_by_key(|s| std::cmp::Reverse(s["width"].as_i64().unwrap_or(0)));
+    storyboards
+}
+
+/// `{title, start, end}` chapters in seconds, as listed in yt-dlp's JSON.
+fn parse_chapters(json: &serde_json::Value) -> Vec<serde_json::Value> {
+    json["chapters"]
+        .as_array()
+        .map(|chapters| {
+            chapters
+                .iter()
+                .filter_map(|c| {
+                    let start = c["start_time"].as_f64()?;
+                    Some(serde_json::json!({
+                        "title": c["title"].as_str().unwrap_or(""),
+                        "start": start,
+                        "end": c["end_time"].as_f64().unwrap_or(start),
+                    }))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// One track per language from yt-dlp's `subtitles` and `automatic_captions`,
+/// preferring WebVTT since that's what `<track>` understands. Auto-captions
+/// come in every machine-translated language, so only those in the video's
+/// own language are kept when it is known.
+fn parse_subtitle_tracks(json: &serde_json::Value) -> Vec<serde_json::Value> {
+    const PREFERRED_EXTS: &[&str] = &["vtt", "srv3", "ttml", "srt", "json3"];
+
+    let video_language = json["language"].as_str().unwrap_or("").to_lowercase();
+    let mut tracks = Vec::new();
+
+    for (key, auto) in [("subtitles", false), ("automatic_captions", true)] {
+        let Some(by_language) = json[key].as_object() else {
+            continue;
+        };
+        for (language, formats) in by_language {
+            // "live_chat" is a replay of chat messages, not captions
+            if language == "live_chat" {
+                continue;
+            }
+            if auto && !video_language.is_empty() {
+                let base = language.trim_end_matches("-orig").to_lowercase();
+                if base != video_language && !base.starts_with(&format!("{}-", video_language)) {
+                    continue;
+                }
+            }
+            let Some(formats) = formats.as_array() else {
+                continue;
+            };
+            let chosen = PREFERRED_EXTS
+                .iter()
+                .find_map(|ext| formats.iter().find(|f| f["ext"].as_str() == Some(*ext)))
+                .or_else(|| formats.first());
+            let Some(track) = chosen else {
+                continue;
+            };
+            let Some(track_url) = track["url"].as_str().filter(|u| !u.is_empty()) else {
+                continue;
+            };
+            tracks.push(serde_json::json!({
+                "language": language,
+                "name": track["name"].as_str().unwrap_or(language),
+                "url": track_url,
+                "format": track["ext"].as_str().unwrap_or(""),
+                "auto": auto,
+            }));
+        }
+    }
+
+    tracks
+}
+
+/// Fetch SponsorBlock segments for a video so the player can skip them.
+/// Uses the hash-prefix endpoint so the video id itself is never sent.
+/// `categories` defaults to every category SponsorBlock knows.
+#[tauri::command]
+pub async fn get_sponsor_segments(
+    video_id: String,
+    categories: Option<Vec<String>>,
+) -> Result<Vec<serde_json::Value>, String> {
+    use sha2::Digest;
+
+    let video_id = video_id.trim().to_string();
+    if video_id.is_empty()
+        || video_id.len() > 32
+        || !video_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid video id: {}", video_id));
+    }
+
+    let categories = categories.filter(|c| !c.is_empty()).unwrap_or_else(|| {
+        ["sponsor", "selfpromo", "interaction", "intro", "outro", "preview", "music_offtopic", "filler"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect()
+    });
+    let categories_json = serde_json::to_string(&categories).map_err(|e| e.to_string())?;
+
+    let hash = format!("{:x}", sha2::Sha256::digest(video_id.as_bytes()));
+    let client = crate::proxy::client_builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(format!("https://sponsor.ajay.app/api/skipSegments/{}", &hash[..4]))
+        .query(&[("categories", categories_json.as_str())])
+        .send()
+        .await
+        .map_err(|e| format!("SponsorBlock request failed: {}", e))?;
+
+    // 404 just means no segments for any video with this prefix
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    if !response.status().is_success() {
+        return Err(format!("SponsorBlock API error: {}", response.status()));
+    }
+
+    let videos: Vec<serde_json::Value> = response.json().await.map_err(|e| e.to_string())?;
+    let segments = videos
+        .iter()
+        .find(|v| v["videoID"].as_str() == Some(video_id.as_str()))
+        .and_then(|v| v["segments"].as_array())
+        .map(|segments| {
+            segments
+                .iter()
+                .filter_map(|seg| {
+                    let range = seg["segment"].as_array()?;
+                    Some(serde_json::json!({
+                        "start": range.first()?.as_f64()?,
+                        "end": range.get(1)?.as_f64()?,
+                        "category": seg["category"].as_str().unwrap_or(""),
+                        "actionType": seg["actionType"].as_str().unwrap_or("skip"),
+                        "uuid": seg["UUID"].as_str().unwrap_or(""),
+                    }))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(segments)
+}
+
+// ────────────────────────────────── Playback Positions ──────────────────────────────────
+
+/// Remember where playback of `video_id` stopped. Works for streams and
+/// downloaded files alike, since both are keyed by the video id.
+#[tauri::command]
+pub async fn save_playback_position(
+    db: State<'_, Arc<Mutex<Database>>>,
+    video_id: String,
+    position: f64,
+    duration: f64,
+) -> Result<(), String> {
+    if video_id.trim().is_empty() {
+        return Err("Video id cannot be empty".to_string());
+    }
+    let position = if position.is_finite() { position.max(0.0) } else { 0.0 };
+    let duration = if duration.is_finite() { duration.max(0.0) } else { 0.0 };
+    let watched_percent = if duration > 0.0 {
+        (position / duration * 100.0).clamp(0.0, 100.0)
+    } else {
+        0.0
+    };
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock
+        .save_playback_position(video_id.trim(), position, duration, watched_percent)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_playback_position(
+    db: State<'_, Arc<Mutex<Database>>>,
+    video_id: String,
+) -> Result<Option<serde_json::Value>, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.get_playback_position(video_id.trim()).map_err(|e| e.to_string())
+}
+
+/// All saved positions, most recent first (for "continue watching" lists
+/// and progress badges).
+#[tauri::command]
+pub async fn get_playback_positions(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.get_playback_positions().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn clear_playback_position(
+    db: State<'_, Arc<Mutex<Database>>>,
+    video_id: String,
+) -> Result<(), String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.delete_playback_position(video_id.trim()).map_err(|e| e.to_string())
+}
+
+// ────────────────────────────────── Listen Queue ──────────────────────────────────
+
+#[tauri::command]
+pub async fn get_listen_queue(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.get_listen_queue().map_err(|e| e.to_string())
+}
+
+/// Append feed items to the listen queue. Returns how many were added
+/// (items already queued are skipped).
+#[tauri::command]
+pub async fn add_to_listen_queue(
+    db: State<'_, Arc<Mutex<Database>>>,
+    feed_item_ids: Vec<String>,
+) -> Result<u32, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    let mut added = 0u32;
+    for id in &feed_item_ids {
+        if db_lock.add_feed_item_to_listen_queue(id).map_err(|e| e.to_string())? {
+            added += 1;
+        }
+    }
+    Ok(added)
+}
+
+/// Queue the newest unqueued videos from subscriptions (one feed, or all of
+/// them), like a podcast app's "add new episodes".
+#[tauri::command]
+pub async fn queue_latest_feed_items(
+    db: State<'_, Arc<Mutex<Database>>>,
+    feed_id: Option<String>,
+    limit: Option<u32>,
+) -> Result<u32, AppError> {
+    let limit = limit.unwrap_or(20).clamp(1, 200) as i64;
+    let db_lock = db.lock()?;
+    let ids = db_lock.get_unqueued_feed_item_ids(feed_id.as_deref(), limit)?;
+    let mut added = 0u32;
+    for id in &ids {
+        if db_lock.add_feed_item_to_listen_queue(id)? {
+            added += 1;
+        }
+    }
+    Ok(added)
+}
+
+/// Persist a new play order; `ids` lists queue entry ids front to back.
+#[tauri::command]
+pub async fn reorder_listen_queue(
+    db: State<'_, Arc<Mutex<Database>>>,
+    ids: Vec<String>,
+) -> Result<(), String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.set_listen_queue_order(&ids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_from_listen_queue(
+    db: State<'_, Arc<Mutex<Database>>>,
+    id: String,
+) -> Result<(), String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.remove_from_listen_queue(&id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn clear_listen_queue(db: State<'_, Arc<Mutex<Database>>>) -> Result<(), String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.clear_listen_queue().map_err(|e| e.to_string())
+}
+
 // ────────────────────────────────── RSS Scheduler ──────────────────────────────────
 
 #[tauri::command]
 pub async fn set_rss_check_interval(
     scheduler: State<'_, std::sync::Arc<tokio::sync::Mutex<crate::rss_scheduler::RssScheduler>>>,
     minutes: u64,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let scheduler = scheduler.lock().await;
     scheduler.set_interval(minutes).await;
     Ok(())
@@ -2096,7 +3897,7 @@ pub async fn set_rss_check_interval(
 #[tauri::command]
 pub async fn get_rss_check_interval(
     scheduler: State<'_, std::sync::Arc<tokio::sync::Mutex<crate::rss_scheduler::RssScheduler>>>,
-) -> Result<u64, String> {
+) -> Result<u64, AppError> {
     let scheduler = scheduler.lock().await;
     Ok(scheduler.get_interval().await)
 }
@@ -2105,49 +3906,45 @@ pub async fn get_rss_check_interval(
 pub async fn check_all_rss_feeds(
     app: AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
-) -> Result<u32, String> {
-    let feeds = {
-        let db_lock = db.lock().map_err(|e| e.to_string())?;
-        db_lock.get_feeds().map_err(|e| e.to_string())?
+) -> Result<u32, AppError> {
+    let (feeds, policy) = {
+        let db_lock = db.lock()?;
+        let feeds = db_lock.get_feeds()?;
+        (feeds, rss::FeedPolicy::load(&db_lock))
     };
 
     let mut updated_count = 0u32;
 
     for feed in feeds {
-        let feed_id = feed["id"].as_str().unwrap_or_default().to_string();
-        let feed_url = feed["url"].as_str().unwrap_or_default().to_string();
+        let feed_id = feed.id;
+        let feed_url = feed.url;
 
         if feed_url.is_empty() {
             continue;
         }
+        if let Err(e) = policy.check(&feed_url) {
+            log::warn!("Skipping feed {}: {}", feed_url, e);
+            continue;
+        }
 
         let normalized_url = match rss::normalize_feed_url(&feed_url).await {
             Ok(url) => url,
             Err(_) => continue,
         };
 
-        let (title, items) = match rss::fetch_feed_items_extended(&app, &normalized_url).await {
+        let (title, items) = match rss::fetch_feed_items_extended(&app, &normalized_url, &feed.tabs).await {
             Ok(result) => result,
             Err(_) => continue,
         };
 
         {
-            let db_lock = db.lock().map_err(|e| e.to_string())?;
+            let db_lock = db.lock()?;
             let _ = db_lock.update_feed_last_checked(&feed_id);
             if !title.is_empty() {
                 let _ = db_lock.update_feed_channel_info(&feed_id, &title, "");
             }
-            for item in &items {
-                let _ = db_lock.insert_feed_item(
-                    &item.id,
-                    &feed_id,
-                    &item.video_id,
-                    &item.title,
-                    &item.thumbnail,
-                    &item.url,
-                    &item.published_at,
-                    &item.video_type,
-                );
+            if let Err(e) = db_lock.insert_feed_items(&feed_id, &items) {
+                log::warn!("Failed to save items for feed {}: {}", feed_id, e);
             }
         }
 
@@ -2163,25 +3960,42 @@ pub async fn mark_feed_item_watched(
     db: State<'_, Arc<Mutex<Database>>>,
     item_id: String,
     watched: bool,
-) -> Result<(), String> {
-    let db_lock = db.lock().map_err(|e| e.to_string())?;
-    db_lock
-        .update_feed_item_downloaded(&item_id, watched)
-        .map_err(|e| e.to_string())?;
-    Ok(())
+) -> Result<(), AppError> {
+    let db_lock = db.lock()?;
+    db_lock.update_feed_item_downloaded(&item_id, watched)
 }
 
+/// `folder_template`, `tabs` and `cookie_profile` are left alone when not
+/// given; see [`rss::render_folder_template`], [`rss::CHANNEL_TABS`] and
+/// [`crate::cookie_profiles`]. An empty `cookie_profile` unsets it.
 #[tauri::command]
 pub async fn update_feed_settings(
     db: State<'_, Arc<Mutex<Database>>>,
     feed_id: String,
     keywords: String,
     auto_download: bool,
-) -> Result<(), String> {
-    let db_lock = db.lock().map_err(|e| e.to_string())?;
-    db_lock
-        .update_feed_settings(&feed_id, &keywords, auto_download)
-        .map_err(|e| e.to_string())?;
+    folder_template: Option<String>,
+    tabs: Option<String>,
+    cookie_profile: Option<String>,
+) -> Result<(), AppError> {
+    let tabs = tabs.map(|t| rss::validate_channel_tabs(&t)).transpose()?;
+    let db_lock = db.lock()?;
+    db_lock.update_feed_settings(&feed_id, &keywords, auto_download)?;
+    if let Some(template) = folder_template {
+        let template = template.trim();
+        rss::validate_folder_template(template)?;
+        db_lock.update_feed_folder_template(&feed_id, template)?;
+    }
+    if let Some(tabs) = tabs {
+        db_lock.update_feed_tabs(&feed_id, &tabs)?;
+    }
+    if let Some(profile) = cookie_profile {
+        let profile_id = match profile.trim() {
+            "" => String::new(),
+            profile => crate::cookie_profiles::find(&db_lock, profile)?.id,
+        };
+        db_lock.update_feed_cookie_profile(&feed_id, &profile_id)?;
+    }
     Ok(())
 }
 
@@ -2247,7 +4061,7 @@ pub async fn resume_all_downloads(
 
     let paused_ids: Vec<String> = downloads
         .iter()
-        .filter(|d| d["status"].as_str() == Some("paused"))
+        .filter(|d| matches!(d["status"].as_str(), Some("paused") | Some("interrupted")))
         .filter_map(|d| d["id"].as_str().map(String::from))
         .collect();
 
@@ -2318,17 +4132,169 @@ pub async fn cancel_all_downloads(
     Ok(cancelled_count)
 }
 
+// ────────────────────────────────── Download batches ──────────────────────────────────
+//
+// Downloads queued together (a playlist, a channel mirror sync, a feed's new
+// videos) share a `batch_id`, so they can be controlled as one.
+
+fn batch_downloads(db: &Arc<Mutex<Database>>, batch_id: &str) -> Result<Vec<serde_json::Value>, String> {
+    if batch_id.trim().is_empty() {
+        return Err("Batch id is required".to_string());
+    }
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    let downloads = db_lock.get_batch_downloads(batch_id).map_err(|e| e.to_string())?;
+    if downloads.is_empty() {
+        return Err(format!("Batch not found: {}", batch_id));
+    }
+    Ok(downloads)
+}
+
+/// Pause every running or waiting download of a batch.
+#[tauri::command]
+pub async fn pause_batch(
+    db: State<'_, Arc<Mutex<Database>>>,
+    dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
+    batch_id: String,
+) -> Result<u32, String> {
+    let downloads = batch_downloads(db.inner(), &batch_id)?;
+    let mut paused_count = 0u32;
+    for download in &downloads {
+        let status = download["status"].as_str().unwrap_or("");
+        if !matches!(status, "downloading" | "queued" | "merging" | "converting") {
+            continue;
+        }
+        let Some(id) = download["id"].as_str() else { continue };
+        {
+            let dm = dl.lock().await;
+            if let Some(active) = dm.active.get(id) {
+                let _ = active.cancel_token.send(true);
+            }
+        }
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let _ = db_lock.update_download_status(id, "paused");
+        paused_count += 1;
+    }
+    Ok(paused_count)
+}
+
+/// Cancel every download of a batch that hasn't finished.
+#[tauri::command]
+pub async fn cancel_batch(
+    db: State<'_, Arc<Mutex<Database>>>,
+    dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
+    batch_id: String,
+) -> Result<u32, String> {
+    let downloads = batch_downloads(db.inner(), &batch_id)?;
+    let mut cancelled_count = 0u32;
+    for download in &downloads {
+        let status = download["status"].as_str().unwrap_or("");
+        if !matches!(
+            status,
+            "downloading" | "queued" | "merging" | "converting" | "paused" | "interrupted" | "pending"
+        ) {
+            continue;
+        }
+        let Some(id) = download["id"].as_str() else { continue };
+        {
+            let mut dl_lock = dl.lock().await;
+            dl_lock.cancel(id);
+        }
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let _ = db_lock.update_download_status(id, "cancelled");
+        cancelled_count += 1;
+    }
+    Ok(cancelled_count)
+}
+
+/// Queue the paused, interrupted, failed and cancelled downloads of a batch
+/// again. They start in their original order, `playlist_concurrency` at a
+/// time.
+#[tauri::command]
+pub async fn retry_batch(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
+    batch_id: String,
+) -> Result<u32, String> {
+    let downloads = batch_downloads(db.inner(), &batch_id)?;
+    let mut entries = Vec::new();
+    {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        for download in &downloads {
+            let status = download["status"].as_str().unwrap_or("");
+            if !matches!(status, "paused" | "interrupted" | "error" | "cancelled") {
+                continue;
+            }
+            let (Some(id), Some(url)) = (download["id"].as_str(), download["url"].as_str()) else {
+                continue;
+            };
+            if url.is_empty() {
+                continue;
+            }
+            let format_id = download["formatId"]
+                .as_str()
+                .filter(|s| !s.is_empty())
+                .map(String::from);
+            db_lock
+                .update_download_status(id, "queued")
+                .map_err(|e| e.to_string())?;
+            entries.push((id.to_string(), url.to_string(), format_id));
+        }
+    }
+    let count = entries.len() as u32;
+    if !entries.is_empty() {
+        crate::playlist_commands::start_queued(&app, db.inner(), dl.inner(), entries, None)?;
+    }
+    Ok(count)
+}
+
+/// Status counts and overall progress of a batch.
+#[tauri::command]
+pub async fn get_batch_progress(
+    db: State<'_, Arc<Mutex<Database>>>,
+    batch_id: String,
+) -> Result<serde_json::Value, String> {
+    let downloads = batch_downloads(db.inner(), &batch_id)?;
+    let count = |statuses: &[&str]| {
+        downloads
+            .iter()
+            .filter(|d| statuses.contains(&d["status"].as_str().unwrap_or("")))
+            .count()
+    };
+    // Finished downloads count as 100%, whatever their last progress event said
+    let progress = downloads
+        .iter()
+        .map(|d| match d["status"].as_str() {
+            Some("completed") => 100.0,
+            _ => d["progress"].as_f64().unwrap_or(0.0).clamp(0.0, 100.0),
+        })
+        .sum::<f64>()
+        / downloads.len() as f64;
+    Ok(serde_json::json!({
+        "batchId": batch_id,
+        "total": downloads.len(),
+        "completed": count(&["completed"]),
+        "active": count(&["downloading", "merging", "converting"]),
+        "queued": count(&["queued", "pending"]),
+        "paused": count(&["paused", "interrupted"]),
+        "failed": count(&["error"]),
+        "cancelled": count(&["cancelled"]),
+        "progress": progress,
+    }))
+}
+
 // ────────────────────────────────── Termux download metadata extraction ──────────────────────────────────
 
 /// Scan the output directory for .info.json files written by yt-dlp --write-info-json.
-/// Returns (title, thumbnail_url) extracted from the most recently modified .info.json.
+/// Returns (title, thumbnail_url, details) extracted from the most recently modified
+/// .info.json, which is deleted afterwards unless `keep` is set.
 #[cfg(target_os = "android")]
-fn extract_info_json_metadata(output_dir: &str) -> (String, String) {
+fn extract_info_json_metadata(output_dir: &str, keep: bool) -> (String, String, Option<download::MediaDetails>) {
     use std::path::Path;
 
     let dir = Path::new(output_dir);
     if !dir.exists() {
-        return (String::new(), String::new());
+        return (String::new(), String::new(), None);
     }
 
     // Find the most recently modified .info.json file
@@ -2354,7 +4320,7 @@ fn extract_info_json_metadata(output_dir: &str) -> (String, String) {
 
     let Some(json_path) = best_path else {
         log::debug!("[extract_info_json_metadata] No .info.json found in {}", output_dir);
-        return (String::new(), String::new());
+        return (String::new(), String::new(), None);
     };
 
     match std::fs::read_to_string(&json_path) {
@@ -2369,26 +4335,28 @@ fn extract_info_json_metadata(output_dir: &str) -> (String, String) {
                         if thumbnail.is_empty() { "(empty)" } else { &thumbnail }
                     );
                     // Clean up the .info.json file after reading
-                    let _ = std::fs::remove_file(&json_path);
-                    (title, thumbnail)
+                    if !keep {
+                        let _ = std::fs::remove_file(&json_path);
+                    }
+                    (title, thumbnail, Some(download::MediaDetails::from_json(&json)))
                 }
                 Err(e) => {
                     log::warn!("[extract_info_json_metadata] Failed to parse JSON: {}", e);
-                    (String::new(), String::new())
+                    (String::new(), String::new(), None)
                 }
             }
         }
         Err(e) => {
             log::warn!("[extract_info_json_metadata] Failed to read file: {}", e);
-            (String::new(), String::new())
+            (String::new(), String::new(), None)
         }
     }
 }
 
 #[cfg(not(target_os = "android"))]
 #[allow(dead_code)]
-fn extract_info_json_metadata(_output_dir: &str) -> (String, String) {
-    (String::new(), String::new())
+fn extract_info_json_metadata(_output_dir: &str, _keep: bool) -> (String, String, Option<download::MediaDetails>) {
+    (String::new(), String::new(), None)
 }
 
 // ────────────────────────────────── Termux download completion poller ──────────────────────────────────
@@ -2399,6 +4367,9 @@ fn extract_info_json_metadata(_output_dir: &str) -> (String, String) {
 /// The sentinel file lives at `<output_dir>/.status/<download_id>` and contains:
 /// - Line 1: "OK" (success) or "FAIL:<exit_code>" (error)
 /// - Line 2 (optional, success only): absolute path to the most recently modified file
+///
+/// While it waits the download is listed in the foreground service's
+/// notification, which keeps Android from killing the app in the background.
 #[cfg(target_os = "android")]
 async fn poll_termux_download_status(
     app: &tauri::AppHandle,
@@ -2406,6 +4377,42 @@ async fn poll_termux_download_status(
     download_id: &str,
     output_dir: &str,
 ) {
+    use crate::notifications::{self, NotifyEvent};
+
+    let (title, _) = notifications::download_title(app, download_id);
+    if let Err(e) = crate::android_bridge::download_service_started(download_id, &title) {
+        log::warn!("[poll_termux] Could not start download service: {}", e);
+    }
+
+    let outcome = watch_termux_sentinel(app, db, download_id, output_dir).await;
+
+    // The title may have been filled in from .info.json by now
+    let (title, _) = notifications::download_title(app, download_id);
+    let (event, message) = match &outcome {
+        Some(Ok(())) => (Some(NotifyEvent::DownloadComplete), ""),
+        Some(Err(e)) => (Some(NotifyEvent::DownloadFailed), e.as_str()),
+        None => (None, ""),
+    };
+    let result = match event {
+        Some(event) if notifications::is_enabled(app, event) => {
+            if event == NotifyEvent::DownloadComplete { "completed" } else { "failed" }
+        }
+        _ => "",
+    };
+    if let Err(e) = crate::android_bridge::download_service_finished(download_id, &title, result, message) {
+        log::warn!("[poll_termux] Could not update download service: {}", e);
+    }
+}
+
+/// Wait for the sentinel and record the result. Returns `None` when the
+/// download was finished or cancelled from the app before Termux reported.
+#[cfg(target_os = "android")]
+async fn watch_termux_sentinel(
+    app: &tauri::AppHandle,
+    db: &Arc<Mutex<Database>>,
+    download_id: &str,
+    output_dir: &str,
+) -> Option<Result<(), String>> {
     use std::path::Path;
 
     let status_file = format!("{}/.status/{}", output_dir, download_id);
@@ -2432,7 +4439,7 @@ async fn poll_termux_download_status(
                             log::info!("[poll_termux] Download {} already in terminal state '{}', stopping poller", download_id, status);
                             // Clean up sentinel file if it exists
                             let _ = std::fs::remove_file(&status_file);
-                            return;
+                            return None;
                         }
                     }
                 }
@@ -2460,7 +4467,7 @@ async fn poll_termux_download_status(
         let lines: Vec<&str> = contents.lines().collect();
         let status_line = lines.first().unwrap_or(&"").trim();
 
-        if status_line == "OK" || status_line.starts_with("OK") {
+        let outcome = if status_line == "OK" || status_line.starts_with("OK") {
             // Success! Try to get the output file path
             let file_path = lines.get(1).map(|s| s.trim().to_string()).unwrap_or_default();
 
@@ -2480,11 +4487,19 @@ async fn poll_termux_download_status(
             );
 
             // Try to extract metadata from .info.json files in the output dir
-            let (meta_title, meta_thumbnail) = extract_info_json_metadata(output_dir);
+            let keep_info_json = db.lock().map(|db_lock| wants_info_json(&db_lock)).unwrap_or(false);
+            let (meta_title, meta_thumbnail, details) = extract_info_json_metadata(output_dir, keep_info_json);
+
+            // Hand the file over to the media collections / picked folder
+            let stored_path = crate::android_commands::move_to_storage_target(db, &file_path)
+                .unwrap_or_else(|| file_path.clone());
 
             // Update DB
             if let Ok(db_lock) = db.lock() {
-                let _ = db_lock.update_download_complete(download_id, &file_path, file_size);
+                let _ = db_lock.update_download_complete(download_id, &stored_path, file_size);
+                if let Some(details) = &details {
+                    let _ = db_lock.set_download_details(download_id, details);
+                }
                 // Update title/thumbnail if we found metadata
                 if !meta_title.is_empty() || !meta_thumbnail.is_empty() {
                     let final_title = if !meta_title.is_empty() { &meta_title } else { &file_path };
@@ -2506,9 +4521,10 @@ async fn poll_termux_download_status(
                 "download-complete",
                 serde_json::json!({
                     "id": download_id,
-                    "outputPath": file_path,
+                    "outputPath": stored_path,
                 }),
             );
+            Ok(())
         } else if status_line.starts_with("FAIL") {
             let error_msg = format!("yt-dlp exited with error ({})", status_line);
             log::warn!("[poll_termux] Download {} failed: {}", download_id, error_msg);
@@ -2524,6 +4540,7 @@ async fn poll_termux_download_status(
                     "error": error_msg,
                 }),
             );
+            Err(error_msg)
         } else {
             // Unknown status — treat as completed (sentinel exists => yt-dlp finished)
             log::warn!("[poll_termux] Unknown sentinel content for {}: {:?}", download_id, status_line);
@@ -2540,12 +4557,13 @@ async fn poll_termux_download_status(
                     "outputPath": "",
                 }),
             );
-        }
+            Ok(())
+        };
 
         // Clean up sentinel file
         let _ = std::fs::remove_file(&status_file);
         log::info!("[poll_termux] Poller finished for download {}", download_id);
-        return;
+        return Some(outcome);
     }
 
     // Timeout — mark as completed (the download probably finished but we can't confirm)
@@ -2562,6 +4580,7 @@ async fn poll_termux_download_status(
             "outputPath": "",
         }),
     );
+    Some(Ok(()))
 }
 
 /// Stub for non-Android — never called but keeps the code compiling.
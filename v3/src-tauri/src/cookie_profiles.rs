@@ -0,0 +1,126 @@
+//! Named cookie sources.
+//!
+//! The `browser_cookies` setting signs every download in to one account,
+//! but memberships are often spread over several. A cookie profile is a
+//! named source, either a browser (with keyring, profile and container in
+//! yt-dlp's `BROWSER[+KEYRING][:PROFILE][::CONTAINER]` syntax) or a
+//! Netscape `cookies.txt` file. Downloads, feeds and the stream player can
+//! name one; it then replaces `browser_cookies` for them. A feed's profile
+//! applies to every download of one of its items that doesn't name a
+//! profile or browser of its own.
+
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::db::Database;
+
+/// Values accepted for a profile's `kind`.
+pub const KINDS: &[&str] = &["browser", "file"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CookieProfile {
+    pub id: String,
+    pub name: String,
+    /// "browser" or "file".
+    pub kind: String,
+    /// A `--cookies-from-browser` value or the path of a cookies.txt file.
+    pub source: String,
+}
+
+impl CookieProfile {
+    /// yt-dlp flags that load the profile's cookies.
+    pub fn ytdlp_args(&self) -> Vec<String> {
+        let flag = if self.kind == "file" { "--cookies" } else { "--cookies-from-browser" };
+        vec![flag.to_string(), self.source.clone()]
+    }
+}
+
+fn validate(name: &str, kind: &str, source: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("A cookie profile needs a name".to_string());
+    }
+    match kind {
+        "browser" => {
+            crate::settings::check_cookie_browser(source)?;
+            if source.eq_ignore_ascii_case("none") {
+                return Err("Pick the browser to read cookies from".to_string());
+            }
+            Ok(())
+        }
+        "file" => {
+            let path = std::path::Path::new(source);
+            if !path.is_absolute() || !path.is_file() {
+                return Err(format!("Cookies file not found: {}", source));
+            }
+            Ok(())
+        }
+        _ => Err(format!("Unknown cookie profile kind '{}'", kind)),
+    }
+}
+
+/// The profile with this id or (case-insensitive) name.
+pub fn find(db: &Database, profile: &str) -> Result<CookieProfile, String> {
+    let profile = profile.trim();
+    db.get_cookie_profiles()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|p| p.id == profile || p.name.eq_ignore_ascii_case(profile))
+        .ok_or_else(|| format!("Cookie profile not found: {}", profile))
+}
+
+/// The flags of a stored profile id; none when it is empty or the
+/// profile has been deleted since.
+pub fn stored_args(db: &Database, id: &str) -> Option<Vec<String>> {
+    if id.is_empty() {
+        return None;
+    }
+    find(db, id).ok().map(|p| p.ytdlp_args())
+}
+
+#[tauri::command]
+pub async fn get_cookie_profiles(db: State<'_, Arc<Mutex<Database>>>) -> Result<Vec<CookieProfile>, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.get_cookie_profiles().map_err(|e| e.to_string())
+}
+
+/// Add a profile, or change the one with `id`.
+#[tauri::command]
+pub async fn save_cookie_profile(
+    db: State<'_, Arc<Mutex<Database>>>,
+    id: Option<String>,
+    name: String,
+    kind: String,
+    source: String,
+) -> Result<CookieProfile, String> {
+    let (name, kind, source) = (name.trim(), kind.trim().to_lowercase(), source.trim());
+    validate(name, &kind, source)?;
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    let id = id.filter(|id| !id.is_empty());
+    let clash = db_lock
+        .get_cookie_profiles()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .any(|p| p.name.eq_ignore_ascii_case(name) && Some(&p.id) != id.as_ref());
+    if clash {
+        return Err(format!("A cookie profile named '{}' already exists", name));
+    }
+    let profile = CookieProfile {
+        id: id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        name: name.to_string(),
+        kind,
+        source: source.to_string(),
+    };
+    db_lock.save_cookie_profile(&profile).map_err(|e| e.to_string())?;
+    Ok(profile)
+}
+
+/// Delete a profile. Feeds and downloads using it go back to
+/// `browser_cookies`.
+#[tauri::command]
+pub async fn delete_cookie_profile(db: State<'_, Arc<Mutex<Database>>>, id: String) -> Result<(), String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.delete_cookie_profile(&id).map_err(|e| e.to_string())
+}
@@ -109,18 +109,88 @@ impl Database {
             INSERT OR IGNORE INTO settings (key, value) VALUES ('theme', 'system');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('language', 'en');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('notifications', 'true');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('notify_download_complete', 'true');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('notify_download_failed', 'true');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('notify_transcription_done', 'true');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('notify_tool_update', 'true');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('feed_https_only', 'false');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('feed_allowed_hosts', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('feed_blocked_hosts', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('webhook_url', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('webhook_payload', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('media_server', 'none');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('media_server_url', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('media_server_library', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('media_server_token', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('watch_folder', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('log_retention_days', '7');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('ytdlp_requests_per_minute', '30');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('ytdlp_cooldown_minutes', '10');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('android_storage_target', 'app');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('download_wifi_only', 'false');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('battery_min_percent', '0');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('battery_resume_percent', '30');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('quiet_hours_start', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('quiet_hours_end', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('quiet_hours_pause_active', 'false');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('download_quota_gb', '0');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('download_quota_period', 'day');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('live_auto_record', 'false');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('live_notify_minutes', '10');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('android_saf_tree_uri', '');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('close_to_tray', 'false');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('auto_launch', 'false');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('start_minimized', 'true');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('auto_start_download', 'true');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('embed_thumbnail', 'true');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('embed_metadata', 'true');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('embed_chapters', 'true');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('write_info_json', 'false');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('merge_policy', 'reencode');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('concurrent_fragments', '1');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('browser_cookies', 'none');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('cookies_fallback_browser', 'none');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('ytdlp_flags', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('ytdlp_extractor_args', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('ytdlp_channel', 'stable');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('ytdlp_path', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('ffmpeg_path', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('use_system_proxy', 'true');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('proxy_url', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('ytdlp_mirrors', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('ffmpeg_mirrors', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('whisper_mirrors', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('ytdlp_checksums', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('ffmpeg_checksums', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('whisper_checksums', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('api_enabled', 'false');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('api_port', '9787');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('api_token', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('preferred_vcodec', 'any');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('preferred_dynamic_range', 'any');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('max_fps', '0');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('min_audio_bitrate', '0');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('video_download_path', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('audio_download_path', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('shorts_download_path', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('podcast_download_path', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('playlist_subfolder', 'true');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('playlist_concurrency', '1');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_provider', 'api');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('openai_api_key', '');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('openai_model', 'whisper-1');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('whisper_cpp_path', '');
             INSERT OR IGNORE INTO settings (key, value) VALUES ('whisper_model_path', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_api_provider', 'openai');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_api_key', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_api_model', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_api_url', '');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_api_auth_header', 'Authorization');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_api_auth_prefix', 'Bearer ');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_api_format', 'multipart');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_api_text_path', '/text');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_api_language_path', '/language');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('transcribe_api_segments_path', '/segments');
             ",
         )?;
 
@@ -145,6 +215,313 @@ impl Database {
             self.set_schema_version(2);
         }
 
+        if current_version < 3 {
+            // Migration 3: Timed segments on transcripts + full-text index.
+            // The index is keyed on `fts_id`, an INTEGER stored with each
+            // row, since the implicit rowid of a table with a TEXT primary
+            // key may change on VACUUM.
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute_batch(
+                "
+                ALTER TABLE transcripts ADD COLUMN segments TEXT DEFAULT '[]';
+                ALTER TABLE transcripts ADD COLUMN fts_id INTEGER;
+                UPDATE transcripts SET fts_id = rowid;
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_transcripts_fts_id ON transcripts(fts_id);
+
+                CREATE VIRTUAL TABLE transcripts_fts
+                USING fts5(title, text, content='transcripts', content_rowid='fts_id');
+
+                CREATE TRIGGER transcripts_fts_ai AFTER INSERT ON transcripts BEGIN
+                    INSERT INTO transcripts_fts(rowid, title, text) VALUES (new.fts_id, new.title, new.text);
+                END;
+
+                CREATE TRIGGER transcripts_fts_ad AFTER DELETE ON transcripts BEGIN
+                    INSERT INTO transcripts_fts(transcripts_fts, rowid, title, text)
+                    VALUES ('delete', old.fts_id, old.title, old.text);
+                END;
+
+                CREATE TRIGGER transcripts_fts_au AFTER UPDATE OF title, text ON transcripts BEGIN
+                    INSERT INTO transcripts_fts(transcripts_fts, rowid, title, text)
+                    VALUES ('delete', old.fts_id, old.title, old.text);
+                    INSERT INTO transcripts_fts(rowid, title, text) VALUES (new.fts_id, new.title, new.text);
+                END;
+
+                INSERT INTO transcripts_fts(transcripts_fts) VALUES ('rebuild');
+                ",
+            )?;
+            tx.commit()?;
+            self.set_schema_version(3);
+        }
+
+        if current_version < 4 {
+            // Migration 4: Link transcripts to the download they were made from
+            let _ = self.conn.execute(
+                "ALTER TABLE transcripts ADD COLUMN download_id TEXT REFERENCES downloads(id) ON DELETE SET NULL", []);
+            self.set_schema_version(4);
+        }
+
+        if current_version < 5 {
+            // Migration 5: Per-entry playlist tracking
+            let _ = self.conn.execute_batch(
+                "
+                CREATE TABLE IF NOT EXISTS playlist_items (
+                    id TEXT PRIMARY KEY,
+                    playlist_id TEXT NOT NULL REFERENCES playlists(id) ON DELETE CASCADE,
+                    download_id TEXT REFERENCES downloads(id) ON DELETE SET NULL,
+                    video_id TEXT NOT NULL,
+                    url TEXT NOT NULL DEFAULT '',
+                    title TEXT NOT NULL DEFAULT '',
+                    thumbnail TEXT DEFAULT '',
+                    playlist_index INTEGER NOT NULL DEFAULT 0,
+                    status TEXT NOT NULL DEFAULT 'pending',
+                    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+                    UNIQUE(playlist_id, video_id)
+                );
+                ",
+            );
+            self.set_schema_version(5);
+        }
+
+        if current_version < 6 {
+            // Migration 6: Channel mirrors are playlists with their own folder/format
+            let _ = self.conn.execute(
+                "ALTER TABLE playlists ADD COLUMN kind TEXT DEFAULT 'playlist'", []);
+            let _ = self.conn.execute(
+                "ALTER TABLE playlists ADD COLUMN output_dir TEXT DEFAULT ''", []);
+            let _ = self.conn.execute(
+                "ALTER TABLE playlists ADD COLUMN format_id TEXT DEFAULT ''", []);
+            self.set_schema_version(6);
+        }
+
+        if current_version < 7 {
+            // Migration 7: Resume points for streams and downloaded files
+            let _ = self.conn.execute_batch(
+                "
+                CREATE TABLE IF NOT EXISTS playback_positions (
+                    video_id TEXT PRIMARY KEY,
+                    position_secs REAL NOT NULL DEFAULT 0,
+                    duration_secs REAL NOT NULL DEFAULT 0,
+                    watched_percent REAL NOT NULL DEFAULT 0,
+                    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+                );
+                ",
+            );
+            self.set_schema_version(7);
+        }
+
+        if current_version < 8 {
+            // Migration 8: Audio listening queue built from feed items
+            let _ = self.conn.execute_batch(
+                "
+                CREATE TABLE IF NOT EXISTS listen_queue (
+                    id TEXT PRIMARY KEY,
+                    feed_item_id TEXT REFERENCES feed_items(id) ON DELETE SET NULL,
+                    video_id TEXT NOT NULL UNIQUE,
+                    url TEXT NOT NULL DEFAULT '',
+                    title TEXT NOT NULL DEFAULT '',
+                    thumbnail TEXT DEFAULT '',
+                    channel TEXT DEFAULT '',
+                    sort_order INTEGER NOT NULL DEFAULT 0,
+                    added_at TEXT NOT NULL DEFAULT (datetime('now'))
+                );
+                ",
+            );
+            self.set_schema_version(8);
+        }
+
+        if current_version < 9 {
+            // Migration 9: Per-download webhook target and delivery log
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN webhook_url TEXT DEFAULT ''", []);
+            let _ = self.conn.execute_batch(
+                "
+                CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                    id TEXT PRIMARY KEY,
+                    download_id TEXT,
+                    url TEXT NOT NULL,
+                    event TEXT NOT NULL,
+                    success INTEGER NOT NULL DEFAULT 0,
+                    status_code INTEGER,
+                    attempts INTEGER NOT NULL DEFAULT 0,
+                    error TEXT DEFAULT '',
+                    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+                );
+                CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_created
+                ON webhook_deliveries(created_at DESC);
+                ",
+            );
+            self.set_schema_version(9);
+        }
+
+        if current_version < 10 {
+            // Migration 10: Clip of the video to download (`--download-sections`)
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN section TEXT DEFAULT ''", []);
+            self.set_schema_version(10);
+        }
+
+        if current_version < 11 {
+            // Migration 11: "Only since date" filter for playlists and mirrors
+            let _ = self.conn.execute(
+                "ALTER TABLE playlists ADD COLUMN date_after TEXT DEFAULT ''", []);
+            self.set_schema_version(11);
+        }
+
+        if current_version < 12 {
+            // Migration 12: Why a download needed something unusual (e.g. cookies)
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN note TEXT DEFAULT ''", []);
+            self.set_schema_version(12);
+        }
+
+        if current_version < 13 {
+            // Migration 13: Download modes (archive bundles) and their files
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN mode TEXT DEFAULT ''", []);
+            let _ = self.conn.execute_batch(
+                "
+                CREATE TABLE IF NOT EXISTS download_artifacts (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    download_id TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    path TEXT NOT NULL,
+                    size INTEGER NOT NULL DEFAULT 0
+                );
+                CREATE INDEX IF NOT EXISTS idx_download_artifacts_download_id
+                ON download_artifacts(download_id);
+                ",
+            );
+            self.set_schema_version(13);
+        }
+
+        if current_version < 14 {
+            // Migration 14: Metadata from the .info.json of a download
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN description TEXT DEFAULT ''", []);
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN tags TEXT DEFAULT ''", []);
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN upload_date TEXT DEFAULT ''", []);
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN view_count INTEGER", []);
+            self.set_schema_version(14);
+        }
+
+        if current_version < 15 {
+            // Migration 15: Speed samples of a download, JSON
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN speed_history TEXT", []);
+            self.set_schema_version(15);
+        }
+
+        if current_version < 16 {
+            // Migration 16: Per-download `-N` override, 0 = use the setting
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN concurrent_fragments INTEGER DEFAULT 0", []);
+            self.set_schema_version(16);
+        }
+
+        if current_version < 17 {
+            // Migration 17: Per-download destination folder, '' = from the settings
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN output_dir TEXT DEFAULT ''", []);
+            self.set_schema_version(17);
+        }
+
+        if current_version < 18 {
+            // Migration 18: Downloads queued together (a playlist, a mirror sync) share a batch id
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN batch_id TEXT DEFAULT ''", []);
+            self.set_schema_version(18);
+        }
+
+        if current_version < 19 {
+            // Migration 19: Media duration in seconds, from the .info.json
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN duration REAL", []);
+            self.set_schema_version(19);
+        }
+
+        if current_version < 20 {
+            // Migration 20: Per-feed folder template for auto-downloads
+            let _ = self.conn.execute(
+                "ALTER TABLE feeds ADD COLUMN folder_template TEXT NOT NULL DEFAULT ''", []);
+            self.set_schema_version(20);
+        }
+
+        if current_version < 21 {
+            // Migration 21: Bytes downloaded per local day, for the download quota
+            let _ = self.conn.execute_batch(
+                "
+                CREATE TABLE IF NOT EXISTS data_usage (
+                    day TEXT PRIMARY KEY,
+                    bytes INTEGER NOT NULL DEFAULT 0
+                );
+                ",
+            );
+            self.set_schema_version(21);
+        }
+
+        if current_version < 22 {
+            // Migration 22: Channel tabs a YouTube feed follows
+            let _ = self.conn.execute(
+                "ALTER TABLE feeds ADD COLUMN tabs TEXT NOT NULL DEFAULT 'videos,shorts'", []);
+            self.set_schema_version(22);
+        }
+
+        if current_version < 23 {
+            // Migration 23: Scheduled premieres and livestreams seen in feeds
+            let _ = self.conn.execute_batch(
+                "
+                CREATE TABLE IF NOT EXISTS upcoming_streams (
+                    video_id TEXT PRIMARY KEY,
+                    feed_id TEXT NOT NULL,
+                    title TEXT NOT NULL DEFAULT '',
+                    url TEXT NOT NULL,
+                    thumbnail TEXT NOT NULL DEFAULT '',
+                    scheduled_at INTEGER NOT NULL,
+                    notified INTEGER NOT NULL DEFAULT 0,
+                    download_id TEXT NOT NULL DEFAULT ''
+                );
+                ",
+            );
+            self.set_schema_version(23);
+        }
+
+        if current_version < 24 {
+            // Migration 24: Named cookie sources, picked per download and per feed
+            let _ = self.conn.execute_batch(
+                "
+                CREATE TABLE IF NOT EXISTS cookie_profiles (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    source TEXT NOT NULL,
+                    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+                );
+                ",
+            );
+            let _ = self.conn.execute(
+                "ALTER TABLE downloads ADD COLUMN cookie_profile TEXT NOT NULL DEFAULT ''", []);
+            let _ = self.conn.execute(
+                "ALTER TABLE feeds ADD COLUMN cookie_profile TEXT NOT NULL DEFAULT ''", []);
+            self.set_schema_version(24);
+        }
+
+        if current_version < 25 {
+            // Migration 25: Whether a playlist item's download was created for
+            // the playlist or only linked to an existing one
+            let _ = self.conn.execute(
+                "ALTER TABLE playlist_items ADD COLUMN owns_download INTEGER NOT NULL DEFAULT 0", []);
+            let _ = self.conn.execute(
+                "UPDATE playlist_items SET owns_download = 1
+                 WHERE download_id IN (SELECT id FROM downloads WHERE source = 'playlist')",
+                [],
+            );
+            self.set_schema_version(25);
+        }
+
         // Indexes (idempotent — CREATE IF NOT EXISTS)
         self.conn.execute_batch(
             "
@@ -159,6 +536,18 @@ impl Database {
 
             CREATE INDEX IF NOT EXISTS idx_downloads_url_format
             ON downloads(url, format_id);
+
+            CREATE INDEX IF NOT EXISTS idx_downloads_batch_id
+            ON downloads(batch_id);
+
+            CREATE INDEX IF NOT EXISTS idx_transcripts_download_id
+            ON transcripts(download_id);
+
+            CREATE INDEX IF NOT EXISTS idx_playlist_items_playlist_index
+            ON playlist_items(playlist_id, playlist_index);
+
+            CREATE INDEX IF NOT EXISTS idx_playlist_items_download_id
+            ON playlist_items(download_id);
             ",
         )?;
         
@@ -207,6 +596,16 @@ impl Database {
         Ok(())
     }
 
+    /// Add a download in the `queued` state, to be started later by whoever
+    /// processes the queue (e.g. `ytdl run`).
+    pub fn queue_download(&self, id: &str, url: &str, format_id: &str, source: &str) -> AppResult<()> {
+        self.conn.execute(
+            "INSERT INTO downloads (id, url, title, format_id, source, status) VALUES (?1, ?2, ?2, ?3, ?4, 'queued')",
+            params![id, url, format_id, source],
+        )?;
+        Ok(())
+    }
+
     pub fn update_download_status(&self, id: &str, status: &str) -> AppResult<()> {
         self.conn.execute(
             "UPDATE downloads SET status = ?2, updated_at = datetime('now') WHERE id = ?1",
@@ -242,6 +641,18 @@ impl Database {
         Ok(())
     }
 
+    /// Mark downloads left mid-transfer by a crash or forced quit as
+    /// `interrupted`. Returns how many were changed.
+    pub fn mark_interrupted_downloads(&self) -> AppResult<usize> {
+        let changed = self.conn.execute(
+            "UPDATE downloads SET status = 'interrupted', speed = '', eta = '',
+                error = 'Interrupted when the app last closed', updated_at = datetime('now')
+             WHERE status IN ('downloading', 'merging')",
+            [],
+        )?;
+        Ok(changed)
+    }
+
     pub fn update_download_error(&self, id: &str, error: &str) -> AppResult<()> {
         // Don't overwrite "paused" or "cancelled" status — those are user-initiated
         // and must be preserved so "Resume All" can find paused downloads.
@@ -262,12 +673,236 @@ impl Database {
         Ok(())
     }
 
+    /// Store the format selector a download was started with and its label.
+    pub fn set_download_format(&self, id: &str, format_id: &str, format_label: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE downloads SET format_id = ?2, format_label = ?3 WHERE id = ?1",
+            params![id, format_id, format_label],
+        )?;
+        Ok(())
+    }
+
+    /// Store the clip (`--download-sections` value) a download is limited to.
+    pub fn set_download_section(&self, id: &str, section: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE downloads SET section = ?2 WHERE id = ?1",
+            params![id, section],
+        )?;
+        Ok(())
+    }
+
+    /// Explain something about how a download was done, shown under it.
+    pub fn set_download_note(&self, id: &str, note: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE downloads SET note = ?2, updated_at = datetime('now') WHERE id = ?1",
+            params![id, note],
+        )?;
+        Ok(())
+    }
+
+    /// Remember a download's mode (`archive`), so resuming it keeps it.
+    pub fn set_download_mode(&self, id: &str, mode: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE downloads SET mode = ?2 WHERE id = ?1",
+            params![id, mode],
+        )?;
+        Ok(())
+    }
+
+    /// Remember the cookie profile a download uses, so resuming it keeps it.
+    pub fn set_download_cookie_profile(&self, id: &str, profile_id: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE downloads SET cookie_profile = ?2 WHERE id = ?1",
+            params![id, profile_id],
+        )?;
+        Ok(())
+    }
+
+    /// Remember a download's concurrent fragments override, so resuming it
+    /// keeps it.
+    pub fn set_download_fragments(&self, id: &str, fragments: u32) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE downloads SET concurrent_fragments = ?2 WHERE id = ?1",
+            params![id, fragments],
+        )?;
+        Ok(())
+    }
+
+    /// Remember the folder a download was sent to instead of the configured
+    /// one, so resuming it keeps it.
+    pub fn set_download_output_dir(&self, id: &str, output_dir: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE downloads SET output_dir = ?2 WHERE id = ?1",
+            params![id, output_dir],
+        )?;
+        Ok(())
+    }
+
+    /// Folders downloads of the file at `path` were sent to instead of the
+    /// configured ones.
+    pub fn get_download_output_dirs_for_file(&self, path: &str) -> AppResult<Vec<String>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT DISTINCT output_dir FROM downloads WHERE file_path = ?1 AND COALESCE(output_dir, '') != ''",
+        )?;
+        let rows = stmt.query_map(params![path], |row| row.get::<_, String>(0))?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    pub fn set_download_batch(&self, id: &str, batch_id: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE downloads SET batch_id = ?2 WHERE id = ?1",
+            params![id, batch_id],
+        )?;
+        Ok(())
+    }
+
+    /// Store the description, tags, upload date and view count of a download.
+    pub fn set_download_details(&self, id: &str, details: &crate::download::MediaDetails) -> AppResult<()> {
+        let tags = if details.tags.is_empty() {
+            String::new()
+        } else {
+            serde_json::to_string(&details.tags)?
+        };
+        self.conn.execute(
+            "UPDATE downloads SET description = ?2, tags = ?3, upload_date = ?4, view_count = ?5, duration = ?6 WHERE id = ?1",
+            params![id, details.description, tags, details.upload_date, details.view_count, details.duration],
+        )?;
+        Ok(())
+    }
+
+    pub fn count_downloads_with_status(&self, status: &str) -> AppResult<i64> {
+        let count = self.conn.query_row(
+            "SELECT COUNT(*) FROM downloads WHERE status = ?1",
+            params![status],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Add to the bytes downloaded on `day` ("YYYY-MM-DD").
+    pub fn add_data_usage(&self, day: &str, bytes: i64) -> AppResult<()> {
+        self.conn.execute(
+            "INSERT INTO data_usage (day, bytes) VALUES (?1, ?2)
+             ON CONFLICT(day) DO UPDATE SET bytes = bytes + excluded.bytes",
+            params![day, bytes],
+        )?;
+        Ok(())
+    }
+
+    /// Bytes downloaded on `day` ("YYYY-MM-DD") and later.
+    pub fn get_data_usage_since(&self, day: &str) -> AppResult<i64> {
+        let bytes = self.conn.query_row(
+            "SELECT COALESCE(SUM(bytes), 0) FROM data_usage WHERE day >= ?1",
+            params![day],
+            |row| row.get(0),
+        )?;
+        Ok(bytes)
+    }
+
+    /// Stored speed samples of a download (JSON), see `speed_history`.
+    pub fn get_speed_history(&self, id: &str) -> AppResult<Option<String>> {
+        let json = self
+            .conn
+            .query_row(
+                "SELECT speed_history FROM downloads WHERE id = ?1",
+                params![id],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .unwrap_or_default();
+        Ok(json)
+    }
+
+    pub fn set_speed_history(&self, id: &str, json: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE downloads SET speed_history = ?2 WHERE id = ?1",
+            params![id, json],
+        )?;
+        Ok(())
+    }
+
     pub fn delete_download(&self, id: &str) -> AppResult<()> {
         self.conn
             .execute("DELETE FROM downloads WHERE id = ?1", params![id])?;
+        self.conn
+            .execute("DELETE FROM download_artifacts WHERE download_id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Replace the recorded files of an archive bundle with `(kind, path, size)`.
+    pub fn replace_download_artifacts(&self, download_id: &str, files: &[(String, String, i64)]) -> AppResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM download_artifacts WHERE download_id = ?1", params![download_id])?;
+        for (kind, path, size) in files {
+            tx.execute(
+                "INSERT INTO download_artifacts (download_id, kind, path, size) VALUES (?1, ?2, ?3, ?4)",
+                params![download_id, kind, path, size],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get_download_artifacts(&self, download_id: &str) -> AppResult<Vec<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT kind, path, size FROM download_artifacts WHERE download_id = ?1 ORDER BY kind, path",
+        )?;
+        let rows = stmt.query_map(params![download_id], |row| {
+            Ok(serde_json::json!({
+                "kind": row.get::<_, String>(0)?,
+                "path": row.get::<_, String>(1)?,
+                "size": row.get::<_, i64>(2)?,
+            }))
+        })?;
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+
+    /// `(id, title, file_path, file_size)` of every completed download that
+    /// has a file.
+    pub fn get_download_files(&self) -> AppResult<Vec<(String, String, String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title, file_path, COALESCE(file_size, 0) FROM downloads
+             WHERE status = 'completed' AND COALESCE(file_path, '') != ''
+             ORDER BY created_at",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?;
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+
+    /// Point a download at its file's new location. Recorded artifacts under
+    /// `old_base` are moved to `new_base` along with it.
+    pub fn relink_download_file(&self, id: &str, file_path: &str, old_base: &str, new_base: &str) -> AppResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE downloads SET file_path = ?2, updated_at = datetime('now') WHERE id = ?1",
+            params![id, file_path],
+        )?;
+        if !old_base.is_empty() {
+            tx.execute(
+                "UPDATE download_artifacts SET path = ?3 || substr(path, length(?2) + 1)
+                 WHERE download_id = ?1 AND substr(path, 1, length(?2)) = ?2",
+                params![id, old_base, new_base],
+            )?;
+        }
+        tx.commit()?;
         Ok(())
     }
 
+    /// Whether some download's `file_path` is exactly `path`.
+    pub fn has_download_file(&self, path: &str) -> AppResult<bool> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT 1 FROM downloads WHERE file_path = ?1 LIMIT 1")?;
+        Ok(stmt.exists(params![path])?)
+    }
+
     pub fn update_download_priority(&self, id: &str, priority: i32) -> AppResult<()> {
         self.conn.execute(
             "UPDATE downloads SET priority = ?2, updated_at = datetime('now') WHERE id = ?1",
@@ -285,30 +920,53 @@ impl Database {
     }
 
     pub fn get_downloads(&self) -> AppResult<Vec<serde_json::Value>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, url, title, thumbnail, status, progress, speed, eta, file_path, file_size, format_id, format_label, error, priority, created_at, updated_at, COALESCE(source, 'single') FROM downloads ORDER BY priority DESC, created_at DESC"
-        )?;
-        let rows = stmt.query_map([], |row| {
-            Ok(serde_json::json!({
-                "id": row.get::<_, String>(0)?,
-                "url": row.get::<_, String>(1)?,
-                "title": row.get::<_, String>(2)?,
-                "thumbnail": row.get::<_, String>(3)?,
-                "status": row.get::<_, String>(4)?,
-                "progress": row.get::<_, f64>(5)?,
-                "speed": row.get::<_, String>(6)?,
-                "eta": row.get::<_, String>(7)?,
-                "filePath": row.get::<_, String>(8)?,
-                "fileSize": row.get::<_, i64>(9)?,
-                "formatId": row.get::<_, String>(10)?,
-                "formatLabel": row.get::<_, String>(11)?,
-                "error": row.get::<_, String>(12)?,
-                "priority": row.get::<_, i32>(13).unwrap_or(0),
-                "createdAt": row.get::<_, String>(14)?,
-                "updatedAt": row.get::<_, String>(15)?,
-                "source": row.get::<_, String>(16).unwrap_or_else(|_| "single".to_string()),
-            }))
-        })?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM downloads ORDER BY priority DESC, created_at DESC",
+            DOWNLOAD_COLUMNS
+        ))?;
+        let rows = stmt.query_map([], download_row_to_json)?;
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+
+    pub fn get_download(&self, id: &str) -> AppResult<Option<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM downloads WHERE id = ?1",
+            DOWNLOAD_COLUMNS
+        ))?;
+        let mut rows = stmt.query_map(params![id], download_row_to_json)?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Downloads of a batch, oldest first.
+    pub fn get_batch_downloads(&self, batch_id: &str) -> AppResult<Vec<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM downloads WHERE batch_id = ?1 ORDER BY created_at ASC",
+            DOWNLOAD_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![batch_id], download_row_to_json)?;
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+
+    /// Completed downloads of videos listed in a feed, newest first.
+    pub fn get_feed_downloads(&self, feed_id: &str) -> AppResult<Vec<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM downloads
+             WHERE status = 'completed' AND url IN (SELECT url FROM feed_items WHERE feed_id = ?1)
+             ORDER BY created_at DESC",
+            DOWNLOAD_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![feed_id], download_row_to_json)?;
         let mut result = Vec::new();
         for row in rows {
             result.push(row?);
@@ -316,14 +974,23 @@ impl Database {
         Ok(result)
     }
 
-    /// Check if a download with the given URL and format already exists with an active status.
+    /// Check if a download with the given URL, format and section already exists with an active status.
     /// Returns the status string if a duplicate is found, None otherwise.
     /// This is O(1) via SQL instead of loading all rows (Issue #15).
-    pub fn download_exists_by_url(&self, url: &str, format_id: &str) -> AppResult<Option<String>> {
+    pub fn download_exists_by_url(&self, url: &str, format_id: &str, section: &str) -> AppResult<Option<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT status FROM downloads WHERE url = ?1 AND COALESCE(format_id, '') = ?2 AND COALESCE(section, '') = ?3 AND status IN ('completed', 'downloading', 'queued') LIMIT 1"
+        )?;
+        let result = stmt.query_row(params![url, format_id, section], |row| row.get::<_, String>(0)).ok();
+        Ok(result)
+    }
+
+    /// Most recent download row for `url`, any status.
+    pub fn get_download_id_by_url(&self, url: &str) -> AppResult<Option<String>> {
         let mut stmt = self.conn.prepare(
-            "SELECT status FROM downloads WHERE url = ?1 AND COALESCE(format_id, '') = ?2 AND status IN ('completed', 'downloading', 'queued') LIMIT 1"
+            "SELECT id FROM downloads WHERE url = ?1 ORDER BY created_at DESC LIMIT 1"
         )?;
-        let result = stmt.query_row(params![url, format_id], |row| row.get::<_, String>(0)).ok();
+        let result = stmt.query_row(params![url], |row| row.get::<_, String>(0)).ok();
         Ok(result)
     }
 
@@ -368,89 +1035,97 @@ impl Database {
         Ok(())
     }
 
-    pub fn get_feeds(&self) -> AppResult<Vec<serde_json::Value>> {
-        // Batch-load all feed items to avoid N+1 queries
-        let mut items_map: std::collections::HashMap<String, Vec<serde_json::Value>> =
-            std::collections::HashMap::new();
-        {
-            let mut items_stmt = self.conn.prepare(
-                "SELECT id, feed_id, video_id, title, thumbnail, url, published_at, downloaded, video_type FROM feed_items ORDER BY published_at DESC"
-            )?;
-            let item_rows = items_stmt.query_map([], |row| {
-                let feed_id: String = row.get(1)?;
-                let downloaded_raw: i64 = row.get::<_, i64>(7).unwrap_or(0);
-                let item = serde_json::json!({
-                    "id": row.get::<_, String>(0)?,
-                    "videoId": row.get::<_, String>(2)?,
-                    "title": row.get::<_, String>(3)?,
-                    "thumbnail": row.get::<_, String>(4)?,
-                    "url": row.get::<_, String>(5)?,
-                    "publishedAt": row.get::<_, String>(6)?,
-                    "status": if downloaded_raw != 0 { "downloaded" } else { "not_queued" },
-                    "videoType": row.get::<_, Option<String>>(8)?.unwrap_or_else(|| "video".to_string()),
-                });
-                Ok((feed_id, item))
-            })?;
-            for row in item_rows {
-                if let Ok((feed_id, item)) = row {
-                    items_map.entry(feed_id).or_default().push(item);
-                }
-            }
-        }
-
+    /// Feeds with item counts and the newest item's date, without the
+    /// items themselves (see [`Database::get_feed_items`]).
+    pub fn get_feeds(&self) -> AppResult<Vec<FeedSummary>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, url, title, channel_name, thumbnail, auto_download, keywords, last_checked, created_at FROM feeds ORDER BY created_at DESC"
+            "SELECT f.id, f.url, f.title, f.channel_name, f.thumbnail, f.auto_download, f.keywords, f.last_checked, f.created_at,
+                    COUNT(fi.id),
+                    COALESCE(SUM(CASE WHEN fi.downloaded = 0 THEN 1 ELSE 0 END), 0),
+                    COALESCE(MAX(fi.published_at), ''),
+                    f.folder_template, f.tabs, f.cookie_profile
+             FROM feeds f
+             LEFT JOIN feed_items fi ON fi.feed_id = f.id
+             GROUP BY f.id
+             ORDER BY f.created_at DESC"
         )?;
         let rows = stmt.query_map([], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-                row.get::<_, String>(3)?,
-                row.get::<_, String>(4)?,
-                row.get::<_, bool>(5)?,
-                row.get::<_, String>(6)?,
-                row.get::<_, String>(7)?,
-                row.get::<_, String>(8)?,
-            ))
+            Ok(FeedSummary {
+                id: row.get(0)?,
+                url: row.get(1)?,
+                title: row.get(2)?,
+                channel_name: row.get(3)?,
+                channel_avatar: row.get(4)?,
+                auto_download: row.get(5)?,
+                keywords: row.get(6)?,
+                last_checked: row.get(7)?,
+                created_at: row.get(8)?,
+                item_count: row.get(9)?,
+                new_count: row.get(10)?,
+                latest_published_at: row.get(11)?,
+                folder_template: row.get(12)?,
+                tabs: row.get(13)?,
+                cookie_profile: row.get(14)?,
+            })
         })?;
         let mut result = Vec::new();
         for row in rows {
-            let (
-                id,
-                url,
-                title,
-                channel_name,
-                thumbnail,
-                auto_download,
-                keywords,
-                last_checked,
-                created_at,
-            ) = row?;
-            let items = items_map.remove(&id).unwrap_or_default();
-            result.push(serde_json::json!({
-                "id": id,
-                "url": url,
-                "title": title,
-                "channelName": channel_name,
-                "channelAvatar": thumbnail,
-                "autoDownload": auto_download,
-                "keywords": keywords,
-                "lastChecked": last_checked,
-                "createdAt": created_at,
-                "items": items,
-            }));
+            result.push(row?);
         }
         Ok(result)
     }
 
-    pub fn delete_feed(&self, id: &str) -> AppResult<()> {
-        self.conn
-            .execute("DELETE FROM feeds WHERE id = ?1", params![id])?;
-        Ok(())
-    }
+    /// A page of one feed's items, newest first, and how many items match
+    /// `filter` in total. `filter` is "all", "new" (not downloaded),
+    /// "downloaded", "video" or "short".
+    pub fn get_feed_items(
+        &self,
+        feed_id: &str,
+        offset: i64,
+        limit: i64,
+        filter: &str,
+    ) -> AppResult<(Vec<FeedItem>, i64)> {
+        let condition = match filter {
+            "all" | "" => "1 = 1",
+            "new" => "downloaded = 0",
+            "downloaded" => "downloaded != 0",
+            "video" => "COALESCE(video_type, 'video') != 'short'",
+            "short" => "video_type = 'short'",
+            other => {
+                return Err(crate::error::AppError::InvalidArgument(format!(
+                    "Unknown feed item filter '{}'",
+                    other
+                )))
+            }
+        };
 
-    pub fn update_feed_last_checked(&self, id: &str) -> AppResult<()> {
+        let total = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM feed_items WHERE feed_id = ?1 AND {}", condition),
+            params![feed_id],
+            |row| row.get::<_, i64>(0),
+        )?;
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, video_id, title, thumbnail, url, published_at, downloaded, video_type
+             FROM feed_items WHERE feed_id = ?1 AND {}
+             ORDER BY published_at DESC LIMIT ?2 OFFSET ?3",
+            condition
+        ))?;
+        let rows = stmt.query_map(params![feed_id, limit, offset], feed_item_from_row)?;
+        let mut items = Vec::new();
+        for row in rows {
+            items.push(row?);
+        }
+        Ok((items, total))
+    }
+
+    pub fn delete_feed(&self, id: &str) -> AppResult<()> {
+        self.conn
+            .execute("DELETE FROM feeds WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn update_feed_last_checked(&self, id: &str) -> AppResult<()> {
         self.conn.execute(
             "UPDATE feeds SET last_checked = datetime('now') WHERE id = ?1",
             params![id],
@@ -490,6 +1165,72 @@ impl Database {
         Ok(())
     }
 
+    pub fn update_feed_folder_template(&self, id: &str, template: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE feeds SET folder_template = ?2 WHERE id = ?1",
+            params![id, template],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_feed_tabs(&self, id: &str, tabs: &str) -> AppResult<()> {
+        self.conn.execute("UPDATE feeds SET tabs = ?2 WHERE id = ?1", params![id, tabs])?;
+        Ok(())
+    }
+
+    pub fn update_feed_cookie_profile(&self, id: &str, profile_id: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE feeds SET cookie_profile = ?2 WHERE id = ?1",
+            params![id, profile_id],
+        )?;
+        Ok(())
+    }
+
+    /// Cookie profile of a feed that has an item with this URL, if any.
+    pub fn get_feed_cookie_profile_for_url(&self, url: &str) -> AppResult<Option<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT f.cookie_profile FROM feed_items fi JOIN feeds f ON f.id = fi.feed_id
+             WHERE fi.url = ?1 AND f.cookie_profile != '' LIMIT 1",
+        )?;
+        Ok(stmt.query_row(params![url], |row| row.get(0)).ok())
+    }
+
+    // --- Cookie profiles ---
+
+    pub fn get_cookie_profiles(&self) -> AppResult<Vec<crate::cookie_profiles::CookieProfile>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, name, kind, source FROM cookie_profiles ORDER BY name COLLATE NOCASE")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(crate::cookie_profiles::CookieProfile {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                kind: row.get(2)?,
+                source: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    pub fn save_cookie_profile(&self, profile: &crate::cookie_profiles::CookieProfile) -> AppResult<()> {
+        self.conn.execute(
+            "INSERT INTO cookie_profiles (id, name, kind, source) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(id) DO UPDATE SET name = excluded.name, kind = excluded.kind, source = excluded.source",
+            params![profile.id, profile.name, profile.kind, profile.source],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a profile and unset it wherever it is used.
+    pub fn delete_cookie_profile(&self, id: &str) -> AppResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM cookie_profiles WHERE id = ?1", params![id])?;
+        tx.execute("UPDATE feeds SET cookie_profile = '' WHERE cookie_profile = ?1", params![id])?;
+        tx.execute("UPDATE downloads SET cookie_profile = '' WHERE cookie_profile = ?1", params![id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
     // --- Feed Items ---
 
     pub fn insert_feed_item(
@@ -558,107 +1299,118 @@ impl Database {
             .unwrap_or(false)
     }
 
-    pub fn get_feed_items(&self, feed_id: &str) -> AppResult<Vec<serde_json::Value>> {
-        let query_with_type =
-            "SELECT id, video_id, title, thumbnail, url, published_at, downloaded, video_type FROM feed_items WHERE feed_id = ?1 ORDER BY published_at DESC";
-
-        let mut result = Vec::new();
-
-        match self.conn.prepare(query_with_type) {
-            Ok(mut stmt) => {
-                let rows = stmt.query_map(params![feed_id], |row| {
-                    let downloaded_raw: i64 = row.get::<_, i64>(6).unwrap_or(0);
-                    Ok(serde_json::json!({
-                        "id": row.get::<_, String>(0)?,
-                        "videoId": row.get::<_, String>(1)?,
-                        "title": row.get::<_, String>(2)?,
-                        "thumbnail": row.get::<_, String>(3)?,
-                        "url": row.get::<_, String>(4)?,
-                        "publishedAt": row.get::<_, String>(5)?,
-                        "status": if downloaded_raw != 0 { "downloaded" } else { "not_queued" },
-                        "videoType": row.get::<_, Option<String>>(7)?.unwrap_or_else(|| "video".to_string()),
-                    }))
-                })?;
-                for row in rows {
-                    result.push(row?);
+    /// Upsert a batch of feed items in one transaction. Returns the IDs of
+    /// items that weren't stored before.
+    pub fn insert_feed_items(&self, feed_id: &str, items: &[crate::rss::RssItem]) -> AppResult<Vec<String>> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut new_ids = Vec::new();
+        {
+            let mut exists = tx.prepare_cached("SELECT 1 FROM feed_items WHERE id = ?1")?;
+            let mut insert = tx.prepare_cached(
+                "INSERT INTO feed_items (id, feed_id, video_id, title, thumbnail, url, published_at, video_type) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) \
+                 ON CONFLICT(id) DO UPDATE SET \
+                     feed_id = excluded.feed_id, \
+                     video_id = excluded.video_id, \
+                     title = excluded.title, \
+                     thumbnail = excluded.thumbnail, \
+                     url = excluded.url, \
+                     published_at = excluded.published_at, \
+                     video_type = excluded.video_type",
+            )?;
+            for item in items {
+                let is_new = !exists.exists(params![item.id])?;
+                insert.execute(params![
+                    item.id,
+                    feed_id,
+                    item.video_id,
+                    item.title,
+                    item.thumbnail,
+                    item.url,
+                    item.published_at,
+                    item.video_type
+                ])?;
+                if is_new {
+                    new_ids.push(item.id.clone());
                 }
-                Ok(result)
             }
-            Err(e) => {
-                println!("[DB] feed_items full-schema read failed, using fallback: {:?}", e);
-                let with_thumb_url = self.conn.prepare(
-                    "SELECT id, video_id, title, thumbnail, url, published_at, downloaded FROM feed_items WHERE feed_id = ?1 ORDER BY published_at DESC",
-                );
-
-                if let Ok(mut stmt) = with_thumb_url {
-                    let rows = stmt.query_map(params![feed_id], |row| {
-                        let downloaded_raw: i64 = row.get::<_, i64>(6).unwrap_or(0);
-                        let url = row.get::<_, String>(4)?;
-                        let title = row.get::<_, String>(2)?;
-                        let inferred = if url.to_lowercase().contains("/shorts/")
-                            || title.to_lowercase().contains("#short")
-                            || title.to_lowercase().contains("#shorts")
-                        {
-                            "short"
-                        } else {
-                            "video"
-                            };
-
-                        Ok(serde_json::json!({
-                            "id": row.get::<_, String>(0)?,
-                            "videoId": row.get::<_, String>(1)?,
-                            "thumbnail": row.get::<_, String>(3)?,
-                            "url": url,
-                            "publishedAt": row.get::<_, String>(5)?,
-                            "status": if downloaded_raw != 0 { "downloaded" } else { "not_queued" },
-                            "videoType": inferred,
-                        }))
-                    })?;
-                    for row in rows {
-                        result.push(row?);
-                    }
-                    println!("[DB] Read {} items with thumb/url fallback", result.len());
-                    return Ok(result);
-                }
-                println!("[DB] Using minimal schema fallback read");
-                let mut stmt = self.conn.prepare(
-                    "SELECT id, video_id, title, published_at, downloaded FROM feed_items WHERE feed_id = ?1 ORDER BY published_at DESC",
-                )?;
-                let rows = stmt.query_map(params![feed_id], |row| {
-                    let downloaded_raw: i64 = row.get::<_, i64>(4).unwrap_or(0);
-                    let video_id = row.get::<_, String>(1)?;
-                    let title = row.get::<_, String>(2)?;
-                    let inferred = if title.to_lowercase().contains("#short")
-                        || title.to_lowercase().contains("#shorts")
-                    {
-                        "short"
-                    } else {
-                        "video"
-                    };
-                    let url = if inferred == "short" {
-                        format!("https://www.youtube.com/shorts/{}", video_id)
-                    } else {
-                        format!("https://www.youtube.com/watch?v={}", video_id)
-                    };
-
-                    Ok(serde_json::json!({
-                        "id": row.get::<_, String>(0)?,
-                        "videoId": video_id.clone(),
-                        "title": title,
-                        "thumbnail": format!("https://i.ytimg.com/vi/{}/mqdefault.jpg", video_id),
-                        "url": url,
-                        "publishedAt": row.get::<_, String>(3)?,
-                        "status": if downloaded_raw != 0 { "downloaded" } else { "not_queued" },
-                        "videoType": inferred,
-                    }))
-                })?;
-                for row in rows {
-                    result.push(row?);
+            // A new start time means a new "starting soon" notification
+            let mut upcoming = tx.prepare_cached(
+                "INSERT INTO upcoming_streams (video_id, feed_id, title, url, thumbnail, scheduled_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+                 ON CONFLICT(video_id) DO UPDATE SET \
+                     title = excluded.title, \
+                     url = excluded.url, \
+                     thumbnail = excluded.thumbnail, \
+                     notified = CASE WHEN scheduled_at = excluded.scheduled_at THEN notified ELSE 0 END, \
+                     scheduled_at = excluded.scheduled_at",
+            )?;
+            for item in items {
+                if let Some(scheduled_at) = item.scheduled_at {
+                    upcoming.execute(params![
+                        item.video_id,
+                        feed_id,
+                        item.title,
+                        item.url,
+                        item.thumbnail,
+                        scheduled_at
+                    ])?;
                 }
-                println!("[DB] Read {} items with minimal schema", result.len());
-                Ok(result)
             }
         }
+        tx.commit()?;
+        Ok(new_ids)
+    }
+
+    // --- Upcoming premieres and livestreams ---
+
+    /// Scheduled streams, soonest first, with whether their feed
+    /// auto-downloads.
+    pub fn get_upcoming_streams(&self) -> AppResult<Vec<UpcomingStream>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT u.video_id, u.feed_id, COALESCE(f.title, ''), u.title, u.url, u.thumbnail, u.scheduled_at,
+                    u.notified, u.download_id, COALESCE(f.auto_download, 0)
+             FROM upcoming_streams u LEFT JOIN feeds f ON f.id = u.feed_id
+             ORDER BY u.scheduled_at",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(UpcomingStream {
+                video_id: row.get(0)?,
+                feed_id: row.get(1)?,
+                feed_title: row.get(2)?,
+                title: row.get(3)?,
+                url: row.get(4)?,
+                thumbnail: row.get(5)?,
+                scheduled_at: row.get(6)?,
+                notified: row.get(7)?,
+                download_id: row.get(8)?,
+                auto_download: row.get(9)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    pub fn set_upcoming_notified(&self, video_id: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE upcoming_streams SET notified = 1 WHERE video_id = ?1",
+            params![video_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_upcoming_download(&self, video_id: &str, download_id: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE upcoming_streams SET download_id = ?2 WHERE video_id = ?1",
+            params![video_id, download_id],
+        )?;
+        Ok(())
+    }
+
+    /// Forget streams scheduled before `before` (Unix seconds).
+    pub fn delete_upcoming_before(&self, before: i64) -> AppResult<usize> {
+        let removed = self.conn.execute(
+            "DELETE FROM upcoming_streams WHERE scheduled_at < ?1",
+            params![before],
+        )?;
+        Ok(removed)
     }
 
     pub fn update_feed_item_downloaded(&self, id: &str, downloaded: bool) -> AppResult<()> {
@@ -671,17 +1423,24 @@ impl Database {
 
     // --- Transcripts ---
 
-    pub fn insert_transcript(&self, id: &str, source: &str, title: &str) -> AppResult<()> {
+    pub fn insert_transcript(
+        &self,
+        id: &str,
+        source: &str,
+        title: &str,
+        download_id: Option<&str>,
+    ) -> AppResult<()> {
         self.conn.execute(
-            "INSERT INTO transcripts (id, source, title) VALUES (?1, ?2, ?3)",
-            params![id, source, title],
+            "INSERT INTO transcripts (id, source, title, download_id, fts_id)
+             VALUES (?1, ?2, ?3, ?4, (SELECT COALESCE(MAX(fts_id), 0) + 1 FROM transcripts))",
+            params![id, source, title, download_id],
         )?;
         Ok(())
     }
 
     pub fn get_transcripts(&self) -> AppResult<Vec<serde_json::Value>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, source, title, language, text, status, progress, duration_secs, error, created_at FROM transcripts ORDER BY created_at DESC"
+            "SELECT id, source, title, language, text, status, progress, duration_secs, error, created_at, download_id FROM transcripts ORDER BY created_at DESC"
         )?;
         let rows = stmt.query_map([], |row| {
             Ok(serde_json::json!({
@@ -695,6 +1454,7 @@ impl Database {
                 "durationSecs": row.get::<_, i64>(7)?,
                 "error": row.get::<_, String>(8)?,
                 "createdAt": row.get::<_, String>(9)?,
+                "downloadId": row.get::<_, Option<String>>(10)?,
             }))
         })?;
         let mut result = Vec::new();
@@ -725,6 +1485,65 @@ impl Database {
         Ok(())
     }
 
+    /// Store timed segments (JSON array of `{start, end, text}`) for a transcript.
+    pub fn update_transcript_segments(
+        &self,
+        id: &str,
+        segments: &str,
+        duration_secs: i64,
+    ) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE transcripts SET segments = ?2, duration_secs = ?3 WHERE id = ?1",
+            params![id, segments, duration_secs],
+        )?;
+        Ok(())
+    }
+
+    /// Full-text search over transcript titles and text. `query` is passed to
+    /// FTS5 as a quoted phrase so user input never hits the MATCH syntax.
+    pub fn search_transcripts(&self, query: &str, limit: i64) -> AppResult<Vec<serde_json::Value>> {
+        let phrase = format!("\"{}\"", query.replace('"', "\"\""));
+        let mut stmt = self.conn.prepare(
+            "SELECT t.id, t.source, t.title, t.text, COALESCE(t.segments, '[]'), t.duration_secs, t.created_at,
+                    snippet(transcripts_fts, 1, '«', '»', '…', 16)
+             FROM transcripts_fts
+             JOIN transcripts t ON t.fts_id = transcripts_fts.rowid
+             WHERE transcripts_fts MATCH ?1 AND t.status = 'completed'
+             ORDER BY bm25(transcripts_fts)
+             LIMIT ?2"
+        )?;
+        let rows = stmt.query_map(params![phrase, limit], |row| {
+            let segments: String = row.get(4)?;
+            Ok(serde_json::json!({
+                "id": row.get::<_, String>(0)?,
+                "source": row.get::<_, String>(1)?,
+                "title": row.get::<_, String>(2)?,
+                "text": row.get::<_, String>(3)?,
+                "segments": serde_json::from_str::<serde_json::Value>(&segments)
+                    .unwrap_or_else(|_| serde_json::json!([])),
+                "durationSecs": row.get::<_, i64>(5)?,
+                "createdAt": row.get::<_, String>(6)?,
+                "snippet": row.get::<_, String>(7)?,
+            }))
+        })?;
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+
+    /// Fail transcripts whose job died with the previous run. Returns how
+    /// many were changed.
+    pub fn fail_stuck_transcripts(&self) -> AppResult<usize> {
+        let changed = self.conn.execute(
+            "UPDATE transcripts SET status = 'error', error = 'Interrupted when the app last closed'
+             WHERE status IN ('pending', 'processing')",
+            [],
+        )?;
+        Ok(changed)
+    }
+
     pub fn update_transcript_error(&self, id: &str, error: &str) -> AppResult<()> {
         self.conn.execute(
             "UPDATE transcripts SET status = 'error', error = ?2 WHERE id = ?1",
@@ -756,6 +1575,30 @@ impl Database {
         Ok(())
     }
 
+    pub fn get_playlist_id_by_url(&self, url: &str) -> AppResult<Option<String>> {
+        let mut stmt = self.conn.prepare("SELECT id FROM playlists WHERE url = ?1")?;
+        let result = stmt.query_row(params![url], |row| row.get::<_, String>(0)).ok();
+        Ok(result)
+    }
+
+    /// Record the outcome of a sync pass: entry count, completed count and
+    /// `last_sync = now`.
+    pub fn update_playlist_sync(
+        &self,
+        id: &str,
+        title: &str,
+        total_videos: i32,
+        downloaded_videos: i32,
+    ) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE playlists SET title = CASE WHEN ?2 = '' THEN title ELSE ?2 END, total_videos = ?3,
+                 downloaded_videos = ?4, last_sync = datetime('now'), updated_at = datetime('now')
+             WHERE id = ?1",
+            params![id, title, total_videos, downloaded_videos],
+        )?;
+        Ok(())
+    }
+
     pub fn update_playlist_progress(&self, id: &str, downloaded_videos: i32) -> AppResult<()> {
         self.conn.execute(
             "UPDATE playlists SET downloaded_videos = ?2, updated_at = datetime('now') WHERE id = ?1",
@@ -777,9 +1620,56 @@ impl Database {
         Ok(())
     }
 
+    /// Turn a playlist row into a channel mirror and store its per-channel
+    /// folder, naming template, format and schedule flag.
+    pub fn update_channel_mirror_settings(
+        &self,
+        id: &str,
+        output_dir: &str,
+        naming_template: &str,
+        format_id: &str,
+        auto_sync: bool,
+    ) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE playlists SET kind = 'channel', output_dir = ?2, naming_template = ?3, format_id = ?4,
+                 auto_sync = ?5, updated_at = datetime('now')
+             WHERE id = ?1",
+            params![id, output_dir, naming_template, format_id, auto_sync as i32],
+        )?;
+        Ok(())
+    }
+
+    /// Only entries uploaded on/after `date_after` (`YYYYMMDD`, empty for
+    /// all) are queued when the playlist is synced.
+    pub fn update_playlist_date_after(&self, id: &str, date_after: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE playlists SET date_after = ?2, updated_at = datetime('now') WHERE id = ?1",
+            params![id, date_after],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_playlist_naming_template(&self, id: &str, naming_template: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE playlists SET naming_template = ?2, updated_at = datetime('now') WHERE id = ?1",
+            params![id, naming_template],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_playlist(&self, id: &str) -> AppResult<Option<serde_json::Value>> {
+        Ok(self
+            .get_playlists()?
+            .into_iter()
+            .find(|p| p["id"].as_str() == Some(id)))
+    }
+
     pub fn get_playlists(&self) -> AppResult<Vec<serde_json::Value>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, url, title, thumbnail, total_videos, downloaded_videos, status, naming_template, auto_sync, last_sync, created_at, updated_at FROM playlists ORDER BY created_at DESC"
+            "SELECT id, url, title, thumbnail, total_videos, downloaded_videos, status, naming_template, auto_sync, last_sync, created_at, updated_at,
+                    COALESCE(kind, 'playlist'), COALESCE(output_dir, ''), COALESCE(format_id, ''),
+                    COALESCE(date_after, '')
+             FROM playlists ORDER BY created_at DESC"
         )?;
         let rows = stmt.query_map([], |row| {
             Ok(serde_json::json!({
@@ -795,6 +1685,10 @@ impl Database {
                 "lastSync": row.get::<_, String>(9)?,
                 "createdAt": row.get::<_, String>(10)?,
                 "updatedAt": row.get::<_, String>(11)?,
+                "kind": row.get::<_, String>(12)?,
+                "outputDir": row.get::<_, String>(13)?,
+                "formatId": row.get::<_, String>(14)?,
+                "dateAfter": row.get::<_, String>(15)?,
             }))
         })?;
         let mut result = Vec::new();
@@ -804,9 +1698,537 @@ impl Database {
         Ok(result)
     }
 
+    /// Delete a playlist and its items. Downloads stay; see
+    /// [`Self::get_playlist_owned_download_ids`] for the ones to remove first.
     pub fn delete_playlist(&self, id: &str) -> AppResult<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM playlist_items WHERE playlist_id = ?1", params![id])?;
+        tx.execute("DELETE FROM playlists WHERE id = ?1", params![id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // --- Playlist items ---
+
+    /// Insert or refresh an entry of a playlist, keyed by (playlist_id, video_id).
+    /// Returns the item id.
+    pub fn upsert_playlist_item(
+        &self,
+        playlist_id: &str,
+        video_id: &str,
+        url: &str,
+        title: &str,
+        thumbnail: &str,
+        playlist_index: i64,
+    ) -> AppResult<String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.conn.execute(
+            "INSERT INTO playlist_items (id, playlist_id, video_id, url, title, thumbnail, playlist_index)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(playlist_id, video_id) DO UPDATE SET
+                url = excluded.url,
+                title = excluded.title,
+                thumbnail = excluded.thumbnail,
+                playlist_index = excluded.playlist_index,
+                updated_at = datetime('now')",
+            params![id, playlist_id, video_id, url, title, thumbnail, playlist_index],
+        )?;
+        let item_id = self.conn.query_row(
+            "SELECT id FROM playlist_items WHERE playlist_id = ?1 AND video_id = ?2",
+            params![playlist_id, video_id],
+            |row| row.get::<_, String>(0),
+        )?;
+        Ok(item_id)
+    }
+
+    /// Naming/placement details for a download that belongs to a playlist or
+    /// channel mirror, so resumed/retried jobs keep the same naming.
+    pub fn get_playlist_context_for_download(
+        &self,
+        download_id: &str,
+    ) -> AppResult<Option<PlaylistContext>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT p.title, COALESCE(p.naming_template, ''), pi.playlist_index,
+                    COALESCE(p.kind, 'playlist'), COALESCE(p.output_dir, '')
+             FROM playlist_items pi
+             JOIN playlists p ON p.id = pi.playlist_id
+             WHERE pi.download_id = ?1
+             LIMIT 1"
+        )?;
+        let result = stmt
+            .query_row(params![download_id], |row| {
+                Ok(PlaylistContext {
+                    title: row.get(0)?,
+                    naming_template: row.get(1)?,
+                    index: row.get(2)?,
+                    kind: row.get(3)?,
+                    output_dir: row.get(4)?,
+                })
+            })
+            .ok();
+        Ok(result)
+    }
+
+    pub fn get_playlist_id_for_download(&self, download_id: &str) -> AppResult<Option<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT playlist_id FROM playlist_items WHERE download_id = ?1 LIMIT 1")?;
+        let result = stmt.query_row(params![download_id], |row| row.get::<_, String>(0)).ok();
+        Ok(result)
+    }
+
+    /// `created` marks a download queued for this item rather than an
+    /// existing one; relinking the same download keeps what it was.
+    pub fn link_playlist_item_download(&self, item_id: &str, download_id: &str, created: bool) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE playlist_items SET
+                owns_download = CASE WHEN download_id = ?2 THEN owns_download ELSE ?3 END,
+                download_id = ?2, status = 'queued', updated_at = datetime('now')
+             WHERE id = ?1",
+            params![item_id, download_id, created],
+        )?;
+        Ok(())
+    }
+
+    /// Downloads a playlist created that no other playlist links to.
+    pub fn get_playlist_owned_download_ids(&self, playlist_id: &str) -> AppResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT pi.download_id FROM playlist_items pi
+             WHERE pi.playlist_id = ?1 AND pi.owns_download = 1 AND pi.download_id IS NOT NULL
+               AND NOT EXISTS (
+                   SELECT 1 FROM playlist_items o
+                   WHERE o.download_id = pi.download_id AND o.playlist_id != ?1
+               )",
+        )?;
+        let rows = stmt.query_map(params![playlist_id], |row| row.get(0))?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    pub fn update_playlist_item_status(&self, item_id: &str, status: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE playlist_items SET status = ?2, updated_at = datetime('now') WHERE id = ?1",
+            params![item_id, status],
+        )?;
+        Ok(())
+    }
+
+    /// Entries of a playlist in playlist order. The status reflects the linked
+    /// download when there is one, so it never goes stale.
+    pub fn get_playlist_items(&self, playlist_id: &str) -> AppResult<Vec<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{} WHERE pi.playlist_id = ?1 ORDER BY pi.playlist_index ASC",
+            PLAYLIST_ITEM_SELECT
+        ))?;
+        let rows = stmt.query_map(params![playlist_id], playlist_item_row_to_json)?;
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+
+    pub fn get_playlist_item(&self, item_id: &str) -> AppResult<Option<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(&format!("{} WHERE pi.id = ?1", PLAYLIST_ITEM_SELECT))?;
+        let mut rows = stmt.query_map(params![item_id], playlist_item_row_to_json)?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    // --- Playback positions ---
+
+    pub fn save_playback_position(
+        &self,
+        video_id: &str,
+        position_secs: f64,
+        duration_secs: f64,
+        watched_percent: f64,
+    ) -> AppResult<()> {
+        self.conn.execute(
+            "INSERT INTO playback_positions (video_id, position_secs, duration_secs, watched_percent, updated_at)
+             VALUES (?1, ?2, ?3, ?4, datetime('now'))
+             ON CONFLICT(video_id) DO UPDATE SET
+                position_secs = excluded.position_secs,
+                duration_secs = excluded.duration_secs,
+                watched_percent = excluded.watched_percent,
+                updated_at = excluded.updated_at",
+            params![video_id, position_secs, duration_secs, watched_percent],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_playback_position(&self, video_id: &str) -> AppResult<Option<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT video_id, position_secs, duration_secs, watched_percent, updated_at
+             FROM playback_positions WHERE video_id = ?1"
+        )?;
+        let result = stmt.query_row(params![video_id], playback_position_row_to_json).ok();
+        Ok(result)
+    }
+
+    pub fn get_playback_positions(&self) -> AppResult<Vec<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT video_id, position_secs, duration_secs, watched_percent, updated_at
+             FROM playback_positions ORDER BY updated_at DESC"
+        )?;
+        let rows = stmt.query_map([], playback_position_row_to_json)?;
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+
+    pub fn delete_playback_position(&self, video_id: &str) -> AppResult<()> {
         self.conn
-            .execute("DELETE FROM playlists WHERE id = ?1", params![id])?;
+            .execute("DELETE FROM playback_positions WHERE video_id = ?1", params![video_id])?;
         Ok(())
     }
+
+    // --- Listen queue ---
+
+    /// Append a feed item to the end of the listen queue. Returns false if
+    /// the item doesn't exist or its video is already queued.
+    pub fn add_feed_item_to_listen_queue(&self, feed_item_id: &str) -> AppResult<bool> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO listen_queue (id, feed_item_id, video_id, url, title, thumbnail, channel, sort_order)
+             SELECT ?1, fi.id, fi.video_id, fi.url, fi.title, fi.thumbnail,
+                    COALESCE(NULLIF(f.channel_name, ''), f.title),
+                    (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM listen_queue)
+             FROM feed_items fi
+             JOIN feeds f ON f.id = fi.feed_id
+             WHERE fi.id = ?2",
+            params![id, feed_item_id],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Newest regular videos (no shorts/live) not yet in the listen queue,
+    /// optionally from a single feed.
+    pub fn get_unqueued_feed_item_ids(&self, feed_id: Option<&str>, limit: i64) -> AppResult<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT fi.id FROM feed_items fi
+             WHERE (?1 IS NULL OR fi.feed_id = ?1)
+               AND COALESCE(fi.video_type, 'video') = 'video'
+               AND fi.video_id NOT IN (SELECT video_id FROM listen_queue)
+             ORDER BY fi.published_at DESC
+             LIMIT ?2"
+        )?;
+        let rows = stmt.query_map(params![feed_id, limit], |row| row.get::<_, String>(0))?;
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+
+    /// Queue in play order, with any saved playback position.
+    pub fn get_listen_queue(&self) -> AppResult<Vec<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT lq.id, lq.feed_item_id, lq.video_id, lq.url, lq.title, lq.thumbnail, lq.channel, lq.added_at,
+                    COALESCE(pp.position_secs, 0), COALESCE(pp.watched_percent, 0)
+             FROM listen_queue lq
+             LEFT JOIN playback_positions pp ON pp.video_id = lq.video_id
+             ORDER BY lq.sort_order ASC, lq.added_at ASC"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(serde_json::json!({
+                "id": row.get::<_, String>(0)?,
+                "feedItemId": row.get::<_, Option<String>>(1)?,
+                "videoId": row.get::<_, String>(2)?,
+                "url": row.get::<_, String>(3)?,
+                "title": row.get::<_, String>(4)?,
+                "thumbnail": row.get::<_, String>(5)?,
+                "channel": row.get::<_, String>(6)?,
+                "addedAt": row.get::<_, String>(7)?,
+                "position": row.get::<_, f64>(8)?,
+                "watchedPercent": row.get::<_, f64>(9)?,
+            }))
+        })?;
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+
+    pub fn set_listen_queue_order(&self, ids: &[String]) -> AppResult<()> {
+        for (index, id) in ids.iter().enumerate() {
+            self.conn.execute(
+                "UPDATE listen_queue SET sort_order = ?2 WHERE id = ?1",
+                params![id, index as i64 + 1],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn remove_from_listen_queue(&self, id: &str) -> AppResult<()> {
+        self.conn.execute("DELETE FROM listen_queue WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn clear_listen_queue(&self) -> AppResult<()> {
+        self.conn.execute("DELETE FROM listen_queue", [])?;
+        Ok(())
+    }
+
+    // --- Webhooks ---
+
+    pub fn set_download_webhook(&self, id: &str, url: &str) -> AppResult<()> {
+        self.conn.execute(
+            "UPDATE downloads SET webhook_url = ?2 WHERE id = ?1",
+            params![id, url],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_download_webhook(&self, id: &str) -> AppResult<String> {
+        let url = self
+            .conn
+            .query_row(
+                "SELECT COALESCE(webhook_url, '') FROM downloads WHERE id = ?1",
+                params![id],
+                |row| row.get::<_, String>(0),
+            )
+            .unwrap_or_default();
+        Ok(url)
+    }
+
+    /// Record a delivery and keep only the most recent 200.
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert_webhook_delivery(
+        &self,
+        download_id: Option<&str>,
+        url: &str,
+        event: &str,
+        success: bool,
+        status_code: Option<u16>,
+        attempts: u32,
+        error: &str,
+    ) -> AppResult<()> {
+        self.conn.execute(
+            "INSERT INTO webhook_deliveries (id, download_id, url, event, success, status_code, attempts, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                uuid::Uuid::new_v4().to_string(),
+                download_id,
+                url,
+                event,
+                success as i32,
+                status_code,
+                attempts,
+                error
+            ],
+        )?;
+        self.conn.execute(
+            "DELETE FROM webhook_deliveries WHERE id NOT IN
+             (SELECT id FROM webhook_deliveries ORDER BY created_at DESC LIMIT 200)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_webhook_deliveries(&self, limit: i64) -> AppResult<Vec<serde_json::Value>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, download_id, url, event, success, status_code, attempts, error, created_at
+             FROM webhook_deliveries ORDER BY created_at DESC LIMIT ?1"
+        )?;
+        let rows = stmt.query_map(params![limit], |row| {
+            Ok(serde_json::json!({
+                "id": row.get::<_, String>(0)?,
+                "downloadId": row.get::<_, Option<String>>(1)?,
+                "url": row.get::<_, String>(2)?,
+                "event": row.get::<_, String>(3)?,
+                "success": row.get::<_, i32>(4)? != 0,
+                "statusCode": row.get::<_, Option<i64>>(5)?,
+                "attempts": row.get::<_, i64>(6)?,
+                "error": row.get::<_, Option<String>>(7)?.unwrap_or_default(),
+                "createdAt": row.get::<_, String>(8)?,
+            }))
+        })?;
+        let mut result = Vec::new();
+        for row in rows {
+            result.push(row?);
+        }
+        Ok(result)
+    }
+}
+
+fn playback_position_row_to_json(row: &rusqlite::Row) -> rusqlite::Result<serde_json::Value> {
+    Ok(serde_json::json!({
+        "videoId": row.get::<_, String>(0)?,
+        "position": row.get::<_, f64>(1)?,
+        "duration": row.get::<_, f64>(2)?,
+        "watchedPercent": row.get::<_, f64>(3)?,
+        "updatedAt": row.get::<_, String>(4)?,
+    }))
+}
+
+/// See [`Database::get_playlist_context_for_download`].
+pub struct PlaylistContext {
+    pub title: String,
+    pub naming_template: String,
+    pub index: i64,
+    /// `playlist` or `channel` (mirror).
+    pub kind: String,
+    pub output_dir: String,
+}
+
+const DOWNLOAD_COLUMNS: &str = "id, url, title, thumbnail, status, progress, speed, eta, file_path, file_size, format_id, format_label, error, priority, created_at, updated_at, COALESCE(source, 'single'), COALESCE(section, ''), COALESCE(note, ''), COALESCE(mode, ''), COALESCE(description, ''), COALESCE(tags, ''), COALESCE(upload_date, ''), view_count, COALESCE(concurrent_fragments, 0), COALESCE(output_dir, ''), COALESCE(batch_id, ''), duration, COALESCE(cookie_profile, '')";
+
+fn download_row_to_json(row: &rusqlite::Row) -> rusqlite::Result<serde_json::Value> {
+    let error = row.get::<_, String>(12)?;
+    let error_code = (!error.is_empty()).then(|| crate::error::ErrorCode::classify(&error));
+    Ok(serde_json::json!({
+        "id": row.get::<_, String>(0)?,
+        "url": row.get::<_, String>(1)?,
+        "title": row.get::<_, String>(2)?,
+        "thumbnail": row.get::<_, String>(3)?,
+        "status": row.get::<_, String>(4)?,
+        "progress": row.get::<_, f64>(5)?,
+        "speed": row.get::<_, String>(6)?,
+        "eta": row.get::<_, String>(7)?,
+        "filePath": row.get::<_, String>(8)?,
+        "fileSize": row.get::<_, i64>(9)?,
+        "formatId": row.get::<_, String>(10)?,
+        "formatLabel": row.get::<_, String>(11)?,
+        "error": error,
+        "errorCode": error_code,
+        "priority": row.get::<_, i32>(13).unwrap_or(0),
+        "createdAt": row.get::<_, String>(14)?,
+        "updatedAt": row.get::<_, String>(15)?,
+        "source": row.get::<_, String>(16).unwrap_or_else(|_| "single".to_string()),
+        "section": row.get::<_, String>(17).unwrap_or_default(),
+        "note": row.get::<_, String>(18).unwrap_or_default(),
+        "mode": row.get::<_, String>(19).unwrap_or_default(),
+        "description": row.get::<_, String>(20).unwrap_or_default(),
+        "tags": serde_json::from_str::<Vec<String>>(&row.get::<_, String>(21).unwrap_or_default())
+            .unwrap_or_default(),
+        "uploadDate": row.get::<_, String>(22).unwrap_or_default(),
+        "viewCount": row.get::<_, Option<i64>>(23).unwrap_or(None),
+        "concurrentFragments": row.get::<_, u32>(24).unwrap_or(0),
+        "outputDir": row.get::<_, String>(25).unwrap_or_default(),
+        "batchId": row.get::<_, String>(26).unwrap_or_default(),
+        "duration": row.get::<_, Option<f64>>(27).unwrap_or(None),
+        "cookieProfile": row.get::<_, String>(28).unwrap_or_default(),
+    }))
+}
+
+/// A feed with its item counts, as listed by [`Database::get_feeds`].
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedSummary {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    pub channel_name: String,
+    pub channel_avatar: String,
+    pub auto_download: bool,
+    pub keywords: String,
+    pub last_checked: String,
+    pub created_at: String,
+    pub item_count: i64,
+    /// Items not downloaded yet.
+    pub new_count: i64,
+    pub latest_published_at: String,
+    /// Where auto-downloads of the feed go, see
+    /// [`crate::rss::render_folder_template`]. Empty for the download folder.
+    pub folder_template: String,
+    /// Channel tabs followed, a comma-separated list of
+    /// [`crate::rss::CHANNEL_TABS`]. Only used for YouTube channels.
+    pub tabs: String,
+    /// Cookie profile for downloads of the feed's items, empty for
+    /// `browser_cookies`. See [`crate::cookie_profiles`].
+    pub cookie_profile: String,
+}
+
+/// A scheduled premiere or livestream, see [`crate::live_schedule`].
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingStream {
+    pub video_id: String,
+    pub feed_id: String,
+    pub feed_title: String,
+    pub title: String,
+    pub url: String,
+    pub thumbnail: String,
+    /// Unix seconds.
+    pub scheduled_at: i64,
+    /// Whether "starting soon" was sent.
+    pub notified: bool,
+    /// The recording, empty until one is started.
+    pub download_id: String,
+    pub auto_download: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedItem {
+    pub id: String,
+    pub video_id: String,
+    pub title: String,
+    pub thumbnail: String,
+    pub url: String,
+    pub published_at: String,
+    /// "downloaded" or "not_queued".
+    pub status: String,
+    /// "video" or "short".
+    pub video_type: String,
+}
+
+impl From<&crate::rss::RssItem> for FeedItem {
+    fn from(item: &crate::rss::RssItem) -> Self {
+        Self {
+            id: item.id.clone(),
+            video_id: item.video_id.clone(),
+            title: item.title.clone(),
+            thumbnail: item.thumbnail.clone(),
+            url: item.url.clone(),
+            published_at: item.published_at.clone(),
+            status: if item.downloaded { "downloaded" } else { "not_queued" }.to_string(),
+            video_type: item.video_type.clone(),
+        }
+    }
+}
+
+/// Row from `SELECT id, video_id, title, thumbnail, url, published_at,
+/// downloaded, video_type FROM feed_items`.
+fn feed_item_from_row(row: &rusqlite::Row) -> rusqlite::Result<FeedItem> {
+    let downloaded = row.get::<_, i64>(6).unwrap_or(0) != 0;
+    Ok(FeedItem {
+        id: row.get(0)?,
+        video_id: row.get(1)?,
+        title: row.get(2)?,
+        thumbnail: row.get(3)?,
+        url: row.get(4)?,
+        published_at: row.get(5)?,
+        status: if downloaded { "downloaded" } else { "not_queued" }.to_string(),
+        video_type: row.get::<_, Option<String>>(7)?.unwrap_or_else(|| "video".to_string()),
+    })
+}
+
+const PLAYLIST_ITEM_SELECT: &str = "SELECT pi.id, pi.playlist_id, pi.download_id, pi.video_id, pi.url, pi.title, pi.thumbnail, pi.playlist_index,
+        CASE WHEN pi.status IN ('removed', 'unavailable') THEN pi.status ELSE COALESCE(d.status, pi.status) END,
+        COALESCE(d.progress, 0.0), COALESCE(d.error, ''), COALESCE(d.file_path, ''), pi.updated_at
+     FROM playlist_items pi
+     LEFT JOIN downloads d ON d.id = pi.download_id";
+
+fn playlist_item_row_to_json(row: &rusqlite::Row) -> rusqlite::Result<serde_json::Value> {
+    Ok(serde_json::json!({
+        "id": row.get::<_, String>(0)?,
+        "playlistId": row.get::<_, String>(1)?,
+        "downloadId": row.get::<_, Option<String>>(2)?,
+        "videoId": row.get::<_, String>(3)?,
+        "url": row.get::<_, String>(4)?,
+        "title": row.get::<_, String>(5)?,
+        "thumbnail": row.get::<_, String>(6)?,
+        "index": row.get::<_, i64>(7)?,
+        "status": row.get::<_, String>(8)?,
+        "progress": row.get::<_, f64>(9)?,
+        "error": row.get::<_, String>(10)?,
+        "filePath": row.get::<_, String>(11)?,
+        "updatedAt": row.get::<_, String>(12)?,
+    }))
 }
@@ -0,0 +1,172 @@
+//! `ytdl://` links from browsers and scripts.
+//!
+//! `ytdl://download?url=<video url>[&format=<format id>][&quality=1080p][&mode=audio][&section=1:30-2:45]`
+//! opens (or focuses) the app and queues the download. Plain `http(s)`
+//! URLs handed to the app are treated the same as a `download` link.
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands;
+use crate::download::QualityPreference;
+
+pub const SCHEME: &str = "ytdl";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadLink {
+    pub url: String,
+    pub format_id: Option<String>,
+    pub mode: Option<String>,
+    /// Time range or chapter pattern, see `start_download`.
+    pub section: Option<String>,
+}
+
+/// Parse an incoming link. Accepts `ytdl://download?...` and bare
+/// `http(s)://` video URLs.
+pub fn parse_link(link: &str) -> Result<DownloadLink, String> {
+    let parsed = url::Url::parse(link.trim()).map_err(|e| format!("Invalid link: {}", e))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(DownloadLink {
+            url: parsed.to_string(),
+            format_id: None,
+            mode: None,
+            section: None,
+        }),
+        SCHEME => {
+            // `ytdl://download?..` puts the action in the host, `ytdl:download?..` in the path
+            let action = parsed
+                .host_str()
+                .unwrap_or_else(|| parsed.path().trim_matches('/'))
+                .to_lowercase();
+            if action != "download" {
+                return Err(format!("Unsupported ytdl:// action '{}'", action));
+            }
+
+            let param = |name: &str| {
+                parsed
+                    .query_pairs()
+                    .find(|(k, _)| k == name)
+                    .map(|(_, v)| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+            };
+            let url = param("url").ok_or_else(|| "ytdl://download link has no url parameter".to_string())?;
+            // An explicit format wins; a quality is turned into a format
+            // expression the same way the default quality setting is
+            let format_id = param("format").or_else(|| {
+                param("quality").map(|q| QualityPreference::from_quality(&q).format_expression())
+            });
+            Ok(DownloadLink {
+                url,
+                format_id,
+                mode: param("mode"),
+                section: param("section"),
+            })
+        }
+        other => Err(format!("Unsupported link scheme '{}'", other)),
+    }
+}
+
+/// Bring the main window to the front.
+pub fn focus_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// Focus the app and queue the download a link describes. Progress is
+/// reported through the usual download events; the outcome of queueing is
+/// emitted as `external-download` (`{url, id}` or `{url, error}`).
+pub fn handle_link(app: &AppHandle, link: &str) {
+    focus_main_window(app);
+
+    let request = match parse_link(link) {
+        Ok(request) => request,
+        Err(e) => {
+            log::warn!("[deep_link] Rejected link: {}", e);
+            let _ = app.emit("external-download", serde_json::json!({ "url": link, "error": e }));
+            return;
+        }
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        log::info!("[deep_link] Queueing {}", request.url);
+        let result = commands::start_download(
+            app.clone(),
+            app.state(),
+            app.state(),
+            request.url.clone(),
+            request.format_id,
+            request.mode,
+            None,
+            None,
+            request.section,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await;
+        let payload = match result {
+            Ok(id) => serde_json::json!({ "url": request.url, "id": id }),
+            Err(e) => {
+                log::warn!("[deep_link] Failed to queue {}: {}", request.url, e);
+                serde_json::json!({ "url": request.url, "error": e.to_string(), "code": e.code() })
+            }
+        };
+        let _ = app.emit("external-download", payload);
+    });
+}
+
+/// Video URLs passed as command-line arguments. `ytdl://` links are left
+/// out: the deep-link plugin delivers those itself.
+pub fn urls_from_args<I: IntoIterator<Item = String>>(args: I) -> Vec<String> {
+    args.into_iter()
+        .filter(|arg| {
+            let lower = arg.to_lowercase();
+            lower.starts_with("http://") || lower.starts_with("https://")
+        })
+        .collect()
+}
+
+/// A second launch was redirected here by the single-instance plugin:
+/// focus the window and queue any URLs it was given.
+pub fn handle_second_instance(app: &AppHandle, argv: Vec<String>) {
+    let urls = urls_from_args(argv.into_iter().skip(1));
+    log::info!("[deep_link] Second instance forwarded {} URL(s)", urls.len());
+    focus_main_window(app);
+    for url in urls {
+        handle_link(app, &url);
+    }
+}
+
+/// Hook the OS URL handler up to [`handle_link`] and process the link the
+/// app was launched with, if any.
+pub fn init(app: &tauri::App) {
+    use tauri_plugin_deep_link::DeepLinkExt;
+
+    // Installers register the scheme on Windows/macOS; this covers Linux
+    // AppImages and dev builds
+    #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+    if let Err(e) = app.deep_link().register_all() {
+        log::warn!("[deep_link] Could not register {}:// handler: {}", SCHEME, e);
+    }
+
+    let handle = app.handle().clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle_link(&handle, url.as_str());
+        }
+    });
+
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        for url in urls {
+            handle_link(app.handle(), url.as_str());
+        }
+    }
+    // `ytdl https://...` from a shell or "Open with" on the first launch
+    for url in urls_from_args(std::env::args().skip(1)) {
+        handle_link(app.handle(), &url);
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::Manager;
 use tokio::process::Command;
 
@@ -244,6 +244,7 @@ pub fn create_hidden_command(program: &str) -> Command {
     
     let mut cmd = Command::new(program);
     cmd.creation_flags(CREATE_NO_WINDOW);
+    crate::proxy::apply_to_command(&mut cmd);
     cmd
 }
 
@@ -355,6 +356,7 @@ pub fn create_hidden_command(program: &str) -> Command {
         cmd.env("LC_ALL", "en_US.UTF-8");
     }
 
+    crate::proxy::apply_to_command(&mut cmd);
     cmd
 }
 
@@ -397,8 +399,10 @@ fn get_android_cache_dir() -> Option<PathBuf> {
     None
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+/// Missing fields default so the frontend can hand back what it got from
+/// `get_video_info` (see `start_download`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct VideoInfo {
     pub id: String,
     pub title: String,
@@ -407,6 +411,30 @@ pub struct VideoInfo {
     pub uploader: String,
     pub url: String,
     pub formats: Vec<VideoFormat>,
+    /// Empty when the video has no chapters.
+    pub chapters: Vec<Chapter>,
+    /// Audio languages, only listed when there is more than one.
+    pub audio_tracks: Vec<AudioTrack>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioTrack {
+    /// yt-dlp's language code, e.g. `en-US`.
+    pub language: String,
+    /// e.g. "English (United States)".
+    pub label: String,
+    /// The video's own audio rather than a dub.
+    pub original: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chapter {
+    pub title: String,
+    /// Seconds from the start of the video.
+    pub start: f64,
+    pub end: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -423,6 +451,9 @@ pub struct VideoFormat {
     pub fps: Option<f64>,
     pub tbr: Option<f64>,
     pub format_note: String,
+    /// Audio language, when yt-dlp knows it.
+    #[serde(default)]
+    pub language: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -433,6 +464,9 @@ pub struct PlaylistEntry {
     pub url: String,
     pub index: usize,
     pub thumbnail: Option<String>,
+    /// `YYYYMMDD`, when yt-dlp reports one (exact or approximate).
+    #[serde(default)]
+    pub upload_date: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -451,9 +485,86 @@ pub struct DownloadProgress {
     pub progress: f64,
     pub speed: String,
     pub eta: String,
+    /// Size of the file being downloaded as yt-dlp prints it, e.g.
+    /// `~100.50MiB`; empty when unknown.
+    #[serde(default)]
+    pub total: String,
     pub status: String,
 }
 
+/// What a download is, used to pick its destination folder.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MediaKind {
+    Video,
+    Audio,
+    Short,
+    Podcast,
+}
+
+/// Direct links to audio files (podcast enclosures).
+const PODCAST_EXTENSIONS: &[&str] = &[".mp3", ".m4a", ".aac", ".ogg", ".opus", ".oga"];
+
+impl MediaKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "video" => Some(Self::Video),
+            "audio" => Some(Self::Audio),
+            "short" | "shorts" => Some(Self::Short),
+            "podcast" | "podcasts" => Some(Self::Podcast),
+            _ => None,
+        }
+    }
+
+    /// The name [`from_name`](Self::from_name) reads back.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Video => "video",
+            Self::Audio => "audio",
+            Self::Short => "shorts",
+            Self::Podcast => "podcast",
+        }
+    }
+
+    /// Setting holding this kind's folder. Empty means `download_path`.
+    pub fn path_setting(self) -> &'static str {
+        match self {
+            Self::Video => "video_download_path",
+            Self::Audio => "audio_download_path",
+            Self::Short => "shorts_download_path",
+            Self::Podcast => "podcast_download_path",
+        }
+    }
+
+    /// Guess the kind from the URL and requested format. `formats` (from
+    /// `fetch_video_info`, may be empty) resolves numeric format ids.
+    pub fn detect(url: &str, format_id: Option<&str>, formats: &[VideoFormat]) -> Self {
+        let path = url::Url::parse(url)
+            .map(|u| u.path().to_lowercase())
+            .unwrap_or_default();
+        if PODCAST_EXTENSIONS.iter().any(|ext| path.ends_with(ext)) {
+            return Self::Podcast;
+        }
+        if path.starts_with("/shorts/") {
+            return Self::Short;
+        }
+
+        let audio_only = match format_id.map(str::trim) {
+            Some(fid) if fid.starts_with("bestaudio") || fid.starts_with("worstaudio") || fid == "audio" => true,
+            Some(fid) if !fid.is_empty() => fid.split('+').all(|part| {
+                formats
+                    .iter()
+                    .any(|f| f.format_id == part && (f.vcodec == "none" || f.vcodec.is_empty()) && f.acodec != "none")
+            }),
+            _ => false,
+        };
+        if audio_only {
+            Self::Audio
+        } else {
+            Self::Video
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ActiveDownload {
     pub id: String,
@@ -521,13 +632,32 @@ impl DownloadManager {
     }
 }
 
+/// User-chosen binary from the settings (`ytdlp_path`, `ffmpeg_path`),
+/// if set and still present on disk.
+pub fn binary_override(app_handle: &tauri::AppHandle, key: &str) -> Option<String> {
+    let db = app_handle.try_state::<std::sync::Arc<std::sync::Mutex<crate::db::Database>>>()?;
+    let path = db.lock().ok()?.get_setting(key).unwrap_or(None)?;
+    let path = path.trim();
+    if path.is_empty() {
+        return None;
+    }
+    match crate::settings::check_tool_path(key, path) {
+        Ok(()) => Some(path.to_string()),
+        Err(e) => {
+            log::warn!("[binary_override] Ignoring {}: {}", key, e);
+            None
+        }
+    }
+}
+
 /// Resolves the yt-dlp binary path.
 /// Priority:
 /// 1) Environment variable YTDLP_PATH
-/// 2) [Android] Bundled libytdlp.so in nativeLibraryDir
-/// 3) [Android] Termux yt-dlp installation
-/// 4) Installed binary in bin_dir
-/// 5) System PATH
+/// 2) `ytdlp_path` setting
+/// 3) [Android] Bundled libytdlp.so in nativeLibraryDir
+/// 4) [Android] Termux yt-dlp installation
+/// 5) Installed binary in bin_dir
+/// 6) System PATH
 pub fn get_ytdlp_path(app_handle: &tauri::AppHandle) -> String {
     // Check environment variable first (for custom path)
     if let Ok(custom_path) = std::env::var("YTDLP_PATH") {
@@ -536,6 +666,11 @@ pub fn get_ytdlp_path(app_handle: &tauri::AppHandle) -> String {
             return custom_path;
         }
     }
+
+    if let Some(custom_path) = binary_override(app_handle, "ytdlp_path") {
+        log::info!("[get_ytdlp_path] Using custom path from settings: {}", custom_path);
+        return custom_path;
+    }
     
     // On Android, check nativeLibraryDir first (has exec permissions for .so files).
     // NOTE: Bundled Linux ARM64 `yt-dlp` binaries will likely fail on Android because
@@ -580,10 +715,11 @@ pub fn get_ytdlp_path(app_handle: &tauri::AppHandle) -> String {
 /// Resolves the ffmpeg binary path.
 /// Priority:
 /// 1) Environment variable FFMPEG_PATH
-/// 2) [Android] Bundled libffmpeg.so in nativeLibraryDir
-/// 3) [Android] Termux ffmpeg installation
-/// 4) Installed binary in bin_dir
-/// 5) System PATH
+/// 2) `ffmpeg_path` setting
+/// 3) [Android] Bundled libffmpeg.so in nativeLibraryDir
+/// 4) [Android] Termux ffmpeg installation
+/// 5) Installed binary in bin_dir
+/// 6) System PATH
 pub fn get_ffmpeg_path(app_handle: &tauri::AppHandle) -> String {
     // Check environment variable first (for custom path)
     if let Ok(custom_path) = std::env::var("FFMPEG_PATH") {
@@ -592,6 +728,11 @@ pub fn get_ffmpeg_path(app_handle: &tauri::AppHandle) -> String {
             return custom_path;
         }
     }
+
+    if let Some(custom_path) = binary_override(app_handle, "ffmpeg_path") {
+        log::info!("[get_ffmpeg_path] Using custom path from settings: {}", custom_path);
+        return custom_path;
+    }
     
     // On Android, check nativeLibraryDir first (same ELF interpreter caveat as yt-dlp).
     #[cfg(target_os = "android")]
@@ -627,6 +768,16 @@ pub fn get_ffmpeg_path(app_handle: &tauri::AppHandle) -> String {
 
 /// Get ffprobe path (used alongside ffmpeg)
 pub fn get_ffprobe_path(app_handle: &tauri::AppHandle) -> String {
+    // A custom ffmpeg usually ships its ffprobe in the same folder
+    if let Some(custom_ffmpeg) = binary_override(app_handle, "ffmpeg_path") {
+        let name = if cfg!(windows) { "ffprobe.exe" } else { "ffprobe" };
+        if let Some(probe) = PathBuf::from(custom_ffmpeg).parent().map(|dir| dir.join(name)) {
+            if probe.is_file() {
+                return probe.to_string_lossy().to_string();
+            }
+        }
+    }
+
     // On Android, check nativeLibraryDir first
     #[cfg(target_os = "android")]
     {
@@ -659,8 +810,10 @@ pub fn get_ffprobe_path(app_handle: &tauri::AppHandle) -> String {
 pub async fn fetch_video_info(ytdlp: &str, url: &str) -> AppResult<VideoInfo> {
     log::info!("[fetch_video_info] Using yt-dlp: {}", ytdlp);
     log::info!("[fetch_video_info] URL: {}", url);
-    
+    crate::rate_limit::acquire().await.map_err(AppError::YtDlp)?;
+
     let output = create_hidden_command(ytdlp)
+        .args(crate::extractor_args::ytdlp_args())
         .args(["--dump-json", "--no-download", "--no-warnings", url])
         .output()
         .await
@@ -679,6 +832,7 @@ pub async fn fetch_video_info(ytdlp: &str, url: &str) -> AppResult<VideoInfo> {
             }
         })?;
 
+    crate::rate_limit::observe(&String::from_utf8_lossy(&output.stderr));
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(AppError::YtDlp(format!(
@@ -722,10 +876,29 @@ fn parse_video_info_json_inner(json: &serde_json::Value, url: &str) -> AppResult
                 fps: f["fps"].as_f64(),
                 tbr: f["tbr"].as_f64(),
                 format_note: f["format_note"].as_str().unwrap_or("").to_string(),
+                language: f["language"].as_str().filter(|l| !l.is_empty()).map(String::from),
             })
         })
         .collect();
 
+    let audio_tracks = parse_audio_tracks(json);
+
+    let chapters = json["chapters"]
+        .as_array()
+        .map(|chapters| {
+            chapters
+                .iter()
+                .filter_map(|c| {
+                    Some(Chapter {
+                        title: c["title"].as_str().unwrap_or_default().to_string(),
+                        start: c["start_time"].as_f64()?,
+                        end: c["end_time"].as_f64()?,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
     Ok(VideoInfo {
         id: json["id"].as_str().unwrap_or("").to_string(),
         title: json["title"].as_str().unwrap_or("Unknown").to_string(),
@@ -734,22 +907,117 @@ fn parse_video_info_json_inner(json: &serde_json::Value, url: &str) -> AppResult
         uploader: json["uploader"].as_str().unwrap_or("Unknown").to_string(),
         url: url.to_string(),
         formats,
+        chapters,
+        audio_tracks,
     })
 }
 
+/// What an .info.json says about a video besides its title and thumbnail.
+#[derive(Debug, Clone, Default)]
+pub struct MediaDetails {
+    pub description: String,
+    pub tags: Vec<String>,
+    /// `YYYY-MM-DD`, empty when unknown.
+    pub upload_date: String,
+    pub view_count: Option<i64>,
+    /// Seconds.
+    pub duration: Option<f64>,
+}
+
+impl MediaDetails {
+    pub fn from_json(json: &serde_json::Value) -> Self {
+        let tags = json["tags"]
+            .as_array()
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|t| t.as_str())
+                    .filter(|t| !t.trim().is_empty())
+                    .map(|t| t.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        // yt-dlp reports YYYYMMDD
+        let upload_date = json["upload_date"]
+            .as_str()
+            .filter(|d| d.len() == 8 && d.bytes().all(|b| b.is_ascii_digit()))
+            .map(|d| format!("{}-{}-{}", &d[..4], &d[4..6], &d[6..]))
+            .unwrap_or_default();
+        Self {
+            description: json["description"].as_str().unwrap_or("").trim().to_string(),
+            tags,
+            upload_date,
+            view_count: json["view_count"].as_i64(),
+            duration: json["duration"].as_f64().filter(|d| *d > 0.0),
+        }
+    }
+}
+
+/// The .info.json yt-dlp wrote next to a downloaded file, if there is one.
+/// It shares the file's name, e.g. `Title.info.json` for `Title.mp4`.
+pub fn read_info_json(file_path: &str) -> Option<serde_json::Value> {
+    if file_path.is_empty() {
+        return None;
+    }
+    let path = Path::new(file_path).with_extension("info.json");
+    let content = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(json) => Some(json),
+        Err(e) => {
+            log::warn!("[info_json] Failed to parse {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
 /// Fetch playlist metadata via yt-dlp --flat-playlist
 pub async fn fetch_playlist_info(ytdlp: &str, url: &str) -> AppResult<PlaylistInfo> {
+    fetch_playlist_info_with_args(ytdlp, url, &[]).await
+}
+
+/// Flat playlist fetch with extra yt-dlp arguments (e.g. extractor args
+/// that add approximate upload dates to flat entries).
+pub async fn fetch_playlist_info_with_args(
+    ytdlp: &str,
+    url: &str,
+    extra_args: &[&str],
+) -> AppResult<PlaylistInfo> {
+    let json = run_flat_playlist(ytdlp, url, extra_args).await?;
+    Ok(playlist_from_json(&json, 1))
+}
+
+/// One page of a flat playlist: entries `start` to `end` (1-based,
+/// inclusive), numbered by their position in the whole playlist. Also
+/// returns how many entries yt-dlp listed, counting ones without a usable
+/// URL, so a short page marks the end, and the playlist's total size when
+/// the extractor reports it.
+pub async fn fetch_playlist_page(
+    ytdlp: &str,
+    url: &str,
+    extra_args: &[&str],
+    start: usize,
+    end: usize,
+) -> AppResult<(PlaylistInfo, usize, Option<usize>)> {
+    let range = format!("{}:{}", start, end);
+    let mut args: Vec<&str> = vec!["--playlist-items", &range];
+    args.extend_from_slice(extra_args);
+    let json = run_flat_playlist(ytdlp, url, &args).await?;
+    let listed = json["entries"].as_array().map(|e| e.len()).unwrap_or(0);
+    let total = json["playlist_count"].as_u64().map(|n| n as usize);
+    Ok((playlist_from_json(&json, start), listed, total))
+}
+
+async fn run_flat_playlist(ytdlp: &str, url: &str, extra_args: &[&str]) -> AppResult<serde_json::Value> {
+    crate::rate_limit::acquire().await.map_err(AppError::YtDlp)?;
     let output = create_hidden_command(ytdlp)
-        .args([
-            "-J",
-            "--flat-playlist",
-            "--no-warnings",
-            url,
-        ])
+        .args(["-J", "--flat-playlist", "--no-warnings"])
+        .args(crate::extractor_args::ytdlp_args())
+        .args(extra_args)
+        .arg(url)
         .output()
         .await
         .map_err(|e| AppError::YtDlp(format!("Failed to execute yt-dlp: {}", e)))?;
 
+    crate::rate_limit::observe(&String::from_utf8_lossy(&output.stderr));
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(AppError::YtDlp(format!(
@@ -759,9 +1027,12 @@ pub async fn fetch_playlist_info(ytdlp: &str, url: &str) -> AppResult<PlaylistIn
         )));
     }
 
-    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
-        .map_err(|e| AppError::YtDlp(format!("Failed to parse yt-dlp JSON: {}", e)))?;
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| AppError::YtDlp(format!("Failed to parse yt-dlp JSON: {}", e)))
+}
 
+/// Entries of a flat playlist JSON, the first one numbered `first_index`.
+fn playlist_from_json(json: &serde_json::Value, first_index: usize) -> PlaylistInfo {
     let entries = json["entries"]
         .as_array()
         .unwrap_or(&Vec::new())
@@ -790,18 +1061,528 @@ pub async fn fetch_playlist_info(ytdlp: &str, url: &str) -> AppResult<PlaylistIn
                 id: id.to_string(),
                 title: title.to_string(),
                 url: entry_url,
-                index: idx + 1,
+                index: first_index + idx,
                 thumbnail: entry["thumbnail"].as_str().map(String::from),
+                upload_date: entry["upload_date"]
+                    .as_str()
+                    .map(String::from)
+                    .or_else(|| {
+                        entry["timestamp"]
+                            .as_i64()
+                            .or_else(|| entry["release_timestamp"].as_i64())
+                            .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                            .map(|dt| dt.format("%Y%m%d").to_string())
+                    }),
             })
         })
         .collect::<Vec<_>>();
 
-    Ok(PlaylistInfo {
+    PlaylistInfo {
         id: json["id"].as_str().unwrap_or("").to_string(),
         title: json["title"].as_str().unwrap_or("Playlist").to_string(),
         entry_count: entries.len(),
         entries,
-    })
+    }
+}
+
+/// Output template used when the caller doesn't pass one.
+pub const DEFAULT_FILE_TEMPLATE: &str = "%(title)s.%(ext)s";
+
+/// Longest file name yt-dlp may produce, extension excluded. Leaves room
+/// for `.f137.mp4.part` style suffixes under the usual 255-byte limit.
+const MAX_FILENAME_LEN: usize = 180;
+
+/// Names Windows refuses for files regardless of extension.
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Make a string safe to use as a single path component (playlist
+/// subfolders, literal parts of templates).
+pub fn sanitize_path_component(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '|' | '?' | ':' | '*' | '<' | '>' | '"' | '/' | '\\' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let trimmed = cleaned.trim().trim_matches('.').trim();
+    if trimmed.is_empty() {
+        return "_".to_string();
+    }
+    let truncated: String = trimmed.chars().take(120).collect();
+    let stem = truncated.split('.').next().unwrap_or_default().trim_end();
+    if WINDOWS_RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(stem)) {
+        format!("_{}", truncated)
+    } else {
+        truncated
+    }
+}
+
+/// Whether an output template stays inside the download folder: relative,
+/// no drive letter and no `..` component.
+pub fn is_safe_output_template(template: &str) -> bool {
+    let template = template.trim();
+    !template.is_empty()
+        && !template.starts_with('/')
+        && !template.starts_with('\\')
+        && template.chars().nth(1) != Some(':')
+        && !template.split(['/', '\\']).any(|part| part.trim() == "..")
+}
+
+/// Check that the existing file `path` lies under `dir` once symlinks
+/// and `..` are resolved.
+fn ensure_within(dir: &Path, path: &Path) -> Result<(), String> {
+    let dir = dir
+        .canonicalize()
+        .map_err(|e| format!("Cannot resolve download folder {}: {}", dir.display(), e))?;
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("Cannot resolve {}: {}", path.display(), e))?;
+    if path.starts_with(&dir) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Output file {} is outside the download folder {}",
+            path.display(),
+            dir.display()
+        ))
+    }
+}
+
+/// Render a playlist naming template for one entry. Each entry is
+/// downloaded by its own video URL, so yt-dlp never sees the playlist
+/// context — `%(playlist_index)…` and `%(playlist_title)s`/`%(playlist)s`
+/// are substituted here. Returns `None` for templates that try to escape
+/// the output directory.
+pub fn render_playlist_template(template: &str, index: usize, playlist_title: &str) -> Option<String> {
+    static INDEX_RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let index_re = INDEX_RE.get_or_init(|| {
+        regex::Regex::new(r"%\(playlist_index\)(0?)(\d*)([ds])").unwrap()
+    });
+
+    let template = template.trim();
+    if !is_safe_output_template(template) {
+        return None;
+    }
+
+    let rendered = index_re.replace_all(template, |caps: &regex::Captures| {
+        let width: usize = caps[2].parse().unwrap_or(0);
+        if &caps[1] == "0" {
+            format!("{:0width$}", index, width = width)
+        } else {
+            format!("{:width$}", index, width = width)
+        }
+    });
+    // Literal '%' in titles must be escaped for yt-dlp's template engine
+    let title = sanitize_path_component(playlist_title).replace('%', "%%");
+    let rendered = rendered
+        .replace("%(playlist_title)s", &title)
+        .replace("%(playlist)s", &title);
+
+    if rendered.contains("%(ext)s") {
+        Some(rendered)
+    } else {
+        Some(format!("{}.%(ext)s", rendered))
+    }
+}
+
+/// Parse `[[HH:]MM:]SS[.fff]` into seconds.
+fn parse_timestamp(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if value.is_empty() || value.split(':').count() > 3 {
+        return None;
+    }
+    let mut seconds = 0.0;
+    for part in value.split(':') {
+        if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return None;
+        }
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(seconds)
+}
+
+/// Seconds as yt-dlp takes them, without a trailing `.0`.
+fn format_seconds(seconds: f64) -> String {
+    let rounded = (seconds * 1000.0).round() / 1000.0;
+    if rounded.fract() == 0.0 {
+        format!("{}", rounded as u64)
+    } else {
+        format!("{}", rounded)
+    }
+}
+
+/// `1h02m03s`, `2m05s` or `45s`, for file names.
+fn format_clip_time(seconds: f64) -> String {
+    let total = seconds.floor() as u64;
+    let (h, m, s) = (total / 3600, total % 3600 / 60, total % 60);
+    if h > 0 {
+        format!("{}h{:02}m{:02}s", h, m, s)
+    } else if m > 0 {
+        format!("{}m{:02}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+/// Split a `*START-END` range (the `*` is optional). An empty start is the
+/// beginning, an empty end or `inf` the end of the video.
+fn parse_time_range(range: &str) -> Option<(f64, Option<f64>)> {
+    let (start, end) = range.strip_prefix('*').unwrap_or(range).split_once('-')?;
+    let start = match start.trim() {
+        "" => 0.0,
+        s => parse_timestamp(s)?,
+    };
+    let end = match end.trim() {
+        "" | "inf" => None,
+        e => Some(parse_timestamp(e)?),
+    };
+    Some((start, end))
+}
+
+/// Check a clip requested for a download and return it in
+/// `--download-sections` form. A time range (`1:30-2:45`, `90-`,
+/// `*0:10-inf`) becomes `*START-END` in seconds; anything else is a regex
+/// matched against chapter titles and passed on as is.
+pub fn normalize_download_section(section: &str) -> Result<String, String> {
+    let section = section.trim();
+    if section.is_empty() || section.chars().any(|c| c.is_control()) {
+        return Err("Invalid section: expected START-END or a chapter title pattern".to_string());
+    }
+    if let Some((start, end)) = parse_time_range(section) {
+        if end.is_some_and(|end| end <= start) {
+            return Err(format!("Invalid section '{}': the end must come after the start", section));
+        }
+        return Ok(format!(
+            "*{}-{}",
+            format_seconds(start),
+            end.map(format_seconds).unwrap_or_else(|| "inf".to_string())
+        ));
+    }
+    if section.starts_with('*') {
+        return Err(format!("Invalid time range '{}': expected *START-END", section));
+    }
+    regex::Regex::new(section).map_err(|e| format!("Invalid chapter pattern '{}': {}", section, e))?;
+    Ok(section.to_string())
+}
+
+/// Add the clip to a file name template, so a section never overwrites
+/// the full video or another clip of it: `Title [clip 1m30s-2m45s].mp4`,
+/// or the chapter title for chapter selections. Templates that don't end
+/// in `.%(ext)s` are left alone.
+pub fn section_file_template(template: &str, section: &str) -> String {
+    let label = match parse_time_range(section) {
+        Some((start, end)) => format!(
+            "clip {}-{}",
+            format_clip_time(start),
+            end.map(format_clip_time).unwrap_or_else(|| "end".to_string())
+        ),
+        None => "%(section_title)s".to_string(),
+    };
+    match template.strip_suffix(".%(ext)s") {
+        Some(stem) => format!("{} [{}].%(ext)s", stem, label),
+        None => template.to_string(),
+    }
+}
+
+/// Distinct audio languages among the audio-only formats. YouTube's
+/// `format_note` reads "<language name>[ original], <quality>"; the first
+/// part becomes the label. Empty for single-language videos.
+fn parse_audio_tracks(json: &serde_json::Value) -> Vec<AudioTrack> {
+    let mut tracks: Vec<AudioTrack> = Vec::new();
+    for f in json["formats"].as_array().into_iter().flatten() {
+        let audio_only = f["vcodec"].as_str() == Some("none") && f["acodec"].as_str().is_some_and(|a| a != "none");
+        let Some(language) = f["language"].as_str().filter(|l| !l.is_empty() && audio_only) else {
+            continue;
+        };
+        if tracks.iter().any(|t| t.language == language) {
+            continue;
+        }
+        let note = f["format_note"].as_str().unwrap_or_default();
+        let label = note
+            .split(", ")
+            .next()
+            .filter(|l| note.contains(", ") && !l.is_empty())
+            .unwrap_or(language)
+            .trim_end_matches(" original");
+        tracks.push(AudioTrack {
+            language: language.to_string(),
+            label: label.to_string(),
+            original: f["language_preference"].as_i64().unwrap_or(0) >= 10 || note.contains("original"),
+        });
+    }
+    if tracks.len() < 2 {
+        return Vec::new();
+    }
+    tracks.sort_by_key(|t| !t.original);
+    tracks
+}
+
+/// Check audio language codes (`en`, `pt-BR`) passed to `start_download`.
+pub fn validate_audio_languages(languages: &[String]) -> Result<(), String> {
+    for language in languages {
+        let mut parts = language.split('-');
+        let primary = parts.next().unwrap_or_default();
+        let valid = (2..=3).contains(&primary.len())
+            && primary.chars().all(|c| c.is_ascii_alphabetic())
+            && parts.all(|p| !p.is_empty() && p.len() <= 8 && p.chars().all(|c| c.is_ascii_alphanumeric()));
+        if !valid {
+            return Err(format!("Invalid audio language '{}'", language));
+        }
+    }
+    Ok(())
+}
+
+/// Rewrite a format selector so its audio comes from the given language
+/// tracks, one stream per language. In every `/` alternative, `bestaudio`
+/// style parts and audio-only format ids are replaced; alternatives with
+/// no separate audio (`best`, combined formats) are kept as fallbacks.
+pub fn select_audio_languages(selector: &str, languages: &[String], formats: &[VideoFormat]) -> String {
+    let is_audio = |part: &str| {
+        part.starts_with("bestaudio")
+            || part.starts_with("worstaudio")
+            || formats
+                .iter()
+                .any(|f| f.format_id == part && (f.vcodec == "none" || f.vcodec.is_empty()) && f.acodec != "none")
+    };
+    let tracks: Vec<String> = languages
+        .iter()
+        .map(|l| format!("bestaudio[language^={}]", l))
+        .collect();
+    selector
+        .split('/')
+        .map(|alternative| {
+            let parts: Vec<&str> = alternative.split('+').collect();
+            if !parts.iter().any(|p| is_audio(p)) {
+                return alternative.to_string();
+            }
+            let mut rewritten: Vec<String> = parts
+                .iter()
+                .filter(|p| !is_audio(p))
+                .map(|p| p.to_string())
+                .collect();
+            rewritten.extend(tracks.iter().cloned());
+            rewritten.join("+")
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Whether a selector merges more than one audio stream, which yt-dlp
+/// only does with `--audio-multistreams`.
+pub fn has_multiple_audio_streams(selector: &str) -> bool {
+    selector
+        .split('/')
+        .any(|alternative| alternative.matches("bestaudio[language").count() > 1)
+}
+
+/// Format preference used when a download has no explicit format id.
+#[derive(Debug, Clone, Default)]
+pub struct QualityPreference {
+    /// Highest video height to pick, `None` = no limit.
+    pub max_height: Option<u32>,
+    /// "avc", "vp9" or "av1"; preferred but not required.
+    pub vcodec: Option<String>,
+    /// `Some(true)` prefers HDR video, `Some(false)` SDR.
+    pub hdr: Option<bool>,
+    /// Highest frame rate to prefer, `None` = any.
+    pub max_fps: Option<u32>,
+    /// Lowest audio bitrate (kbps) to prefer, 0 = any.
+    pub min_audio_bitrate: u32,
+    pub audio_only: bool,
+}
+
+impl QualityPreference {
+    /// Build from the `default_quality`, `preferred_vcodec`,
+    /// `preferred_dynamic_range`, `max_fps` and `min_audio_bitrate`
+    /// settings, read through `setting`. Unknown values mean "no preference".
+    pub fn from_settings(setting: impl Fn(&str) -> String) -> Self {
+        let quality = setting("default_quality").trim().to_lowercase();
+        let max_height = quality
+            .strip_suffix('p')
+            .and_then(|h| h.parse::<u32>().ok())
+            .or(if quality == "4k" { Some(2160) } else { None });
+        let vcodec = match setting("preferred_vcodec").trim().to_lowercase().as_str() {
+            v @ ("avc" | "vp9" | "av1") => Some(v.to_string()),
+            _ => None,
+        };
+        let hdr = match setting("preferred_dynamic_range").trim().to_lowercase().as_str() {
+            "hdr" => Some(true),
+            "sdr" => Some(false),
+            _ => None,
+        };
+        Self {
+            max_height,
+            vcodec,
+            hdr,
+            max_fps: setting("max_fps").trim().parse().ok().filter(|fps| *fps > 0),
+            min_audio_bitrate: setting("min_audio_bitrate").trim().parse().unwrap_or(0),
+            audio_only: quality == "audio",
+        }
+    }
+
+    /// Just a `default_quality` value, with no other preferences.
+    pub fn from_quality(quality: &str) -> Self {
+        Self::from_settings(|key| if key == "default_quality" { quality.to_string() } else { String::new() })
+    }
+
+    /// yt-dlp `-f` expression: the preferred combination first, then
+    /// progressively looser fallbacks so a download never fails just
+    /// because a preference can't be met.
+    pub fn format_expression(&self) -> String {
+        let audio = if self.min_audio_bitrate > 0 {
+            format!("ba[abr>={}]", self.min_audio_bitrate)
+        } else {
+            "ba".to_string()
+        };
+        if self.audio_only {
+            return dedup_alternatives(&[format!("{}/ba/b", audio)]);
+        }
+
+        let height = self
+            .max_height
+            .map(|h| format!("[height<={}]", h))
+            .unwrap_or_default();
+        let codec = match self.vcodec.as_deref() {
+            Some("avc") => "[vcodec^=avc1]",
+            Some("vp9") => "[vcodec~=^vp0?9]",
+            Some("av1") => "[vcodec^=av01]",
+            _ => "",
+        };
+        // Formats without a dynamic range are taken to be SDR
+        let range = match self.hdr {
+            Some(true) => "[dynamic_range!=SDR]",
+            Some(false) => "[dynamic_range=?SDR]",
+            None => "",
+        };
+        let fps = self
+            .max_fps
+            .map(|fps| format!("[fps<=?{}]", fps))
+            .unwrap_or_default();
+
+        // Codec goes first, then HDR/SDR, then the frame rate: HDR only
+        // comes in VP9 and AV1, and a frame rate cap is closest to a limit
+        dedup_alternatives(&[
+            format!("bv*{}{}{}{}+{}", height, fps, range, codec, audio),
+            format!("bv*{}{}{}+{}", height, fps, range, audio),
+            format!("bv*{}{}+{}", height, fps, audio),
+            format!("bv*{}+{}", height, audio),
+            format!("bv*{}+ba", height),
+            format!("b{}", height),
+            "bv*+ba/b".to_string(),
+        ])
+    }
+}
+
+/// yt-dlp's generic format selectors (see "FORMAT SELECTION" in its docs).
+const FORMAT_KEYWORDS: &[&str] = &[
+    "best", "worst", "bestvideo", "worstvideo", "bestaudio", "worstaudio",
+    "b", "w", "bv", "wv", "ba", "wa", "all", "mergeall",
+];
+
+/// Check a requested `-f` value before it reaches yt-dlp. Alternatives are
+/// separated by `/`, and the selector passes when at least one of them
+/// resolves: every format it merges (`+`) or lists (`,`) names a format
+/// from `formats` or a generic selector (`best`, `bv*[height<=1080]`, ...),
+/// optionally followed by `[...]` filters. So `137+140/best` is fine even
+/// when 137 is missing. With an empty `formats` list (nothing fetched yet)
+/// only the syntax is checked.
+pub fn validate_format_id(format_id: &str, formats: &[VideoFormat]) -> Result<(), String> {
+    let format_id = format_id.trim();
+    if format_id.len() > 200 {
+        return Err("Invalid format selector: too long".to_string());
+    }
+    let allowed_char = |c: char| c.is_ascii_alphanumeric() || "-_.*+/,[]<>=!^$~?:()'\" ".contains(c);
+    if let Some(bad) = format_id.chars().find(|c| !allowed_char(*c)) {
+        return Err(format!("Invalid format selector '{}': unexpected '{}'", format_id, bad));
+    }
+
+    let mut unknown = Vec::new();
+    if resolve_format_selector(format_id, formats, &mut unknown)
+        .map_err(|_| format!("Invalid format selector '{}'", format_id))?
+    {
+        return Ok(());
+    }
+    unknown.dedup();
+    let available: Vec<&str> = formats.iter().map(|f| f.format_id.as_str()).take(20).collect();
+    Err(format!(
+        "Requested format is not available for this video: {}. Available formats: {}",
+        unknown.join(", "),
+        available.join(", ")
+    ))
+}
+
+/// Split `selector` at each of `separators` outside `(...)` and `[...]`.
+fn split_top_level<'a>(selector: &'a str, separators: &[char]) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0i32, 0);
+    for (i, c) in selector.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            c if depth == 0 && separators.contains(&c) => {
+                parts.push(&selector[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&selector[start..]);
+    parts
+}
+
+/// Whether some `/` alternative of `selector` resolves against `formats`,
+/// collecting format ids that don't into `unknown`. `Err` on bad syntax.
+fn resolve_format_selector(selector: &str, formats: &[VideoFormat], unknown: &mut Vec<String>) -> Result<bool, ()> {
+    let mut resolved = false;
+    for alternative in split_top_level(selector, &['/']) {
+        let mut all_known = true;
+        for part in split_top_level(alternative, &['+', ',']) {
+            let part = part.trim();
+            if let Some(group) = part.strip_prefix('(') {
+                let close = group.rfind(')').ok_or(())?;
+                if !group[close + 1..].trim_start().is_empty() && !group[close + 1..].trim_start().starts_with('[') {
+                    return Err(());
+                }
+                all_known &= resolve_format_selector(&group[..close], formats, unknown)?;
+                continue;
+            }
+            // Filters like [height<=1080] follow the base selector
+            let base = part.split('[').next().unwrap_or_default().trim();
+            if base.is_empty() {
+                return Err(());
+            }
+            let keyword = base.trim_end_matches('*');
+            if FORMAT_KEYWORDS.contains(&keyword) {
+                continue;
+            }
+            if formats.is_empty() {
+                if !base.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                    return Err(());
+                }
+            } else if !formats.iter().any(|f| f.format_id == base) {
+                unknown.push(base.to_string());
+                all_known = false;
+            }
+        }
+        resolved |= all_known;
+    }
+    Ok(resolved)
+}
+
+/// Join `/`-separated format alternatives, dropping repeats.
+fn dedup_alternatives(parts: &[String]) -> String {
+    let mut seen: Vec<&str> = Vec::new();
+    for alt in parts.iter().flat_map(|p| p.split('/')) {
+        if !seen.contains(&alt) {
+            seen.push(alt);
+        }
+    }
+    seen.join("/")
 }
 
 /// Run yt-dlp download with progress reporting
@@ -811,12 +1592,22 @@ pub async fn run_download(
     url: &str,
     output_dir: &str,
     format_id: Option<&str>,
+    quality: &QualityPreference,
     extra_args: &[String],
     progress_tx: tokio::sync::mpsc::Sender<DownloadProgress>,
     cancel_rx: tokio::sync::watch::Receiver<bool>,
     download_id: String,
+    file_template: Option<&str>,
+    log_file: Option<PathBuf>,
 ) -> AppResult<String> {
-    let output_template = format!("{}/%(title)s.%(ext)s", output_dir);
+    let file_template = file_template.unwrap_or(DEFAULT_FILE_TEMPLATE);
+    if !is_safe_output_template(file_template) {
+        return Err(AppError::Download(format!(
+            "Invalid file name template '{}': it must be a relative path",
+            file_template
+        )));
+    }
+    let output_template = format!("{}/{}", output_dir, file_template);
 
     // For --ffmpeg-location: on Android with bundled .so files, create symlinks
     // from libffmpeg.so -> ffmpeg so yt-dlp can find them by standard name
@@ -869,6 +1660,11 @@ pub async fn run_download(
         output_template.clone(),
         "--print".to_string(),
         "after_move:filepath".to_string(),
+        // Titles may hold characters or lengths other systems (or a synced
+        // Windows share) can't store
+        "--windows-filenames".to_string(),
+        "--trim-filenames".to_string(),
+        MAX_FILENAME_LEN.to_string(),
     ];
 
     // Enable partial download resume (yt-dlp supports continuing partial files)
@@ -884,16 +1680,23 @@ pub async fn run_download(
         }
     } else {
         args.push("-f".to_string());
-        args.push("bestvideo+bestaudio/best".to_string());
+        args.push(quality.format_expression());
     }
 
-    // Merge audio+video when separate streams
-    args.push("--merge-output-format".to_string());
-    args.push("mp4".to_string());
+    // Merge audio+video when separate streams; see `remux` for formats
+    // that don't fit MP4
+    let handle_merge = !crate::remux::overridden_by(extra_args);
+    if handle_merge {
+        args.push("--merge-output-format".to_string());
+        args.push(crate::remux::MERGE_OUTPUT_FORMAT.to_string());
+        args.push("--print".to_string());
+        args.push(crate::remux::PRINT_TEMPLATE.to_string());
+    }
 
     for extra in extra_args {
         args.push(extra.clone());
     }
+    args.extend(crate::extractor_args::ytdlp_args());
     args.push(url.to_string());
 
     log::info!("[run_download] Starting yt-dlp: {} {}", ytdlp, args.join(" "));
@@ -921,18 +1724,65 @@ pub async fn run_download(
 
     let stdout = child.stdout.take()
         .ok_or_else(|| AppError::Download("Failed to capture yt-dlp stdout".to_string()))?;
+    let stderr = child.stderr.take()
+        .ok_or_else(|| AppError::Download("Failed to capture yt-dlp stderr".to_string()))?;
     let id = download_id.clone();
 
+    // Full yt-dlp output is kept per download for debugging failures
+    let log = std::sync::Arc::new(DownloadLog::create(log_file.as_deref(), ytdlp, &args));
+    let log_for_stdout = log.clone();
+
+    // Read stderr so the pipe never fills up; its ERROR lines explain failures
+    let error_lines = std::sync::Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
+    let error_lines_clone = error_lines.clone();
+    let log_for_stderr = log.clone();
+    let stderr_handle = tokio::spawn(async move {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            log_for_stderr.write_line(&line);
+            if line.starts_with("ERROR:") {
+                error_lines_clone.lock().await.push(line);
+            }
+        }
+    });
+
     // Capture output file path from stdout
     let output_path = std::sync::Arc::new(tokio::sync::Mutex::new(String::new()));
     let output_path_clone = output_path.clone();
 
+    // The merge yt-dlp settled on; a download that would need re-encoding
+    // is stopped here when the merge policy says so
+    let merge_plan = std::sync::Arc::new(tokio::sync::Mutex::new(None::<crate::remux::MergePlan>));
+    let merge_plan_clone = merge_plan.clone();
+    let (abort_tx, mut abort_rx) = tokio::sync::oneshot::channel::<String>();
+    let mut abort_tx = Some(abort_tx);
+    let convert_tx = progress_tx.clone();
+    let convert_cancel_rx = cancel_rx.clone();
+
     // Read stdout for progress
     let progress_handle = tokio::spawn(async move {
         use tokio::io::{AsyncBufReadExt, BufReader};
         let reader = BufReader::new(stdout);
         let mut lines = reader.lines();
         while let Ok(Some(line)) = lines.next_line().await {
+            // Progress lines would drown out everything else
+            if !line.starts_with("[download]") || !line.contains('%') {
+                log_for_stdout.write_line(&line);
+            }
+            if let Some(plan) = crate::remux::MergePlan::parse(&line) {
+                if plan.needs_reencode() && crate::remux::current() == crate::remux::MergePolicy::Abort {
+                    if let Some(abort_tx) = abort_tx.take() {
+                        let _ = abort_tx.send(
+                            "The picked formats can't be merged into MP4 without re-encoding \
+                             (merge policy: abort)"
+                                .to_string(),
+                        );
+                    }
+                }
+                *merge_plan_clone.lock().await = Some(plan);
+                continue;
+            }
             if let Some(progress) = parse_ytdlp_progress(&line) {
                 let _ = progress_tx
                     .send(DownloadProgress {
@@ -940,6 +1790,7 @@ pub async fn run_download(
                         progress: progress.0,
                         speed: progress.1,
                         eta: progress.2,
+                        total: progress.3,
                         status: "downloading".to_string(),
                     })
                     .await;
@@ -952,20 +1803,43 @@ pub async fn run_download(
     });
 
     // Wait for completion or cancellation
-    tokio::select! {
+    let outcome = tokio::select! {
         result = child.wait() => {
             progress_handle.abort();
+            // ffmpeg children can hold stderr open after yt-dlp exits
+            let _ = tokio::time::timeout(std::time::Duration::from_secs(2), stderr_handle).await;
             match result {
                 Ok(status) if status.success() => {
                     let file_path = output_path.lock().await.clone();
                     if file_path.is_empty() {
                         Ok(download_id)
                     } else {
-                        Ok(file_path)
+                        match ensure_within(Path::new(output_dir), Path::new(&file_path)) {
+                            Err(e) => Err(AppError::Download(e)),
+                            Ok(_) => match merge_plan.lock().await.clone() {
+                                Some(plan) if handle_merge && plan.wants_mp4(crate::remux::current()) => {
+                                    crate::remux::to_mp4(
+                                        ffmpeg,
+                                        &file_path,
+                                        &plan,
+                                        &download_id,
+                                        &convert_tx,
+                                        convert_cancel_rx,
+                                    )
+                                    .await
+                                }
+                                _ => Ok(file_path),
+                            },
+                        }
                     }
                 }
                 Ok(status) => {
-                    Err(AppError::Download(format!("yt-dlp exited with code: {}", status)))
+                    let mut message = format!("yt-dlp exited with code: {}", status);
+                    if let Some(last) = error_lines.lock().await.last() {
+                        message.push('\n');
+                        message.push_str(last);
+                    }
+                    Err(AppError::Download(message))
                 }
                 Err(e) => Err(AppError::Download(format!("yt-dlp process error: {}", e))),
             }
@@ -974,6 +1848,66 @@ pub async fn run_download(
             let _ = child.kill().await;
             Err(AppError::Download("Download cancelled".to_string()))
         }
+        Ok(reason) = &mut abort_rx => {
+            let _ = child.kill().await;
+            Err(AppError::Download(reason))
+        }
+    };
+    match &outcome {
+        Ok(path) => log.write_line(&format!("# finished: {}", path)),
+        Err(e) => log.write_line(&format!("# failed: {}", e)),
+    }
+    outcome
+}
+
+/// Where the yt-dlp output of download `id` is kept.
+pub fn download_log_path(app_data: &Path, id: &str) -> PathBuf {
+    app_data
+        .join("logs")
+        .join("downloads")
+        .join(format!("{}.log", sanitize_path_component(id)))
+}
+
+/// A download's log file. Writing is best-effort: a log that can't be
+/// created or written never fails the download.
+struct DownloadLog(Option<std::sync::Mutex<std::fs::File>>);
+
+impl DownloadLog {
+    fn create(path: Option<&Path>, ytdlp: &str, args: &[String]) -> Self {
+        let file = path.and_then(|path| {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            // A retry starts a fresh log
+            std::fs::File::create(path)
+                .map_err(|e| log::warn!("[run_download] Failed to create {}: {}", path.display(), e))
+                .ok()
+        });
+        let log = Self(file.map(std::sync::Mutex::new));
+        log.write_line(&format!("# {}", chrono::Local::now().to_rfc3339()));
+        // Credentials passed through the custom flags stay out of the file
+        let mut shown = Vec::with_capacity(args.len());
+        let mut redact_next = false;
+        for arg in args {
+            if redact_next {
+                shown.push("<redacted>");
+            } else {
+                shown.push(arg.as_str());
+            }
+            redact_next = matches!(
+                arg.as_str(),
+                "-u" | "--username" | "-p" | "--password" | "--video-password" | "--ap-username" | "--ap-password"
+            );
+        }
+        log.write_line(&format!("$ {} {}", ytdlp, shown.join(" ")));
+        log
+    }
+
+    fn write_line(&self, line: &str) {
+        use std::io::Write;
+        if let Some(Ok(mut file)) = self.0.as_ref().map(|f| f.lock()) {
+            let _ = writeln!(file, "{}", line);
+        }
     }
 }
 
@@ -1028,12 +1962,14 @@ fn extract_output_file_path_from_line(line: &str) -> Option<String> {
 }
 
 /// Parse yt-dlp progress line like "[download]  50.0% of ~100MiB at 5.00MiB/s ETA 00:10"
-fn parse_ytdlp_progress(line: &str) -> Option<(f64, String, String)> {
+/// into (percent, speed, eta, total size).
+fn parse_ytdlp_progress(line: &str) -> Option<(f64, String, String, String)> {
     use std::sync::OnceLock;
 
     static RE_PROGRESS: OnceLock<regex::Regex> = OnceLock::new();
     static RE_SPEED: OnceLock<regex::Regex> = OnceLock::new();
     static RE_ETA: OnceLock<regex::Regex> = OnceLock::new();
+    static RE_TOTAL: OnceLock<regex::Regex> = OnceLock::new();
 
     if !line.contains("[download]") || !line.contains('%') {
         return None;
@@ -1042,6 +1978,7 @@ fn parse_ytdlp_progress(line: &str) -> Option<(f64, String, String)> {
     let re_progress = RE_PROGRESS.get_or_init(|| regex::Regex::new(r"(\d+\.?\d*)%").unwrap());
     let re_speed = RE_SPEED.get_or_init(|| regex::Regex::new(r"at\s+(\S+)").unwrap());
     let re_eta = RE_ETA.get_or_init(|| regex::Regex::new(r"ETA\s+(\S+)").unwrap());
+    let re_total = RE_TOTAL.get_or_init(|| regex::Regex::new(r"of\s+(~?\s*[\d.]+\w+)").unwrap());
 
     let progress = {
         let cap = re_progress.captures(line)?;
@@ -1060,5 +1997,11 @@ fn parse_ytdlp_progress(line: &str) -> Option<(f64, String, String)> {
         .map(|m| m.as_str().to_string())
         .unwrap_or_default();
 
-    Some((progress, speed, eta))
+    let total = re_total
+        .captures(line)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().replace(' ', ""))
+        .unwrap_or_default();
+
+    Some((progress, speed, eta, total))
 }
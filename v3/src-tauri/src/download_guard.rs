@@ -0,0 +1,394 @@
+//! The download guard: conditions for running downloads.
+//!
+//! A background check holds new and queued downloads as paused, and
+//! pauses running ones, while one of these applies:
+//!
+//! - `download_wifi_only` is on and the connection is metered (mobile
+//!   data, a hotspot, a connection marked metered by the user). Detection
+//!   uses ConnectivityManager on Android, NetworkManager (`nmcli`) on Linux
+//!   and the connection cost from WinRT on Windows. Elsewhere, or when the
+//!   check fails, the connection counts as unmetered.
+//! - The battery is below the thresholds from [`crate::battery`].
+//! - It is inside the window from [`crate::quiet_hours`], which only
+//!   pauses running downloads when set to.
+//! - The data quota from [`crate::quota`] is used up, which never pauses
+//!   running downloads.
+//!
+//! When the conditions are met again the downloads paused here are
+//! resumed. Downloads paused by the user are left alone. The check runs
+//! every [`CHECK_INTERVAL`] while something downloads and every
+//! [`IDLE_CHECK_INTERVAL`] otherwise.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::db::Database;
+use crate::download::DownloadManager;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(20);
+/// Between checks while nothing is downloading. Downloads started in
+/// between are judged by the last result until the next check.
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(120);
+
+struct Guard {
+    wifi_only: bool,
+    /// Last detection result, `None` when unknown.
+    metered: Option<bool>,
+    battery_low: bool,
+    quiet: bool,
+    over_quota: bool,
+    /// Downloads started over the quota with `force_start_download`.
+    quota_exempt: Vec<String>,
+    /// Downloads paused by the guard, resumed when it lifts.
+    held: Vec<String>,
+}
+
+impl Guard {
+    fn reason(&self) -> Option<&'static str> {
+        self.reason_for(None)
+    }
+
+    /// Like [`Guard::reason`], leaving out the quota for an exempt download.
+    fn reason_for(&self, id: Option<&str>) -> Option<&'static str> {
+        if self.wifi_only && self.metered == Some(true) {
+            Some("Downloads are set to Wi-Fi only and this connection is metered")
+        } else if self.battery_low {
+            Some("Downloads are paused while the battery is low")
+        } else if self.quiet {
+            Some("Downloads wait for quiet hours to end")
+        } else if self.over_quota && !id.is_some_and(|id| self.quota_exempt.iter().any(|e| e == id)) {
+            Some("The download quota for this period is used up")
+        } else {
+            None
+        }
+    }
+
+    /// Whether running downloads are paused too. Quiet hours alone only
+    /// hold new ones unless `quiet_hours_pause_active` is on.
+    fn pauses_active(&self) -> bool {
+        (self.wifi_only && self.metered == Some(true))
+            || self.battery_low
+            || (self.quiet && crate::quiet_hours::pauses_active())
+    }
+}
+
+static GUARD: Mutex<Guard> = Mutex::new(Guard {
+    wifi_only: false,
+    metered: None,
+    battery_low: false,
+    quiet: false,
+    over_quota: false,
+    quota_exempt: Vec::new(),
+    held: Vec::new(),
+});
+
+/// Reload `download_wifi_only`. Called at startup and whenever it changes.
+pub fn load_from_db(db: &Database) {
+    let wifi_only = db.get_setting("download_wifi_only").unwrap_or(None).as_deref() == Some("true");
+    if let Ok(mut guard) = GUARD.lock() {
+        guard.wifi_only = wifi_only;
+    }
+}
+
+/// True while downloads should wait for an unmetered connection, a
+/// charged battery, the end of quiet hours or a new quota period.
+pub fn downloads_held() -> bool {
+    held_reason().is_some()
+}
+
+/// Like [`downloads_held`] for download `id`, which may be exempt from
+/// the quota.
+pub fn download_held(id: &str) -> bool {
+    GUARD.lock().map(|g| g.reason_for(Some(id)).is_some()).unwrap_or(false)
+}
+
+/// Hold new downloads right away once the quota is used up, rather than
+/// at the next check.
+pub fn set_over_quota() {
+    if let Ok(mut guard) = GUARD.lock() {
+        guard.over_quota = true;
+    }
+}
+
+/// Let `id` start while the quota is used up.
+pub fn exempt_from_quota(id: &str) {
+    if let Ok(mut guard) = GUARD.lock() {
+        if !guard.quota_exempt.iter().any(|e| e == id) {
+            guard.quota_exempt.push(id.to_string());
+        }
+    }
+}
+
+/// Why downloads are held, for errors shown to the user.
+pub fn held_reason() -> Option<&'static str> {
+    GUARD.lock().ok()?.reason()
+}
+
+pub fn battery_low() -> bool {
+    GUARD.lock().map(|g| g.battery_low).unwrap_or(false)
+}
+
+/// Hold scheduled work (feed checks, playlist syncs) until the battery
+/// is charging or recharged.
+pub async fn wait_for_battery() {
+    let mut logged = false;
+    while battery_low() {
+        if !logged {
+            log::info!("[guard] Battery low, holding scheduled checks");
+            logged = true;
+        }
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
+
+/// Remember a download paused because of the network so it's resumed later.
+pub fn hold(id: &str) {
+    if let Ok(mut guard) = GUARD.lock() {
+        if !guard.held.iter().any(|h| h == id) {
+            guard.held.push(id.to_string());
+        }
+    }
+}
+
+/// Whether the active connection is metered, `None` if unknown.
+pub async fn is_metered() -> Option<bool> {
+    #[cfg(target_os = "android")]
+    {
+        match crate::android_bridge::get_network_state() {
+            Ok(state) => match state.as_str() {
+                "metered" => Some(true),
+                "unmetered" => Some(false),
+                _ => None,
+            },
+            Err(e) => {
+                log::debug!("[network] Could not read network state: {}", e);
+                None
+            }
+        }
+    }
+    #[cfg(target_os = "linux")]
+    {
+        // "yes", "yes (guessed)", "no", "no (guessed)" or "unknown"
+        let output = crate::download::create_hidden_command("nmcli")
+            .args(["-t", "-f", "METERED", "general"])
+            .output()
+            .await
+            .ok()?;
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_lowercase();
+        if value.starts_with("yes") {
+            Some(true)
+        } else if value.starts_with("no") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        // NetworkCostType: Unrestricted, Fixed, Variable or Unknown
+        let script = "$p = [Windows.Networking.Connectivity.NetworkInformation,Windows.Networking.Connectivity,ContentType=WindowsRuntime]::GetInternetConnectionProfile(); \
+                      if ($p) { $p.GetConnectionCost().NetworkCostType }";
+        let output = crate::download::create_hidden_command("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", script])
+            .output()
+            .await
+            .ok()?;
+        match String::from_utf8_lossy(&output.stdout).trim() {
+            "Unrestricted" => Some(false),
+            "Fixed" | "Variable" => Some(true),
+            _ => None,
+        }
+    }
+    #[cfg(not(any(target_os = "android", target_os = "linux", target_os = "windows")))]
+    {
+        None
+    }
+}
+
+/// Start the background check. Call once after the managed state is set up.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            check(&app).await;
+            let checked = std::time::Instant::now();
+            loop {
+                tokio::time::sleep(CHECK_INTERVAL).await;
+                if downloading(&app).await || checked.elapsed() >= IDLE_CHECK_INTERVAL {
+                    break;
+                }
+            }
+        }
+    });
+}
+
+async fn downloading(app: &AppHandle) -> bool {
+    let dl = app.state::<Arc<tokio::sync::Mutex<DownloadManager>>>().inner().clone();
+    let dm = dl.lock().await;
+    !dm.active.is_empty()
+}
+
+async fn check(app: &AppHandle) {
+    let (wifi_only, was_battery_low) = match GUARD.lock() {
+        Ok(g) => (g.wifi_only, g.battery_low),
+        Err(_) => return,
+    };
+    let metered = if wifi_only {
+        is_metered().await
+    } else {
+        None
+    };
+    let battery = if crate::battery::enabled() {
+        crate::battery::status().await
+    } else {
+        None
+    };
+    let battery_low = crate::battery::is_low(battery, was_battery_low);
+    let quiet = crate::quiet_hours::is_quiet_now();
+    let over_quota = {
+        let db = app.state::<Arc<Mutex<Database>>>();
+        let Ok(db_lock) = db.lock() else { return };
+        crate::quota::exceeded(&db_lock)
+    };
+
+    let (was_held, was_pausing, now_held, now_pausing, to_resume, reason) = {
+        let Ok(mut guard) = GUARD.lock() else { return };
+        let was_held = guard.reason().is_some();
+        let was_pausing = guard.pauses_active();
+        guard.metered = metered;
+        guard.battery_low = battery_low;
+        guard.quiet = quiet;
+        guard.over_quota = over_quota;
+        if !over_quota {
+            guard.quota_exempt.clear();
+        }
+        let reason = guard.reason();
+        let now_held = reason.is_some();
+        let to_resume = if now_held { Vec::new() } else { std::mem::take(&mut guard.held) };
+        (was_held, was_pausing, now_held, guard.pauses_active(), to_resume, reason)
+    };
+
+    let metered = metered == Some(true);
+    if now_pausing && !was_pausing {
+        let paused = pause_active(app).await;
+        log::info!(
+            "[guard] {}, paused {} download(s)",
+            if battery_low {
+                "Battery low"
+            } else if metered {
+                "Metered connection"
+            } else {
+                "Quiet hours"
+            },
+            paused
+        );
+        let _ = app.emit(
+            "network-changed",
+            serde_json::json!({
+                "metered": metered,
+                "batteryLow": battery_low,
+                "quietHours": quiet,
+                "overQuota": over_quota,
+                "paused": paused,
+            }),
+        );
+    } else if now_held && !was_held {
+        log::info!("[guard] {}, holding new downloads", reason.unwrap_or_default());
+        let _ = app.emit(
+            "network-changed",
+            serde_json::json!({
+                "metered": metered,
+                "batteryLow": battery_low,
+                "quietHours": quiet,
+                "overQuota": over_quota,
+                "paused": 0,
+            }),
+        );
+    } else if !now_held && (was_held || !to_resume.is_empty()) {
+        let resumed = resume_held(app, to_resume).await;
+        log::info!("[guard] Conditions met again, resumed {} download(s)", resumed);
+        let _ = app.emit(
+            "network-changed",
+            serde_json::json!({
+                "metered": metered,
+                "batteryLow": battery_low,
+                "quietHours": quiet,
+                "overQuota": over_quota,
+                "resumed": resumed,
+            }),
+        );
+    }
+}
+
+async fn pause_active(app: &AppHandle) -> usize {
+    let db = app.state::<Arc<Mutex<Database>>>().inner().clone();
+    let dl = app.state::<Arc<tokio::sync::Mutex<DownloadManager>>>().inner().clone();
+    let ids: Vec<String> = {
+        let Ok(db_lock) = db.lock() else { return 0 };
+        db_lock
+            .get_downloads()
+            .unwrap_or_default()
+            .iter()
+            .filter(|d| matches!(d["status"].as_str(), Some("downloading" | "queued" | "merging")))
+            .filter_map(|d| d["id"].as_str().map(String::from))
+            .collect()
+    };
+
+    for id in &ids {
+        {
+            let dm = dl.lock().await;
+            // Termux downloads on Android have no token and keep going
+            if let Some(active) = dm.active.get(id) {
+                let _ = active.cancel_token.send(true);
+            } else if cfg!(target_os = "android") {
+                continue;
+            }
+        }
+        if let Ok(db_lock) = db.lock() {
+            let _ = db_lock.update_download_status(id, "paused");
+        }
+        hold(id);
+    }
+    GUARD.lock().map(|g| g.held.len()).unwrap_or(0)
+}
+
+async fn resume_held(app: &AppHandle, ids: Vec<String>) -> usize {
+    let db = app.state::<Arc<Mutex<Database>>>().inner().clone();
+    let dl = app.state::<Arc<tokio::sync::Mutex<DownloadManager>>>().inner().clone();
+    let mut resumed = 0;
+    for id in ids {
+        let entry = {
+            let Ok(db_lock) = db.lock() else { break };
+            db_lock.get_download(&id).ok().flatten()
+        };
+        // Skip downloads the user cancelled, deleted or already resumed
+        let Some(entry) = entry.filter(|e| e["status"].as_str() == Some("paused")) else {
+            continue;
+        };
+        let url = entry["url"].as_str().unwrap_or_default().to_string();
+        let format_id = entry["formatId"].as_str().filter(|s| !s.is_empty()).map(String::from);
+        match crate::commands::start_download_existing(app.clone(), db.clone(), dl.clone(), id.clone(), url, format_id).await {
+            Ok(()) => resumed += 1,
+            Err(e) => log::warn!("[guard] Failed to resume {}: {}", id, e),
+        }
+    }
+    resumed
+}
+
+/// Connection state for the settings page and the downloads list.
+#[tauri::command]
+pub async fn get_network_status() -> Result<serde_json::Value, String> {
+    let wifi_only = GUARD.lock().map_err(|e| e.to_string())?.wifi_only;
+    let metered = is_metered().await;
+    let guard = GUARD.lock().map_err(|e| e.to_string())?;
+    Ok(serde_json::json!({
+        "wifiOnly": wifi_only,
+        "metered": metered,
+        "held": guard.reason().is_some(),
+        "batteryLow": guard.battery_low,
+        "quietHours": guard.quiet,
+        "overQuota": guard.over_quota,
+        "heldDownloads": guard.held.len(),
+    }))
+}
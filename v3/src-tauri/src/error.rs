@@ -36,12 +36,119 @@ pub enum AppError {
     Other(String),
 }
 
+/// Machine-readable error category the frontend keys remediation hints
+/// off. Most failures reach us as yt-dlp/OS message text, so the category
+/// is recognized from the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorCode {
+    Network,
+    GeoBlocked,
+    AgeRestricted,
+    LoginRequired,
+    RateLimited,
+    DiskFull,
+    PermissionDenied,
+    FormatUnavailable,
+    VideoUnavailable,
+    ToolMissing,
+    Cancelled,
+    NotFound,
+    InvalidArgument,
+    Database,
+    Unknown,
+}
+
+impl ErrorCode {
+    pub fn classify(message: &str) -> Self {
+        let m = message.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| m.contains(n));
+
+        if has(&["download cancelled"]) {
+            Self::Cancelled
+        } else if has(&["no space left", "disk full", "not enough space", "os error 28", "os error 112"]) {
+            Self::DiskFull
+        } else if has(&["not available in your country", "geo restrict", "geo-restrict", "blocked it in your country", "not available from your location"]) {
+            Self::GeoBlocked
+        } else if has(&["confirm your age", "age-restricted", "age restricted", "inappropriate for some users"]) {
+            Self::AgeRestricted
+        } else if has(&["not a bot", "login required", "sign in", "members-only", "join this channel", "private video", "use --cookies"]) {
+            Self::LoginRequired
+        } else if has(&["http error 429", "too many requests"]) {
+            Self::RateLimited
+        } else if has(&["requested format is not available", "format is not available", "no video formats found"]) {
+            Self::FormatUnavailable
+        } else if has(&["video unavailable", "has been removed", "this video is not available", "this live event will begin", "premieres in"]) {
+            Self::VideoUnavailable
+        } else if has(&["permission denied", "os error 13", "access is denied"]) {
+            Self::PermissionDenied
+        } else if has(&["failed to spawn yt-dlp", "yt-dlp not found", "ffmpeg not found", "os error 2)", "no such file or directory (os error 2"]) {
+            Self::ToolMissing
+        } else if has(&[
+            "unable to download webpage",
+            "unable to download json",
+            "timed out",
+            "connection",
+            "name or service not known",
+            "temporary failure in name resolution",
+            "getaddrinfo",
+            "network is unreachable",
+            "ssl",
+            "failed to resolve",
+        ]) {
+            Self::Network
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Short remediation hint for the UI.
+    pub fn hint(self) -> Option<&'static str> {
+        match self {
+            Self::Network => Some("Check your internet connection or proxy settings and try again."),
+            Self::GeoBlocked => Some("This video is blocked in your region. Try a proxy or VPN in another country."),
+            Self::AgeRestricted => Some("This video is age-restricted. Pick a browser to read cookies from in Settings, signed in to an adult account."),
+            Self::LoginRequired => Some("The site wants you to sign in. Pick a browser to read cookies from in Settings."),
+            Self::RateLimited => Some("The site is rate-limiting requests. Wait a while before retrying or lower concurrent downloads."),
+            Self::DiskFull => Some("The download folder's disk is full. Free up space or choose another folder."),
+            Self::PermissionDenied => Some("The app can't write to or run this location. Check folder and tool permissions."),
+            Self::FormatUnavailable => Some("The selected format isn't offered for this video. Pick another quality or format."),
+            Self::VideoUnavailable => Some("The video is private, removed or not live yet."),
+            Self::ToolMissing => Some("yt-dlp or FFmpeg could not be started. Reinstall it from Settings."),
+            Self::Cancelled
+            | Self::NotFound
+            | Self::InvalidArgument
+            | Self::Database
+            | Self::Unknown => None,
+        }
+    }
+}
+
+impl AppError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::Database(_) => ErrorCode::Database,
+            AppError::Io(e) if e.kind() == std::io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::InvalidArgument(_) => ErrorCode::InvalidArgument,
+            other => ErrorCode::classify(&other.to_string()),
+        }
+    }
+}
+
+/// Serialized as `{ code, message, hint }` for the frontend.
 impl serde::Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        use serde::ser::SerializeStruct;
+        let code = self.code();
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", &code)?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("hint", &code.hint())?;
+        state.end()
     }
 }
 
@@ -51,4 +158,10 @@ impl From<String> for AppError {
     }
 }
 
+impl<T> From<std::sync::PoisonError<T>> for AppError {
+    fn from(e: std::sync::PoisonError<T>) -> Self {
+        AppError::Other(e.to_string())
+    }
+}
+
 pub type AppResult<T> = Result<T, AppError>;
@@ -0,0 +1,83 @@
+//! User-configured `--extractor-args` for every yt-dlp run.
+//!
+//! When a YouTube change breaks format extraction, the usual workaround is
+//! an extractor argument such as `youtube:player_client=ios`. The
+//! `ytdlp_extractor_args` setting holds such entries, separated by spaces
+//! or new lines, each `EXTRACTOR:KEY=VALUE[;KEY=VALUE...]`. They are
+//! validated when saved and passed to downloads, info and playlist
+//! fetches, feed checks and transcription alike, see [`ytdlp_args`].
+
+use std::sync::RwLock;
+
+use crate::db::Database;
+
+static ENTRIES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Split a setting value into its entries.
+pub fn parse(value: &str) -> Vec<String> {
+    value.split_whitespace().map(str::to_string).collect()
+}
+
+/// Check a `ytdlp_extractor_args` setting value.
+pub fn validate(value: &str) -> Result<(), String> {
+    for entry in parse(value) {
+        let (extractor, args) = entry
+            .split_once(':')
+            .ok_or_else(|| format!("Extractor argument '{}' must look like extractor:key=value", entry))?;
+        if extractor.is_empty() || !extractor.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("'{}' is not an extractor name", extractor));
+        }
+        for arg in args.split(';') {
+            let (key, value) = arg
+                .split_once('=')
+                .ok_or_else(|| format!("Extractor argument '{}' is missing '=value'", arg))?;
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("'{}' is not an extractor argument name", key));
+            }
+            if value.chars().any(|c| c.is_control()) {
+                return Err(format!("Extractor argument '{}' has an invalid value", key));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reload the entries from the `ytdlp_extractor_args` setting. Called at
+/// startup and whenever the setting changes. An invalid stored value is
+/// ignored rather than passed on.
+pub fn load_from_db(db: &Database) {
+    let value = db.get_setting("ytdlp_extractor_args").unwrap_or(None).unwrap_or_default();
+    let entries = match validate(&value) {
+        Ok(()) => parse(&value),
+        Err(e) => {
+            log::warn!("[extractor_args] Ignoring ytdlp_extractor_args: {}", e);
+            Vec::new()
+        }
+    };
+    if !entries.is_empty() {
+        log::info!("[extractor_args] Using {}", entries.join(" "));
+    }
+    if let Ok(mut current) = ENTRIES.write() {
+        *current = entries;
+    }
+}
+
+/// `--extractor-args` flags for a yt-dlp command line.
+pub fn ytdlp_args() -> Vec<String> {
+    let Ok(entries) = ENTRIES.read() else { return Vec::new() };
+    entries
+        .iter()
+        .flat_map(|entry| ["--extractor-args".to_string(), entry.clone()])
+        .collect()
+}
+
+/// Whether the configured entries set extractor argument `key`, e.g.
+/// `player_client`.
+pub fn sets(key: &str) -> bool {
+    let Ok(entries) = ENTRIES.read() else { return false };
+    entries.iter().any(|entry| {
+        entry
+            .split_once(':')
+            .is_some_and(|(_, args)| args.split(';').any(|arg| arg.split('=').next() == Some(key)))
+    })
+}
@@ -1,13 +1,52 @@
+pub mod archive_bundle;
+pub mod autostart;
+pub mod battery;
+pub mod cast;
+pub mod chapter_tracks;
+pub mod chromecast;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub mod cli;
 pub mod commands;
+pub mod cookie_profiles;
 pub mod db;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub mod deep_link;
 pub mod download;
+pub mod download_guard;
 pub mod error;
+pub mod extractor_args;
+pub mod library_scan;
+pub mod live_schedule;
+pub mod logger;
+pub mod media_server;
+pub mod mirror_commands;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub mod native_host;
+pub mod notifications;
 pub mod playlist_commands;
+pub mod playlist_scheduler;
+pub mod podcast;
+pub mod proxy;
+pub mod queue_summary;
+pub mod quiet_hours;
+pub mod quota;
+pub mod rate_limit;
+pub mod reconcile;
+pub mod remote_api;
+pub mod remux;
 pub mod rss;
 pub mod rss_scheduler;
+pub mod secrets;
 pub mod settings;
+pub mod speed_history;
+pub mod stream_proxy;
+pub mod subscription_import;
+pub mod throttle;
 pub mod transcription_commands;
+pub mod transcription_provider;
 pub mod tool_install_commands;
+pub mod watch_folder;
+pub mod webhooks;
 pub mod android_commands;
 #[cfg(target_os = "android")]
 pub mod android_bridge;
@@ -17,24 +56,20 @@ use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // Initialize logging backend.
-    // On Android: android_logger sends log::* output to logcat.
-    // On desktop: env_logger sends log::* output to stderr.
-    #[cfg(target_os = "android")]
-    {
-        android_logger::init_once(
-            android_logger::Config::default()
-                .with_max_level(log::LevelFilter::Debug)
-                .with_tag("YTDL-Rust"),
-        );
-        log::info!("[YTDL] Android logger initialized — Rust logs now visible in logcat");
-    }
-    #[cfg(not(target_os = "android"))]
-    {
-        let _ = env_logger::try_init();
-    }
-
-    let builder = tauri::Builder::default()
+    // Console (stderr / logcat), in-app buffer and, once the app data dir
+    // is known, a daily log file.
+    logger::init();
+
+    let builder = tauri::Builder::default();
+
+    // Must be the first plugin: a second launch hands its arguments to the
+    // running instance and exits before anything else (DB included) opens
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+        deep_link::handle_second_instance(app, argv);
+    }));
+
+    let builder = builder
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -44,7 +79,10 @@ pub fn run() {
         .plugin(tauri_plugin_store::Builder::default().build());
 
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
-    let builder = builder.plugin(tauri_plugin_updater::Builder::new().build());
+    let builder = builder
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(autostart::plugin());
 
     #[cfg(any(target_os = "android", target_os = "ios"))]
     let builder = builder;
@@ -90,6 +128,21 @@ pub fn run() {
                 e
             })?;
             log::info!("[YTDL] Database ready");
+            let retention = database
+                .get_setting("log_retention_days")
+                .unwrap_or(None)
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(logger::DEFAULT_RETENTION_DAYS);
+            logger::set_log_dir(&app_data.join("logs"), retention);
+            secrets::migrate_plaintext_secrets(&database);
+            proxy::load_from_db(&database);
+            extractor_args::load_from_db(&database);
+            remux::load_from_db(&database);
+            rate_limit::load_from_db(&database);
+            download_guard::load_from_db(&database);
+            battery::load_from_db(&database);
+            quiet_hours::load_from_db(&database);
+            quota::load_from_db(&database);
 
             #[cfg(any(target_os = "android", target_os = "ios"))]
             {
@@ -128,6 +181,8 @@ pub fn run() {
                 }
             }
 
+            reconcile::run(app, &database);
+            autostart::init(app, &database);
             app.manage(std::sync::Arc::new(std::sync::Mutex::new(database)));
 
             // Initialize download manager
@@ -138,6 +193,10 @@ pub fn run() {
             let rss_scheduler = rss_scheduler::RssScheduler::new();
             app.manage(std::sync::Arc::new(tokio::sync::Mutex::new(rss_scheduler)));
 
+            // Initialize playlist auto-sync scheduler
+            let playlist_scheduler = playlist_scheduler::PlaylistScheduler::new();
+            app.manage(std::sync::Arc::new(tokio::sync::Mutex::new(playlist_scheduler)));
+
             // Initialize active transcription cancellation tokens
             let transcription_jobs: std::sync::Arc<
                 tokio::sync::Mutex<
@@ -146,6 +205,12 @@ pub fn run() {
             > = std::sync::Arc::new(tokio::sync::Mutex::new(HashMap::new()));
             app.manage(transcription_jobs);
 
+            // Local proxy for the built-in player (listener starts on first use)
+            app.manage(stream_proxy::StreamProxy::new());
+            app.manage(stream_proxy::StreamInfoCache::new());
+            app.manage(cast::CastManager::new());
+            app.manage(remote_api::ApiServer::new());
+
             // Start RSS scheduler in background
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
@@ -154,30 +219,103 @@ pub fn run() {
                 scheduler.start(app_handle.clone()).await;
             });
 
+            // Start playlist sync scheduler in background
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let scheduler = app_handle.state::<std::sync::Arc<tokio::sync::Mutex<playlist_scheduler::PlaylistScheduler>>>();
+                let scheduler = scheduler.lock().await;
+                scheduler.start(app_handle.clone()).await;
+            });
+
+            // Pause downloads on metered connections or low battery
+            download_guard::start(app.handle().clone());
+
+            // Pick up downloads left queued by the last session
+            reconcile::resume_queue(app.handle().clone());
+
+            // Overall progress of the download queue
+            queue_summary::start(app.handle().clone());
+
+            // Check for a yt-dlp update once the app has settled
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                    tool_install_commands::notify_tool_updates(app_handle).await;
+                });
+            }
+
+            remote_api::init(app);
+
+            // Podcast feeds shared on the LAN
+            podcast::init(app);
+
+            // Link files dropped into the watch folder
+            #[cfg(not(target_os = "android"))]
+            watch_folder::init(app);
+
+            // Premieres and livestreams: "starting soon" and auto-record
+            live_schedule::init(app);
+
+            // Buttons on the Android download notification
+            #[cfg(target_os = "android")]
+            android_commands::init_notification_actions(app);
+
+            // ytdl:// links (needs the managed state above)
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            deep_link::init(app);
+
             log::info!("YTDL v{} started", env!("CARGO_PKG_VERSION"));
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::get_video_info,
             playlist_commands::get_playlist_info,
+            playlist_commands::get_account_playlist,
             commands::start_download,
             playlist_commands::start_playlist_download,
+            playlist_commands::get_playlists,
+            playlist_commands::update_playlist_settings,
+            playlist_commands::get_playlist_items,
+            playlist_commands::retry_playlist_item,
+            playlist_commands::sync_playlist,
+            playlist_commands::export_playlist_m3u,
+            playlist_commands::delete_playlist,
+            // Channel mirrors
+            mirror_commands::add_channel_mirror,
+            mirror_commands::get_channel_mirrors,
+            mirror_commands::update_channel_mirror,
+            mirror_commands::sync_channel_mirror,
+            // Playlist Scheduler
+            playlist_commands::set_playlist_sync_interval,
+            playlist_commands::get_playlist_sync_interval,
+            playlist_commands::sync_auto_playlists,
             commands::pause_download,
             commands::resume_download,
             commands::cancel_download,
             commands::retry_download,
             commands::delete_download,
+            commands::get_download_log,
+            archive_bundle::get_download_artifacts,
+            speed_history::get_speed_history,
+            queue_summary::get_queue_summary,
             commands::get_downloads,
             commands::get_settings,
             commands::save_setting,
+            commands::get_app_settings,
+            commands::update_app_settings,
             commands::select_directory,
             commands::get_feeds,
+            commands::get_feed_items,
             commands::add_feed,
             commands::remove_feed,
             commands::check_feed,
             transcription_commands::start_transcription,
+            transcription_commands::transcribe_download,
             transcription_commands::get_transcripts,
             transcription_commands::delete_transcript,
+            transcription_commands::search_transcripts,
             transcription_commands::check_openai_transcription_api,
             transcription_commands::install_local_transcription,
             tool_install_commands::check_ytdlp,
@@ -186,6 +324,40 @@ pub fn run() {
             tool_install_commands::install_ffmpeg,
             tool_install_commands::get_ytdlp_version,
             tool_install_commands::get_ytdlp_latest_version,
+            tool_install_commands::set_binary_override,
+            tool_install_commands::test_binary_override,
+            tool_install_commands::check_environment,
+            proxy::get_proxy_status,
+            // Local HTTP API
+            remote_api::get_api_status,
+            remote_api::set_api_enabled,
+            remote_api::regenerate_api_token,
+            // Browser extension
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            native_host::install_native_host,
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            native_host::uninstall_native_host,
+            reconcile::get_startup_report,
+            // Logs
+            logger::get_recent_logs,
+            logger::open_log_file,
+            // Completion webhooks
+            webhooks::set_download_webhook,
+            webhooks::get_webhook_deliveries,
+            webhooks::test_webhook,
+            media_server::test_media_server,
+            media_server::refresh_media_library,
+            // yt-dlp throttling
+            rate_limit::get_rate_limit_status,
+            rate_limit::clear_rate_limit_cooldown,
+            download_guard::get_network_status,
+            battery::get_battery_status,
+            quota::get_quota_status,
+            quota::force_start_download,
+            live_schedule::get_upcoming_streams,
+            cookie_profiles::get_cookie_profiles,
+            cookie_profiles::save_cookie_profile,
+            cookie_profiles::delete_cookie_profile,
             tool_install_commands::update_ytdlp,
             tool_install_commands::get_ffmpeg_version,
             tool_install_commands::check_ffmpeg_update,
@@ -195,6 +367,7 @@ pub fn run() {
             tool_install_commands::get_binary_info,
             commands::open_external,
             commands::open_path,
+            commands::reveal_in_folder,
             // RSS Scheduler
             commands::set_rss_check_interval,
             commands::get_rss_check_interval,
@@ -203,10 +376,42 @@ pub fn run() {
             commands::update_feed_settings,
             // Stream proxy
             commands::get_stream_url,
+            commands::refresh_stream_url,
+            commands::get_sponsor_segments,
+            // Casting
+            podcast::export_podcast_feed,
+            podcast::share_podcast_feed,
+            podcast::unshare_podcast_feed,
+            podcast::get_podcast_shares,
+            subscription_import::import_subscriptions,
+            subscription_import::export_subscriptions,
+            library_scan::scan_library,
+            library_scan::relink_downloads,
+            cast::discover_cast_devices,
+            cast::cast_media,
+            cast::cast_control,
+            cast::cast_seek,
+            cast::get_cast_status,
+            // Playback positions
+            commands::save_playback_position,
+            commands::get_playback_position,
+            commands::get_playback_positions,
+            commands::clear_playback_position,
+            // Listen queue
+            commands::get_listen_queue,
+            commands::add_to_listen_queue,
+            commands::queue_latest_feed_items,
+            commands::reorder_listen_queue,
+            commands::remove_from_listen_queue,
+            commands::clear_listen_queue,
             // Batch operations
             commands::pause_all_downloads,
             commands::resume_all_downloads,
             commands::cancel_all_downloads,
+            commands::pause_batch,
+            commands::cancel_batch,
+            commands::retry_batch,
+            commands::get_batch_progress,
             commands::set_download_priority,
             // Export
             commands::export_downloads,
@@ -217,6 +422,7 @@ pub fn run() {
             android_commands::launch_termux_setup,
             android_commands::termux_download,
             android_commands::request_storage_permission,
+            android_commands::pick_android_download_folder,
             tool_install_commands::probe_ytdlp,
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,377 @@
+//! Registering media that was downloaded outside the app.
+//!
+//! [`scan_library`] walks a folder for audio and video files and works out
+//! which video each one is: from the `.info.json` yt-dlp wrote next to it,
+//! then from the source URL `--embed-metadata` stores in the file (read with
+//! ffprobe), then from the `[id]` yt-dlp's default file name ends with.
+//! Matched files become completed downloads with source `library`, so they
+//! show up in the list and count as already downloaded when the same video
+//! is queued again. The scanned folder is stored as their output folder,
+//! which lets the app open, reveal and delete them like its own downloads.
+//!
+//! [`relink_downloads`] repairs the list after the files moved: paths under
+//! an old base folder are rewritten to a new one, and files that are still
+//! missing can be found by name in a folder that is scanned for them.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::db::Database;
+use crate::download::{self, MediaDetails};
+
+const MEDIA_EXTENSIONS: &[&str] = &[
+    "mp4", "mkv", "webm", "mov", "m4v", "avi", "m4a", "mp3", "opus", "ogg", "flac", "wav", "aac",
+];
+
+/// Deep enough for `Channel/Season/Video`, shallow enough to stay out of
+/// trouble when pointed at a home folder.
+const MAX_DEPTH: usize = 6;
+
+/// Cap on the unmatched paths sent back; the count is always complete.
+const MAX_UNMATCHED_LISTED: usize = 200;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryScanReport {
+    /// Media files looked at.
+    pub scanned: usize,
+    pub added: usize,
+    /// Files already in the list, or videos downloaded under another path.
+    pub already_known: usize,
+    pub unmatched: usize,
+    pub unmatched_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScanProgress {
+    scanned: usize,
+    added: usize,
+    current: String,
+}
+
+/// What a file was matched to.
+struct Match {
+    url: String,
+    title: String,
+    thumbnail: String,
+    details: Option<MediaDetails>,
+}
+
+fn is_media_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| MEDIA_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+/// Media files under `root`, skipping hidden folders and yt-dlp's leftovers.
+fn collect_media_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0usize)];
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else { continue };
+            if file_type.is_dir() {
+                if depth < MAX_DEPTH {
+                    pending.push((path, depth + 1));
+                }
+            } else if file_type.is_file() && is_media_file(&path) && !name.contains(".part") && !name.contains(".temp.") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// The id in yt-dlp's default `Title [id].ext` file name.
+fn video_id_from_file_name(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let id = stem.strip_suffix(']')?.rsplit_once('[')?.1;
+    let valid = id.len() == 11 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then(|| id.to_string())
+}
+
+/// The title part of a file name, without a trailing ` [id]`.
+fn title_from_file_name(path: &Path) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    match stem.rsplit_once(" [") {
+        Some((title, _)) if video_id_from_file_name(path).is_some() => title.to_string(),
+        _ => stem,
+    }
+}
+
+/// Source URL and title from the file's own tags. yt-dlp's
+/// `--embed-metadata` stores the page URL as `purl` (MP4) or `comment`.
+async fn embedded_metadata(ffprobe: &str, path: &Path) -> Option<(String, String)> {
+    let output = download::create_hidden_command(ffprobe)
+        .args(["-v", "quiet", "-print_format", "json", "-show_format"])
+        .arg(path)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    let tags = json["format"]["tags"].as_object()?;
+    let tag = |name: &str| {
+        tags.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .and_then(|(_, value)| value.as_str())
+            .map(|value| value.trim().to_string())
+    };
+    let url = ["purl", "comment", "description"]
+        .iter()
+        .filter_map(|name| tag(name))
+        .find(|value| value.starts_with("https://") || value.starts_with("http://"))?;
+    Some((url, tag("title").unwrap_or_default()))
+}
+
+async fn match_file(ffprobe: &str, path: &Path) -> Option<Match> {
+    let file_path = path.to_string_lossy();
+    if let Some(json) = download::read_info_json(&file_path) {
+        let url = json["webpage_url"]
+            .as_str()
+            .or_else(|| json["original_url"].as_str())
+            .map(str::to_string);
+        if let Some(url) = url {
+            return Some(Match {
+                url,
+                title: json["title"].as_str().unwrap_or_default().to_string(),
+                thumbnail: json["thumbnail"].as_str().unwrap_or_default().to_string(),
+                details: Some(MediaDetails::from_json(&json)),
+            });
+        }
+    }
+    if let Some((url, title)) = embedded_metadata(ffprobe, path).await {
+        return Some(Match { url, title, thumbnail: String::new(), details: None });
+    }
+    let id = video_id_from_file_name(path)?;
+    Some(Match {
+        url: format!("https://www.youtube.com/watch?v={}", id),
+        title: String::new(),
+        thumbnail: format!("https://i.ytimg.com/vi/{}/hqdefault.jpg", id),
+        details: None,
+    })
+}
+
+/// Walk `path` and register the media files in it as completed downloads.
+#[tauri::command]
+pub async fn scan_library(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    path: String,
+) -> Result<LibraryScanReport, String> {
+    let root = PathBuf::from(path.trim());
+    if !root.is_dir() {
+        return Err(format!("Not a folder: {}", root.display()));
+    }
+    let root_dir = root.to_string_lossy().to_string();
+    let files = tokio::task::spawn_blocking(move || collect_media_files(&root))
+        .await
+        .map_err(|e| e.to_string())?;
+    let ffprobe = download::get_ffprobe_path(&app);
+
+    let mut report = LibraryScanReport::default();
+    for file in files {
+        report.scanned += 1;
+        let file_path = file.to_string_lossy().to_string();
+        let _ = app.emit(
+            "library-scan-progress",
+            ScanProgress {
+                scanned: report.scanned,
+                added: report.added,
+                current: file_path.clone(),
+            },
+        );
+
+        let known = {
+            let db_lock = db.lock().map_err(|e| e.to_string())?;
+            db_lock.has_download_file(&file_path).map_err(|e| e.to_string())?
+        };
+        if known {
+            report.already_known += 1;
+            continue;
+        }
+        let Some(found) = match_file(&ffprobe, &file).await else {
+            report.unmatched += 1;
+            if report.unmatched_files.len() < MAX_UNMATCHED_LISTED {
+                report.unmatched_files.push(file_path);
+            }
+            continue;
+        };
+
+        let size = std::fs::metadata(&file).map(|m| m.len() as i64).unwrap_or(0);
+        let title = if found.title.is_empty() { title_from_file_name(&file) } else { found.title };
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        if db_lock
+            .download_exists_by_url(&found.url, "", "")
+            .map_err(|e| e.to_string())?
+            .is_some()
+        {
+            report.already_known += 1;
+            continue;
+        }
+        let id = uuid::Uuid::new_v4().to_string();
+        db_lock
+            .insert_download_with_source(&id, &found.url, &title, &found.thumbnail, "library")
+            .map_err(|e| e.to_string())?;
+        db_lock
+            .update_download_complete(&id, &file_path, size)
+            .map_err(|e| e.to_string())?;
+        db_lock
+            .set_download_output_dir(&id, &root_dir)
+            .map_err(|e| e.to_string())?;
+        if let Some(details) = &found.details {
+            if let Err(e) = db_lock.set_download_details(&id, details) {
+                log::warn!("[library] Failed to store metadata of {}: {}", file_path, e);
+            }
+        }
+        report.added += 1;
+    }
+
+    log::info!(
+        "[library] Scanned {} files: {} added, {} already known, {} unmatched",
+        report.scanned,
+        report.added,
+        report.already_known,
+        report.unmatched
+    );
+    Ok(report)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingFile {
+    pub id: String,
+    pub title: String,
+    pub file_path: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelinkReport {
+    /// Completed downloads with a file path.
+    pub checked: usize,
+    /// Files that were where the list said.
+    pub present: usize,
+    pub relinked: usize,
+    pub missing: Vec<MissingFile>,
+}
+
+/// The file among `candidates` that is most likely the one recorded with
+/// `size`: the only one, or the only one of that size.
+fn pick_candidate<'a>(candidates: &'a [PathBuf], size: i64) -> Option<&'a PathBuf> {
+    if let [only] = candidates {
+        return Some(only);
+    }
+    if size <= 0 {
+        return None;
+    }
+    let mut same_size = candidates
+        .iter()
+        .filter(|path| std::fs::metadata(path).is_ok_and(|m| m.len() as i64 == size));
+    match (same_size.next(), same_size.next()) {
+        (Some(path), None) => Some(path),
+        _ => None,
+    }
+}
+
+/// Fix the file paths of completed downloads whose files moved. Missing
+/// files under `old_base` are looked for at the same place under
+/// `new_base`; those still missing are looked up by file name among the
+/// media files in `scan_dir`. Whatever can't be found is listed as missing.
+#[tauri::command]
+pub async fn relink_downloads(
+    db: State<'_, Arc<Mutex<Database>>>,
+    old_base: Option<String>,
+    new_base: Option<String>,
+    scan_dir: Option<String>,
+) -> Result<RelinkReport, String> {
+    let non_empty = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let rebase = match (non_empty(old_base), non_empty(new_base)) {
+        (Some(old), Some(new)) => Some((PathBuf::from(old), PathBuf::from(new))),
+        (None, None) => None,
+        _ => return Err("Give both the old and the new folder".to_string()),
+    };
+    let scan_dir = non_empty(scan_dir).map(PathBuf::from);
+    if rebase.is_none() && scan_dir.is_none() {
+        return Err("Give the old and new folder, or a folder to search".to_string());
+    }
+
+    let mut by_name: HashMap<OsString, Vec<PathBuf>> = HashMap::new();
+    if let Some(dir) = scan_dir {
+        if !dir.is_dir() {
+            return Err(format!("Not a folder: {}", dir.display()));
+        }
+        let files = tokio::task::spawn_blocking(move || collect_media_files(&dir))
+            .await
+            .map_err(|e| e.to_string())?;
+        for file in files {
+            if let Some(name) = file.file_name() {
+                by_name.entry(name.to_os_string()).or_default().push(file);
+            }
+        }
+    }
+
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    let rows = db_lock.get_download_files().map_err(|e| e.to_string())?;
+    let mut report = RelinkReport { checked: rows.len(), ..Default::default() };
+    for (id, title, file_path, size) in rows {
+        let old_path = PathBuf::from(&file_path);
+        if old_path.exists() {
+            report.present += 1;
+            continue;
+        }
+
+        let moved = rebase.as_ref().and_then(|(old, new)| {
+            let candidate = new.join(old_path.strip_prefix(old).ok()?);
+            candidate.exists().then(|| (candidate, old.clone(), new.clone()))
+        });
+        // Found by name: files recorded next to it moved along with it
+        let found = moved.or_else(|| {
+            let candidates = by_name.get(old_path.file_name()?)?;
+            let candidate = pick_candidate(candidates, size)?;
+            let new_path = candidate.to_string_lossy();
+            if db_lock.has_download_file(&new_path).unwrap_or(true) {
+                return None;
+            }
+            Some((candidate.clone(), old_path.parent()?.to_path_buf(), candidate.parent()?.to_path_buf()))
+        });
+
+        match found {
+            Some((new_path, old_base, new_base)) => {
+                db_lock
+                    .relink_download_file(
+                        &id,
+                        &new_path.to_string_lossy(),
+                        &old_base.to_string_lossy(),
+                        &new_base.to_string_lossy(),
+                    )
+                    .map_err(|e| e.to_string())?;
+                report.relinked += 1;
+            }
+            None => report.missing.push(MissingFile { id, title, file_path }),
+        }
+    }
+
+    log::info!(
+        "[library] Relink: {} checked, {} relinked, {} missing",
+        report.checked,
+        report.relinked,
+        report.missing.len()
+    );
+    Ok(report)
+}
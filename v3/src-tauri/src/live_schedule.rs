@@ -0,0 +1,180 @@
+//! Scheduled premieres and livestreams.
+//!
+//! Channel listings mark videos that haven't started yet with their start
+//! time (`RssItem::scheduled_at`), and `insert_feed_items` keeps those in
+//! `upcoming_streams`. This module checks the table every half minute:
+//! `live_notify_minutes` before a start (0 = off) it emits
+//! `live-starting-soon` and shows a notification, and with
+//! `live_auto_record` on it starts a download in mode `live` for streams
+//! of auto-download feeds once they are due. Until the stream is actually
+//! up yt-dlp can't read it, so a failed start is retried for a while.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::commands;
+use crate::db::{Database, UpcomingStream};
+
+/// `mode` value that records a livestream or premiere.
+pub const MODE: &str = "live";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Wait this long after a failed start before trying again.
+const RETRY_AFTER: Duration = Duration::from_secs(120);
+/// Streams this late without starting are given up on.
+const GIVE_UP_AFTER_SECS: i64 = 6 * 60 * 60;
+/// Rows are kept this long after the scheduled start.
+const KEEP_SECS: i64 = 24 * 60 * 60;
+
+/// yt-dlp flags for a recording: from the first segment, and waiting for
+/// a stream that is late to start.
+pub fn ytdlp_args() -> Vec<String> {
+    ["--live-from-start", "--wait-for-video", "30-120"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+struct Config {
+    auto_record: bool,
+    /// 0 = no "starting soon" notification.
+    notify_minutes: i64,
+}
+
+fn load_config(app: &AppHandle) -> Option<Config> {
+    let db = app.try_state::<Arc<Mutex<Database>>>()?;
+    let db_lock = db.lock().ok()?;
+    let setting = |key: &str| db_lock.get_setting(key).unwrap_or(None);
+    Some(Config {
+        auto_record: setting("live_auto_record").as_deref() == Some("true"),
+        notify_minutes: setting("live_notify_minutes")
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .unwrap_or(10),
+    })
+}
+
+fn stream_payload(stream: &UpcomingStream) -> serde_json::Value {
+    serde_json::json!({
+        "videoId": stream.video_id,
+        "feedId": stream.feed_id,
+        "title": stream.title,
+        "url": stream.url,
+        "scheduledAt": stream.scheduled_at,
+    })
+}
+
+fn notify_starting_soon(app: &AppHandle, stream: &UpcomingStream, now: i64) {
+    let minutes = ((stream.scheduled_at - now).max(0) + 59) / 60;
+    let _ = app.emit("live-starting-soon", stream_payload(stream));
+    let channel = if stream.feed_title.is_empty() { "A channel" } else { stream.feed_title.as_str() };
+    crate::notifications::notify(
+        app,
+        crate::notifications::NotifyEvent::NewFeedItems,
+        "Starting Soon",
+        &format!("{}: {} (in {} min)", channel, stream.title, minutes),
+    );
+}
+
+async fn record(app: &AppHandle, stream: &UpcomingStream) -> bool {
+    let result = commands::start_download(
+        app.clone(),
+        app.state(),
+        app.state(),
+        stream.url.clone(),
+        None,
+        Some(MODE.to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await;
+    match result {
+        Ok(id) => {
+            log::info!("[live] Recording {} as {}", stream.url, id);
+            if let Some(db) = app.try_state::<Arc<Mutex<Database>>>() {
+                if let Ok(db_lock) = db.lock() {
+                    let _ = db_lock.set_upcoming_download(&stream.video_id, &id);
+                }
+            }
+            let mut payload = stream_payload(stream);
+            payload["id"] = serde_json::json!(id);
+            let _ = app.emit("live-recording-started", payload);
+            true
+        }
+        Err(e) => {
+            log::debug!("[live] Not recording {} yet: {}", stream.url, e);
+            false
+        }
+    }
+}
+
+async fn check(app: &AppHandle, retry_at: &mut HashMap<String, Instant>) {
+    let Some(config) = load_config(app) else { return };
+    let now = chrono::Utc::now().timestamp();
+    let (soon, due) = {
+        let Some(db) = app.try_state::<Arc<Mutex<Database>>>() else { return };
+        let Ok(db_lock) = db.lock() else { return };
+        let _ = db_lock.delete_upcoming_before(now - KEEP_SECS);
+        let streams = db_lock.get_upcoming_streams().unwrap_or_default();
+        let (mut soon, mut due) = (Vec::new(), Vec::new());
+        for stream in streams {
+            if !stream.download_id.is_empty() || stream.scheduled_at + GIVE_UP_AFTER_SECS < now {
+                continue;
+            }
+            if config.notify_minutes > 0
+                && !stream.notified
+                && now < stream.scheduled_at
+                && now >= stream.scheduled_at - config.notify_minutes * 60
+            {
+                let _ = db_lock.set_upcoming_notified(&stream.video_id);
+                soon.push(stream.clone());
+            }
+            if config.auto_record && stream.auto_download && now >= stream.scheduled_at {
+                due.push(stream);
+            }
+        }
+        (soon, due)
+    };
+
+    for stream in &soon {
+        notify_starting_soon(app, stream, now);
+    }
+
+    retry_at.retain(|video_id, _| due.iter().any(|s| s.video_id == *video_id));
+    for stream in due {
+        if retry_at.get(&stream.video_id).is_some_and(|at| Instant::now() < *at) {
+            continue;
+        }
+        if record(app, &stream).await {
+            retry_at.remove(&stream.video_id);
+        } else {
+            retry_at.insert(stream.video_id.clone(), Instant::now() + RETRY_AFTER);
+        }
+    }
+}
+
+/// Start watching for scheduled streams.
+pub fn init(app: &tauri::App) {
+    let handle = app.handle().clone();
+    tauri::async_runtime::spawn(async move {
+        let mut retry_at: HashMap<String, Instant> = HashMap::new();
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            check(&handle, &mut retry_at).await;
+        }
+    });
+}
+
+/// Premieres and livestreams scheduled in followed channels.
+#[tauri::command]
+pub async fn get_upcoming_streams(db: State<'_, Arc<Mutex<Database>>>) -> Result<Vec<UpcomingStream>, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.get_upcoming_streams().map_err(|e| e.to_string())
+}
@@ -0,0 +1,293 @@
+//! Application log.
+//!
+//! [`AppLogger`] is the `log` backend. Every record goes to the console
+//! logger (env_logger on desktop, logcat on Android), to an in-memory
+//! buffer behind the in-app log viewer and, once [`set_log_dir`] has run,
+//! to a daily file under `<app data>/logs`. Files older than the
+//! `log_retention_days` setting are removed at startup and on rollover.
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+const FILE_PREFIX: &str = "ytdl-";
+/// Kept in memory for `get_recent_logs`.
+const RECENT_CAPACITY: usize = 2000;
+/// A day's file is rotated early once it grows past this.
+const MAX_FILE_BYTES: u64 = 20 * 1024 * 1024;
+pub const DEFAULT_RETENTION_DAYS: u32 = 7;
+
+static LOGGER: OnceLock<AppLogger> = OnceLock::new();
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+struct LogFile {
+    dir: PathBuf,
+    date: String,
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+pub struct AppLogger {
+    console: Box<dyn Log>,
+    /// Records at or above this level reach the file and the buffer even
+    /// when the console filter is stricter.
+    level: LevelFilter,
+    file: Mutex<Option<LogFile>>,
+    recent: Mutex<VecDeque<LogEntry>>,
+    retention_days: AtomicU32,
+}
+
+/// Install the logger. Call once, before anything logs.
+pub fn init() {
+    #[cfg(target_os = "android")]
+    let (console, level): (Box<dyn Log>, LevelFilter) = (
+        Box::new(android_logger::AndroidLogger::new(
+            android_logger::Config::default()
+                .with_max_level(LevelFilter::Debug)
+                .with_tag("YTDL-Rust"),
+        )),
+        LevelFilter::Debug,
+    );
+    #[cfg(not(target_os = "android"))]
+    let (console, level): (Box<dyn Log>, LevelFilter) = {
+        let env = env_logger::Builder::from_default_env().build();
+        let level = env.filter().max(LevelFilter::Info);
+        (Box::new(env), level)
+    };
+
+    let logger = LOGGER.get_or_init(|| AppLogger {
+        console,
+        level,
+        file: Mutex::new(None),
+        recent: Mutex::new(VecDeque::with_capacity(RECENT_CAPACITY)),
+        retention_days: AtomicU32::new(DEFAULT_RETENTION_DAYS),
+    });
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+/// Start writing to `dir` and remove files past the retention period.
+pub fn set_log_dir(dir: &Path, retention_days: u32) {
+    let Some(logger) = LOGGER.get() else { return };
+    logger.retention_days.store(retention_days, Ordering::Relaxed);
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        log::warn!("[logger] Failed to create {}: {}", dir.display(), e);
+        return;
+    }
+    let date = today();
+    match open_file(dir, &date) {
+        Ok(file) => {
+            if let Ok(mut slot) = logger.file.lock() {
+                *slot = Some(file);
+            }
+        }
+        Err(e) => log::warn!("[logger] Failed to open log file in {}: {}", dir.display(), e),
+    }
+    cleanup(dir, retention_days);
+}
+
+pub fn set_retention_days(days: u32) {
+    if let Some(logger) = LOGGER.get() {
+        logger.retention_days.store(days, Ordering::Relaxed);
+        if let Some(dir) = log_dir() {
+            cleanup(&dir, days);
+        }
+    }
+}
+
+pub fn log_dir() -> Option<PathBuf> {
+    let logger = LOGGER.get()?;
+    let slot = logger.file.lock().ok()?;
+    slot.as_ref().map(|f| f.dir.clone())
+}
+
+pub fn current_log_file() -> Option<PathBuf> {
+    let logger = LOGGER.get()?;
+    let slot = logger.file.lock().ok()?;
+    slot.as_ref().map(|f| f.path.clone())
+}
+
+/// Newest last. `min_level` drops anything less severe.
+pub fn recent(limit: usize, min_level: Option<Level>) -> Vec<LogEntry> {
+    let Some(logger) = LOGGER.get() else { return Vec::new() };
+    let Ok(recent) = logger.recent.lock() else { return Vec::new() };
+    let mut entries: Vec<LogEntry> = recent
+        .iter()
+        .rev()
+        .filter(|e| match min_level {
+            Some(min) => e.level.parse::<Level>().map(|l| l <= min).unwrap_or(true),
+            None => true,
+        })
+        .take(limit)
+        .cloned()
+        .collect();
+    entries.reverse();
+    entries
+}
+
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+fn open_file(dir: &Path, date: &str) -> std::io::Result<LogFile> {
+    let path = dir.join(format!("{}{}.log", FILE_PREFIX, date));
+    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+    let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+    Ok(LogFile { dir: dir.to_path_buf(), date: date.to_string(), path, file, written })
+}
+
+/// Delete app logs and per-download logs (`downloads/`) last modified
+/// more than `days` days ago.
+fn cleanup(dir: &Path, days: u32) {
+    if days == 0 {
+        return;
+    }
+    let Some(cutoff) = std::time::SystemTime::now()
+        .checked_sub(std::time::Duration::from_secs(days as u64 * 24 * 60 * 60))
+    else {
+        return;
+    };
+    remove_older(dir, FILE_PREFIX, cutoff);
+    remove_older(&dir.join("downloads"), "", cutoff);
+}
+
+fn remove_older(dir: &Path, prefix: &str, cutoff: std::time::SystemTime) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with(prefix) || !name.ends_with(".log") {
+            continue;
+        }
+        let modified = entry.metadata().and_then(|m| m.modified());
+        if matches!(modified, Ok(time) if time < cutoff) {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+impl AppLogger {
+    fn write_file(&self, line: &str) {
+        let Ok(mut slot) = self.file.lock() else { return };
+        let Some(current) = slot.as_mut() else { return };
+
+        let date = today();
+        let rotate_early = current.written + line.len() as u64 > MAX_FILE_BYTES;
+        if date != current.date || rotate_early {
+            let dir = current.dir.clone();
+            if rotate_early && date == current.date {
+                // Keep the full file under a timestamped name
+                let stamp = chrono::Local::now().format("%H%M%S");
+                let _ = std::fs::rename(
+                    &current.path,
+                    dir.join(format!("{}{}-{}.log", FILE_PREFIX, date, stamp)),
+                );
+            }
+            match open_file(&dir, &date) {
+                Ok(file) => *current = file,
+                Err(_) => return,
+            }
+            if !rotate_early {
+                cleanup(&dir, self.retention_days.load(Ordering::Relaxed));
+            }
+        }
+
+        if current.file.write_all(line.as_bytes()).is_ok() {
+            current.written += line.len() as u64;
+        }
+    }
+}
+
+impl Log for AppLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level || self.console.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.console.enabled(record.metadata()) {
+            self.console.log(record);
+        }
+        if record.level() > self.level {
+            return;
+        }
+        // Dependencies only get their warnings and errors recorded
+        if record.level() > Level::Warn && !record.target().starts_with("ytdl") {
+            return;
+        }
+
+        let entry = LogEntry {
+            timestamp: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        self.write_file(&format!(
+            "{} {:<5} [{}] {}\n",
+            entry.timestamp, entry.level, entry.target, entry.message
+        ));
+        if let Ok(mut recent) = self.recent.lock() {
+            if recent.len() == RECENT_CAPACITY {
+                recent.pop_front();
+            }
+            recent.push_back(entry);
+        }
+    }
+
+    fn flush(&self) {
+        self.console.flush();
+        if let Ok(mut slot) = self.file.lock() {
+            if let Some(current) = slot.as_mut() {
+                let _ = current.file.flush();
+            }
+        }
+    }
+}
+
+/// Most recent log lines for the in-app viewer, oldest first.
+#[tauri::command]
+pub fn get_recent_logs(limit: Option<usize>, level: Option<String>) -> Result<Vec<LogEntry>, String> {
+    let min_level = match level.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(value) => Some(
+            value
+                .parse::<Level>()
+                .map_err(|_| format!("Unknown log level '{}'", value))?,
+        ),
+    };
+    Ok(recent(limit.unwrap_or(500).min(RECENT_CAPACITY), min_level))
+}
+
+/// Open today's log file with the system viewer. Returns its path.
+#[tauri::command]
+pub async fn open_log_file() -> Result<String, String> {
+    let path = current_log_file().ok_or("File logging is not active")?;
+    if let Some(logger) = LOGGER.get() {
+        logger.flush();
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        let opened = crate::android_bridge::open_file_path(&path.to_string_lossy())?;
+        if !opened {
+            return Err("Could not open log file on Android".to_string());
+        }
+    }
+    #[cfg(not(target_os = "android"))]
+    open::that(&path).map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+
+    Ok(path.to_string_lossy().to_string())
+}
@@ -1,6 +1,12 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
-    // Logging is initialized inside run() (env_logger for desktop, android_logger for Android)
+    // `ytdl add|list|download|run ...` work without opening a window
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(code) = ytdl_lib::cli::run_from_args(&args) {
+        std::process::exit(code);
+    }
+
+    // Logging is initialized inside run() (see logger.rs)
     ytdl_lib::run();
 }
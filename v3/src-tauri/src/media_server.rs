@@ -0,0 +1,240 @@
+//! Library refreshes on a Jellyfin or Plex server.
+//!
+//! With `media_server` set to `jellyfin` or `plex`, finished downloads
+//! inside `media_server_library` (any download when it is empty) make the
+//! server at `media_server_url` rescan just the folders they landed in, so
+//! new videos show up without waiting for the scheduled scan. The API key
+//! or Plex token is the `media_server_token` secret. Downloads finishing
+//! close together, like a playlist, are sent as one refresh after
+//! [`SETTLE_DELAY`].
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::db::Database;
+
+pub const SERVERS: &[&str] = &["none", "jellyfin", "plex"];
+const SETTLE_DELAY: Duration = Duration::from_secs(20);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Folders waiting for the next refresh.
+static PENDING: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+pub fn validate_server_url(url: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(url.trim()).map_err(|e| format!("Invalid media server URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("Media server URL must start with http:// or https://".to_string());
+    }
+    if parsed.host_str().is_none() {
+        return Err("Media server URL has no host".to_string());
+    }
+    Ok(())
+}
+
+/// The server token's keyring entry, read before locking the database.
+fn keyring_token() -> Option<String> {
+    crate::secrets::keyring_secret("media_server_token")
+}
+
+struct Server {
+    kind: String,
+    url: String,
+    token: String,
+    library: String,
+}
+
+impl Server {
+    /// The configured server, `None` when the integration is off.
+    /// `token_from_keyring` is read before `db` is locked.
+    fn load(db: &Database, token_from_keyring: Option<String>) -> Option<Self> {
+        let setting = |key: &str| db.get_setting(key).unwrap_or(None).unwrap_or_default();
+        let kind = setting("media_server");
+        let url = setting("media_server_url").trim().trim_end_matches('/').to_string();
+        if !matches!(kind.as_str(), "jellyfin" | "plex") || url.is_empty() {
+            return None;
+        }
+        Some(Self {
+            kind,
+            url,
+            token: crate::secrets::secret_or_setting(db, "media_server_token", token_from_keyring),
+            library: setting("media_server_library").trim().to_string(),
+        })
+    }
+
+    fn client() -> Result<reqwest::Client, String> {
+        crate::proxy::client_builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| e.to_string())
+    }
+
+    fn request(&self, client: &reqwest::Client, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = client
+            .request(method, format!("{}{}", self.url, path))
+            .header("Accept", "application/json")
+            .header("User-Agent", concat!("YTDL/", env!("CARGO_PKG_VERSION")));
+        if self.token.is_empty() {
+            request
+        } else if self.kind == "plex" {
+            request.header("X-Plex-Token", &self.token)
+        } else {
+            request.header("X-Emby-Token", &self.token)
+        }
+    }
+
+    /// Server name, as a check of the URL and token.
+    async fn check(&self) -> Result<String, String> {
+        let client = Self::client()?;
+        let path = if self.kind == "plex" { "/library/sections" } else { "/System/Info" };
+        let response = self
+            .request(&client, reqwest::Method::GET, path)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        let info: serde_json::Value = response.json().await.unwrap_or_default();
+        let name = if self.kind == "plex" {
+            info["MediaContainer"]["title1"].as_str()
+        } else {
+            info["ServerName"].as_str()
+        };
+        Ok(name.filter(|n| !n.is_empty()).unwrap_or(&self.kind).to_string())
+    }
+
+    /// Ask the server to rescan `folders`.
+    async fn refresh(&self, folders: &[PathBuf]) -> Result<(), String> {
+        let client = Self::client()?;
+        if self.kind == "jellyfin" {
+            let updates: Vec<serde_json::Value> = folders
+                .iter()
+                .map(|folder| serde_json::json!({ "Path": folder.to_string_lossy(), "UpdateType": "Created" }))
+                .collect();
+            let response = self
+                .request(&client, reqwest::Method::POST, "/Library/Media/Updated")
+                .json(&serde_json::json!({ "Updates": updates }))
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("HTTP {}", response.status()));
+            }
+            return Ok(());
+        }
+
+        // Plex scans per library section; find the one holding each folder
+        let response = self
+            .request(&client, reqwest::Method::GET, "/library/sections")
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        let sections: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        let sections = sections["MediaContainer"]["Directory"].as_array().cloned().unwrap_or_default();
+        for folder in folders {
+            let section = sections.iter().find_map(|section| {
+                let key = section["key"].as_str()?;
+                section["Location"]
+                    .as_array()?
+                    .iter()
+                    .filter_map(|location| location["path"].as_str())
+                    .any(|path| folder.starts_with(path))
+                    .then(|| key.to_string())
+            });
+            let Some(section) = section else {
+                log::warn!("[media_server] No Plex library contains {}", folder.display());
+                continue;
+            };
+            let response = self
+                .request(&client, reqwest::Method::GET, &format!("/library/sections/{}/refresh", section))
+                .query(&[("path", folder.to_string_lossy())])
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("HTTP {}", response.status()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Queue a library refresh for a finished download at `file_path`.
+pub fn download_finished(app: &AppHandle, file_path: &str) {
+    let Some(db) = app.try_state::<Arc<Mutex<Database>>>() else { return };
+    let token = keyring_token();
+    let server = {
+        let Ok(db_lock) = db.lock() else { return };
+        Server::load(&db_lock, token)
+    };
+    let Some(server) = server else { return };
+    let Some(folder) = Path::new(file_path).parent() else { return };
+    if !server.library.is_empty() && !folder.starts_with(&server.library) {
+        return;
+    }
+
+    let first = {
+        let Ok(mut pending) = PENDING.lock() else { return };
+        let first = pending.is_empty();
+        pending.insert(folder.to_path_buf());
+        first
+    };
+    if !first {
+        return;
+    }
+    let db = db.inner().clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SETTLE_DELAY).await;
+        let folders: Vec<PathBuf> = match PENDING.lock() {
+            Ok(mut pending) => std::mem::take(&mut *pending).into_iter().collect(),
+            Err(_) => return,
+        };
+        // Settings may have changed while waiting
+        let token = keyring_token();
+        let server = match db.lock() {
+            Ok(db_lock) => Server::load(&db_lock, token),
+            Err(_) => return,
+        };
+        let Some(server) = server else { return };
+        match server.refresh(&folders).await {
+            Ok(()) => log::info!("[media_server] Asked {} to rescan {} folder(s)", server.kind, folders.len()),
+            Err(e) => log::warn!("[media_server] {} library refresh failed: {}", server.kind, e),
+        }
+    });
+}
+
+/// Check the configured server and token; returns the server's name.
+#[tauri::command]
+pub async fn test_media_server(db: State<'_, Arc<Mutex<Database>>>) -> Result<String, String> {
+    let token = keyring_token();
+    let server = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        Server::load(&db_lock, token)
+    };
+    let server = server.ok_or_else(|| "No media server configured".to_string())?;
+    server.check().await
+}
+
+/// Rescan the library folder (or the download folder) right away.
+#[tauri::command]
+pub async fn refresh_media_library(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<(), String> {
+    let token = keyring_token();
+    let (server, download_dir) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let download_dir = crate::commands::resolve_download_dir(&app, &db_lock, crate::download::MediaKind::Video);
+        (Server::load(&db_lock, token), download_dir)
+    };
+    let server = server.ok_or_else(|| "No media server configured".to_string())?;
+    let folder = if server.library.is_empty() { download_dir } else { server.library.clone() };
+    server.refresh(&[PathBuf::from(folder)]).await
+}
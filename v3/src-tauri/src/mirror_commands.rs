@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, State};
+
+use crate::commands::validate_url;
+use crate::db::Database;
+use crate::download::{self, DownloadManager};
+use crate::playlist_commands::{
+    ensure_playlist_record, fetch_playlist_since, normalize_date_filter, reconcile_playlist,
+    sync_playlist_inner,
+};
+
+/// yt-dlp `--download-archive` file kept in every mirror folder. Videos
+/// listed there are never downloaded again, even if the app's database is
+/// reset or the folder was seeded by a plain yt-dlp run.
+pub const MIRROR_ARCHIVE_FILE: &str = "archive.txt";
+
+/// Mirrors sort by upload date by default so the folder reads chronologically.
+pub const DEFAULT_MIRROR_TEMPLATE: &str = "%(upload_date)s - %(title)s.%(ext)s";
+
+/// Tabs a YouTube channel URL can already point at.
+const CHANNEL_TABS: &[&str] = &["videos", "shorts", "streams", "live", "playlists", "podcasts", "releases"];
+
+/// Folder a mirror downloads into: its configured `output_dir`, or
+/// `<download dir>/<channel title>`.
+pub(crate) fn resolve_mirror_dir(download_dir: &str, title: &str, output_dir: &str) -> PathBuf {
+    if !output_dir.trim().is_empty() {
+        return PathBuf::from(output_dir.trim());
+    }
+    let name = if title.trim().is_empty() {
+        "Channel".to_string()
+    } else {
+        download::sanitize_path_component(title)
+    };
+    Path::new(download_dir).join(name)
+}
+
+/// Video ids recorded in the mirror folder's yt-dlp archive
+/// (lines of the form `<extractor> <id>`).
+pub(crate) fn read_archive_ids(dir: &Path) -> HashSet<String> {
+    std::fs::read_to_string(dir.join(MIRROR_ARCHIVE_FILE))
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| line.split_whitespace().nth(1))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A bare YouTube channel URL makes `--flat-playlist` list the channel's
+/// tabs rather than its videos, so point it at the uploads tab.
+fn channel_uploads_url(input: &str) -> String {
+    let trimmed = input.trim().trim_end_matches('/');
+    let Ok(parsed) = url::Url::parse(trimmed) else {
+        return trimmed.to_string();
+    };
+    let host = parsed.host_str().unwrap_or_default();
+    if !(host == "youtube.com" || host.ends_with(".youtube.com")) {
+        return trimmed.to_string();
+    }
+
+    let segments: Vec<&str> = parsed.path_segments().map(|s| s.collect()).unwrap_or_default();
+    let is_channel = match segments.first() {
+        Some(first) if first.starts_with('@') => segments.len() == 1,
+        Some(&"channel") | Some(&"c") | Some(&"user") => segments.len() == 2,
+        _ => false,
+    };
+    let has_tab = segments
+        .last()
+        .map(|last| CHANNEL_TABS.contains(last))
+        .unwrap_or(false);
+
+    if is_channel && !has_tab {
+        let mut with_tab = parsed.clone();
+        with_tab.set_path(&format!("{}/videos", parsed.path().trim_end_matches('/')));
+        with_tab.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn validate_mirror_template(template: &str) -> Result<(), String> {
+    if download::render_playlist_template(template, 1, "").is_none() {
+        return Err("Invalid naming template: it must be a relative file name".to_string());
+    }
+    Ok(())
+}
+
+/// `YYYY-MM-DD` or `YYYYMMDD` to `YYYYMMDD`, empty for no cut-off.
+fn mirror_date_after(date_after: Option<&str>) -> Result<String, String> {
+    match date_after.map(str::trim).filter(|d| !d.is_empty()) {
+        Some(raw) => normalize_date_filter(raw),
+        None => Ok(String::new()),
+    }
+}
+
+/// Start mirroring a channel: enumerate every upload, queue whatever isn't
+/// in the mirror's archive yet and (with `auto_sync`, the default) keep it
+/// updated on the playlist sync schedule. Adding a channel that is already
+/// mirrored updates its settings and syncs it. With `date_after` only
+/// uploads from that day on are mirrored, now and on later syncs. Returns
+/// the sync summary.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn add_channel_mirror(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
+    url: String,
+    output_dir: Option<String>,
+    naming_template: Option<String>,
+    format: Option<String>,
+    auto_sync: Option<bool>,
+    date_after: Option<String>,
+) -> Result<serde_json::Value, String> {
+    validate_url(&url)?;
+    let naming_template = naming_template
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| DEFAULT_MIRROR_TEMPLATE.to_string());
+    validate_mirror_template(&naming_template)?;
+    let date_after = mirror_date_after(date_after.as_deref())?;
+
+    let uploads_url = channel_uploads_url(&url);
+    let ytdlp = download::get_ytdlp_path(&app);
+    log::info!("Enumerating channel uploads for mirror: {}", uploads_url);
+    let since = Some(date_after.as_str()).filter(|d| !d.is_empty());
+    let info = fetch_playlist_since(&ytdlp, &uploads_url, since).await?;
+
+    let id = ensure_playlist_record(db.inner(), &uploads_url, &info)?;
+    {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        db_lock
+            .update_channel_mirror_settings(
+                &id,
+                output_dir.as_deref().unwrap_or("").trim(),
+                &naming_template,
+                format.as_deref().unwrap_or("").trim(),
+                auto_sync.unwrap_or(true),
+            )
+            .map_err(|e| e.to_string())?;
+        db_lock
+            .update_playlist_date_after(&id, &date_after)
+            .map_err(|e| e.to_string())?;
+    }
+
+    reconcile_playlist(&app, db.inner(), dl.inner(), &id, &info).await
+}
+
+#[tauri::command]
+pub async fn get_channel_mirrors(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    let playlists = db_lock.get_playlists().map_err(|e| e.to_string())?;
+    Ok(playlists
+        .into_iter()
+        .filter(|p| p["kind"].as_str() == Some("channel"))
+        .collect())
+}
+
+/// Change a mirror's folder, naming template, format, schedule or date
+/// cut-off. Only affects videos downloaded from now on; existing files are
+/// not moved.
+#[tauri::command]
+pub async fn update_channel_mirror(
+    db: State<'_, Arc<Mutex<Database>>>,
+    id: String,
+    output_dir: String,
+    naming_template: String,
+    format: Option<String>,
+    auto_sync: bool,
+    date_after: Option<String>,
+) -> Result<(), String> {
+    validate_mirror_template(&naming_template)?;
+    let date_after = mirror_date_after(date_after.as_deref())?;
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    let existing = db_lock
+        .get_playlist(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Channel mirror not found: {}", id))?;
+    if existing["kind"].as_str() != Some("channel") {
+        return Err(format!("Not a channel mirror: {}", id));
+    }
+    db_lock
+        .update_channel_mirror_settings(
+            &id,
+            output_dir.trim(),
+            naming_template.trim(),
+            format.as_deref().unwrap_or("").trim(),
+            auto_sync,
+        )
+        .map_err(|e| e.to_string())?;
+    db_lock
+        .update_playlist_date_after(&id, &date_after)
+        .map_err(|e| e.to_string())
+}
+
+/// Check a mirror for new uploads right now.
+#[tauri::command]
+pub async fn sync_channel_mirror(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
+    id: String,
+) -> Result<serde_json::Value, String> {
+    sync_playlist_inner(&app, db.inner(), dl.inner(), &id).await
+}
@@ -0,0 +1,391 @@
+//! Native messaging host for the companion browser extension.
+//!
+//! The browser starts this binary with the extension's origin as its first
+//! argument and exchanges JSON messages over stdin/stdout, each prefixed
+//! with its length as a native-endian `u32`. Requests:
+//!
+//! - `{"type": "ping"}` → `{"type": "pong", "version", "apiEnabled"}`
+//! - `{"type": "download", "url", "formatId"?, "quality"?, "mode"?,
+//!   "cookiesFromBrowser"?, "cookieProfile"?}` → `{"type": "queued", "url", "id"?}` or
+//!   `{"type": "error", "url", "error"}`. Once the download finishes the
+//!   host sends `{"type": "completed" | "failed", "id", "url", ...}`.
+//! - `{"type": "status", "id"}` → `{"type": "status", "download"}`
+//!
+//! Downloads are handed to the running app through the local HTTP API
+//! (see [`crate::remote_api`]). If it isn't enabled, the URL is passed to
+//! a new launch of the app instead, which forwards it to the running
+//! instance; completion can't be reported in that case.
+//!
+//! [`install_native_host`] writes the host manifest for Chrome, Chromium,
+//! Edge, Brave or Firefox.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::download::QualityPreference;
+use crate::secrets;
+
+/// Name the extension passes to `runtime.connectNative`.
+pub const HOST_NAME: &str = "com.ytdl.native";
+/// Chrome's limit for messages sent to the host.
+const MAX_MESSAGE: u32 = 4 * 1024 * 1024;
+const STATUS_POLL: Duration = Duration::from_secs(2);
+
+type Stdout = Arc<tokio::sync::Mutex<tokio::io::Stdout>>;
+
+/// Whether the browser launched us as a native messaging host: Chromium
+/// passes the caller's origin, Firefox the manifest path and extension id.
+pub fn is_native_messaging_launch(args: &[String]) -> bool {
+    match args.get(1).map(String::as_str) {
+        Some(first) if first.starts_with("chrome-extension://") => true,
+        Some("--native-messaging") => true,
+        Some(first) => first.ends_with(".json") && args.len() == 3 && first.contains(HOST_NAME),
+        None => false,
+    }
+}
+
+/// Serve messages until the browser closes stdin.
+pub fn run() -> i32 {
+    let runtime = match tokio::runtime::Builder::new_multi_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            log::error!("[native_host] Failed to start runtime: {}", e);
+            return 1;
+        }
+    };
+    runtime.block_on(async {
+        let stdout: Stdout = Arc::new(tokio::sync::Mutex::new(tokio::io::stdout()));
+        let mut stdin = tokio::io::stdin();
+        loop {
+            let message = match read_message(&mut stdin).await {
+                Ok(Some(message)) => message,
+                Ok(None) => return 0,
+                Err(e) => {
+                    log::warn!("[native_host] {}", e);
+                    return 1;
+                }
+            };
+            let reply = handle_message(&message, &stdout).await;
+            if let Err(e) = write_message(&stdout, &reply).await {
+                log::warn!("[native_host] Failed to reply: {}", e);
+                return 1;
+            }
+        }
+    })
+}
+
+async fn read_message(stdin: &mut tokio::io::Stdin) -> Result<Option<serde_json::Value>, String> {
+    let mut len = [0u8; 4];
+    match stdin.read_exact(&mut len).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    }
+    let len = u32::from_ne_bytes(len);
+    if len > MAX_MESSAGE {
+        return Err(format!("Message too large ({} bytes)", len));
+    }
+    let mut body = vec![0u8; len as usize];
+    stdin.read_exact(&mut body).await.map_err(|e| e.to_string())?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| format!("Invalid message: {}", e))
+}
+
+async fn write_message(stdout: &Stdout, message: &serde_json::Value) -> Result<(), String> {
+    let body = message.to_string();
+    let mut stdout = stdout.lock().await;
+    stdout
+        .write_all(&(body.len() as u32).to_ne_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+    stdout.write_all(body.as_bytes()).await.map_err(|e| e.to_string())?;
+    stdout.flush().await.map_err(|e| e.to_string())
+}
+
+/// Base URL and token of the local API, if it's enabled.
+fn api_endpoint() -> Option<(String, String)> {
+    let db = crate::cli::open_db().ok()?;
+    if db.get_setting("api_enabled").unwrap_or(None).as_deref() != Some("true") {
+        return None;
+    }
+    let port = db
+        .get_setting("api_port")
+        .unwrap_or(None)
+        .and_then(|p| p.trim().parse::<u16>().ok())
+        .unwrap_or(crate::remote_api::DEFAULT_PORT);
+    let token = secrets::get_secret(&db, "api_token");
+    (!token.is_empty()).then(|| (format!("http://127.0.0.1:{}/api", port), token))
+}
+
+fn api_client() -> Result<reqwest::Client, String> {
+    // Loopback only, never through a proxy
+    reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_secs(60))
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+async fn api_get(base: &str, token: &str, path: &str) -> Result<serde_json::Value, String> {
+    let response = api_client()?
+        .get(format!("{}{}", base, path))
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| format!("YTDL is not running: {}", e))?;
+    let ok = response.status().is_success();
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    if ok {
+        Ok(body)
+    } else {
+        Err(body["error"].as_str().unwrap_or("Request failed").to_string())
+    }
+}
+
+async fn handle_message(message: &serde_json::Value, stdout: &Stdout) -> serde_json::Value {
+    let text = |key: &str| {
+        message[key]
+            .as_str()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+
+    match message["type"].as_str().unwrap_or("") {
+        "ping" => serde_json::json!({
+            "type": "pong",
+            "version": env!("CARGO_PKG_VERSION"),
+            "apiEnabled": api_endpoint().is_some(),
+        }),
+        "download" => {
+            let Some(url) = text("url") else {
+                return serde_json::json!({ "type": "error", "error": "Missing url" });
+            };
+            if let Err(e) = crate::commands::validate_url(&url) {
+                return serde_json::json!({ "type": "error", "url": url, "error": e });
+            }
+            let format_id = text("formatId").or_else(|| {
+                text("quality").map(|q| QualityPreference::from_quality(&q).format_expression())
+            });
+            match api_endpoint() {
+                Some((base, token)) => {
+                    let body = serde_json::json!({
+                        "url": url,
+                        "formatId": format_id,
+                        "mode": text("mode"),
+                        "cookiesFromBrowser": text("cookiesFromBrowser"),
+                        "cookieProfile": text("cookieProfile"),
+                    });
+                    match queue_via_api(&base, &token, &body).await {
+                        Ok(id) => {
+                            tokio::spawn(watch_download(base, token, id.clone(), url.clone(), stdout.clone()));
+                            serde_json::json!({ "type": "queued", "url": url, "id": id })
+                        }
+                        Err(e) => serde_json::json!({ "type": "error", "url": url, "error": e }),
+                    }
+                }
+                None => match launch_app_with(&url) {
+                    Ok(()) => serde_json::json!({ "type": "queued", "url": url }),
+                    Err(e) => serde_json::json!({ "type": "error", "url": url, "error": e }),
+                },
+            }
+        }
+        "status" => {
+            let Some(id) = text("id") else {
+                return serde_json::json!({ "type": "error", "error": "Missing id" });
+            };
+            let Some((base, token)) = api_endpoint() else {
+                return serde_json::json!({ "type": "error", "id": id, "error": "The local API is disabled" });
+            };
+            match api_get(&base, &token, &format!("/downloads/{}", id)).await {
+                Ok(download) => serde_json::json!({ "type": "status", "download": download }),
+                Err(e) => serde_json::json!({ "type": "error", "id": id, "error": e }),
+            }
+        }
+        other => serde_json::json!({ "type": "error", "error": format!("Unknown message type '{}'", other) }),
+    }
+}
+
+async fn queue_via_api(base: &str, token: &str, body: &serde_json::Value) -> Result<String, String> {
+    let response = api_client()?
+        .post(format!("{}/downloads", base))
+        .bearer_auth(token)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("YTDL is not running: {}", e))?;
+    let ok = response.status().is_success();
+    let reply: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    match reply["id"].as_str() {
+        Some(id) if ok => Ok(id.to_string()),
+        _ => Err(reply["error"].as_str().unwrap_or("Request failed").to_string()),
+    }
+}
+
+/// Poll a queued download and tell the extension when it's done.
+async fn watch_download(base: String, token: String, id: String, url: String, stdout: Stdout) {
+    loop {
+        tokio::time::sleep(STATUS_POLL).await;
+        let download = match api_get(&base, &token, &format!("/downloads/{}", id)).await {
+            Ok(download) => download,
+            Err(e) => {
+                log::debug!("[native_host] Stopped watching {}: {}", id, e);
+                return;
+            }
+        };
+        let message = match download["status"].as_str() {
+            Some("completed") => serde_json::json!({
+                "type": "completed",
+                "id": id,
+                "url": url,
+                "filePath": download["filePath"],
+            }),
+            Some("error") => serde_json::json!({
+                "type": "failed",
+                "id": id,
+                "url": url,
+                "error": download["error"],
+            }),
+            Some("cancelled") => serde_json::json!({ "type": "failed", "id": id, "url": url, "error": "Cancelled" }),
+            _ => continue,
+        };
+        let _ = write_message(&stdout, &message).await;
+        return;
+    }
+}
+
+/// Hand the URL to the app the same way "Open with" would; the single
+/// instance plugin forwards it if the app is already running.
+fn launch_app_with(url: &str) -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    std::process::Command::new(exe)
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to start YTDL: {}", e))
+}
+
+/// Folder the browser reads user-level host manifests from. `None` on
+/// Windows, where manifests are registered in the registry instead.
+fn manifest_dir(browser: &str) -> Result<Option<PathBuf>, String> {
+    if cfg!(windows) {
+        return Ok(None);
+    }
+    let home = dirs::home_dir().ok_or("Could not determine the home directory")?;
+    let relative = if cfg!(target_os = "macos") {
+        match browser {
+            "chrome" => "Library/Application Support/Google/Chrome/NativeMessagingHosts",
+            "chromium" => "Library/Application Support/Chromium/NativeMessagingHosts",
+            "edge" => "Library/Application Support/Microsoft Edge/NativeMessagingHosts",
+            "brave" => "Library/Application Support/BraveSoftware/Brave-Browser/NativeMessagingHosts",
+            "firefox" => "Library/Application Support/Mozilla/NativeMessagingHosts",
+            _ => return Err(format!("Unsupported browser '{}'", browser)),
+        }
+    } else {
+        match browser {
+            "chrome" => ".config/google-chrome/NativeMessagingHosts",
+            "chromium" => ".config/chromium/NativeMessagingHosts",
+            "edge" => ".config/microsoft-edge/NativeMessagingHosts",
+            "brave" => ".config/BraveSoftware/Brave-Browser/NativeMessagingHosts",
+            "firefox" => ".mozilla/native-messaging-hosts",
+            _ => return Err(format!("Unsupported browser '{}'", browser)),
+        }
+    };
+    Ok(Some(home.join(relative)))
+}
+
+#[cfg(windows)]
+fn registry_key(browser: &str) -> Result<String, String> {
+    let base = match browser {
+        "chrome" | "brave" => r"HKCU\Software\Google\Chrome\NativeMessagingHosts",
+        "chromium" => r"HKCU\Software\Chromium\NativeMessagingHosts",
+        "edge" => r"HKCU\Software\Microsoft\Edge\NativeMessagingHosts",
+        "firefox" => r"HKCU\Software\Mozilla\NativeMessagingHosts",
+        _ => return Err(format!("Unsupported browser '{}'", browser)),
+    };
+    Ok(format!(r"{}\{}", base, HOST_NAME))
+}
+
+/// Register this binary as the native messaging host for `browser`
+/// ("chrome", "chromium", "edge", "brave" or "firefox"), allowing only the
+/// given extension id. Returns the manifest path.
+#[tauri::command]
+pub async fn install_native_host(browser: String, extension_id: String) -> Result<String, String> {
+    let browser = browser.trim().to_lowercase();
+    let extension_id = extension_id.trim().to_string();
+    let valid_id = !extension_id.is_empty()
+        && extension_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '@' | '.' | '-' | '_' | '{' | '}'));
+    if !valid_id {
+        return Err("Invalid extension id".to_string());
+    }
+
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let mut manifest = serde_json::json!({
+        "name": HOST_NAME,
+        "description": "YTDL",
+        "path": exe.to_string_lossy(),
+        "type": "stdio",
+    });
+    if browser == "firefox" {
+        manifest["allowed_extensions"] = serde_json::json!([extension_id]);
+    } else {
+        manifest["allowed_origins"] = serde_json::json!([format!("chrome-extension://{}/", extension_id)]);
+    }
+
+    let dir = match manifest_dir(&browser)? {
+        Some(dir) => dir,
+        // Windows: keep the manifest with the app data, point the registry at it
+        None => crate::cli::app_data_dir()?.join("native-messaging").join(&browser),
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(format!("{}.json", HOST_NAME));
+    let body = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    #[cfg(windows)]
+    {
+        let status = crate::download::create_hidden_command("reg")
+            .args(["add", &registry_key(&browser)?, "/ve", "/t", "REG_SZ", "/d"])
+            .arg(&path)
+            .arg("/f")
+            .status()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !status.success() {
+            return Err("Failed to register the native messaging host".to_string());
+        }
+    }
+
+    log::info!("[native_host] Installed {} manifest at {}", browser, path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Remove the host manifest for `browser`.
+#[tauri::command]
+pub async fn uninstall_native_host(browser: String) -> Result<(), String> {
+    let browser = browser.trim().to_lowercase();
+    let dir = match manifest_dir(&browser)? {
+        Some(dir) => dir,
+        None => crate::cli::app_data_dir()?.join("native-messaging").join(&browser),
+    };
+    let path = dir.join(format!("{}.json", HOST_NAME));
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = crate::download::create_hidden_command("reg")
+            .args(["delete", &registry_key(&browser)?, "/f"])
+            .status()
+            .await;
+    }
+    Ok(())
+}
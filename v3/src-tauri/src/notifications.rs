@@ -0,0 +1,99 @@
+//! Desktop notifications.
+//!
+//! Every notification goes through [`notify`], which checks the master
+//! `notifications` switch and the per-event setting before showing it.
+
+use std::sync::{Arc, Mutex};
+
+use tauri::{AppHandle, Manager};
+
+use crate::db::Database;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotifyEvent {
+    DownloadComplete,
+    DownloadFailed,
+    /// New videos from RSS feeds or synced playlists.
+    NewFeedItems,
+    TranscriptionDone,
+    ToolUpdate,
+}
+
+impl NotifyEvent {
+    /// Setting that enables this kind of notification.
+    pub fn setting_key(self) -> &'static str {
+        match self {
+            Self::DownloadComplete => "notify_download_complete",
+            Self::DownloadFailed => "notify_download_failed",
+            Self::NewFeedItems => "rss_notifications",
+            Self::TranscriptionDone => "notify_transcription_done",
+            Self::ToolUpdate => "notify_tool_update",
+        }
+    }
+}
+
+fn enabled(db: &Database, event: NotifyEvent) -> bool {
+    // Missing keys count as on, like their defaults
+    let on = |key: &str| db.get_setting(key).unwrap_or(None).as_deref() != Some("false");
+    on("notifications") && on(event.setting_key())
+}
+
+/// Whether the user wants notifications for `event`.
+pub fn is_enabled(app: &AppHandle, event: NotifyEvent) -> bool {
+    let Some(db) = app.try_state::<Arc<Mutex<Database>>>() else { return false };
+    let allowed = db.lock().map(|db| enabled(&db, event)).unwrap_or(false);
+    allowed
+}
+
+/// Show a notification if the user wants this kind of event.
+pub fn notify(app: &AppHandle, event: NotifyEvent, title: &str, body: &str) {
+    if !is_enabled(app, event) {
+        return;
+    }
+
+    #[cfg(desktop)]
+    {
+        use tauri_plugin_notification::NotificationExt;
+        if let Err(e) = app.notification().builder().title(title).body(body).show() {
+            log::debug!("[notifications] Failed to show {:?}: {}", event, e);
+        }
+    }
+    #[cfg(mobile)]
+    let _ = (title, body);
+}
+
+/// Title (or URL) and status of a download row.
+pub(crate) fn download_title(app: &AppHandle, id: &str) -> (String, String) {
+    let row = app.try_state::<Arc<Mutex<Database>>>().and_then(|db| {
+        let db = db.lock().ok()?;
+        db.get_download(id).ok()?
+    });
+    let field = |key: &str| {
+        row.as_ref()
+            .and_then(|r| r[key].as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let title = field("title");
+    (if title.is_empty() { field("url") } else { title }, field("status"))
+}
+
+pub fn download_complete(app: &AppHandle, id: &str) {
+    let (title, _) = download_title(app, id);
+    notify(app, NotifyEvent::DownloadComplete, "Download complete", &title);
+}
+
+pub fn download_failed(app: &AppHandle, id: &str, error: &str) {
+    let (title, status) = download_title(app, id);
+    // Pausing and cancelling stop yt-dlp the same way a failure does
+    if status == "paused" || status == "cancelled" {
+        return;
+    }
+    let first_line = error.lines().next().unwrap_or(error);
+    notify(
+        app,
+        NotifyEvent::DownloadFailed,
+        "Download failed",
+        &format!("{}\n{}", title, first_line),
+    );
+}
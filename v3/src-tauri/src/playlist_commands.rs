@@ -1,23 +1,31 @@
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, State};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
 
 use crate::commands::validate_url;
 use crate::db::Database;
-use crate::download::{self, DownloadManager};
+use crate::download::{self, DownloadManager, PlaylistEntry, PlaylistInfo};
+use crate::error::AppError;
+use crate::playlist_scheduler::PlaylistScheduler;
 
 #[tauri::command]
-pub async fn get_playlist_info(app: AppHandle, url: String) -> Result<serde_json::Value, String> {
+pub async fn get_playlist_info(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    url: String,
+    refresh: Option<bool>,
+) -> Result<serde_json::Value, AppError> {
     validate_url(&url)?;
     let ytdlp = download::get_ytdlp_path(&app);
     log::info!("Fetching playlist info for: {}", url);
-    let info = download::fetch_playlist_info(&ytdlp, &url)
+    let info = fetch_playlist_cached(db.inner(), &ytdlp, &url, None, Some(&app), None, refresh.unwrap_or(false))
         .await
         .map_err(|e| {
             log::error!("Playlist fetch error: {}", e);
-            e.to_string()
+            e
         })?;
     log::info!("Playlist fetched: {} entries", info.entry_count);
-    serde_json::to_value(&info).map_err(|e| e.to_string())
+    Ok(serde_json::to_value(&info)?)
 }
 
 #[tauri::command]
@@ -29,12 +37,34 @@ pub async fn start_playlist_download(
     start_index: Option<usize>,
     end_index: Option<usize>,
     format: Option<String>,
+    naming_template: Option<String>,
+    concurrency: Option<usize>,
+    reverse: Option<bool>,
+    date_after: Option<String>,
+    last_n: Option<usize>,
+    refresh: Option<bool>,
 ) -> Result<Vec<String>, String> {
     validate_url(&url)?;
+    let date_after = match date_after.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        Some(raw) => Some(normalize_date_filter(raw)?),
+        None => None,
+    };
+
     let ytdlp = download::get_ytdlp_path(&app);
-    let playlist_info = download::fetch_playlist_info(&ytdlp, &url)
-        .await
-        .map_err(|e| e.to_string())?;
+    // Only the pages up to the range end are needed, unless counting from
+    // the end of the playlist
+    let limit = end_index.filter(|_| last_n.is_none());
+    let playlist_info = fetch_playlist_cached(
+        db.inner(),
+        &ytdlp,
+        &url,
+        date_after.as_deref(),
+        None,
+        limit,
+        refresh.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
 
     let start = start_index.unwrap_or(1).max(1);
     let end = end_index
@@ -45,59 +75,1030 @@ pub async fn start_playlist_download(
         return Err("Invalid playlist range".to_string());
     }
 
+    let playlist_id = ensure_playlist_record(db.inner(), &url, &playlist_info)?;
+    // Later syncs keep to the same cut-off
+    {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        db_lock
+            .update_playlist_date_after(&playlist_id, date_after.as_deref().unwrap_or(""))
+            .map_err(|e| e.to_string())?;
+    }
+
+    if let Some(template) = naming_template.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        if download::render_playlist_template(template, 1, &playlist_info.title).is_none() {
+            return Err("Invalid naming template: it must be a relative file name".to_string());
+        }
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        db_lock
+            .update_playlist_naming_template(&playlist_id, template)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut selected: Vec<PlaylistEntry> = playlist_info
+        .entries
+        .iter()
+        .filter(|entry| entry.index >= start && entry.index <= end)
+        .filter(|entry| match &date_after {
+            Some(after) => uploaded_since(entry, after),
+            None => true,
+        })
+        .cloned()
+        .collect();
+
+    if let Some(n) = last_n.filter(|n| *n > 0) {
+        let skip = selected.len().saturating_sub(n);
+        selected.drain(..skip);
+    }
+    if reverse.unwrap_or(false) {
+        selected.reverse();
+    }
+
+    queue_playlist_entries(
+        &app,
+        db.inner(),
+        dl.inner(),
+        Some(&playlist_id),
+        &selected,
+        format,
+        concurrency,
+    )
+    .await
+}
+
+/// Fetch a playlist's entries. With a date filter, YouTube's tab extractor
+/// is asked to approximate upload dates from the "N days ago" labels, as
+/// flat entries carry none otherwise.
+pub(crate) async fn fetch_playlist_since(
+    ytdlp: &str,
+    url: &str,
+    date_after: Option<&str>,
+) -> Result<PlaylistInfo, String> {
+    fetch_playlist(ytdlp, url, date_after, None, None, None)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Entries fetched per yt-dlp call. One call for a 5000-item playlist
+/// runs for minutes with nothing to show; pages let the UI list (and
+/// download) the first entries while the rest load.
+const PLAYLIST_PAGE_SIZE: usize = 500;
+/// Stop after this many pages (100,000 entries).
+const MAX_PLAYLIST_PAGES: usize = 200;
+
+/// A page of a playlist fetched for the UI.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PlaylistInfoProgress<'a> {
+    url: &'a str,
+    id: &'a str,
+    title: &'a str,
+    /// The entries of this page only.
+    entries: &'a [PlaylistEntry],
+    /// Entries fetched so far, this page included.
+    fetched: usize,
+    /// Size of the playlist when the extractor reports it.
+    total: Option<usize>,
+    done: bool,
+}
+
+/// Fetch a playlist page by page. With `progress`, each page is emitted as
+/// `playlist-info-progress`; with `limit`, pages past that entry aren't
+/// fetched.
+async fn fetch_playlist(
+    ytdlp: &str,
+    url: &str,
+    date_after: Option<&str>,
+    cookies_browser: Option<&str>,
+    progress: Option<&AppHandle>,
+    limit: Option<usize>,
+) -> Result<PlaylistInfo, AppError> {
+    let mut extra_args: Vec<&str> = Vec::new();
+    if date_after.is_some() {
+        extra_args.extend(["--extractor-args", "youtubetab:approximate_date"]);
+    }
+    if let Some(browser) = cookies_browser {
+        extra_args.extend(["--cookies-from-browser", browser]);
+    }
+    let limit = limit.filter(|l| *l > 0);
+
+    let mut info: Option<PlaylistInfo> = None;
+    for page in 0..MAX_PLAYLIST_PAGES {
+        let start = page * PLAYLIST_PAGE_SIZE + 1;
+        let end = (start + PLAYLIST_PAGE_SIZE - 1).min(limit.unwrap_or(usize::MAX));
+        let (chunk, listed, total) = download::fetch_playlist_page(ytdlp, url, &extra_args, start, end).await?;
+        let done = listed < end + 1 - start || Some(end) == limit || total.is_some_and(|t| end >= t);
+
+        let all = info.get_or_insert_with(|| PlaylistInfo {
+            id: chunk.id.clone(),
+            title: chunk.title.clone(),
+            entries: Vec::new(),
+            entry_count: 0,
+        });
+        all.entries.extend(chunk.entries.iter().cloned());
+        all.entry_count = all.entries.len();
+        if let Some(app) = progress {
+            let _ = app.emit(
+                "playlist-info-progress",
+                PlaylistInfoProgress {
+                    url,
+                    id: &all.id,
+                    title: &all.title,
+                    entries: &chunk.entries,
+                    fetched: all.entry_count,
+                    total,
+                    done,
+                },
+            );
+        }
+        if done {
+            break;
+        }
+        log::debug!("[playlist] {}: {} entries so far", url, all.entry_count);
+    }
+    Ok(info.unwrap_or_else(|| PlaylistInfo {
+        id: String::new(),
+        title: "Playlist".to_string(),
+        entries: Vec::new(),
+        entry_count: 0,
+    }))
+}
+
+/// YouTube lists that only exist for the signed-in account, by name and
+/// list id.
+pub const ACCOUNT_PLAYLISTS: &[(&str, &str)] = &[("watch_later", "WL"), ("liked", "LL")];
+
+/// Whether `url` is one of [`ACCOUNT_PLAYLISTS`].
+fn is_account_playlist(url: &str) -> bool {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.query_pairs().find(|(k, _)| k == "list").map(|(_, v)| v.into_owned()))
+        .is_some_and(|list| ACCOUNT_PLAYLISTS.iter().any(|(_, id)| *id == list))
+}
+
+/// The browser to read cookies from for playlists behind a sign-in:
+/// `browser_cookies`, else `cookies_fallback_browser`.
+fn playlist_cookie_browser(db: &Arc<Mutex<Database>>) -> Option<String> {
+    let db_lock = db.lock().ok()?;
+    ["browser_cookies", "cookies_fallback_browser"].iter().find_map(|key| {
+        db_lock
+            .get_setting(key)
+            .unwrap_or(None)
+            .map(|b| b.trim().to_lowercase())
+            .filter(|b| !b.is_empty() && b != "none")
+    })
+}
+
+/// [`fetch_playlist_since`] for playlists that may need the account's
+/// cookies. Watch Later and Liked videos are always fetched with them;
+/// other playlists are retried with them when YouTube says the list is
+/// private or wants a sign-in.
+pub(crate) async fn fetch_playlist_signed_in(
+    db: &Arc<Mutex<Database>>,
+    ytdlp: &str,
+    url: &str,
+    date_after: Option<&str>,
+    progress: Option<&AppHandle>,
+    limit: Option<usize>,
+) -> Result<PlaylistInfo, AppError> {
+    let browser = playlist_cookie_browser(db);
+    if is_account_playlist(url) {
+        let browser = browser.ok_or_else(|| {
+            AppError::YtDlp(
+                "Watch Later and Liked videos need a sign-in: pick a browser to read cookies from in Settings".to_string(),
+            )
+        })?;
+        return fetch_playlist(ytdlp, url, date_after, Some(&browser), progress, limit).await;
+    }
+
+    let result = fetch_playlist(ytdlp, url, date_after, None, progress, limit).await;
+    let Some(browser) = browser else { return result };
+    match &result {
+        Err(e)
+            if e.code() == crate::error::ErrorCode::LoginRequired
+                || e.to_string().to_lowercase().contains("playlist does not exist")
+                || e.to_string().to_lowercase().contains("playlist is private") =>
+        {
+            log::info!("[playlist] {} needs a sign-in, retrying with {} cookies", url, browser);
+            fetch_playlist(ytdlp, url, date_after, Some(&browser), progress, limit).await
+        }
+        _ => result,
+    }
+}
+
+/// How long a fetched listing is reused for the same playlist, so
+/// `start_playlist_download` right after `get_playlist_info` doesn't list
+/// the playlist again.
+const PLAYLIST_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+struct CachedPlaylist {
+    url: String,
+    date_after: Option<String>,
+    fetched_at: Instant,
+    /// Every entry was fetched, not only the pages up to a limit.
+    complete: bool,
+    info: PlaylistInfo,
+}
+
+static PLAYLIST_CACHE: Mutex<Vec<CachedPlaylist>> = Mutex::new(Vec::new());
+
+/// [`fetch_playlist_signed_in`] through a short-lived cache, by URL and
+/// date filter. `refresh` skips the cache and replaces what's in it.
+/// Syncs don't go through here: they always need the current listing.
+pub(crate) async fn fetch_playlist_cached(
+    db: &Arc<Mutex<Database>>,
+    ytdlp: &str,
+    url: &str,
+    date_after: Option<&str>,
+    progress: Option<&AppHandle>,
+    limit: Option<usize>,
+    refresh: bool,
+) -> Result<PlaylistInfo, AppError> {
+    let limit = limit.filter(|l| *l > 0);
+    if !refresh {
+        if let Ok(mut cache) = PLAYLIST_CACHE.lock() {
+            cache.retain(|c| c.fetched_at.elapsed() < PLAYLIST_CACHE_TTL);
+            let hit = cache.iter().find(|c| {
+                c.url == url
+                    && c.date_after.as_deref() == date_after
+                    && (c.complete || limit.is_some_and(|l| l <= c.info.entry_count))
+            });
+            if let Some(hit) = hit {
+                log::info!("[playlist] Using the listing of {} fetched {}s ago", url, hit.fetched_at.elapsed().as_secs());
+                return Ok(hit.info.clone());
+            }
+        }
+    }
+
+    let info = fetch_playlist_signed_in(db, ytdlp, url, date_after, progress, limit).await?;
+    if let Ok(mut cache) = PLAYLIST_CACHE.lock() {
+        cache.retain(|c| !(c.url == url && c.date_after.as_deref() == date_after));
+        cache.push(CachedPlaylist {
+            url: url.to_string(),
+            date_after: date_after.map(String::from),
+            fetched_at: Instant::now(),
+            complete: limit.map_or(true, |l| info.entry_count < l),
+            info: info.clone(),
+        });
+    }
+    Ok(info)
+}
+
+/// Fetch the signed-in account's Watch Later or Liked videos (`name` is
+/// a key of [`ACCOUNT_PLAYLISTS`]) or a private playlist URL with the
+/// configured browser cookies. The result's `url` is what to pass to
+/// `start_playlist_download`.
+#[tauri::command]
+pub async fn get_account_playlist(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    name: String,
+    refresh: Option<bool>,
+) -> Result<serde_json::Value, AppError> {
+    let name = name.trim();
+    let url = match ACCOUNT_PLAYLISTS.iter().find(|(key, _)| *key == name) {
+        Some((_, list)) => format!("https://www.youtube.com/playlist?list={}", list),
+        None => {
+            validate_url(name)?;
+            name.to_string()
+        }
+    };
+    let ytdlp = download::get_ytdlp_path(&app);
+    let info = fetch_playlist_cached(db.inner(), &ytdlp, &url, None, Some(&app), None, refresh.unwrap_or(false)).await?;
+    log::info!("[playlist] Account playlist {} fetched: {} entries", url, info.entry_count);
+    let mut value = serde_json::to_value(&info)?;
+    value["url"] = serde_json::Value::String(url);
+    Ok(value)
+}
+
+/// Whether `entry` was uploaded on or after `after` (`YYYYMMDD`).
+/// Undated entries can't be proven newer, so they don't count.
+pub(crate) fn uploaded_since(entry: &PlaylistEntry, after: &str) -> bool {
+    entry
+        .upload_date
+        .as_deref()
+        .is_some_and(|date| date >= after)
+}
+
+/// Accept `YYYYMMDD` or `YYYY-MM-DD` and return `YYYYMMDD`.
+pub(crate) fn normalize_date_filter(raw: &str) -> Result<String, String> {
+    let compact: String = raw.chars().filter(|c| *c != '-').collect();
+    if compact.len() == 8
+        && compact.chars().all(|c| c.is_ascii_digit())
+        && chrono::NaiveDate::parse_from_str(&compact, "%Y%m%d").is_ok()
+    {
+        Ok(compact)
+    } else {
+        Err(format!("Invalid date '{}': expected YYYY-MM-DD", raw))
+    }
+}
+
+/// Get or create the `playlists` row for `url` so the sync scheduler can
+/// pick it up later. Returns the playlist id.
+pub(crate) fn ensure_playlist_record(
+    db: &Arc<Mutex<Database>>,
+    url: &str,
+    info: &PlaylistInfo,
+) -> Result<String, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    match db_lock.get_playlist_id_by_url(url).map_err(|e| e.to_string())? {
+        Some(id) => Ok(id),
+        None => {
+            let id = uuid::Uuid::new_v4().to_string();
+            db_lock
+                .insert_playlist(&id, url, &info.title, info.entry_count as i32)
+                .map_err(|e| e.to_string())?;
+            Ok(id)
+        }
+    }
+}
+
+/// Upper bound for the per-playlist concurrency cap.
+const MAX_PLAYLIST_CONCURRENCY: usize = 8;
+
+/// Insert queued download rows for every entry that isn't already
+/// downloaded or in flight, then start them in playlist order, at most
+/// `concurrency` at a time (defaults to the `playlist_concurrency` setting,
+/// which is 1 — sequential). When `playlist_id` is given each entry is also
+/// recorded in `playlist_items` and linked to its download. The new rows
+/// share a batch id, see `pause_batch` and friends.
+/// Returns the new download ids.
+pub(crate) async fn queue_playlist_entries(
+    app: &AppHandle,
+    db: &Arc<Mutex<Database>>,
+    dl: &Arc<tokio::sync::Mutex<DownloadManager>>,
+    playlist_id: Option<&str>,
+    entries: &[PlaylistEntry],
+    format: Option<String>,
+    concurrency: Option<usize>,
+) -> Result<Vec<String>, String> {
     let mut download_ids = Vec::new();
-    let mut entries_to_start: Vec<(String, String)> = Vec::new();
+    let mut entries_to_start: Vec<(String, String, Option<String>)> = Vec::new();
+    let batch_id = uuid::Uuid::new_v4().to_string();
 
     {
         let db_lock = db.lock().map_err(|e| e.to_string())?;
 
-        for entry in playlist_info.entries.iter() {
-            if entry.index < start || entry.index > end {
-                continue;
-            }
+        for entry in entries {
+            let thumb = entry.thumbnail.clone().unwrap_or_default();
+            let item_id = match playlist_id {
+                Some(pid) => Some(
+                    db_lock
+                        .upsert_playlist_item(pid, &entry.id, &entry.url, &entry.title, &thumb, entry.index as i64)
+                        .map_err(|e| e.to_string())?,
+                ),
+                None => None,
+            };
 
             // O(1) indexed lookup instead of O(n) in-memory scan
-            if db_lock.download_exists_by_url(&entry.url, "").unwrap_or(None).is_some() {
+            if db_lock.download_exists_by_url(&entry.url, "", "").unwrap_or(None).is_some() {
+                if let (Some(item_id), Ok(Some(existing))) =
+                    (item_id.as_deref(), db_lock.get_download_id_by_url(&entry.url))
+                {
+                    let _ = db_lock.link_playlist_item_download(item_id, &existing, false);
+                }
                 continue;
             }
 
             let id = uuid::Uuid::new_v4().to_string();
-            let thumb = entry.thumbnail.clone().unwrap_or_default();
             db_lock
                 .insert_download_with_source(&id, &entry.url, &entry.title, &thumb, "playlist")
                 .map_err(|e| e.to_string())?;
+            db_lock
+                .set_download_batch(&id, &batch_id)
+                .map_err(|e| e.to_string())?;
             db_lock
                 .update_download_status(&id, "queued")
                 .map_err(|e| e.to_string())?;
+            if let Some(item_id) = item_id.as_deref() {
+                db_lock
+                    .link_playlist_item_download(item_id, &id, true)
+                    .map_err(|e| e.to_string())?;
+            }
 
             download_ids.push(id.clone());
-            entries_to_start.push((id, entry.url.clone()));
-        }
-    }
-
-    // Limit concurrent playlist downloads to avoid spawning hundreds of yt-dlp processes
-    let concurrency = 3usize;
-    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(concurrency));
-
-    for (id, url) in entries_to_start {
-        let app_clone = app.clone();
-        let db_clone = db.inner().clone();
-        let dl_clone = dl.inner().clone();
-        let format_clone = format.clone();
-        let sem = semaphore.clone();
-        tokio::spawn(async move {
-            let _permit = sem.acquire().await;
-            let _ = crate::commands::start_download_existing(
-                app_clone,
-                db_clone,
-                dl_clone,
-                id,
+            entries_to_start.push((id, entry.url.clone(), format.clone()));
+        }
+    }
+
+    if entries_to_start.is_empty() {
+        return Ok(download_ids);
+    }
+
+    start_queued(app, db, dl, entries_to_start, concurrency)?;
+
+    Ok(download_ids)
+}
+
+/// Start already-queued downloads (id, URL, format) in order, at most
+/// `concurrency` at a time (defaults to the `playlist_concurrency`
+/// setting). Entries whose status changed while they waited are skipped.
+pub(crate) fn start_queued(
+    app: &AppHandle,
+    db: &Arc<Mutex<Database>>,
+    dl: &Arc<tokio::sync::Mutex<DownloadManager>>,
+    entries_to_start: Vec<(String, String, Option<String>)>,
+    concurrency: Option<usize>,
+) -> Result<(), String> {
+    let concurrency = match concurrency {
+        Some(n) => n,
+        None => {
+            let db_lock = db.lock().map_err(|e| e.to_string())?;
+            db_lock
+                .get_setting("playlist_concurrency")
+                .unwrap_or(None)
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .unwrap_or(1)
+        }
+    }
+    .clamp(1, MAX_PLAYLIST_CONCURRENCY);
+
+    // Entries stay "queued" in the download list and are fed through a
+    // semaphore; each permit is held until that download's task finishes,
+    // so at most `concurrency` yt-dlp processes run for these entries.
+    let app = app.clone();
+    let db = db.clone();
+    let dl = dl.clone();
+    tokio::spawn(async move {
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency));
+
+        for (id, url, format) in entries_to_start {
+            let Ok(permit) = semaphore.clone().acquire_owned().await else {
+                break;
+            };
+
+            // Skip entries the user cancelled/paused/deleted while waiting
+            let still_queued = db
+                .lock()
+                .ok()
+                .and_then(|db_lock| db_lock.get_download(&id).ok().flatten())
+                .map(|row| row["status"].as_str() == Some("queued"))
+                .unwrap_or(false);
+            if !still_queued {
+                continue;
+            }
+
+            match crate::commands::start_download_existing_tracked(
+                app.clone(),
+                db.clone(),
+                dl.clone(),
+                id.clone(),
                 url,
-                format_clone,
+                format,
             )
-            .await;
-        });
+            .await
+            {
+                Ok(handle) => {
+                    tokio::spawn(async move {
+                        let _ = handle.await;
+                        drop(permit);
+                    });
+                }
+                Err(e) => {
+                    log::warn!("[playlist] Failed to start {}: {}", id, e);
+                    if let Ok(db_lock) = db.lock() {
+                        let _ = db_lock.update_download_error(&id, &e);
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Called when a download finishes (either way). If it belongs to a playlist,
+/// refresh `playlists.downloaded_videos` and emit `playlist-progress` with
+/// the playlist's aggregate state so the UI can show a single progress bar.
+pub(crate) fn emit_playlist_progress(app: &AppHandle, db: &Arc<Mutex<Database>>, download_id: &str) {
+    let Ok(db_lock) = db.lock() else {
+        return;
+    };
+    let Some(playlist_id) = db_lock.get_playlist_id_for_download(download_id).unwrap_or(None) else {
+        return;
+    };
+    let Ok(items) = db_lock.get_playlist_items(&playlist_id) else {
+        return;
+    };
+
+    let tracked: Vec<&serde_json::Value> = items
+        .iter()
+        .filter(|item| !matches!(item["status"].as_str(), Some("removed") | Some("unavailable")))
+        .collect();
+    let total = tracked.len();
+    let completed = tracked
+        .iter()
+        .filter(|item| matches!(item["status"].as_str(), Some("completed") | Some("archived")))
+        .count();
+    let failed = tracked
+        .iter()
+        .filter(|item| item["status"].as_str() == Some("error"))
+        .count();
+    let in_flight: f64 = tracked
+        .iter()
+        .filter(|item| item["status"].as_str() == Some("downloading"))
+        .map(|item| item["progress"].as_f64().unwrap_or(0.0).clamp(0.0, 100.0) / 100.0)
+        .sum();
+    let percent = if total == 0 {
+        0.0
+    } else {
+        ((completed + failed) as f64 + in_flight) / total as f64 * 100.0
+    };
+    let current_title = tracked
+        .iter()
+        .find(|item| item["status"].as_str() == Some("downloading"))
+        .and_then(|item| item["title"].as_str())
+        .unwrap_or("");
+
+    let _ = db_lock.update_playlist_progress(&playlist_id, completed as i32);
+    drop(db_lock);
+
+    let _ = app.emit(
+        "playlist-progress",
+        serde_json::json!({
+            "playlistId": playlist_id,
+            "completed": completed,
+            "failed": failed,
+            "total": total,
+            "percent": percent,
+            "currentTitle": current_title,
+        }),
+    );
+}
+
+#[tauri::command]
+pub async fn get_playlists(
+    db: State<'_, Arc<Mutex<Database>>>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    let playlists = db_lock.get_playlists().map_err(|e| e.to_string())?;
+    // Channel mirrors are listed separately by `get_channel_mirrors`
+    Ok(playlists
+        .into_iter()
+        .filter(|p| p["kind"].as_str() != Some("channel"))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn update_playlist_settings(
+    db: State<'_, Arc<Mutex<Database>>>,
+    id: String,
+    naming_template: String,
+    auto_sync: bool,
+) -> Result<(), String> {
+    if download::render_playlist_template(&naming_template, 1, "").is_none() {
+        return Err("Invalid naming template: it must be a relative file name".to_string());
     }
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock
+        .update_playlist_settings(&id, &naming_template, auto_sync)
+        .map_err(|e| e.to_string())
+}
 
-    Ok(download_ids)
+/// Incrementally sync one playlist against its recorded `playlist_items`:
+/// queue entries that are new since the last sync or came back after being
+/// flagged, flag entries that have disappeared (`removed`) or turned
+/// private/deleted (`unavailable`), and refresh the playlist's counts.
+#[tauri::command]
+pub async fn sync_playlist(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
+    id: String,
+) -> Result<serde_json::Value, String> {
+    sync_playlist_inner(&app, db.inner(), dl.inner(), &id).await
+}
+
+pub(crate) async fn sync_playlist_inner(
+    app: &AppHandle,
+    db: &Arc<Mutex<Database>>,
+    dl: &Arc<tokio::sync::Mutex<DownloadManager>>,
+    playlist_id: &str,
+) -> Result<serde_json::Value, String> {
+    let (url, date_after) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let playlist = db_lock
+            .get_playlist(playlist_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Playlist not found: {}", playlist_id))?;
+        (
+            playlist["url"].as_str().unwrap_or_default().to_string(),
+            playlist["dateAfter"].as_str().unwrap_or_default().to_string(),
+        )
+    };
+    validate_url(&url)?;
+
+    let ytdlp = download::get_ytdlp_path(app);
+    let date_after = Some(date_after.as_str()).filter(|d| !d.is_empty());
+    let info = fetch_playlist_signed_in(db, &ytdlp, &url, date_after, None, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    reconcile_playlist(app, db, dl, playlist_id, &info).await
+}
+
+/// Diff freshly fetched `info` against the recorded items of a playlist and
+/// queue what's new or back. Entries of a channel mirror that are already in the
+/// mirror's yt-dlp archive are recorded as `archived` instead of queued;
+/// new entries older than the playlist's `date_after` are left out.
+pub(crate) async fn reconcile_playlist(
+    app: &AppHandle,
+    db: &Arc<Mutex<Database>>,
+    dl: &Arc<tokio::sync::Mutex<DownloadManager>>,
+    playlist_id: &str,
+    info: &PlaylistInfo,
+) -> Result<serde_json::Value, String> {
+    let (playlist, known_items, download_dir) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let playlist = db_lock
+            .get_playlist(playlist_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Playlist not found: {}", playlist_id))?;
+        let items = db_lock
+            .get_playlist_items(playlist_id)
+            .map_err(|e| e.to_string())?;
+        let download_dir = db_lock
+            .get_setting("download_path")
+            .map_err(|e| e.to_string())?
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| crate::commands::default_download_dir(app));
+        (playlist, items, download_dir)
+    };
+    let format = playlist["formatId"]
+        .as_str()
+        .filter(|f| !f.trim().is_empty())
+        .map(String::from);
+    let archived_ids = if playlist["kind"].as_str() == Some("channel") {
+        let title = if info.title.is_empty() {
+            playlist["title"].as_str().unwrap_or_default()
+        } else {
+            info.title.as_str()
+        };
+        let dir = crate::mirror_commands::resolve_mirror_dir(
+            &download_dir,
+            title,
+            playlist["outputDir"].as_str().unwrap_or_default(),
+        );
+        crate::mirror_commands::read_archive_ids(&dir)
+    } else {
+        std::collections::HashSet::new()
+    };
+
+    let known: std::collections::HashMap<String, &serde_json::Value> = known_items
+        .iter()
+        .filter_map(|item| Some((item["videoId"].as_str()?.to_string(), item)))
+        .collect();
+    let current: std::collections::HashSet<&str> =
+        info.entries.iter().map(|e| e.id.as_str()).collect();
+
+    let date_after = playlist["dateAfter"].as_str().unwrap_or_default();
+    let mut new_entries = Vec::new();
+    let mut returned = 0usize;
+    let mut unavailable = 0usize;
+    let mut archived = 0usize;
+    let mut too_old = 0usize;
+    {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        for entry in &info.entries {
+            if archived_ids.contains(&entry.id) && !known.contains_key(&entry.id) {
+                let thumb = entry.thumbnail.clone().unwrap_or_default();
+                if let Ok(item_id) = db_lock.upsert_playlist_item(
+                    playlist_id, &entry.id, &entry.url, &entry.title, &thumb, entry.index as i64,
+                ) {
+                    let _ = db_lock.update_playlist_item_status(&item_id, "archived");
+                }
+                archived += 1;
+            } else if is_unavailable_entry(entry) {
+                let thumb = entry.thumbnail.clone().unwrap_or_default();
+                if let Ok(item_id) = db_lock.upsert_playlist_item(
+                    playlist_id, &entry.id, &entry.url, &entry.title, &thumb, entry.index as i64,
+                ) {
+                    let _ = db_lock.update_playlist_item_status(&item_id, "unavailable");
+                }
+                unavailable += 1;
+            } else if let Some(item) = known.get(&entry.id) {
+                // Came back (re-added or made public again): queued with the
+                // new entries, or linked to its download if that still exists
+                if matches!(item["status"].as_str(), Some("removed") | Some("unavailable")) {
+                    new_entries.push(entry.clone());
+                    returned += 1;
+                }
+            } else if !date_after.is_empty() && !uploaded_since(entry, date_after) {
+                too_old += 1;
+            } else {
+                new_entries.push(entry.clone());
+            }
+        }
+    }
+
+    let mut removed = 0usize;
+    {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        for (video_id, item) in &known {
+            if !current.contains(video_id.as_str()) && item["status"].as_str() != Some("removed") {
+                if let Some(item_id) = item["id"].as_str() {
+                    let _ = db_lock.update_playlist_item_status(item_id, "removed");
+                    removed += 1;
+                }
+            }
+        }
+    }
+
+    let queued = queue_playlist_entries(app, db, dl, Some(playlist_id), &new_entries, format, None).await?;
+
+    {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let downloaded = db_lock
+            .get_playlist_items(playlist_id)
+            .map_err(|e| e.to_string())?
+            .iter()
+            .filter(|item| matches!(item["status"].as_str(), Some("completed") | Some("archived")))
+            .count();
+        db_lock
+            .update_playlist_sync(playlist_id, &info.title, info.entry_count as i32, downloaded as i32)
+            .map_err(|e| e.to_string())?;
+    }
+
+    log::info!(
+        "Synced playlist: {} - {} entries, {} new, {} back, {} removed, {} unavailable, {} archived, {} before cut-off",
+        info.title,
+        info.entry_count,
+        queued.len(),
+        returned,
+        removed,
+        unavailable,
+        archived,
+        too_old
+    );
+
+    Ok(serde_json::json!({
+        "playlistId": playlist_id,
+        "title": info.title,
+        "totalVideos": info.entry_count,
+        "newItems": queued.len(),
+        "downloadIds": queued,
+        "returned": returned,
+        "removed": removed,
+        "unavailable": unavailable,
+        "archived": archived,
+        "tooOld": too_old,
+    }))
+}
+
+/// Flat-playlist placeholders yt-dlp returns for videos that are gone.
+fn is_unavailable_entry(entry: &PlaylistEntry) -> bool {
+    matches!(entry.title.as_str(), "[Private video]" | "[Deleted video]" | "[Unavailable video]")
+}
+
+#[tauri::command]
+pub async fn get_playlist_items(
+    db: State<'_, Arc<Mutex<Database>>>,
+    playlist_id: String,
+) -> Result<Vec<serde_json::Value>, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.get_playlist_items(&playlist_id).map_err(|e| e.to_string())
+}
+
+/// Retry a single playlist entry: restarts its failed/cancelled download, or
+/// creates a fresh one if the entry was never queued (or its row was deleted).
+#[tauri::command]
+pub async fn retry_playlist_item(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
+    item_id: String,
+    format: Option<String>,
+) -> Result<String, String> {
+    let (download_id, url) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let item = db_lock
+            .get_playlist_item(&item_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Playlist item not found: {}", item_id))?;
+        let url = item["url"].as_str().unwrap_or_default().to_string();
+        let status = item["status"].as_str().unwrap_or_default();
+        if matches!(status, "downloading" | "queued" | "completed") && !item["downloadId"].is_null() {
+            return Err(format!("Playlist item is already {}", status));
+        }
+
+        let (download_id, created) = match item["downloadId"].as_str() {
+            Some(id) => (id.to_string(), false),
+            None => {
+                let id = uuid::Uuid::new_v4().to_string();
+                db_lock
+                    .insert_download_with_source(
+                        &id,
+                        &url,
+                        item["title"].as_str().unwrap_or_default(),
+                        item["thumbnail"].as_str().unwrap_or_default(),
+                        "playlist",
+                    )
+                    .map_err(|e| e.to_string())?;
+                (id, true)
+            }
+        };
+        db_lock
+            .update_download_status(&download_id, "queued")
+            .map_err(|e| e.to_string())?;
+        db_lock
+            .link_playlist_item_download(&item_id, &download_id, created)
+            .map_err(|e| e.to_string())?;
+        (download_id, url)
+    };
+
+    crate::commands::start_download_existing(
+        app,
+        db.inner().clone(),
+        dl.inner().clone(),
+        download_id.clone(),
+        url,
+        format,
+    )
+    .await?;
+    Ok(download_id)
+}
+
+/// Write an `.m3u8` playlist referencing the downloaded files in playlist
+/// order. Defaults to `<folder of the first file>/<playlist title>.m3u8`;
+/// entries in that folder are written as relative paths so the folder can
+/// be moved as a whole. Returns the path written.
+#[tauri::command]
+pub async fn export_playlist_m3u(
+    db: State<'_, Arc<Mutex<Database>>>,
+    id: String,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let (title, items, download_dir) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let playlist = db_lock
+            .get_playlist(&id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Playlist not found: {}", id))?;
+        let items = db_lock.get_playlist_items(&id).map_err(|e| e.to_string())?;
+        let download_dir = db_lock
+            .get_setting("download_path")
+            .map_err(|e| e.to_string())?
+            .filter(|v| !v.trim().is_empty());
+        (
+            playlist["title"].as_str().unwrap_or("Playlist").to_string(),
+            items,
+            download_dir,
+        )
+    };
+
+    let files: Vec<(String, std::path::PathBuf)> = items
+        .iter()
+        .filter(|item| item["status"].as_str() == Some("completed"))
+        .filter_map(|item| {
+            let path = crate::commands::resolve_download_file(
+                item["filePath"].as_str().filter(|p| !p.trim().is_empty()),
+                item["title"].as_str().filter(|t| !t.trim().is_empty()),
+                download_dir.as_deref(),
+            )?;
+            Some((item["title"].as_str().unwrap_or_default().to_string(), path))
+        })
+        .collect();
+
+    if files.is_empty() {
+        return Err("No downloaded files found for this playlist".to_string());
+    }
+
+    let m3u_path = match output_path.filter(|p| !p.trim().is_empty()) {
+        Some(p) => std::path::PathBuf::from(p),
+        None => files[0]
+            .1
+            .parent()
+            .map(|dir| dir.join(format!("{}.m3u8", download::sanitize_path_component(&title))))
+            .ok_or_else(|| "Cannot determine playlist folder".to_string())?,
+    };
+    let m3u_dir = m3u_path.parent().map(|p| p.to_path_buf());
+
+    let mut content = String::from("#EXTM3U\n");
+    content.push_str(&format!("#PLAYLIST:{}\n", title.replace(['\r', '\n'], " ")));
+    for (entry_title, path) in &files {
+        let reference = m3u_dir
+            .as_deref()
+            .and_then(|dir| path.strip_prefix(dir).ok())
+            .unwrap_or(path.as_path())
+            .to_string_lossy()
+            .to_string();
+        content.push_str(&format!(
+            "#EXTINF:-1,{}\n{}\n",
+            entry_title.replace(['\r', '\n'], " "),
+            reference
+        ));
+    }
+
+    std::fs::write(&m3u_path, content)
+        .map_err(|e| format!("Failed to write '{}': {}", m3u_path.display(), e))?;
+    log::info!("[export_playlist_m3u] Wrote {} entries to {}", files.len(), m3u_path.display());
+    Ok(m3u_path.to_string_lossy().to_string())
+}
+
+/// Delete a playlist. With `delete_downloads` the downloads it queued are
+/// cancelled and removed too (optionally with their files) instead of being
+/// left behind as orphaned rows. Downloads that existed before the playlist
+/// linked them, or that another playlist links to, are only unlinked.
+/// Files that couldn't be deleted (e.g. outside the download folders) are
+/// kept, and listed in the result, while their rows are still removed.
+#[tauri::command]
+pub async fn delete_playlist(
+    db: State<'_, Arc<Mutex<Database>>>,
+    dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
+    id: String,
+    delete_downloads: Option<bool>,
+    delete_files: Option<bool>,
+) -> Result<PlaylistDeletion, String> {
+    let delete_downloads = delete_downloads.unwrap_or(false);
+    let delete_files = delete_files.unwrap_or(false);
+
+    let download_ids: Vec<String> = if delete_downloads {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        db_lock
+            .get_playlist_owned_download_ids(&id)
+            .map_err(|e| e.to_string())?
+    } else {
+        Vec::new()
+    };
+
+    // Stop anything still running before its row disappears
+    {
+        let dm = dl.lock().await;
+        for download_id in &download_ids {
+            if let Some(active) = dm.active.get(download_id) {
+                let _ = active.cancel_token.send(true);
+            }
+        }
+    }
+    {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        for download_id in &download_ids {
+            let _ = db_lock.update_download_status(download_id, "cancelled");
+        }
+    }
+
+    let mut result = PlaylistDeletion::default();
+    for download_id in &download_ids {
+        match crate::commands::delete_download_record(db.inner(), download_id, delete_files) {
+            Ok(()) => result.removed += 1,
+            Err(e) => {
+                log::warn!("[delete_playlist] {}: {}", download_id, e);
+                let file_path = {
+                    let db_lock = db.lock().map_err(|e| e.to_string())?;
+                    db_lock
+                        .get_download(download_id)
+                        .unwrap_or(None)
+                        .and_then(|d| d["filePath"].as_str().map(String::from))
+                        .filter(|p| !p.is_empty())
+                };
+                // A missing file shouldn't keep the row around; one that is
+                // still there is reported as kept
+                if crate::commands::delete_download_record(db.inner(), download_id, false).is_ok() {
+                    result.removed += 1;
+                    if let Some(path) = file_path.filter(|p| std::path::Path::new(p).exists()) {
+                        result.kept_files.push(path);
+                    }
+                }
+            }
+        }
+    }
+
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.delete_playlist(&id).map_err(|e| e.to_string())?;
+    Ok(result)
+}
+
+/// What [`delete_playlist`] did with the playlist's downloads.
+#[derive(Debug, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistDeletion {
+    /// Downloads removed.
+    removed: u32,
+    /// Files that were asked to be deleted but are still on disk.
+    kept_files: Vec<String>,
+}
+
+// ────────────────────────────────── Playlist Scheduler ──────────────────────────────────
+
+#[tauri::command]
+pub async fn set_playlist_sync_interval(
+    scheduler: State<'_, Arc<tokio::sync::Mutex<PlaylistScheduler>>>,
+    minutes: u64,
+) -> Result<(), String> {
+    let scheduler = scheduler.lock().await;
+    scheduler.set_interval(minutes).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_playlist_sync_interval(
+    scheduler: State<'_, Arc<tokio::sync::Mutex<PlaylistScheduler>>>,
+) -> Result<u64, String> {
+    let scheduler = scheduler.lock().await;
+    Ok(scheduler.get_interval().await)
+}
+
+/// Run an auto-sync pass right now instead of waiting for the timer.
+#[tauri::command]
+pub async fn sync_auto_playlists(app: AppHandle) -> Result<u32, String> {
+    crate::playlist_scheduler::sync_auto_playlists(&app).await
 }
@@ -0,0 +1,152 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+use tauri::{AppHandle, Manager, Emitter};
+
+use crate::db::Database;
+use crate::download::DownloadManager;
+
+/// Background scheduler that re-fetches playlists marked `auto_sync` and
+/// queues any entries that haven't been downloaded yet. Mirrors
+/// `RssScheduler`: a `Notify` wakes the loop on interval changes/shutdown.
+pub struct PlaylistScheduler {
+    is_running: Arc<Mutex<bool>>,
+    interval_minutes: Arc<Mutex<u64>>,
+    wake_notify: Arc<Notify>,
+    abort_handle: Mutex<Option<tokio::task::AbortHandle>>,
+}
+
+impl PlaylistScheduler {
+    pub fn new() -> Self {
+        Self {
+            is_running: Arc::new(Mutex::new(false)),
+            interval_minutes: Arc::new(Mutex::new(360)), // Default 6 hours
+            wake_notify: Arc::new(Notify::new()),
+            abort_handle: Mutex::new(None),
+        }
+    }
+
+    /// Start the background playlist sync task
+    pub async fn start(&self, app: AppHandle) {
+        let mut is_running = self.is_running.lock().await;
+        if *is_running {
+            return;
+        }
+        *is_running = true;
+        drop(is_running);
+
+        let is_running_clone = self.is_running.clone();
+        let interval_minutes = self.interval_minutes.clone();
+        let wake_notify = self.wake_notify.clone();
+
+        let handle = tokio::spawn(async move {
+            loop {
+                if !*is_running_clone.lock().await {
+                    break;
+                }
+
+                let minutes = *interval_minutes.lock().await;
+                if minutes == 0 {
+                    wake_notify.notified().await;
+                    continue;
+                }
+
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(minutes * 60)) => {}
+                    _ = wake_notify.notified() => {
+                        continue;
+                    }
+                }
+
+                if !*is_running_clone.lock().await {
+                    break;
+                }
+
+                // Low battery: wait until charging or recharged
+                crate::download_guard::wait_for_battery().await;
+
+                if crate::quiet_hours::is_quiet_now() {
+                    log::info!("[PlaylistScheduler] Quiet hours, skipping sync");
+                    continue;
+                }
+
+                if let Err(e) = sync_auto_playlists(&app).await {
+                    log::error!("Playlist background sync failed: {}", e);
+                }
+            }
+            log::info!("[PlaylistScheduler] Background task stopped");
+        });
+
+        let mut abort = self.abort_handle.lock().await;
+        *abort = Some(handle.abort_handle());
+    }
+
+    /// Stop the background task (graceful shutdown)
+    pub async fn stop(&self) {
+        let mut is_running = self.is_running.lock().await;
+        *is_running = false;
+        drop(is_running);
+
+        self.wake_notify.notify_one();
+
+        let mut abort = self.abort_handle.lock().await;
+        if let Some(handle) = abort.take() {
+            handle.abort();
+        }
+    }
+
+    /// Set the sync interval in minutes (0 to disable).
+    pub async fn set_interval(&self, minutes: u64) {
+        let mut interval = self.interval_minutes.lock().await;
+        *interval = minutes;
+        drop(interval);
+        self.wake_notify.notify_one();
+    }
+
+    pub async fn get_interval(&self) -> u64 {
+        *self.interval_minutes.lock().await
+    }
+}
+
+/// Sync every playlist with `auto_sync` enabled. Returns the number of newly
+/// queued videos across all playlists.
+pub async fn sync_auto_playlists(app: &AppHandle) -> Result<u32, String> {
+    let db = app.state::<Arc<std::sync::Mutex<Database>>>().inner().clone();
+    let dl = app.state::<Arc<Mutex<DownloadManager>>>().inner().clone();
+
+    let playlists = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        db_lock.get_playlists().map_err(|e| e.to_string())?
+    };
+
+    let mut total_new = 0u32;
+
+    for playlist in playlists.iter().filter(|p| p["autoSync"].as_bool() == Some(true)) {
+        let playlist_id = playlist["id"].as_str().unwrap_or_default().to_string();
+
+        let summary = match crate::playlist_commands::sync_playlist_inner(app, &db, &dl, &playlist_id).await {
+            Ok(summary) => summary,
+            Err(e) => {
+                log::warn!("Failed to sync playlist {}: {}", playlist_id, e);
+                continue;
+            }
+        };
+
+        let new_items = summary["newItems"].as_u64().unwrap_or(0) as u32;
+        if new_items > 0 {
+            total_new += new_items;
+            let _ = app.emit("playlist-synced", &summary);
+        }
+    }
+
+    if total_new > 0 {
+        crate::notifications::notify(
+            app,
+            crate::notifications::NotifyEvent::NewFeedItems,
+            "Playlists Updated",
+            &format!("{} new videos queued from synced playlists", total_new),
+        );
+    }
+
+    Ok(total_new)
+}
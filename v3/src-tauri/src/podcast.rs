@@ -0,0 +1,374 @@
+//! Podcast feeds of downloaded audio.
+//!
+//! Completed audio downloads, either all of them or those of one RSS
+//! subscription, are listed as the episodes of an RSS 2.0 podcast feed
+//! (with the `itunes:` tags podcast apps expect). A feed can be exported to
+//! a file whose enclosures point at the local files, or shared on the LAN:
+//! the LAN side of [`StreamProxy`] then serves the feed and its episodes
+//! under pinned tokens, so an app like AntennaPod can subscribe to it.
+//!
+//! Shared feeds are kept in the `podcast_shares` setting (feed key → feed
+//! token, the key being a feed id or [`ALL_AUDIO`]), published again at
+//! startup and rewritten whenever a download completes.
+
+use std::collections::{BTreeMap, HashSet};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use sha1::{Digest, Sha1};
+use tauri::{AppHandle, Listener, Manager, State};
+
+use crate::db::Database;
+use crate::stream_proxy::{self, StreamProxy};
+
+/// Key of the feed holding every audio download.
+pub const ALL_AUDIO: &str = "all";
+const SHARES_SETTING: &str = "podcast_shares";
+
+/// One downloaded file, as a feed item.
+struct Episode {
+    id: String,
+    title: String,
+    link: String,
+    description: String,
+    thumbnail: String,
+    path: PathBuf,
+    size: u64,
+    published: String,
+}
+
+struct Podcast {
+    title: String,
+    link: String,
+    image: String,
+    episodes: Vec<Episode>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodcastShare {
+    /// Feed id, or `"all"`.
+    pub feed_id: String,
+    pub title: String,
+    pub url: String,
+    pub episodes: usize,
+}
+
+fn feed_key(feed_id: Option<&str>) -> String {
+    feed_id
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .unwrap_or(ALL_AUDIO)
+        .to_string()
+}
+
+/// RFC 2822 date of a download: its upload date, else when it was added.
+fn publish_date(row: &serde_json::Value) -> String {
+    let upload = row["uploadDate"]
+        .as_str()
+        .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .and_then(|d| d.and_hms_opt(0, 0, 0));
+    let created = row["createdAt"]
+        .as_str()
+        .and_then(|d| chrono::NaiveDateTime::parse_from_str(d, "%Y-%m-%d %H:%M:%S").ok());
+    upload
+        .or(created)
+        .map(|d| d.and_utc().to_rfc2822())
+        .unwrap_or_default()
+}
+
+fn load(db: &Database, key: &str) -> Result<Podcast, String> {
+    let (title, link, image, rows) = if key == ALL_AUDIO {
+        let rows = db.get_downloads().map_err(|e| e.to_string())?;
+        ("YTDL downloads".to_string(), String::new(), String::new(), rows)
+    } else {
+        let feed = db
+            .get_feeds()
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .find(|f| f.id == key)
+            .ok_or_else(|| format!("Feed not found: {}", key))?;
+        let rows = db.get_feed_downloads(key).map_err(|e| e.to_string())?;
+        let title = if feed.channel_name.is_empty() { feed.title } else { feed.channel_name };
+        (title, feed.url, feed.channel_avatar, rows)
+    };
+
+    let episodes = rows
+        .iter()
+        .filter(|row| row["status"].as_str() == Some("completed"))
+        .filter_map(|row| {
+            let path = PathBuf::from(row["filePath"].as_str()?);
+            if !stream_proxy::content_type_for_path(&path).starts_with("audio/") {
+                return None;
+            }
+            let size = std::fs::metadata(&path).ok().filter(|m| m.is_file())?.len();
+            Some(Episode {
+                id: row["id"].as_str()?.to_string(),
+                title: row["title"].as_str().unwrap_or_default().to_string(),
+                link: row["url"].as_str().unwrap_or_default().to_string(),
+                description: row["description"].as_str().unwrap_or_default().to_string(),
+                thumbnail: row["thumbnail"].as_str().unwrap_or_default().to_string(),
+                published: publish_date(row),
+                path,
+                size,
+            })
+        })
+        .collect();
+    Ok(Podcast { title, link, image, episodes })
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// The feed XML, with each episode's enclosure at `enclosure(episode)`.
+fn render(podcast: &Podcast, enclosure: impl Fn(&Episode) -> String) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <rss version=\"2.0\" xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\">\n<channel>\n",
+    );
+    xml.push_str(&format!("<title>{}</title>\n", xml_escape(&podcast.title)));
+    xml.push_str(&format!("<link>{}</link>\n", xml_escape(&podcast.link)));
+    xml.push_str(&format!(
+        "<description>{}</description>\n",
+        xml_escape(&format!("Downloaded with YTDL: {}", podcast.title))
+    ));
+    xml.push_str(&format!("<itunes:author>{}</itunes:author>\n", xml_escape(&podcast.title)));
+    if !podcast.image.is_empty() {
+        xml.push_str(&format!("<itunes:image href=\"{}\"/>\n", xml_escape(&podcast.image)));
+    }
+    for episode in &podcast.episodes {
+        xml.push_str("<item>\n");
+        xml.push_str(&format!("<title>{}</title>\n", xml_escape(&episode.title)));
+        xml.push_str(&format!("<guid isPermaLink=\"false\">{}</guid>\n", xml_escape(&episode.id)));
+        if !episode.link.is_empty() {
+            xml.push_str(&format!("<link>{}</link>\n", xml_escape(&episode.link)));
+        }
+        if !episode.published.is_empty() {
+            xml.push_str(&format!("<pubDate>{}</pubDate>\n", episode.published));
+        }
+        if !episode.description.is_empty() {
+            xml.push_str(&format!("<description>{}</description>\n", xml_escape(&episode.description)));
+        }
+        if !episode.thumbnail.is_empty() {
+            xml.push_str(&format!("<itunes:image href=\"{}\"/>\n", xml_escape(&episode.thumbnail)));
+        }
+        xml.push_str(&format!(
+            "<enclosure url=\"{}\" length=\"{}\" type=\"{}\"/>\n",
+            xml_escape(&enclosure(episode)),
+            episode.size,
+            stream_proxy::content_type_for_path(&episode.path)
+        ));
+        xml.push_str("</item>\n");
+    }
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// Write a feed whose enclosures are `file://` URLs of the downloads.
+/// Returns the number of episodes.
+#[tauri::command]
+pub async fn export_podcast_feed(
+    db: State<'_, Arc<Mutex<Database>>>,
+    feed_id: Option<String>,
+    path: String,
+) -> Result<usize, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Choose a file to write the feed to".to_string());
+    }
+    let podcast = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        load(&db_lock, &feed_key(feed_id.as_deref()))?
+    };
+    let xml = render(&podcast, |episode| {
+        url::Url::from_file_path(&episode.path)
+            .map(String::from)
+            .unwrap_or_else(|_| episode.path.to_string_lossy().to_string())
+    });
+    if let Some(parent) = Path::new(path).parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(path, xml).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(podcast.episodes.len())
+}
+
+fn load_shares(db: &Database) -> BTreeMap<String, String> {
+    db.get_setting(SHARES_SETTING)
+        .unwrap_or(None)
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default()
+}
+
+fn save_shares(db: &Database, shares: &BTreeMap<String, String>) -> Result<(), String> {
+    let value = serde_json::to_string(shares).map_err(|e| e.to_string())?;
+    db.save_setting(SHARES_SETTING, &value).map_err(|e| e.to_string())
+}
+
+/// Token of an episode of the feed shared under `feed_token`; stable so
+/// podcast apps don't see the episode as new each time.
+fn episode_token(feed_token: &str, episode_id: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(feed_token.as_bytes());
+    hasher.update(b":");
+    hasher.update(episode_id.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Address other devices on the LAN can reach us on: that of the interface
+/// with the default route. No packets are sent.
+fn lan_ip() -> Result<IpAddr, String> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").map_err(|e| e.to_string())?;
+    socket
+        .connect(("192.0.2.1", 9))
+        .map_err(|_| "Not connected to a network".to_string())?;
+    Ok(socket.local_addr().map_err(|e| e.to_string())?.ip())
+}
+
+fn feeds_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?.join("podcasts");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+/// Publish every shared feed again and stop serving anything no longer in
+/// one. Returns the shares as published.
+async fn refresh(app: &AppHandle) -> Result<Vec<PodcastShare>, String> {
+    let db = app.state::<Arc<Mutex<Database>>>();
+    let proxy = app.state::<StreamProxy>();
+    let shares = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        load_shares(&db_lock)
+    };
+    let mut published = Vec::new();
+    let mut live: HashSet<String> = HashSet::new();
+    if !shares.is_empty() {
+        let ip = lan_ip()?;
+        let dir = feeds_dir(app)?;
+        for (key, token) in &shares {
+            let podcast = {
+                let db_lock = db.lock().map_err(|e| e.to_string())?;
+                match load(&db_lock, key) {
+                    Ok(podcast) => podcast,
+                    Err(e) => {
+                        log::warn!("[podcast] Not publishing {}: {}", key, e);
+                        continue;
+                    }
+                }
+            };
+            let mut urls = std::collections::HashMap::new();
+            for episode in &podcast.episodes {
+                let episode_token = episode_token(token, &episode.id);
+                proxy.pin_file(&episode_token, episode.path.clone())?;
+                urls.insert(episode.id.clone(), proxy.lan_url(&episode_token, ip).await?);
+                live.insert(episode_token);
+            }
+            let xml = render(&podcast, |episode| urls.get(&episode.id).cloned().unwrap_or_default());
+            let file = dir.join(format!("{}.xml", crate::download::sanitize_path_component(key)));
+            std::fs::write(&file, xml).map_err(|e| e.to_string())?;
+            proxy.pin_file(token, file)?;
+            live.insert(token.clone());
+            published.push(PodcastShare {
+                feed_id: key.clone(),
+                title: podcast.title,
+                url: proxy.lan_url(token, ip).await?,
+                episodes: podcast.episodes.len(),
+            });
+        }
+    }
+    proxy.unpin(|token| live.contains(token));
+    Ok(published)
+}
+
+/// Share a feed (all audio downloads when `feed_id` is empty) on the LAN
+/// and return the URL to subscribe to.
+#[tauri::command]
+pub async fn share_podcast_feed(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    feed_id: Option<String>,
+) -> Result<PodcastShare, String> {
+    let key = feed_key(feed_id.as_deref());
+    {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        load(&db_lock, &key)?;
+        let mut shares = load_shares(&db_lock);
+        shares
+            .entry(key.clone())
+            .or_insert_with(|| uuid::Uuid::new_v4().simple().to_string());
+        save_shares(&db_lock, &shares)?;
+    }
+    refresh(&app)
+        .await?
+        .into_iter()
+        .find(|share| share.feed_id == key)
+        .ok_or_else(|| "Failed to publish the podcast feed".to_string())
+}
+
+/// Stop sharing a feed. Its URL stops working, and sharing it again gives
+/// a new one.
+#[tauri::command]
+pub async fn unshare_podcast_feed(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    feed_id: Option<String>,
+) -> Result<(), String> {
+    let key = feed_key(feed_id.as_deref());
+    {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let mut shares = load_shares(&db_lock);
+        if shares.remove(&key).is_none() {
+            return Ok(());
+        }
+        save_shares(&db_lock, &shares)?;
+    }
+    if let Ok(dir) = feeds_dir(&app) {
+        let _ = std::fs::remove_file(dir.join(format!("{}.xml", crate::download::sanitize_path_component(&key))));
+    }
+    refresh(&app).await.map(|_| ())
+}
+
+#[tauri::command]
+pub async fn get_podcast_shares(app: AppHandle) -> Result<Vec<PodcastShare>, String> {
+    refresh(&app).await
+}
+
+/// Publish the shared feeds and keep them current as downloads complete.
+/// Call after the database and [`StreamProxy`] are managed.
+pub fn init(app: &tauri::App) {
+    let shared = {
+        let db = app.state::<Arc<Mutex<Database>>>();
+        let Ok(db) = db.lock() else { return };
+        !load_shares(&db).is_empty()
+    };
+    let handle = app.handle().clone();
+    app.listen_any("download-complete", move |_| {
+        let handle = handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let shared = {
+                let db = handle.state::<Arc<Mutex<Database>>>();
+                let Ok(db) = db.lock() else { return };
+                !load_shares(&db).is_empty()
+            };
+            if shared {
+                if let Err(e) = refresh(&handle).await {
+                    log::warn!("[podcast] Failed to update shared feeds: {}", e);
+                }
+            }
+        });
+    });
+    if shared {
+        let handle = app.handle().clone();
+        tauri::async_runtime::spawn(async move {
+            match refresh(&handle).await {
+                Ok(shares) => log::info!("[podcast] Sharing {} feed(s) on the LAN", shares.len()),
+                Err(e) => log::warn!("[podcast] Failed to publish shared feeds: {}", e),
+            }
+        });
+    }
+}
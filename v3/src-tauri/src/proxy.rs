@@ -0,0 +1,141 @@
+//! Outbound proxy configuration shared by every HTTP client and child
+//! process (yt-dlp, ffmpeg).
+//!
+//! By default the system proxy from `HTTP_PROXY` / `HTTPS_PROXY` /
+//! `ALL_PROXY` / `NO_PROXY` is used. The `proxy_url` setting overrides it,
+//! and turning `use_system_proxy` off makes every connection direct.
+
+use std::sync::RwLock;
+
+use crate::db::Database;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProxyMode {
+    /// Use the proxy environment variables, if any.
+    System,
+    /// Ignore the environment and connect directly.
+    Direct,
+    /// Send everything through this proxy (NO_PROXY still applies).
+    Custom(String),
+}
+
+static PROXY_MODE: RwLock<ProxyMode> = RwLock::new(ProxyMode::System);
+
+const PROXY_ENV_VARS: &[&str] = &[
+    "HTTP_PROXY", "http_proxy", "HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy",
+];
+
+pub fn current() -> ProxyMode {
+    PROXY_MODE.read().map(|m| m.clone()).unwrap_or(ProxyMode::System)
+}
+
+/// Reload the proxy mode from the `use_system_proxy` and `proxy_url`
+/// settings. Called at startup and whenever either setting changes.
+pub fn load_from_db(db: &Database) {
+    let setting = |key: &str| db.get_setting(key).unwrap_or(None).unwrap_or_default();
+    let custom = setting("proxy_url").trim().to_string();
+    let mode = if !custom.is_empty() {
+        ProxyMode::Custom(custom)
+    } else if setting("use_system_proxy") == "false" {
+        ProxyMode::Direct
+    } else {
+        ProxyMode::System
+    };
+    log::info!("[proxy] Using {}", describe(&mode));
+    if let Ok(mut current) = PROXY_MODE.write() {
+        *current = mode;
+    }
+}
+
+/// Check a `proxy_url` setting value. Only HTTP(S) proxies are accepted,
+/// the HTTP client has no SOCKS support.
+pub fn validate_proxy_url(value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(());
+    }
+    let parsed = url::Url::parse(value).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("Proxy URL must start with http:// or https://".to_string());
+    }
+    if parsed.host_str().is_none() {
+        return Err("Proxy URL has no host".to_string());
+    }
+    Ok(())
+}
+
+/// A `reqwest` client builder with the configured proxy applied. Every
+/// outbound client should start from this instead of `Client::builder()`.
+pub fn client_builder() -> reqwest::ClientBuilder {
+    let builder = reqwest::Client::builder();
+    match current() {
+        // reqwest reads the proxy environment variables by default
+        ProxyMode::System => builder,
+        ProxyMode::Direct => builder.no_proxy(),
+        ProxyMode::Custom(url) => match reqwest::Proxy::all(&url) {
+            Ok(proxy) => builder.proxy(proxy.no_proxy(reqwest::NoProxy::from_env())),
+            Err(e) => {
+                log::warn!("[proxy] Ignoring invalid custom proxy: {}", e);
+                builder
+            }
+        },
+    }
+}
+
+/// Apply the configured proxy to a child process through the standard
+/// proxy environment variables, which yt-dlp and ffmpeg both honor.
+pub fn apply_to_command(cmd: &mut tokio::process::Command) {
+    match current() {
+        ProxyMode::System => {}
+        ProxyMode::Direct => {
+            for var in PROXY_ENV_VARS {
+                cmd.env_remove(var);
+            }
+        }
+        ProxyMode::Custom(url) => {
+            for var in PROXY_ENV_VARS {
+                cmd.env(var, &url);
+            }
+        }
+    }
+}
+
+/// Proxy environment variables seen by the app, for the settings UI.
+pub fn system_proxy_env() -> serde_json::Value {
+    let get = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+    };
+    serde_json::json!({
+        "httpProxy": get(&["HTTP_PROXY", "http_proxy"]),
+        "httpsProxy": get(&["HTTPS_PROXY", "https_proxy"]),
+        "allProxy": get(&["ALL_PROXY", "all_proxy"]),
+        "noProxy": get(&["NO_PROXY", "no_proxy"]),
+    })
+}
+
+fn describe(mode: &ProxyMode) -> String {
+    match mode {
+        ProxyMode::System => "system proxy settings".to_string(),
+        ProxyMode::Direct => "direct connections (proxy disabled)".to_string(),
+        // The URL may carry credentials, keep it out of the log
+        ProxyMode::Custom(_) => "custom proxy".to_string(),
+    }
+}
+
+/// Report the active proxy mode and what the environment provides.
+#[tauri::command]
+pub async fn get_proxy_status() -> Result<serde_json::Value, String> {
+    let mode = current();
+    let (name, url) = match &mode {
+        ProxyMode::System => ("system", None),
+        ProxyMode::Direct => ("direct", None),
+        ProxyMode::Custom(url) => ("custom", Some(url.clone())),
+    };
+    Ok(serde_json::json!({
+        "mode": name,
+        "proxyUrl": url,
+        "environment": system_proxy_env(),
+    }))
+}
@@ -0,0 +1,131 @@
+//! Totals across the whole download queue.
+//!
+//! Running downloads report their progress here (see [`update`]), which
+//! gives the bytes left and the combined speed. Queued downloads have no
+//! size yet; they are counted at the average size of the running ones and
+//! the summary is marked `estimated`. While anything runs, the summary is
+//! emitted as `queue-progress` every [`EMIT_INTERVAL`], and once more when
+//! the queue empties.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::db::Database;
+use crate::download::DownloadProgress;
+use crate::speed_history::{parse_size, parse_speed};
+
+const EMIT_INTERVAL: Duration = Duration::from_secs(2);
+
+struct Running {
+    id: String,
+    /// 0-100.
+    progress: f64,
+    total_bytes: Option<f64>,
+    bytes_per_sec: f64,
+}
+
+static RUNNING: Mutex<Vec<Running>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSummary {
+    pub active: usize,
+    pub queued: usize,
+    pub remaining_bytes: f64,
+    pub bytes_per_sec: f64,
+    /// `None` while nothing is transferring.
+    pub eta_seconds: Option<f64>,
+    /// Queued downloads are included at an assumed size.
+    pub estimated: bool,
+}
+
+/// Note a progress update of a running download.
+pub fn update(progress: &DownloadProgress) {
+    let Ok(mut running) = RUNNING.lock() else { return };
+    let entry = match running.iter().position(|r| r.id == progress.id) {
+        Some(index) => &mut running[index],
+        None => {
+            running.push(Running {
+                id: progress.id.clone(),
+                progress: 0.0,
+                total_bytes: None,
+                bytes_per_sec: 0.0,
+            });
+            running.last_mut().expect("just pushed")
+        }
+    };
+    entry.progress = progress.progress.clamp(0.0, 100.0);
+    if let Some(total) = parse_size(&progress.total) {
+        entry.total_bytes = Some(total);
+    }
+    entry.bytes_per_sec = parse_speed(&progress.speed).unwrap_or(0.0);
+}
+
+/// Forget a download whose yt-dlp run has ended.
+pub fn remove(id: &str) {
+    if let Ok(mut running) = RUNNING.lock() {
+        running.retain(|r| r.id != id);
+    }
+}
+
+pub fn summarize(db: &Database) -> QueueSummary {
+    let queued = db.count_downloads_with_status("queued").unwrap_or(0) as usize;
+    let (active, remaining, speed, sizes): (usize, f64, f64, Vec<f64>) = match RUNNING.lock() {
+        Ok(running) => (
+            running.len(),
+            running
+                .iter()
+                .filter_map(|r| Some(r.total_bytes? * (100.0 - r.progress) / 100.0))
+                .sum(),
+            running.iter().map(|r| r.bytes_per_sec).sum(),
+            running.iter().filter_map(|r| r.total_bytes).collect(),
+        ),
+        Err(_) => (0, 0.0, 0.0, Vec::new()),
+    };
+
+    let estimated = queued > 0 && !sizes.is_empty();
+    let remaining = if estimated {
+        let average = sizes.iter().sum::<f64>() / sizes.len() as f64;
+        remaining + average * queued as f64
+    } else {
+        remaining
+    };
+    QueueSummary {
+        active,
+        queued,
+        remaining_bytes: remaining,
+        bytes_per_sec: speed,
+        eta_seconds: (speed > 0.0).then(|| remaining / speed),
+        estimated,
+    }
+}
+
+#[tauri::command]
+pub async fn get_queue_summary(db: tauri::State<'_, Arc<Mutex<Database>>>) -> Result<QueueSummary, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    Ok(summarize(&db_lock))
+}
+
+/// Emit `queue-progress` while downloads run.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut was_busy = false;
+        loop {
+            tokio::time::sleep(EMIT_INTERVAL).await;
+            let busy = RUNNING.lock().map(|r| !r.is_empty()).unwrap_or(false);
+            if !busy && !was_busy {
+                continue;
+            }
+            was_busy = busy;
+            let summary = {
+                let db = app.state::<Arc<Mutex<Database>>>();
+                let Ok(db_lock) = db.lock() else { continue };
+                summarize(&db_lock)
+            };
+            let _ = app.emit("queue-progress", &summary);
+        }
+    });
+}
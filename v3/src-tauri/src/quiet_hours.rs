@@ -0,0 +1,61 @@
+//! Quiet hours.
+//!
+//! Between `quiet_hours_start` and `quiet_hours_end` ("HH:MM", local time;
+//! either empty = off) new and queued downloads wait as paused and the RSS
+//! and playlist schedulers skip their checks. With
+//! `quiet_hours_pause_active` on, running downloads are paused as well.
+//! The window may wrap past midnight ("23:00" to "07:00"). Enforcement
+//! lives in the download guard ([`crate::download_guard`]), which resumes
+//! the held downloads once the window ends.
+
+use std::sync::Mutex;
+
+use chrono::Timelike;
+
+use crate::db::Database;
+
+struct Window {
+    /// Minutes after midnight, `None` when quiet hours are off.
+    start: Option<u32>,
+    end: Option<u32>,
+    pause_active: bool,
+}
+
+static WINDOW: Mutex<Window> = Mutex::new(Window { start: None, end: None, pause_active: false });
+
+/// Minutes after midnight for an "HH:MM" time.
+pub fn parse_time(value: &str) -> Option<u32> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    let (hours, minutes) = (hours.parse::<u32>().ok()?, minutes.parse::<u32>().ok()?);
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+/// Reload the window. Called at startup and whenever one of the settings
+/// changes.
+pub fn load_from_db(db: &Database) {
+    let time = |key: &str| db.get_setting(key).unwrap_or(None).and_then(|v| parse_time(&v));
+    let pause_active = db.get_setting("quiet_hours_pause_active").unwrap_or(None).as_deref() == Some("true");
+    if let Ok(mut window) = WINDOW.lock() {
+        window.start = time("quiet_hours_start");
+        window.end = time("quiet_hours_end");
+        window.pause_active = pause_active;
+    }
+}
+
+/// Whether the current local time falls inside the window.
+pub fn is_quiet_now() -> bool {
+    let Ok(window) = WINDOW.lock() else { return false };
+    let (Some(start), Some(end)) = (window.start, window.end) else { return false };
+    let now = chrono::Local::now();
+    let minute = now.hour() * 60 + now.minute();
+    if start <= end {
+        (start..end).contains(&minute)
+    } else {
+        minute >= start || minute < end
+    }
+}
+
+/// Whether running downloads are paused too, not only new ones held.
+pub fn pauses_active() -> bool {
+    WINDOW.lock().map(|w| w.pause_active).unwrap_or(false)
+}
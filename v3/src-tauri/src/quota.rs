@@ -0,0 +1,216 @@
+//! Data-usage quota.
+//!
+//! With `download_quota_gb` set (0 = off), the bytes each running download
+//! transfers are added up per local day in the `data_usage` table. Once the
+//! total for the current `download_quota_period` ("day", or "week" starting
+//! Monday) reaches the quota, the download guard
+//! ([`crate::download_guard`]) holds new and queued downloads as paused
+//! until the period resets.
+//! Running downloads finish. [`force_start_download`] starts a held
+//! download anyway.
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::db::Database;
+use crate::download::{DownloadManager, DownloadProgress};
+use crate::speed_history::parse_size;
+
+/// Values accepted for `download_quota_period`.
+pub const PERIODS: &[&str] = &["day", "week"];
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+struct Quota {
+    /// 0 = off.
+    limit_bytes: i64,
+    weekly: bool,
+}
+
+static QUOTA: Mutex<Quota> = Mutex::new(Quota { limit_bytes: 0, weekly: false });
+
+/// Bytes and percentage of the current file of each running download
+/// already counted. yt-dlp starts over at 0% for each format it fetches.
+static COUNTED: Mutex<Vec<(String, f64, f64)>> = Mutex::new(Vec::new());
+
+/// A file is taken to be finished and the next one started when the
+/// percentage jumps from above the first value to below the second.
+/// Smaller dips come from the estimated (`~`) size of fragmented
+/// downloads moving around.
+const NEXT_FILE_FROM: f64 = 90.0;
+const NEXT_FILE_TO: f64 = 5.0;
+
+/// Usage of the period starting on the given day, read once and then kept
+/// up to date by [`record`] so progress updates don't sum the table.
+static USED: Mutex<Option<(NaiveDate, i64)>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaStatus {
+    /// 0 when the quota is off.
+    pub limit_bytes: i64,
+    pub period: String,
+    pub used_bytes: i64,
+    pub exceeded: bool,
+    /// RFC 3339, start of the next period.
+    pub resets_at: String,
+}
+
+/// Reload the quota. Called at startup and whenever it changes.
+pub fn load_from_db(db: &Database) {
+    let gb = db
+        .get_setting("download_quota_gb")
+        .unwrap_or(None)
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .unwrap_or(0);
+    let weekly = db.get_setting("download_quota_period").unwrap_or(None).as_deref() == Some("week");
+    if let Ok(mut quota) = QUOTA.lock() {
+        quota.limit_bytes = (gb as f64 * GIB) as i64;
+        quota.weekly = weekly;
+    }
+    if let Ok(mut used) = USED.lock() {
+        *used = None;
+    }
+}
+
+/// First day of the current period.
+fn period_start() -> NaiveDate {
+    let today = Local::now().date_naive();
+    let weekly = QUOTA.lock().map(|q| q.weekly).unwrap_or(false);
+    if weekly {
+        today - Duration::days(today.weekday().num_days_from_monday() as i64)
+    } else {
+        today
+    }
+}
+
+fn next_period_start() -> NaiveDate {
+    let weekly = QUOTA.lock().map(|q| q.weekly).unwrap_or(false);
+    period_start() + Duration::days(if weekly { 7 } else { 1 })
+}
+
+pub fn used_bytes(db: &Database) -> i64 {
+    let start = period_start();
+    if let Ok(used) = USED.lock() {
+        if let Some((cached_start, bytes)) = *used {
+            if cached_start == start {
+                return bytes;
+            }
+        }
+    }
+    let bytes = db
+        .get_data_usage_since(&start.format("%Y-%m-%d").to_string())
+        .unwrap_or(0);
+    if let Ok(mut used) = USED.lock() {
+        *used = Some((start, bytes));
+    }
+    bytes
+}
+
+/// Whether the quota is on and used up for the current period.
+pub fn exceeded(db: &Database) -> bool {
+    let limit = QUOTA.lock().map(|q| q.limit_bytes).unwrap_or(0);
+    limit > 0 && used_bytes(db) >= limit
+}
+
+/// Count the bytes a progress update adds. Called for every update of a
+/// running download. The first update of a run only sets the baseline: a
+/// resumed download reports the part fetched before, which was counted
+/// then.
+pub fn record(db: &Database, progress: &DownloadProgress) {
+    let Some(total) = parse_size(&progress.total) else { return };
+    let percent = progress.progress.clamp(0.0, 100.0);
+    let done = total * percent / 100.0;
+    let added = {
+        let Ok(mut counted) = COUNTED.lock() else { return };
+        match counted.iter_mut().find(|(id, _, _)| *id == progress.id) {
+            Some((_, last, last_percent)) => {
+                let next_file = *last_percent > NEXT_FILE_FROM && percent < NEXT_FILE_TO;
+                let added = if next_file { done } else { (done - *last).max(0.0) };
+                *last = done;
+                *last_percent = percent;
+                added
+            }
+            None => {
+                counted.push((progress.id.clone(), done, percent));
+                0.0
+            }
+        }
+    };
+    if added < 1.0 {
+        return;
+    }
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    if let Err(e) = db.add_data_usage(&today, added as i64) {
+        log::warn!("[quota] Failed to record data usage: {}", e);
+        return;
+    }
+    if let Ok(mut used) = USED.lock() {
+        match *used {
+            Some((start, ref mut bytes)) if start == period_start() => *bytes += added as i64,
+            _ => *used = None,
+        }
+    }
+    if exceeded(db) {
+        crate::download_guard::set_over_quota();
+    }
+}
+
+/// Forget a download whose yt-dlp run has ended.
+pub fn finish(id: &str) {
+    if let Ok(mut counted) = COUNTED.lock() {
+        counted.retain(|(counted_id, _, _)| counted_id != id);
+    }
+}
+
+/// Quota, usage in the current period and when it resets.
+#[tauri::command]
+pub async fn get_quota_status(db: State<'_, Arc<Mutex<Database>>>) -> Result<QuotaStatus, String> {
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    let (limit_bytes, weekly) = {
+        let quota = QUOTA.lock().map_err(|e| e.to_string())?;
+        (quota.limit_bytes, quota.weekly)
+    };
+    let used_bytes = used_bytes(&db_lock);
+    let resets_at = next_period_start()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|t| t.and_local_timezone(Local).earliest())
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default();
+    Ok(QuotaStatus {
+        limit_bytes,
+        period: if weekly { "week" } else { "day" }.to_string(),
+        used_bytes,
+        exceeded: limit_bytes > 0 && used_bytes >= limit_bytes,
+        resets_at,
+    })
+}
+
+/// Start a download held by the quota now. Other conditions (Wi-Fi only,
+/// battery, quiet hours) still apply.
+#[tauri::command]
+pub async fn force_start_download(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    dl: State<'_, Arc<tokio::sync::Mutex<DownloadManager>>>,
+    id: String,
+) -> Result<(), String> {
+    let entry = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        db_lock
+            .get_download(&id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Download not found: {}", id))?
+    };
+    if !matches!(entry["status"].as_str(), Some("paused" | "queued")) {
+        return Err("Only paused or queued downloads can be started over the quota".to_string());
+    }
+    crate::download_guard::exempt_from_quota(&id);
+    let url = entry["url"].as_str().unwrap_or_default().to_string();
+    let format_id = entry["formatId"].as_str().filter(|s| !s.is_empty()).map(String::from);
+    log::info!("[quota] Starting {} over the quota", id);
+    crate::commands::start_download_existing(app, db.inner().clone(), dl.inner().clone(), id, url, format_id).await
+}
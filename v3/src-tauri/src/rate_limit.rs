@@ -0,0 +1,142 @@
+//! Throttling for yt-dlp metadata calls.
+//!
+//! Video info, playlist and feed listings and stream extraction all go
+//! through [`acquire`] before starting yt-dlp, which spaces them to at most
+//! `ytdlp_requests_per_minute` (0 = unlimited). When yt-dlp reports HTTP
+//! 429 ([`observe`]), every call is refused for `ytdlp_cooldown_minutes`,
+//! doubling on each further 429 up to eight times as long, so a
+//! large sync backs off instead of getting the IP banned. Downloads
+//! themselves are not throttled.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::db::Database;
+
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 30;
+pub const DEFAULT_COOLDOWN_MINUTES: u32 = 10;
+const MAX_COOLDOWN_FACTOR: u32 = 8;
+const WINDOW: Duration = Duration::from_secs(60);
+
+struct Limiter {
+    requests_per_minute: u32,
+    cooldown: Duration,
+    /// Start times of the calls in the last [`WINDOW`].
+    recent: VecDeque<Instant>,
+    cooldown_until: Option<Instant>,
+    /// 429s in a row without a successful call in between.
+    strikes: u32,
+}
+
+static LIMITER: Mutex<Limiter> = Mutex::new(Limiter {
+    requests_per_minute: DEFAULT_REQUESTS_PER_MINUTE,
+    cooldown: Duration::from_secs(DEFAULT_COOLDOWN_MINUTES as u64 * 60),
+    recent: VecDeque::new(),
+    cooldown_until: None,
+    strikes: 0,
+});
+
+/// Reload the limits from the `ytdlp_requests_per_minute` and
+/// `ytdlp_cooldown_minutes` settings. Called at startup and whenever
+/// either setting changes.
+pub fn load_from_db(db: &Database) {
+    let setting = |key: &str, default: u32| {
+        db.get_setting(key)
+            .unwrap_or(None)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(default)
+    };
+    let requests_per_minute = setting("ytdlp_requests_per_minute", DEFAULT_REQUESTS_PER_MINUTE);
+    let cooldown_minutes = setting("ytdlp_cooldown_minutes", DEFAULT_COOLDOWN_MINUTES);
+    if let Ok(mut limiter) = LIMITER.lock() {
+        limiter.requests_per_minute = requests_per_minute;
+        limiter.cooldown = Duration::from_secs(cooldown_minutes as u64 * 60);
+    }
+}
+
+/// Wait for a free slot. Fails right away while cooling down after a 429.
+pub async fn acquire() -> Result<(), String> {
+    loop {
+        let wait = {
+            let mut limiter = LIMITER.lock().map_err(|e| e.to_string())?;
+            let now = Instant::now();
+            if let Some(until) = limiter.cooldown_until {
+                if until > now {
+                    return Err(format!(
+                        "yt-dlp is paused for {} after YouTube answered Too Many Requests (HTTP 429)",
+                        format_remaining(until - now)
+                    ));
+                }
+                limiter.cooldown_until = None;
+            }
+            while limiter.recent.front().is_some_and(|t| now.duration_since(*t) >= WINDOW) {
+                limiter.recent.pop_front();
+            }
+            let limit = limiter.requests_per_minute as usize;
+            if limit == 0 || limiter.recent.len() < limit {
+                limiter.recent.push_back(now);
+                return Ok(());
+            }
+            // Full window: wait until the oldest call drops out of it
+            limiter.recent.front().map(|t| WINDOW - now.duration_since(*t)).unwrap_or_default()
+        };
+        log::debug!("[rate_limit] Waiting {:?} for a yt-dlp slot", wait);
+        tokio::time::sleep(wait + Duration::from_millis(50)).await;
+    }
+}
+
+/// Feed yt-dlp's error output back to the limiter after every call. Any
+/// answer other than a 429 ends the run of strikes.
+pub fn observe(stderr: &str) {
+    let Ok(mut limiter) = LIMITER.lock() else { return };
+    let lower = stderr.to_lowercase();
+    if !lower.contains("http error 429") && !lower.contains("too many requests") {
+        limiter.strikes = 0;
+        return;
+    }
+    limiter.strikes += 1;
+    let factor = 2u32.saturating_pow(limiter.strikes - 1).min(MAX_COOLDOWN_FACTOR);
+    let cooldown = limiter.cooldown * factor;
+    limiter.cooldown_until = Some(Instant::now() + cooldown);
+    log::warn!(
+        "[rate_limit] yt-dlp got HTTP 429, pausing metadata requests for {}",
+        format_remaining(cooldown)
+    );
+}
+
+fn format_remaining(duration: Duration) -> String {
+    let secs = duration.as_secs().max(1);
+    if secs < 60 {
+        format!("{}s", secs)
+    } else {
+        format!("{}m {}s", secs / 60, secs % 60)
+    }
+}
+
+/// Current limits and cool-down, for the settings UI.
+#[tauri::command]
+pub fn get_rate_limit_status() -> Result<serde_json::Value, String> {
+    let limiter = LIMITER.lock().map_err(|e| e.to_string())?;
+    let now = Instant::now();
+    let cooldown_secs = limiter
+        .cooldown_until
+        .filter(|until| *until > now)
+        .map(|until| (until - now).as_secs())
+        .unwrap_or(0);
+    let used = limiter.recent.iter().filter(|t| now.duration_since(**t) < WINDOW).count();
+    Ok(serde_json::json!({
+        "requestsPerMinute": limiter.requests_per_minute,
+        "requestsLastMinute": used,
+        "cooldownRemainingSecs": cooldown_secs,
+    }))
+}
+
+/// End a cool-down early.
+#[tauri::command]
+pub fn clear_rate_limit_cooldown() -> Result<(), String> {
+    let mut limiter = LIMITER.lock().map_err(|e| e.to_string())?;
+    limiter.cooldown_until = None;
+    limiter.strikes = 0;
+    Ok(())
+}
@@ -0,0 +1,183 @@
+//! Startup reconciliation.
+//!
+//! A crash or forced quit leaves rows claiming work that no process is
+//! doing anymore. Before anything starts, downloads stuck mid-transfer are
+//! marked `interrupted` (resumable), running transcripts are failed and
+//! their temp files removed. The summary is kept for `get_startup_report`
+//! since the webview isn't listening yet when this runs.
+//!
+//! Downloads still `queued` from the last session (a playlist that wasn't
+//! fed through yet, say) are started shortly after launch when
+//! `auto_start_download` is on, see [`resume_queue`].
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::db::Database;
+use crate::download::DownloadManager;
+
+/// Gives the network and battery checks and the webview time to start.
+const RESUME_DELAY: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupReport {
+    pub interrupted_downloads: usize,
+    pub failed_transcripts: usize,
+    pub removed_temp_files: usize,
+    /// Queued downloads started at launch, see [`resume_queue`].
+    pub resumed_downloads: Vec<ResumedDownload>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumedDownload {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+}
+
+pub struct StartupReportState(pub Mutex<StartupReport>);
+
+pub fn run(app: &tauri::App, db: &Database) {
+    let mut report = StartupReport::default();
+
+    // On Android downloads run in Termux and outlive the app; the poller
+    // picks them back up
+    #[cfg(desktop)]
+    match db.mark_interrupted_downloads() {
+        Ok(count) => report.interrupted_downloads = count,
+        Err(e) => log::warn!("[reconcile] Failed to mark interrupted downloads: {}", e),
+    }
+
+    match db.fail_stuck_transcripts() {
+        Ok(count) => report.failed_transcripts = count,
+        Err(e) => log::warn!("[reconcile] Failed to reset transcripts: {}", e),
+    }
+
+    if let Ok(temp_dir) = app.path().temp_dir() {
+        report.removed_temp_files = remove_transcribe_temp_files(&temp_dir);
+    }
+
+    if report.interrupted_downloads + report.failed_transcripts + report.removed_temp_files > 0 {
+        log::info!(
+            "[reconcile] {} interrupted downloads, {} failed transcripts, {} temp files removed",
+            report.interrupted_downloads,
+            report.failed_transcripts,
+            report.removed_temp_files
+        );
+    }
+    let _ = app.emit("startup-reconciled", &report);
+    app.manage(StartupReportState(Mutex::new(report)));
+}
+
+/// Audio extracted for transcription (`transcribe-<id>*`). Nothing else
+/// is transcribing yet, so all of them are leftovers.
+fn remove_transcribe_temp_files(temp_dir: &std::path::Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(temp_dir) else { return 0 };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let is_leftover = entry.file_name().to_string_lossy().starts_with("transcribe-")
+            && entry.file_type().map(|t| t.is_file()).unwrap_or(false);
+        if is_leftover && std::fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Start the downloads left `queued` by the last session, highest priority
+/// and then oldest first, at most `max_concurrent_downloads` at a time.
+/// Emits `queue-resumed` with the list. Does nothing with
+/// `auto_start_download` off.
+pub fn resume_queue(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(RESUME_DELAY).await;
+        let db = app.state::<Arc<Mutex<Database>>>().inner().clone();
+        let dl = app.state::<Arc<tokio::sync::Mutex<DownloadManager>>>().inner().clone();
+
+        let (mut queued, concurrency) = {
+            let Ok(db_lock) = db.lock() else { return };
+            let setting = |key: &str| db_lock.get_setting(key).unwrap_or(None).unwrap_or_default();
+            if setting("auto_start_download") == "false" {
+                return;
+            }
+            let queued: Vec<serde_json::Value> = db_lock
+                .get_downloads()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|d| d["status"].as_str() == Some("queued"))
+                .collect();
+            let concurrency = setting("max_concurrent_downloads").trim().parse::<usize>().unwrap_or(3).max(1);
+            (queued, concurrency)
+        };
+        if queued.is_empty() {
+            return;
+        }
+        queued.sort_by_key(|d| (-d["priority"].as_i64().unwrap_or(0), d["createdAt"].as_str().unwrap_or("").to_string()));
+
+        let resumed: Vec<ResumedDownload> = queued
+            .iter()
+            .map(|d| ResumedDownload {
+                id: d["id"].as_str().unwrap_or_default().to_string(),
+                url: d["url"].as_str().unwrap_or_default().to_string(),
+                title: d["title"].as_str().unwrap_or_default().to_string(),
+            })
+            .collect();
+        log::info!("[reconcile] Resuming {} queued downloads", resumed.len());
+        if let Some(state) = app.try_state::<StartupReportState>() {
+            if let Ok(mut report) = state.0.lock() {
+                report.resumed_downloads = resumed.clone();
+            }
+        }
+        let _ = app.emit("queue-resumed", &resumed);
+
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency));
+        for (download, row) in resumed.into_iter().zip(queued) {
+            let Ok(permit) = semaphore.clone().acquire_owned().await else { break };
+
+            // Skip downloads the user cancelled, paused or deleted meanwhile
+            let still_queued = db
+                .lock()
+                .ok()
+                .and_then(|db_lock| db_lock.get_download(&download.id).ok().flatten())
+                .is_some_and(|d| d["status"].as_str() == Some("queued"));
+            if !still_queued {
+                continue;
+            }
+
+            let format_id = row["formatId"].as_str().filter(|f| !f.is_empty()).map(String::from);
+            match crate::commands::start_download_existing_tracked(
+                app.clone(),
+                db.clone(),
+                dl.clone(),
+                download.id.clone(),
+                download.url,
+                format_id,
+            )
+            .await
+            {
+                Ok(handle) => {
+                    tokio::spawn(async move {
+                        let _ = handle.await;
+                        drop(permit);
+                    });
+                }
+                Err(e) => {
+                    log::warn!("[reconcile] Failed to resume {}: {}", download.id, e);
+                    if let Ok(db_lock) = db.lock() {
+                        let _ = db_lock.update_download_error(&download.id, &e);
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_startup_report(state: tauri::State<'_, StartupReportState>) -> Result<StartupReport, String> {
+    let report = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(report.clone())
+}
@@ -0,0 +1,648 @@
+//! Local HTTP API for browser extensions and scripts.
+//!
+//! When the `api_enabled` setting is on, a server listens on
+//! `127.0.0.1:<api_port>` and exposes the core download operations:
+//!
+//! | Method | Path                                   | |
+//! |--------|----------------------------------------|-|
+//! | GET    | `/api/health`                          | version, no token needed |
+//! | GET    | `/api/downloads?status=&limit=`        | list downloads |
+//! | POST   | `/api/downloads`                       | `{url, formatId?, mode?, cookiesFromBrowser?, cookieProfile?, section?, audioLanguages?}` → `{id}` |
+//! | GET    | `/api/downloads/<id>`                  | one download |
+//! | POST   | `/api/downloads/<id>/pause`            | also `resume`, `cancel`, `retry` |
+//! | GET    | `/api/feeds`                           | RSS feeds |
+//! | GET    | `/api/events`                          | WebSocket, see below |
+//!
+//! Every request except `/api/health` needs the `api_token` secret, as
+//! `Authorization: Bearer <token>` or, for WebSockets (browsers can't set
+//! headers on those), a `?token=` query parameter. Requests whose `Host` is
+//! not a loopback name are refused so DNS rebinding can't reach the API.
+//!
+//! `/api/events` mirrors the app's progress events as text frames of the
+//! form `{"event": "download-progress", "payload": {...}}`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use base64::Engine;
+use sha1::{Digest, Sha1};
+use tauri::{AppHandle, Listener, Manager, State};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc, watch};
+
+use crate::commands;
+use crate::db::Database;
+use crate::secrets;
+
+pub const DEFAULT_PORT: u16 = 9787;
+/// App events forwarded to `/api/events` subscribers.
+const MIRRORED_EVENTS: &[&str] = &[
+    "download-progress",
+    "download-complete",
+    "download-error",
+    "downloads-resumed",
+    "rss-updated",
+    "playlist-synced",
+    "playlist-progress",
+    "transcription-progress",
+];
+/// Upper bound on the request head we're willing to buffer.
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+const MAX_REQUEST_BODY: usize = 64 * 1024;
+/// Clients only send control frames; anything bigger is a protocol error.
+const MAX_WS_FRAME: u64 = 64 * 1024;
+const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The API server and the event feed behind `/api/events`. Managed as
+/// Tauri state; the listener runs only while the API is enabled.
+pub struct ApiServer {
+    running: tokio::sync::Mutex<Option<RunningServer>>,
+    token: Arc<RwLock<String>>,
+    events: broadcast::Sender<String>,
+}
+
+struct RunningServer {
+    port: u16,
+    shutdown: watch::Sender<bool>,
+}
+
+impl ApiServer {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(256);
+        Self {
+            running: tokio::sync::Mutex::new(None),
+            token: Arc::new(RwLock::new(String::new())),
+            events,
+        }
+    }
+
+    pub async fn port(&self) -> Option<u16> {
+        self.running.lock().await.as_ref().map(|s| s.port)
+    }
+
+    /// (Re)start the listener on `port` with `token`.
+    pub async fn start(&self, app: AppHandle, port: u16, token: String) -> Result<(), String> {
+        self.stop().await;
+        if let Ok(mut current) = self.token.write() {
+            *current = token;
+        }
+
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .await
+            .map_err(|e| format!("Failed to start API server on port {}: {}", port, e))?;
+        let (shutdown, mut shutdown_rx) = watch::channel(false);
+        let token = self.token.clone();
+        let events = self.events.clone();
+
+        log::info!("[remote_api] Listening on 127.0.0.1:{}", port);
+        tokio::spawn(async move {
+            loop {
+                let (socket, _) = tokio::select! {
+                    _ = shutdown_rx.changed() => break,
+                    conn = listener.accept() => match conn {
+                        Ok(conn) => conn,
+                        Err(e) => {
+                            log::warn!("[remote_api] accept failed: {}", e);
+                            continue;
+                        }
+                    },
+                };
+                let app = app.clone();
+                let token = token.clone();
+                let events = events.subscribe();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_connection(socket, &app, &token, events).await {
+                        log::debug!("[remote_api] connection ended: {}", e);
+                    }
+                });
+            }
+            log::info!("[remote_api] Stopped");
+        });
+
+        *self.running.lock().await = Some(RunningServer { port, shutdown });
+        Ok(())
+    }
+
+    pub async fn stop(&self) {
+        if let Some(server) = self.running.lock().await.take() {
+            let _ = server.shutdown.send(true);
+        }
+    }
+
+    fn set_token(&self, token: &str) {
+        if let Ok(mut current) = self.token.write() {
+            *current = token.to_string();
+        }
+    }
+}
+
+/// Forward app events to WebSocket subscribers and start the server if the
+/// API is enabled. Call after [`ApiServer`] and the database are managed.
+pub fn init(app: &tauri::App) {
+    let server = app.state::<ApiServer>();
+    for name in MIRRORED_EVENTS {
+        let events = server.events.clone();
+        app.listen_any(*name, move |event| {
+            // Nobody listening is the common case; the send error is expected
+            let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap_or_default();
+            let _ = events.send(serde_json::json!({ "event": name, "payload": payload }).to_string());
+        });
+    }
+
+    let db = app.state::<Arc<Mutex<Database>>>();
+    let port = {
+        let Ok(db) = db.lock() else { return };
+        let enabled = db.get_setting("api_enabled").unwrap_or(None).as_deref() == Some("true");
+        enabled.then(|| configured_port(&db))
+    };
+    let Some(port) = port else { return };
+    let token = match ensure_token(&db) {
+        Ok(token) => token,
+        Err(e) => {
+            log::warn!("[remote_api] Not starting, no API token: {}", e);
+            return;
+        }
+    };
+
+    let app = app.handle().clone();
+    tauri::async_runtime::spawn(async move {
+        let server = app.state::<ApiServer>();
+        if let Err(e) = server.start(app.clone(), port, token).await {
+            log::warn!("[remote_api] {}", e);
+        }
+    });
+}
+
+fn configured_port(db: &Database) -> u16 {
+    db.get_setting("api_port")
+        .unwrap_or(None)
+        .and_then(|p| p.trim().parse::<u16>().ok())
+        .filter(|p| *p >= 1024)
+        .unwrap_or(DEFAULT_PORT)
+}
+
+fn new_token() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+/// The stored API token, generating one on first use. Takes the database
+/// lock only around its own reads and writes, not the keyring calls.
+fn ensure_token(db: &Mutex<Database>) -> Result<String, String> {
+    let from_keyring = secrets::keyring_secret("api_token");
+    let token = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        secrets::secret_or_setting(&db, "api_token", from_keyring)
+    };
+    if !token.is_empty() {
+        return Ok(token);
+    }
+    let token = new_token();
+    let in_keyring = secrets::keyring_store("api_token", &token);
+    let db = db.lock().map_err(|e| e.to_string())?;
+    secrets::store_setting(&db, "api_token", &token, in_keyring)?;
+    Ok(token)
+}
+
+fn token_matches(given: &str, expected: &str) -> bool {
+    // Length leaks nothing useful (tokens are fixed-size); compare the rest
+    // without an early exit
+    !expected.is_empty()
+        && given.len() == expected.len()
+        && given.bytes().zip(expected.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.rsplit_once(':') {
+        Some((name, port)) if !name.ends_with(':') && port.chars().all(|c| c.is_ascii_digit()) => name,
+        _ => host,
+    };
+    matches!(name, "127.0.0.1" | "localhost" | "[::1]")
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+
+    fn token(&self) -> Option<&str> {
+        self.header("authorization")
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .or_else(|| self.query.get("token").map(String::as_str))
+            .map(str::trim)
+    }
+}
+
+async fn read_request(reader: &mut BufReader<TcpStream>) -> Result<Option<Request>, String> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await.map_err(|e| e.to_string())?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let target = parts.next().unwrap_or("").to_string();
+    if method.is_empty() {
+        return Ok(None);
+    }
+
+    let mut headers = HashMap::new();
+    let mut head_len = request_line.len();
+    loop {
+        let mut line = String::new();
+        let n = reader.read_line(&mut line).await.map_err(|e| e.to_string())?;
+        head_len += n;
+        if n == 0 || line == "\r\n" || line == "\n" {
+            break;
+        }
+        if head_len > MAX_REQUEST_HEAD {
+            return Err("Request head too large".to_string());
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    if length > MAX_REQUEST_BODY {
+        return Err("Request body too large".to_string());
+    }
+    let mut body = vec![0u8; length];
+    reader.read_exact(&mut body).await.map_err(|e| e.to_string())?;
+
+    let parsed = url::Url::parse(&format!("http://localhost{}", target)).map_err(|e| e.to_string())?;
+    Ok(Some(Request {
+        method,
+        path: parsed.path().trim_end_matches('/').to_string(),
+        query: parsed.query_pairs().into_owned().collect(),
+        headers,
+        body,
+    }))
+}
+
+async fn handle_connection(
+    socket: TcpStream,
+    app: &AppHandle,
+    token: &RwLock<String>,
+    events: broadcast::Receiver<String>,
+) -> Result<(), String> {
+    let mut reader = BufReader::new(socket);
+    let request = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut reader)).await {
+        Ok(Ok(Some(request))) => request,
+        Ok(Ok(None)) => return Ok(()),
+        Ok(Err(e)) => {
+            return write_json(reader.get_mut(), 400, &serde_json::json!({ "error": e })).await;
+        }
+        Err(_) => return Err("Timed out reading request".to_string()),
+    };
+
+    let socket = reader.get_mut();
+    if !request.header("host").map(is_loopback_host).unwrap_or(false) {
+        return write_json(socket, 403, &serde_json::json!({ "error": "Forbidden host" })).await;
+    }
+    if request.method == "OPTIONS" {
+        return write_response(socket, 204, "", &[]).await;
+    }
+    if request.path == "/api/health" {
+        let body = serde_json::json!({ "app": "ytdl", "version": env!("CARGO_PKG_VERSION") });
+        return write_json(socket, 200, &body).await;
+    }
+
+    let expected = token.read().map(|t| t.clone()).unwrap_or_default();
+    if !token_matches(request.token().unwrap_or(""), &expected) {
+        return write_json(socket, 401, &serde_json::json!({ "error": "Missing or invalid API token" })).await;
+    }
+
+    if request.path == "/api/events" {
+        let key = request
+            .header("sec-websocket-key")
+            .filter(|_| {
+                request
+                    .header("upgrade")
+                    .map(|u| u.eq_ignore_ascii_case("websocket"))
+                    .unwrap_or(false)
+            })
+            .map(str::to_string);
+        let Some(key) = key else {
+            return write_json(socket, 426, &serde_json::json!({ "error": "WebSocket upgrade required" })).await;
+        };
+        return serve_websocket(reader.into_inner(), &key, events).await;
+    }
+
+    let (status, body) = match route(app, &request).await {
+        Ok(body) => (200, body),
+        Err((status, message)) => (status, serde_json::json!({ "error": message })),
+    };
+    write_json(socket, status, &body).await
+}
+
+async fn route(app: &AppHandle, request: &Request) -> Result<serde_json::Value, (u16, String)> {
+    let segments: Vec<&str> = request.path.trim_start_matches('/').split('/').collect();
+    let bad_request = |e: String| (400, e);
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["api", "downloads"]) => {
+            let mut downloads = commands::get_downloads(app.state()).await.map_err(|e| (500, e))?;
+            if let Some(status) = request.query.get("status") {
+                downloads.retain(|d| d["status"].as_str() == Some(status.as_str()));
+            }
+            if let Some(limit) = request.query.get("limit").and_then(|l| l.parse::<usize>().ok()) {
+                downloads.truncate(limit);
+            }
+            Ok(serde_json::json!(downloads))
+        }
+        ("POST", ["api", "downloads"]) => {
+            let body: serde_json::Value = serde_json::from_slice(&request.body)
+                .map_err(|e| (400, format!("Invalid JSON body: {}", e)))?;
+            let url = body["url"]
+                .as_str()
+                .ok_or_else(|| (400, "Missing url".to_string()))?
+                .to_string();
+            let text = |key: &str| body[key].as_str().map(str::to_string).filter(|s| !s.is_empty());
+            if let Some(dir) = text("outputDir") {
+                let db = app.state::<Arc<Mutex<Database>>>();
+                let db = db.lock().map_err(|e| (500, e.to_string()))?;
+                commands::check_remote_output_dir(&db, dir.trim()).map_err(bad_request)?;
+            }
+            let id = commands::start_download(
+                app.clone(),
+                app.state(),
+                app.state(),
+                url,
+                text("formatId"),
+                text("mode"),
+                text("cookiesFromBrowser"),
+                None,
+                text("section"),
+                body["audioLanguages"].as_array().map(|languages| {
+                    languages.iter().filter_map(|l| l.as_str().map(String::from)).collect()
+                }),
+                body["concurrentFragments"].as_u64().map(|n| n.min(u32::MAX as u64) as u32),
+                text("outputDir"),
+                text("cookieProfile"),
+            )
+            .await
+            .map_err(|e| bad_request(e.to_string()))?;
+            Ok(serde_json::json!({ "id": id }))
+        }
+        ("GET", ["api", "downloads", id]) => {
+            let db = app.state::<Arc<Mutex<Database>>>();
+            let db = db.lock().map_err(|e| (500, e.to_string()))?;
+            db.get_download(id)
+                .map_err(|e| (500, e.to_string()))?
+                .ok_or_else(|| (404, format!("Download not found: {}", id)))
+        }
+        ("POST", ["api", "downloads", id, action]) => {
+            let id = id.to_string();
+            let result = match *action {
+                "pause" => commands::pause_download(app.state(), app.state(), id).await,
+                "resume" => commands::resume_download(app.clone(), app.state(), app.state(), id)
+                    .await
+                    .map_err(|e| e.to_string()),
+                "cancel" => commands::cancel_download(app.state(), app.state(), id).await,
+                "retry" => commands::retry_download(app.clone(), app.state(), app.state(), id)
+                    .await
+                    .map_err(|e| e.to_string()),
+                _ => return Err((404, format!("Unknown action '{}'", action))),
+            };
+            result.map_err(bad_request)?;
+            Ok(serde_json::json!({ "ok": true }))
+        }
+        ("GET", ["api", "feeds"]) => {
+            let feeds = commands::get_feeds(app.state()).await.map_err(|e| (500, e.to_string()))?;
+            Ok(serde_json::json!(feeds))
+        }
+        (_, ["api", ..]) => Err((404, format!("No route for {} {}", request.method, request.path))),
+        _ => Err((404, "Not found".to_string())),
+    }
+}
+
+async fn write_response(
+    socket: &mut TcpStream,
+    status: u16,
+    body: &str,
+    extra_headers: &[(&str, &str)],
+) -> Result<(), String> {
+    let reason = match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        426 => "Upgrade Required",
+        _ => "Internal Server Error",
+    };
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\ncontent-length: {}\r\naccess-control-allow-origin: *\r\naccess-control-allow-methods: GET, POST, OPTIONS\r\naccess-control-allow-headers: authorization, content-type\r\nconnection: close\r\n",
+        status,
+        reason,
+        body.len()
+    );
+    for (name, value) in extra_headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    socket.write_all(head.as_bytes()).await.map_err(|e| e.to_string())?;
+    socket.write_all(body.as_bytes()).await.map_err(|e| e.to_string())
+}
+
+async fn write_json(socket: &mut TcpStream, status: u16, body: &serde_json::Value) -> Result<(), String> {
+    write_response(socket, status, &body.to_string(), &[("content-type", "application/json")]).await
+}
+
+/// Complete the WebSocket handshake and push events until the client goes
+/// away. Incoming frames are only read for ping and close.
+async fn serve_websocket(
+    mut socket: TcpStream,
+    key: &str,
+    mut events: broadcast::Receiver<String>,
+) -> Result<(), String> {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(WS_GUID.as_bytes());
+    let accept = base64::engine::general_purpose::STANDARD.encode(hasher.finalize());
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nupgrade: websocket\r\nconnection: Upgrade\r\nsec-websocket-accept: {}\r\n\r\n",
+        accept
+    );
+    socket.write_all(response.as_bytes()).await.map_err(|e| e.to_string())?;
+
+    let (mut reader, mut writer) = socket.into_split();
+    // Frames are read on their own task: a partially read frame would be
+    // lost if the read were a `select!` branch that lost the race
+    let (control_tx, mut control_rx) = mpsc::channel::<(u8, Vec<u8>)>(8);
+    let read_task = tokio::spawn(async move {
+        while let Ok(frame) = read_frame(&mut reader).await {
+            if control_tx.send(frame).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let result = loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(text) => {
+                    if let Err(e) = write_frame(&mut writer, 0x1, text.as_bytes()).await {
+                        break Err(e);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    log::debug!("[remote_api] WebSocket client lagged, dropped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break Ok(()),
+            },
+            frame = control_rx.recv() => match frame {
+                Some((0x8, _)) | None => {
+                    let _ = write_frame(&mut writer, 0x8, &[]).await;
+                    break Ok(());
+                }
+                Some((0x9, payload)) => {
+                    if let Err(e) = write_frame(&mut writer, 0xA, &payload).await {
+                        break Err(e);
+                    }
+                }
+                Some(_) => {}
+            },
+        }
+    };
+    read_task.abort();
+    result
+}
+
+async fn read_frame(reader: &mut OwnedReadHalf) -> Result<(u8, Vec<u8>), String> {
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head).await.map_err(|e| e.to_string())?;
+    let opcode = head[0] & 0x0f;
+    let masked = head[1] & 0x80 != 0;
+    let mut len = (head[1] & 0x7f) as u64;
+    if len == 126 {
+        let mut ext = [0u8; 2];
+        reader.read_exact(&mut ext).await.map_err(|e| e.to_string())?;
+        len = u16::from_be_bytes(ext) as u64;
+    } else if len == 127 {
+        let mut ext = [0u8; 8];
+        reader.read_exact(&mut ext).await.map_err(|e| e.to_string())?;
+        len = u64::from_be_bytes(ext);
+    }
+    if len > MAX_WS_FRAME {
+        return Err("WebSocket frame too large".to_string());
+    }
+
+    let mut mask = [0u8; 4];
+    if masked {
+        reader.read_exact(&mut mask).await.map_err(|e| e.to_string())?;
+    }
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload).await.map_err(|e| e.to_string())?;
+    if masked {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+    }
+    Ok((opcode, payload))
+}
+
+async fn write_frame(writer: &mut OwnedWriteHalf, opcode: u8, payload: &[u8]) -> Result<(), String> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode);
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame).await.map_err(|e| e.to_string())
+}
+
+/// Whether the API is enabled/running, its address and token.
+#[tauri::command]
+pub async fn get_api_status(
+    db: State<'_, Arc<Mutex<Database>>>,
+    server: State<'_, ApiServer>,
+) -> Result<serde_json::Value, String> {
+    let from_keyring = secrets::keyring_secret("api_token");
+    let (enabled, port, token) = {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        let enabled = db.get_setting("api_enabled").map_err(|e| e.to_string())?.as_deref() == Some("true");
+        (enabled, configured_port(&db), secrets::secret_or_setting(&db, "api_token", from_keyring))
+    };
+    let running_port = server.port().await;
+    Ok(serde_json::json!({
+        "enabled": enabled,
+        "running": running_port.is_some(),
+        "port": running_port.unwrap_or(port),
+        "baseUrl": format!("http://127.0.0.1:{}/api", running_port.unwrap_or(port)),
+        "token": token,
+    }))
+}
+
+/// Turn the API on or off, optionally moving it to another port.
+#[tauri::command]
+pub async fn set_api_enabled(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    server: State<'_, ApiServer>,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<serde_json::Value, String> {
+    if let Some(port) = port {
+        if port < 1024 {
+            return Err("API port must be between 1024 and 65535".to_string());
+        }
+    }
+
+    let port = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        if let Some(port) = port {
+            db_lock.save_setting("api_port", &port.to_string()).map_err(|e| e.to_string())?;
+        }
+        db_lock
+            .save_setting("api_enabled", &enabled.to_string())
+            .map_err(|e| e.to_string())?;
+        configured_port(&db_lock)
+    };
+    let token = if enabled { ensure_token(&db)? } else { String::new() };
+
+    if enabled {
+        server.start(app.clone(), port, token).await?;
+    } else {
+        server.stop().await;
+    }
+    get_api_status(db, server).await
+}
+
+/// Replace the API token. Clients using the old one are rejected from the
+/// next request on.
+#[tauri::command]
+pub async fn regenerate_api_token(
+    db: State<'_, Arc<Mutex<Database>>>,
+    server: State<'_, ApiServer>,
+) -> Result<String, String> {
+    let token = new_token();
+    let in_keyring = secrets::keyring_store("api_token", &token);
+    {
+        let db = db.lock().map_err(|e| e.to_string())?;
+        secrets::store_setting(&db, "api_token", &token, in_keyring)?;
+    }
+    server.set_token(&token);
+    Ok(token)
+}
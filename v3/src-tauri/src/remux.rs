@@ -0,0 +1,245 @@
+//! Getting downloads into MP4 when the picked formats don't merge into it.
+//!
+//! yt-dlp only merges into MP4 when the formats' file types match (a WebM
+//! video and an M4A audio don't), so it is given [`MERGE_OUTPUT_FORMAT`]
+//! and falls back to MKV, reporting what it picked through
+//! [`PRINT_TEMPLATE`] before anything is downloaded. An MKV whose codecs
+//! MP4 can hold is remuxed afterwards, which is quick and lossless. When a
+//! codec doesn't fit, the `merge_policy` setting decides:
+//!
+//! - `reencode` converts the streams that don't fit, reported with the
+//!   `converting` status and ffmpeg's progress;
+//! - `mkv` keeps the MKV;
+//! - `abort` stops the download before anything is fetched.
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::db::Database;
+use crate::download::{self, DownloadProgress};
+use crate::error::{AppError, AppResult};
+
+pub const POLICIES: &[&str] = &["reencode", "mkv", "abort"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    Reencode,
+    Mkv,
+    Abort,
+}
+
+static POLICY: RwLock<MergePolicy> = RwLock::new(MergePolicy::Reencode);
+
+/// Reload the policy from the `merge_policy` setting. Called at startup
+/// and whenever the setting changes.
+pub fn load_from_db(db: &Database) {
+    let policy = match db.get_setting("merge_policy").unwrap_or(None).as_deref() {
+        Some("mkv") => MergePolicy::Mkv,
+        Some("abort") => MergePolicy::Abort,
+        _ => MergePolicy::Reencode,
+    };
+    if let Ok(mut current) = POLICY.write() {
+        *current = policy;
+    }
+}
+
+pub fn current() -> MergePolicy {
+    POLICY.read().map(|p| *p).unwrap_or(MergePolicy::Reencode)
+}
+
+/// `--merge-output-format`: MP4, or MKV when the formats don't fit it.
+pub const MERGE_OUTPUT_FORMAT: &str = "mp4/mkv";
+
+/// `--print` run once the formats are picked, parsed by [`MergePlan::parse`].
+pub const PRINT_TEMPLATE: &str =
+    "video:ytdl-merge %(ext)s %(requested_formats.1.ext|-)s %(duration|0)s %(vcodec|none)s %(acodec|none)s";
+
+/// Whether the user's own flags already decide the container, in which
+/// case none of this applies.
+pub fn overridden_by(extra_args: &[String]) -> bool {
+    extra_args.iter().any(|arg| {
+        ["--merge-output-format", "--remux-video", "--recode-video"]
+            .iter()
+            .any(|flag| arg.starts_with(flag))
+    })
+}
+
+fn mp4_video(codec: &str) -> bool {
+    let codec = codec.to_lowercase();
+    codec == "none"
+        || ["avc1", "avc3", "h264", "hev1", "hvc1", "h265", "av01", "vp09", "vp9", "mp4v"]
+            .iter()
+            .any(|c| codec.starts_with(c))
+}
+
+fn mp4_audio(codec: &str) -> bool {
+    let codec = codec.to_lowercase();
+    codec == "none"
+        || ["mp4a", "aac", "opus", "mp3", "ac-3", "ac3", "ec-3", "eac3", "flac", "alac"]
+            .iter()
+            .any(|c| codec.starts_with(c))
+}
+
+/// What yt-dlp picked, from the [`PRINT_TEMPLATE`] line.
+#[derive(Debug, Clone)]
+pub struct MergePlan {
+    /// Separate formats merged into MKV instead of MP4.
+    pub fallback: bool,
+    /// Seconds, 0 when unknown.
+    pub duration: f64,
+    pub copy_video: bool,
+    pub copy_audio: bool,
+}
+
+impl MergePlan {
+    /// `None` for any other line.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.trim().strip_prefix("ytdl-merge ")?.split(' ');
+        let ext = fields.next()?;
+        let merged = fields.next()? != "-";
+        let duration = fields.next()?.parse().unwrap_or(0.0);
+        let vcodec = fields.next().unwrap_or("none");
+        let acodec = fields.next().unwrap_or("none");
+        Some(Self {
+            fallback: merged && ext == "mkv",
+            duration,
+            copy_video: mp4_video(vcodec),
+            copy_audio: mp4_audio(acodec),
+        })
+    }
+
+    /// Whether getting it into MP4 means re-encoding a stream.
+    pub fn needs_reencode(&self) -> bool {
+        self.fallback && !(self.copy_video && self.copy_audio)
+    }
+
+    /// Whether the MKV yt-dlp produces should be turned into an MP4.
+    pub fn wants_mp4(&self, policy: MergePolicy) -> bool {
+        self.fallback && (!self.needs_reencode() || policy == MergePolicy::Reencode)
+    }
+}
+
+/// Seconds from an `out_time_us=` line of ffmpeg's `-progress` output.
+fn progress_seconds(line: &str) -> Option<f64> {
+    let micros: f64 = line.strip_prefix("out_time_us=")?.trim().parse().ok()?;
+    Some(micros / 1_000_000.0)
+}
+
+/// Turn the MKV at `input` into an MP4 next to it, copying what fits and
+/// re-encoding the rest, then remove the MKV. Progress is sent with the
+/// `converting` status, or `merging` for a plain remux. Returns the path
+/// of the MP4.
+pub async fn to_mp4(
+    ffmpeg: &str,
+    input: &str,
+    plan: &MergePlan,
+    id: &str,
+    progress_tx: &tokio::sync::mpsc::Sender<DownloadProgress>,
+    mut cancel_rx: tokio::sync::watch::Receiver<bool>,
+) -> AppResult<String> {
+    let source = Path::new(input);
+    let target: PathBuf = source.with_extension("mp4");
+    let status = if plan.needs_reencode() { "converting" } else { "merging" };
+    log::info!(
+        "[remux] {}: {} into MP4 (video {}, audio {})",
+        id,
+        if plan.needs_reencode() { "re-encoding" } else { "remuxing" },
+        if plan.copy_video { "copied" } else { "re-encoded" },
+        if plan.copy_audio { "copied" } else { "re-encoded" },
+    );
+
+    let mut cmd = download::create_hidden_command(ffmpeg);
+    cmd.args(["-y", "-v", "error", "-nostats", "-progress", "pipe:1", "-i"])
+        .arg(source)
+        // Attachments (an embedded MKV thumbnail) have no place in MP4
+        .args(["-map", "0:v", "-map", "0:a?", "-map", "0:s?", "-c:s", "mov_text"]);
+    if plan.copy_video {
+        cmd.args(["-c:v", "copy"]);
+    } else {
+        cmd.args(["-c:v", "libx264", "-crf", "20", "-preset", "veryfast"]);
+    }
+    if plan.copy_audio {
+        cmd.args(["-c:a", "copy"]);
+    } else {
+        cmd.args(["-c:a", "aac", "-b:a", "192k"]);
+    }
+    let mut child = cmd
+        .args(["-movflags", "+faststart"])
+        .arg(&target)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Download(format!("Failed to start ffmpeg: {}", e)))?;
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let reader = {
+        let progress_tx = progress_tx.clone();
+        let id = id.to_string();
+        let duration = plan.duration;
+        tokio::spawn(async move {
+            use tokio::io::{AsyncBufReadExt, BufReader};
+            let Some(stdout) = stdout else { return };
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let Some(seconds) = progress_seconds(&line) else { continue };
+                let progress = if duration > 0.0 { (seconds / duration * 100.0).clamp(0.0, 100.0) } else { 0.0 };
+                let _ = progress_tx
+                    .send(DownloadProgress {
+                        id: id.clone(),
+                        progress,
+                        speed: String::new(),
+                        eta: String::new(),
+                        total: String::new(),
+                        status: status.to_string(),
+                    })
+                    .await;
+            }
+        })
+    };
+    let errors = tokio::spawn(async move {
+        use tokio::io::AsyncReadExt;
+        let mut text = String::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_string(&mut text).await;
+        }
+        text
+    });
+
+    let cancelled = async {
+        while !*cancel_rx.borrow() {
+            if cancel_rx.changed().await.is_err() {
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    let result = tokio::select! {
+        status = child.wait() => status.map_err(|e| AppError::Download(format!("ffmpeg process error: {}", e))),
+        _ = cancelled => {
+            let _ = child.kill().await;
+            let _ = std::fs::remove_file(&target);
+            return Err(AppError::Download("Download cancelled".to_string()));
+        }
+    };
+    reader.abort();
+
+    match result {
+        Ok(status) if status.success() => {
+            let _ = std::fs::remove_file(source);
+            Ok(target.to_string_lossy().to_string())
+        }
+        Ok(status) => {
+            let _ = std::fs::remove_file(&target);
+            let stderr = errors.await.unwrap_or_default();
+            Err(AppError::Download(format!(
+                "Converting to MP4 failed ({}): {}",
+                status,
+                stderr.trim()
+            )))
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&target);
+            Err(e)
+        }
+    }
+}
@@ -4,6 +4,65 @@ use tauri::AppHandle;
 use crate::download;
 use crate::error::{AppError, AppResult};
 
+/// Which feed URLs may be added and fetched: the `feed_https_only`,
+/// `feed_allowed_hosts` and `feed_blocked_hosts` settings. A host entry
+/// also covers its subdomains; an empty allowlist allows every host.
+#[derive(Debug, Clone, Default)]
+pub struct FeedPolicy {
+    pub https_only: bool,
+    pub allowed_hosts: Vec<String>,
+    pub blocked_hosts: Vec<String>,
+}
+
+/// Split a comma, space or newline separated host list. `*.` prefixes are
+/// dropped since entries match subdomains anyway.
+pub fn parse_host_list(value: &str) -> Vec<String> {
+    value
+        .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .map(|h| h.trim().trim_start_matches("*.").trim_matches('.').to_lowercase())
+        .filter(|h| !h.is_empty())
+        .collect()
+}
+
+fn host_matches(host: &str, entry: &str) -> bool {
+    host == entry || host.ends_with(&format!(".{}", entry))
+}
+
+impl FeedPolicy {
+    pub fn from_settings(settings: &crate::settings::AppSettings) -> Self {
+        Self {
+            https_only: settings.feed_https_only,
+            allowed_hosts: parse_host_list(&settings.feed_allowed_hosts),
+            blocked_hosts: parse_host_list(&settings.feed_blocked_hosts),
+        }
+    }
+
+    pub fn load(db: &crate::db::Database) -> Self {
+        Self::from_settings(&crate::settings::AppSettings::load(db))
+    }
+
+    pub fn check(&self, url: &str) -> AppResult<()> {
+        let parsed = url::Url::parse(url.trim())
+            .map_err(|e| AppError::Rss(format!("Invalid feed URL '{}': {}", url, e)))?;
+        if self.https_only && parsed.scheme() != "https" {
+            return Err(AppError::Rss(format!("Feed URLs must use HTTPS: {}", url)));
+        }
+        let host = parsed
+            .host_str()
+            .map(|h| h.trim_end_matches('.').to_lowercase())
+            .unwrap_or_default();
+        if self.blocked_hosts.iter().any(|entry| host_matches(&host, entry)) {
+            return Err(AppError::Rss(format!("Feeds from {} are blocked", host)));
+        }
+        if !self.allowed_hosts.is_empty()
+            && !self.allowed_hosts.iter().any(|entry| host_matches(&host, entry))
+        {
+            return Err(AppError::Rss(format!("Feeds from {} are not in the allowed hosts", host)));
+        }
+        Ok(())
+    }
+}
+
 fn normalize_input_url(input: &str) -> String {
     let trimmed = input.trim();
     if trimmed.starts_with("http://") || trimmed.starts_with("https://") {
@@ -30,7 +89,7 @@ fn channel_id_from_channel_url(url: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
-fn extract_channel_id_from_feed_url(feed_url: &str) -> Option<String> {
+pub(crate) fn extract_channel_id_from_feed_url(feed_url: &str) -> Option<String> {
     if feed_url.contains("channel_id=") {
         feed_url
             .split("channel_id=")
@@ -45,9 +104,10 @@ fn extract_channel_id_from_feed_url(feed_url: &str) -> Option<String> {
 async fn resolve_youtube_channel_id(url: &str) -> AppResult<String> {
     use reqwest::header::CONTENT_TYPE;
 
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::client_builder()
         .user_agent("YTDL/3.0")
         .timeout(std::time::Duration::from_secs(20))
+        .redirect(crate::commands::ssrf_redirect_policy())
         .build()
         .map_err(|e| AppError::Rss(format!("HTTP client error: {}", e)))?;
 
@@ -118,6 +178,10 @@ pub struct RssItem {
     pub published_at: String,
     pub downloaded: bool,
     pub video_type: String,
+    /// Unix seconds a premiere or livestream that hasn't started is
+    /// scheduled for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_at: Option<i64>,
 }
 
 fn uploads_playlist_id(channel_id: &str) -> Option<String> {
@@ -139,6 +203,8 @@ fn upload_date_to_iso(upload_date: &str) -> String {
 }
 
 async fn run_ytdlp_json(ytdlp: &str, target_url: &str, playlist_end: &str) -> AppResult<serde_json::Value> {
+    crate::rate_limit::acquire().await.map_err(AppError::Rss)?;
+
     // On Android, yt-dlp can only run inside Termux via RUN_COMMAND Intent.
     #[cfg(target_os = "android")]
     {
@@ -149,6 +215,7 @@ async fn run_ytdlp_json(ytdlp: &str, target_url: &str, playlist_end: &str) -> Ap
     #[cfg(not(target_os = "android"))]
     {
         let output = download::create_hidden_command(ytdlp)
+            .args(crate::extractor_args::ytdlp_args())
             .args([
                 "-J",
                 "--flat-playlist",
@@ -163,6 +230,7 @@ async fn run_ytdlp_json(ytdlp: &str, target_url: &str, playlist_end: &str) -> Ap
             .await
             .map_err(|e| AppError::Rss(format!("Failed to execute yt-dlp: {}", e)))?;
 
+        crate::rate_limit::observe(&String::from_utf8_lossy(&output.stderr));
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
             return Err(AppError::Rss(format!("yt-dlp failed: {}", stderr)));
@@ -190,9 +258,14 @@ async fn run_ytdlp_json_termux(target_url: &str, playlist_end: &str) -> AppResul
 
     // Shell-escape the URL
     let escaped_url = format!("'{}'", target_url.replace('\'', "'\\''"));
+    // Validated entries hold no quotes of their own
+    let extractor_args: String = crate::extractor_args::ytdlp_args()
+        .iter()
+        .map(|arg| format!(" '{}'", arg))
+        .collect();
     let command = format!(
-        "yt-dlp -J --flat-playlist --no-warnings --skip-download --ignore-errors --playlist-end {} {}",
-        playlist_end, escaped_url
+        "yt-dlp -J --flat-playlist --no-warnings --skip-download --ignore-errors --playlist-end {}{} {}",
+        playlist_end, extractor_args, escaped_url
     );
 
     match crate::android_bridge::run_termux_check(&command, &output_file) {
@@ -205,12 +278,14 @@ async fn run_ytdlp_json_termux(target_url: &str, playlist_end: &str) -> AppResul
                     // JSON response starts with { — wait until we have complete JSON
                     if !trimmed.is_empty() && trimmed.starts_with('{') && trimmed.ends_with('}') {
                         let _ = std::fs::remove_file(&output_file);
+                        crate::rate_limit::observe("");
                         return serde_json::from_str::<serde_json::Value>(trimmed)
                             .map_err(|e| AppError::Rss(format!("Invalid JSON from Termux: {}", e)));
                     }
                     // If it's an error message (not JSON), return it
                     if !trimmed.is_empty() && !trimmed.starts_with('{') && i > 10 {
                         let _ = std::fs::remove_file(&output_file);
+                        crate::rate_limit::observe(trimmed);
                         return Err(AppError::Rss(format!("yt-dlp error: {}", trimmed)));
                     }
                 }
@@ -239,107 +314,173 @@ fn entry_thumbnail(entry: &serde_json::Value, video_id: &str) -> String {
         .unwrap_or_else(|| format!("https://i.ytimg.com/vi/{}/mqdefault.jpg", video_id))
 }
 
-async fn fetch_youtube_uploads_items(app: &AppHandle, channel_id: &str) -> AppResult<Vec<RssItem>> {
+/// Tabs of a YouTube channel a feed can follow, see `feeds.tabs`.
+pub const CHANNEL_TABS: &[&str] = &["videos", "shorts", "streams"];
+pub const DEFAULT_CHANNEL_TABS: &str = "videos,shorts";
+
+/// The known tabs in a comma-separated list, in [`CHANNEL_TABS`] order.
+pub fn parse_channel_tabs(value: &str) -> Vec<&'static str> {
+    let wanted: Vec<String> = value.split(',').map(|t| t.trim().to_lowercase()).collect();
+    CHANNEL_TABS
+        .iter()
+        .copied()
+        .filter(|tab| wanted.iter().any(|w| w == tab))
+        .collect()
+}
+
+/// Check a feed's tab list before it is stored and return it normalized.
+pub fn validate_channel_tabs(value: &str) -> Result<String, String> {
+    if let Some(unknown) = value
+        .split(',')
+        .map(|t| t.trim().to_lowercase())
+        .find(|t| !t.is_empty() && !CHANNEL_TABS.contains(&t.as_str()))
+    {
+        return Err(format!("Unknown channel tab '{}': use {}", unknown, CHANNEL_TABS.join(", ")));
+    }
+    let tabs = parse_channel_tabs(value);
+    if tabs.is_empty() {
+        return Err("Pick at least one channel tab".to_string());
+    }
+    Ok(tabs.join(","))
+}
+
+/// The channel tab an item comes from, by its `video_type`.
+fn tab_of(video_type: &str) -> &'static str {
+    match video_type {
+        "short" => "shorts",
+        "stream" => "streams",
+        _ => "videos",
+    }
+}
+
+/// Drop items from tabs the feed doesn't follow.
+pub fn keep_channel_tabs(items: &mut Vec<RssItem>, tabs: &[&str]) {
+    items.retain(|item| tabs.contains(&tab_of(&item.video_type)));
+}
+
+fn tab_item(entry: &serde_json::Value, id: String, url: String, video_type: &str) -> RssItem {
+    RssItem {
+        id: id.clone(),
+        title: entry["title"].as_str().unwrap_or("Unknown").to_string(),
+        video_id: id.clone(),
+        url,
+        thumbnail: entry_thumbnail(entry, &id),
+        published_at: entry["upload_date"]
+            .as_str()
+            .map(upload_date_to_iso)
+            .unwrap_or_default(),
+        downloaded: false,
+        video_type: video_type.to_string(),
+        scheduled_at: if entry["live_status"].as_str() == Some("is_upcoming") {
+            entry["release_timestamp"].as_i64()
+        } else {
+            None
+        },
+    }
+}
+
+fn entry_id(entry: &serde_json::Value) -> Option<String> {
+    entry["id"].as_str().or_else(|| entry["url"].as_str()).map(String::from)
+}
+
+/// Recent items of the channel's `tabs`. The flag is false when one of
+/// the tabs couldn't be listed.
+async fn fetch_youtube_uploads_items(app: &AppHandle, channel_id: &str, tabs: &[&str]) -> AppResult<(Vec<RssItem>, bool)> {
     let ytdlp = download::get_ytdlp_path(app);
-    let shorts_url = format!("https://www.youtube.com/channel/{}/shorts", channel_id);
-    let videos_url = format!("https://www.youtube.com/channel/{}/videos", channel_id);
+    let tab_url = |tab: &str| format!("https://www.youtube.com/channel/{}/{}", channel_id, tab);
 
     // Limit to 50 most recent per tab — enough for regular RSS checks.
     // yt-dlp returns newest first, so this covers the latest uploads.
     let limit = "50";
 
     let mut short_ids = std::collections::HashSet::new();
+    let mut stream_ids = std::collections::HashSet::new();
     let mut all_items = Vec::new();
-
-    if let Ok(shorts_json) = run_ytdlp_json(&ytdlp, &shorts_url, limit).await {
-        if let Some(entries) = shorts_json["entries"].as_array() {
-            for entry in entries {
-                let id = match entry["id"].as_str().or_else(|| entry["url"].as_str()) {
-                    Some(v) => v.to_string(),
-                    None => continue,
-                };
-                short_ids.insert(id.clone());
-
-                all_items.push(RssItem {
-                    id: id.clone(),
-                    title: entry["title"].as_str().unwrap_or("Unknown").to_string(),
-                    video_id: id.clone(),
-                    url: format!("https://www.youtube.com/shorts/{}", id),
-                    thumbnail: entry_thumbnail(entry, &id),
-                    published_at: entry["upload_date"]
-                        .as_str()
-                        .map(upload_date_to_iso)
-                        .unwrap_or_default(),
-                    downloaded: false,
-                    video_type: "short".to_string(),
-                });
+    let mut complete = true;
+
+    if tabs.contains(&"shorts") {
+        match run_ytdlp_json(&ytdlp, &tab_url("shorts"), limit).await {
+            Ok(shorts_json) => {
+                for entry in shorts_json["entries"].as_array().into_iter().flatten() {
+                    let Some(id) = entry_id(entry) else { continue };
+                    short_ids.insert(id.clone());
+                    let url = format!("https://www.youtube.com/shorts/{}", id);
+                    all_items.push(tab_item(entry, id, url, "short"));
+                }
             }
+            Err(_) => complete = false,
         }
     }
 
-    let videos_json = match run_ytdlp_json(&ytdlp, &videos_url, limit).await {
-        Ok(json) => Ok(json),
-        Err(_) => {
-            if let Some(uploads_id) = uploads_playlist_id(channel_id) {
-                let playlist_url = format!("https://www.youtube.com/playlist?list={}", uploads_id);
-                run_ytdlp_json(&ytdlp, &playlist_url, limit).await
-            } else {
-                Err(AppError::Rss("No uploads playlist fallback available".to_string()))
+    if tabs.contains(&"streams") {
+        match run_ytdlp_json(&ytdlp, &tab_url("streams"), limit).await {
+            Ok(streams_json) => {
+                for entry in streams_json["entries"].as_array().into_iter().flatten() {
+                    let Some(id) = entry_id(entry) else { continue };
+                    stream_ids.insert(id.clone());
+                    let url = format!("https://www.youtube.com/watch?v={}", id);
+                    all_items.push(tab_item(entry, id, url, "stream"));
+                }
             }
+            Err(_) => complete = false,
         }
-    };
-
-    if let Ok(json) = videos_json {
-        if let Some(entries) = json["entries"].as_array() {
-            for entry in entries {
-                let id = match entry["id"].as_str().or_else(|| entry["url"].as_str()) {
-                    Some(v) => v.to_string(),
-                    None => continue,
-                };
-
-                let title = entry["title"].as_str().unwrap_or("Unknown").to_string();
-                let marked_short = short_ids.contains(&id)
-                    || title.to_lowercase().contains("#short")
-                    || title.to_lowercase().contains("#shorts")
-                    || entry["url"]
-                        .as_str()
-                        .map(|u| u.contains("/shorts/"))
-                        .unwrap_or(false);
+    }
 
-                let url = if marked_short {
-                    format!("https://www.youtube.com/shorts/{}", id)
+    if tabs.contains(&"videos") {
+        let videos_json = match run_ytdlp_json(&ytdlp, &tab_url("videos"), limit).await {
+            Ok(json) => Ok(json),
+            Err(_) => {
+                if let Some(uploads_id) = uploads_playlist_id(channel_id) {
+                    let playlist_url = format!("https://www.youtube.com/playlist?list={}", uploads_id);
+                    run_ytdlp_json(&ytdlp, &playlist_url, limit).await
                 } else {
-                    format!("https://www.youtube.com/watch?v={}", id)
-                };
-
-                all_items.push(RssItem {
-                    id: id.clone(),
-                    title,
-                    video_id: id.clone(),
-                    url,
-                    thumbnail: entry_thumbnail(entry, &id),
-                    published_at: entry["upload_date"]
-                        .as_str()
-                        .map(upload_date_to_iso)
-                        .unwrap_or_default(),
-                    downloaded: false,
-                    video_type: if marked_short {
-                        "short".to_string()
+                    Err(AppError::Rss("No uploads playlist fallback available".to_string()))
+                }
+            }
+        };
+
+        match videos_json {
+            Ok(json) => {
+                for entry in json["entries"].as_array().into_iter().flatten() {
+                    let Some(id) = entry_id(entry) else { continue };
+
+                    let title = entry["title"].as_str().unwrap_or("Unknown").to_lowercase();
+                    let marked_short = short_ids.contains(&id)
+                        || title.contains("#short")
+                        || title.contains("#shorts")
+                        || entry["url"]
+                            .as_str()
+                            .map(|u| u.contains("/shorts/"))
+                            .unwrap_or(false);
+                    // The uploads playlist fallback mixes in past streams
+                    let marked_stream = stream_ids.contains(&id)
+                        || matches!(entry["live_status"].as_str(), Some("is_live" | "was_live" | "is_upcoming"));
+
+                    let (url, video_type) = if marked_short {
+                        (format!("https://www.youtube.com/shorts/{}", id), "short")
+                    } else if marked_stream {
+                        (format!("https://www.youtube.com/watch?v={}", id), "stream")
                     } else {
-                        "video".to_string()
-                    },
-                });
+                        (format!("https://www.youtube.com/watch?v={}", id), "video")
+                    };
+                    all_items.push(tab_item(entry, id, url, video_type));
+                }
             }
+            Err(_) => complete = false,
         }
     }
 
     let mut seen = std::collections::HashSet::new();
     all_items.retain(|item| seen.insert(item.id.clone()));
+    keep_channel_tabs(&mut all_items, tabs);
 
-    Ok(all_items)
+    Ok((all_items, complete))
 }
 
-pub async fn fetch_feed_items_extended(app: &AppHandle, feed_url: &str) -> AppResult<(String, Vec<RssItem>)> {
+/// The feed's items, for YouTube channels from the channel `tabs` (a
+/// comma-separated list of [`CHANNEL_TABS`]) rather than the RSS feed
+/// alone, which holds only the last 15 uploads of every kind.
+pub async fn fetch_feed_items_extended(app: &AppHandle, feed_url: &str, tabs: &str) -> AppResult<(String, Vec<RssItem>)> {
     let (mut title, mut items) = match fetch_feed_items(feed_url).await {
         Ok((t, i)) => (t, i),
         Err(e) => {
@@ -349,8 +490,10 @@ pub async fn fetch_feed_items_extended(app: &AppHandle, feed_url: &str) -> AppRe
     };
 
     if looks_like_youtube_url(feed_url) && feed_url.contains("feeds/videos.xml") {
+        let tabs = parse_channel_tabs(tabs);
+        let tabs = if tabs.is_empty() { parse_channel_tabs(DEFAULT_CHANNEL_TABS) } else { tabs };
         if let Some(channel_id) = extract_channel_id_from_feed_url(feed_url) {
-            if let Ok(yt_items) = fetch_youtube_uploads_items(app, &channel_id).await {
+            if let Ok((yt_items, complete)) = fetch_youtube_uploads_items(app, &channel_id, &tabs).await {
                 let mut map: std::collections::HashMap<String, RssItem> = yt_items
                     .into_iter()
                     .map(|item| (item.id.clone(), item))
@@ -364,7 +507,9 @@ pub async fn fetch_feed_items_extended(app: &AppHandle, feed_url: &str) -> AppRe
                         if !item.thumbnail.is_empty() {
                             existing.thumbnail = item.thumbnail;
                         }
-                    } else {
+                    } else if !complete {
+                        // Items only the RSS feed has belong to tabs that
+                        // aren't followed, unless a tab failed to load
                         map.insert(item.id.clone(), item);
                     }
                 }
@@ -375,6 +520,7 @@ pub async fn fetch_feed_items_extended(app: &AppHandle, feed_url: &str) -> AppRe
                 }
             }
         }
+        keep_channel_tabs(&mut items, &tabs);
     }
 
     items.sort_by(|a, b| b.published_at.cmp(&a.published_at));
@@ -405,6 +551,74 @@ pub fn channel_to_rss_url(url: &str) -> AppResult<String> {
     ))
 }
 
+/// Placeholders of a feed's folder template.
+pub const FOLDER_PLACEHOLDERS: &[&str] = &["{channel}", "{feed}", "{year}", "{month}", "{day}", "{type}"];
+
+/// The folder an auto-downloaded item of a feed goes to, relative to the
+/// download folder, from the feed's `folder_template` (e.g.
+/// `Subscriptions/{channel}/{year}`). Each placeholder is made safe as a
+/// path component; the date is the item's publish date, or today's.
+/// `None` for templates that are absolute or leave the download folder.
+pub fn render_folder_template(template: &str, channel: &str, feed: &str, item: &RssItem) -> Option<std::path::PathBuf> {
+    let template = template.trim();
+    if template.is_empty()
+        || template.starts_with('/')
+        || template.starts_with('\\')
+        || template.chars().nth(1) == Some(':')
+    {
+        return None;
+    }
+    let date = chrono::NaiveDate::parse_from_str(item.published_at.get(..10).unwrap_or_default(), "%Y-%m-%d")
+        .unwrap_or_else(|_| chrono::Local::now().date_naive());
+    let channel = if channel.trim().is_empty() { feed } else { channel };
+    let kind = if item.video_type.is_empty() { "video" } else { item.video_type.as_str() };
+
+    let mut folder = std::path::PathBuf::new();
+    for segment in template.split(['/', '\\']).map(str::trim).filter(|s| !s.is_empty()) {
+        if segment == "." || segment == ".." {
+            return None;
+        }
+        let rendered = segment
+            .replace("{channel}", &download::sanitize_path_component(channel))
+            .replace("{feed}", &download::sanitize_path_component(feed))
+            .replace("{year}", &date.format("%Y").to_string())
+            .replace("{month}", &date.format("%m").to_string())
+            .replace("{day}", &date.format("%d").to_string())
+            .replace("{type}", kind);
+        folder.push(download::sanitize_path_component(&rendered));
+    }
+    (!folder.as_os_str().is_empty()).then_some(folder)
+}
+
+/// Check a folder template before it is stored.
+pub fn validate_folder_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Ok(());
+    }
+    let sample = RssItem {
+        id: String::new(),
+        title: String::new(),
+        video_id: String::new(),
+        url: String::new(),
+        thumbnail: String::new(),
+        published_at: String::new(),
+        downloaded: false,
+        video_type: String::new(),
+        scheduled_at: None,
+    };
+    if render_folder_template(template, "Channel", "Feed", &sample).is_none() {
+        return Err("Invalid folder template: it must be a relative folder without '..'".to_string());
+    }
+    let unknown = template
+        .match_indices('{')
+        .filter_map(|(i, _)| template[i..].find('}').map(|end| &template[i..=i + end]))
+        .find(|p| !FOLDER_PLACEHOLDERS.contains(p));
+    match unknown {
+        Some(p) => Err(format!("Unknown placeholder {} in folder template", p)),
+        None => Ok(()),
+    }
+}
+
 pub async fn normalize_feed_url(url: &str) -> AppResult<String> {
     let url = normalize_input_url(url);
 
@@ -448,9 +662,10 @@ pub async fn normalize_feed_url(url: &str) -> AppResult<String> {
 pub async fn fetch_feed_items(feed_url: &str) -> AppResult<(String, Vec<RssItem>)> {
     use reqwest::header::CONTENT_TYPE;
 
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::client_builder()
         .user_agent("YTDL/3.0")
         .timeout(std::time::Duration::from_secs(30))
+        .redirect(crate::commands::ssrf_redirect_policy())
         .build()
         .map_err(|e| AppError::Rss(format!("HTTP client error: {}", e)))?;
 
@@ -486,7 +701,7 @@ pub async fn fetch_feed_items(feed_url: &str) -> AppResult<(String, Vec<RssItem>
 async fn fetch_youtube_channel_avatar(channel_id: &str) -> Option<String> {
     let channel_url = format!("https://www.youtube.com/channel/{}", channel_id);
 
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::client_builder()
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
         .timeout(std::time::Duration::from_secs(10))
         .build()
@@ -676,6 +891,7 @@ fn parse_atom_feed(xml: &str) -> AppResult<(String, Vec<RssItem>)> {
                                 published_at: current_published.clone(),
                                 downloaded: false,
                                 video_type: video_type.to_string(),
+                                scheduled_at: None,
                             });
                         }
                         in_entry = false;
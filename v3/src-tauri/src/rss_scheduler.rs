@@ -75,6 +75,15 @@ impl RssScheduler {
                     break;
                 }
 
+                // Low battery: wait until charging or recharged
+                crate::download_guard::wait_for_battery().await;
+
+                // Quiet hours: skip this check, the next one comes after the interval
+                if crate::quiet_hours::is_quiet_now() {
+                    log::info!("[RssScheduler] Quiet hours, skipping feed check");
+                    continue;
+                }
+
                 // Check all feeds
                 if let Err(e) = check_all_feeds(&app).await {
                     log::error!("RSS background check failed: {}", e);
@@ -125,21 +134,30 @@ async fn check_all_feeds(app: &AppHandle) -> Result<(), String> {
     let db = app.state::<Arc<std::sync::Mutex<Database>>>();
     
     // Get all feeds
-    let feeds = {
+    let (feeds, policy) = {
         let db_lock = db.lock().map_err(|e| e.to_string())?;
-        db_lock.get_feeds().map_err(|e| e.to_string())?
+        let feeds = db_lock.get_feeds().map_err(|e| e.to_string())?;
+        (feeds, rss::FeedPolicy::load(&db_lock))
     };
 
     let mut new_items_count = 0;
 
     for feed in feeds {
-        let feed_id = feed["id"].as_str().unwrap_or_default().to_string();
-        let feed_url = feed["url"].as_str().unwrap_or_default().to_string();
-        let feed_title = feed["channelName"].as_str().unwrap_or("Unknown").to_string();
+        let feed_title = if feed.channel_name.is_empty() {
+            "Unknown".to_string()
+        } else {
+            feed.channel_name
+        };
+        let feed_id = feed.id;
+        let feed_url = feed.url;
 
         if feed_url.is_empty() {
             continue;
         }
+        if let Err(e) = policy.check(&feed_url) {
+            log::warn!("Skipping RSS feed {}: {}", feed_url, e);
+            continue;
+        }
 
         // Normalize and fetch
         let normalized_url = match rss::normalize_feed_url(&feed_url).await {
@@ -150,7 +168,7 @@ async fn check_all_feeds(app: &AppHandle) -> Result<(), String> {
             }
         };
 
-        let (title, items) = match rss::fetch_feed_items_extended(app, &normalized_url).await {
+        let (title, items) = match rss::fetch_feed_items_extended(app, &normalized_url, &feed.tabs).await {
             Ok(result) => result,
             Err(e) => {
                 log::warn!("Failed to fetch RSS feed {}: {}", feed_url, e);
@@ -165,20 +183,9 @@ async fn check_all_feeds(app: &AppHandle) -> Result<(), String> {
             if !title.is_empty() {
                 let _ = db_lock.update_feed_channel_info(&feed_id, &title, "");
             }
-            for item in &items {
-                let already_exists = db_lock.feed_item_exists(&item.id);
-                if db_lock.insert_feed_item(
-                    &item.id,
-                    &feed_id,
-                    &item.video_id,
-                    &item.title,
-                    &item.thumbnail,
-                    &item.url,
-                    &item.published_at,
-                    &item.video_type,
-                ).is_ok() && !already_exists {
-                    new_items_count += 1;
-                }
+            match db_lock.insert_feed_items(&feed_id, &items) {
+                Ok(new_ids) => new_items_count += new_ids.len(),
+                Err(e) => log::warn!("Failed to save items for feed {}: {}", feed_url, e),
             }
         }
 
@@ -190,21 +197,12 @@ async fn check_all_feeds(app: &AppHandle) -> Result<(), String> {
             "newItems": new_items_count
         }));
 
-        if let Ok(db_lock) = db.lock() {
-            if let Ok(Some(notifications)) = db_lock.get_setting("notifications") {
-                if notifications == "true" {
-                    #[cfg(desktop)]
-                    {
-                        use tauri_plugin_notification::NotificationExt;
-                        let _ = app.notification()
-                            .builder()
-                            .title("New Videos Available")
-                            .body(&format!("{} new videos from your subscriptions", new_items_count))
-                            .show();
-                    }
-                }
-            }
-        }
+        crate::notifications::notify(
+            app,
+            crate::notifications::NotifyEvent::NewFeedItems,
+            "New Videos Available",
+            &format!("{} new videos from your subscriptions", new_items_count),
+        );
     }
 
     Ok(())
@@ -0,0 +1,132 @@
+//! API keys and other credentials.
+//!
+//! On desktop, secrets live in the OS keychain (macOS Keychain, Windows
+//! Credential Manager, Secret Service on Linux) under the `ytdl` service,
+//! and their row in the `settings` table is kept empty. Where no keyring is
+//! available (mobile, headless Linux without a Secret Service) they fall
+//! back to the settings table as before.
+//!
+//! Keyring calls can block for a while (Secret Service over D-Bus, an
+//! unlock prompt), so callers sharing the database make them with
+//! [`keyring_secret`] and [`keyring_store`] before taking its lock and
+//! finish with [`secret_or_setting`] and [`store_setting`] under it.
+
+use crate::db::Database;
+
+/// Setting keys that are stored as secrets.
+pub const SECRET_KEYS: &[&str] = &["openai_api_key", "transcribe_api_key", "api_token", "media_server_token"];
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+const KEYRING_SERVICE: &str = "ytdl";
+
+pub fn is_secret_key(key: &str) -> bool {
+    SECRET_KEYS.contains(&key)
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+mod keychain {
+    use super::KEYRING_SERVICE;
+
+    /// `Ok(None)` if the keyring works but has no entry for `key`.
+    pub fn get(key: &str) -> Result<Option<String>, String> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE, key).map_err(|e| e.to_string())?;
+        match entry.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn set(key: &str, value: &str) -> Result<(), String> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE, key).map_err(|e| e.to_string())?;
+        if value.is_empty() {
+            match entry.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+                Err(e) => Err(e.to_string()),
+            }
+        } else {
+            entry.set_password(value).map_err(|e| e.to_string())
+        }
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+mod keychain {
+    pub fn get(_key: &str) -> Result<Option<String>, String> {
+        Err("No system keyring on this platform".to_string())
+    }
+
+    pub fn set(_key: &str, _value: &str) -> Result<(), String> {
+        Err("No system keyring on this platform".to_string())
+    }
+}
+
+/// The keyring entry for `key`, `None` when there is none or the keyring
+/// can't be used.
+pub fn keyring_secret(key: &str) -> Option<String> {
+    match keychain::get(key) {
+        Ok(value) => value.filter(|v| !v.is_empty()),
+        Err(e) => {
+            log::debug!("[secrets] Keyring unavailable for {}: {}", key, e);
+            None
+        }
+    }
+}
+
+/// A secret given what [`keyring_secret`] returned for it: that value, or
+/// else the (fallback or not yet migrated) settings value.
+pub fn secret_or_setting(db: &Database, key: &str, from_keyring: Option<String>) -> String {
+    from_keyring.unwrap_or_else(|| db.get_setting(key).unwrap_or(None).unwrap_or_default())
+}
+
+/// Read a secret: the keyring entry if there is one, otherwise the
+/// settings value.
+pub fn get_secret(db: &Database, key: &str) -> String {
+    secret_or_setting(db, key, keyring_secret(key))
+}
+
+/// Put a secret in the keyring. Returns whether it is there now; if not,
+/// [`store_setting`] keeps it in the settings table instead.
+pub fn keyring_store(key: &str, value: &str) -> bool {
+    match keychain::set(key, value) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("[secrets] Keyring unavailable, storing {} in settings: {}", key, e);
+            false
+        }
+    }
+}
+
+/// The settings row of a secret after [`keyring_store`]: blank when the
+/// keyring took it, the value itself otherwise.
+pub fn store_setting(db: &Database, key: &str, value: &str, in_keyring: bool) -> Result<(), String> {
+    db.save_setting(key, if in_keyring { "" } else { value })
+        .map_err(|e| e.to_string())
+}
+
+/// Store a secret in the keyring and blank its settings row. If the keyring
+/// can't be used the value is written to the settings table instead.
+pub fn set_secret(db: &Database, key: &str, value: &str) -> Result<(), String> {
+    store_setting(db, key, value, keyring_store(key, value))
+}
+
+/// Move plaintext secrets left in the settings table by older versions
+/// into the keyring. Values stay where they are if the keyring fails.
+pub fn migrate_plaintext_secrets(db: &Database) {
+    for key in SECRET_KEYS {
+        let value = db.get_setting(key).unwrap_or(None).unwrap_or_default();
+        if value.is_empty() {
+            continue;
+        }
+        match keychain::set(key, &value) {
+            Ok(()) => {
+                if let Err(e) = db.save_setting(key, "") {
+                    log::warn!("[secrets] Moved {} to keyring but failed to clear setting: {}", key, e);
+                } else {
+                    log::info!("[secrets] Moved {} to the system keyring", key);
+                }
+            }
+            Err(e) => log::debug!("[secrets] Keeping {} in settings: {}", key, e),
+        }
+    }
+}
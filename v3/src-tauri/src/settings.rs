@@ -1,27 +1,140 @@
-/// Typed representation of application settings.
-///
-/// Currently settings are persisted as individual key-value pairs in the
-/// database via `save_setting` / `get_settings` commands.  This struct is
-/// kept for reference and potential future migration to a strongly-typed
-/// config layer.
+//! Typed representation of application settings.
+//!
+//! Settings are persisted as individual key-value pairs in the `settings`
+//! table. [`AppSettings`] is the typed view over the keys the app itself
+//! understands: it applies defaults for missing values, parses and
+//! validates them, and writes them back. Keys it doesn't know about (tool
+//! paths, transcription options, ...) are still stored as plain strings via
+//! `save_setting`.
 use serde::{Deserialize, Serialize};
 
+use crate::db::Database;
+use crate::error::AppResult;
+
+/// Field names match the frontend's `AppSettings` interface.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 pub struct AppSettings {
     pub theme: String,
     pub language: String,
     pub download_path: String,
+    /// Per-kind destination folders; empty = `download_path`.
+    pub video_download_path: String,
+    pub audio_download_path: String,
+    pub shorts_download_path: String,
+    pub podcast_download_path: String,
+    pub max_concurrent_downloads: u32,
+    /// MB/s, 0 = unlimited.
+    pub speed_limit: u32,
+    /// Fragments of a DASH/HLS download fetched at once (yt-dlp `-N`).
+    pub concurrent_fragments: u32,
+    #[serde(rename = "autoStartDownloads")]
+    pub auto_start_download: bool,
+    /// Master switch; the `notify_*` flags pick which events notify.
     pub notifications: bool,
+    pub notify_download_complete: bool,
+    pub notify_download_failed: bool,
+    pub notify_transcription_done: bool,
+    pub notify_tool_update: bool,
     pub close_to_tray: bool,
     pub auto_launch: bool,
-    pub auto_start_download: bool,
+    /// Start minimized when launched on login.
+    pub start_minimized: bool,
+    pub default_quality: String,
+    /// "any", "avc", "vp9" or "av1".
+    pub preferred_vcodec: String,
+    /// "any", "sdr" or "hdr".
+    pub preferred_dynamic_range: String,
+    /// Highest frame rate to prefer, 0 = any.
+    pub max_fps: u32,
+    /// kbps, 0 = any.
+    pub min_audio_bitrate: u32,
+    pub quality_preset: String,
+    pub default_format: String,
     pub embed_thumbnail: bool,
     pub embed_metadata: bool,
+    /// Chapter markers in the output container.
+    pub embed_chapters: bool,
+    /// Keep yt-dlp's .info.json next to each download; its description,
+    /// tags, upload date and view count are stored with the download.
+    pub write_info_json: bool,
+    /// When the picked formats only merge into MP4 by re-encoding:
+    /// "reencode", "mkv" (keep an MKV) or "abort", see `remux`.
+    pub merge_policy: String,
+    #[serde(rename = "browserForCookies")]
     pub browser_cookies: String,
+    /// Browser whose cookies a download is retried with once when it fails
+    /// for being age-restricted or members-only, "none" = no retry.
+    pub cookies_fallback_browser: String,
     pub ytdlp_flags: String,
+    /// `EXTRACTOR:KEY=VALUE` entries passed to every yt-dlp run, see
+    /// `extractor_args`.
+    pub ytdlp_extractor_args: String,
+    /// yt-dlp release channel: "stable", "nightly" or "master".
+    pub ytdlp_channel: String,
+    #[serde(rename = "configPath")]
     pub config_file: String,
+    /// Honor HTTP(S)_PROXY/NO_PROXY; ignored when `proxy_url` is set.
+    pub use_system_proxy: bool,
+    pub proxy_url: String,
+    /// Minutes, 0 = disabled.
+    pub rss_check_interval: u64,
+    pub rss_notifications: bool,
+    pub rss_auto_download: bool,
+    /// Refuse feed URLs that aren't HTTPS.
+    pub feed_https_only: bool,
+    /// Feed hosts (and their subdomains) allowed, comma separated; empty =
+    /// any host.
+    pub feed_allowed_hosts: String,
+    /// Feed hosts refused even if allowed above.
+    pub feed_blocked_hosts: String,
+    pub playlist_subfolder: bool,
+    pub playlist_concurrency: u32,
+    /// Called when any download finishes; empty = off.
+    pub webhook_url: String,
+    /// Request body with `{{placeholders}}`; empty = the default JSON payload.
+    pub webhook_payload: String,
+    /// "none", "jellyfin" or "plex"; see `media_server`.
+    pub media_server: String,
+    pub media_server_url: String,
+    /// Only downloads in this folder trigger a refresh; empty = any.
+    pub media_server_library: String,
+    /// Folder polled for `.txt`/`.url` link files; empty = off. See
+    /// `watch_folder`.
+    pub watch_folder: String,
+    /// Days of log files to keep, 0 = keep all.
+    pub log_retention_days: u32,
+    /// yt-dlp metadata calls allowed per minute, 0 = unlimited.
+    pub ytdlp_requests_per_minute: u32,
+    /// Pause after an HTTP 429, doubled for each further one.
+    pub ytdlp_cooldown_minutes: u32,
+    /// Android: where finished downloads end up. "app" leaves them in the
+    /// download folder, "media" moves them to Movies/Music, "saf" to the
+    /// folder picked with `pick_android_download_folder`.
+    pub android_storage_target: String,
+    /// Pause downloads on metered connections and resume on Wi-Fi.
+    pub download_wifi_only: bool,
+    /// Pause downloads and scheduled checks below this charge when not
+    /// charging, 0 = off.
+    pub battery_min_percent: u32,
+    /// Resume at or above this charge even when not charging.
+    pub battery_resume_percent: u32,
+    /// "HH:MM" local time; new downloads and scheduled checks wait between
+    /// start and end. Either empty = off. See `quiet_hours`.
+    pub quiet_hours_start: String,
+    pub quiet_hours_end: String,
+    /// Also pause running downloads during quiet hours.
+    pub quiet_hours_pause_active: bool,
+    /// GiB downloaded per period before queued downloads wait, 0 = off.
+    /// See `quota`.
+    pub download_quota_gb: u32,
+    /// "day" or "week" (starting Monday).
+    pub download_quota_period: String,
+    /// Record scheduled premieres and livestreams of auto-download feeds
+    /// when they start. See `live_schedule`.
+    pub live_auto_record: bool,
+    /// Minutes before a scheduled stream to notify, 0 = off.
+    pub live_notify_minutes: u32,
 }
 
 impl Default for AppSettings {
@@ -32,7 +145,7 @@ impl Default for AppSettings {
             let base = std::env::var("EXTERNAL_STORAGE").unwrap_or_else(|_| "/sdcard".to_string());
             std::path::PathBuf::from(format!("{}/Download/YTDL", base))
         };
-        
+
         #[cfg(not(target_os = "android"))]
         let download_dir = dirs::download_dir()
             .or_else(|| dirs::home_dir().map(|h| h.join("Downloads")))
@@ -43,15 +156,546 @@ impl Default for AppSettings {
             theme: "system".to_string(),
             language: "en".to_string(),
             download_path: download_dir.to_string_lossy().to_string(),
+            video_download_path: String::new(),
+            audio_download_path: String::new(),
+            shorts_download_path: String::new(),
+            podcast_download_path: String::new(),
+            max_concurrent_downloads: 3,
+            speed_limit: 0,
+            concurrent_fragments: 1,
+            auto_start_download: true,
             notifications: true,
+            notify_download_complete: true,
+            notify_download_failed: true,
+            notify_transcription_done: true,
+            notify_tool_update: true,
             close_to_tray: false,
             auto_launch: false,
-            auto_start_download: true,
+            start_minimized: true,
+            default_quality: "best".to_string(),
+            preferred_vcodec: "any".to_string(),
+            preferred_dynamic_range: "any".to_string(),
+            max_fps: 0,
+            min_audio_bitrate: 0,
+            quality_preset: "best".to_string(),
+            default_format: "mp4".to_string(),
             embed_thumbnail: true,
             embed_metadata: true,
+            embed_chapters: true,
+            write_info_json: false,
+            merge_policy: "reencode".to_string(),
             browser_cookies: "none".to_string(),
+            cookies_fallback_browser: "none".to_string(),
             ytdlp_flags: String::new(),
+            ytdlp_extractor_args: String::new(),
+            ytdlp_channel: "stable".to_string(),
             config_file: String::new(),
+            use_system_proxy: true,
+            proxy_url: String::new(),
+            rss_check_interval: 60,
+            rss_notifications: true,
+            rss_auto_download: false,
+            feed_https_only: false,
+            feed_allowed_hosts: String::new(),
+            feed_blocked_hosts: String::new(),
+            playlist_subfolder: true,
+            playlist_concurrency: 1,
+            webhook_url: String::new(),
+            webhook_payload: String::new(),
+            media_server: "none".to_string(),
+            media_server_url: String::new(),
+            media_server_library: String::new(),
+            watch_folder: String::new(),
+            log_retention_days: crate::logger::DEFAULT_RETENTION_DAYS,
+            ytdlp_requests_per_minute: crate::rate_limit::DEFAULT_REQUESTS_PER_MINUTE,
+            ytdlp_cooldown_minutes: crate::rate_limit::DEFAULT_COOLDOWN_MINUTES,
+            android_storage_target: "app".to_string(),
+            download_wifi_only: false,
+            battery_min_percent: 0,
+            battery_resume_percent: 30,
+            quiet_hours_start: String::new(),
+            quiet_hours_end: String::new(),
+            quiet_hours_pause_active: false,
+            download_quota_gb: 0,
+            download_quota_period: "day".to_string(),
+            live_auto_record: false,
+            live_notify_minutes: 10,
+        }
+    }
+}
+
+const THEMES: &[&str] = &["light", "dark", "system"];
+const DEFAULT_QUALITIES: &[&str] = &["best", "2160p", "1440p", "1080p", "720p", "480p", "360p", "audio"];
+const VIDEO_CODECS: &[&str] = &["any", "avc", "vp9", "av1"];
+const DYNAMIC_RANGES: &[&str] = &["any", "sdr", "hdr"];
+const QUALITY_PRESETS: &[&str] = &["best", "4k", "1080p", "720p", "audio"];
+const YTDLP_CHANNELS: &[&str] = &["stable", "nightly", "master"];
+const ANDROID_STORAGE_TARGETS: &[&str] = &["app", "media", "saf"];
+/// Browsers yt-dlp's `--cookies-from-browser` accepts.
+const COOKIE_BROWSERS: &[&str] = &["none", "brave", "chrome", "chromium", "edge", "firefox", "opera", "safari", "vivaldi", "whale"];
+
+impl AppSettings {
+    /// Stored settings over the defaults. Values that don't parse are
+    /// logged and replaced by their default.
+    pub fn load(db: &Database) -> Self {
+        let mut settings = Self::default();
+        if let Ok(serde_json::Value::Object(stored)) = db.get_all_settings() {
+            for (key, value) in stored {
+                let Some(value) = value.as_str() else { continue };
+                // An empty download path means "use the default"
+                if key == "download_path" && value.trim().is_empty() {
+                    continue;
+                }
+                if let Err(e) = settings.apply(&key, value) {
+                    log::warn!("[settings] Ignoring stored {}: {}", key, e);
+                }
+            }
+        }
+        settings
+    }
+
+    /// Set the field stored under `key` from its string form. Returns
+    /// `Ok(false)` for keys that aren't part of the typed settings.
+    pub fn apply(&mut self, key: &str, value: &str) -> Result<bool, String> {
+        fn parse_bool(key: &str, value: &str) -> Result<bool, String> {
+            match value.trim() {
+                "true" | "1" => Ok(true),
+                "false" | "0" => Ok(false),
+                _ => Err(format!("{} must be true or false", key)),
+            }
+        }
+        fn parse_num<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+            value
+                .trim()
+                .parse::<T>()
+                .map_err(|_| format!("{} must be a whole number", key))
+        }
+
+        match key {
+            "theme" => self.theme = value.trim().to_string(),
+            "language" => self.language = value.trim().to_string(),
+            "download_path" => self.download_path = value.trim().to_string(),
+            "video_download_path" => self.video_download_path = value.trim().to_string(),
+            "audio_download_path" => self.audio_download_path = value.trim().to_string(),
+            "shorts_download_path" => self.shorts_download_path = value.trim().to_string(),
+            "podcast_download_path" => self.podcast_download_path = value.trim().to_string(),
+            "max_concurrent_downloads" => self.max_concurrent_downloads = parse_num(key, value)?,
+            "speed_limit" => self.speed_limit = parse_num(key, value)?,
+            "concurrent_fragments" => self.concurrent_fragments = parse_num(key, value)?,
+            "auto_start_download" => self.auto_start_download = parse_bool(key, value)?,
+            "notifications" => self.notifications = parse_bool(key, value)?,
+            "notify_download_complete" => self.notify_download_complete = parse_bool(key, value)?,
+            "notify_download_failed" => self.notify_download_failed = parse_bool(key, value)?,
+            "notify_transcription_done" => self.notify_transcription_done = parse_bool(key, value)?,
+            "notify_tool_update" => self.notify_tool_update = parse_bool(key, value)?,
+            "close_to_tray" => self.close_to_tray = parse_bool(key, value)?,
+            "auto_launch" => self.auto_launch = parse_bool(key, value)?,
+            "start_minimized" => self.start_minimized = parse_bool(key, value)?,
+            "default_quality" => self.default_quality = value.trim().to_string(),
+            "preferred_vcodec" => self.preferred_vcodec = value.trim().to_lowercase(),
+            "preferred_dynamic_range" => self.preferred_dynamic_range = value.trim().to_lowercase(),
+            "max_fps" => self.max_fps = parse_num(key, value)?,
+            "min_audio_bitrate" => self.min_audio_bitrate = parse_num(key, value)?,
+            "quality_preset" => self.quality_preset = value.trim().to_string(),
+            "default_format" => self.default_format = value.trim().to_string(),
+            "embed_thumbnail" => self.embed_thumbnail = parse_bool(key, value)?,
+            "embed_metadata" => self.embed_metadata = parse_bool(key, value)?,
+            "embed_chapters" => self.embed_chapters = parse_bool(key, value)?,
+            "write_info_json" => self.write_info_json = parse_bool(key, value)?,
+            "merge_policy" => self.merge_policy = value.trim().to_lowercase(),
+            "browser_cookies" => self.browser_cookies = value.trim().to_string(),
+            "cookies_fallback_browser" => self.cookies_fallback_browser = value.trim().to_string(),
+            "ytdlp_flags" => self.ytdlp_flags = value.to_string(),
+            "ytdlp_extractor_args" => self.ytdlp_extractor_args = value.trim().to_string(),
+            "ytdlp_channel" => self.ytdlp_channel = value.trim().to_lowercase(),
+            "config_file" => self.config_file = value.trim().to_string(),
+            "use_system_proxy" => self.use_system_proxy = parse_bool(key, value)?,
+            "proxy_url" => self.proxy_url = value.trim().to_string(),
+            "rss_check_interval" => self.rss_check_interval = parse_num(key, value)?,
+            "rss_notifications" => self.rss_notifications = parse_bool(key, value)?,
+            "rss_auto_download" => self.rss_auto_download = parse_bool(key, value)?,
+            "feed_https_only" => self.feed_https_only = parse_bool(key, value)?,
+            "feed_allowed_hosts" => self.feed_allowed_hosts = value.trim().to_string(),
+            "feed_blocked_hosts" => self.feed_blocked_hosts = value.trim().to_string(),
+            "playlist_subfolder" => self.playlist_subfolder = parse_bool(key, value)?,
+            "playlist_concurrency" => self.playlist_concurrency = parse_num(key, value)?,
+            "webhook_url" => self.webhook_url = value.trim().to_string(),
+            "webhook_payload" => self.webhook_payload = value.to_string(),
+            "media_server" => self.media_server = value.trim().to_lowercase(),
+            "media_server_url" => self.media_server_url = value.trim().to_string(),
+            "media_server_library" => self.media_server_library = value.trim().to_string(),
+            "watch_folder" => self.watch_folder = value.trim().to_string(),
+            "log_retention_days" => self.log_retention_days = parse_num(key, value)?,
+            "ytdlp_requests_per_minute" => self.ytdlp_requests_per_minute = parse_num(key, value)?,
+            "ytdlp_cooldown_minutes" => self.ytdlp_cooldown_minutes = parse_num(key, value)?,
+            "android_storage_target" => self.android_storage_target = value.trim().to_lowercase(),
+            "download_wifi_only" => self.download_wifi_only = parse_bool(key, value)?,
+            "battery_min_percent" => self.battery_min_percent = parse_num(key, value)?,
+            "battery_resume_percent" => self.battery_resume_percent = parse_num(key, value)?,
+            "quiet_hours_start" => self.quiet_hours_start = value.trim().to_string(),
+            "quiet_hours_end" => self.quiet_hours_end = value.trim().to_string(),
+            "quiet_hours_pause_active" => self.quiet_hours_pause_active = parse_bool(key, value)?,
+            "download_quota_gb" => self.download_quota_gb = parse_num(key, value)?,
+            "download_quota_period" => self.download_quota_period = value.trim().to_lowercase(),
+            "live_auto_record" => self.live_auto_record = parse_bool(key, value)?,
+            "live_notify_minutes" => self.live_notify_minutes = parse_num(key, value)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// The settings as `(key, value)` pairs in their stored string form.
+    pub fn to_pairs(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("theme", self.theme.clone()),
+            ("language", self.language.clone()),
+            ("download_path", self.download_path.clone()),
+            ("video_download_path", self.video_download_path.clone()),
+            ("audio_download_path", self.audio_download_path.clone()),
+            ("shorts_download_path", self.shorts_download_path.clone()),
+            ("podcast_download_path", self.podcast_download_path.clone()),
+            ("max_concurrent_downloads", self.max_concurrent_downloads.to_string()),
+            ("speed_limit", self.speed_limit.to_string()),
+            ("concurrent_fragments", self.concurrent_fragments.to_string()),
+            ("auto_start_download", self.auto_start_download.to_string()),
+            ("notifications", self.notifications.to_string()),
+            ("notify_download_complete", self.notify_download_complete.to_string()),
+            ("notify_download_failed", self.notify_download_failed.to_string()),
+            ("notify_transcription_done", self.notify_transcription_done.to_string()),
+            ("notify_tool_update", self.notify_tool_update.to_string()),
+            ("close_to_tray", self.close_to_tray.to_string()),
+            ("auto_launch", self.auto_launch.to_string()),
+            ("start_minimized", self.start_minimized.to_string()),
+            ("default_quality", self.default_quality.clone()),
+            ("preferred_vcodec", self.preferred_vcodec.clone()),
+            ("preferred_dynamic_range", self.preferred_dynamic_range.clone()),
+            ("max_fps", self.max_fps.to_string()),
+            ("min_audio_bitrate", self.min_audio_bitrate.to_string()),
+            ("quality_preset", self.quality_preset.clone()),
+            ("default_format", self.default_format.clone()),
+            ("embed_thumbnail", self.embed_thumbnail.to_string()),
+            ("embed_metadata", self.embed_metadata.to_string()),
+            ("embed_chapters", self.embed_chapters.to_string()),
+            ("write_info_json", self.write_info_json.to_string()),
+            ("merge_policy", self.merge_policy.clone()),
+            ("browser_cookies", self.browser_cookies.clone()),
+            ("cookies_fallback_browser", self.cookies_fallback_browser.clone()),
+            ("ytdlp_flags", self.ytdlp_flags.clone()),
+            ("ytdlp_extractor_args", self.ytdlp_extractor_args.clone()),
+            ("ytdlp_channel", self.ytdlp_channel.clone()),
+            ("config_file", self.config_file.clone()),
+            ("use_system_proxy", self.use_system_proxy.to_string()),
+            ("proxy_url", self.proxy_url.clone()),
+            ("rss_check_interval", self.rss_check_interval.to_string()),
+            ("rss_notifications", self.rss_notifications.to_string()),
+            ("rss_auto_download", self.rss_auto_download.to_string()),
+            ("feed_https_only", self.feed_https_only.to_string()),
+            ("feed_allowed_hosts", self.feed_allowed_hosts.clone()),
+            ("feed_blocked_hosts", self.feed_blocked_hosts.clone()),
+            ("playlist_subfolder", self.playlist_subfolder.to_string()),
+            ("playlist_concurrency", self.playlist_concurrency.to_string()),
+            ("webhook_url", self.webhook_url.clone()),
+            ("webhook_payload", self.webhook_payload.clone()),
+            ("media_server", self.media_server.clone()),
+            ("media_server_url", self.media_server_url.clone()),
+            ("media_server_library", self.media_server_library.clone()),
+            ("watch_folder", self.watch_folder.clone()),
+            ("log_retention_days", self.log_retention_days.to_string()),
+            ("ytdlp_requests_per_minute", self.ytdlp_requests_per_minute.to_string()),
+            ("ytdlp_cooldown_minutes", self.ytdlp_cooldown_minutes.to_string()),
+            ("android_storage_target", self.android_storage_target.clone()),
+            ("download_wifi_only", self.download_wifi_only.to_string()),
+            ("battery_min_percent", self.battery_min_percent.to_string()),
+            ("battery_resume_percent", self.battery_resume_percent.to_string()),
+            ("quiet_hours_start", self.quiet_hours_start.clone()),
+            ("quiet_hours_end", self.quiet_hours_end.clone()),
+            ("quiet_hours_pause_active", self.quiet_hours_pause_active.to_string()),
+            ("download_quota_gb", self.download_quota_gb.to_string()),
+            ("download_quota_period", self.download_quota_period.clone()),
+            ("live_auto_record", self.live_auto_record.to_string()),
+            ("live_notify_minutes", self.live_notify_minutes.to_string()),
+        ]
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (key, _) in self.to_pairs() {
+            self.check(key)?;
+        }
+        Ok(())
+    }
+
+    /// Validate the field stored under `key`.
+    pub fn check(&self, key: &str) -> Result<(), String> {
+        match key {
+            "theme" if !THEMES.contains(&self.theme.as_str()) => {
+                Err(format!("Unknown theme '{}'", self.theme))
+            }
+            "language" if self.language.is_empty() || self.language.len() > 10 => {
+                Err(format!("Invalid language '{}'", self.language))
+            }
+            "download_path" => check_folder("Download path", &self.download_path),
+            "video_download_path" if !self.video_download_path.is_empty() => {
+                check_folder("Video folder", &self.video_download_path)
+            }
+            "audio_download_path" if !self.audio_download_path.is_empty() => {
+                check_folder("Audio folder", &self.audio_download_path)
+            }
+            "shorts_download_path" if !self.shorts_download_path.is_empty() => {
+                check_folder("Shorts folder", &self.shorts_download_path)
+            }
+            "podcast_download_path" if !self.podcast_download_path.is_empty() => {
+                check_folder("Podcast folder", &self.podcast_download_path)
+            }
+            "max_concurrent_downloads" if !(1..=10).contains(&self.max_concurrent_downloads) => {
+                Err("Concurrent downloads must be between 1 and 10".to_string())
+            }
+            "concurrent_fragments" => check_concurrent_fragments(self.concurrent_fragments),
+            "speed_limit" if self.speed_limit > 10_000 => {
+                Err("Speed limit must be at most 10000 MB/s (0 = unlimited)".to_string())
+            }
+            "default_quality" if !DEFAULT_QUALITIES.contains(&self.default_quality.as_str()) => {
+                Err(format!("Unknown default quality '{}'", self.default_quality))
+            }
+            "preferred_vcodec" if !VIDEO_CODECS.contains(&self.preferred_vcodec.as_str()) => {
+                Err(format!("Unknown video codec '{}'", self.preferred_vcodec))
+            }
+            "preferred_dynamic_range" if !DYNAMIC_RANGES.contains(&self.preferred_dynamic_range.as_str()) => {
+                Err(format!("Unknown dynamic range '{}'", self.preferred_dynamic_range))
+            }
+            "merge_policy" if !crate::remux::POLICIES.contains(&self.merge_policy.as_str()) => {
+                Err(format!("Unknown merge policy '{}'", self.merge_policy))
+            }
+            "max_fps" if self.max_fps > 240 => Err("Maximum frame rate must be at most 240".to_string()),
+            "min_audio_bitrate" if self.min_audio_bitrate > 512 => {
+                Err("Minimum audio bitrate must be at most 512 kbps".to_string())
+            }
+            "quality_preset" if !QUALITY_PRESETS.contains(&self.quality_preset.as_str()) => {
+                Err(format!("Unknown quality preset '{}'", self.quality_preset))
+            }
+            "browser_cookies" => check_cookie_browser(&self.browser_cookies),
+            "cookies_fallback_browser" => check_cookie_browser(&self.cookies_fallback_browser),
+            "ytdlp_flags" => crate::commands::split_ytdlp_flags(&self.ytdlp_flags).map(|_| ()),
+            "ytdlp_extractor_args" => crate::extractor_args::validate(&self.ytdlp_extractor_args),
+            "ytdlp_channel" if !YTDLP_CHANNELS.contains(&self.ytdlp_channel.as_str()) => {
+                Err(format!("Unknown yt-dlp channel '{}'", self.ytdlp_channel))
+            }
+            "config_file"
+                if !self.config_file.is_empty()
+                    && !std::path::Path::new(&self.config_file).is_file() =>
+            {
+                Err(format!("Config file '{}' does not exist", self.config_file))
+            }
+            "proxy_url" => crate::proxy::validate_proxy_url(&self.proxy_url),
+            "rss_check_interval" if self.rss_check_interval > 7 * 24 * 60 => {
+                Err("RSS check interval must be at most one week (0 = disabled)".to_string())
+            }
+            "playlist_concurrency" if !(1..=8).contains(&self.playlist_concurrency) => {
+                Err("Playlist concurrency must be between 1 and 8".to_string())
+            }
+            "feed_allowed_hosts" => check_host_list("Allowed feed hosts", &self.feed_allowed_hosts),
+            "feed_blocked_hosts" => check_host_list("Blocked feed hosts", &self.feed_blocked_hosts),
+            "webhook_url" if !self.webhook_url.is_empty() => {
+                crate::webhooks::validate_webhook_url(&self.webhook_url)
+            }
+            "webhook_payload" if self.webhook_payload.len() > 16 * 1024 => {
+                Err("Webhook payload must be at most 16 KB".to_string())
+            }
+            "media_server" if !crate::media_server::SERVERS.contains(&self.media_server.as_str()) => {
+                Err(format!("Unknown media server '{}'", self.media_server))
+            }
+            "media_server_url" if !self.media_server_url.is_empty() => {
+                crate::media_server::validate_server_url(&self.media_server_url)
+            }
+            "media_server_library" if !self.media_server_library.is_empty() => {
+                check_folder("Media library folder", &self.media_server_library)
+            }
+            "watch_folder" if !self.watch_folder.is_empty() => check_folder("Watch folder", &self.watch_folder),
+            "log_retention_days" if self.log_retention_days > 365 => {
+                Err("Log retention must be at most 365 days (0 = keep all)".to_string())
+            }
+            "ytdlp_requests_per_minute" if self.ytdlp_requests_per_minute > 600 => {
+                Err("yt-dlp requests per minute must be at most 600 (0 = unlimited)".to_string())
+            }
+            "ytdlp_cooldown_minutes" if !(1..=24 * 60).contains(&self.ytdlp_cooldown_minutes) => {
+                Err("Rate limit cool-down must be between 1 minute and 24 hours".to_string())
+            }
+            "android_storage_target"
+                if !ANDROID_STORAGE_TARGETS.contains(&self.android_storage_target.as_str()) =>
+            {
+                Err(format!("Unknown storage target '{}'", self.android_storage_target))
+            }
+            "battery_min_percent" if self.battery_min_percent > 100 => {
+                Err("Battery threshold must be between 0 and 100%".to_string())
+            }
+            "battery_resume_percent"
+                if self.battery_resume_percent > 100
+                    || self.battery_resume_percent < self.battery_min_percent =>
+            {
+                Err("Resume threshold must be between the pause threshold and 100%".to_string())
+            }
+            "quiet_hours_start" | "quiet_hours_end" => {
+                let value = if key == "quiet_hours_start" { &self.quiet_hours_start } else { &self.quiet_hours_end };
+                if value.is_empty() || crate::quiet_hours::parse_time(value).is_some() {
+                    Ok(())
+                } else {
+                    Err(format!("Quiet hours: '{}' is not a time (HH:MM)", value))
+                }
+            }
+            "download_quota_gb" if self.download_quota_gb > 100_000 => {
+                Err("Download quota must be at most 100000 GB (0 = off)".to_string())
+            }
+            "download_quota_period" if !crate::quota::PERIODS.contains(&self.download_quota_period.as_str()) => {
+                Err(format!("Unknown quota period '{}'", self.download_quota_period))
+            }
+            "live_notify_minutes" if self.live_notify_minutes > 1440 => {
+                Err("Live notification lead must be at most 1440 minutes (0 = off)".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn save(&self, db: &Database) -> AppResult<()> {
+        for (key, value) in self.to_pairs() {
+            db.save_setting(key, &value)?;
+        }
+        Ok(())
+    }
+}
+
+/// `-N` values yt-dlp is allowed to use, for the setting and per-download
+/// overrides.
+pub fn check_concurrent_fragments(fragments: u32) -> Result<(), String> {
+    if (1..=16).contains(&fragments) {
+        Ok(())
+    } else {
+        Err("Concurrent fragments must be between 1 and 16".to_string())
+    }
+}
+
+/// yt-dlp syntax: BROWSER[+KEYRING][:PROFILE][::CONTAINER]
+pub(crate) fn check_cookie_browser(value: &str) -> Result<(), String> {
+    let browser = value.split(['+', ':']).next().unwrap_or("").to_lowercase();
+    if !COOKIE_BROWSERS.contains(&browser.as_str()) {
+        return Err(format!("Unsupported cookie browser '{}'", value));
+    }
+    Ok(())
+}
+
+/// Each comma-separated entry must be a bare host name.
+fn check_host_list(label: &str, value: &str) -> Result<(), String> {
+    for host in crate::rss::parse_host_list(value) {
+        if !host.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.') {
+            return Err(format!("{}: '{}' is not a host name", label, host));
+        }
+    }
+    Ok(())
+}
+
+/// A download folder must be absolute and either exist or have an existing
+/// parent (the folder itself is created on first download).
+pub fn check_folder(label: &str, value: &str) -> Result<(), String> {
+    let path = std::path::Path::new(value);
+    if value.is_empty() || !path.is_absolute() {
+        return Err(format!("{} must be an absolute path", label));
+    }
+    if path.exists() {
+        if !path.is_dir() {
+            return Err(format!("{} '{}' is not a folder", label, value));
         }
+    } else if !path.parent().map(|p| p.is_dir()).unwrap_or(false) {
+        return Err(format!("{} '{}' does not exist", label, value));
+    }
+    Ok(())
+}
+
+/// Settings holding a binary the app runs, with the file names (lowercase,
+/// without extension) that binary may have.
+const TOOL_PATH_SETTINGS: &[(&str, &[&str])] = &[
+    ("ytdlp_path", &["yt-dlp", "yt_dlp", "ytdlp", "libytdlp"]),
+    ("ffmpeg_path", &["ffmpeg", "libffmpeg"]),
+    ("whisper_cpp_path", &["whisper", "whisper-cli", "whisper-cpp", "main", "libwhisper"]),
+];
+
+/// Check a user-supplied binary before it is saved or run: an absolute
+/// path to an existing executable whose name matches the tool and whose
+/// contents start like a program (native executable or `#!` script).
+/// Empty values (no override) pass. Keys that aren't tool paths pass.
+pub fn check_tool_path(key: &str, value: &str) -> Result<(), String> {
+    let Some((_, names)) = TOOL_PATH_SETTINGS.iter().find(|(k, _)| *k == key) else {
+        return Ok(());
+    };
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(());
+    }
+    if value
+        .chars()
+        .any(|c| c.is_control() || matches!(c, ';' | '|' | '&' | '$' | '`' | '<' | '>' | '\'' | '"'))
+    {
+        return Err(format!("'{}' contains characters not allowed in a tool path", value));
+    }
+
+    let path = std::path::Path::new(value);
+    if !path.is_absolute() {
+        return Err(format!("'{}' must be an absolute path", value));
+    }
+    let metadata = std::fs::metadata(path).map_err(|_| format!("File not found: {}", value))?;
+    if !metadata.is_file() {
+        return Err(format!("'{}' is not a file", value));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return Err(format!("'{}' is not executable", value));
+        }
+    }
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let stem = [".exe", ".so"]
+        .iter()
+        .find_map(|ext| file_name.strip_suffix(ext))
+        .unwrap_or(&file_name);
+    if !names.iter().any(|name| stem == *name || stem.starts_with(&format!("{}_", name))) {
+        return Err(format!(
+            "'{}' doesn't look like {} (expected a file named {})",
+            value,
+            names[0],
+            names.join(", ")
+        ));
+    }
+    if cfg!(windows) && !file_name.ends_with(".exe") {
+        return Err(format!("'{}' is not an .exe file", value));
+    }
+
+    let mut magic = [0u8; 4];
+    let read = std::fs::File::open(path)
+        .and_then(|mut f| std::io::Read::read(&mut f, &mut magic))
+        .map_err(|e| format!("Cannot read {}: {}", value, e))?;
+    let is_program = read >= 2
+        && (magic.starts_with(b"MZ")                    // Windows PE
+            || magic.starts_with(b"#!")                 // script (yt-dlp zipapp)
+            || magic == *b"\x7fELF"                      // Linux/Android
+            || matches!(magic, [0xcf | 0xce, 0xfa, 0xed, 0xfe] | [0xca, 0xfe, 0xba, 0xbe])); // Mach-O
+    if !is_program {
+        return Err(format!("'{}' is not an executable program", value));
+    }
+    Ok(())
+}
+
+/// Validate a single `save_setting` write against the typed settings.
+/// Keys outside [`AppSettings`] are accepted as-is, apart from tool paths.
+pub fn validate_setting(key: &str, value: &str) -> Result<(), String> {
+    if key == "download_path" && value.trim().is_empty() {
+        return Ok(());
+    }
+    check_tool_path(key, value)?;
+    let mut settings = AppSettings::default();
+    if settings.apply(key, value)? {
+        settings.check(key)?;
     }
+    Ok(())
 }
@@ -0,0 +1,146 @@
+//! Transfer-rate history of each download.
+//!
+//! While a download runs, its progress updates are sampled every
+//! [`SAMPLE_INTERVAL`] into a ring buffer of [`CAPACITY`] entries. When
+//! yt-dlp exits (finished, failed, paused or cancelled) the buffer is
+//! appended to the samples stored on the download, so a resumed download
+//! keeps one continuous history. The UI draws it as a sparkline, which
+//! makes throttling show up as a cliff in the curve.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+/// An hour at one sample per [`SAMPLE_INTERVAL`].
+const CAPACITY: usize = 720;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeedSample {
+    /// Unix time in seconds.
+    pub t: i64,
+    pub bytes_per_sec: f64,
+}
+
+struct Recording {
+    id: String,
+    last: Instant,
+    samples: VecDeque<SpeedSample>,
+}
+
+static ACTIVE: Mutex<Vec<Recording>> = Mutex::new(Vec::new());
+
+/// Parse a yt-dlp speed like `5.00MiB/s` or `812.3KiB/s` into bytes per
+/// second. `Unknown B/s` and anything else unreadable give `None`.
+pub fn parse_speed(speed: &str) -> Option<f64> {
+    parse_size(speed.trim().strip_suffix("/s")?)
+}
+
+/// Parse a yt-dlp size like `~100.5MiB` or `1.2GB` into bytes.
+pub fn parse_size(size: &str) -> Option<f64> {
+    let size = size.trim().trim_start_matches('~').trim();
+    let split = size.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (number, unit) = size.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let factor = match unit.trim() {
+        "B" => 1.0,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        "KB" | "kB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        _ => return None,
+    };
+    Some(number * factor)
+}
+
+fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Note the speed from a progress update of download `id`. At most one
+/// sample per [`SAMPLE_INTERVAL`] is kept.
+pub fn record(id: &str, speed: &str) {
+    let Some(bytes_per_sec) = parse_speed(speed) else { return };
+    let Ok(mut active) = ACTIVE.lock() else { return };
+    let now = Instant::now();
+    let sample = SpeedSample { t: now_unix(), bytes_per_sec };
+    match active.iter_mut().find(|r| r.id == id) {
+        Some(recording) => {
+            if now.duration_since(recording.last) < SAMPLE_INTERVAL {
+                return;
+            }
+            recording.last = now;
+            if recording.samples.len() == CAPACITY {
+                recording.samples.pop_front();
+            }
+            recording.samples.push_back(sample);
+        }
+        None => active.push(Recording {
+            id: id.to_string(),
+            last: now,
+            samples: VecDeque::from([sample]),
+        }),
+    }
+}
+
+fn stored(db: &Database, id: &str) -> Vec<SpeedSample> {
+    db.get_speed_history(id)
+        .unwrap_or_default()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Store the samples of a download whose yt-dlp run has ended.
+pub fn finish(db: &Database, id: &str) {
+    let recording = ACTIVE.lock().ok().and_then(|mut active| {
+        let index = active.iter().position(|r| r.id == id)?;
+        Some(active.swap_remove(index))
+    });
+    let Some(recording) = recording else { return };
+
+    let mut samples = stored(db, id);
+    samples.extend(recording.samples);
+    let excess = samples.len().saturating_sub(CAPACITY);
+    samples.drain(..excess);
+    match serde_json::to_string(&samples) {
+        Ok(json) => {
+            if let Err(e) = db.set_speed_history(id, &json) {
+                log::warn!("[speed_history] Failed to store samples of {}: {}", id, e);
+            }
+        }
+        Err(e) => log::warn!("[speed_history] Failed to encode samples of {}: {}", id, e),
+    }
+}
+
+/// Speed samples of a download, oldest first: what is stored plus what
+/// the current run has recorded so far.
+#[tauri::command]
+pub async fn get_speed_history(
+    db: tauri::State<'_, std::sync::Arc<Mutex<Database>>>,
+    id: String,
+) -> Result<Vec<SpeedSample>, String> {
+    let mut samples = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        stored(&db_lock, &id)
+    };
+    if let Ok(active) = ACTIVE.lock() {
+        if let Some(recording) = active.iter().find(|r| r.id == id) {
+            samples.extend(recording.samples.iter().copied());
+        }
+    }
+    let excess = samples.len().saturating_sub(CAPACITY);
+    samples.drain(..excess);
+    Ok(samples)
+}
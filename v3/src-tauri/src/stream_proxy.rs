@@ -0,0 +1,581 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::OnceCell;
+
+/// Local HTTP proxy for the built-in player.
+///
+/// Googlevideo (and most other CDNs yt-dlp resolves to) reject requests that
+/// lack the headers/cookies yt-dlp used during extraction, and the webview
+/// can't set those on a `<video src>`. Each upstream URL is registered here
+/// with its headers under an unguessable token and played back from
+/// `http://127.0.0.1:<port>/stream/<token>`; range requests are forwarded so
+/// seeking works. Only registered URLs are ever fetched, so this is not an
+/// open proxy.
+///
+/// For casting, a second listener on all interfaces is started on demand so
+/// renderers on the LAN can fetch the same tokens, plus downloaded files
+/// registered with [`StreamProxy::register_file`]. Tokens handed to a
+/// renderer are served to that renderer's address only. The listener
+/// prefers [`LAN_PORT`] so URLs handed out for longer (podcast feeds)
+/// survive a restart.
+pub struct StreamProxy {
+    port: OnceCell<u16>,
+    lan_port: OnceCell<u16>,
+    targets: Arc<Mutex<HashMap<String, StreamTarget>>>,
+}
+
+#[derive(Clone)]
+enum TargetSource {
+    Remote { url: String, headers: Vec<(String, String)> },
+    File(PathBuf),
+}
+
+#[derive(Clone)]
+struct StreamTarget {
+    source: TargetSource,
+    registered_at: Instant,
+    /// Registered under a caller-chosen token and kept past [`TARGET_TTL`].
+    pinned: bool,
+    /// The only address allowed to fetch it, e.g. a cast renderer.
+    peer: Option<IpAddr>,
+}
+
+/// Signed CDN URLs expire after ~6 hours, so older registrations are useless.
+const TARGET_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+/// Port the LAN listener tries first, falling back to any free one.
+pub const LAN_PORT: u16 = 9788;
+/// Upper bound on the request head we're willing to buffer.
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+/// Clients that haven't sent a full request head by then are dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// Upstream response headers passed through to the player.
+const FORWARDED_HEADERS: &[&str] = &["content-type", "content-length", "content-range", "accept-ranges", "last-modified", "etag"];
+/// Read size when serving local files.
+const FILE_CHUNK: usize = 256 * 1024;
+
+impl StreamProxy {
+    pub fn new() -> Self {
+        Self {
+            port: OnceCell::new(),
+            lan_port: OnceCell::new(),
+            targets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Register `url` (fetched with `headers`) and return the local URL that
+    /// serves it. The listener is started on first use.
+    pub async fn register(&self, url: &str, headers: Vec<(String, String)>) -> Result<String, String> {
+        let port = self.ensure_started().await?;
+        let token = self.insert(
+            TargetSource::Remote {
+                url: url.to_string(),
+                headers,
+            },
+            None,
+        )?;
+        Ok(format!("http://127.0.0.1:{}/stream/{}", port, token))
+    }
+
+    /// Register `url` for `peer` alone and return its token. Use
+    /// [`StreamProxy::lan_url`] to get the URL it fetches.
+    pub fn register_for(&self, url: &str, headers: Vec<(String, String)>, peer: IpAddr) -> Result<String, String> {
+        self.insert(
+            TargetSource::Remote {
+                url: url.to_string(),
+                headers,
+            },
+            Some(peer),
+        )
+    }
+
+    /// Register a local file for `peer` alone and return its token. Use
+    /// [`StreamProxy::lan_url`] to get the URL it fetches.
+    pub fn register_file(&self, path: PathBuf, peer: IpAddr) -> Result<String, String> {
+        self.insert(TargetSource::File(path), Some(peer))
+    }
+
+    /// A new token for whatever `token` serves, for `peer` alone.
+    pub fn share(&self, token: &str, peer: IpAddr) -> Result<String, String> {
+        let source = {
+            let targets = self.targets.lock().map_err(|e| e.to_string())?;
+            targets
+                .get(token)
+                .map(|t| t.source.clone())
+                .ok_or_else(|| "Stream is no longer registered".to_string())?
+        };
+        self.insert(source, Some(peer))
+    }
+
+    /// Stop serving `token`.
+    pub fn revoke(&self, token: &str) {
+        if let Ok(mut targets) = self.targets.lock() {
+            targets.remove(token);
+        }
+    }
+
+    /// Serve a local file under `token` until [`StreamProxy::unpin`] or the
+    /// app quits, replacing whatever was registered under it.
+    pub fn pin_file(&self, token: &str, path: PathBuf) -> Result<(), String> {
+        let mut targets = self.targets.lock().map_err(|e| e.to_string())?;
+        targets.insert(
+            token.to_string(),
+            StreamTarget {
+                source: TargetSource::File(path),
+                registered_at: Instant::now(),
+                pinned: true,
+                peer: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Drop the pinned tokens `keep` says no to.
+    pub fn unpin(&self, keep: impl Fn(&str) -> bool) {
+        if let Ok(mut targets) = self.targets.lock() {
+            targets.retain(|token, t| !t.pinned || keep(token));
+        }
+    }
+
+    /// The token of a URL previously returned by [`StreamProxy::register`],
+    /// if `url` is one of ours and still registered.
+    pub fn token_for_local_url(&self, url: &str) -> Option<String> {
+        let port = self.port.get()?;
+        let token = url.strip_prefix(&format!("http://127.0.0.1:{}/stream/", port))?;
+        let targets = self.targets.lock().ok()?;
+        targets.contains_key(token).then(|| token.to_string())
+    }
+
+    /// URL for `token` on the LAN listener, advertised under `host_ip`
+    /// (the address of the interface facing the device that will fetch it).
+    pub async fn lan_url(&self, token: &str, host_ip: std::net::IpAddr) -> Result<String, String> {
+        let port = self.ensure_lan_started().await?;
+        Ok(format!("http://{}:{}/stream/{}", host_ip, port, token))
+    }
+
+    fn insert(&self, source: TargetSource, peer: Option<IpAddr>) -> Result<String, String> {
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let mut targets = self.targets.lock().map_err(|e| e.to_string())?;
+        targets.retain(|_, t| t.pinned || t.registered_at.elapsed() < TARGET_TTL);
+        targets.insert(
+            token.clone(),
+            StreamTarget {
+                source,
+                registered_at: Instant::now(),
+                pinned: false,
+                peer: peer.map(|p| p.to_canonical()),
+            },
+        );
+        Ok(token)
+    }
+
+    async fn ensure_started(&self) -> Result<u16, String> {
+        let targets = self.targets.clone();
+        self.port
+            .get_or_try_init(|| spawn_listener("127.0.0.1:0", targets))
+            .await
+            .copied()
+    }
+
+    async fn ensure_lan_started(&self) -> Result<u16, String> {
+        let targets = self.targets.clone();
+        self.lan_port
+            .get_or_try_init(|| async move {
+                match spawn_listener(("0.0.0.0", LAN_PORT), targets.clone()).await {
+                    Ok(port) => Ok(port),
+                    Err(e) => {
+                        log::warn!("[stream_proxy] Port {} unavailable ({}), using any free port", LAN_PORT, e);
+                        spawn_listener(("0.0.0.0", 0), targets).await
+                    }
+                }
+            })
+            .await
+            .copied()
+    }
+}
+
+async fn spawn_listener(
+    addr: impl tokio::net::ToSocketAddrs,
+    targets: Arc<Mutex<HashMap<String, StreamTarget>>>,
+) -> Result<u16, String> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| format!("Failed to start stream proxy: {}", e))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    let client = crate::proxy::client_builder()
+        .connect_timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    log::info!("[stream_proxy] Listening on {}", listener.local_addr().map_err(|e| e.to_string())?);
+    tokio::spawn(async move {
+        loop {
+            let (socket, remote) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    log::warn!("[stream_proxy] accept failed: {}", e);
+                    continue;
+                }
+            };
+            let targets = targets.clone();
+            let client = client.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(socket, remote.ip(), &targets, &client).await {
+                    // Players routinely drop connections mid-body when seeking
+                    log::debug!("[stream_proxy] connection ended: {}", e);
+                }
+            });
+        }
+    });
+    Ok(port)
+}
+
+struct RequestHead {
+    method: String,
+    path: String,
+    range: Option<String>,
+}
+
+/// Read the request line and headers, at most [`MAX_REQUEST_HEAD`] bytes of
+/// them. `None` when the head doesn't fit.
+async fn read_head(reader: &mut BufReader<TcpStream>) -> Result<Option<RequestHead>, String> {
+    let mut limited = reader.take(MAX_REQUEST_HEAD as u64);
+
+    let mut request_line = String::new();
+    limited.read_line(&mut request_line).await.map_err(|e| e.to_string())?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let path = parts.next().unwrap_or("").to_string();
+
+    let mut range: Option<String> = None;
+    let mut line = request_line;
+    loop {
+        if !line.ends_with('\n') && limited.limit() == 0 {
+            return Ok(None);
+        }
+        line.clear();
+        let n = limited.read_line(&mut line).await.map_err(|e| e.to_string())?;
+        if n == 0 && limited.limit() == 0 {
+            return Ok(None);
+        }
+        if n == 0 || line == "\r\n" || line == "\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("range") {
+                range = Some(value.trim().to_string());
+            }
+        }
+    }
+    Ok(Some(RequestHead { method, path, range }))
+}
+
+async fn handle_connection(
+    socket: TcpStream,
+    remote: IpAddr,
+    targets: &Arc<Mutex<HashMap<String, StreamTarget>>>,
+    client: &reqwest::Client,
+) -> Result<(), String> {
+    let mut reader = BufReader::new(socket);
+    let RequestHead { method, path, range } =
+        match tokio::time::timeout(REQUEST_TIMEOUT, read_head(&mut reader)).await {
+            Ok(Ok(Some(head))) => head,
+            Ok(Ok(None)) => {
+                return write_status(reader.get_mut(), 431, "Request Header Fields Too Large").await;
+            }
+            Ok(Err(e)) => return Err(e),
+            Err(_) => return Err("Timed out reading request".to_string()),
+        };
+
+    let socket = reader.get_mut();
+    if method != "GET" && method != "HEAD" {
+        return write_status(socket, 405, "Method Not Allowed").await;
+    }
+
+    let target = path
+        .strip_prefix("/stream/")
+        .and_then(|token| targets.lock().ok()?.get(token).cloned())
+        .filter(|t| t.peer.map_or(true, |peer| peer == remote.to_canonical()));
+    let Some(target) = target else {
+        return write_status(socket, 404, "Not Found").await;
+    };
+
+    match target.source {
+        TargetSource::Remote { url, headers } => {
+            proxy_remote(socket, client, &method, &url, &headers, range.as_deref()).await
+        }
+        TargetSource::File(path) => serve_file(socket, &method, &path, range.as_deref()).await,
+    }
+}
+
+async fn proxy_remote(
+    socket: &mut TcpStream,
+    client: &reqwest::Client,
+    method: &str,
+    url: &str,
+    headers: &[(String, String)],
+    range: Option<&str>,
+) -> Result<(), String> {
+    let mut request = if method == "HEAD" { client.head(url) } else { client.get(url) };
+    for (name, value) in headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+    if let Some(range) = range {
+        request = request.header("Range", range);
+    }
+
+    let mut upstream = match request.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("[stream_proxy] upstream request failed: {}", e);
+            return write_status(socket, 502, "Bad Gateway").await;
+        }
+    };
+
+    let status = upstream.status();
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or("")
+    );
+    for name in FORWARDED_HEADERS {
+        if let Some(value) = upstream.headers().get(*name).and_then(|v| v.to_str().ok()) {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+    }
+    if !upstream.headers().contains_key("accept-ranges") {
+        head.push_str("accept-ranges: bytes\r\n");
+    }
+    head.push_str("access-control-allow-origin: *\r\nconnection: close\r\n\r\n");
+    socket.write_all(head.as_bytes()).await.map_err(|e| e.to_string())?;
+
+    if method == "GET" {
+        while let Some(chunk) = upstream.chunk().await.map_err(|e| e.to_string())? {
+            socket.write_all(&chunk).await.map_err(|e| e.to_string())?;
+        }
+    }
+    socket.flush().await.map_err(|e| e.to_string())
+}
+
+async fn serve_file(
+    socket: &mut TcpStream,
+    method: &str,
+    path: &std::path::Path,
+    range: Option<&str>,
+) -> Result<(), String> {
+    let mut file = match tokio::fs::File::open(path).await {
+        Ok(file) => file,
+        Err(_) => return write_status(socket, 404, "Not Found").await,
+    };
+    let size = file.metadata().await.map_err(|e| e.to_string())?.len();
+
+    let requested = range.and_then(|r| parse_byte_range(r, size));
+    if range.is_some() && requested.is_none() {
+        let response = format!(
+            "HTTP/1.1 416 Range Not Satisfiable\r\ncontent-range: bytes */{}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            size
+        );
+        return socket.write_all(response.as_bytes()).await.map_err(|e| e.to_string());
+    }
+    let (start, end) = requested.unwrap_or((0, size.saturating_sub(1)));
+    let length = if size == 0 { 0 } else { end - start + 1 };
+
+    let mut head = if requested.is_some() {
+        format!(
+            "HTTP/1.1 206 Partial Content\r\ncontent-range: bytes {}-{}/{}\r\n",
+            start, end, size
+        )
+    } else {
+        "HTTP/1.1 200 OK\r\n".to_string()
+    };
+    head.push_str(&format!(
+        "content-type: {}\r\ncontent-length: {}\r\naccept-ranges: bytes\r\n\
+         access-control-allow-origin: *\r\ntransferMode.dlna.org: Streaming\r\nconnection: close\r\n\r\n",
+        content_type_for_path(path),
+        length
+    ));
+    socket.write_all(head.as_bytes()).await.map_err(|e| e.to_string())?;
+
+    if method == "GET" && length > 0 {
+        file.seek(std::io::SeekFrom::Start(start)).await.map_err(|e| e.to_string())?;
+        let mut remaining = length;
+        let mut buf = vec![0u8; FILE_CHUNK];
+        while remaining > 0 {
+            let want = (remaining as usize).min(FILE_CHUNK);
+            let n = file.read(&mut buf[..want]).await.map_err(|e| e.to_string())?;
+            if n == 0 {
+                break;
+            }
+            socket.write_all(&buf[..n]).await.map_err(|e| e.to_string())?;
+            remaining -= n as u64;
+        }
+    }
+    socket.flush().await.map_err(|e| e.to_string())
+}
+
+/// Parse a single `bytes=start-end` / `bytes=start-` / `bytes=-suffix` range
+/// into inclusive offsets. Multi-range requests aren't supported.
+fn parse_byte_range(header: &str, size: u64) -> Option<(u64, u64)> {
+    let spec = header.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') || size == 0 {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix: u64 = suffix.parse().ok()?;
+            (size.saturating_sub(suffix), size - 1)
+        }
+        (start, "") => (start.parse().ok()?, size - 1),
+        (start, end) => (start.parse().ok()?, end.parse::<u64>().ok()?.min(size - 1)),
+    };
+    (start <= end && start < size).then_some((start, end))
+}
+
+pub(crate) fn content_type_for_path(path: &std::path::Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|v| v.to_str())
+        .map(|v| v.to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "mp4" | "m4v" => "video/mp4",
+        "webm" => "video/webm",
+        "mkv" => "video/x-matroska",
+        "mov" => "video/quicktime",
+        "mp3" => "audio/mpeg",
+        "m4a" | "aac" => "audio/mp4",
+        "opus" | "ogg" => "audio/ogg",
+        "flac" => "audio/flac",
+        "wav" => "audio/wav",
+        "xml" | "rss" => "application/rss+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+async fn write_status(socket: &mut TcpStream, code: u16, reason: &str) -> Result<(), String> {
+    let response = format!(
+        "HTTP/1.1 {} {}\r\ncontent-length: 0\r\naccess-control-allow-origin: *\r\nconnection: close\r\n\r\n",
+        code, reason
+    );
+    socket.write_all(response.as_bytes()).await.map_err(|e| e.to_string())
+}
+
+/// Cached `get_stream_url` results, so reopening a video doesn't rerun
+/// yt-dlp while its CDN URLs are still good.
+pub struct StreamInfoCache {
+    entries: Mutex<HashMap<String, serde_json::Value>>,
+}
+
+/// Entries are dropped this long before their URLs actually expire, so the
+/// player never starts on a URL that dies mid-video.
+const EXPIRY_MARGIN_SECS: i64 = 10 * 60;
+/// Lifetime assumed for URLs that don't carry an `expire` parameter.
+const DEFAULT_STREAM_TTL_SECS: i64 = 30 * 60;
+
+impl StreamInfoCache {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `cookie_profile` is the id of the profile used, empty for none.
+    pub fn key(url: &str, proxy: bool, audio_only: bool, cookie_profile: &str) -> String {
+        format!("{}|{}|{}|{}", url.trim(), proxy, audio_only, cookie_profile)
+    }
+
+    /// The cached info with a fresh `expiresIn`, if still usable.
+    pub fn get(&self, key: &str) -> Option<serde_json::Value> {
+        let mut entries = self.entries.lock().ok()?;
+        let now = chrono::Utc::now().timestamp();
+        entries.retain(|_, info| info["cacheUntil"].as_i64().unwrap_or(0) > now);
+        let mut info = entries.get(key)?.clone();
+        with_expires_in(&mut info, now);
+        Some(info)
+    }
+
+    /// Store `info` and return it with `expiresIn` filled in.
+    pub fn insert(&self, key: String, mut info: serde_json::Value) -> serde_json::Value {
+        let now = chrono::Utc::now().timestamp();
+        let expires_at = info["expiresAt"].as_i64().filter(|e| *e > 0);
+        let cache_until = match expires_at {
+            Some(at) => at - EXPIRY_MARGIN_SECS,
+            None => now + DEFAULT_STREAM_TTL_SECS,
+        };
+        info["cacheUntil"] = serde_json::json!(cache_until);
+        with_expires_in(&mut info, now);
+        if cache_until > now {
+            if let Ok(mut entries) = self.entries.lock() {
+                entries.insert(key, info.clone());
+            }
+        }
+        info
+    }
+
+    pub fn remove(&self, key: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(key);
+        }
+    }
+}
+
+fn with_expires_in(info: &mut serde_json::Value, now: i64) {
+    let expires_in = match info["expiresAt"].as_i64().filter(|e| *e > 0) {
+        Some(at) => (at - now).max(0),
+        None => 0,
+    };
+    info["expiresIn"] = serde_json::json!(expires_in);
+}
+
+/// Unix time a signed CDN URL stops working, from its `expire` query
+/// parameter (googlevideo) if it has one.
+pub fn url_expiry(url: &str) -> Option<i64> {
+    let parsed = url::Url::parse(url).ok()?;
+    let expire = parsed
+        .query_pairs()
+        .find(|(k, _)| k == "expire")
+        .and_then(|(_, v)| v.parse::<i64>().ok());
+    expire.or_else(|| {
+        // Some manifests put it in the path: /expire/<ts>/
+        let mut segments = parsed.path_segments()?;
+        segments.find(|s| *s == "expire")?;
+        segments.next()?.parse().ok()
+    })
+}
+
+/// Request headers yt-dlp reports for a format: its `http_headers` plus a
+/// `Cookie` header built from the `cookies` field (a `Set-Cookie`-style
+/// string, present when cookies were used for extraction).
+pub fn headers_for_format(format: &serde_json::Value) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = format["http_headers"]
+        .as_object()
+        .map(|map| {
+            map.iter()
+                .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if let Some(cookies) = format["cookies"].as_str() {
+        const ATTRIBUTES: &[&str] = &["domain", "path", "expires", "max-age", "secure", "httponly", "samesite"];
+        let pairs: Vec<&str> = cookies
+            .split(';')
+            .map(str::trim)
+            .filter(|part| {
+                let name = part.split('=').next().unwrap_or("").trim().to_lowercase();
+                part.contains('=') && !ATTRIBUTES.contains(&name.as_str())
+            })
+            .collect();
+        if !pairs.is_empty() {
+            headers.retain(|(k, _)| !k.eq_ignore_ascii_case("cookie"));
+            headers.push(("Cookie".to_string(), pairs.join("; ")));
+        }
+    }
+
+    headers
+}
@@ -0,0 +1,317 @@
+//! Importing channel subscriptions as RSS feeds, and exporting the feed
+//! list for other apps.
+//!
+//! Two exports are understood: the `subscriptions.csv` of a Google Takeout
+//! (`Channel Id,Channel Url,Channel Title`, with localized headers) and
+//! NewPipe's `subscriptions.json`. Each channel goes through
+//! [`rss::normalize_feed_url`] like a feed added by hand; channels that are
+//! already subscribed are skipped. Progress is reported with
+//! `subscription-import-progress` events and failures are listed in the
+//! result rather than stopping the import.
+//!
+//! Exports are written as NewPipe's `subscriptions.json` or as a FreeTube
+//! profile (`profiles.db`, one JSON object per line). Only YouTube channel
+//! feeds can be carried over; other feeds are left out.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::db::Database;
+use crate::error::AppError;
+use crate::rss;
+
+/// One channel from an export.
+#[derive(Debug, Clone)]
+struct Subscription {
+    url: String,
+    title: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportFailure {
+    pub title: String,
+    pub url: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    /// "takeout" or "newpipe".
+    pub format: String,
+    pub total: usize,
+    pub imported: usize,
+    /// Already subscribed.
+    pub skipped: usize,
+    pub failures: Vec<ImportFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportProgress {
+    done: usize,
+    total: usize,
+    imported: usize,
+    skipped: usize,
+    failed: usize,
+    current: String,
+}
+
+/// Split one CSV line, honouring double-quoted fields.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Takeout's `subscriptions.csv`. The header row is localized, so rows are
+/// recognized by their channel id instead.
+fn parse_takeout(text: &str) -> Vec<Subscription> {
+    text.lines()
+        .map(csv_fields)
+        .filter_map(|fields| {
+            let id = fields.first()?.trim();
+            if !id.starts_with("UC") || id.len() != 24 {
+                return None;
+            }
+            let url = fields
+                .get(1)
+                .map(|u| u.trim().to_string())
+                .filter(|u| !u.is_empty())
+                .unwrap_or_else(|| format!("https://www.youtube.com/channel/{}", id));
+            let title = fields.get(2).map(|t| t.trim().to_string()).unwrap_or_default();
+            Some(Subscription { url, title })
+        })
+        .collect()
+}
+
+/// NewPipe's `subscriptions.json`.
+fn parse_newpipe(json: &serde_json::Value) -> Vec<Subscription> {
+    json["subscriptions"]
+        .as_array()
+        .map(|subscriptions| {
+            subscriptions
+                .iter()
+                .filter_map(|s| {
+                    let url = s["url"].as_str()?.trim().to_string();
+                    let title = s["name"].as_str().unwrap_or_default().trim().to_string();
+                    (!url.is_empty()).then_some(Subscription { url, title })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_export(text: &str) -> Result<(&'static str, Vec<Subscription>), String> {
+    let text = text.trim_start_matches('\u{feff}');
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(text) {
+        if json.get("subscriptions").is_some() {
+            return Ok(("newpipe", parse_newpipe(&json)));
+        }
+        return Err("This JSON file is not a NewPipe subscriptions export".to_string());
+    }
+    let subscriptions = parse_takeout(text);
+    if subscriptions.is_empty() {
+        return Err("No channels found. Use Takeout's subscriptions.csv or NewPipe's subscriptions.json".to_string());
+    }
+    Ok(("takeout", subscriptions))
+}
+
+/// Subscribe to every channel in the export at `path`.
+#[tauri::command]
+pub async fn import_subscriptions(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    path: String,
+) -> Result<ImportReport, AppError> {
+    let text = std::fs::read_to_string(path.trim()).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let (format, subscriptions) = parse_export(&text)?;
+
+    let (policy, mut known) = {
+        let db_lock = db.lock()?;
+        let known: HashSet<String> = db_lock
+            .get_feeds()?
+            .into_iter()
+            .map(|f| f.url)
+            .collect();
+        (rss::FeedPolicy::load(&db_lock), known)
+    };
+
+    let total = subscriptions.len();
+    let mut report = ImportReport {
+        format: format.to_string(),
+        total,
+        imported: 0,
+        skipped: 0,
+        failures: Vec::new(),
+    };
+    for (done, subscription) in subscriptions.into_iter().enumerate() {
+        let _ = app.emit(
+            "subscription-import-progress",
+            ImportProgress {
+                done,
+                total,
+                imported: report.imported,
+                skipped: report.skipped,
+                failed: report.failures.len(),
+                current: subscription.title.clone(),
+            },
+        );
+
+        let feed_url = match rss::normalize_feed_url(&subscription.url).await {
+            Ok(url) => url,
+            Err(e) => {
+                report.failures.push(ImportFailure {
+                    title: subscription.title,
+                    url: subscription.url,
+                    error: e.to_string(),
+                });
+                continue;
+            }
+        };
+        if known.contains(&feed_url) {
+            report.skipped += 1;
+            continue;
+        }
+        if let Err(e) = policy.check(&feed_url) {
+            report.failures.push(ImportFailure {
+                title: subscription.title,
+                url: subscription.url,
+                error: e.to_string(),
+            });
+            continue;
+        }
+
+        let title = if subscription.title.is_empty() { feed_url.clone() } else { subscription.title.clone() };
+        let id = uuid::Uuid::new_v4().to_string();
+        let inserted = db
+            .lock()
+            .map_err(|e| e.to_string())
+            .and_then(|db_lock| db_lock.insert_feed(&id, &feed_url, &title, "").map_err(|e| e.to_string()));
+        match inserted {
+            Ok(()) => {
+                known.insert(feed_url);
+                report.imported += 1;
+            }
+            Err(e) => report.failures.push(ImportFailure {
+                title: subscription.title,
+                url: subscription.url,
+                error: e,
+            }),
+        }
+    }
+
+    let _ = app.emit(
+        "subscription-import-progress",
+        ImportProgress {
+            done: total,
+            total,
+            imported: report.imported,
+            skipped: report.skipped,
+            failed: report.failures.len(),
+            current: String::new(),
+        },
+    );
+    log::info!(
+        "[import] {} subscriptions: {} imported, {} already subscribed, {} failed",
+        report.format,
+        report.imported,
+        report.skipped,
+        report.failures.len()
+    );
+    Ok(report)
+}
+
+/// Formats accepted by [`export_subscriptions`].
+pub const EXPORT_FORMATS: &[&str] = &["newpipe", "freetube"];
+
+/// Bumped only when NewPipe changes its import format.
+const NEWPIPE_APP_VERSION: &str = "0.27.6";
+const NEWPIPE_APP_VERSION_INT: u32 = 1000;
+
+/// Write the YouTube channels among the feeds to `path` in `format` (see
+/// [`EXPORT_FORMATS`]). Returns the number of channels written.
+#[tauri::command]
+pub async fn export_subscriptions(
+    db: State<'_, Arc<Mutex<Database>>>,
+    format: String,
+    path: String,
+) -> Result<usize, AppError> {
+    let format = format.trim().to_lowercase();
+    if !EXPORT_FORMATS.contains(&format.as_str()) {
+        return Err(AppError::InvalidArgument(format!("Unknown export format: {}", format)));
+    }
+    let feeds = {
+        let db_lock = db.lock()?;
+        db_lock.get_feeds()?
+    };
+
+    let mut seen = HashSet::new();
+    let channels: Vec<(String, String, String)> = feeds
+        .into_iter()
+        .filter_map(|feed| {
+            let channel_id = rss::extract_channel_id_from_feed_url(&feed.url)?;
+            let name = if feed.channel_name.is_empty() { feed.title } else { feed.channel_name };
+            Some((channel_id, name, feed.channel_avatar))
+        })
+        .filter(|(channel_id, _, _)| seen.insert(channel_id.clone()))
+        .collect();
+
+    let contents = if format == "newpipe" {
+        let subscriptions: Vec<serde_json::Value> = channels
+            .iter()
+            .map(|(channel_id, name, _)| {
+                serde_json::json!({
+                    "service_id": 0,
+                    "url": format!("https://www.youtube.com/channel/{}", channel_id),
+                    "name": name,
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&serde_json::json!({
+            "app_version": NEWPIPE_APP_VERSION,
+            "app_version_int": NEWPIPE_APP_VERSION_INT,
+            "subscriptions": subscriptions,
+        }))?
+    } else {
+        // FreeTube imports its own profile database; "allChannels" is the
+        // profile every subscription lives in
+        let subscriptions: Vec<serde_json::Value> = channels
+            .iter()
+            .map(|(channel_id, name, thumbnail)| {
+                serde_json::json!({ "id": channel_id, "name": name, "thumbnail": thumbnail })
+            })
+            .collect();
+        let profile = serde_json::json!({
+            "_id": "allChannels",
+            "name": "All Channels",
+            "bgColor": "#000000",
+            "textColor": "#FFFFFF",
+            "subscriptions": subscriptions,
+        });
+        format!("{}\n", profile)
+    };
+
+    let path = path.trim();
+    std::fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    log::info!("[export] Wrote {} subscriptions as {} to {}", channels.len(), format, path);
+    Ok(channels.len())
+}
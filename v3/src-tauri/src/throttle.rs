@@ -0,0 +1,226 @@
+//! YouTube throttling detection and player client switching.
+//!
+//! When YouTube throttles a stream, its speed collapses to around 50-80
+//! KB/s and stays there for the rest of the file. A YouTube download whose
+//! speed stays below [`THROTTLED_SPEED`] for [`THROTTLE_WINDOW`], with at
+//! least [`MIN_REMAINING`] still to go, is stopped and started again with
+//! another player client (`--extractor-args youtube:player_client=...`),
+//! trying [`PLAYER_CLIENTS`] in turn. `--continue` keeps what was already
+//! downloaded. Every switch is logged, and the clients tried are noted on
+//! the download.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::db::Database;
+use crate::download::{self, DownloadProgress, QualityPreference};
+use crate::error::AppResult;
+use crate::speed_history::{parse_size, parse_speed};
+
+const THROTTLED_SPEED: f64 = 100.0 * 1024.0;
+const THROTTLE_WINDOW: Duration = Duration::from_secs(20);
+/// Short streams (usually the audio) finish before a switch would pay off.
+const MIN_REMAINING: f64 = 10.0 * 1024.0 * 1024.0;
+
+/// Tried in this order once the default client is throttled.
+pub const PLAYER_CLIENTS: &[&str] = &["android", "ios", "tv", "web_safari"];
+
+fn is_youtube(url: &str) -> bool {
+    let Ok(parsed) = url::Url::parse(url.trim()) else { return false };
+    let host = parsed.host_str().unwrap_or_default();
+    host == "youtube.com" || host.ends_with(".youtube.com") || host == "youtu.be"
+}
+
+/// A player client picked by the user (custom flags or the extractor
+/// arguments setting) is left alone.
+fn sets_player_client(args: &[String]) -> bool {
+    args.iter().any(|arg| arg.contains("player_client")) || crate::extractor_args::sets("player_client")
+}
+
+/// Watches the progress of one yt-dlp run for the throttling signature.
+#[derive(Default)]
+struct Detector {
+    slow_since: Option<Instant>,
+}
+
+impl Detector {
+    /// Whether the download has been throttled for [`THROTTLE_WINDOW`].
+    fn update(&mut self, progress: &DownloadProgress) -> bool {
+        let remaining = parse_size(&progress.total)
+            .map(|total| total * (100.0 - progress.progress.clamp(0.0, 100.0)) / 100.0);
+        let slow = parse_speed(&progress.speed).is_some_and(|speed| speed > 0.0 && speed < THROTTLED_SPEED)
+            && remaining.is_some_and(|remaining| remaining > MIN_REMAINING);
+        if !slow {
+            self.slow_since = None;
+            return false;
+        }
+        self.slow_since.get_or_insert_with(Instant::now).elapsed() >= THROTTLE_WINDOW
+    }
+}
+
+/// One yt-dlp run that is stopped once it looks throttled. Returns the
+/// result and whether it was stopped for throttling.
+#[allow(clippy::too_many_arguments)]
+async fn run_watched(
+    ytdlp: &str,
+    ffmpeg: &str,
+    url: &str,
+    output_dir: &str,
+    format_id: Option<&str>,
+    quality: &QualityPreference,
+    extra_args: &[String],
+    progress_tx: tokio::sync::mpsc::Sender<DownloadProgress>,
+    cancel_rx: tokio::sync::watch::Receiver<bool>,
+    id: &str,
+    file_template: Option<&str>,
+    log_file: Option<std::path::PathBuf>,
+) -> (AppResult<String>, bool) {
+    let (inner_tx, mut inner_rx) = tokio::sync::mpsc::channel::<DownloadProgress>(32);
+    let (stop_tx, stop_rx) = tokio::sync::watch::channel(*cancel_rx.borrow());
+    let stop_tx = Arc::new(stop_tx);
+    let throttled = Arc::new(AtomicBool::new(false));
+
+    // The user cancelling stops the run as before
+    let cancel_forward = {
+        let stop_tx = stop_tx.clone();
+        let mut cancel_rx = cancel_rx.clone();
+        tokio::spawn(async move {
+            while cancel_rx.changed().await.is_ok() {
+                if *cancel_rx.borrow() {
+                    let _ = stop_tx.send(true);
+                }
+            }
+        })
+    };
+    {
+        let throttled = throttled.clone();
+        tokio::spawn(async move {
+            let mut detector = Detector::default();
+            while let Some(progress) = inner_rx.recv().await {
+                if detector.update(&progress) && !throttled.swap(true, Ordering::SeqCst) {
+                    let _ = stop_tx.send(true);
+                }
+                let _ = progress_tx.send(progress).await;
+            }
+        });
+    }
+
+    let result = download::run_download(
+        ytdlp,
+        ffmpeg,
+        url,
+        output_dir,
+        format_id,
+        quality,
+        extra_args,
+        inner_tx,
+        stop_rx,
+        id.to_string(),
+        file_template,
+        log_file,
+    )
+    .await;
+    cancel_forward.abort();
+    let stopped = result.is_err() && throttled.load(Ordering::SeqCst) && !*cancel_rx.borrow();
+    (result, stopped)
+}
+
+/// [`download::run_download`], switching the YouTube player client while
+/// the download is throttled. Other sites, and downloads with a player
+/// client already chosen by the user, run unwatched.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_download(
+    db: &Arc<Mutex<Database>>,
+    ytdlp: &str,
+    ffmpeg: &str,
+    url: &str,
+    output_dir: &str,
+    format_id: Option<&str>,
+    quality: &QualityPreference,
+    extra_args: &[String],
+    progress_tx: tokio::sync::mpsc::Sender<DownloadProgress>,
+    cancel_rx: tokio::sync::watch::Receiver<bool>,
+    id: &str,
+    file_template: Option<&str>,
+    log_file: Option<std::path::PathBuf>,
+) -> AppResult<String> {
+    if !is_youtube(url) || sets_player_client(extra_args) {
+        return download::run_download(
+            ytdlp,
+            ffmpeg,
+            url,
+            output_dir,
+            format_id,
+            quality,
+            extra_args,
+            progress_tx,
+            cancel_rx,
+            id.to_string(),
+            file_template,
+            log_file,
+        )
+        .await;
+    }
+
+    let mut args = extra_args.to_vec();
+    let mut tried: Vec<&str> = Vec::new();
+    for client in PLAYER_CLIENTS.iter().copied().map(Some).chain([None]) {
+        let (result, stopped) = run_watched(
+            ytdlp,
+            ffmpeg,
+            url,
+            output_dir,
+            format_id,
+            quality,
+            &args,
+            progress_tx.clone(),
+            cancel_rx.clone(),
+            id,
+            file_template,
+            log_file.clone(),
+        )
+        .await;
+        if !stopped {
+            if result.is_ok() && !tried.is_empty() {
+                if let Ok(db_lock) = db.lock() {
+                    let note = format!("Throttled, switched player client: {}", tried.join(", "));
+                    let _ = db_lock.set_download_note(id, &note);
+                }
+            }
+            return result;
+        }
+        let Some(client) = client else { break };
+        log::info!("[throttle] {} is throttled, retrying with the {} player client", id, client);
+        tried.push(client);
+        args = extra_args.to_vec();
+        args.push("--extractor-args".to_string());
+        args.push(format!("youtube:player_client={}", client));
+    }
+
+    // Every client was throttled too; finish at whatever speed we get
+    log::warn!(
+        "[throttle] {} still throttled after trying {}, continuing without switching",
+        id,
+        tried.join(", ")
+    );
+    if let Ok(db_lock) = db.lock() {
+        let note = format!("Throttled with every player client tried: {}", tried.join(", "));
+        let _ = db_lock.set_download_note(id, &note);
+    }
+    download::run_download(
+        ytdlp,
+        ffmpeg,
+        url,
+        output_dir,
+        format_id,
+        quality,
+        extra_args,
+        progress_tx,
+        cancel_rx,
+        id.to_string(),
+        file_template,
+        log_file,
+    )
+    .await
+}
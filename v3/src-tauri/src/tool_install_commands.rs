@@ -1,7 +1,255 @@
-use tauri::{AppHandle, Emitter};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
 
+use crate::db::Database;
 use crate::download;
 
+/// GitHub repository yt-dlp is installed/updated from, per the
+/// `ytdlp_channel` setting. Nightly and master builds are published to
+/// separate repos with the same asset names as stable releases.
+fn ytdlp_release_repo(app: &AppHandle) -> &'static str {
+    match app_setting(app, "ytdlp_channel").as_str() {
+        "nightly" => "yt-dlp/yt-dlp-nightly-builds",
+        "master" => "yt-dlp/yt-dlp-master-builds",
+        _ => "yt-dlp/yt-dlp",
+    }
+}
+
+fn app_setting(app: &AppHandle, key: &str) -> String {
+    app.try_state::<Arc<Mutex<Database>>>()
+        .and_then(|db| db.lock().ok().and_then(|db| db.get_setting(key).unwrap_or(None)))
+        .unwrap_or_default()
+}
+
+/// Where a tool file can be fetched from.
+#[derive(Debug, Clone)]
+#[cfg_attr(target_os = "android", allow(dead_code))]
+pub(crate) enum ToolSource {
+    Remote(String),
+    /// A file in a local folder or network share (offline installs).
+    Local(std::path::PathBuf),
+}
+
+#[cfg_attr(target_os = "android", allow(dead_code))]
+impl ToolSource {
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            ToolSource::Remote(url) => url.clone(),
+            ToolSource::Local(path) => path.display().to_string(),
+        }
+    }
+}
+
+/// Candidate sources for `asset`: every entry of the `<tool>_mirrors`
+/// setting (newline or comma separated HTTPS base URLs or local folders) in
+/// order, then `official` if given. A mirror only has to serve the same
+/// file names as the official download, e.g. `https://mirror.example/yt-dlp`
+/// or `D:\offline\tools`. Files are checked against a checksum pinned in
+/// the matching `<tool>_checksums` setting or else the checksums of the
+/// official release, never ones the mirror serves.
+pub(crate) fn tool_sources(app: &AppHandle, mirrors_key: &str, asset: &str, official: Option<&str>) -> Vec<ToolSource> {
+    let mut sources: Vec<ToolSource> = app_setting(app, mirrors_key)
+        .split(['\n', ','])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            if entry.starts_with("https://") {
+                Some(ToolSource::Remote(format!("{}/{}", entry.trim_end_matches('/'), asset)))
+            } else if entry.starts_with("http://") {
+                log::warn!("[tool_sources] Ignoring mirror '{}' in {}: only HTTPS mirrors are used", entry, mirrors_key);
+                None
+            } else if let Some(path) = entry.strip_prefix("file://") {
+                Some(ToolSource::Local(std::path::Path::new(path).join(asset)))
+            } else if std::path::Path::new(entry).is_absolute() {
+                Some(ToolSource::Local(std::path::Path::new(entry).join(asset)))
+            } else {
+                log::warn!("[tool_sources] Ignoring mirror '{}' in {}: not a URL or absolute path", entry, mirrors_key);
+                None
+            }
+        })
+        .collect();
+    if let Some(url) = official {
+        sources.push(ToolSource::Remote(url.to_string()));
+    }
+    sources
+}
+
+/// Fetch a tool file into `dest` from the first source that works, in
+/// order. Returns the file and the source it came from.
+#[cfg(not(target_os = "android"))]
+pub(crate) async fn fetch_tool_file(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    sources: &[ToolSource],
+    dest: &std::path::Path,
+    tool: &str,
+) -> Result<(DownloadedFile, ToolSource), String> {
+    let mut errors = Vec::new();
+    for source in sources {
+        log::info!("[fetch_tool_file] Fetching {} from {}", tool, source.describe());
+        let result = match source {
+            ToolSource::Remote(url) => download_to_file(app, client, url, dest, tool).await,
+            ToolSource::Local(path) => copy_local_file(path, dest).await,
+        };
+        match result {
+            Ok(file) => return Ok((file, source.clone())),
+            Err(e) => {
+                log::warn!("[fetch_tool_file] {} failed: {}", source.describe(), e);
+                errors.push(format!("{}: {}", source.describe(), e));
+            }
+        }
+    }
+    if errors.is_empty() {
+        return Err(format!("No download source configured for {}", tool));
+    }
+    Err(format!("All download sources failed for {}:\n{}", tool, errors.join("\n")))
+}
+
+/// Read a small text file (checksum list) from a source.
+#[cfg(not(target_os = "android"))]
+async fn read_tool_text(client: &reqwest::Client, source: &ToolSource) -> Option<String> {
+    match source {
+        ToolSource::Remote(url) => {
+            let resp = client
+                .get(url)
+                .timeout(std::time::Duration::from_secs(30))
+                .send()
+                .await
+                .ok()?;
+            if !resp.status().is_success() {
+                return None;
+            }
+            resp.text().await.ok()
+        }
+        ToolSource::Local(path) => tokio::fs::read_to_string(path).await.ok(),
+    }
+}
+
+/// The SHA-256 listed for `asset` in a `sha256sum`-style list.
+#[cfg(not(target_os = "android"))]
+fn checksum_in(sums: &str, asset: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        let name = parts.next()?.trim_start_matches('*');
+        (name == asset).then(|| hash.to_lowercase())
+    })
+}
+
+/// A SHA-256 written out as 64 hex digits.
+#[cfg(not(target_os = "android"))]
+fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// SHA-256 of `asset` pinned in the `checksums_key` setting (e.g.
+/// `ytdlp_checksums`), so mirror and local-folder installs can be verified
+/// without reaching the official release. One entry per line: a bare hash
+/// (for every file of the tool), a `sha256sum`-style `<hash>  <file>` line,
+/// or the HTTPS URL or absolute path of such a list.
+#[cfg(not(target_os = "android"))]
+pub(crate) async fn pinned_checksum(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    checksums_key: &str,
+    asset: &str,
+) -> Option<String> {
+    for entry in app_setting(app, checksums_key).lines().map(str::trim).filter(|e| !e.is_empty()) {
+        let list = if entry.starts_with("https://") {
+            Some(ToolSource::Remote(entry.to_string()))
+        } else if let Some(path) = entry.strip_prefix("file://") {
+            Some(ToolSource::Local(path.into()))
+        } else if std::path::Path::new(entry).is_absolute() {
+            Some(ToolSource::Local(entry.into()))
+        } else {
+            None
+        };
+        let found = match list {
+            Some(source) => read_tool_text(client, &source)
+                .await
+                .and_then(|sums| checksum_in(&sums, asset)),
+            None if is_sha256(entry) => Some(entry.to_lowercase()),
+            None => checksum_in(entry, asset),
+        };
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+/// SHA-256 of `asset` as GitHub lists it for the latest release of `repo`.
+/// `None` when the release doesn't carry digests.
+#[cfg(not(target_os = "android"))]
+pub(crate) async fn github_asset_digest(client: &reqwest::Client, repo: &str, asset: &str) -> Result<Option<String>, String> {
+    let release: serde_json::Value = client
+        .get(format!("https://api.github.com/repos/{}/releases/latest", repo))
+        .header(reqwest::header::USER_AGENT, "YTDL/3.0")
+        .timeout(std::time::Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch release info of {}: {}", repo, e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse release info of {}: {}", repo, e))?;
+    Ok(release["assets"]
+        .as_array()
+        .and_then(|assets| assets.iter().find(|a| a["name"].as_str() == Some(asset)))
+        .and_then(|a| a["digest"].as_str())
+        .and_then(|digest| digest.strip_prefix("sha256:"))
+        .map(str::to_lowercase))
+}
+
+/// Check a fetched file against the expected checksum, removing it when it
+/// doesn't match or there is none to check against. `checksums_key` is the
+/// setting a checksum can be pinned in.
+#[cfg(not(target_os = "android"))]
+pub(crate) fn verify_checksum(
+    file: &DownloadedFile,
+    expected: Option<String>,
+    asset: &str,
+    path: &std::path::Path,
+    checksums_key: &str,
+) -> Result<(), String> {
+    let Some(expected) = expected else {
+        let _ = std::fs::remove_file(path);
+        return Err(format!(
+            "No SHA-256 checksum found for {}, so the download can't be verified. \
+             The official checksums couldn't be reached; pin one in the '{}' setting to install offline.",
+            asset, checksums_key
+        ));
+    };
+    if file.sha256 != expected {
+        let _ = std::fs::remove_file(path);
+        return Err(format!(
+            "SHA-256 checksum mismatch for {}!\nExpected: {}\nGot:      {}\nThe download may be corrupted or tampered with, or the mirror is out of date.",
+            asset, expected, file.sha256
+        ));
+    }
+    log::info!("SHA-256 checksum verified for {}", asset);
+    Ok(())
+}
+
+#[cfg(not(target_os = "android"))]
+async fn copy_local_file(src: &std::path::Path, dest: &std::path::Path) -> Result<DownloadedFile, String> {
+    let src = src.to_path_buf();
+    let dest = dest.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        use sha2::Digest;
+        let mut input = std::fs::File::open(&src).map_err(|e| format!("Cannot open {}: {}", src.display(), e))?;
+        let mut hasher = sha2::Sha256::new();
+        std::io::copy(&mut input, &mut hasher).map_err(|e| format!("Cannot read {}: {}", src.display(), e))?;
+        let bytes = std::fs::copy(&src, &dest)
+            .map_err(|e| format!("Failed to copy {} to {}: {}", src.display(), dest.display(), e))?;
+        Ok(DownloadedFile {
+            bytes,
+            sha256: format!("{:x}", hasher.finalize()),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 fn ensure_tool_bin_dir(app: &AppHandle) -> Result<std::path::PathBuf, String> {
     let bin_dir = download::get_binary_dir(app);
     
@@ -37,6 +285,111 @@ fn ensure_tool_bin_dir(app: &AppHandle) -> Result<std::path::PathBuf, String> {
     }
 }
 
+/// Result of [`download_to_file`].
+#[cfg(not(target_os = "android"))]
+pub(crate) struct DownloadedFile {
+    pub bytes: u64,
+    /// Lowercase hex SHA-256 of the downloaded content.
+    pub sha256: String,
+}
+
+/// Stream `url` into `dest`, emitting `install-progress` events for `tool`
+/// with byte counts and speed as chunks arrive. The whole body is never
+/// held in memory, so multi-GB whisper models are fine. `dest` is removed
+/// again if the download fails part-way.
+#[cfg(not(target_os = "android"))]
+pub(crate) async fn download_to_file(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    url: &str,
+    dest: &std::path::Path,
+    tool: &str,
+) -> Result<DownloadedFile, String> {
+    use sha2::Digest;
+    use tokio::io::AsyncWriteExt;
+
+    // No overall timeout (large models take a while), but a stalled
+    // connection is given up on after a minute without data.
+    const CHUNK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+    const EMIT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Download failed: {}. Please check your internet connection.", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Download failed with status: {}. Please try again later.", response.status()));
+    }
+    let total = response.content_length();
+
+    let mut file = tokio::fs::File::create(dest)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}. Check if the directory is writable.", dest.display(), e))?;
+    let mut hasher = sha2::Sha256::new();
+    let mut downloaded: u64 = 0;
+    let started = std::time::Instant::now();
+    let mut last_emit = started;
+
+    let result: Result<(), String> = async {
+        loop {
+            let chunk = tokio::time::timeout(CHUNK_TIMEOUT, response.chunk())
+                .await
+                .map_err(|_| "Download stalled: no data received for 60 seconds".to_string())?
+                .map_err(|e| format!("Failed to read download: {}", e))?;
+            let Some(chunk) = chunk else { break };
+
+            hasher.update(&chunk);
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+            downloaded += chunk.len() as u64;
+
+            if last_emit.elapsed() >= EMIT_INTERVAL {
+                last_emit = std::time::Instant::now();
+                emit_download_progress(app, tool, downloaded, total, started.elapsed());
+            }
+        }
+        file.flush().await.map_err(|e| e.to_string())
+    }
+    .await;
+
+    if let Err(e) = result {
+        drop(file);
+        let _ = tokio::fs::remove_file(dest).await;
+        return Err(e);
+    }
+    emit_download_progress(app, tool, downloaded, total, started.elapsed());
+
+    Ok(DownloadedFile {
+        bytes: downloaded,
+        sha256: format!("{:x}", hasher.finalize()),
+    })
+}
+
+#[cfg(not(target_os = "android"))]
+fn emit_download_progress(
+    app: &AppHandle,
+    tool: &str,
+    downloaded: u64,
+    total: Option<u64>,
+    elapsed: std::time::Duration,
+) {
+    let progress = total
+        .filter(|t| *t > 0)
+        .map(|t| ((downloaded as f64 / t as f64) * 100.0).min(100.0).round() as u64)
+        .unwrap_or(0);
+    let speed = downloaded as f64 / elapsed.as_secs_f64().max(0.001);
+    let _ = app.emit("install-progress", serde_json::json!({
+        "tool": tool,
+        "status": "downloading",
+        "progress": progress,
+        "downloadedBytes": downloaded,
+        "totalBytes": total,
+        "bytesPerSecond": speed.round() as u64,
+    }));
+}
+
 /// Get a shared directory for Termux check output files.
 /// Uses shared storage `.checks/` dir accessible by both our app and Termux.
 #[cfg(target_os = "android")]
@@ -403,7 +756,8 @@ pub async fn install_ytdlp(app: AppHandle) -> Result<(), String> {
         
         // Try Python/pip approach
         let bin_dir = ensure_tool_bin_dir(&app)?;
-        match install_ytdlp_via_pip(&app, &bin_dir).await {
+        let prerelease = ytdlp_release_repo(&app) != "yt-dlp/yt-dlp";
+        match install_ytdlp_via_pip(&app, &bin_dir, prerelease).await {
             Ok(()) => return Ok(()),
             Err(e) => {
                 log::warn!("[install_ytdlp] pip installation failed: {}", e);
@@ -425,13 +779,16 @@ pub async fn install_ytdlp(app: AppHandle) -> Result<(), String> {
     {
     let bin_dir = ensure_tool_bin_dir(&app)?;
 
-    let (url, filename) = if cfg!(target_os = "windows") {
-        ("https://github.com/yt-dlp/yt-dlp/releases/latest/download/yt-dlp.exe", "yt-dlp.exe")
+    let repo = ytdlp_release_repo(&app);
+    let (asset, filename) = if cfg!(target_os = "windows") {
+        ("yt-dlp.exe", "yt-dlp.exe")
     } else if cfg!(target_os = "macos") {
-        ("https://github.com/yt-dlp/yt-dlp/releases/latest/download/yt-dlp_macos", "yt-dlp")
+        ("yt-dlp_macos", "yt-dlp")
     } else {
-        ("https://github.com/yt-dlp/yt-dlp/releases/latest/download/yt-dlp", "yt-dlp")
+        ("yt-dlp", "yt-dlp")
     };
+    let official_url = format!("https://github.com/{}/releases/latest/download/{}", repo, asset);
+    let sources = tool_sources(&app, "ytdlp_mirrors", asset, Some(&official_url));
 
     let _ = app.emit("install-progress", serde_json::json!({
         "tool": "yt-dlp",
@@ -439,51 +796,29 @@ pub async fn install_ytdlp(app: AppHandle) -> Result<(), String> {
         "progress": 0
     }));
 
-    let dl_client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300))
+    let dl_client = crate::proxy::client_builder()
+        .connect_timeout(std::time::Duration::from_secs(30))
         .build()
         .map_err(|e| format!("HTTP client error: {}", e))?;
-    let response = dl_client.get(url).send().await.map_err(|e| format!("Download failed: {}. Please check your internet connection.", e))?;
-    if !response.status().is_success() {
-        return Err(format!("Download failed with status: {}. Please try again later.", response.status()));
-    }
-    let bytes = response.bytes().await.map_err(|e| format!("Failed to read download: {}", e))?;
-
-    // Verify SHA256 checksum against the official SHA2-256SUMS file
-    let binary_basename = url.rsplit('/').next().unwrap_or(filename);
-    let checksums_url = url.rsplit_once('/').map(|(base, _)| format!("{}/SHA2-256SUMS", base))
-        .unwrap_or_else(|| "https://github.com/yt-dlp/yt-dlp/releases/latest/download/SHA2-256SUMS".to_string());
-
-    match dl_client.get(&checksums_url).send().await {
-        Ok(resp) if resp.status().is_success() => {
-            if let Ok(sums_text) = resp.text().await {
-                use sha2::Digest;
-                let computed = format!("{:x}", sha2::Sha256::digest(&bytes));
-                let expected = sums_text.lines()
-                    .find(|line| line.ends_with(binary_basename))
-                    .and_then(|line| line.split_whitespace().next())
-                    .map(|h| h.to_lowercase());
-
-                if let Some(expected_hash) = expected {
-                    if computed != expected_hash {
-                        return Err(format!(
-                            "SHA-256 checksum mismatch for {}!\nExpected: {}\nGot:      {}\nThe download may be corrupted or tampered with.",
-                            binary_basename, expected_hash, computed
-                        ));
-                    }
-                    log::info!("SHA-256 checksum verified for {}", binary_basename);
-                } else {
-                    log::warn!("Could not find checksum for '{}' in SHA2-256SUMS — skipping verification", binary_basename);
-                }
-            }
-        }
-        _ => {
-            log::warn!("Could not fetch SHA2-256SUMS for checksum verification — skipping");
+    let dest = bin_dir.join(filename);
+    let partial = bin_dir.join(format!("{}.part", filename));
+    let (downloaded, source) = fetch_tool_file(&app, &dl_client, &sources, &partial, "yt-dlp").await?;
+    log::info!("Downloaded {} ({} bytes) from {}", filename, downloaded.bytes, source.describe());
+
+    // Verify against the pinned checksum, or else the SHA2-256SUMS of the
+    // official release, whichever source the binary came from
+    let expected = match pinned_checksum(&app, &dl_client, "ytdlp_checksums", asset).await {
+        Some(hash) => Some(hash),
+        None => {
+            let sums_url = format!("https://github.com/{}/releases/latest/download/SHA2-256SUMS", repo);
+            read_tool_text(&dl_client, &ToolSource::Remote(sums_url))
+                .await
+                .and_then(|sums| checksum_in(&sums, asset))
         }
-    }
+    };
+    verify_checksum(&downloaded, expected, asset, &partial, "ytdlp_checksums")?;
 
-    let dest = bin_dir.join(filename);
-    std::fs::write(&dest, &bytes).map_err(|e| format!("Failed to save {}: {}. Check if the directory is writable.", dest.display(), e))?;
+    std::fs::rename(&partial, &dest).map_err(|e| format!("Failed to save {}: {}. Check if the directory is writable.", dest.display(), e))?;
 
     #[cfg(unix)]
     {
@@ -528,7 +863,7 @@ pub async fn install_ytdlp(app: AppHandle) -> Result<(), String> {
 
 /// Install yt-dlp via Python/pip and create a wrapper script
 #[cfg(target_os = "android")]
-async fn install_ytdlp_via_pip(_app: &AppHandle, bin_dir: &std::path::Path) -> Result<(), String> {
+async fn install_ytdlp_via_pip(_app: &AppHandle, bin_dir: &std::path::Path, prerelease: bool) -> Result<(), String> {
     log::info!("[install_ytdlp_via_pip] Starting pip installation for Android");
     
     // Check if python3 is available
@@ -571,10 +906,16 @@ async fn install_ytdlp_via_pip(_app: &AppHandle, bin_dir: &std::path::Path) -> R
     log::info!("[install_ytdlp_via_pip] pip is available");
 
     // Try to install yt-dlp via pip
-    let install_result = tokio::process::Command::new(python_cmd)
-        .args(["-m", "pip", "install", "--user", "yt-dlp"])
-        .output()
-        .await;
+    // pip has no nightly channel of its own; nightlies are published as
+    // pre-releases
+    let mut pip_args = vec!["-m", "pip", "install", "--user", "-U"];
+    if prerelease {
+        pip_args.push("--pre");
+    }
+    pip_args.push("yt-dlp");
+    let mut pip_cmd = tokio::process::Command::new(python_cmd);
+    crate::proxy::apply_to_command(&mut pip_cmd);
+    let install_result = pip_cmd.args(&pip_args).output().await;
 
     match install_result {
         Ok(output) => {
@@ -729,32 +1070,33 @@ pub async fn install_ffmpeg(app: AppHandle) -> Result<(), String> {
         "progress": 0
     }));
 
-    let dl_client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(600))
+    let dl_client = crate::proxy::client_builder()
+        .connect_timeout(std::time::Duration::from_secs(30))
         .build()
         .map_err(|e| format!("HTTP client error: {}", e))?;
 
     if cfg!(target_os = "windows") {
-        use std::io::{Read, Write};
-
         // Download ffmpeg ZIP
         let url = "https://www.gyan.dev/ffmpeg/builds/ffmpeg-release-essentials.zip";
-        let response = dl_client.get(url).send().await.map_err(|e| format!("Download failed: {}. Please check your internet connection.", e))?;
-        if !response.status().is_success() {
-            return Err(format!("Download failed with status: {}. Please try again later.", response.status()));
-        }
-        let bytes = response.bytes().await.map_err(|e| format!("Failed to read download: {}", e))?;
+        let sources = tool_sources(&app, "ffmpeg_mirrors", "ffmpeg-release-essentials.zip", Some(url));
+        let temp_zip = bin_dir.join("ffmpeg_temp.zip");
+        let (downloaded, _) = fetch_tool_file(&app, &dl_client, &sources, &temp_zip, "ffmpeg").await?;
+        // gyan.dev publishes the hash next to each build
+        let asset = "ffmpeg-release-essentials.zip";
+        let expected = match pinned_checksum(&app, &dl_client, "ffmpeg_checksums", asset).await {
+            Some(hash) => Some(hash),
+            None => read_tool_text(&dl_client, &ToolSource::Remote(format!("{}.sha256", url)))
+                .await
+                .and_then(|text| text.split_whitespace().next().map(str::to_lowercase)),
+        };
+        verify_checksum(&downloaded, expected, asset, &temp_zip, "ffmpeg_checksums")?;
 
         let _ = app.emit("install-progress", serde_json::json!({
             "tool": "ffmpeg",
             "status": "extracting",
-            "progress": 50
+            "progress": 100
         }));
 
-        // Write to temp zip file
-        let temp_zip = bin_dir.join("ffmpeg_temp.zip");
-        std::fs::write(&temp_zip, &bytes).map_err(|e| format!("Failed to save ZIP file: {}. Check directory permissions.", e))?;
-
         // Extract ffmpeg.exe from ZIP
         let file = std::fs::File::open(&temp_zip).map_err(|e| format!("Failed to open ZIP file: {}", e))?;
         let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Failed to parse ZIP: {}. The file may be corrupted.", e))?;
@@ -768,18 +1110,14 @@ pub async fn install_ffmpeg(app: AppHandle) -> Result<(), String> {
             if name.ends_with("bin/ffmpeg.exe") || name.ends_with("bin\\ffmpeg.exe") {
                 let dest = bin_dir.join("ffmpeg.exe");
                 let mut outfile = std::fs::File::create(&dest).map_err(|e| format!("Failed to create ffmpeg.exe: {}. Check directory permissions.", e))?;
-                let mut buffer = Vec::new();
-                entry.read_to_end(&mut buffer).map_err(|e| format!("Failed to read from ZIP: {}", e))?;
-                outfile.write_all(&buffer).map_err(|e| format!("Failed to write ffmpeg.exe: {}", e))?;
+                std::io::copy(&mut entry, &mut outfile).map_err(|e| format!("Failed to write ffmpeg.exe: {}", e))?;
                 found = true;
             }
             // Also extract ffprobe.exe if present
             if name.ends_with("bin/ffprobe.exe") || name.ends_with("bin\\ffprobe.exe") {
                 let dest = bin_dir.join("ffprobe.exe");
                 let mut outfile = std::fs::File::create(&dest).map_err(|e| format!("Failed to create ffprobe.exe: {}. Check directory permissions.", e))?;
-                let mut buffer = Vec::new();
-                entry.read_to_end(&mut buffer).map_err(|e| format!("Failed to read from ZIP: {}", e))?;
-                outfile.write_all(&buffer).map_err(|e| format!("Failed to write ffprobe.exe: {}", e))?;
+                std::io::copy(&mut entry, &mut outfile).map_err(|e| format!("Failed to write ffprobe.exe: {}", e))?;
             }
         }
 
@@ -851,27 +1189,40 @@ pub async fn install_ffmpeg(app: AppHandle) -> Result<(), String> {
         let ffmpeg_dest = bin_dir.join("ffmpeg");
         let ffprobe_dest = bin_dir.join("ffprobe");
 
-        let ffmpeg_bytes = dl_client.get(ffmpeg_url).send()
-            .await
-            .map_err(|e| format!("Download failed: {}. Please check your internet connection.", e))?
-            .bytes()
-            .await
-            .map_err(|e| format!("Failed to read download: {}", e))?;
-        std::fs::write(&ffmpeg_dest, &ffmpeg_bytes).map_err(|e| format!("Failed to save ffmpeg: {}. Check directory permissions.", e))?;
+        let asset_name = |url: &str| url.rsplit('/').next().unwrap_or_default().to_string();
+
+        // A file from a mirror has to match the pinned checksum or the
+        // digest GitHub lists for the official asset; the official download
+        // itself is trusted over HTTPS when the release carries no digests
+        let fetch_verified = |url: &'static str, partial: std::path::PathBuf, tool: &'static str| {
+            let (app, client) = (app.clone(), dl_client.clone());
+            async move {
+                let asset = asset_name(url);
+                let sources = tool_sources(&app, "ffmpeg_mirrors", &asset, Some(url));
+                let (downloaded, source) = fetch_tool_file(&app, &client, &sources, &partial, tool).await?;
+                let expected = match pinned_checksum(&app, &client, "ffmpeg_checksums", &asset).await {
+                    Some(hash) => Ok(Some(hash)),
+                    None => github_asset_digest(&client, "eugeneware/ffmpeg-static", &asset).await,
+                };
+                let official = matches!(&source, ToolSource::Remote(u) if u == url);
+                match expected {
+                    Ok(None) if official => Ok(()),
+                    Ok(expected) => verify_checksum(&downloaded, expected, &asset, &partial, "ffmpeg_checksums"),
+                    Err(e) => {
+                        log::warn!("[install_ffmpeg] {}", e);
+                        verify_checksum(&downloaded, None, &asset, &partial, "ffmpeg_checksums")
+                    }
+                }
+            }
+        };
 
-        let _ = app.emit("install-progress", serde_json::json!({
-            "tool": "ffmpeg",
-            "status": "downloading",
-            "progress": 75
-        }));
+        let ffmpeg_partial = bin_dir.join("ffmpeg.part");
+        fetch_verified(ffmpeg_url, ffmpeg_partial.clone(), "ffmpeg").await?;
+        std::fs::rename(&ffmpeg_partial, &ffmpeg_dest).map_err(|e| format!("Failed to save ffmpeg: {}. Check directory permissions.", e))?;
 
-        let ffprobe_bytes = dl_client.get(ffprobe_url).send()
-            .await
-            .map_err(|e| format!("Download failed: {}. Please check your internet connection.", e))?
-            .bytes()
-            .await
-            .map_err(|e| format!("Failed to read download: {}", e))?;
-        std::fs::write(&ffprobe_dest, &ffprobe_bytes).map_err(|e| format!("Failed to save ffprobe: {}. Check directory permissions.", e))?;
+        let ffprobe_partial = bin_dir.join("ffprobe.part");
+        fetch_verified(ffprobe_url, ffprobe_partial.clone(), "ffprobe").await?;
+        std::fs::rename(&ffprobe_partial, &ffprobe_dest).map_err(|e| format!("Failed to save ffprobe: {}. Check directory permissions.", e))?;
 
         #[cfg(unix)]
         {
@@ -932,6 +1283,8 @@ pub async fn get_binary_info(app: AppHandle) -> Result<serde_json::Value, String
     let mut info = serde_json::json!({
         "ytdlpPath": ytdlp_path,
         "ffmpegPath": ffmpeg_path,
+        "ytdlpOverride": download::binary_override(&app, "ytdlp_path").is_some(),
+        "ffmpegOverride": download::binary_override(&app, "ffmpeg_path").is_some(),
         "binDir": bin_dir.to_string_lossy(),
         "platform": std::env::consts::OS,
         "arch": std::env::consts::ARCH,
@@ -961,6 +1314,188 @@ pub async fn get_binary_info(app: AppHandle) -> Result<serde_json::Value, String
     Ok(info)
 }
 
+/// Setting key and version flag for a tool that can be overridden.
+fn binary_override_spec(tool: &str) -> Result<(&'static str, &'static str), String> {
+    match tool {
+        "yt-dlp" | "ytdlp" => Ok(("ytdlp_path", "--version")),
+        "ffmpeg" => Ok(("ffmpeg_path", "-version")),
+        "whisper" | "whisper-cpp" => Ok(("whisper_cpp_path", "--help")),
+        _ => Err(format!("Unknown tool: {}", tool)),
+    }
+}
+
+/// Check `path` as a value for setting `key`, then run it with its
+/// version flag and return the first line of output.
+async fn probe_binary(key: &str, path: &str, version_flag: &str) -> Result<String, String> {
+    crate::settings::check_tool_path(key, path)?;
+    run_version(path, version_flag).await
+}
+
+/// Like [`probe_binary`], but `path` may also be a bare name on PATH.
+async fn run_version(path: &str, version_flag: &str) -> Result<String, String> {
+    let output = tokio::time::timeout(
+        std::time::Duration::from_secs(15),
+        download::create_hidden_command(path).arg(version_flag).output(),
+    )
+    .await
+    .map_err(|_| format!("{} did not respond within 15 seconds", path))?
+    .map_err(|e| format!("Cannot run {}: {}", path, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} exited with an error: {}", path, stderr.trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(stdout
+        .lines()
+        .chain(stderr.lines())
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or_default()
+        .trim()
+        .to_string())
+}
+
+/// Point the app at a user-supplied yt-dlp, ffmpeg or whisper.cpp binary.
+/// The binary is run once to make sure it works before it's saved; an
+/// empty path clears the override. Returns the binary's version line.
+#[tauri::command]
+pub async fn set_binary_override(
+    db: tauri::State<'_, Arc<Mutex<Database>>>,
+    tool: String,
+    path: String,
+) -> Result<String, String> {
+    let (key, version_flag) = binary_override_spec(&tool)?;
+    let path = path.trim().to_string();
+    let version = if path.is_empty() {
+        String::new()
+    } else {
+        probe_binary(key, &path, version_flag).await?
+    };
+    let db_lock = db.lock().map_err(|e| e.to_string())?;
+    db_lock.save_setting(key, &path).map_err(|e| e.to_string())?;
+    Ok(version)
+}
+
+/// Check a candidate binary without saving it.
+#[tauri::command]
+pub async fn test_binary_override(tool: String, path: String) -> Result<String, String> {
+    let (key, version_flag) = binary_override_spec(&tool)?;
+    if path.trim().is_empty() {
+        return Err("Path cannot be empty".to_string());
+    }
+    probe_binary(key, path.trim(), version_flag).await
+}
+
+/// One entry of the [`check_environment`] report.
+fn env_check(result: Result<String, String>, path: Option<String>, required: bool) -> serde_json::Value {
+    let (ok, detail, error) = match result {
+        Ok(detail) => (true, Some(detail), None),
+        Err(e) => (false, None, Some(e)),
+    };
+    serde_json::json!({
+        "ok": ok,
+        "required": required,
+        "path": path,
+        "detail": detail,
+        "error": error,
+    })
+}
+
+/// Verify everything the app depends on in one go: yt-dlp, ffmpeg,
+/// ffprobe, whisper.cpp and its model (required only when local
+/// transcription is selected), reaching YouTube and writing to the
+/// download folder. The checks run concurrently; `ready` is true when
+/// every required check passed.
+#[tauri::command]
+pub async fn check_environment(app: AppHandle) -> Result<serde_json::Value, String> {
+    let ytdlp = download::get_ytdlp_path(&app);
+    let ffmpeg = download::get_ffmpeg_path(&app);
+    let ffprobe = download::get_ffprobe_path(&app);
+    let whisper = app_setting(&app, "whisper_cpp_path").trim().to_string();
+    let whisper_model = app_setting(&app, "whisper_model_path").trim().to_string();
+    let whisper_required = app_setting(&app, "transcribe_provider") == "local";
+    let download_dir = {
+        let db = app.state::<Arc<Mutex<Database>>>();
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        crate::commands::resolve_download_dir(&app, &db_lock, download::MediaKind::Video)
+    };
+
+    let whisper_check = async {
+        if whisper.is_empty() {
+            return Err("whisper.cpp is not installed".to_string());
+        }
+        probe_binary("whisper_cpp_path", &whisper, "--help").await.map(|_| "whisper.cpp runs".to_string())
+    };
+    let model_check = async {
+        if whisper_model.is_empty() {
+            return Err("No whisper model installed".to_string());
+        }
+        let meta = tokio::fs::metadata(&whisper_model)
+            .await
+            .map_err(|e| format!("Model file missing: {}", e))?;
+        Ok(format!("{} MB", meta.len() / (1024 * 1024)))
+    };
+    let network_check = async {
+        let client = crate::proxy::client_builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .map_err(|e| e.to_string())?;
+        let started = std::time::Instant::now();
+        let resp = client
+            .head("https://www.youtube.com/")
+            .send()
+            .await
+            .map_err(|e| format!("Cannot reach YouTube: {}", e))?;
+        Ok(format!("HTTP {} in {} ms", resp.status().as_u16(), started.elapsed().as_millis()))
+    };
+    let dir_check = async {
+        let dir = std::path::PathBuf::from(&download_dir);
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+        let probe = dir.join(format!(".ytdl-write-test-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&probe, b"ok")
+            .await
+            .map_err(|e| format!("Cannot write to {}: {}", dir.display(), e))?;
+        let _ = tokio::fs::remove_file(&probe).await;
+        Ok("writable".to_string())
+    };
+
+    let (ytdlp_res, ffmpeg_res, ffprobe_res, whisper_res, model_res, network_res, dir_res) = tokio::join!(
+        run_version(&ytdlp, "--version"),
+        run_version(&ffmpeg, "-version"),
+        run_version(&ffprobe, "-version"),
+        whisper_check,
+        model_check,
+        network_check,
+        dir_check,
+    );
+
+    let checks = serde_json::json!({
+        "ytdlp": env_check(ytdlp_res, Some(ytdlp), true),
+        "ffmpeg": env_check(ffmpeg_res, Some(ffmpeg), true),
+        "ffprobe": env_check(ffprobe_res, Some(ffprobe), false),
+        "whisper": env_check(whisper_res, Some(whisper).filter(|p| !p.is_empty()), whisper_required),
+        "whisperModel": env_check(model_res, Some(whisper_model).filter(|p| !p.is_empty()), whisper_required),
+        "network": env_check(network_res, None, true),
+        "downloadDir": env_check(dir_res, Some(download_dir), true),
+    });
+    let ready = checks
+        .as_object()
+        .map(|all| {
+            all.values()
+                .all(|c| c["ok"].as_bool() == Some(true) || c["required"].as_bool() != Some(true))
+        })
+        .unwrap_or(false);
+
+    Ok(serde_json::json!({
+        "ready": ready,
+        "platform": std::env::consts::OS,
+        "checks": checks,
+    }))
+}
+
 #[tauri::command]
 pub fn get_app_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
@@ -984,17 +1519,18 @@ pub async fn get_ytdlp_version(app: AppHandle) -> Result<String, String> {
     }
 }
 
-/// Get latest available yt-dlp version from GitHub
+/// Get latest available yt-dlp version from GitHub, for the selected channel
 #[tauri::command]
-pub async fn get_ytdlp_latest_version() -> Result<String, String> {
-    let client = reqwest::Client::builder()
+pub async fn get_ytdlp_latest_version(app: AppHandle) -> Result<String, String> {
+    let repo = ytdlp_release_repo(&app);
+    let client = crate::proxy::client_builder()
         .user_agent("YTDL/3.0")
         .timeout(std::time::Duration::from_secs(15))
         .build()
         .map_err(|e| e.to_string())?;
     
     let response = client
-        .get("https://api.github.com/repos/yt-dlp/yt-dlp/releases/latest")
+        .get(format!("https://api.github.com/repos/{}/releases/latest", repo))
         .send()
         .await
         .map_err(|e| format!("Failed to check for updates: {}", e))?;
@@ -1010,7 +1546,31 @@ pub async fn get_ytdlp_latest_version() -> Result<String, String> {
         .ok_or_else(|| "Could not find version".to_string())
 }
 
-/// Update yt-dlp to latest version
+/// Notify when a newer yt-dlp is available on the selected channel. Run
+/// once in the background after startup.
+pub async fn notify_tool_updates(app: AppHandle) {
+    use crate::notifications::{self, NotifyEvent};
+    if !notifications::is_enabled(&app, NotifyEvent::ToolUpdate) {
+        return;
+    }
+    let (Ok(installed), Ok(latest)) = (
+        get_ytdlp_version(app.clone()).await,
+        get_ytdlp_latest_version(app.clone()).await,
+    ) else {
+        return;
+    };
+    // Versions are dates (2024.08.06[.123456]), so they compare as strings
+    if latest.trim_start_matches('v') > installed.as_str() {
+        notifications::notify(
+            &app,
+            NotifyEvent::ToolUpdate,
+            "yt-dlp update available",
+            &format!("Version {} is available (installed: {})", latest, installed),
+        );
+    }
+}
+
+/// Update yt-dlp to the latest version of the selected channel
 #[tauri::command]
 pub async fn update_ytdlp(app: AppHandle) -> Result<(), String> {
     // Use the same function as install
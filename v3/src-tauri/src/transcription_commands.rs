@@ -6,6 +6,10 @@ use tauri::{AppHandle, Emitter, Manager, State};
 
 use crate::db::Database;
 use crate::download;
+use crate::tool_install_commands::tool_sources;
+#[cfg(not(target_os = "android"))]
+use crate::tool_install_commands::{fetch_tool_file, github_asset_digest, pinned_checksum, verify_checksum};
+use crate::transcription_provider::ApiProvider;
 
 /// Helper macro for transcription error handling — avoids repeating the
 /// "update DB + emit error event + return" pattern ~15 times.
@@ -39,16 +43,121 @@ async fn wait_for_cancel(mut rx: tokio::sync::watch::Receiver<bool>) {
     }
 }
 
+/// Delete the intermediate files of a job that is stopping early.
+async fn remove_temp_files(files: &[PathBuf]) {
+    for file in files {
+        let _ = tokio::fs::remove_file(file).await;
+    }
+}
+
+/// Like `Command::output()`, but kills the child as soon as the job is
+/// cancelled. Returns `Ok(None)` when the cancel token fired first.
+#[cfg(not(target_os = "android"))]
+async fn output_unless_cancelled(
+    cmd: &mut tokio::process::Command,
+    cancel_rx: tokio::sync::watch::Receiver<bool>,
+) -> std::io::Result<Option<std::process::Output>> {
+    let child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    tokio::select! {
+        output = child.wait_with_output() => output.map(Some),
+        // Dropping the wait future drops the child, which kills it.
+        _ = wait_for_cancel(cancel_rx) => Ok(None),
+    }
+}
+
+type TranscriptionJobs = Arc<tokio::sync::Mutex<HashMap<String, tokio::sync::watch::Sender<bool>>>>;
+
 #[tauri::command]
 pub async fn start_transcription(
     app: AppHandle,
     db: State<'_, Arc<Mutex<Database>>>,
-    transcription_jobs: State<'_, Arc<tokio::sync::Mutex<HashMap<String, tokio::sync::watch::Sender<bool>>>>>,
+    transcription_jobs: State<'_, TranscriptionJobs>,
     source: String,
     model_size: Option<String>,
+) -> Result<String, String> {
+    spawn_transcription(
+        app,
+        db.inner().clone(),
+        transcription_jobs.inner().clone(),
+        source.clone(),
+        source,
+        model_size.unwrap_or_default(),
+        None,
+    )
+    .await
+}
+
+/// Transcribe a finished download straight from its file on disk, linking
+/// the transcript to the download row instead of re-fetching audio.
+#[tauri::command]
+pub async fn transcribe_download(
+    app: AppHandle,
+    db: State<'_, Arc<Mutex<Database>>>,
+    transcription_jobs: State<'_, TranscriptionJobs>,
+    id: String,
+    model_size: Option<String>,
+) -> Result<String, String> {
+    let (file_path, title, download_dir) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let row = db_lock
+            .get_download(&id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Download not found: {}", id))?;
+        if row["status"].as_str() != Some("completed") {
+            return Err("Download has not completed yet".to_string());
+        }
+        let download_dir = db_lock
+            .get_setting("download_path")
+            .map_err(|e| e.to_string())?
+            .filter(|v| !v.trim().is_empty());
+        (
+            row["filePath"].as_str().unwrap_or("").to_string(),
+            row["title"].as_str().unwrap_or("").to_string(),
+            download_dir,
+        )
+    };
+
+    let resolved = crate::commands::resolve_download_file(
+        Some(file_path.as_str()).filter(|p| !p.trim().is_empty()),
+        Some(title.as_str()).filter(|t| !t.trim().is_empty()),
+        download_dir.as_deref(),
+    )
+    .ok_or_else(|| format!("Downloaded file not found on disk: {}", file_path))?;
+
+    let title = if title.trim().is_empty() {
+        resolved.to_string_lossy().to_string()
+    } else {
+        title
+    };
+
+    spawn_transcription(
+        app,
+        db.inner().clone(),
+        transcription_jobs.inner().clone(),
+        resolved.to_string_lossy().to_string(),
+        title,
+        model_size.unwrap_or_default(),
+        Some(id),
+    )
+    .await
+}
+
+async fn spawn_transcription(
+    app: AppHandle,
+    db: Arc<Mutex<Database>>,
+    transcription_jobs: TranscriptionJobs,
+    source: String,
+    title: String,
+    model_override: String,
+    download_id: Option<String>,
 ) -> Result<String, String> {
     let id = uuid::Uuid::new_v4().to_string();
-    let model_override = model_size.unwrap_or_default();
     let (cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
 
     {
@@ -56,20 +165,15 @@ pub async fn start_transcription(
         jobs.insert(id.clone(), cancel_tx);
     }
 
-    let (provider, api_key, api_model, whisper_cpp, whisper_model) = {
+    let transcribe_key = crate::secrets::keyring_secret("transcribe_api_key");
+    let openai_key = crate::secrets::keyring_secret("openai_api_key");
+    let (provider, api_provider, whisper_cpp, whisper_model) = {
         let db_lock = db.lock().map_err(|e| e.to_string())?;
         let provider = db_lock
             .get_setting("transcribe_provider")
             .unwrap_or(None)
             .unwrap_or_else(|| "api".to_string());
-        let api_key = db_lock
-            .get_setting("openai_api_key")
-            .unwrap_or(None)
-            .unwrap_or_default();
-        let api_model = db_lock
-            .get_setting("openai_model")
-            .unwrap_or(None)
-            .unwrap_or_else(|| "whisper-1".to_string());
+        let api_provider = ApiProvider::from_settings(&db_lock, &model_override, transcribe_key, openai_key);
         let whisper_cpp = db_lock
             .get_setting("whisper_cpp_path")
             .unwrap_or(None)
@@ -78,14 +182,14 @@ pub async fn start_transcription(
             .get_setting("whisper_model_path")
             .unwrap_or(None)
             .unwrap_or_default();
-        (provider, api_key, api_model, whisper_cpp, whisper_model)
+        (provider, api_provider, whisper_cpp, whisper_model)
     };
 
     // Insert transcript record into DB
     {
         let db_lock = db.lock().map_err(|e| e.to_string())?;
         db_lock
-            .insert_transcript(&id, &source, &source)
+            .insert_transcript(&id, &source, &title, download_id.as_deref())
             .map_err(|e| e.to_string())?;
         db_lock
             .update_transcript_status(&id, "processing", 0.0)
@@ -95,14 +199,12 @@ pub async fn start_transcription(
     let app_clone = app.clone();
     let id_clone = id.clone();
     let source_clone = source.clone();
-    let db_clone = db.inner().clone();
+    let db_clone = db.clone();
     let provider_clone = provider.clone();
-    let api_key_clone = api_key.clone();
-    let api_model_clone = api_model.clone();
+    let api_provider_clone = api_provider.clone();
     let whisper_cpp_clone = whisper_cpp.clone();
     let whisper_model_clone = whisper_model.clone();
-    let model_override_clone = model_override.clone();
-    let transcription_jobs_clone = transcription_jobs.inner().clone();
+    let transcription_jobs_clone = transcription_jobs.clone();
     let cancel_rx_clone = cancel_rx.clone();
 
     tokio::spawn(async move {
@@ -160,6 +262,14 @@ pub async fn start_transcription(
                 let mut found = false;
                 for i in 0..100 {
                     tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+
+                    // Termux runs out-of-process; stop waiting and discard output
+                    if *cancel_rx_clone.borrow() {
+                        let _ = tokio::fs::remove_file(&status_file).await;
+                        let _ = tokio::fs::remove_file(&audio_file).await;
+                        return;
+                    }
+
                     if let Ok(content) = tokio::fs::read_to_string(&status_file).await {
                         let trimmed = content.trim();
                         if trimmed.contains("TRANSCRIBE_EXIT:0") {
@@ -230,8 +340,9 @@ pub async fn start_transcription(
                 let output_audio = base.with_extension("mp3");
 
                 let ytdlp = download::get_ytdlp_path(&app_clone);
-                let output = download::create_hidden_command(&ytdlp)
-                    .args([
+                let mut ytdlp_cmd = download::create_hidden_command(&ytdlp);
+                ytdlp_cmd.args(crate::extractor_args::ytdlp_args());
+                ytdlp_cmd.args([
                         "-x",
                         "--audio-format",
                         "mp3",
@@ -242,17 +353,21 @@ pub async fn start_transcription(
                         "-o",
                         &output_template,
                         &source_clone,
-                    ])
-                    .output()
-                    .await;
+                    ]);
+                let output = output_unless_cancelled(&mut ytdlp_cmd, cancel_rx_clone.clone()).await;
 
                 match output {
-                    Ok(result) => {
+                    Ok(Some(result)) => {
                         if !result.status.success() {
                             let stderr = String::from_utf8_lossy(&result.stderr);
                             transcription_bail!(db_clone, app_clone, &id_clone, stderr.trim());
                         }
                     }
+                    Ok(None) => {
+                        // Cancelled mid-download: drop any partial audio
+                        let _ = tokio::fs::remove_file(&output_audio).await;
+                        return;
+                    }
                     Err(e) => {
                         transcription_bail!(db_clone, app_clone, &id_clone, e);
                     }
@@ -269,7 +384,7 @@ pub async fn start_transcription(
             PathBuf::from(source_clone)
         };
 
-        let (text, language) = if provider_clone == "local" {
+        let (text, language, segments) = if provider_clone == "local" {
             if whisper_cpp_clone.is_empty() || whisper_model_clone.is_empty() {
                 transcription_bail!(db_clone, app_clone, &id_clone, "Local transcription requires whisper_cpp_path and whisper_model_path. Please run setup first.");
             }
@@ -286,10 +401,11 @@ pub async fn start_transcription(
                 let status_file = format!("{}/whisper_run_{}.txt", check_dir, id_clone);
                 let _ = std::fs::remove_file(&status_file);
 
-                // whisper-cli -m model -f audio -otxt -of output_base
+                // whisper-cli -m model -f audio -otxt -oj -of output_base
                 let output_base = format!("{}/{}", transcribe_dir, id_clone);
+                let output_json = format!("{}.json", output_base);
                 let whisper_cmd = format!(
-                    "mkdir -p '{}' && '{}' -m '{}' -f '{}' -otxt -of '{}' 2>&1; echo \"WHISPER_EXIT:$?\"",
+                    "mkdir -p '{}' && '{}' -m '{}' -f '{}' -otxt -oj -of '{}' 2>&1; echo \"WHISPER_EXIT:$?\"",
                     transcribe_dir, whisper_cpp_clone, whisper_model_clone,
                     audio_path_str, output_base
                 );
@@ -364,8 +480,10 @@ pub async fn start_transcription(
                             format!("Failed to read whisper output: {}", e));
                     }
                 };
+                let segments = read_whisper_json_segments(&PathBuf::from(&output_json)).await;
+                let _ = tokio::fs::remove_file(&output_json).await;
 
-                (text, String::new())
+                (text, String::new(), segments)
             }
 
             // ── Desktop: run whisper-cli directly ──
@@ -392,27 +510,31 @@ pub async fn start_transcription(
                 let extracted_audio = extraction_dir.join(format!("transcribe-{}-local.wav", id_clone));
                 let source_input = audio_path.to_string_lossy().to_string();
                 let extracted_output = extracted_audio.to_string_lossy().to_string();
-                let ffmpeg_result = download::create_hidden_command(&ffmpeg_path)
-                    .args([
-                        "-y",
-                        "-i",
-                        &source_input,
-                        "-vn",
-                        "-ac",
-                        "1",
-                        "-ar",
-                        "16000",
-                        &extracted_output,
-                    ])
-                    .output()
-                    .await;
+                let mut ffmpeg_cmd = download::create_hidden_command(&ffmpeg_path);
+                ffmpeg_cmd.args([
+                    "-y",
+                    "-i",
+                    &source_input,
+                    "-vn",
+                    "-ac",
+                    "1",
+                    "-ar",
+                    "16000",
+                    &extracted_output,
+                ]);
+                let ffmpeg_result = output_unless_cancelled(&mut ffmpeg_cmd, cancel_rx_clone.clone()).await;
 
                 match ffmpeg_result {
-                    Ok(result) if result.status.success() => {
+                    Ok(Some(result)) if result.status.success() => {
                         local_audio_path = extracted_audio.clone();
                         temp_files.push(extracted_audio);
                     }
-                    Ok(result) => {
+                    Ok(None) => {
+                        let _ = tokio::fs::remove_file(&extracted_audio).await;
+                        remove_temp_files(&temp_files).await;
+                        return;
+                    }
+                    Ok(Some(result)) => {
                         let stderr = String::from_utf8_lossy(&result.stderr).to_string();
                         transcription_bail!(db_clone, app_clone, &id_clone, format!("Failed to extract audio from media file: {}", stderr.trim()));
                     }
@@ -431,6 +553,11 @@ pub async fn start_transcription(
 
             let output_base = temp_dir.join(format!("transcribe-{}", id_clone));
             let output_txt = output_base.with_extension("txt");
+            let output_json = output_base.with_extension("json");
+
+            if let Err(e) = crate::settings::check_tool_path("whisper_cpp_path", &whisper_cpp_clone) {
+                transcription_bail!(db_clone, app_clone, &id_clone, e);
+            }
 
             let audio_path_str = local_audio_path.to_string_lossy().to_string();
             let output_base_str = output_base.to_string_lossy().to_string();
@@ -441,10 +568,14 @@ pub async fn start_transcription(
                 "-f",
                 &audio_path_str,
                 "-otxt",
+                "-oj",
+                "-pp",
                 "-of",
                 &output_base_str,
             ])
-            .stdin(Stdio::null());
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
 
             let mut child = match cmd.spawn() {
                 Ok(child) => child,
@@ -453,6 +584,52 @@ pub async fn start_transcription(
                 }
             };
 
+            // whisper.cpp prints each finished segment to stdout and
+            // "progress = N%" to stderr (-pp); forward both as they arrive.
+            use tokio::io::{AsyncBufReadExt, BufReader};
+            if let Some(stdout) = child.stdout.take() {
+                let app_events = app_clone.clone();
+                let id_events = id_clone.clone();
+                tokio::spawn(async move {
+                    let mut lines = BufReader::new(stdout).lines();
+                    let mut partial_text = String::new();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let Some(segment) = parse_whisper_segment_line(&line) else {
+                            continue;
+                        };
+                        if !partial_text.is_empty() {
+                            partial_text.push(' ');
+                        }
+                        partial_text.push_str(segment["text"].as_str().unwrap_or(""));
+                        emit_partial_transcript(&app_events, &id_events, None, &partial_text, vec![segment]);
+                    }
+                });
+            }
+            if let Some(stderr) = child.stderr.take() {
+                let app_events = app_clone.clone();
+                let id_events = id_clone.clone();
+                tokio::spawn(async move {
+                    let mut lines = BufReader::new(stderr).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        let Some(pct) = line
+                            .split("progress =")
+                            .nth(1)
+                            .and_then(|rest| rest.trim().trim_end_matches('%').trim().parse::<f64>().ok())
+                        else {
+                            continue;
+                        };
+                        let _ = app_events.emit(
+                            "transcription-progress",
+                            serde_json::json!({
+                                "id": id_events,
+                                "progress": (pct * 0.99).min(99.0),
+                                "status": "processing"
+                            }),
+                        );
+                    }
+                });
+            }
+
             let status = tokio::select! {
                 result = child.wait() => {
                     match result {
@@ -469,6 +646,9 @@ pub async fn start_transcription(
             };
 
             let Some(status) = status else {
+                temp_files.push(output_txt);
+                temp_files.push(output_json);
+                remove_temp_files(&temp_files).await;
                 return;
             };
 
@@ -482,88 +662,165 @@ pub async fn start_transcription(
                     transcription_bail!(db_clone, app_clone, &id_clone, e);
                 }
             };
+            let segments = read_whisper_json_segments(&output_json).await;
+            temp_files.push(output_txt);
+            temp_files.push(output_json);
 
-            (text, String::new())
+            (text, String::new(), segments)
             } // #[cfg(not(target_os = "android"))]
         } else {
-            let api_key = if !api_key_clone.is_empty() {
-                api_key_clone
-            } else {
-                transcription_bail!(db_clone, app_clone, &id_clone, "OpenAI API key is missing");
-            };
+            if api_provider_clone.requires_api_key() && api_provider_clone.api_key.trim().is_empty() {
+                transcription_bail!(db_clone, app_clone, &id_clone,
+                    format!("No API key configured for transcription provider '{}'", api_provider_clone.name));
+            }
 
-            let bytes = match tokio::fs::read(&audio_path).await {
-                Ok(b) => b,
-                Err(e) => {
-                    transcription_bail!(db_clone, app_clone, &id_clone, e);
+            // Local video files (e.g. transcribe_download) are shrunk to a
+            // compact mono mp3 first so they fit under the upload limit.
+            #[cfg(not(target_os = "android"))]
+            let audio_path = {
+                let ext = audio_path
+                    .extension()
+                    .and_then(|v| v.to_str())
+                    .map(|v| v.to_lowercase())
+                    .unwrap_or_default();
+                if ["mp3", "wav", "m4a", "flac", "ogg", "opus", "aac", "wma"].contains(&ext.as_str()) {
+                    audio_path
+                } else {
+                    let extraction_dir = match app_clone.path().temp_dir() {
+                        Ok(dir) => dir,
+                        Err(e) => {
+                            transcription_bail!(db_clone, app_clone, &id_clone, e);
+                        }
+                    };
+                    let extracted = extraction_dir.join(format!("transcribe-{}-api.mp3", id_clone));
+                    let source_input = audio_path.to_string_lossy().to_string();
+                    let extracted_output = extracted.to_string_lossy().to_string();
+                    let ffmpeg_path = download::get_ffmpeg_path(&app_clone);
+                    let mut ffmpeg_cmd = download::create_hidden_command(&ffmpeg_path);
+                    ffmpeg_cmd.args([
+                        "-y", "-i", &source_input, "-vn", "-ac", "1", "-ar", "16000",
+                        "-b:a", "64k", &extracted_output,
+                    ]);
+                    let result = output_unless_cancelled(&mut ffmpeg_cmd, cancel_rx_clone.clone()).await;
+                    match result {
+                        Ok(Some(r)) if r.status.success() => {
+                            temp_files.push(extracted.clone());
+                            extracted
+                        }
+                        Ok(None) => {
+                            let _ = tokio::fs::remove_file(&extracted).await;
+                            remove_temp_files(&temp_files).await;
+                            return;
+                        }
+                        Ok(Some(r)) => {
+                            let stderr = String::from_utf8_lossy(&r.stderr).to_string();
+                            transcription_bail!(db_clone, app_clone, &id_clone, format!("Failed to extract audio from media file: {}", stderr.trim()));
+                        }
+                        Err(e) => {
+                            transcription_bail!(db_clone, app_clone, &id_clone, format!("Failed to run ffmpeg: {}", e));
+                        }
+                    }
                 }
             };
 
-            // OpenAI Whisper API has a 25 MB file-size limit
-            const MAX_UPLOAD_SIZE: usize = 25 * 1024 * 1024;
-            if bytes.len() > MAX_UPLOAD_SIZE {
-                transcription_bail!(
-                    db_clone, app_clone, &id_clone,
-                    format!(
-                        "Audio file is too large ({:.1} MB). OpenAI Whisper API limit is 25 MB. \
-                         Try a shorter clip or use local transcription.",
-                        bytes.len() as f64 / (1024.0 * 1024.0)
-                    )
-                );
-            }
-
-            let model = if !model_override_clone.is_empty() {
-                model_override_clone
+            // Long recordings are split into fixed-length chunks so each
+            // request stays small and partial text can be shown as it lands.
+            let file_size = tokio::fs::metadata(&audio_path).await.map(|m| m.len()).unwrap_or(0);
+            #[cfg(not(target_os = "android"))]
+            let chunks = if file_size > API_CHUNK_THRESHOLD_BYTES {
+                match split_audio_for_api(&app_clone, &audio_path, &id_clone, cancel_rx_clone.clone()).await {
+                    Ok(Some(parts)) => {
+                        temp_files.extend(parts.iter().cloned());
+                        parts
+                    }
+                    Ok(None) => {
+                        remove_temp_files(&temp_files).await;
+                        return;
+                    }
+                    Err(e) => {
+                        transcription_bail!(db_clone, app_clone, &id_clone, e);
+                    }
+                }
             } else {
-                api_model_clone
+                vec![audio_path.clone()]
+            };
+            #[cfg(target_os = "android")]
+            let chunks = {
+                let _ = file_size;
+                vec![audio_path.clone()]
             };
 
-            let part = reqwest::multipart::Part::bytes(bytes).file_name("audio.mp3");
-            let form = reqwest::multipart::Form::new()
-                .text("model", model)
-                .part("file", part);
+            let total_chunks = chunks.len();
+            let mut full_text = String::new();
+            let mut language = String::new();
+            let mut all_segments: Vec<serde_json::Value> = Vec::new();
 
-            let client = match reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(300))
-                .build()
-            {
-                Ok(c) => c,
-                Err(e) => {
-                    transcription_bail!(db_clone, app_clone, &id_clone, e);
+            for (index, chunk) in chunks.iter().enumerate() {
+                if *cancel_rx_clone.borrow() {
+                    remove_temp_files(&temp_files).await;
+                    return;
                 }
-            };
-            let response = match client
-                .post("https://api.openai.com/v1/audio/transcriptions")
-                .bearer_auth(api_key)
-                .multipart(form)
-                .send()
-                .await
-            {
-                Ok(r) => r,
-                Err(e) => {
-                    transcription_bail!(db_clone, app_clone, &id_clone, e);
-                }
-            };
 
-            if !response.status().is_success() {
-                let body = response.text().await.unwrap_or_default();
-                transcription_bail!(db_clone, app_clone, &id_clone, body);
-            }
+                let bytes = match tokio::fs::read(chunk).await {
+                    Ok(b) => b,
+                    Err(e) => {
+                        transcription_bail!(db_clone, app_clone, &id_clone, e);
+                    }
+                };
 
-            let json: serde_json::Value = match response.json().await {
-                Ok(v) => v,
-                Err(e) => {
-                    transcription_bail!(db_clone, app_clone, &id_clone, e);
+                let file_name = chunk
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "audio.mp3".to_string());
+                let output = match api_provider_clone.transcribe(bytes, &file_name).await {
+                    Ok(output) => output,
+                    Err(e) => {
+                        transcription_bail!(db_clone, app_clone, &id_clone, e);
+                    }
+                };
+
+                let offset = (index as u64 * API_CHUNK_SECONDS) as f64;
+                let chunk_segments: Vec<serde_json::Value> = output
+                    .segments
+                    .into_iter()
+                    .map(|seg| serde_json::json!({
+                        "start": seg["start"].as_f64().unwrap_or(0.0) + offset,
+                        "end": seg["end"].as_f64().unwrap_or(0.0) + offset,
+                        "text": seg["text"],
+                    }))
+                    .collect();
+
+                if !output.text.is_empty() {
+                    if !full_text.is_empty() {
+                        full_text.push(' ');
+                    }
+                    full_text.push_str(&output.text);
+                }
+                if language.is_empty() {
+                    language = output.language;
                 }
-            };
 
-            let text = json["text"].as_str().unwrap_or("").to_string();
-            let language = json["language"].as_str().unwrap_or("").to_string();
-            (text, language)
+                if total_chunks > 1 {
+                    let progress = ((index + 1) as f64 / total_chunks as f64 * 99.0).min(99.0);
+                    emit_partial_transcript(&app_clone, &id_clone, Some(progress), &full_text, chunk_segments.clone());
+                }
+                all_segments.extend(chunk_segments);
+            }
+
+            (full_text, language, all_segments)
         };
 
         if let Ok(db_lock) = db_clone.lock() {
             let _ = db_lock.update_transcript_complete(&id_clone, &text, &language);
+            if !segments.is_empty() {
+                let duration = segments
+                    .last()
+                    .and_then(|seg| seg["end"].as_f64())
+                    .unwrap_or(0.0)
+                    .ceil() as i64;
+                let segments_json = serde_json::Value::Array(segments).to_string();
+                let _ = db_lock.update_transcript_segments(&id_clone, &segments_json, duration);
+            }
         }
 
         let _ = app_clone.emit(
@@ -576,6 +833,12 @@ pub async fn start_transcription(
                 "language": language
             }),
         );
+        crate::notifications::notify(
+            &app_clone,
+            crate::notifications::NotifyEvent::TranscriptionDone,
+            "Transcription complete",
+            &title,
+        );
 
         for temp in temp_files {
             let _ = tokio::fs::remove_file(temp).await;
@@ -603,7 +866,7 @@ pub async fn get_transcripts(
 #[tauri::command]
 pub async fn delete_transcript(
     db: State<'_, Arc<Mutex<Database>>>,
-    transcription_jobs: State<'_, Arc<tokio::sync::Mutex<HashMap<String, tokio::sync::watch::Sender<bool>>>>>,
+    transcription_jobs: State<'_, TranscriptionJobs>,
     id: String,
 ) -> Result<(), String> {
     {
@@ -617,6 +880,215 @@ pub async fn delete_transcript(
     db_lock.delete_transcript(&id).map_err(|e| e.to_string())
 }
 
+/// Chunk length for long API uploads (10 minutes of 64 kbps mono ≈ 4.8 MB).
+const API_CHUNK_SECONDS: u64 = 600;
+#[cfg(not(target_os = "android"))]
+const API_CHUNK_THRESHOLD_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Split an audio file into `API_CHUNK_SECONDS` mp3 pieces with ffmpeg's
+/// segment muxer. Returns the chunk paths in playback order, or `None` if
+/// the job was cancelled while ffmpeg was running.
+#[cfg(not(target_os = "android"))]
+async fn split_audio_for_api(
+    app: &AppHandle,
+    audio_path: &std::path::Path,
+    id: &str,
+    cancel_rx: tokio::sync::watch::Receiver<bool>,
+) -> Result<Option<Vec<PathBuf>>, String> {
+    let temp_dir = app.path().temp_dir().map_err(|e| e.to_string())?;
+    let prefix = format!("transcribe-{}-chunk-", id);
+    let pattern = temp_dir.join(format!("{}%03d.mp3", prefix));
+    let input = audio_path.to_string_lossy().to_string();
+    let pattern_str = pattern.to_string_lossy().to_string();
+    let segment_time = API_CHUNK_SECONDS.to_string();
+
+    let ffmpeg_path = download::get_ffmpeg_path(app);
+    let mut cmd = download::create_hidden_command(&ffmpeg_path);
+    cmd.args([
+        "-y", "-i", &input, "-vn", "-ac", "1", "-ar", "16000", "-b:a", "64k",
+        "-f", "segment", "-segment_time", &segment_time, "-reset_timestamps", "1",
+        &pattern_str,
+    ]);
+    let result = output_unless_cancelled(&mut cmd, cancel_rx)
+        .await
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+
+    let mut parts = Vec::new();
+    let mut entries = tokio::fs::read_dir(&temp_dir).await.map_err(|e| e.to_string())?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            parts.push(entry.path());
+        }
+    }
+    parts.sort();
+
+    let Some(result) = result else {
+        for part in &parts {
+            let _ = tokio::fs::remove_file(part).await;
+        }
+        return Ok(None);
+    };
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr).to_string();
+        return Err(format!("Failed to split audio into chunks: {}", stderr.trim()));
+    }
+    if parts.is_empty() {
+        return Err("Audio chunking produced no output".to_string());
+    }
+    Ok(Some(parts))
+}
+
+/// Emit a growing transcript while a job is still running. `segments`
+/// holds only the newly finished pieces; `partialText` is everything so far.
+fn emit_partial_transcript(
+    app: &AppHandle,
+    id: &str,
+    progress: Option<f64>,
+    partial_text: &str,
+    segments: Vec<serde_json::Value>,
+) {
+    let mut payload = serde_json::json!({
+        "id": id,
+        "status": "processing",
+        "partialText": partial_text,
+        "segments": segments,
+    });
+    if let Some(progress) = progress {
+        payload["progress"] = serde_json::json!(progress);
+    }
+    let _ = app.emit("transcription-progress", payload);
+}
+
+/// Parse a whisper.cpp stdout line like
+/// `[00:01:02.500 --> 00:01:05.000]  some text` into a segment.
+fn parse_whisper_segment_line(line: &str) -> Option<serde_json::Value> {
+    let rest = line.trim().strip_prefix('[')?;
+    let (range, text) = rest.split_once(']')?;
+    let (from, to) = range.split_once("-->")?;
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(serde_json::json!({
+        "start": parse_whisper_timestamp(from.trim())?,
+        "end": parse_whisper_timestamp(to.trim())?,
+        "text": text,
+    }))
+}
+
+fn parse_whisper_timestamp(value: &str) -> Option<f64> {
+    let mut parts = value.split(':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.replace(',', ".").parse().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// Parse whisper.cpp `-oj` output into `{start, end, text}` segments (seconds).
+/// Missing or malformed files yield an empty list — segments are optional.
+async fn read_whisper_json_segments(path: &std::path::Path) -> Vec<serde_json::Value> {
+    let Ok(content) = tokio::fs::read_to_string(path).await else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    json["transcription"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .map(|seg| serde_json::json!({
+                    "start": seg["offsets"]["from"].as_f64().unwrap_or(0.0) / 1000.0,
+                    "end": seg["offsets"]["to"].as_f64().unwrap_or(0.0) / 1000.0,
+                    "text": seg["text"].as_str().unwrap_or("").trim(),
+                }))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Locate `phrase` inside a transcript and return the playback positions
+/// where it was spoken. Uses timed segments when available, otherwise
+/// estimates the position from the character offset within the text.
+fn locate_phrase(hit: &serde_json::Value, phrase: &str, max_matches: usize) -> Vec<serde_json::Value> {
+    let needle = phrase.to_lowercase();
+    let mut matches = Vec::new();
+
+    if let Some(segments) = hit["segments"].as_array().filter(|s| !s.is_empty()) {
+        for seg in segments {
+            let seg_text = seg["text"].as_str().unwrap_or("");
+            if seg_text.to_lowercase().contains(&needle) {
+                matches.push(serde_json::json!({
+                    "start": seg["start"].as_f64().unwrap_or(0.0),
+                    "end": seg["end"].as_f64().unwrap_or(0.0),
+                    "text": seg_text,
+                    "estimated": false,
+                }));
+                if matches.len() >= max_matches {
+                    break;
+                }
+            }
+        }
+        return matches;
+    }
+
+    let text = hit["text"].as_str().unwrap_or("");
+    let duration = hit["durationSecs"].as_i64().unwrap_or(0) as f64;
+    let lower = text.to_lowercase();
+    let total_chars = lower.chars().count().max(1) as f64;
+    for (byte_idx, _) in lower.match_indices(&needle).take(max_matches) {
+        let char_idx = lower[..byte_idx].chars().count() as f64;
+        let context: String = lower[byte_idx..].chars().take(80).collect();
+        matches.push(serde_json::json!({
+            "start": if duration > 0.0 { (char_idx / total_chars * duration).floor() } else { 0.0 },
+            "end": serde_json::Value::Null,
+            "text": context,
+            "estimated": true,
+        }));
+    }
+    matches
+}
+
+/// Full-text search across all completed transcripts. Each result carries an
+/// FTS snippet plus the timestamps where the phrase occurs so the player can
+/// seek straight to it.
+#[tauri::command]
+pub async fn search_transcripts(
+    db: State<'_, Arc<Mutex<Database>>>,
+    query: String,
+    limit: Option<i64>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let limit = limit.unwrap_or(50).clamp(1, 500);
+
+    let hits = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        db_lock
+            .search_transcripts(&query, limit)
+            .map_err(|e| e.to_string())?
+    };
+
+    Ok(hits
+        .into_iter()
+        .map(|hit| {
+            let matches = locate_phrase(&hit, &query, 20);
+            serde_json::json!({
+                "id": hit["id"],
+                "source": hit["source"],
+                "title": hit["title"],
+                "snippet": hit["snippet"],
+                "durationSecs": hit["durationSecs"],
+                "createdAt": hit["createdAt"],
+                "matches": matches,
+            })
+        })
+        .collect())
+}
+
 fn map_local_model_to_filename(model_id: &str) -> Result<&'static str, String> {
     match model_id {
         "whisper-tiny" => Ok("ggml-tiny.bin"),
@@ -648,7 +1120,7 @@ pub async fn check_openai_transcription_api(
         model.trim().to_string()
     };
 
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::client_builder()
         .user_agent("YTDL/3.0")
         .timeout(std::time::Duration::from_secs(15))
         .build()
@@ -705,9 +1177,9 @@ pub async fn install_local_transcription(
 
     let whisper_cli = bin_dir.join("whisper-cli.exe");
 
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::client_builder()
         .user_agent("YTDL/3.0")
-        .timeout(std::time::Duration::from_secs(300))
+        .connect_timeout(std::time::Duration::from_secs(30))
         .build()
         .map_err(|e| e.to_string())?;
 
@@ -718,43 +1190,75 @@ pub async fn install_local_transcription(
             "progress": 10
         }));
 
-        let release_json: serde_json::Value = client
-            .get("https://api.github.com/repos/ggml-org/whisper.cpp/releases/latest")
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch whisper.cpp release info: {}", e))?
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse whisper.cpp release info: {}", e))?;
-
-        let assets = release_json["assets"]
-            .as_array()
-            .ok_or_else(|| "whisper.cpp release assets are missing".to_string())?;
-
         let asset_name = if cfg!(target_arch = "x86") {
             "whisper-bin-Win32.zip"
         } else {
             "whisper-bin-x64.zip"
         };
+        let temp_zip = whisper_root.join("whisper-bin-temp.zip");
 
-        let asset_url = assets
-            .iter()
-            .find(|a| a["name"].as_str() == Some(asset_name))
-            .and_then(|a| a["browser_download_url"].as_str())
-            .ok_or_else(|| format!("Could not find '{}' in whisper.cpp latest release", asset_name))?;
+        // Configured mirrors first; the official asset URL has to be looked
+        // up through the GitHub API, so only do that if they all fail.
+        // Either way the archive has to match the pinned checksum or the
+        // digest GitHub lists before anything in it gets run
+        let pinned = pinned_checksum(&app, &client, "whisper_checksums", asset_name).await;
+        let mirrors = tool_sources(&app, "whisper_mirrors", asset_name, None);
+        let from_mirror = if mirrors.is_empty() {
+            None
+        } else {
+            fetch_tool_file(&app, &client, &mirrors, &temp_zip, "whisper.cpp").await.ok()
+        };
 
-        let zip_bytes = client
-            .get(asset_url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to download whisper.cpp binaries: {}", e))?
-            .bytes()
-            .await
-            .map_err(|e| format!("Failed to read whisper.cpp archive: {}", e))?;
+        if let Some((downloaded, _)) = from_mirror {
+            let expected = match pinned {
+                Some(hash) => Some(hash),
+                None => github_asset_digest(&client, "ggml-org/whisper.cpp", asset_name)
+                    .await
+                    .unwrap_or_else(|e| {
+                        log::warn!("[whisper] {}", e);
+                        None
+                    }),
+            };
+            verify_checksum(&downloaded, expected, asset_name, &temp_zip, "whisper_checksums")?;
+        } else {
+            let release_json: serde_json::Value = client
+                .get("https://api.github.com/repos/ggml-org/whisper.cpp/releases/latest")
+                .timeout(std::time::Duration::from_secs(30))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to fetch whisper.cpp release info: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse whisper.cpp release info: {}", e))?;
 
-        let temp_zip = whisper_root.join("whisper-bin-temp.zip");
-        std::fs::write(&temp_zip, &zip_bytes)
-            .map_err(|e| format!("Failed to write whisper.cpp temp archive: {}", e))?;
+            let assets = release_json["assets"]
+                .as_array()
+                .ok_or_else(|| "whisper.cpp release assets are missing".to_string())?;
+
+            let asset = assets
+                .iter()
+                .find(|a| a["name"].as_str() == Some(asset_name))
+                .ok_or_else(|| format!("Could not find '{}' in whisper.cpp latest release", asset_name))?;
+            let asset_url = asset["browser_download_url"]
+                .as_str()
+                .ok_or_else(|| format!("Could not find '{}' in whisper.cpp latest release", asset_name))?;
+
+            let downloaded = crate::tool_install_commands::download_to_file(&app, &client, asset_url, &temp_zip, "whisper.cpp")
+                .await
+                .map_err(|e| format!("Failed to download whisper.cpp binaries: {}", e))?;
+
+            // The official download is trusted over HTTPS when the release
+            // carries no digest and nothing is pinned
+            let expected = pinned.or_else(|| {
+                asset["digest"]
+                    .as_str()
+                    .and_then(|d| d.strip_prefix("sha256:"))
+                    .map(str::to_lowercase)
+            });
+            if expected.is_some() {
+                verify_checksum(&downloaded, expected, asset_name, &temp_zip, "whisper_checksums")?;
+            }
+        }
 
         let file = std::fs::File::open(&temp_zip)
             .map_err(|e| format!("Failed to open whisper.cpp archive: {}", e))?;
@@ -813,16 +1317,14 @@ pub async fn install_local_transcription(
             "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{}?download=true",
             model_filename
         );
-        let model_bytes = client
-            .get(&model_url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to download model '{}': {}", model_filename, e))?
-            .bytes()
+        // Download next to the final path so an interrupted download is
+        // never mistaken for an installed model
+        let partial = model_dir.join(format!("{}.part", model_filename));
+        let sources = tool_sources(&app, "whisper_mirrors", model_filename, Some(&model_url));
+        fetch_tool_file(&app, &client, &sources, &partial, "whisper-model")
             .await
-            .map_err(|e| format!("Failed to read model '{}': {}", model_filename, e))?;
-
-        std::fs::write(&model_path, &model_bytes)
+            .map_err(|e| format!("Failed to download model '{}': {}", model_filename, e))?;
+        std::fs::rename(&partial, &model_path)
             .map_err(|e| format!("Failed to save model '{}': {}", model_filename, e))?;
     }
 
@@ -1004,10 +1506,21 @@ async fn install_local_transcription_android(
         model_filename
     );
 
+    // Try each mirror in order, then the official URL. Local folders are
+    // copied instead of downloaded.
+    let fetch_chain = tool_sources(&app, "whisper_mirrors", model_filename, Some(&model_url))
+        .iter()
+        .map(|source| match source {
+            crate::tool_install_commands::ToolSource::Remote(url) => format!("curl -fL -o '{}' '{}'", model_path, url.replace('\'', "")),
+            crate::tool_install_commands::ToolSource::Local(path) => format!("cp '{}' '{}'", path.to_string_lossy().replace('\'', ""), model_path),
+        })
+        .collect::<Vec<_>>()
+        .join(" || ");
+
     // Check if model already exists, if not download via curl in Termux
     let model_cmd = format!(
-        "mkdir -p '{}' && if [ -f '{}' ]; then echo 'MODEL_EXISTS'; else curl -L -o '{}' '{}' 2>&1 && echo 'MODEL_DOWNLOADED' || echo 'MODEL_DOWNLOAD_FAILED'; fi",
-        model_dir, model_path, model_path, model_url
+        "mkdir -p '{}' && if [ -f '{}' ]; then echo 'MODEL_EXISTS'; else {{ {}; }} 2>&1 && echo 'MODEL_DOWNLOADED' || echo 'MODEL_DOWNLOAD_FAILED'; fi",
+        model_dir, model_path, fetch_chain
     );
 
     match crate::android_bridge::run_termux_check(&model_cmd, &model_check_file) {
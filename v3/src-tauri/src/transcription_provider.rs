@@ -0,0 +1,277 @@
+//! Remote speech-to-text backends for the "api" transcription provider.
+//!
+//! OpenAI, Groq and most self-hosted servers (faster-whisper-server, speaches,
+//! LocalAI) speak the OpenAI multipart `audio/transcriptions` API. Deepgram
+//! takes the raw audio as the request body. Everything that differs between
+//! them — endpoint, auth header, upload style and where the text lives in the
+//! response — is described by an [`ApiProvider`] built from settings.
+
+use crate::db::Database;
+use crate::secrets;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UploadStyle {
+    /// `multipart/form-data` with `file` + `model` fields (OpenAI-compatible).
+    Multipart,
+    /// Raw audio bytes as the request body (Deepgram).
+    RawBody,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiProvider {
+    pub name: String,
+    pub endpoint: String,
+    pub auth_header: String,
+    pub auth_prefix: String,
+    pub api_key: String,
+    pub model: String,
+    pub upload: UploadStyle,
+    /// JSON pointers into the response body.
+    pub text_path: String,
+    pub language_path: String,
+    pub segments_path: String,
+    pub max_upload_bytes: Option<usize>,
+}
+
+pub struct TranscriptionOutput {
+    pub text: String,
+    pub language: String,
+    /// `{start, end, text}` objects, seconds. Empty if the provider has none.
+    pub segments: Vec<serde_json::Value>,
+}
+
+const OPENAI_ENDPOINT: &str = "https://api.openai.com/v1/audio/transcriptions";
+const GROQ_ENDPOINT: &str = "https://api.groq.com/openai/v1/audio/transcriptions";
+const DEEPGRAM_ENDPOINT: &str = "https://api.deepgram.com/v1/listen";
+
+impl ApiProvider {
+    /// Build the provider from the `transcribe_api_*` settings. An explicit
+    /// `model_override` (from the UI) wins over the stored model. The keys'
+    /// keyring entries are read by the caller before it locks `db`.
+    pub fn from_settings(
+        db: &Database,
+        model_override: &str,
+        transcribe_key: Option<String>,
+        openai_key: Option<String>,
+    ) -> Self {
+        let setting = |key: &str| db.get_setting(key).unwrap_or(None).unwrap_or_default();
+
+        let name = Some(setting("transcribe_api_provider"))
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "openai".to_string());
+        // The OpenAI key is only ever sent to OpenAI; other providers need
+        // their own.
+        let api_key = Some(secrets::secret_or_setting(db, "transcribe_api_key", transcribe_key))
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| match name.as_str() {
+                "openai" => secrets::secret_or_setting(db, "openai_api_key", openai_key),
+                _ => String::new(),
+            });
+        let stored_model = match name.as_str() {
+            "openai" => setting("openai_model"),
+            _ => setting("transcribe_api_model"),
+        };
+        let model = if !model_override.trim().is_empty() {
+            model_override.trim().to_string()
+        } else {
+            stored_model.trim().to_string()
+        };
+
+        match name.as_str() {
+            "groq" => Self {
+                endpoint: GROQ_ENDPOINT.to_string(),
+                model: if model.is_empty() || model == "whisper-1" {
+                    "whisper-large-v3".to_string()
+                } else {
+                    model
+                },
+                max_upload_bytes: Some(25 * 1024 * 1024),
+                ..Self::openai_compatible(name, api_key)
+            },
+            "deepgram" => {
+                let model = if model.is_empty() { "nova-2".to_string() } else { model };
+                Self {
+                    name,
+                    endpoint: format!(
+                        "{}?model={}&smart_format=true&detect_language=true&utterances=true",
+                        DEEPGRAM_ENDPOINT, model
+                    ),
+                    auth_header: "Authorization".to_string(),
+                    auth_prefix: "Token ".to_string(),
+                    api_key,
+                    model,
+                    upload: UploadStyle::RawBody,
+                    text_path: "/results/channels/0/alternatives/0/transcript".to_string(),
+                    language_path: "/results/channels/0/detected_language".to_string(),
+                    segments_path: "/results/utterances".to_string(),
+                    max_upload_bytes: None,
+                }
+            }
+            "custom" => {
+                let or_default = |key: &str, default: &str| {
+                    Some(setting(key))
+                        .filter(|v| !v.trim().is_empty())
+                        .unwrap_or_else(|| default.to_string())
+                };
+                Self {
+                    endpoint: setting("transcribe_api_url").trim().to_string(),
+                    auth_header: or_default("transcribe_api_auth_header", "Authorization"),
+                    // Prefix may legitimately be empty (e.g. "X-Api-Key: <key>")
+                    auth_prefix: db
+                        .get_setting("transcribe_api_auth_prefix")
+                        .unwrap_or(None)
+                        .unwrap_or_else(|| "Bearer ".to_string()),
+                    model,
+                    upload: if setting("transcribe_api_format") == "raw" {
+                        UploadStyle::RawBody
+                    } else {
+                        UploadStyle::Multipart
+                    },
+                    text_path: or_default("transcribe_api_text_path", "/text"),
+                    language_path: or_default("transcribe_api_language_path", "/language"),
+                    segments_path: or_default("transcribe_api_segments_path", "/segments"),
+                    max_upload_bytes: None,
+                    ..Self::openai_compatible(name, api_key)
+                }
+            }
+            _ => Self {
+                model: if model.is_empty() { "whisper-1".to_string() } else { model },
+                ..Self::openai_compatible("openai".to_string(), api_key)
+            },
+        }
+    }
+
+    fn openai_compatible(name: String, api_key: String) -> Self {
+        Self {
+            name,
+            endpoint: OPENAI_ENDPOINT.to_string(),
+            auth_header: "Authorization".to_string(),
+            auth_prefix: "Bearer ".to_string(),
+            api_key,
+            model: String::new(),
+            upload: UploadStyle::Multipart,
+            text_path: "/text".to_string(),
+            language_path: "/language".to_string(),
+            segments_path: "/segments".to_string(),
+            max_upload_bytes: Some(25 * 1024 * 1024),
+        }
+    }
+
+    /// Self-hosted endpoints usually run without auth; hosted ones never do.
+    pub fn requires_api_key(&self) -> bool {
+        self.name != "custom"
+    }
+
+    pub async fn transcribe(&self, bytes: Vec<u8>, file_name: &str) -> Result<TranscriptionOutput, String> {
+        if self.endpoint.is_empty() {
+            return Err("Transcription API endpoint is not configured".to_string());
+        }
+        if self.requires_api_key() && self.api_key.trim().is_empty() {
+            return Err(format!("No API key configured for transcription provider '{}'", self.name));
+        }
+        if let Some(limit) = self.max_upload_bytes {
+            if bytes.len() > limit {
+                return Err(format!(
+                    "Audio file is too large ({:.1} MB). The {} API limit is {} MB. \
+                     Try a shorter clip or use local transcription.",
+                    bytes.len() as f64 / (1024.0 * 1024.0),
+                    self.name,
+                    limit / (1024 * 1024)
+                ));
+            }
+        }
+
+        let client = crate::proxy::client_builder()
+            .timeout(std::time::Duration::from_secs(300))
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        let mut request = client.post(&self.endpoint);
+        if !self.api_key.trim().is_empty() {
+            request = request.header(
+                self.auth_header.as_str(),
+                format!("{}{}", self.auth_prefix, self.api_key.trim()),
+            );
+        }
+
+        request = match self.upload {
+            UploadStyle::Multipart => {
+                // Only whisper models return timed segments (verbose_json);
+                // gpt-4o-*-transcribe rejects it.
+                let response_format = if self.model.contains("whisper") { "verbose_json" } else { "json" };
+                let part = reqwest::multipart::Part::bytes(bytes).file_name(file_name.to_string());
+                let form = reqwest::multipart::Form::new()
+                    .text("model", self.model.clone())
+                    .text("response_format", response_format)
+                    .part("file", part);
+                request.multipart(form)
+            }
+            UploadStyle::RawBody => request
+                .header("Content-Type", content_type_for(file_name))
+                .body(bytes),
+        };
+
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("{} API error ({}): {}", self.name, status, body));
+        }
+
+        let json: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        Ok(self.parse_response(&json))
+    }
+
+    fn parse_response(&self, json: &serde_json::Value) -> TranscriptionOutput {
+        let text = json
+            .pointer(&self.text_path)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .trim()
+            .to_string();
+        let language = json
+            .pointer(&self.language_path)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        let segments = json
+            .pointer(&self.segments_path)
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|seg| {
+                        // Deepgram utterances use "transcript" instead of "text"
+                        let seg_text = seg["text"]
+                            .as_str()
+                            .or_else(|| seg["transcript"].as_str())
+                            .unwrap_or("");
+                        serde_json::json!({
+                            "start": seg["start"].as_f64().unwrap_or(0.0),
+                            "end": seg["end"].as_f64().unwrap_or(0.0),
+                            "text": seg_text.trim(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        TranscriptionOutput { text, language, segments }
+    }
+}
+
+fn content_type_for(file_name: &str) -> &'static str {
+    let ext = std::path::Path::new(file_name)
+        .extension()
+        .and_then(|v| v.to_str())
+        .map(|v| v.to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "m4a" | "aac" => "audio/mp4",
+        "flac" => "audio/flac",
+        "ogg" | "opus" => "audio/ogg",
+        _ => "application/octet-stream",
+    }
+}
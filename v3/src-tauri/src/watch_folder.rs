@@ -0,0 +1,156 @@
+//! Downloads from link files dropped into a folder.
+//!
+//! When `watch_folder` is set, `.txt` and `.url` files appearing in it are
+//! read for video links, each link is queued like one sent by the browser
+//! extension, and the file is moved into a `done/` subfolder. Pointing it
+//! at a synced folder (Syncthing, Dropbox, …) lets other devices send links.
+//! The folder is polled, so nothing needs to be restarted when the setting
+//! changes.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands;
+use crate::db::Database;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Files changed more recently than this may still be syncing.
+const SETTLE_TIME: Duration = Duration::from_secs(3);
+const DONE_DIR: &str = "done";
+
+fn watched_folder(app: &AppHandle) -> Option<PathBuf> {
+    let db = app.try_state::<Arc<Mutex<Database>>>()?;
+    let db_lock = db.lock().ok()?;
+    let folder = db_lock.get_setting("watch_folder").unwrap_or(None)?;
+    let folder = folder.trim();
+    (!folder.is_empty()).then(|| PathBuf::from(folder))
+}
+
+fn is_link_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("txt") || e.eq_ignore_ascii_case("url"))
+}
+
+/// Link files in `folder` that have stopped changing.
+fn pending_files(folder: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(folder) else { return Vec::new() };
+    let now = SystemTime::now();
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .map(|entry| entry.path())
+        .filter(|path| is_link_file(path))
+        .filter(|path| {
+            std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| now.duration_since(modified).unwrap_or_default() >= SETTLE_TIME)
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// The links in a `.txt` file (any `http(s)://` word) or a `.url`
+/// Internet Shortcut (its `URL=` line).
+fn links_in(contents: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        let line = line.strip_prefix("URL=").unwrap_or(line);
+        for word in line.split_whitespace() {
+            let word = word.trim_matches(|c: char| matches!(c, '<' | '>' | '"' | '\'' | ',' | ';'));
+            let lower = word.to_lowercase();
+            if (lower.starts_with("http://") || lower.starts_with("https://")) && !links.iter().any(|l| l == word) {
+                links.push(word.to_string());
+            }
+        }
+    }
+    links
+}
+
+/// Move a handled file into `done/`, keeping earlier files of the same name.
+fn move_to_done(folder: &Path, file: &Path) -> std::io::Result<()> {
+    let done = folder.join(DONE_DIR);
+    std::fs::create_dir_all(&done)?;
+    let name = file.file_name().unwrap_or_default();
+    let mut target = done.join(name);
+    if target.exists() {
+        let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+        let ext = file.extension().unwrap_or_default().to_string_lossy();
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        target = done.join(format!("{}-{}.{}", stem, stamp, ext));
+    }
+    std::fs::rename(file, target)
+}
+
+/// Queue the links in `file` and move it to `done/`. Returns false when
+/// the file stayed where it is.
+async fn process_file(app: &AppHandle, folder: &Path, file: &Path) -> bool {
+    let contents = match std::fs::read_to_string(file) {
+        Ok(contents) => contents,
+        Err(e) => {
+            log::warn!("[watch_folder] Failed to read {}: {}", file.display(), e);
+            return false;
+        }
+    };
+    let links = links_in(&contents);
+    log::info!("[watch_folder] {} link(s) in {}", links.len(), file.display());
+
+    for url in links {
+        let result = commands::start_download(
+            app.clone(),
+            app.state(),
+            app.state(),
+            url.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await;
+        let payload = match result {
+            Ok(id) => serde_json::json!({ "url": url, "id": id }),
+            Err(e) => {
+                log::warn!("[watch_folder] Failed to queue {}: {}", url, e);
+                serde_json::json!({ "url": url, "error": e.to_string(), "code": e.code() })
+            }
+        };
+        let _ = app.emit("external-download", payload);
+    }
+
+    match move_to_done(folder, file) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("[watch_folder] Failed to move {} to {}/: {}", file.display(), DONE_DIR, e);
+            false
+        }
+    }
+}
+
+/// Start polling the watch folder.
+pub fn init(app: &tauri::App) {
+    let handle = app.handle().clone();
+    tauri::async_runtime::spawn(async move {
+        // Files that couldn't be read or moved aren't queued again
+        let mut stuck: HashSet<PathBuf> = HashSet::new();
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let Some(folder) = watched_folder(&handle) else { continue };
+            for file in pending_files(&folder) {
+                if !stuck.contains(&file) && !process_file(&handle, &folder, &file).await {
+                    stuck.insert(file);
+                }
+            }
+        }
+    });
+}
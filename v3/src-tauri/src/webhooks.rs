@@ -0,0 +1,235 @@
+//! Webhooks fired when downloads finish.
+//!
+//! The `webhook_url` setting is called for every download; a download can
+//! also carry its own URL (`set_download_webhook`). Each call is retried a
+//! few times and the outcome recorded in `webhook_deliveries`.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::db::Database;
+
+/// Delay before each retry; the first attempt goes out immediately.
+const RETRY_DELAYS: &[u64] = &[2, 10, 30];
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(url.trim()).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("Webhook URL must start with http:// or https://".to_string());
+    }
+    if parsed.host_str().is_none() {
+        return Err("Webhook URL has no host".to_string());
+    }
+    Ok(())
+}
+
+/// Replace `{{key}}` placeholders. Values are JSON-escaped (without the
+/// surrounding quotes) so they can sit inside string literals of a JSON
+/// template.
+fn render_template(template: &str, fields: &serde_json::Value) -> String {
+    let mut out = template.to_string();
+    if let Some(map) = fields.as_object() {
+        for (key, value) in map {
+            let text = match value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Null => String::new(),
+                other => other.to_string(),
+            };
+            let escaped = serde_json::to_string(&text).unwrap_or_default();
+            out = out.replace(&format!("{{{{{}}}}}", key), &escaped[1..escaped.len() - 1]);
+        }
+    }
+    out
+}
+
+/// POST `body` to `url`, retrying on connection errors and non-2xx
+/// responses. Returns the last status code, attempt count and error.
+async fn deliver(url: &str, body: &str) -> (Option<u16>, u32, Result<(), String>) {
+    let client = match crate::proxy::client_builder().timeout(REQUEST_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => return (None, 0, Err(e.to_string())),
+    };
+    let content_type = if serde_json::from_str::<serde_json::Value>(body).is_ok() {
+        "application/json"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+
+    let mut attempts = 0u32;
+    let mut status = None;
+    let mut result = Err(String::new());
+    for delay in std::iter::once(0).chain(RETRY_DELAYS.iter().copied()) {
+        if delay > 0 {
+            tokio::time::sleep(Duration::from_secs(delay)).await;
+        }
+        attempts += 1;
+        let response = client
+            .post(url)
+            .header("Content-Type", content_type)
+            .header("User-Agent", concat!("YTDL/", env!("CARGO_PKG_VERSION")))
+            .body(body.to_string())
+            .send()
+            .await;
+        match response {
+            Ok(resp) => {
+                status = Some(resp.status().as_u16());
+                if resp.status().is_success() {
+                    result = Ok(());
+                    break;
+                }
+                result = Err(format!("HTTP {}", resp.status()));
+                // Client errors won't go away by retrying
+                if resp.status().is_client_error() && resp.status().as_u16() != 429 {
+                    break;
+                }
+            }
+            Err(e) => result = Err(e.to_string()),
+        }
+    }
+    (status, attempts, result)
+}
+
+async fn send_and_log(
+    db: &Arc<Mutex<Database>>,
+    download_id: Option<&str>,
+    url: &str,
+    event: &str,
+    body: &str,
+) -> Result<Option<u16>, String> {
+    let (status, attempts, result) = deliver(url, body).await;
+    match &result {
+        Ok(()) => log::info!("[webhooks] Delivered {} to {}", event, url),
+        Err(e) => log::warn!("[webhooks] {} to {} failed after {} attempts: {}", event, url, attempts, e),
+    }
+    if let Ok(db) = db.lock() {
+        let error = result.as_ref().err().map(String::as_str).unwrap_or_default();
+        let _ = db.insert_webhook_delivery(download_id, url, event, result.is_ok(), status, attempts, error);
+    }
+    result.map(|_| status)
+}
+
+/// Fire the global and per-download webhooks for a finished download.
+/// `outcome` is the output path or the error message.
+pub fn download_finished(app: &AppHandle, id: &str, outcome: Result<&str, &str>) {
+    let Some(db) = app.try_state::<Arc<Mutex<Database>>>() else { return };
+    let db = db.inner().clone();
+
+    let (row, targets, template) = {
+        let Ok(db_lock) = db.lock() else { return };
+        let setting = |key: &str| db_lock.get_setting(key).unwrap_or(None).unwrap_or_default();
+        let mut targets = Vec::new();
+        let global = setting("webhook_url");
+        if !global.trim().is_empty() {
+            targets.push(global.trim().to_string());
+        }
+        let own = db_lock.get_download_webhook(id).unwrap_or_default();
+        if !own.trim().is_empty() && !targets.contains(&own) {
+            targets.push(own.trim().to_string());
+        }
+        let row = db_lock.get_download(id).ok().flatten();
+        (row, targets, setting("webhook_payload"))
+    };
+    if targets.is_empty() {
+        return;
+    }
+
+    let row = row.unwrap_or_default();
+    let field = |key: &str| row[key].as_str().unwrap_or_default().to_string();
+    // Pausing and cancelling stop yt-dlp the same way a failure does
+    if matches!(field("status").as_str(), "paused" | "cancelled") {
+        return;
+    }
+
+    let event = if outcome.is_ok() { "download.completed" } else { "download.failed" };
+    let fields = serde_json::json!({
+        "event": event,
+        "id": id,
+        "title": field("title"),
+        "url": field("url"),
+        "status": if outcome.is_ok() { "completed" } else { "failed" },
+        "filePath": outcome.ok().unwrap_or_default(),
+        "fileSize": row["fileSize"],
+        "error": outcome.err().unwrap_or_default(),
+        "finishedAt": chrono::Utc::now().to_rfc3339(),
+    });
+    let body = if template.trim().is_empty() {
+        fields.to_string()
+    } else {
+        render_template(&template, &fields)
+    };
+
+    let id = id.to_string();
+    tauri::async_runtime::spawn(async move {
+        for url in targets {
+            let _ = send_and_log(&db, Some(&id), &url, event, &body).await;
+        }
+    });
+}
+
+/// Set or clear (empty `url`) the webhook for one download.
+#[tauri::command]
+pub async fn set_download_webhook(
+    db: State<'_, Arc<Mutex<Database>>>,
+    id: String,
+    url: String,
+) -> Result<(), String> {
+    let url = url.trim();
+    if !url.is_empty() {
+        validate_webhook_url(url)?;
+    }
+    let db = db.lock().map_err(|e| e.to_string())?;
+    db.set_download_webhook(&id, url).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_webhook_deliveries(
+    db: State<'_, Arc<Mutex<Database>>>,
+    limit: Option<i64>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let db = db.lock().map_err(|e| e.to_string())?;
+    db.get_webhook_deliveries(limit.unwrap_or(50).clamp(1, 200))
+        .map_err(|e| e.to_string())
+}
+
+/// Send a sample payload to `url` (or the configured webhook) using the
+/// current template.
+#[tauri::command]
+pub async fn test_webhook(
+    db: State<'_, Arc<Mutex<Database>>>,
+    url: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let (url, template) = {
+        let db_lock = db.lock().map_err(|e| e.to_string())?;
+        let setting = |key: &str| db_lock.get_setting(key).unwrap_or(None).unwrap_or_default();
+        let url = url.filter(|u| !u.trim().is_empty()).unwrap_or_else(|| setting("webhook_url"));
+        (url.trim().to_string(), setting("webhook_payload"))
+    };
+    if url.is_empty() {
+        return Err("No webhook URL configured".to_string());
+    }
+    validate_webhook_url(&url)?;
+
+    let fields = serde_json::json!({
+        "event": "test",
+        "id": "test",
+        "title": "Test webhook",
+        "url": "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+        "status": "completed",
+        "filePath": "",
+        "fileSize": 0,
+        "error": "",
+        "finishedAt": chrono::Utc::now().to_rfc3339(),
+    });
+    let body = if template.trim().is_empty() {
+        fields.to_string()
+    } else {
+        render_template(&template, &fields)
+    };
+
+    let db = db.inner().clone();
+    let status = send_and_log(&db, None, &url, "test", &body).await?;
+    Ok(serde_json::json!({ "success": true, "statusCode": status }))
+}
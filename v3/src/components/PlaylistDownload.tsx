@@ -1,4 +1,4 @@
-import { useState, useCallback } from "react";
+import { useState, useCallback, useEffect, useRef } from "react";
 import { useTranslation } from "react-i18next";
 import { useAtomValue } from "jotai";
 import { Button } from "@/components/ui/button";
@@ -11,8 +11,14 @@ import {
   List,
   ClipboardPaste,
   ChevronDown,
+  UserRound,
 } from "lucide-react";
-import { commands, type PlaylistInfo } from "@/lib/tauri";
+import {
+  commands,
+  events,
+  type AccountPlaylist,
+  type PlaylistInfo,
+} from "@/lib/tauri";
 import {
   DropdownMenu,
   DropdownMenuContent,
@@ -35,25 +41,102 @@ export function PlaylistDownload({ onDownloadStart }: PlaylistDownloadProps) {
   const [startIndex, setStartIndex] = useState("1");
   const [endIndex, setEndIndex] = useState("");
   const [selectedQuality, setSelectedQuality] = useState<string>("best");
+  const [total, setTotal] = useState<number | null>(null);
 
-  const handleFetchPlaylist = useCallback(async () => {
-    if (!url.trim()) return;
+  // Large playlists arrive in pages; list them as they come so a range
+  // can be downloaded before the rest is fetched. The end of the range
+  // follows the pages until it's edited.
+  const fetchingRef = useRef(false);
+  const fetchedRef = useRef(0);
+  // Fetching the same playlist again means "refresh"; otherwise the
+  // backend reuses a listing from the last few minutes
+  const lastFetchRef = useRef<string | null>(null);
+  useEffect(() => {
+    const unlisten = events.onPlaylistInfoProgress((evt) => {
+      if (!fetchingRef.current) return;
+      const previous = fetchedRef.current;
+      fetchedRef.current = evt.fetched;
+      setTotal(evt.total);
+      setPlaylist((prev) => ({
+        id: evt.id,
+        title: evt.title,
+        entries: [...(prev?.entries ?? []), ...evt.entries],
+        entryCount: evt.fetched,
+      }));
+      setEndIndex((end) =>
+        end === "" || end === String(previous) ? String(evt.fetched) : end,
+      );
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
 
+  const beginFetch = useCallback(() => {
+    fetchingRef.current = true;
+    fetchedRef.current = 0;
     setLoading(true);
     setError(null);
     setPlaylist(null);
+    setTotal(null);
+    setStartIndex("1");
+    setEndIndex("");
+  }, []);
+
+  const endFetch = useCallback(() => {
+    fetchingRef.current = false;
+    setLoading(false);
+  }, []);
 
+  const handleFetchPlaylist = useCallback(async () => {
+    if (!url.trim()) return;
+
+    const refresh = lastFetchRef.current === url.trim();
+    lastFetchRef.current = url.trim();
+    beginFetch();
     try {
-      const info = await commands.getPlaylistInfo(url.trim());
+      const info = await commands.getPlaylistInfo(url.trim(), refresh);
       setPlaylist(info);
-      setStartIndex("1");
-      setEndIndex(String(info.entryCount));
+      setEndIndex((end) =>
+        end === "" || end === String(fetchedRef.current)
+          ? String(info.entryCount)
+          : end,
+      );
     } catch (err) {
+      setPlaylist(null);
       setError(err instanceof Error ? err.message : "Failed to fetch playlist");
     } finally {
-      setLoading(false);
+      endFetch();
     }
-  }, [url]);
+  }, [url, beginFetch, endFetch]);
+
+  // Watch Later and friends need the browser's cookies; the backend
+  // returns the list URL to download from.
+  const handleFetchAccountPlaylist = useCallback(
+    async (name: AccountPlaylist) => {
+      const refresh = lastFetchRef.current === name;
+      lastFetchRef.current = name;
+      beginFetch();
+      try {
+        const info = await commands.getAccountPlaylist(name, refresh);
+        setUrl(info.url);
+        setPlaylist(info);
+        setEndIndex((end) =>
+          end === "" || end === String(fetchedRef.current)
+            ? String(info.entryCount)
+            : end,
+        );
+      } catch (err) {
+        setPlaylist(null);
+        setError(
+          err instanceof Error ? err.message : "Failed to fetch playlist",
+        );
+      } finally {
+        endFetch();
+      }
+    },
+    [beginFetch, endFetch],
+  );
 
   const handleDownloadPlaylist = useCallback(async () => {
     if (!playlist) return;
@@ -69,12 +152,23 @@ export function PlaylistDownload({ onDownloadStart }: PlaylistDownloadProps) {
         format: selectedQuality,
       });
       onDownloadStart();
-      setUrl("");
-      setPlaylist(null);
+      // A range taken from the first pages: keep fetching the rest
+      if (!loading) {
+        setUrl("");
+        setPlaylist(null);
+      }
     } catch (err) {
       setError(err instanceof Error ? err.message : "Failed to start download");
     }
-  }, [playlist, url, startIndex, endIndex, selectedQuality, onDownloadStart]);
+  }, [
+    playlist,
+    url,
+    startIndex,
+    endIndex,
+    selectedQuality,
+    onDownloadStart,
+    loading,
+  ]);
 
   const handlePaste = useCallback(async () => {
     try {
@@ -156,6 +250,30 @@ export function PlaylistDownload({ onDownloadStart }: PlaylistDownloadProps) {
             </DropdownMenuItem>
           </DropdownMenuContent>
         </DropdownMenu>
+        <DropdownMenu>
+          <DropdownMenuTrigger asChild>
+            <Button
+              variant="outline"
+              disabled={loading}
+              title={t("download.accountPlaylists")}
+              className="h-12 w-12 px-0 rounded-full bg-background/50 border-border/50 hover:bg-muted shadow-sm flex-shrink-0"
+            >
+              <UserRound className="w-5 h-5" />
+            </Button>
+          </DropdownMenuTrigger>
+          <DropdownMenuContent align="end" className="rounded-xl">
+            <DropdownMenuItem
+              onClick={() => handleFetchAccountPlaylist("watch_later")}
+            >
+              {t("download.watchLater")}
+            </DropdownMenuItem>
+            <DropdownMenuItem
+              onClick={() => handleFetchAccountPlaylist("liked")}
+            >
+              {t("download.likedVideos")}
+            </DropdownMenuItem>
+          </DropdownMenuContent>
+        </DropdownMenu>
         <Button
           onClick={handleFetchPlaylist}
           disabled={!url.trim() || loading}
@@ -184,7 +302,7 @@ export function PlaylistDownload({ onDownloadStart }: PlaylistDownloadProps) {
       )}
 
       {/* Loading */}
-      {loading && (
+      {loading && !playlist && (
         <Card className="p-8">
           <div className="flex flex-col items-center gap-3">
             <Loader2 className="w-8 h-8 animate-spin text-primary" />
@@ -196,7 +314,7 @@ export function PlaylistDownload({ onDownloadStart }: PlaylistDownloadProps) {
       )}
 
       {/* Playlist Info */}
-      {playlist && !loading && (
+      {playlist && (
         <div className="space-y-4">
           <Card className="p-4">
             <div className="flex items-center gap-3 mb-4">
@@ -204,7 +322,21 @@ export function PlaylistDownload({ onDownloadStart }: PlaylistDownloadProps) {
               <div>
                 <h3 className="font-semibold">{playlist.title}</h3>
                 <p className="text-sm text-muted-foreground">
-                  {t("download.videosCount", { count: playlist.entryCount })}
+                  {loading ? (
+                    <span className="inline-flex items-center gap-1.5">
+                      <Loader2 className="w-3.5 h-3.5 animate-spin" />
+                      {total
+                        ? t("download.playlistFetchedOf", {
+                            fetched: playlist.entryCount,
+                            total,
+                          })
+                        : t("download.playlistFetched", {
+                            fetched: playlist.entryCount,
+                          })}
+                    </span>
+                  ) : (
+                    t("download.videosCount", { count: playlist.entryCount })
+                  )}
                 </p>
               </div>
             </div>
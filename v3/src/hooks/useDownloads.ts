@@ -1,7 +1,11 @@
 import { useAtom } from "jotai";
 import { useCallback, useEffect, useRef } from "react";
 import { commands, events } from "@/lib/tauri";
-import type { DownloadStatus } from "@/lib/tauri";
+import type {
+  DownloadChoice,
+  DownloadStatus,
+  VideoInfo,
+} from "@/lib/tauri";
 import { downloadsAtom, downloadLoadingAtom } from "@/store/atoms";
 import { toast } from "sonner";
 
@@ -80,6 +84,54 @@ export function useDownloads() {
       }),
     );
 
+    // Status changed outside the window (notification buttons)
+    unlistenPromises.push(
+      events.onDownloadStatusChange((evt) => {
+        setDownloads((prev) =>
+          prev.map((d) => (d.id === evt.id ? { ...d, status: evt.status } : d)),
+        );
+      }),
+    );
+
+    // Wi-Fi-only / low battery / quiet hours: downloads were paused or resumed
+    unlistenPromises.push(
+      events.onNetworkChanged((evt) => {
+        if (evt.paused) {
+          toast.info(
+            evt.batteryLow
+              ? `Battery low: paused ${evt.paused} download(s)`
+              : evt.metered
+                ? `Metered connection: paused ${evt.paused} download(s)`
+                : `Quiet hours: paused ${evt.paused} download(s)`,
+          );
+        } else if (evt.resumed) {
+          toast.info(`Resumed ${evt.resumed} download(s)`);
+        }
+        loadDownloads();
+      }),
+    );
+
+    // Downloads left queued by the last session were started
+    unlistenPromises.push(
+      events.onQueueResumed((resumed) => {
+        toast.info(`Resumed ${resumed.length} queued download(s)`);
+        loadDownloads();
+      }),
+    );
+
+    // Scheduled premieres and livestreams of followed channels
+    unlistenPromises.push(
+      events.onLiveStartingSoon((evt) => {
+        toast.info(`Starting soon: ${evt.title}`);
+      }),
+    );
+    unlistenPromises.push(
+      events.onLiveRecordingStarted((evt) => {
+        toast.info(`Recording live: ${evt.title}`);
+        loadDownloads();
+      }),
+    );
+
     // 2. Then load initial data (listeners are already active)
     loadDownloads();
 
@@ -91,9 +143,24 @@ export function useDownloads() {
   }, [setDownloads, loadDownloads]);
 
   const startDownload = useCallback(
-    async (url: string, formatId?: string) => {
+    async (
+      url: string,
+      formatId?: string,
+      info?: VideoInfo,
+      choice: DownloadChoice = {},
+    ) => {
       try {
-        const id = await commands.startDownload(url, formatId);
+        const id = await commands.startDownload(
+          url,
+          formatId,
+          choice.mode,
+          undefined,
+          info,
+          choice.section,
+          choice.audioLanguages,
+          choice.concurrentFragments,
+          choice.outputDir,
+        );
         // Reload from DB to get real title/thumbnail
         await loadDownloads();
         return id;
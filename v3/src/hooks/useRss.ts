@@ -4,6 +4,9 @@ import { commands, type RssFeed } from "@/lib/tauri";
 import { feedsAtom, feedsLoadingAtom } from "@/store/atoms";
 import { toast } from "sonner";
 
+/** Items loaded per feed at a time. */
+export const FEED_PAGE_SIZE = 120;
+
 export function useRss() {
   const [feeds, setFeeds] = useAtom(feedsAtom);
   const [loading, setLoading] = useAtom(feedsLoadingAtom);
@@ -12,9 +15,24 @@ export function useRss() {
   const loadFeeds = useCallback(async () => {
     try {
       setLoading(true);
-      const data = await commands.getFeeds();
-      // Type assertion: Rust backend returns compatible structure
-      setFeeds(data as unknown as RssFeed[]);
+      const summaries = await commands.getFeeds();
+      const withItems = await Promise.all(
+        summaries.map(async (feed) => {
+          try {
+            const page = await commands.getFeedItems(
+              feed.id,
+              0,
+              FEED_PAGE_SIZE,
+            );
+            return { ...feed, items: page.items };
+          } catch (err) {
+            console.error(`Failed to load items of feed ${feed.id}:`, err);
+            return { ...feed, items: [] };
+          }
+        }),
+      );
+      // Type assertion: keywords come back as the stored string
+      setFeeds(withItems as unknown as RssFeed[]);
     } catch (err) {
       console.error("Failed to load feeds:", err);
     } finally {
@@ -121,5 +139,52 @@ export function useRss() {
     [setFeeds, loadFeeds],
   );
 
-  return { feeds, loading, loadFeeds, addFeed, removeFeed, checkFeed };
+  /** Append the next page of items for each of `feedIds` that has more. */
+  const loadMoreItems = useCallback(
+    async (feedIds: string[]) => {
+      const targets = feeds.filter(
+        (f) =>
+          feedIds.includes(f.id) &&
+          f.items.length < (f.itemCount ?? f.items.length),
+      );
+      const pages = await Promise.all(
+        targets.map(async (feed) => {
+          try {
+            const page = await commands.getFeedItems(
+              feed.id,
+              feed.items.length,
+              FEED_PAGE_SIZE,
+            );
+            return [feed.id, page.items] as const;
+          } catch (err) {
+            console.error(`Failed to load more items of ${feed.id}:`, err);
+            return [feed.id, []] as const;
+          }
+        }),
+      );
+      const byFeed = new Map(pages);
+      setFeeds((prev) =>
+        prev.map((f) => {
+          const more = byFeed.get(f.id);
+          if (!more || more.length === 0) return f;
+          const known = new Set(f.items.map((i) => i.id));
+          return {
+            ...f,
+            items: [...f.items, ...more.filter((i) => !known.has(i.id))],
+          };
+        }),
+      );
+    },
+    [feeds, setFeeds],
+  );
+
+  return {
+    feeds,
+    loading,
+    loadFeeds,
+    loadMoreItems,
+    addFeed,
+    removeFeed,
+    checkFeed,
+  };
 }
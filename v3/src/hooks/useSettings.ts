@@ -26,6 +26,7 @@ export function useSettings() {
           10,
         ),
         speedLimit: parseInt(raw.speed_limit || "0", 10),
+        concurrentFragments: parseInt(raw.concurrent_fragments || "1", 10),
         autoStartDownloads: raw.auto_start_download !== "false",
         theme: (raw.theme || "system") as "light" | "dark" | "system",
         language: raw.language || "en",
@@ -40,14 +41,38 @@ export function useSettings() {
           | "720p"
           | "audio",
         defaultFormat: raw.default_format || "mp4",
+        preferredVcodec: (raw.preferred_vcodec || "any") as
+          | "any"
+          | "avc"
+          | "vp9"
+          | "av1",
+        preferredDynamicRange: (raw.preferred_dynamic_range || "any") as
+          | "any"
+          | "sdr"
+          | "hdr",
+        maxFps: parseInt(raw.max_fps || "0", 10),
         embedThumbnail: raw.embed_thumbnail !== "false",
         embedMetadata: raw.embed_metadata !== "false",
+        embedChapters: raw.embed_chapters !== "false",
+        writeInfoJson: raw.write_info_json === "true",
+        mergePolicy: (raw.merge_policy || "reencode") as
+          | "reencode"
+          | "mkv"
+          | "abort",
         browserForCookies: raw.browser_cookies || "none",
+        cookiesFallbackBrowser: raw.cookies_fallback_browser || "none",
         configPath: raw.config_file || "",
         ytdlpFlags: raw.ytdlp_flags || "",
+        ytdlpExtractorArgs: raw.ytdlp_extractor_args || "",
         rssCheckInterval: parseInt(raw.rss_check_interval || "60", 10),
         rssNotifications: raw.rss_notifications !== "false",
         rssAutoDownload: raw.rss_auto_download === "true",
+        androidStorageTarget: (raw.android_storage_target || "app") as
+          | "app"
+          | "media"
+          | "saf",
+        downloadWifiOnly: raw.download_wifi_only === "true",
+        batteryMinPercent: parseInt(raw.battery_min_percent || "0", 10),
       };
       setSettings(s);
       downloadPathRef.current = s.downloadPath;
@@ -78,6 +103,7 @@ export function useSettings() {
           download_path: "downloadPath",
           max_concurrent_downloads: "maxConcurrentDownloads",
           speed_limit: "speedLimit",
+          concurrent_fragments: "concurrentFragments",
           auto_start_download: "autoStartDownloads",
           notifications: "notifications",
           close_to_tray: "closeToTray",
@@ -85,14 +111,25 @@ export function useSettings() {
           default_quality: "defaultQuality",
           quality_preset: "qualityPreset",
           default_format: "defaultFormat",
+          preferred_vcodec: "preferredVcodec",
+          preferred_dynamic_range: "preferredDynamicRange",
+          max_fps: "maxFps",
           embed_thumbnail: "embedThumbnail",
           embed_metadata: "embedMetadata",
+          embed_chapters: "embedChapters",
+          write_info_json: "writeInfoJson",
+          merge_policy: "mergePolicy",
           browser_cookies: "browserForCookies",
+          cookies_fallback_browser: "cookiesFallbackBrowser",
           config_file: "configPath",
           ytdlp_flags: "ytdlpFlags",
+          ytdlp_extractor_args: "ytdlpExtractorArgs",
           rss_check_interval: "rssCheckInterval",
           rss_notifications: "rssNotifications",
           rss_auto_download: "rssAutoDownload",
+          android_storage_target: "androidStorageTarget",
+          download_wifi_only: "downloadWifiOnly",
+          battery_min_percent: "batteryMinPercent",
         };
         const attrKey = keyMap[key];
         if (attrKey) {
@@ -100,7 +137,10 @@ export function useSettings() {
           const numericKeys = new Set([
             "maxConcurrentDownloads",
             "speedLimit",
+            "concurrentFragments",
             "rssCheckInterval",
+            "batteryMinPercent",
+            "maxFps",
           ]);
           const booleanTrueKeys = new Set([
             "autoStartDownloads",
@@ -108,11 +148,14 @@ export function useSettings() {
             "rssNotifications",
             "embedThumbnail",
             "embedMetadata",
+            "embedChapters",
           ]);
           const booleanFalseKeys = new Set([
             "closeToTray",
             "autoLaunch",
             "rssAutoDownload",
+            "downloadWifiOnly",
+            "writeInfoJson",
           ]);
 
           let typed: string | number | boolean = value;
@@ -16,8 +16,38 @@ export interface DownloadItem {
   formatId?: string;
   formatLabel?: string;
   error?: string;
+  /** Category of `error`, when there is one. */
+  errorCode?: ErrorCode | null;
   priority?: number;
-  source?: "single" | "playlist";
+  source?: "single" | "playlist" | "rss" | "cli" | "library";
+  /**
+   * Clip the download is limited to: `*START-END` in seconds, or a
+   * chapter title pattern. Empty for the whole video.
+   */
+  section?: string;
+  /** Why the download needed something unusual, e.g. browser cookies. */
+  note?: string;
+  /**
+   * "archive" for archive bundles, "tracks" for albums split by chapters
+   * (see `getDownloadArtifacts`), "live" for recorded streams.
+   */
+  mode?: string;
+  /** From the download's .info.json, when yt-dlp wrote one. */
+  description?: string;
+  tags?: string[];
+  /** YYYY-MM-DD */
+  uploadDate?: string;
+  viewCount?: number | null;
+  /** `-N` override of this download, 0 = the setting. */
+  concurrentFragments?: number;
+  /** Folder picked for this download, empty = from the settings. */
+  outputDir?: string;
+  /** Shared by downloads queued together (a playlist, a mirror sync). */
+  batchId?: string;
+  /** Seconds, from the .info.json. */
+  duration?: number | null;
+  /** Cookie profile id the download runs with, empty = the setting. */
+  cookieProfile?: string;
   createdAt: string;
   updatedAt?: string;
 }
@@ -29,7 +59,11 @@ export type DownloadStatus =
   | "completed"
   | "error"
   | "cancelled"
-  | "merging";
+  | "merging"
+  /** Re-encoding into MP4 after the download, see `mergePolicy`. */
+  | "converting"
+  /** Was downloading when the app last closed; resumable. */
+  | "interrupted";
 
 export interface VideoInfo {
   id: string;
@@ -39,6 +73,17 @@ export interface VideoInfo {
   uploader?: string;
   url: string;
   formats: VideoFormat[];
+  chapters?: StreamChapter[];
+  /** Audio languages, only listed when there is more than one. */
+  audioTracks?: AudioTrack[];
+}
+
+export interface AudioTrack {
+  /** yt-dlp language code, e.g. "en-US". */
+  language: string;
+  label: string;
+  /** The video's own audio rather than a dub. */
+  original: boolean;
 }
 
 export interface VideoFormat {
@@ -51,6 +96,7 @@ export interface VideoFormat {
   fps?: number;
   tbr?: number;
   formatNote: string;
+  language?: string;
   width?: number;
   height?: number;
 }
@@ -62,6 +108,7 @@ export interface PlaylistEntry {
   url: string;
   index: number;
   thumbnail?: string;
+  uploadDate?: string | null;
 }
 
 export interface PlaylistInfo {
@@ -71,14 +118,74 @@ export interface PlaylistInfo {
   entryCount: number;
 }
 
+/** Lists of the signed-in YouTube account, fetched with browser cookies. */
+export type AccountPlaylist = "watch_later" | "liked";
+
 export interface PlaylistDownloadOptions {
   url: string;
   startIndex?: number;
   endIndex?: number;
   format?: string;
+  /** yt-dlp file template, e.g. "%(playlist_index)03d - %(title)s.%(ext)s" */
+  namingTemplate?: string;
+  /** Max simultaneous downloads for this playlist (default: sequential) */
+  concurrency?: number;
+  /** Download in reverse playlist order */
+  reverse?: boolean;
+  /** Only entries uploaded on/after this date (YYYY-MM-DD) */
+  dateAfter?: string;
+  /** Only the last N entries of the selection */
+  lastN?: number;
+  /** List the playlist again instead of reusing a recent listing */
+  refresh?: boolean;
   [key: string]: unknown; // Add index signature for Tauri invoke compatibility
 }
 
+export interface PlaylistRecord {
+  id: string;
+  url: string;
+  title: string;
+  thumbnail: string;
+  totalVideos: number;
+  downloadedVideos: number;
+  status: string;
+  namingTemplate: string;
+  autoSync: boolean;
+  lastSync: string;
+  createdAt: string;
+  updatedAt: string;
+  kind: "playlist" | "channel";
+  outputDir: string;
+  formatId: string;
+  /** Syncs only queue entries uploaded on/after this date (YYYYMMDD) */
+  dateAfter: string;
+}
+
+export interface ChannelMirrorOptions {
+  outputDir?: string;
+  namingTemplate?: string;
+  format?: string;
+  autoSync?: boolean;
+  /** Only mirror uploads from this date on (YYYY-MM-DD) */
+  dateAfter?: string;
+}
+
+export interface PlaylistItemRecord {
+  id: string;
+  playlistId: string;
+  downloadId: string | null;
+  videoId: string;
+  url: string;
+  title: string;
+  thumbnail: string;
+  index: number;
+  status: DownloadStatus | "pending" | "removed" | "unavailable";
+  progress: number;
+  error: string;
+  filePath: string;
+  updatedAt: string;
+}
+
 // --- RSS types ---
 export interface RssFeed {
   id: string;
@@ -90,17 +197,71 @@ export interface RssFeed {
   autoDownload: boolean;
   keywords: string[];
   ignoreKeywords: string[];
+  /**
+   * Folder for auto-downloads, relative to the download folder, e.g.
+   * `Subscriptions/{channel}/{year}`. Also `{feed}`, `{month}`, `{day}`
+   * and `{type}`. Empty for the download folder itself.
+   */
+  folderTemplate?: string;
+  /**
+   * Channel tabs a YouTube feed follows, comma-separated
+   * (`ChannelTab`s). Defaults to "videos,shorts".
+   */
+  tabs?: string;
+  /** Cookie profile id for downloads of the feed's items, "" = none. */
+  cookieProfile?: string;
+  /** Loaded separately with `getFeedItems`. */
+  items: RssItem[];
+  itemCount?: number;
+  /** Items not downloaded yet. */
+  newCount?: number;
+  latestPublishedAt?: string;
+}
+
+export type FeedItemFilter = "all" | "new" | "downloaded" | "video" | "short";
+
+export interface FeedItemsPage {
   items: RssItem[];
+  /** Items matching the filter across all pages. */
+  total: number;
+  offset: number;
+  hasMore: boolean;
 }
 
+/** What `getFeeds` returns: the feed without its items. */
+export type FeedSummary = Omit<RssFeed, "items"> &
+  Required<Pick<RssFeed, "itemCount" | "newCount" | "latestPublishedAt">>;
+
+/** Tabs of a YouTube channel a feed can follow. */
+export type ChannelTab = "videos" | "shorts" | "streams";
+
 export interface RssItem {
   id: string;
+  videoId?: string;
   title: string;
   url: string;
   thumbnail?: string;
   publishedAt: string;
   status: "not_queued" | "queued" | "downloaded";
-  videoType?: "video" | "short" | "unknown";
+  /** "stream" for items from a channel's live tab. */
+  videoType?: "video" | "short" | "stream" | "unknown";
+}
+
+/** A premiere or livestream scheduled in a followed channel. */
+export interface UpcomingStream {
+  videoId: string;
+  feedId: string;
+  feedTitle: string;
+  title: string;
+  url: string;
+  thumbnail: string;
+  /** Unix seconds. */
+  scheduledAt: number;
+  /** "Starting soon" was sent. */
+  notified: boolean;
+  /** The recording, empty until one is started. */
+  downloadId: string;
+  autoDownload: boolean;
 }
 
 // --- Settings types ---
@@ -112,6 +273,76 @@ export interface StreamQuality {
   ext: string;
 }
 
+export interface SubtitleTrack {
+  language: string;
+  name: string;
+  url: string;
+  format: string;
+  /** Auto-generated captions rather than uploaded subtitles. */
+  auto: boolean;
+}
+
+export interface StreamChapter {
+  title: string;
+  start: number;
+  end: number;
+}
+
+export interface Storyboard {
+  formatId: string;
+  /** Size of a single frame within a sprite sheet. */
+  width: number;
+  height: number;
+  rows: number;
+  columns: number;
+  fps: number;
+  fragments: { url: string; duration: number }[];
+}
+
+export interface CastDevice {
+  id: string;
+  name: string;
+  manufacturer: string;
+  model: string;
+  location: string;
+  kind: "dlna" | "googlecast";
+}
+
+export interface CastStatus {
+  state: string;
+  position: number;
+  duration: number;
+}
+
+export interface ListenQueueItem {
+  id: string;
+  feedItemId: string | null;
+  videoId: string;
+  url: string;
+  title: string;
+  thumbnail: string;
+  channel: string;
+  addedAt: string;
+  position: number;
+  watchedPercent: number;
+}
+
+export interface PlaybackPosition {
+  videoId: string;
+  position: number;
+  duration: number;
+  watchedPercent: number;
+  updatedAt: string;
+}
+
+export interface SponsorSegment {
+  start: number;
+  end: number;
+  category: string;
+  actionType: string;
+  uuid: string;
+}
+
 export interface StreamInfo {
   videoUrl: string;
   audioUrl: string;
@@ -121,12 +352,26 @@ export interface StreamInfo {
   duration: number;
   uploader: string;
   qualities: StreamQuality[];
+  subtitles: SubtitleTrack[];
+  chapters: StreamChapter[];
+  storyboards: Storyboard[];
+  audioOnly?: boolean;
+  /** Unix seconds when the stream URLs stop working; 0 if unknown. */
+  expiresAt: number;
+  /** Seconds of validity left at the time of the call. */
+  expiresIn: number;
+  /** Set when the URLs above point at the local stream proxy. */
+  proxied?: boolean;
+  /** Original CDN URLs, only present when proxied. */
+  direct?: Partial<Pick<StreamInfo, "videoUrl" | "audioUrl" | "combinedUrl">>;
 }
 
 export interface AppSettings {
   downloadPath: string;
   maxConcurrentDownloads: number;
   speedLimit: number; // MB/s, 0 = unlimited
+  /** Fragments fetched at once for DASH/HLS (yt-dlp `-N`), 1-16. */
+  concurrentFragments: number;
   autoStartDownloads: boolean;
   theme: "light" | "dark" | "system";
   language: string;
@@ -135,16 +380,320 @@ export interface AppSettings {
   autoLaunch: boolean;
   defaultQuality: string;
   qualityPreset: "best" | "4k" | "1080p" | "720p" | "audio"; // Quick quality presets
+  /** Used with `defaultQuality` when no format is picked. */
+  preferredVcodec: "any" | "avc" | "vp9" | "av1";
+  preferredDynamicRange: "any" | "sdr" | "hdr";
+  maxFps: number; // 0 = any
   defaultFormat: string;
   embedThumbnail: boolean;
   embedMetadata: boolean;
+  embedChapters: boolean;
+  /** Keep .info.json sidecars and store their metadata with downloads. */
+  writeInfoJson: boolean;
+  /** When formats only merge into MP4 by re-encoding. */
+  mergePolicy: "reencode" | "mkv" | "abort";
   browserForCookies: string;
+  /** Browser to retry age-restricted/members-only failures with, or "none". */
+  cookiesFallbackBrowser: string;
   configPath: string;
   ytdlpFlags: string;
+  /** `extractor:key=value` entries for every yt-dlp run, space-separated. */
+  ytdlpExtractorArgs: string;
   // RSS settings
   rssCheckInterval: number;
   rssNotifications: boolean;
   rssAutoDownload: boolean;
+  /** Android: where finished downloads are moved. */
+  androidStorageTarget: AndroidStorageTarget;
+  /** Pause downloads on metered connections, resume on Wi-Fi. */
+  downloadWifiOnly: boolean;
+  /** Pause downloads and scheduled checks below this charge, 0 = off. */
+  batteryMinPercent: number;
+}
+
+/**
+ * "app" keeps files in the download folder, "media" moves them to
+ * Movies/YTDL or Music/YTDL, "saf" to the folder picked with
+ * `pickAndroidDownloadFolder`.
+ */
+export type AndroidStorageTarget = "app" | "media" | "saf";
+
+export interface EnvironmentCheck {
+  ok: boolean;
+  /** A failed check only blocks `ready` when required. */
+  required: boolean;
+  path: string | null;
+  detail: string | null;
+  error: string | null;
+}
+
+export interface EnvironmentReport {
+  ready: boolean;
+  platform: string;
+  checks: {
+    ytdlp: EnvironmentCheck;
+    ffmpeg: EnvironmentCheck;
+    ffprobe: EnvironmentCheck;
+    whisper: EnvironmentCheck;
+    whisperModel: EnvironmentCheck;
+    network: EnvironmentCheck;
+    downloadDir: EnvironmentCheck;
+  };
+}
+
+export type NativeHostBrowser =
+  | "chrome"
+  | "chromium"
+  | "edge"
+  | "brave"
+  | "firefox";
+
+export interface ApiStatus {
+  enabled: boolean;
+  running: boolean;
+  port: number;
+  baseUrl: string;
+  token: string;
+}
+
+export interface StartupReport {
+  interruptedDownloads: number;
+  failedTranscripts: number;
+  removedTempFiles: number;
+  /** Queued downloads started at launch (`auto_start_download`). */
+  resumedDownloads: ResumedDownload[];
+}
+
+/** A download left queued by the last session and started at launch. */
+export interface ResumedDownload {
+  id: string;
+  url: string;
+  title: string;
+}
+
+/** A file of an archive bundle. */
+export interface DownloadArtifact {
+  kind:
+    | "video"
+    | "audio"
+    | "track"
+    | "thumbnail"
+    | "description"
+    | "info"
+    | "subtitle"
+    | "other";
+  path: string;
+  size: number;
+}
+
+/** One transfer-rate sample of a download. */
+export interface SpeedSample {
+  /** Unix time in seconds. */
+  t: number;
+  bytesPerSec: number;
+}
+
+export interface DownloadLog {
+  id: string;
+  path: string;
+  exists: boolean;
+  content: string;
+  /** Only the end of a long log is returned. */
+  truncated: boolean;
+}
+
+export interface LogEntry {
+  timestamp: string;
+  level: "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE";
+  target: string;
+  message: string;
+}
+
+export interface WebhookDelivery {
+  id: string;
+  downloadId: string | null;
+  url: string;
+  event: "download.completed" | "download.failed" | "test";
+  success: boolean;
+  statusCode: number | null;
+  attempts: number;
+  error: string;
+  createdAt: string;
+}
+
+export interface ProxyStatus {
+  mode: "system" | "direct" | "custom";
+  proxyUrl: string | null;
+  environment: {
+    httpProxy: string | null;
+    httpsProxy: string | null;
+    allProxy: string | null;
+    noProxy: string | null;
+  };
+}
+
+export type BinaryTool = "yt-dlp" | "ffmpeg" | "whisper";
+
+/**
+ * Destination folder by kind, or "archive": the video plus thumbnail,
+ * description, info.json with comments and subtitles in a folder of its own.
+ */
+export type DownloadMode =
+  | "video"
+  | "audio"
+  | "shorts"
+  | "podcast"
+  /** Everything about the video in a folder of its own. */
+  | "archive"
+  /** Audio split by chapters into tagged tracks, in an album folder. */
+  | "tracks"
+  /** A livestream or premiere, recorded from its start. */
+  | "live";
+
+/** Options picked in the quality dialog. */
+export interface DownloadChoice {
+  section?: string;
+  audioLanguages?: string[];
+  mode?: DownloadMode;
+  /** Overrides the `concurrentFragments` setting. */
+  concurrentFragments?: number;
+  /** Absolute folder for this download only. */
+  outputDir?: string;
+}
+
+/** Typed settings as returned by `get_app_settings`. */
+export interface BackendSettings extends AppSettings {
+  /** Per-mode destination folders, empty = `downloadPath`. */
+  videoDownloadPath: string;
+  audioDownloadPath: string;
+  shortsDownloadPath: string;
+  podcastDownloadPath: string;
+  minAudioBitrate: number; // kbps, 0 = any
+  /** Release channel used by installYtdlp/updateYtdlp. */
+  ytdlpChannel: "stable" | "nightly" | "master";
+  /** Honor HTTP(S)_PROXY/NO_PROXY; `proxyUrl` takes precedence when set. */
+  useSystemProxy: boolean;
+  proxyUrl: string;
+  playlistSubfolder: boolean;
+  playlistConcurrency: number;
+  /** Refuse feed URLs that aren't HTTPS. */
+  feedHttpsOnly: boolean;
+  /** Comma-separated feed hosts (subdomains included); empty = any host. */
+  feedAllowedHosts: string;
+  /** Comma-separated feed hosts refused even when allowed. */
+  feedBlockedHosts: string;
+  /** Per-event switches under the `notifications` master switch. */
+  notifyDownloadComplete: boolean;
+  notifyDownloadFailed: boolean;
+  notifyTranscriptionDone: boolean;
+  notifyToolUpdate: boolean;
+  /** Start minimized when launched on login (`autoLaunch`). */
+  startMinimized: boolean;
+  /** Called when any download finishes; empty = off. */
+  webhookUrl: string;
+  /**
+   * Request body with `{{event}}`, `{{id}}`, `{{title}}`, `{{url}}`,
+   * `{{status}}`, `{{filePath}}`, `{{fileSize}}`, `{{error}}` and
+   * `{{finishedAt}}` placeholders; empty = the default JSON payload.
+   */
+  webhookPayload: string;
+  /**
+   * Server asked to rescan the folders downloads finish in; its API key or
+   * Plex token is the `media_server_token` secret (`saveSetting`).
+   */
+  mediaServer: "none" | "jellyfin" | "plex";
+  mediaServerUrl: string;
+  /** Only downloads in this folder trigger a refresh; empty = any. */
+  mediaServerLibrary: string;
+  /**
+   * Folder checked every few seconds for `.txt`/`.url` files with video
+   * links; they are queued and the file moved to `done/`. Empty = off.
+   */
+  watchFolder: string;
+  /** Days of log files to keep, 0 = keep all. */
+  logRetentionDays: number;
+  /** yt-dlp metadata calls allowed per minute, 0 = unlimited. */
+  ytdlpRequestsPerMinute: number;
+  /** Pause after an HTTP 429, doubled for each further one. */
+  ytdlpCooldownMinutes: number;
+  androidStorageTarget: AndroidStorageTarget;
+  downloadWifiOnly: boolean;
+  /** Pause below this charge when not charging, 0 = off. */
+  batteryMinPercent: number;
+  /** Resume at or above this charge even when not charging. */
+  batteryResumePercent: number;
+  /**
+   * "HH:MM" local time; new downloads wait and feed checks and playlist
+   * syncs are skipped between start and end. Either empty = off.
+   */
+  quietHoursStart: string;
+  quietHoursEnd: string;
+  /** Also pause running downloads during quiet hours. */
+  quietHoursPauseActive: boolean;
+  /** GiB downloaded per period before queued downloads wait, 0 = off. */
+  downloadQuotaGb: number;
+  /** A week starts on Monday. */
+  downloadQuotaPeriod: "day" | "week";
+  /**
+   * Record scheduled premieres and livestreams of auto-download feeds
+   * when they start.
+   */
+  liveAutoRecord: boolean;
+  /** Minutes before a scheduled stream to notify, 0 = off. */
+  liveNotifyMinutes: number;
+}
+
+export interface NetworkStatus {
+  wifiOnly: boolean;
+  /** null where the connection type can't be detected. */
+  metered: boolean | null;
+  /**
+   * Downloads are waiting for an unmetered connection, a charged battery
+   * or the end of quiet hours.
+   */
+  held: boolean;
+  batteryLow: boolean;
+  quietHours: boolean;
+  overQuota: boolean;
+  heldDownloads: number;
+}
+
+export interface BatteryStatus {
+  /** null on devices without a battery. */
+  battery: { percent: number; charging: boolean } | null;
+  enabled: boolean;
+  low: boolean;
+}
+
+export type CookieProfileKind = "browser" | "file";
+
+/** A named cookie source downloads, feeds and the player can pick. */
+export interface CookieProfile {
+  id: string;
+  name: string;
+  kind: CookieProfileKind;
+  /**
+   * Browser as `BROWSER[+KEYRING][:PROFILE][::CONTAINER]`, or the absolute
+   * path of a cookies.txt file.
+   */
+  source: string;
+}
+
+export interface QuotaStatus {
+  /** 0 when the quota is off. */
+  limitBytes: number;
+  period: "day" | "week";
+  usedBytes: number;
+  exceeded: boolean;
+  /** Start of the next period, RFC 3339. */
+  resetsAt: string;
+}
+
+export interface RateLimitStatus {
+  requestsPerMinute: number;
+  requestsLastMinute: number;
+  /** 0 unless yt-dlp calls are paused after an HTTP 429. */
+  cooldownRemainingSecs: number;
 }
 
 // --- Transcript types ---
@@ -160,6 +709,7 @@ export interface RawTranscriptItem {
   durationSecs?: number;
   createdAt?: string;
   error?: string;
+  downloadId?: string | null;
 }
 
 /** Normalised transcript item used throughout the UI */
@@ -176,64 +726,373 @@ export interface TranscriptItem {
   error?: string;
 }
 
+/** A transcript matching a full-text search, with seekable positions */
+export interface TranscriptSearchResult {
+  id: string;
+  source: string;
+  title: string;
+  snippet: string;
+  durationSecs: number;
+  createdAt: string;
+  matches: {
+    start: number;
+    end: number | null;
+    text: string;
+    estimated: boolean;
+  }[];
+}
+
+// --- Errors ---
+export type ErrorCode =
+  | "network"
+  | "geo-blocked"
+  | "age-restricted"
+  | "login-required"
+  | "rate-limited"
+  | "disk-full"
+  | "permission-denied"
+  | "format-unavailable"
+  | "video-unavailable"
+  | "tool-missing"
+  | "cancelled"
+  | "not-found"
+  | "invalid-argument"
+  | "database"
+  | "unknown";
+
+/** Structured error returned by commands that categorize their failures. */
+export interface AppErrorPayload {
+  code: ErrorCode;
+  message: string;
+  hint: string | null;
+}
+
+/**
+ * Thrown by `invokeWithCode`. Stringifies to the plain message so existing
+ * `${err}` handling keeps working.
+ */
+export class CommandError extends Error {
+  code: ErrorCode;
+  hint: string | null;
+
+  constructor(payload: AppErrorPayload) {
+    super(payload.message);
+    this.name = "CommandError";
+    this.code = payload.code;
+    this.hint = payload.hint;
+  }
+
+  toString() {
+    return this.message;
+  }
+}
+
+function isAppErrorPayload(value: unknown): value is AppErrorPayload {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    "code" in value &&
+    "message" in value
+  );
+}
+
+async function invokeWithCode<T>(
+  cmd: string,
+  args?: Record<string, unknown>,
+): Promise<T> {
+  try {
+    return await invoke<T>(cmd, args);
+  } catch (err) {
+    throw isAppErrorPayload(err) ? new CommandError(err) : err;
+  }
+}
+
 // --- Tauri commands ---
 export const commands = {
   // Download commands
-  startDownload: (url: string, formatId?: string) =>
-    invoke<string>("start_download", { url, formatId }),
+  /**
+   * `mode` picks the destination folder; guessed from the URL/format if
+   * omitted. Pass the `getVideoInfo` result as `info` to skip fetching it
+   * again. `section` downloads only a time range (`1:30-2:45`, `90-`) or
+   * the chapters whose titles match a pattern. `audioLanguages` picks
+   * tracks from `VideoInfo.audioTracks`; several become separate streams.
+   * `concurrentFragments` overrides the setting for this download, and
+   * `outputDir` the destination folder.
+   */
+  startDownload: (
+    url: string,
+    formatId?: string,
+    mode?: DownloadMode,
+    cookiesFromBrowser?: string,
+    info?: VideoInfo,
+    section?: string,
+    audioLanguages?: string[],
+    concurrentFragments?: number,
+    outputDir?: string,
+    cookieProfile?: string,
+  ) =>
+    invokeWithCode<string>("start_download", {
+      url,
+      formatId,
+      mode,
+      cookiesFromBrowser,
+      info,
+      section,
+      audioLanguages,
+      concurrentFragments,
+      outputDir,
+      cookieProfile,
+    }),
   pauseDownload: (id: string) => invoke<void>("pause_download", { id }),
-  resumeDownload: (id: string) => invoke<void>("resume_download", { id }),
+  resumeDownload: (id: string) =>
+    invokeWithCode<void>("resume_download", { id }),
   cancelDownload: (id: string) => invoke<void>("cancel_download", { id }),
-  retryDownload: (id: string) => invoke<void>("retry_download", { id }),
+  retryDownload: (id: string) =>
+    invokeWithCode<void>("retry_download", { id }),
   deleteDownload: (id: string, deleteFile: boolean) =>
     invoke<void>("delete_download", { id, deleteFile }),
+  /** yt-dlp output of the last run of a download. */
+  getDownloadLog: (id: string) => invoke<DownloadLog>("get_download_log", { id }),
+  getDownloadArtifacts: (id: string) =>
+    invoke<DownloadArtifact[]>("get_download_artifacts", { id }),
+  getQueueSummary: () => invoke<QueueSummary>("get_queue_summary"),
+  /** Speed samples of a download, oldest first, for a sparkline. */
+  getSpeedHistory: (id: string) =>
+    invoke<SpeedSample[]>("get_speed_history", { id }),
   getDownloads: () => invoke<DownloadItem[]>("get_downloads"),
-  getVideoInfo: (url: string) => invoke<VideoInfo>("get_video_info", { url }),
-  getPlaylistInfo: (url: string) =>
-    invoke<PlaylistInfo>("get_playlist_info", { url }),
+  getVideoInfo: (url: string) =>
+    invokeWithCode<VideoInfo>("get_video_info", { url }),
+  /** Listings are reused for a few minutes unless `refresh` is set. */
+  getPlaylistInfo: (url: string, refresh?: boolean) =>
+    invokeWithCode<PlaylistInfo>("get_playlist_info", { url, refresh }),
+  /** Watch Later, Liked videos or a private playlist URL, using the
+   * configured browser cookies. Pass the returned `url` on to
+   * `startPlaylistDownload`. */
+  getAccountPlaylist: (name: AccountPlaylist | string, refresh?: boolean) =>
+    invokeWithCode<PlaylistInfo & { url: string }>("get_account_playlist", {
+      name,
+      refresh,
+    }),
   startPlaylistDownload: (options: PlaylistDownloadOptions) =>
     invoke<string[]>("start_playlist_download", options),
+  getPlaylists: () => invoke<PlaylistRecord[]>("get_playlists"),
+  updatePlaylistSettings: (
+    id: string,
+    namingTemplate: string,
+    autoSync: boolean,
+  ) =>
+    invoke<void>("update_playlist_settings", { id, namingTemplate, autoSync }),
+  getPlaylistItems: (playlistId: string) =>
+    invoke<PlaylistItemRecord[]>("get_playlist_items", { playlistId }),
+  retryPlaylistItem: (itemId: string, format?: string) =>
+    invoke<string>("retry_playlist_item", { itemId, format }),
+  syncPlaylist: (id: string) => invoke<PlaylistSyncedEvent>("sync_playlist", { id }),
+  exportPlaylistM3u: (id: string, outputPath?: string) =>
+    invoke<string>("export_playlist_m3u", { id, outputPath }),
+  deletePlaylist: (
+    id: string,
+    deleteDownloads?: boolean,
+    deleteFiles?: boolean,
+  ) =>
+    invoke<PlaylistDeletion>("delete_playlist", {
+      id,
+      deleteDownloads,
+      deleteFiles,
+    }),
+  addChannelMirror: (url: string, options: ChannelMirrorOptions = {}) =>
+    invoke<PlaylistSyncedEvent>("add_channel_mirror", { url, ...options }),
+  getChannelMirrors: () => invoke<PlaylistRecord[]>("get_channel_mirrors"),
+  updateChannelMirror: (
+    id: string,
+    outputDir: string,
+    namingTemplate: string,
+    autoSync: boolean,
+    format?: string,
+    dateAfter?: string,
+  ) =>
+    invoke<void>("update_channel_mirror", {
+      id,
+      outputDir,
+      namingTemplate,
+      format,
+      autoSync,
+      dateAfter,
+    }),
+  syncChannelMirror: (id: string) =>
+    invoke<PlaylistSyncedEvent>("sync_channel_mirror", { id }),
+  setPlaylistSyncInterval: (minutes: number) =>
+    invoke<void>("set_playlist_sync_interval", { minutes }),
+  getPlaylistSyncInterval: () => invoke<number>("get_playlist_sync_interval"),
+  syncAutoPlaylists: () => invoke<number>("sync_auto_playlists"),
 
   // Batch download operations
   pauseAllDownloads: () => invoke<number>("pause_all_downloads"),
   resumeAllDownloads: () => invoke<number>("resume_all_downloads"),
   cancelAllDownloads: () => invoke<number>("cancel_all_downloads"),
-  exportDownloads: (format: "json" | "csv") =>
-    invoke<string>("export_downloads", { format }),
+  pauseBatch: (batchId: string) => invoke<number>("pause_batch", { batchId }),
+  cancelBatch: (batchId: string) =>
+    invoke<number>("cancel_batch", { batchId }),
+  /** Queues the batch's paused, failed and cancelled downloads again. */
+  retryBatch: (batchId: string) => invoke<number>("retry_batch", { batchId }),
+  getBatchProgress: (batchId: string) =>
+    invoke<BatchProgress>("get_batch_progress", { batchId }),
+  /**
+   * The download list as a report. With `options.path` it is written
+   * there and the path is returned; otherwise the report itself is.
+   */
+  exportDownloads: (format: ExportFormat, options: ExportOptions = {}) =>
+    invoke<string>("export_downloads", { format, ...options }),
+  /** Register media files already in a folder as completed downloads. */
+  scanLibrary: (path: string) =>
+    invoke<LibraryScanReport>("scan_library", { path }),
+  /** Fix the file paths of downloads whose files moved, rebasing
+   * `oldBase` onto `newBase` and/or searching `scanDir` by file name. */
+  relinkDownloads: (options: {
+    oldBase?: string;
+    newBase?: string;
+    scanDir?: string;
+  }) => invoke<RelinkReport>("relink_downloads", options),
+
+  // Podcast feeds of downloaded audio; no feedId = every audio download
+  /** Writes a feed with file:// enclosures, returns the episode count. */
+  exportPodcastFeed: (path: string, feedId?: string) =>
+    invoke<number>("export_podcast_feed", { feedId, path }),
+  /** Serves the feed on the LAN and returns the URL to subscribe to. */
+  sharePodcastFeed: (feedId?: string) =>
+    invoke<PodcastShare>("share_podcast_feed", { feedId }),
+  unsharePodcastFeed: (feedId?: string) =>
+    invoke<void>("unshare_podcast_feed", { feedId }),
+  getPodcastShares: () => invoke<PodcastShare[]>("get_podcast_shares"),
 
   // Settings commands
   getSettings: () => invoke<Record<string, string>>("get_settings"),
   saveSetting: (key: string, value: string) =>
     invoke<void>("save_setting", { key, value }),
+  getAppSettings: () => invoke<BackendSettings>("get_app_settings"),
+  /** Validates the changed fields and rejects the whole patch if any is invalid. */
+  updateAppSettings: (patch: Partial<BackendSettings>) =>
+    invoke<BackendSettings>("update_app_settings", { patch }),
   selectDirectory: () => invoke<string | null>("select_directory"),
 
   // RSS commands
-  getFeeds: () => invoke<RssFeed[]>("get_feeds"),
-  addFeed: (url: string) => invoke<string>("add_feed", { url }),
-  removeFeed: (id: string) => invoke<void>("remove_feed", { id }),
-  checkFeed: (id: string) => invoke<RssItem[]>("check_feed", { id }),
-  checkAllRssFeeds: () => invoke<number>("check_all_rss_feeds"),
+  getFeeds: () => invokeWithCode<FeedSummary[]>("get_feeds"),
+  /** One page of a feed's items, newest first. */
+  getFeedItems: (
+    feedId: string,
+    offset?: number,
+    limit?: number,
+    filter?: FeedItemFilter,
+  ) =>
+    invokeWithCode<FeedItemsPage>("get_feed_items", { feedId, offset, limit, filter }),
+  addFeed: (url: string) => invokeWithCode<string>("add_feed", { url }),
+  removeFeed: (id: string) => invokeWithCode<void>("remove_feed", { id }),
+  checkFeed: (id: string) => invokeWithCode<RssItem[]>("check_feed", { id }),
+  checkAllRssFeeds: () => invokeWithCode<number>("check_all_rss_feeds"),
   markFeedItemWatched: (itemId: string, watched: boolean) =>
-    invoke<void>("mark_feed_item_watched", { itemId, watched }),
+    invokeWithCode<void>("mark_feed_item_watched", { itemId, watched }),
+  /** `folderTemplate` and `tabs` are kept as they are when omitted. */
   updateFeedSettings: (
     feedId: string,
     keywords: string,
     autoDownload: boolean,
-  ) => invoke<void>("update_feed_settings", { feedId, keywords, autoDownload }),
+    folderTemplate?: string,
+    tabs?: ChannelTab[],
+    /** Profile id or name; "" goes back to the cookies browser. */
+    cookieProfile?: string,
+  ) =>
+    invokeWithCode<void>("update_feed_settings", {
+      feedId,
+      keywords,
+      autoDownload,
+      folderTemplate,
+      tabs: tabs?.join(","),
+      cookieProfile,
+    }),
+  /** Subscribe to the channels in a Takeout CSV or NewPipe JSON export. */
+  importSubscriptions: (path: string) =>
+    invokeWithCode<SubscriptionImportReport>("import_subscriptions", { path }),
+  /** Write the YouTube feeds as a NewPipe or FreeTube subscriptions file;
+   * returns the number of channels written. */
+  exportSubscriptions: (format: SubscriptionExportFormat, path: string) =>
+    invokeWithCode<number>("export_subscriptions", { format, path }),
 
   // RSS Scheduler commands
   setRssCheckInterval: (minutes: number) =>
-    invoke<void>("set_rss_check_interval", { minutes }),
-  getRssCheckInterval: () => invoke<number>("get_rss_check_interval"),
+    invokeWithCode<void>("set_rss_check_interval", { minutes }),
+  getRssCheckInterval: () => invokeWithCode<number>("get_rss_check_interval"),
 
   // Stream proxy (custom player)
-  getStreamUrl: (url: string) => invoke<StreamInfo>("get_stream_url", { url }),
+  /** `cookieProfile` (id or name) replaces the cookies browser. */
+  getStreamUrl: (
+    url: string,
+    proxy?: boolean,
+    audioOnly?: boolean,
+    cookieProfile?: string,
+  ) =>
+    invoke<StreamInfo>("get_stream_url", {
+      url,
+      proxy,
+      audioOnly,
+      cookieProfile,
+    }),
+  refreshStreamUrl: (
+    url: string,
+    proxy?: boolean,
+    audioOnly?: boolean,
+    cookieProfile?: string,
+  ) =>
+    invoke<StreamInfo>("refresh_stream_url", {
+      url,
+      proxy,
+      audioOnly,
+      cookieProfile,
+    }),
+  getSponsorSegments: (videoId: string, categories?: string[]) =>
+    invoke<SponsorSegment[]>("get_sponsor_segments", { videoId, categories }),
+
+  // Casting (DLNA/UPnP renderers and Google Cast devices)
+  discoverCastDevices: (timeoutMs?: number) =>
+    invoke<CastDevice[]>("discover_cast_devices", { timeoutMs }),
+  castMedia: (deviceId: string, source: string, title?: string) =>
+    invoke<string>("cast_media", { deviceId, source, title }),
+  castControl: (deviceId: string, action: "play" | "pause" | "stop") =>
+    invoke<void>("cast_control", { deviceId, action }),
+  castSeek: (deviceId: string, position: number) =>
+    invoke<void>("cast_seek", { deviceId, position }),
+  getCastStatus: (deviceId: string) =>
+    invoke<CastStatus>("get_cast_status", { deviceId }),
+
+  // Playback positions
+  savePlaybackPosition: (videoId: string, position: number, duration: number) =>
+    invoke<void>("save_playback_position", { videoId, position, duration }),
+  getPlaybackPosition: (videoId: string) =>
+    invoke<PlaybackPosition | null>("get_playback_position", { videoId }),
+  getPlaybackPositions: () =>
+    invoke<PlaybackPosition[]>("get_playback_positions"),
+  clearPlaybackPosition: (videoId: string) =>
+    invoke<void>("clear_playback_position", { videoId }),
+
+  // Listen queue
+  getListenQueue: () => invoke<ListenQueueItem[]>("get_listen_queue"),
+  addToListenQueue: (feedItemIds: string[]) =>
+    invoke<number>("add_to_listen_queue", { feedItemIds }),
+  queueLatestFeedItems: (feedId?: string, limit?: number) =>
+    invokeWithCode<number>("queue_latest_feed_items", { feedId, limit }),
+  reorderListenQueue: (ids: string[]) =>
+    invoke<void>("reorder_listen_queue", { ids }),
+  removeFromListenQueue: (id: string) =>
+    invoke<void>("remove_from_listen_queue", { id }),
+  clearListenQueue: () => invoke<void>("clear_listen_queue"),
 
   // Transcription commands
   startTranscription: (source: string, modelSize?: string) =>
     invoke<string>("start_transcription", { source, modelSize }),
+  transcribeDownload: (id: string, modelSize?: string) =>
+    invoke<string>("transcribe_download", { id, modelSize }),
   getTranscripts: () => invoke<RawTranscriptItem[]>("get_transcripts"),
   deleteTranscript: (id: string) => invoke<void>("delete_transcript", { id }),
+  searchTranscripts: (query: string, limit?: number) =>
+    invoke<TranscriptSearchResult[]>("search_transcripts", { query, limit }),
   checkOpenaiTranscriptionApi: (apiKey: string, model: string) =>
     invoke<{ ok: boolean; model: string }>("check_openai_transcription_api", {
       apiKey,
@@ -253,12 +1112,77 @@ export const commands = {
   getBinaryInfo: () => invoke<Record<string, unknown>>("get_binary_info"),
   openExternal: (url: string) => invoke<void>("open_external", { url }),
   openPath: (path: string) => invoke<void>("open_path", { path }),
+  revealInFolder: (path: string) => invoke<void>("reveal_in_folder", { path }),
   checkYtdlp: () => invoke<boolean>("check_ytdlp"),
   checkFfmpeg: () => invoke<boolean>("check_ffmpeg"),
   installYtdlp: () => invoke<void>("install_ytdlp"),
   installFfmpeg: () => invoke<void>("install_ffmpeg"),
   getYtdlpVersion: () => invoke<string>("get_ytdlp_version"),
   getYtdlpLatestVersion: () => invoke<string>("get_ytdlp_latest_version"),
+  /** Runs the binary once to check it, then saves it. Empty path clears. Returns its version line. */
+  setBinaryOverride: (tool: BinaryTool, path: string) =>
+    invoke<string>("set_binary_override", { tool, path }),
+  testBinaryOverride: (tool: BinaryTool, path: string) =>
+    invoke<string>("test_binary_override", { tool, path }),
+  getProxyStatus: () => invoke<ProxyStatus>("get_proxy_status"),
+  /** Runs every dependency check concurrently, for the onboarding screen. */
+  checkEnvironment: () => invoke<EnvironmentReport>("check_environment"),
+
+  // Local HTTP API
+  getApiStatus: () => invoke<ApiStatus>("get_api_status"),
+  setApiEnabled: (enabled: boolean, port?: number) =>
+    invoke<ApiStatus>("set_api_enabled", { enabled, port }),
+  regenerateApiToken: () => invoke<string>("regenerate_api_token"),
+
+  // Browser extension (desktop only)
+  /** Returns the path of the written host manifest. */
+  installNativeHost: (browser: NativeHostBrowser, extensionId: string) =>
+    invoke<string>("install_native_host", { browser, extensionId }),
+  uninstallNativeHost: (browser: NativeHostBrowser) =>
+    invoke<void>("uninstall_native_host", { browser }),
+
+  /** What startup found left over from the previous run. */
+  getStartupReport: () => invoke<StartupReport>("get_startup_report"),
+
+  // Logs
+  /** Oldest first; `level` drops anything less severe. */
+  getRecentLogs: (limit?: number, level?: LogEntry["level"]) =>
+    invoke<LogEntry[]>("get_recent_logs", { limit, level }),
+  /** Returns the path of the opened file. */
+  openLogFile: () => invoke<string>("open_log_file"),
+
+  // Completion webhooks
+  /** Pass an empty `url` to remove the download's webhook. */
+  setDownloadWebhook: (id: string, url: string) =>
+    invoke<void>("set_download_webhook", { id, url }),
+  getWebhookDeliveries: (limit?: number) =>
+    invoke<WebhookDelivery[]>("get_webhook_deliveries", { limit }),
+  /** Checks the media server URL and token; returns the server's name. */
+  testMediaServer: () => invoke<string>("test_media_server"),
+  refreshMediaLibrary: () => invoke<void>("refresh_media_library"),
+  /** Defaults to the `webhookUrl` setting. */
+  testWebhook: (url?: string) =>
+    invoke<{ success: boolean; statusCode: number | null }>("test_webhook", { url }),
+  getRateLimitStatus: () => invoke<RateLimitStatus>("get_rate_limit_status"),
+  clearRateLimitCooldown: () => invoke<void>("clear_rate_limit_cooldown"),
+  getNetworkStatus: () => invoke<NetworkStatus>("get_network_status"),
+  getBatteryStatus: () => invoke<BatteryStatus>("get_battery_status"),
+  getQuotaStatus: () => invoke<QuotaStatus>("get_quota_status"),
+  /** Start a download held by the quota anyway. */
+  forceStartDownload: (id: string) =>
+    invoke<void>("force_start_download", { id }),
+  getUpcomingStreams: () => invoke<UpcomingStream[]>("get_upcoming_streams"),
+  getCookieProfiles: () => invoke<CookieProfile[]>("get_cookie_profiles"),
+  /** Add a profile, or change the one with `id`. */
+  saveCookieProfile: (
+    name: string,
+    kind: CookieProfileKind,
+    source: string,
+    id?: string,
+  ) =>
+    invoke<CookieProfile>("save_cookie_profile", { id, name, kind, source }),
+  deleteCookieProfile: (id: string) =>
+    invoke<void>("delete_cookie_profile", { id }),
   updateYtdlp: () => invoke<void>("update_ytdlp"),
   getFfmpegVersion: () => invoke<string>("get_ffmpeg_version"),
   checkFfmpegUpdate: () => invoke<boolean>("check_ffmpeg_update"),
@@ -275,6 +1199,10 @@ export const commands = {
       termuxInstalled: boolean;
       termuxHasPermission: boolean;
       hasStoragePermission: boolean;
+      /** Tree URI picked for the "saf" storage target. */
+      safFolder: string;
+      /** False once the user revoked access to `safFolder`. */
+      safFolderAccessible: boolean;
       nativeLibDir: string;
       bundledYtdlpWorks: boolean;
       bundledFfmpegWorks: boolean;
@@ -285,6 +1213,9 @@ export const commands = {
   termuxDownload: (url: string, formatId?: string) =>
     invoke<string>("termux_download", { url, formatId }),
   requestStoragePermission: () => invoke<boolean>("request_storage_permission"),
+  /** Resolves to null if the picker was dismissed. */
+  pickAndroidDownloadFolder: () =>
+    invoke<string | null>("pick_android_download_folder"),
   probeYtdlp: () =>
     invoke<{
       strategy: string;
@@ -301,9 +1232,88 @@ export interface DownloadProgressEvent {
   progress: number;
   speed?: string;
   eta?: string;
+  /** File size as yt-dlp prints it, e.g. "~100.50MiB". */
+  total?: string;
   status?: string;
 }
 
+export type ExportFormat = "json" | "csv" | "html" | "markdown";
+
+export interface ExportOptions {
+  /** Only downloads in one of these statuses. */
+  statuses?: DownloadStatus[];
+  /** YYYY-MM-DD, inclusive, on the day a download was added. */
+  from?: string;
+  to?: string;
+  path?: string;
+}
+
+/** A podcast feed served on the LAN. */
+export interface PodcastShare {
+  /** RSS feed id, or "all" for every audio download. */
+  feedId: string;
+  title: string;
+  url: string;
+  episodes: number;
+}
+
+export type SubscriptionExportFormat = "newpipe" | "freetube";
+
+/** Result of `scanLibrary`. */
+export interface LibraryScanReport {
+  scanned: number;
+  added: number;
+  /** Files already listed, or videos downloaded under another path. */
+  alreadyKnown: number;
+  unmatched: number;
+  /** The first few files no video could be matched to. */
+  unmatchedFiles: string[];
+}
+
+/** Result of `relinkDownloads`. */
+export interface RelinkReport {
+  checked: number;
+  present: number;
+  relinked: number;
+  missing: { id: string; title: string; filePath: string }[];
+}
+
+/** Result of importing a subscriptions export. */
+export interface SubscriptionImportReport {
+  format: "takeout" | "newpipe";
+  total: number;
+  imported: number;
+  /** Channels that were already subscribed. */
+  skipped: number;
+  failures: { title: string; url: string; error: string }[];
+}
+
+/** Status counts of the downloads sharing a `batchId`. */
+export interface BatchProgress {
+  batchId: string;
+  total: number;
+  completed: number;
+  active: number;
+  queued: number;
+  paused: number;
+  failed: number;
+  cancelled: number;
+  /** 0-100, completed downloads counting as 100. */
+  progress: number;
+}
+
+/** Totals across running and queued downloads (`queue-progress`). */
+export interface QueueSummary {
+  active: number;
+  queued: number;
+  remainingBytes: number;
+  bytesPerSec: number;
+  /** null while nothing is transferring. */
+  etaSeconds: number | null;
+  /** Queued downloads are counted at an assumed size. */
+  estimated: boolean;
+}
+
 export interface DownloadCompleteEvent {
   id: string;
   outputPath: string;
@@ -312,6 +1322,7 @@ export interface DownloadCompleteEvent {
 export interface DownloadErrorEvent {
   id: string;
   error: string;
+  code: ErrorCode;
 }
 
 export interface RssUpdatedEvent {
@@ -328,6 +1339,100 @@ export interface RssSyncProgressEvent {
   message?: string;
 }
 
+export interface SubscriptionImportProgressEvent {
+  done: number;
+  total: number;
+  imported: number;
+  skipped: number;
+  failed: number;
+  current: string;
+}
+
+/** A page of a playlist being fetched by `getPlaylistInfo` or `getAccountPlaylist`. */
+export interface PlaylistInfoProgressEvent {
+  url: string;
+  id: string;
+  title: string;
+  /** This page's entries only. */
+  entries: PlaylistEntry[];
+  /** Entries fetched so far, this page included. */
+  fetched: number;
+  /** null when the site doesn't report the playlist's size. */
+  total: number | null;
+  done: boolean;
+}
+
+export interface PlaylistDeletion {
+  /** Downloads removed */
+  removed: number;
+  /** Files that were asked to be deleted but are still on disk */
+  keptFiles: string[];
+}
+
+export interface PlaylistSyncedEvent {
+  playlistId: string;
+  title: string;
+  newItems: number;
+  downloadIds: string[];
+  totalVideos: number;
+  /** Removed or unavailable entries that are back, queued or relinked */
+  returned: number;
+  removed: number;
+  unavailable: number;
+  archived: number;
+  /** New entries skipped for being older than the playlist's `dateAfter` */
+  tooOld: number;
+}
+
+export interface InstallProgressEvent {
+  tool: string;
+  status: "downloading" | "extracting" | "completed";
+  progress: number;
+  /** Present while a file is being downloaded. */
+  downloadedBytes?: number;
+  totalBytes?: number | null;
+  bytesPerSecond?: number;
+}
+
+/** Wi-Fi-only, low-battery or quiet-hours guard paused or resumed downloads. */
+export interface NetworkChangedEvent {
+  metered: boolean;
+  batteryLow: boolean;
+  quietHours: boolean;
+  overQuota: boolean;
+  paused?: number;
+  resumed?: number;
+}
+
+/** A scheduled stream is about to start, or its recording started (`id`). */
+export interface LiveStreamEvent {
+  videoId: string;
+  feedId: string;
+  title: string;
+  url: string;
+  /** Unix seconds. */
+  scheduledAt: number;
+  id?: string;
+}
+
+/** A download requested from outside the app (ytdl:// link). */
+export interface ExternalDownloadEvent {
+  url: string;
+  /** Download id when it was queued. */
+  id?: string;
+  error?: string;
+  code?: ErrorCode;
+}
+
+export interface PlaylistProgressEvent {
+  playlistId: string;
+  completed: number;
+  failed: number;
+  total: number;
+  percent: number;
+  currentTitle: string;
+}
+
 export const events = {
   onDownloadProgress: (
     callback: (event: DownloadProgressEvent) => void,
@@ -361,4 +1466,67 @@ export const events = {
     listen<RssSyncProgressEvent>("rss-sync-progress", (e) =>
       callback(e.payload),
     ),
+  onSubscriptionImportProgress: (
+    callback: (event: SubscriptionImportProgressEvent) => void,
+  ): Promise<UnlistenFn> =>
+    listen<SubscriptionImportProgressEvent>("subscription-import-progress", (e) =>
+      callback(e.payload),
+    ),
+  onPlaylistInfoProgress: (
+    callback: (event: PlaylistInfoProgressEvent) => void,
+  ): Promise<UnlistenFn> =>
+    listen<PlaylistInfoProgressEvent>("playlist-info-progress", (e) =>
+      callback(e.payload),
+    ),
+  onPlaylistSynced: (
+    callback: (event: PlaylistSyncedEvent) => void,
+  ): Promise<UnlistenFn> =>
+    listen<PlaylistSyncedEvent>("playlist-synced", (e) => callback(e.payload)),
+  onPlaylistProgress: (
+    callback: (event: PlaylistProgressEvent) => void,
+  ): Promise<UnlistenFn> =>
+    listen<PlaylistProgressEvent>("playlist-progress", (e) =>
+      callback(e.payload),
+    ),
+  onInstallProgress: (
+    callback: (event: InstallProgressEvent) => void,
+  ): Promise<UnlistenFn> =>
+    listen<InstallProgressEvent>("install-progress", (e) =>
+      callback(e.payload),
+    ),
+  /** Usually fires before the UI listens; see `commands.getStartupReport`. */
+  onStartupReconciled: (
+    callback: (event: StartupReport) => void,
+  ): Promise<UnlistenFn> =>
+    listen<StartupReport>("startup-reconciled", (e) => callback(e.payload)),
+  onNetworkChanged: (
+    callback: (event: NetworkChangedEvent) => void,
+  ): Promise<UnlistenFn> =>
+    listen<NetworkChangedEvent>("network-changed", (e) => callback(e.payload)),
+  onLiveStartingSoon: (
+    callback: (event: LiveStreamEvent) => void,
+  ): Promise<UnlistenFn> =>
+    listen<LiveStreamEvent>("live-starting-soon", (e) => callback(e.payload)),
+  /** Auto-record started a download for a scheduled stream. */
+  onLiveRecordingStarted: (
+    callback: (event: LiveStreamEvent) => void,
+  ): Promise<UnlistenFn> =>
+    listen<LiveStreamEvent>("live-recording-started", (e) =>
+      callback(e.payload),
+    ),
+  /** Queued downloads from the last session were started. */
+  onQueueResumed: (
+    callback: (event: ResumedDownload[]) => void,
+  ): Promise<UnlistenFn> =>
+    listen<ResumedDownload[]>("queue-resumed", (e) => callback(e.payload)),
+  onQueueProgress: (
+    callback: (event: QueueSummary) => void,
+  ): Promise<UnlistenFn> =>
+    listen<QueueSummary>("queue-progress", (e) => callback(e.payload)),
+  onExternalDownload: (
+    callback: (event: ExternalDownloadEvent) => void,
+  ): Promise<UnlistenFn> =>
+    listen<ExternalDownloadEvent>("external-download", (e) =>
+      callback(e.payload),
+    ),
 };
@@ -5,6 +5,15 @@ import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Card, CardContent } from "@/components/ui/card";
 import { Badge } from "@/components/ui/badge";
+import { Label } from "@/components/ui/label";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
 import { Tabs, TabsList, TabsTrigger, TabsContent } from "@/components/ui/tabs";
 import { ScrollArea } from "@/components/ui/scroll-area";
 import {
@@ -27,6 +36,10 @@ import {
   PauseCircle,
   PlayCircle,
   XCircle,
+  Layers,
+  FileDown,
+  FolderSearch,
+  Link2,
 } from "lucide-react";
 import { formatBytes, formatDuration, cn } from "@/lib/utils";
 import { useDownloads } from "@/hooks/useDownloads";
@@ -37,14 +50,21 @@ import {
   platformAtom,
 } from "@/store/atoms";
 import type {
+  DownloadChoice,
   DownloadItem,
   DownloadStatus,
+  ExportFormat,
+  QueueSummary,
+  QuotaStatus,
+  RelinkReport,
+  SpeedSample,
   VideoInfo,
   VideoFormat,
 } from "@/lib/tauri";
 import { PlaylistDownload } from "@/components/PlaylistDownload";
-import { commands } from "@/lib/tauri";
+import { commands, events } from "@/lib/tauri";
 import { toast } from "sonner";
+import { open as openDialog, save } from "@tauri-apps/plugin-dialog";
 
 type FilterTab = "all" | "active" | "completed" | "error";
 type DownloadTab = "single" | "playlist";
@@ -57,6 +77,10 @@ export function DownloadPage() {
   const [fetchingInfo, setFetchingInfo] = useState(false);
   const [filterTab, setFilterTab] = useState<FilterTab>("all");
   const [sourceFilter, setSourceFilter] = useState<SourceFilter>("all");
+  const [batchFilter, setBatchFilter] = useState<string | null>(null);
+  const [showExport, setShowExport] = useState(false);
+  const [scanning, setScanning] = useState(false);
+  const [showRelink, setShowRelink] = useState(false);
   const [downloadTab, setDownloadTab] = useState<DownloadTab>("single");
   const [videoInfo, setVideoInfo] = useAtom(videoInfoAtom);
   const [showQuality, setShowQuality] = useAtom(showQualityDialogAtom);
@@ -66,6 +90,7 @@ export function DownloadPage() {
   const {
     downloads,
     loading,
+    loadDownloads,
     startDownload,
     pauseDownload,
     resumeDownload,
@@ -75,10 +100,48 @@ export function DownloadPage() {
     getVideoInfo,
   } = useDownloads();
 
+  const [queueSummary, setQueueSummary] = useState<QueueSummary | null>(null);
+  useEffect(() => {
+    commands
+      .getQueueSummary()
+      .then(setQueueSummary)
+      .catch(() => {});
+    const unlisten = events.onQueueProgress(setQueueSummary);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Usage only grows while something runs, so refresh as the queue moves
+  const [quota, setQuota] = useState<QuotaStatus | null>(null);
+  const activeCount = queueSummary?.active ?? 0;
+  useEffect(() => {
+    commands
+      .getQuotaStatus()
+      .then(setQuota)
+      .catch(() => {});
+  }, [activeCount, downloads.length]);
+
+  const forceStartDownload = useCallback(
+    async (id: string) => {
+      try {
+        await commands.forceStartDownload(id);
+        loadDownloads();
+      } catch (err) {
+        toast.error(`${t("download.forceStartFailed")}: ${err}`);
+      }
+    },
+    [loadDownloads, t],
+  );
+
   // Search and filter downloads
   const filteredDownloads = useMemo(() => {
     let result = downloads;
 
+    if (batchFilter) {
+      result = result.filter((d) => d.batchId === batchFilter);
+    }
+
     // Apply source filter
     if (sourceFilter !== "all") {
       result = result.filter((d) => (d.source || "single") === sourceFilter);
@@ -101,7 +164,8 @@ export function DownloadPage() {
           (d) =>
             d.status === "downloading" ||
             d.status === "queued" ||
-            d.status === "merging",
+            d.status === "merging" ||
+            d.status === "converting",
         );
       case "completed":
         return result.filter((d) => d.status === "completed");
@@ -112,7 +176,7 @@ export function DownloadPage() {
       default:
         return result;
     }
-  }, [downloads, searchQuery, filterTab, sourceFilter]);
+  }, [downloads, searchQuery, filterTab, sourceFilter, batchFilter]);
 
   // Source counts
   const sourceCounts = useMemo(
@@ -159,6 +223,68 @@ export function DownloadPage() {
     }
   };
 
+  const handleScanLibrary = async () => {
+    const path = await openDialog({ directory: true, multiple: false });
+    if (typeof path !== "string") return;
+    setScanning(true);
+    try {
+      const report = await commands.scanLibrary(path);
+      toast.success(
+        t("download.libraryScanned", {
+          added: report.added,
+          known: report.alreadyKnown,
+          unmatched: report.unmatched,
+        }),
+      );
+      if (report.added > 0) await loadDownloads();
+    } catch (err) {
+      toast.error(t("download.libraryScanFailed", { error: String(err) }));
+    } finally {
+      setScanning(false);
+    }
+  };
+
+  // Computed from the live list so it follows progress events
+  const batchSummary = useMemo(() => {
+    if (!batchFilter) return null;
+    const items = downloads.filter((d) => d.batchId === batchFilter);
+    if (items.length === 0) return null;
+    const progress =
+      items.reduce(
+        (sum, d) => sum + (d.status === "completed" ? 100 : d.progress || 0),
+        0,
+      ) / items.length;
+    return {
+      total: items.length,
+      completed: items.filter((d) => d.status === "completed").length,
+      progress: Math.round(progress),
+    };
+  }, [downloads, batchFilter]);
+
+  const handleBatchAction = async (action: "pause" | "retry" | "cancel") => {
+    if (!batchFilter) return;
+    try {
+      if (action === "pause") {
+        const count = await commands.pauseBatch(batchFilter);
+        if (count > 0) toast.success(t("download.pausedCount", { count }));
+      } else if (action === "retry") {
+        const count = await commands.retryBatch(batchFilter);
+        if (count > 0) toast.success(t("download.resumedCount", { count }));
+      } else {
+        const count = await commands.cancelBatch(batchFilter);
+        if (count > 0) toast.success(t("download.cancelledCount", { count }));
+      }
+    } catch (err) {
+      const key =
+        action === "pause"
+          ? "download.pauseFailed"
+          : action === "retry"
+            ? "download.resumeFailed"
+            : "download.cancelFailed";
+      toast.error(t(key, { error: String(err) }));
+    }
+  };
+
   const counts = useMemo(
     () => ({
       all: downloads.length,
@@ -166,7 +292,8 @@ export function DownloadPage() {
         (d) =>
           d.status === "downloading" ||
           d.status === "queued" ||
-          d.status === "merging",
+          d.status === "merging" ||
+          d.status === "converting",
       ).length,
       completed: downloads.filter((d) => d.status === "completed").length,
       error: downloads.filter(
@@ -223,12 +350,17 @@ export function DownloadPage() {
   ]);
 
   const handleQualitySelect = useCallback(
-    async (formatId: string) => {
+    async (formatId: string, choice: DownloadChoice) => {
       setShowQuality(false);
       const downloadUrl = pendingUrl;
       setUrl("");
       try {
-        await startDownload(downloadUrl, formatId);
+        await startDownload(
+          downloadUrl,
+          formatId,
+          videoInfo ?? undefined,
+          choice,
+        );
         if (platform === "android") {
           toast.info(t("download.termuxStarted"));
         }
@@ -236,7 +368,7 @@ export function DownloadPage() {
         // Error toasted
       }
     },
-    [pendingUrl, startDownload, setShowQuality, platform, t],
+    [pendingUrl, videoInfo, startDownload, setShowQuality, platform, t],
   );
 
   return (
@@ -364,6 +496,17 @@ export function DownloadPage() {
         />
       )}
 
+      <ExportDialog
+        open={showExport}
+        onOpenChange={setShowExport}
+        filterTab={filterTab}
+      />
+      <RelinkDialog
+        open={showRelink}
+        onOpenChange={setShowRelink}
+        onRelinked={loadDownloads}
+      />
+
       {/* Download Queue Section */}
       <div className="flex-1 px-4 sm:px-6 pb-6 flex flex-col min-h-0">
         <div className="flex-1 flex flex-col min-h-0 bg-card/60 backdrop-blur-md border border-border/50 dark:border-white/10 rounded-[24px] shadow-sm overflow-hidden">
@@ -373,6 +516,45 @@ export function DownloadPage() {
                 <h3 className="text-lg font-bold tracking-tight whitespace-nowrap">
                   {t("download.queue")}
                 </h3>
+                {queueSummary && queueSummary.active > 0 && (
+                  <span
+                    className="text-xs text-muted-foreground whitespace-nowrap"
+                    title={
+                      queueSummary.estimated
+                        ? t("download.queueEstimated")
+                        : undefined
+                    }
+                  >
+                    {formatBytes(queueSummary.bytesPerSec)}/s
+                    {queueSummary.remainingBytes > 0 &&
+                      ` · ${queueSummary.estimated ? "~" : ""}${formatBytes(queueSummary.remainingBytes)} ${t("download.queueLeft")}`}
+                    {queueSummary.etaSeconds != null &&
+                      ` · ${formatDuration(queueSummary.etaSeconds)}`}
+                  </span>
+                )}
+                {quota && quota.limitBytes > 0 && (
+                  <span
+                    className={cn(
+                      "text-xs whitespace-nowrap",
+                      quota.exceeded
+                        ? "text-amber-500"
+                        : "text-muted-foreground",
+                    )}
+                    title={t("download.quotaResets", {
+                      time: new Date(quota.resetsAt).toLocaleString(),
+                    })}
+                  >
+                    {t(
+                      quota.period === "week"
+                        ? "download.quotaWeek"
+                        : "download.quotaDay",
+                      {
+                        used: formatBytes(quota.usedBytes),
+                        limit: formatBytes(quota.limitBytes),
+                      },
+                    )}
+                  </span>
+                )}
 
                 {/* Source Filter (Single / Playlist) */}
                 <div className="w-full overflow-x-auto pb-1 sm:pb-0 sm:w-auto scrollbar-hide">
@@ -452,10 +634,107 @@ export function DownloadPage() {
                       {t("download.cancelAll")}
                     </span>
                   </Button>
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={() => setShowExport(true)}
+                    disabled={downloads.length === 0}
+                    className="rounded-full h-8 px-2 sm:px-3 text-xs"
+                  >
+                    <FileDown className="w-3.5 h-3.5 sm:mr-1.5" />
+                    <span className="hidden sm:inline">
+                      {t("download.export")}
+                    </span>
+                  </Button>
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={handleScanLibrary}
+                    disabled={scanning}
+                    title={t("download.scanLibraryHint")}
+                    className="rounded-full h-8 px-2 sm:px-3 text-xs"
+                  >
+                    {scanning ? (
+                      <Loader2 className="w-3.5 h-3.5 sm:mr-1.5 animate-spin" />
+                    ) : (
+                      <FolderSearch className="w-3.5 h-3.5 sm:mr-1.5" />
+                    )}
+                    <span className="hidden sm:inline">
+                      {t("download.scanLibrary")}
+                    </span>
+                  </Button>
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={() => setShowRelink(true)}
+                    disabled={counts.completed === 0}
+                    className="rounded-full h-8 px-2 sm:px-3 text-xs"
+                  >
+                    <Link2 className="w-3.5 h-3.5 sm:mr-1.5" />
+                    <span className="hidden sm:inline">
+                      {t("download.relink")}
+                    </span>
+                  </Button>
                 </div>
               </div>
             </div>
 
+            {batchFilter && batchSummary && (
+              <div className="flex flex-wrap items-center gap-2 mb-3 rounded-2xl bg-primary/5 border border-primary/20 px-3 py-2">
+                <Layers className="w-4 h-4 text-primary" />
+                <span className="text-xs font-medium">
+                  {t("download.batch")}
+                </span>
+                <span className="text-xs text-muted-foreground">
+                  {t("download.batchSummary", batchSummary)}
+                </span>
+                <div className="flex gap-1.5 ml-auto">
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={() => handleBatchAction("pause")}
+                    className="rounded-full h-7 px-2 sm:px-3 text-xs"
+                  >
+                    <PauseCircle className="w-3.5 h-3.5 sm:mr-1.5" />
+                    <span className="hidden sm:inline">
+                      {t("download.pauseBatch")}
+                    </span>
+                  </Button>
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={() => handleBatchAction("retry")}
+                    className="rounded-full h-7 px-2 sm:px-3 text-xs"
+                  >
+                    <RotateCcw className="w-3.5 h-3.5 sm:mr-1.5" />
+                    <span className="hidden sm:inline">
+                      {t("download.retryBatch")}
+                    </span>
+                  </Button>
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={() => handleBatchAction("cancel")}
+                    className="rounded-full h-7 px-2 sm:px-3 text-xs"
+                  >
+                    <XCircle className="w-3.5 h-3.5 sm:mr-1.5" />
+                    <span className="hidden sm:inline">
+                      {t("download.cancelBatch")}
+                    </span>
+                  </Button>
+                  <Button
+                    variant="ghost"
+                    size="icon"
+                    onClick={() => setBatchFilter(null)}
+                    title={t("download.batchClear")}
+                    className="rounded-full h-7 w-7"
+                  >
+                    <X className="w-3.5 h-3.5" />
+                  </Button>
+                </div>
+              </div>
+            )}
+
             {/* Search Input + Status Filter Tabs */}
             <div className="flex items-center gap-3">
               <div className="overflow-x-auto scrollbar-hide">
@@ -537,9 +816,13 @@ export function DownloadPage() {
                       download={dl}
                       onPause={pauseDownload}
                       onResume={resumeDownload}
+                      onForceStart={
+                        quota?.exceeded ? forceStartDownload : undefined
+                      }
                       onCancel={cancelDownload}
                       onRetry={retryDownload}
                       onDelete={deleteDownload}
+                      onShowBatch={setBatchFilter}
                     />
                   ))}
                 </div>
@@ -552,6 +835,271 @@ export function DownloadPage() {
   );
 }
 
+const EXPORT_FORMATS: { value: ExportFormat; label: string; ext: string }[] =
+  [
+    { value: "csv", label: "CSV", ext: "csv" },
+    { value: "json", label: "JSON", ext: "json" },
+    { value: "html", label: "HTML", ext: "html" },
+    { value: "markdown", label: "Markdown", ext: "md" },
+  ];
+
+const FILTER_STATUSES: Record<FilterTab, DownloadStatus[]> = {
+  all: [],
+  active: ["downloading", "queued", "merging", "converting"],
+  completed: ["completed"],
+  error: ["error", "cancelled"],
+};
+
+/** Save the download list as a report, filtered by status and date added. */
+function ExportDialog({
+  open,
+  onOpenChange,
+  filterTab,
+}: {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  filterTab: FilterTab;
+}) {
+  const { t } = useTranslation();
+  const [format, setFormat] = useState<ExportFormat>("csv");
+  const [status, setStatus] = useState<FilterTab>(filterTab);
+  const [from, setFrom] = useState("");
+  const [to, setTo] = useState("");
+  const [exporting, setExporting] = useState(false);
+
+  useEffect(() => {
+    if (open) setStatus(filterTab);
+  }, [open, filterTab]);
+
+  const handleExport = async () => {
+    const ext = EXPORT_FORMATS.find((f) => f.value === format)?.ext ?? format;
+    const path = await save({
+      defaultPath: `downloads.${ext}`,
+      filters: [{ name: format.toUpperCase(), extensions: [ext] }],
+    });
+    if (!path) return;
+    setExporting(true);
+    try {
+      await commands.exportDownloads(format, {
+        statuses: FILTER_STATUSES[status],
+        from: from || undefined,
+        to: to || undefined,
+        path,
+      });
+      toast.success(t("download.exported", { path }));
+      onOpenChange(false);
+    } catch (err) {
+      toast.error(t("download.exportFailed", { error: String(err) }));
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-md">
+        <DialogHeader>
+          <DialogTitle>{t("download.export")}</DialogTitle>
+          <DialogDescription>{t("download.exportDesc")}</DialogDescription>
+        </DialogHeader>
+
+        <div className="space-y-4 py-2">
+          <div className="space-y-2">
+            <Label>{t("download.exportFormat")}</Label>
+            <div className="flex flex-wrap gap-2">
+              {EXPORT_FORMATS.map((f) => (
+                <Button
+                  key={f.value}
+                  variant={format === f.value ? "default" : "outline"}
+                  size="sm"
+                  onClick={() => setFormat(f.value)}
+                  className="rounded-full"
+                >
+                  {f.label}
+                </Button>
+              ))}
+            </div>
+          </div>
+          <div className="space-y-2">
+            <Label>{t("download.exportStatus")}</Label>
+            <Tabs value={status} onValueChange={(v) => setStatus(v as FilterTab)}>
+              <TabsList className="rounded-full h-9 p-1">
+                <TabsTrigger value="all" className="rounded-full text-xs px-3">
+                  {t("download.all")}
+                </TabsTrigger>
+                <TabsTrigger value="active" className="rounded-full text-xs px-3">
+                  {t("download.active")}
+                </TabsTrigger>
+                <TabsTrigger
+                  value="completed"
+                  className="rounded-full text-xs px-3"
+                >
+                  {t("download.completed")}
+                </TabsTrigger>
+                <TabsTrigger value="error" className="rounded-full text-xs px-3">
+                  {t("download.error")}
+                </TabsTrigger>
+              </TabsList>
+            </Tabs>
+          </div>
+          <div className="grid grid-cols-2 gap-3">
+            <div className="space-y-2">
+              <Label>{t("download.exportFrom")}</Label>
+              <Input
+                type="date"
+                value={from}
+                onChange={(e) => setFrom(e.target.value)}
+              />
+            </div>
+            <div className="space-y-2">
+              <Label>{t("download.exportTo")}</Label>
+              <Input
+                type="date"
+                value={to}
+                onChange={(e) => setTo(e.target.value)}
+              />
+            </div>
+          </div>
+        </div>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => onOpenChange(false)}>
+            {t("common.cancel")}
+          </Button>
+          <Button onClick={handleExport} disabled={exporting}>
+            {exporting && <Loader2 className="w-4 h-4 mr-2 animate-spin" />}
+            {t("download.exportSave")}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
+
+function RelinkDialog({
+  open,
+  onOpenChange,
+  onRelinked,
+}: {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+  onRelinked: () => Promise<void>;
+}) {
+  const { t } = useTranslation();
+  const [oldBase, setOldBase] = useState("");
+  const [newBase, setNewBase] = useState("");
+  const [scanDir, setScanDir] = useState("");
+  const [running, setRunning] = useState(false);
+  const [report, setReport] = useState<RelinkReport | null>(null);
+
+  useEffect(() => {
+    if (!open) setReport(null);
+  }, [open]);
+
+  const pickFolder = async (set: (path: string) => void) => {
+    const path = await openDialog({ directory: true, multiple: false });
+    if (typeof path === "string") set(path);
+  };
+
+  const handleRelink = async () => {
+    setRunning(true);
+    try {
+      const result = await commands.relinkDownloads({
+        oldBase: oldBase.trim() || undefined,
+        newBase: newBase.trim() || undefined,
+        scanDir: scanDir.trim() || undefined,
+      });
+      setReport(result);
+      toast.success(
+        t("download.relinked", {
+          relinked: result.relinked,
+          missing: result.missing.length,
+        }),
+      );
+      if (result.relinked > 0) await onRelinked();
+    } catch (err) {
+      toast.error(t("download.relinkFailed", { error: String(err) }));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const folderField = (
+    label: string,
+    value: string,
+    set: (path: string) => void,
+  ) => (
+    <div className="space-y-2">
+      <Label>{label}</Label>
+      <div className="flex gap-2">
+        <Input value={value} onChange={(e) => set(e.target.value)} />
+        <Button variant="outline" size="icon" onClick={() => pickFolder(set)}>
+          <FolderOpen className="w-4 h-4" />
+        </Button>
+      </div>
+    </div>
+  );
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-md">
+        <DialogHeader>
+          <DialogTitle>{t("download.relink")}</DialogTitle>
+          <DialogDescription>{t("download.relinkDesc")}</DialogDescription>
+        </DialogHeader>
+
+        <div className="space-y-4 py-2">
+          {folderField(t("download.relinkOld"), oldBase, setOldBase)}
+          {folderField(t("download.relinkNew"), newBase, setNewBase)}
+          {folderField(t("download.relinkScan"), scanDir, setScanDir)}
+          {report && report.missing.length > 0 && (
+            <div className="space-y-1">
+              <Label>
+                {t("download.relinkMissing", { count: report.missing.length })}
+              </Label>
+              <ScrollArea className="max-h-32 rounded-md border border-border/50">
+                <ul className="p-2 space-y-1 text-xs">
+                  {report.missing.map((file) => (
+                    <li key={file.id} className="truncate" title={file.filePath}>
+                      {file.title || file.filePath}
+                    </li>
+                  ))}
+                </ul>
+              </ScrollArea>
+            </div>
+          )}
+        </div>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => onOpenChange(false)}>
+            {t("common.cancel")}
+          </Button>
+          <Button
+            onClick={handleRelink}
+            disabled={
+              running ||
+              (!scanDir.trim() && (!oldBase.trim() || !newBase.trim()))
+            }
+          >
+            {running && <Loader2 className="w-4 h-4 mr-2 animate-spin" />}
+            {t("download.relinkRun")}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
+
+/** `*90-165` as `1:30–2:45`; chapter patterns without their anchors. */
+function sectionLabel(section: string): string {
+  const range = section.match(/^\*([\d.]+)-([\d.]+|inf)$/);
+  if (range) {
+    const end = range[2] === "inf" ? "…" : formatDuration(Number(range[2]));
+    return `${formatDuration(Number(range[1]))}–${end}`;
+  }
+  return section.replace(/^\^|\$$/g, "").replace(/\\(.)/g, "$1");
+}
+
 // ─── Quality Selection Dialog ────────────────────────────────────────
 
 type MergedFormat = VideoFormat & {
@@ -565,11 +1113,50 @@ function QualityDialog({
   onClose,
 }: {
   info: VideoInfo;
-  onSelect: (formatId: string) => void;
+  onSelect: (formatId: string, choice: DownloadChoice) => void;
   onClose: () => void;
 }) {
   const { t } = useTranslation();
   const [tab, setTab] = useState<"combined" | "video" | "audio">("combined");
+  const [clipStart, setClipStart] = useState("");
+  const [clipEnd, setClipEnd] = useState("");
+  const [clipChapter, setClipChapter] = useState("");
+  // Empty = yt-dlp's default track
+  const [audioLanguages, setAudioLanguages] = useState<string[]>([]);
+  const audioTracks = info.audioTracks ?? [];
+  // Full archive or chapter tracks, one at a time
+  const [bundle, setBundle] = useState<"archive" | "tracks" | undefined>();
+  const toggleBundle = (mode: "archive" | "tracks") =>
+    setBundle((current) => (current === mode ? undefined : mode));
+  // Empty = the concurrent fragments setting
+  const [fragments, setFragments] = useState("");
+  // Empty = the folder from the settings
+  const [outputDir, setOutputDir] = useState("");
+  const pickOutputDir = async () => {
+    const dir = await commands.selectDirectory().catch(() => null);
+    if (dir) setOutputDir(dir);
+  };
+
+  // A chapter wins over a time range; matched by its exact title
+  const section = clipChapter
+    ? `^${clipChapter.replace(/[.*+?^${}()|[\]\\]/g, "\\$&")}$`
+    : clipStart.trim() || clipEnd.trim()
+      ? `${clipStart.trim()}-${clipEnd.trim()}`
+      : undefined;
+  const select = (formatId: string) =>
+    onSelect(formatId, {
+      section,
+      audioLanguages: audioLanguages.length > 0 ? audioLanguages : undefined,
+      mode: bundle,
+      concurrentFragments: fragments ? parseInt(fragments, 10) : undefined,
+      outputDir: outputDir || undefined,
+    });
+  const toggleAudio = (language: string) =>
+    setAudioLanguages((current) =>
+      current.includes(language)
+        ? current.filter((l) => l !== language)
+        : [...current, language],
+    );
   const dialogRef = useRef<HTMLDivElement>(null);
 
   // Focus trap + Escape key handler
@@ -723,6 +1310,138 @@ function QualityDialog({
               </Button>
             </div>
 
+            {/* Clip: time range or a single chapter */}
+            <div className="mt-3 flex flex-wrap items-center gap-2 text-xs">
+              <span className="text-muted-foreground">
+                {t("download.clip")}
+              </span>
+              <Input
+                value={clipStart}
+                onChange={(e) => setClipStart(e.target.value)}
+                placeholder="0:00"
+                aria-label={t("download.clipStart")}
+                disabled={!!clipChapter}
+                className="h-7 w-20 text-xs"
+              />
+              <span className="text-muted-foreground">–</span>
+              <Input
+                value={clipEnd}
+                onChange={(e) => setClipEnd(e.target.value)}
+                placeholder={
+                  info.duration
+                    ? formatDuration(info.duration)
+                    : t("download.clipEnd")
+                }
+                aria-label={t("download.clipEnd")}
+                disabled={!!clipChapter}
+                className="h-7 w-20 text-xs"
+              />
+              {info.chapters && info.chapters.length > 0 && (
+                <select
+                  value={clipChapter}
+                  onChange={(e) => setClipChapter(e.target.value)}
+                  aria-label={t("download.clipChapter")}
+                  className="h-7 max-w-[220px] rounded-md border bg-background px-2 text-xs"
+                >
+                  <option value="">{t("download.clipAllChapters")}</option>
+                  {info.chapters.map((chapter) => (
+                    <option key={chapter.start} value={chapter.title}>
+                      {formatDuration(chapter.start)} · {chapter.title}
+                    </option>
+                  ))}
+                </select>
+              )}
+              <Button
+                variant={bundle === "archive" ? "default" : "outline"}
+                size="sm"
+                className="h-7 px-2 text-xs ml-auto"
+                title={t("download.fullArchiveDesc")}
+                onClick={() => toggleBundle("archive")}
+              >
+                {t("download.fullArchive")}
+              </Button>
+              {info.chapters && info.chapters.length > 1 && (
+                <Button
+                  variant={bundle === "tracks" ? "default" : "outline"}
+                  size="sm"
+                  className="h-7 px-2 text-xs"
+                  title={t("download.chapterTracksDesc")}
+                  onClick={() => toggleBundle("tracks")}
+                >
+                  {t("download.chapterTracks")}
+                </Button>
+              )}
+            </div>
+
+            {/* Audio tracks of multi-language videos; several can be kept */}
+            {audioTracks.length > 1 && (
+              <div className="mt-2 flex flex-wrap items-center gap-1.5 text-xs">
+                <span className="text-muted-foreground">
+                  {t("download.audioTracks")}
+                </span>
+                {audioTracks.map((track) => (
+                  <Button
+                    key={track.language}
+                    variant={
+                      audioLanguages.includes(track.language)
+                        ? "default"
+                        : "outline"
+                    }
+                    size="sm"
+                    className="h-7 px-2 text-xs"
+                    onClick={() => toggleAudio(track.language)}
+                  >
+                    {track.label}
+                    {track.original && ` · ${t("download.originalAudio")}`}
+                  </Button>
+                ))}
+              </div>
+            )}
+
+            {/* Per-download yt-dlp -N and destination folder */}
+            <div className="mt-2 flex items-center gap-2 text-xs">
+              <span className="text-muted-foreground">
+                {t("download.fragments")}
+              </span>
+              <select
+                value={fragments}
+                onChange={(e) => setFragments(e.target.value)}
+                aria-label={t("download.fragments")}
+                title={t("settings.concurrentFragmentsDesc")}
+                className="h-7 rounded-md border bg-background px-2 text-xs"
+              >
+                <option value="">{t("download.fragmentsDefault")}</option>
+                {[1, 2, 4, 8, 16].map((n) => (
+                  <option key={n} value={n}>
+                    {n}
+                  </option>
+                ))}
+              </select>
+              <Button
+                variant={outputDir ? "default" : "outline"}
+                size="sm"
+                className="h-7 px-2 text-xs ml-auto max-w-[240px]"
+                title={outputDir || t("download.saveToDesc")}
+                onClick={pickOutputDir}
+              >
+                <FolderOpen className="w-3.5 h-3.5 mr-1 flex-shrink-0" />
+                <span className="truncate">
+                  {outputDir || t("download.saveTo")}
+                </span>
+              </Button>
+              {outputDir && (
+                <Button
+                  variant="ghost"
+                  size="icon"
+                  className="h-7 w-7"
+                  aria-label={t("download.saveToReset")}
+                  onClick={() => setOutputDir("")}
+                >
+                  <X className="w-3.5 h-3.5" />
+                </Button>
+              )}
+            </div>
+
             <Tabs
               value={tab}
               onValueChange={(v) => setTab(v as typeof tab)}
@@ -746,7 +1465,7 @@ function QualityDialog({
           <ScrollArea key={tab} className="flex-1 h-72 overflow-auto">
             <div className="p-2 space-y-1">
               <button
-                onClick={() => onSelect("best")}
+                onClick={() => select("best")}
                 className="w-full flex items-center gap-3 p-2.5 rounded-lg hover:bg-accent transition-colors text-left"
               >
                 <Zap className="w-4 h-4 text-primary flex-shrink-0" />
@@ -761,7 +1480,7 @@ function QualityDialog({
               {currentFormats.map((fmt) => (
                 <button
                   key={fmt.formatId}
-                  onClick={() => onSelect(fmt.formatId)}
+                  onClick={() => select(fmt.formatId)}
                   className="w-full flex items-center gap-3 p-2.5 rounded-lg hover:bg-accent transition-colors text-left"
                 >
                   {tab === "audio" ? (
@@ -836,16 +1555,21 @@ const DownloadItemCard = memo(function DownloadItemCard({
   download,
   onPause,
   onResume,
+  onForceStart,
   onCancel,
   onRetry,
   onDelete,
+  onShowBatch,
 }: {
   download: DownloadItem;
   onPause: (id: string) => void;
   onResume: (id: string) => void;
+  /** Set while the quota is used up. */
+  onForceStart?: (id: string) => void;
   onCancel: (id: string) => void;
   onRetry: (id: string) => void;
   onDelete: (id: string, deleteFile: boolean) => void;
+  onShowBatch?: (batchId: string) => void;
 }) {
   const { t } = useTranslation();
 
@@ -875,6 +1599,12 @@ const DownloadItemCard = memo(function DownloadItemCard({
             {t("download.paused")}
           </span>
         );
+      case "interrupted":
+        return (
+          <span className="text-[10px] font-medium text-amber-500 bg-amber-500/10 px-2 py-0.5 rounded-full">
+            {t("download.interrupted")}
+          </span>
+        );
       case "error":
         return (
           <span className="text-[10px] font-medium text-destructive bg-destructive/10 px-2 py-0.5 rounded-full">
@@ -893,6 +1623,12 @@ const DownloadItemCard = memo(function DownloadItemCard({
             {t("download.merging")}
           </span>
         );
+      case "converting":
+        return (
+          <span className="text-[10px] font-medium text-amber-500 bg-amber-500/10 px-2 py-0.5 rounded-full">
+            {t("download.converting")}
+          </span>
+        );
       default:
         return (
           <span className="text-[10px] font-medium text-muted-foreground bg-muted px-2 py-0.5 rounded-full">
@@ -931,6 +1667,7 @@ const DownloadItemCard = memo(function DownloadItemCard({
                 download={download}
                 onPause={onPause}
                 onResume={onResume}
+                onForceStart={onForceStart}
                 onCancel={onCancel}
                 onRetry={onRetry}
                 onDelete={onDelete}
@@ -955,10 +1692,65 @@ const DownloadItemCard = memo(function DownloadItemCard({
                 {t("download.playlist")}
               </span>
             )}
+            {download.batchId && onShowBatch && (
+              <button
+                type="button"
+                onClick={() => onShowBatch(download.batchId!)}
+                title={t("download.batchShow")}
+                className="flex items-center gap-1 bg-muted hover:bg-accent px-1.5 py-0.5 rounded font-medium"
+              >
+                <Layers className="w-3 h-3" />
+                {t("download.batch")}
+              </button>
+            )}
+            {download.section && (
+              <span className="bg-muted px-1.5 py-0.5 rounded font-medium">
+                {t("download.clip")}: {sectionLabel(download.section)}
+              </span>
+            )}
+            {download.mode === "archive" && (
+              <span className="bg-muted px-1.5 py-0.5 rounded font-medium">
+                {t("download.fullArchive")}
+              </span>
+            )}
+            {download.mode === "tracks" && (
+              <span className="bg-muted px-1.5 py-0.5 rounded font-medium">
+                {t("download.chapterTracks")}
+              </span>
+            )}
+            {download.note && (
+              <span className="italic truncate" title={download.note}>
+                {download.note}
+              </span>
+            )}
           </div>
 
+          {(download.uploadDate || download.viewCount != null) && (
+            <div
+              className="flex flex-wrap items-center gap-2 text-[11px] text-muted-foreground mb-2"
+              title={download.description || undefined}
+            >
+              {download.uploadDate && <span>{download.uploadDate}</span>}
+              {download.viewCount != null && (
+                <span>
+                  {t("download.views", {
+                    formatted: new Intl.NumberFormat(undefined, {
+                      notation: "compact",
+                    }).format(download.viewCount),
+                  })}
+                </span>
+              )}
+              {download.tags?.slice(0, 5).map((tag) => (
+                <span key={tag} className="bg-muted px-1.5 py-0.5 rounded">
+                  #{tag}
+                </span>
+              ))}
+            </div>
+          )}
+
           {(download.status === "downloading" ||
-            download.status === "merging") && (
+            download.status === "merging" ||
+            download.status === "converting") && (
             <div className="space-y-1.5 mt-1">
               <div className="flex-1 h-1.5 bg-muted/50 rounded-full overflow-hidden">
                 {/* Termux downloads have no progress tracking — show indeterminate animation */}
@@ -971,7 +1763,9 @@ const DownloadItemCard = memo(function DownloadItemCard({
                       "h-full rounded-full transition-all duration-300",
                       download.status === "merging"
                         ? "bg-purple-500 animate-pulse"
-                        : "bg-primary",
+                        : download.status === "converting"
+                          ? "bg-amber-500"
+                          : "bg-primary",
                     )}
                     style={{ width: `${download.progress}%` }}
                   />
@@ -992,6 +1786,7 @@ const DownloadItemCard = memo(function DownloadItemCard({
                       {download.speed && download.speed !== "0" && (
                         <span>{download.speed}</span>
                       )}
+                      <SpeedSparkline id={download.id} />
                     </>
                   )}
                 </span>
@@ -1015,10 +1810,63 @@ const DownloadItemCard = memo(function DownloadItemCard({
   );
 });
 
+/** Transfer rate over time, refreshed while the download runs. */
+function SpeedSparkline({ id }: { id: string }) {
+  const [samples, setSamples] = useState<SpeedSample[]>([]);
+
+  useEffect(() => {
+    let cancelled = false;
+    const load = () =>
+      commands
+        .getSpeedHistory(id)
+        .then((s) => !cancelled && setSamples(s))
+        .catch(() => {});
+    load();
+    const timer = setInterval(load, 5000);
+    return () => {
+      cancelled = true;
+      clearInterval(timer);
+    };
+  }, [id]);
+
+  if (samples.length < 2) return null;
+  const width = 80;
+  const height = 14;
+  const max = Math.max(...samples.map((s) => s.bytesPerSec), 1);
+  const first = samples[0].t;
+  const span = Math.max(samples[samples.length - 1].t - first, 1);
+  const points = samples
+    .map(
+      (s) =>
+        `${(((s.t - first) / span) * width).toFixed(1)},${(
+          height -
+          (s.bytesPerSec / max) * height
+        ).toFixed(1)}`,
+    )
+    .join(" ");
+  return (
+    <svg
+      width={width}
+      height={height}
+      className="text-primary"
+      aria-label={`max ${formatBytes(max)}/s`}
+    >
+      <title>{`max ${formatBytes(max)}/s`}</title>
+      <polyline
+        points={points}
+        fill="none"
+        stroke="currentColor"
+        strokeWidth={1}
+      />
+    </svg>
+  );
+}
+
 function ActionButtons({
   download,
   onPause,
   onResume,
+  onForceStart,
   onCancel,
   onRetry,
   onDelete,
@@ -1026,10 +1874,12 @@ function ActionButtons({
   download: DownloadItem;
   onPause: (id: string) => void;
   onResume: (id: string) => void;
+  onForceStart?: (id: string) => void;
   onCancel: (id: string) => void;
   onRetry: (id: string) => void;
   onDelete: (id: string, deleteFile: boolean) => void;
 }) {
+  const { t } = useTranslation();
   return (
     <>
       {download.status === "completed" && download.filePath && (
@@ -1051,8 +1901,7 @@ function ActionButtons({
             size="icon"
             className="h-8 w-8 rounded-full hover:bg-background/80"
             onClick={() => {
-              const dir = download.filePath!.replace(/[\\/][^\\/]+$/, "");
-              commands.openPath(dir).catch((err) => {
+              commands.revealInFolder(download.filePath!).catch((err) => {
                 toast.error(`Failed to open folder: ${String(err)}`);
               });
             }}
@@ -1071,7 +1920,7 @@ function ActionButtons({
           <Pause className="w-4 h-4" />
         </Button>
       )}
-      {download.status === "paused" && (
+      {(download.status === "paused" || download.status === "interrupted") && (
         <Button
           variant="ghost"
           size="icon"
@@ -1081,6 +1930,18 @@ function ActionButtons({
           <Play className="w-4 h-4" />
         </Button>
       )}
+      {onForceStart &&
+        (download.status === "paused" || download.status === "queued") && (
+          <Button
+            variant="ghost"
+            size="icon"
+            className="h-8 w-8 rounded-full bg-background/50 hover:bg-background shadow-sm border border-border/50 text-amber-500"
+            title={t("download.forceStart")}
+            onClick={() => onForceStart(download.id)}
+          >
+            <Zap className="w-4 h-4" />
+          </Button>
+        )}
       {download.status === "error" && (
         <Button
           variant="ghost"
@@ -1091,7 +1952,9 @@ function ActionButtons({
           <RotateCcw className="w-4 h-4" />
         </Button>
       )}
-      {(download.status === "downloading" || download.status === "queued") && (
+      {(download.status === "downloading" ||
+        download.status === "queued" ||
+        download.status === "converting") && (
         <Button
           variant="ghost"
           size="icon"
@@ -1,4 +1,4 @@
-import { memo, useEffect, useState, useMemo } from "react";
+import { memo, useCallback, useEffect, useState, useMemo } from "react";
 import { useTranslation } from "react-i18next";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
@@ -41,20 +41,38 @@ import {
   Filter,
   CheckCircle2,
   Video,
+  Podcast,
+  Upload,
 } from "lucide-react";
-import { useRss } from "@/hooks/useRss";
+import { FEED_PAGE_SIZE, useRss } from "@/hooks/useRss";
 import type { RssFeed, RssItem } from "@/lib/tauri";
-import { commands, events, type RssSyncProgressEvent } from "@/lib/tauri";
+import {
+  commands,
+  events,
+  type RssSyncProgressEvent,
+  type SubscriptionImportProgressEvent,
+  type SubscriptionExportFormat,
+  type SubscriptionImportReport,
+} from "@/lib/tauri";
 import { toast } from "sonner";
+import { open as openFile, save } from "@tauri-apps/plugin-dialog";
 import { VideoPlayer } from "@/components/VideoPlayer";
 
 export function RssPage() {
   const { t } = useTranslation();
-  const { feeds, loading, addFeed, removeFeed, checkFeed } = useRss();
+  const {
+    feeds,
+    loading,
+    loadFeeds,
+    addFeed,
+    removeFeed,
+    checkFeed,
+    loadMoreItems,
+  } = useRss();
   const [showAddDialog, setShowAddDialog] = useState(false);
   const [selectedFeedId, setSelectedFeedId] = useState<string | null>(null);
   const [authorFilterId, setAuthorFilterId] = useState<string>("all");
-  const [visibleCount, setVisibleCount] = useState(120);
+  const [visibleCount, setVisibleCount] = useState(FEED_PAGE_SIZE);
   const [videoTypeFilter, setVideoTypeFilter] = useState<
     "all" | "video" | "short"
   >("all");
@@ -115,8 +133,33 @@ export function RssPage() {
     [visibleItems, visibleCount],
   );
 
+  // Items still in the DB for the feeds in view; pages come in as needed
+  const unloadedCount = useMemo(
+    () =>
+      scopedFeeds.reduce(
+        (sum, f) =>
+          sum + Math.max(0, (f.itemCount ?? f.items.length) - f.items.length),
+        0,
+      ),
+    [scopedFeeds],
+  );
+
+  const handleLoadMore = useCallback(() => {
+    const next = visibleCount + FEED_PAGE_SIZE;
+    setVisibleCount(next);
+    if (next > visibleItems.length && unloadedCount > 0) {
+      void loadMoreItems(scopedFeeds.map((f) => f.id));
+    }
+  }, [
+    visibleCount,
+    visibleItems.length,
+    unloadedCount,
+    loadMoreItems,
+    scopedFeeds,
+  ]);
+
   useEffect(() => {
-    setVisibleCount(120);
+    setVisibleCount(FEED_PAGE_SIZE);
   }, [selectedFeedId, authorFilterId, videoTypeFilter]);
 
   const authorOptions = useMemo(
@@ -124,7 +167,7 @@ export function RssPage() {
       feeds.map((feed) => ({
         id: feed.id,
         name: feed.channelName || feed.title || "Channel",
-        count: feed.items?.length || 0,
+        count: feed.itemCount ?? feed.items?.length ?? 0,
       })),
     [feeds],
   );
@@ -200,6 +243,45 @@ export function RssPage() {
       toast.success(t("rss.startedDownloads", { count: started }));
   };
 
+  // The feed in view, or every audio download when none is picked
+  const podcastFeedId =
+    selectedFeedId ?? (authorFilterId !== "all" ? authorFilterId : undefined);
+
+  const handleSharePodcast = async () => {
+    try {
+      const share = await commands.sharePodcastFeed(podcastFeedId);
+      await navigator.clipboard.writeText(share.url).catch(() => {});
+      toast.success(
+        t("rss.podcastShared", { count: share.episodes, url: share.url }),
+      );
+    } catch (err) {
+      toast.error(t("rss.podcastFailed", { error: String(err) }));
+    }
+  };
+
+  const handleUnsharePodcast = async () => {
+    try {
+      await commands.unsharePodcastFeed(podcastFeedId);
+      toast.success(t("rss.podcastUnshared"));
+    } catch (err) {
+      toast.error(t("rss.podcastFailed", { error: String(err) }));
+    }
+  };
+
+  const handleExportPodcast = async () => {
+    try {
+      const path = await save({
+        defaultPath: "podcast.xml",
+        filters: [{ name: "RSS", extensions: ["xml", "rss"] }],
+      });
+      if (!path) return;
+      const count = await commands.exportPodcastFeed(path, podcastFeedId);
+      toast.success(t("rss.podcastExported", { count }));
+    } catch (err) {
+      toast.error(t("rss.podcastFailed", { error: String(err) }));
+    }
+  };
+
   const handleRefreshAll = async () => {
     try {
       toast.info(t("rss.checkNow") + "...");
@@ -426,8 +508,45 @@ export function RssPage() {
                   </Tabs>
                 </div>
 
-                {newTopItems.length > 0 && (
-                  <div className="shrink-0">
+                <div className="flex items-center gap-2 shrink-0">
+                  <DropdownMenu>
+                    <DropdownMenuTrigger asChild>
+                      <Button
+                        variant="outline"
+                        size="sm"
+                        className="h-9 rounded-xl shadow-sm text-xs font-medium border-border/50"
+                      >
+                        <Podcast className="w-3.5 h-3.5 mr-2" />
+                        {t("rss.podcast")}
+                      </Button>
+                    </DropdownMenuTrigger>
+                    <DropdownMenuContent
+                      align="end"
+                      sideOffset={8}
+                      className="rounded-xl shadow-md"
+                    >
+                      <DropdownMenuItem
+                        onClick={handleSharePodcast}
+                        className="rounded-lg"
+                      >
+                        {t("rss.podcastShare")}
+                      </DropdownMenuItem>
+                      <DropdownMenuItem
+                        onClick={handleUnsharePodcast}
+                        className="rounded-lg"
+                      >
+                        {t("rss.podcastUnshare")}
+                      </DropdownMenuItem>
+                      <DropdownMenuSeparator />
+                      <DropdownMenuItem
+                        onClick={handleExportPodcast}
+                        className="rounded-lg"
+                      >
+                        {t("rss.podcastExport")}
+                      </DropdownMenuItem>
+                    </DropdownMenuContent>
+                  </DropdownMenu>
+                  {newTopItems.length > 0 && (
                     <Button
                       onClick={handleDownloadAllNewTop}
                       variant="default"
@@ -437,16 +556,16 @@ export function RssPage() {
                       <DownloadCloud className="w-4 h-4 mr-2" />
                       {t("rss.downloadAllNew")} ({newTopItems.length})
                     </Button>
-                  </div>
-                )}
+                  )}
+                </div>
               </div>
 
               {/* Content grid */}
               <FeedDetailView
                 showAll={!selectedFeedId}
                 items={renderedItems}
-                totalCount={visibleItems.length}
-                onLoadMore={() => setVisibleCount((prev) => prev + 120)}
+                totalCount={visibleItems.length + unloadedCount}
+                onLoadMore={handleLoadMore}
                 onPlay={openPlayer}
               />
             </>
@@ -460,6 +579,7 @@ export function RssPage() {
           open={showAddDialog}
           onOpenChange={setShowAddDialog}
           onAdd={addFeed}
+          onImported={loadFeeds}
         />
 
         {/* Custom video player — uses yt-dlp for direct streaming */}
@@ -504,8 +624,10 @@ function ChannelTab({
   onRemove: () => void;
 }) {
   const { t } = useTranslation();
-  const hasNewItems = feed.items?.some((i) => i.status === "not_queued");
-  const itemCount = feed.items?.length || 0;
+  const hasNewItems =
+    (feed.newCount ?? 0) > 0 ||
+    feed.items?.some((i) => i.status === "not_queued");
+  const itemCount = feed.itemCount ?? feed.items?.length ?? 0;
   const isSyncing =
     syncEntry?.phase === "fetching" || syncEntry?.phase === "importing";
   const progressValue = Math.max(0, Math.min(syncEntry?.progress ?? 0, 100));
@@ -979,14 +1101,82 @@ function AddFeedDialog({
   open,
   onOpenChange,
   onAdd,
+  onImported,
 }: {
   open: boolean;
   onOpenChange: (open: boolean) => void;
   onAdd: (url: string) => Promise<void>;
+  onImported: () => Promise<void>;
 }) {
   const { t } = useTranslation();
   const [url, setUrl] = useState("");
   const [adding, setAdding] = useState(false);
+  const [importProgress, setImportProgress] =
+    useState<SubscriptionImportProgressEvent | null>(null);
+  const [importReport, setImportReport] =
+    useState<SubscriptionImportReport | null>(null);
+
+  useEffect(() => {
+    if (!open) setImportReport(null);
+  }, [open]);
+
+  const handleImport = async () => {
+    const path = await openFile({
+      multiple: false,
+      filters: [{ name: "Takeout / NewPipe", extensions: ["csv", "json"] }],
+    });
+    if (typeof path !== "string") return;
+    setImportReport(null);
+    setImportProgress({
+      done: 0,
+      total: 0,
+      imported: 0,
+      skipped: 0,
+      failed: 0,
+      current: "",
+    });
+    const unlisten = await events.onSubscriptionImportProgress(
+      setImportProgress,
+    );
+    try {
+      const report = await commands.importSubscriptions(path);
+      setImportReport(report);
+      toast.success(
+        t("rss.importDone", {
+          imported: report.imported,
+          skipped: report.skipped,
+          failed: report.failures.length,
+        }),
+      );
+      await onImported();
+    } catch (err) {
+      toast.error(t("rss.importFailed", { error: String(err) }));
+    } finally {
+      unlisten();
+      setImportProgress(null);
+    }
+  };
+
+  const handleExport = async (format: SubscriptionExportFormat) => {
+    try {
+      const path = await save(
+        format === "newpipe"
+          ? {
+              defaultPath: "subscriptions.json",
+              filters: [{ name: "NewPipe", extensions: ["json"] }],
+            }
+          : {
+              defaultPath: "profiles.db",
+              filters: [{ name: "FreeTube", extensions: ["db"] }],
+            },
+      );
+      if (!path) return;
+      const count = await commands.exportSubscriptions(format, path);
+      toast.success(t("rss.exportDone", { count }));
+    } catch (err) {
+      toast.error(t("rss.exportFailed", { error: String(err) }));
+    }
+  };
 
   const handleAdd = async () => {
     if (!url.trim()) return;
@@ -1022,6 +1212,69 @@ function AddFeedDialog({
               {t("rss.feedUrlHint")}
             </p>
           </div>
+
+          <div className="space-y-2 border-t border-border/50 pt-4">
+            <Label>{t("rss.importSubscriptions")}</Label>
+            <p className="text-xs text-muted-foreground">
+              {t("rss.importSubscriptionsHint")}
+            </p>
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={handleImport}
+              disabled={importProgress !== null}
+            >
+              {importProgress ? (
+                <Loader2 className="w-4 h-4 mr-2 animate-spin" />
+              ) : (
+                <Upload className="w-4 h-4 mr-2" />
+              )}
+              {importProgress
+                ? t("rss.importProgress", {
+                    done: importProgress.done,
+                    total: importProgress.total,
+                  })
+                : t("rss.importChoose")}
+            </Button>
+            <div className="flex flex-wrap gap-2">
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => handleExport("newpipe")}
+              >
+                {t("rss.exportNewPipe")}
+              </Button>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => handleExport("freetube")}
+              >
+                {t("rss.exportFreeTube")}
+              </Button>
+            </div>
+            {importProgress?.current && (
+              <p className="text-xs text-muted-foreground truncate">
+                {importProgress.current}
+              </p>
+            )}
+            {importReport && importReport.failures.length > 0 && (
+              <ScrollArea className="max-h-32 rounded-md border border-border/50">
+                <ul className="p-2 space-y-1 text-xs">
+                  {importReport.failures.map((failure) => (
+                    <li key={failure.url}>
+                      <span className="font-medium">
+                        {failure.title || failure.url}
+                      </span>
+                      <span className="text-destructive">
+                        {" "}
+                        — {failure.error}
+                      </span>
+                    </li>
+                  ))}
+                </ul>
+              </ScrollArea>
+            )}
+          </div>
         </div>
 
         <DialogFooter>
@@ -1,3 +1,4 @@
+import { useEffect, useState } from "react";
 import { useTranslation } from "react-i18next";
 import { useAtomValue } from "jotai";
 import { Button } from "@/components/ui/button";
@@ -7,17 +8,27 @@ import { Switch } from "@/components/ui/switch";
 import { Separator } from "@/components/ui/separator";
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
 import { ScrollArea } from "@/components/ui/scroll-area";
-import { FolderOpen, Moon, Sun, Monitor, Bug, Lightbulb } from "lucide-react";
+import {
+  FolderOpen,
+  Moon,
+  Sun,
+  Monitor,
+  Bug,
+  Lightbulb,
+  Trash2,
+} from "lucide-react";
 import { useSettings } from "@/hooks/useSettings";
 import { useTheme } from "next-themes";
 import { commands } from "@/lib/tauri";
+import type { CookieProfile, CookieProfileKind } from "@/lib/tauri";
 import { toast } from "sonner";
 import { platformAtom } from "@/store/atoms";
 
 export function SettingsPage() {
   const { t, i18n } = useTranslation();
   const { theme, setTheme } = useTheme();
-  const { settings, saveSetting, selectDirectory } = useSettings();
+  const { settings, saveSetting, selectDirectory, loadSettings } =
+    useSettings();
   const platform = useAtomValue(platformAtom);
 
   const handleThemeChange = (th: string) => {
@@ -284,6 +295,107 @@ export function SettingsPage() {
                   </div>
                 </SettingItem>
 
+                {platform === "android" && (
+                  <>
+                    <Separator />
+
+                    {/* Where finished downloads are moved */}
+                    <SettingItem
+                      title={t("settings.storageTarget")}
+                      description={t("settings.storageTargetDesc")}
+                    >
+                      <div className="flex gap-1.5 flex-wrap">
+                        {(["app", "media", "saf"] as const).map((target) => (
+                          <Button
+                            key={target}
+                            variant={
+                              settings.androidStorageTarget === target
+                                ? "default"
+                                : "outline"
+                            }
+                            size="sm"
+                            className={`rounded-full h-7 text-xs shadow-sm ${settings.androidStorageTarget !== target ? "bg-background/50" : ""}`}
+                            onClick={async () => {
+                              if (target !== "saf") {
+                                saveSetting("android_storage_target", target);
+                                return;
+                              }
+                              try {
+                                const uri =
+                                  await commands.pickAndroidDownloadFolder();
+                                if (uri) {
+                                  await loadSettings();
+                                }
+                              } catch (err) {
+                                toast.error(String(err));
+                              }
+                            }}
+                          >
+                            {target === "app" && t("settings.storageTargetApp")}
+                            {target === "media" &&
+                              t("settings.storageTargetMedia")}
+                            {target === "saf" &&
+                              (settings.androidStorageTarget === "saf"
+                                ? t("settings.storageTargetSaf")
+                                : t("settings.storageTargetPick"))}
+                          </Button>
+                        ))}
+                      </div>
+                    </SettingItem>
+                  </>
+                )}
+
+                <Separator />
+
+                {/* Wi-Fi only */}
+                <SettingItem
+                  title={t("settings.wifiOnly")}
+                  description={t("settings.wifiOnlyDesc")}
+                >
+                  <Switch
+                    checked={settings.downloadWifiOnly}
+                    onCheckedChange={(checked) =>
+                      saveSetting("download_wifi_only", String(checked))
+                    }
+                  />
+                </SettingItem>
+
+                <Separator />
+
+                {/* Low battery */}
+                <SettingItem
+                  title={t("settings.batteryThreshold")}
+                  description={t("settings.batteryThresholdDesc")}
+                >
+                  <div className="flex gap-2 flex-wrap">
+                    {[0, 15, 20, 30].map((percent) => (
+                      <Button
+                        key={percent}
+                        variant={
+                          settings.batteryMinPercent === percent
+                            ? "default"
+                            : "outline"
+                        }
+                        size="sm"
+                        className={`rounded-full h-9 shadow-sm ${settings.batteryMinPercent !== percent ? "bg-background/50" : ""}`}
+                        onClick={async () => {
+                          // Resume a bit higher so it doesn't flap at the threshold
+                          await saveSetting(
+                            "battery_resume_percent",
+                            String(Math.min(percent + 15, 100)),
+                          );
+                          await saveSetting(
+                            "battery_min_percent",
+                            String(percent),
+                          );
+                        }}
+                      >
+                        {percent === 0 ? t("settings.off") : `${percent}%`}
+                      </Button>
+                    ))}
+                  </div>
+                </SettingItem>
+
                 <Separator />
 
                 {/* Default quality */}
@@ -310,6 +422,91 @@ export function SettingsPage() {
 
                 <Separator />
 
+                {/* Codec, HDR and frame rate, built into the format selector */}
+                <SettingItem
+                  title={t("settings.videoCodec")}
+                  description={t("settings.videoCodecDesc")}
+                >
+                  <div className="flex gap-2 flex-wrap">
+                    {[
+                      { value: "any", label: t("settings.any") },
+                      { value: "av1", label: "AV1" },
+                      { value: "vp9", label: "VP9" },
+                      { value: "avc", label: "H.264" },
+                    ].map((opt) => (
+                      <Button
+                        key={opt.value}
+                        variant={
+                          settings.preferredVcodec === opt.value
+                            ? "default"
+                            : "outline"
+                        }
+                        size="sm"
+                        className={`rounded-full h-9 shadow-sm ${settings.preferredVcodec !== opt.value ? "bg-background/50" : ""}`}
+                        onClick={() =>
+                          saveSetting("preferred_vcodec", opt.value)
+                        }
+                      >
+                        {opt.label}
+                      </Button>
+                    ))}
+                  </div>
+                </SettingItem>
+
+                <Separator />
+
+                <SettingItem
+                  title={t("settings.dynamicRange")}
+                  description={t("settings.dynamicRangeDesc")}
+                >
+                  <div className="flex gap-2 flex-wrap">
+                    {[
+                      { value: "any", label: t("settings.any") },
+                      { value: "hdr", label: "HDR" },
+                      { value: "sdr", label: "SDR" },
+                    ].map((opt) => (
+                      <Button
+                        key={opt.value}
+                        variant={
+                          settings.preferredDynamicRange === opt.value
+                            ? "default"
+                            : "outline"
+                        }
+                        size="sm"
+                        className={`rounded-full h-9 shadow-sm ${settings.preferredDynamicRange !== opt.value ? "bg-background/50" : ""}`}
+                        onClick={() =>
+                          saveSetting("preferred_dynamic_range", opt.value)
+                        }
+                      >
+                        {opt.label}
+                      </Button>
+                    ))}
+                  </div>
+                </SettingItem>
+
+                <Separator />
+
+                <SettingItem
+                  title={t("settings.maxFps")}
+                  description={t("settings.maxFpsDesc")}
+                >
+                  <div className="flex gap-2 flex-wrap">
+                    {[0, 30, 60].map((fps) => (
+                      <Button
+                        key={fps}
+                        variant={settings.maxFps === fps ? "default" : "outline"}
+                        size="sm"
+                        className={`rounded-full h-9 shadow-sm ${settings.maxFps !== fps ? "bg-background/50" : ""}`}
+                        onClick={() => saveSetting("max_fps", String(fps))}
+                      >
+                        {fps === 0 ? t("settings.any") : `${fps} fps`}
+                      </Button>
+                    ))}
+                  </div>
+                </SettingItem>
+
+                <Separator />
+
                 {/* Quick quality presets */}
                 <SettingItem
                   title={t("settings.qualityPreset")}
@@ -408,6 +605,34 @@ export function SettingsPage() {
                   </>
                 )}
 
+                {/* Concurrent fragments (yt-dlp -N), also used by Termux */}
+                <SettingItem
+                  title={t("settings.concurrentFragments")}
+                  description={t("settings.concurrentFragmentsDesc")}
+                >
+                  <div className="flex gap-2 flex-wrap">
+                    {[1, 2, 4, 8, 16].map((n) => (
+                      <Button
+                        key={n}
+                        variant={
+                          settings.concurrentFragments === n
+                            ? "default"
+                            : "outline"
+                        }
+                        size="sm"
+                        className={`rounded-full h-9 shadow-sm ${settings.concurrentFragments !== n ? "bg-background/50" : ""}`}
+                        onClick={() =>
+                          saveSetting("concurrent_fragments", String(n))
+                        }
+                      >
+                        {n}
+                      </Button>
+                    ))}
+                  </div>
+                </SettingItem>
+
+                <Separator />
+
                 {/* Auto start */}
                 <SettingItem
                   title={t("settings.autoStart")}
@@ -450,6 +675,66 @@ export function SettingsPage() {
                     }
                   />
                 </SettingItem>
+
+                <Separator />
+
+                {/* Embed chapters */}
+                <SettingItem
+                  title={t("settings.embedChapters")}
+                  description={t("settings.embedChaptersDesc")}
+                >
+                  <Switch
+                    checked={settings.embedChapters}
+                    onCheckedChange={(checked) =>
+                      saveSetting("embed_chapters", String(checked))
+                    }
+                  />
+                </SettingItem>
+
+                <Separator />
+
+                {/* Write info.json */}
+                <SettingItem
+                  title={t("settings.writeInfoJson")}
+                  description={t("settings.writeInfoJsonDesc")}
+                >
+                  <Switch
+                    checked={settings.writeInfoJson}
+                    onCheckedChange={(checked) =>
+                      saveSetting("write_info_json", String(checked))
+                    }
+                  />
+                </SettingItem>
+
+                <Separator />
+
+                {/* Formats that only fit MP4 after re-encoding */}
+                <SettingItem
+                  title={t("settings.mergePolicy")}
+                  description={t("settings.mergePolicyDesc")}
+                >
+                  <div className="flex gap-2 flex-wrap">
+                    {[
+                      { value: "reencode", label: t("settings.mergeReencode") },
+                      { value: "mkv", label: t("settings.mergeMkv") },
+                      { value: "abort", label: t("settings.mergeAbort") },
+                    ].map((opt) => (
+                      <Button
+                        key={opt.value}
+                        variant={
+                          settings.mergePolicy === opt.value
+                            ? "default"
+                            : "outline"
+                        }
+                        size="sm"
+                        className={`rounded-full h-9 shadow-sm ${settings.mergePolicy !== opt.value ? "bg-background/50" : ""}`}
+                        onClick={() => saveSetting("merge_policy", opt.value)}
+                      >
+                        {opt.label}
+                      </Button>
+                    ))}
+                  </div>
+                </SettingItem>
               </div>
             </div>
           </TabsContent>
@@ -562,6 +847,38 @@ export function SettingsPage() {
                     </SettingItem>
 
                     <Separator />
+
+                    <SettingItem
+                      title={t("settings.cookiesFallback")}
+                      description={t("settings.cookiesFallbackDesc")}
+                    >
+                      <div className="flex gap-2">
+                        {["none", "chrome", "firefox", "edge", "brave"].map(
+                          (b) => (
+                            <Button
+                              key={b}
+                              variant={
+                                settings.cookiesFallbackBrowser === b
+                                  ? "default"
+                                  : "outline"
+                              }
+                              size="sm"
+                              onClick={() =>
+                                saveSetting("cookies_fallback_browser", b)
+                              }
+                            >
+                              {b === "none" ? t("settings.off") : b}
+                            </Button>
+                          ),
+                        )}
+                      </div>
+                    </SettingItem>
+
+                    <Separator />
+
+                    <CookieProfilesSetting />
+
+                    <Separator />
                   </>
                 )}
 
@@ -580,6 +897,23 @@ export function SettingsPage() {
 
                 <Separator />
 
+                {/* Extractor arguments, e.g. a YouTube player client */}
+                <SettingItem
+                  title={t("settings.extractorArgs")}
+                  description={t("settings.extractorArgsDesc")}
+                >
+                  <Input
+                    placeholder="youtube:player_client=ios"
+                    className="max-w-md rounded-full"
+                    defaultValue={settings.ytdlpExtractorArgs}
+                    onBlur={(e) =>
+                      saveSetting("ytdlp_extractor_args", e.target.value)
+                    }
+                  />
+                </SettingItem>
+
+                <Separator />
+
                 {/* Config file — desktop only */}
                 {platform !== "android" && (
                   <SettingItem
@@ -611,6 +945,104 @@ export function SettingsPage() {
   );
 }
 
+/** Named cookie sources that downloads, feeds and the player can pick. */
+function CookieProfilesSetting() {
+  const { t } = useTranslation();
+  const [profiles, setProfiles] = useState<CookieProfile[]>([]);
+  const [name, setName] = useState("");
+  const [kind, setKind] = useState<CookieProfileKind>("browser");
+  const [source, setSource] = useState("");
+
+  useEffect(() => {
+    commands.getCookieProfiles().then(setProfiles).catch(() => {});
+  }, []);
+
+  const handleAdd = async () => {
+    try {
+      const profile = await commands.saveCookieProfile(name, kind, source);
+      setProfiles((prev) =>
+        [...prev, profile].sort((a, b) => a.name.localeCompare(b.name)),
+      );
+      setName("");
+      setSource("");
+    } catch (err) {
+      toast.error(String(err));
+    }
+  };
+
+  const handleDelete = async (id: string) => {
+    try {
+      await commands.deleteCookieProfile(id);
+      setProfiles((prev) => prev.filter((p) => p.id !== id));
+    } catch (err) {
+      toast.error(String(err));
+    }
+  };
+
+  return (
+    <SettingItem
+      title={t("settings.cookieProfiles")}
+      description={t("settings.cookieProfilesDesc")}
+    >
+      <div className="flex flex-col gap-2 sm:items-end">
+        {profiles.map((profile) => (
+          <div key={profile.id} className="flex items-center gap-2 text-sm">
+            <span className="font-medium">{profile.name}</span>
+            <span className="text-xs text-muted-foreground truncate max-w-[200px]">
+              {profile.source}
+            </span>
+            <Button
+              variant="ghost"
+              size="icon"
+              className="h-7 w-7"
+              onClick={() => handleDelete(profile.id)}
+            >
+              <Trash2 className="w-3.5 h-3.5" />
+            </Button>
+          </div>
+        ))}
+        <div className="flex flex-wrap gap-2">
+          <Input
+            placeholder={t("settings.cookieProfileName")}
+            className="w-32 rounded-full"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+          />
+          {(["browser", "file"] as const).map((k) => (
+            <Button
+              key={k}
+              variant={kind === k ? "default" : "outline"}
+              size="sm"
+              onClick={() => setKind(k)}
+            >
+              {k === "browser"
+                ? t("settings.cookieProfileBrowser")
+                : t("settings.cookieProfileFile")}
+            </Button>
+          ))}
+          <Input
+            placeholder={
+              kind === "browser"
+                ? "firefox:work"
+                : t("settings.cookieProfileFilePlaceholder")
+            }
+            className="w-48 rounded-full"
+            value={source}
+            onChange={(e) => setSource(e.target.value)}
+          />
+          <Button
+            size="sm"
+            disabled={!name.trim() || !source.trim()}
+            onClick={handleAdd}
+          >
+            {t("settings.cookieProfileAdd")}
+          </Button>
+        </div>
+      </div>
+    </SettingItem>
+  );
+}
+
 function SettingItem({
   title,
   description,
@@ -45,6 +45,7 @@ interface TranscriptProgressPayload {
   progress?: number;
   status?: TranscriptItem["status"];
   text?: string;
+  partialText?: string;
   language?: string;
   error?: string;
 }
@@ -168,7 +169,7 @@ export function TranscribePage() {
                 ...item,
                 progress: payload.progress ?? item.progress,
                 status: payload.status || item.status,
-                text: payload.text ?? item.text,
+                text: payload.text ?? payload.partialText ?? item.text,
                 language: payload.language ?? item.language,
                 error: payload.error ?? item.error,
               }
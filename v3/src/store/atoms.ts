@@ -25,6 +25,7 @@ export const settingsAtom = atom<AppSettings>({
   downloadPath: "",
   maxConcurrentDownloads: 3,
   speedLimit: 0, // 0 = unlimited
+  concurrentFragments: 1,
   autoStartDownloads: true,
   theme: "system",
   language: "en",
@@ -36,12 +37,23 @@ export const settingsAtom = atom<AppSettings>({
   defaultFormat: "mp4",
   embedThumbnail: true,
   embedMetadata: true,
+  embedChapters: true,
+  preferredVcodec: "any",
+  preferredDynamicRange: "any",
+  maxFps: 0,
+  writeInfoJson: false,
+  mergePolicy: "reencode",
   browserForCookies: "none",
+  cookiesFallbackBrowser: "none",
   configPath: "",
   ytdlpFlags: "",
+  ytdlpExtractorArgs: "",
   rssCheckInterval: 60,
   rssNotifications: true,
   rssAutoDownload: false,
+  androidStorageTarget: "app",
+  downloadWifiOnly: false,
+  batteryMinPercent: 0,
 });
 export const settingsLoadedAtom = atom(false);
 